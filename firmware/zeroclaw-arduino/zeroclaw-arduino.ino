@@ -2,13 +2,15 @@
  * ZeroClaw Arduino Uno Firmware
  *
  * Listens for JSON commands on Serial (115200 baud), executes gpio_read/gpio_write,
- * responds with JSON. Compatible with ZeroClaw SerialPeripheral protocol.
+ * adc_read/pwm_set, responds with JSON. Compatible with ZeroClaw SerialPeripheral
+ * protocol version 2.
  *
  * Protocol (newline-delimited JSON):
  *   Request:  {"id":"1","cmd":"gpio_write","args":{"pin":13,"value":1}}
  *   Response: {"id":"1","ok":true,"result":"done"}
  *
  * Arduino Uno: Pin 13 has built-in LED. Digital pins 0-13 supported.
+ * ADC: A0-A5 (pins 14-19). PWM: pins 3, 5, 6, 9, 10, 11 (fixed frequency).
  *
  * 1. Open in Arduino IDE
  * 2. Select Board: Arduino Uno
@@ -55,6 +57,20 @@ bool hasCmd(const char* json, const char* cmd) {
   return strstr(json, search) != NULL;
 }
 
+bool isPwmPin(int pin) {
+  return pin == 3 || pin == 5 || pin == 6 || pin == 9 || pin == 10 || pin == 11;
+}
+
+void replyError(const char* idBuf, const char* msg, int pin) {
+  Serial.print("{\"id\":\"");
+  Serial.print(idBuf);
+  Serial.print("\",\"ok\":false,\"result\":\"\",\"error\":\"");
+  Serial.print(msg);
+  Serial.print(" ");
+  Serial.print(pin);
+  Serial.println("\"}");
+}
+
 void handleLine(const char* line) {
   char idBuf[16];
   copyId(idBuf, sizeof(idBuf), line);
@@ -66,11 +82,12 @@ void handleLine(const char* line) {
     return;
   }
 
-  // Phase C: Dynamic discovery — report GPIO pins and LED pin
+  // Phase C: Dynamic discovery — report GPIO pins, LED pin and v2 features
   if (hasCmd(line, "capabilities")) {
     Serial.print("{\"id\":\"");
     Serial.print(idBuf);
-    Serial.print("\",\"ok\":true,\"result\":\"{\\\"gpio\\\":[0,1,2,3,4,5,6,7,8,9,10,11,12,13],\\\"led_pin\\\":13}\"}");
+    Serial.print("\",\"ok\":true,\"result\":\"{\\\"protocol\\\":2,\\\"gpio\\\":[0,1,2,3,4,5,6,7,8,9,10,11,12,13],\\\"led_pin\\\":13,");
+    Serial.print("\\\"features\\\":[\\\"adc\\\",\\\"pwm\\\"],\\\"adc\\\":[14,15,16,17,18,19],\\\"pwm\\\":[3,5,6,9,10,11]}\"}");
     Serial.println();
     return;
   }
@@ -114,6 +131,37 @@ void handleLine(const char* line) {
     return;
   }
 
+  if (hasCmd(line, "adc_read")) {
+    int pin = parseArg("pin", line);
+    if (pin < 14 || pin > 19) {
+      replyError(idBuf, "Not an ADC pin", pin);
+      return;
+    }
+    int val = analogRead(pin);
+    Serial.print("{\"id\":\"");
+    Serial.print(idBuf);
+    Serial.print("\",\"ok\":true,\"result\":\"");
+    Serial.print(val);
+    Serial.println("\"}");
+    return;
+  }
+
+  if (hasCmd(line, "pwm_set")) {
+    int pin = parseArg("pin", line);
+    int duty = parseArg("duty", line);
+    if (!isPwmPin(pin)) {
+      replyError(idBuf, "Not a PWM pin", pin);
+      return;
+    }
+    // frequency_hz is ignored: Uno timers run PWM at a fixed ~490/980 Hz
+    pinMode(pin, OUTPUT);
+    analogWrite(pin, constrain(duty, 0, 100) * 255L / 100);
+    Serial.print("{\"id\":\"");
+    Serial.print(idBuf);
+    Serial.println("\",\"ok\":true,\"result\":\"done\"}");
+    return;
+  }
+
   // Unknown command
   Serial.print("{\"id\":\"");
   Serial.print(idBuf);
@@ -1,17 +1,37 @@
 //! ZeroClaw ESP32 firmware — JSON-over-serial peripheral.
 //!
-//! Listens for newline-delimited JSON commands on UART0, executes gpio_read/gpio_write,
-//! responds with JSON. Compatible with host ZeroClaw SerialPeripheral protocol.
+//! Listens for newline-delimited JSON commands on UART0, executes them and
+//! responds with JSON. Compatible with host ZeroClaw SerialPeripheral protocol
+//! version 2: gpio_read/gpio_write plus adc_read, pwm_set, i2c_scan/i2c_read/
+//! i2c_write, spi_transfer and uart_transfer (UART1 passthrough).
 //!
 //! Protocol: same as STM32 — see docs/hardware-peripherals-design.md
 
-use esp_idf_svc::hal::gpio::PinDriver;
+use esp_idf_svc::hal::adc::oneshot::config::AdcChannelConfig;
+use esp_idf_svc::hal::adc::oneshot::{AdcChannelDriver, AdcDriver};
+use esp_idf_svc::hal::adc::ADC1;
+use esp_idf_svc::hal::delay::BLOCK;
+use esp_idf_svc::hal::gpio::{Gpio13, Gpio2, Gpio4, Output, PinDriver};
+use esp_idf_svc::hal::i2c::{I2cConfig, I2cDriver};
+use esp_idf_svc::hal::ledc::{config::TimerConfig, LedcDriver, LedcTimerDriver};
 use esp_idf_svc::hal::peripherals::Peripherals;
+use esp_idf_svc::hal::spi::{config::Config as SpiConfig, SpiDeviceDriver, SpiDriver, SpiDriverConfig};
 use esp_idf_svc::hal::uart::{UartConfig, UartDriver};
-use esp_idf_svc::hal::units::Hertz;
+use esp_idf_svc::hal::units::{FromValueType, Hertz};
 use log::info;
 use serde::{Deserialize, Serialize};
 
+/// Protocol version implemented by this firmware.
+const PROTOCOL_VERSION: u32 = 2;
+
+/// Largest transfer accepted per command (matches the host limit).
+const MAX_TRANSFER_BYTES: usize = 64;
+
+/// Pin assignments — adjust for your board.
+const LED_PIN: u64 = 2;
+const ADC_PIN: u64 = 4;
+const PWM_PIN: u64 = 5;
+
 /// Incoming command from host.
 #[derive(Debug, Deserialize)]
 struct Request {
@@ -30,6 +50,17 @@ struct Response {
     error: Option<String>,
 }
 
+/// Drivers for everything the host can reach.
+struct Board<'d> {
+    gpio2: PinDriver<'d, Gpio2, Output>,
+    gpio13: PinDriver<'d, Gpio13, Output>,
+    adc: AdcChannelDriver<'d, Gpio4, AdcDriver<'d, ADC1>>,
+    pwm: LedcDriver<'d>,
+    i2c: I2cDriver<'d>,
+    spi: SpiDeviceDriver<'d, SpiDriver<'d>>,
+    uart1: UartDriver<'d>,
+}
+
 fn main() -> anyhow::Result<()> {
     esp_idf_svc::sys::link_patches();
     esp_idf_svc::log::EspLogger::initialize_default();
@@ -38,8 +69,51 @@ fn main() -> anyhow::Result<()> {
     let pins = peripherals.pins;
 
     // Create GPIO output drivers first (they take ownership of pins)
-    let mut gpio2 = PinDriver::output(pins.gpio2)?;
-    let mut gpio13 = PinDriver::output(pins.gpio13)?;
+    let gpio2 = PinDriver::output(pins.gpio2)?;
+    let gpio13 = PinDriver::output(pins.gpio13)?;
+
+    let adc = AdcChannelDriver::new(
+        AdcDriver::new(peripherals.adc1)?,
+        pins.gpio4,
+        &AdcChannelConfig::default(),
+    )?;
+
+    let pwm_timer = LedcTimerDriver::new(
+        peripherals.ledc.timer0,
+        &TimerConfig::default().frequency(5.kHz().into()),
+    )?;
+    let pwm = LedcDriver::new(peripherals.ledc.channel0, pwm_timer, pins.gpio5)?;
+
+    // I2C0: SDA=6, SCL=7
+    let i2c = I2cDriver::new(
+        peripherals.i2c0,
+        pins.gpio6,
+        pins.gpio7,
+        &I2cConfig::new().baudrate(100.kHz().into()),
+    )?;
+
+    // SPI2: SCLK=10, MOSI=3, MISO=1, CS=0
+    let spi = SpiDeviceDriver::new(
+        SpiDriver::new(
+            peripherals.spi2,
+            pins.gpio10,
+            pins.gpio3,
+            Some(pins.gpio1),
+            &SpiDriverConfig::new(),
+        )?,
+        Some(pins.gpio0),
+        &SpiConfig::new().baudrate(1.MHz().into()),
+    )?;
+
+    // UART1 (passthrough): TX=18, RX=19
+    let uart1 = UartDriver::new(
+        peripherals.uart1,
+        pins.gpio18,
+        pins.gpio19,
+        Option::<esp_idf_svc::hal::gpio::Gpio8>::None,
+        Option::<esp_idf_svc::hal::gpio::Gpio9>::None,
+        &UartConfig::new().baudrate(Hertz(9_600)),
+    )?;
 
     // UART0: TX=21, RX=20 (ESP32) — ESP32-C3 may use different pins; adjust for your board
     let config = UartConfig::new().baudrate(Hertz(115_200));
@@ -47,11 +121,21 @@ fn main() -> anyhow::Result<()> {
         peripherals.uart0,
         pins.gpio21,
         pins.gpio20,
-        Option::<esp_idf_svc::hal::gpio::Gpio0>::None,
-        Option::<esp_idf_svc::hal::gpio::Gpio1>::None,
+        Option::<esp_idf_svc::hal::gpio::Gpio11>::None,
+        Option::<esp_idf_svc::hal::gpio::Gpio12>::None,
         &config,
     )?;
 
+    let mut board = Board {
+        gpio2,
+        gpio13,
+        adc,
+        pwm,
+        i2c,
+        spi,
+        uart1,
+    };
+
     info!("ZeroClaw ESP32 firmware ready on UART0 (115200)");
 
     let mut buf = [0u8; 512];
@@ -65,8 +149,7 @@ fn main() -> anyhow::Result<()> {
                     if b == b'\n' {
                         if !line.is_empty() {
                             if let Ok(line_str) = std::str::from_utf8(&line) {
-                                if let Ok(resp) = handle_request(line_str, &mut gpio2, &mut gpio13)
-                                {
+                                if let Ok(resp) = handle_request(line_str, &mut board) {
                                     let out = serde_json::to_string(&resp).unwrap_or_default();
                                     let _ = uart.write(format!("{}\n", out).as_bytes());
                                 }
@@ -75,7 +158,7 @@ fn main() -> anyhow::Result<()> {
                         }
                     } else {
                         line.push(b);
-                        if line.len() > 400 {
+                        if line.len() > 480 {
                             line.clear();
                         }
                     }
@@ -86,38 +169,107 @@ fn main() -> anyhow::Result<()> {
     }
 }
 
-fn handle_request<G2, G13>(
-    line: &str,
-    gpio2: &mut PinDriver<'_, G2>,
-    gpio13: &mut PinDriver<'_, G13>,
-) -> anyhow::Result<Response>
-where
-    G2: esp_idf_svc::hal::gpio::OutputMode,
-    G13: esp_idf_svc::hal::gpio::OutputMode,
-{
+fn handle_request(line: &str, board: &mut Board<'_>) -> anyhow::Result<Response> {
     let req: Request = serde_json::from_str(line.trim())?;
     let id = req.id.clone();
 
     let result = match req.cmd.as_str() {
+        "ping" => Ok("pong".into()),
         "capabilities" => {
-            // Phase C: report GPIO pins and LED pin (matches Arduino protocol)
+            // Phase C: report GPIO pins and LED pin (matches Arduino protocol),
+            // plus the v2 features and the pins/buses each one may use.
             let caps = serde_json::json!({
-                "gpio": [0, 1, 2, 3, 4, 5, 12, 13, 14, 15, 16, 17, 18, 19],
-                "led_pin": 2
+                "protocol": PROTOCOL_VERSION,
+                "gpio": [2, 13],
+                "led_pin": LED_PIN,
+                "features": ["adc", "pwm", "i2c", "spi", "uart"],
+                "adc": [ADC_PIN],
+                "pwm": [PWM_PIN],
+                "i2c": [0],
+                "spi": [0],
+                "uart": [1]
             });
             Ok(caps.to_string())
         }
         "gpio_read" => {
-            let pin_num = req.args.get("pin").and_then(|v| v.as_u64()).unwrap_or(0) as i32;
-            let value = gpio_read(pin_num)?;
+            let pin_num = arg_u64(&req.args, "pin")?;
+            let value = gpio_read(board, pin_num)?;
             Ok(value.to_string())
         }
         "gpio_write" => {
-            let pin_num = req.args.get("pin").and_then(|v| v.as_u64()).unwrap_or(0) as i32;
+            let pin_num = arg_u64(&req.args, "pin")?;
             let value = req.args.get("value").and_then(|v| v.as_u64()).unwrap_or(0);
-            gpio_write(gpio2, gpio13, pin_num, value)?;
+            gpio_write(board, pin_num, value)?;
             Ok("done".into())
         }
+        "adc_read" => {
+            if arg_u64(&req.args, "pin")? != ADC_PIN {
+                anyhow::bail!("Pin is not ADC-capable");
+            }
+            let raw = board.adc.read()?;
+            Ok(raw.to_string())
+        }
+        "pwm_set" => {
+            if arg_u64(&req.args, "pin")? != PWM_PIN {
+                anyhow::bail!("Pin is not PWM-capable");
+            }
+            let duty = req.args.get("duty").and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let max = board.pwm.get_max_duty();
+            board
+                .pwm
+                .set_duty((f64::from(max) * duty.clamp(0.0, 100.0) / 100.0) as u32)?;
+            Ok("done".into())
+        }
+        "i2c_scan" => {
+            let found: Vec<u8> = (0x08u8..0x78)
+                .filter(|addr| board.i2c.write(*addr, &[], 10).is_ok())
+                .collect();
+            Ok(serde_json::to_string(&found)?)
+        }
+        "i2c_read" => {
+            let addr = arg_u64(&req.args, "address")? as u8;
+            let len = (arg_u64(&req.args, "len")? as usize).min(MAX_TRANSFER_BYTES);
+            let mut data = vec![0u8; len];
+            match req.args.get("register").and_then(|v| v.as_u64()) {
+                Some(reg) => board.i2c.write_read(addr, &[reg as u8], &mut data, BLOCK)?,
+                None => board.i2c.read(addr, &mut data, BLOCK)?,
+            }
+            Ok(serde_json::to_string(&data)?)
+        }
+        "i2c_write" => {
+            let addr = arg_u64(&req.args, "address")? as u8;
+            let mut payload = Vec::new();
+            if let Some(reg) = req.args.get("register").and_then(|v| v.as_u64()) {
+                payload.push(reg as u8);
+            }
+            payload.extend(arg_bytes(&req.args, "data")?);
+            board.i2c.write(addr, &payload, BLOCK)?;
+            Ok("done".into())
+        }
+        "spi_transfer" => {
+            let write = arg_bytes(&req.args, "data")?;
+            let mut read = vec![0u8; write.len()];
+            board.spi.transfer(&mut read, &write)?;
+            Ok(serde_json::to_string(&read)?)
+        }
+        "uart_transfer" => {
+            if let Some(baud) = req.args.get("baud").and_then(|v| v.as_u64()) {
+                board.uart1.change_baudrate(Hertz(baud as u32))?;
+            }
+            let data = req.args.get("data").and_then(|v| v.as_str()).unwrap_or("");
+            let read_len = req
+                .args
+                .get("read_len")
+                .and_then(|v| v.as_u64())
+                .map_or(MAX_TRANSFER_BYTES, |n| (n as usize).min(MAX_TRANSFER_BYTES));
+            let timeout_ms = req.args.get("timeout_ms").and_then(|v| v.as_u64()).unwrap_or(500);
+            board.uart1.clear_rx()?;
+            board.uart1.write(data.as_bytes())?;
+            let mut reply = vec![0u8; read_len];
+            let ticks = esp_idf_svc::hal::delay::TickType::new_millis(timeout_ms).ticks();
+            let n = board.uart1.read(&mut reply, ticks)?;
+            Ok(String::from_utf8_lossy(&reply[..n]).into_owned())
+        }
         _ => Err(anyhow::anyhow!("Unknown command: {}", req.cmd)),
     };
 
@@ -137,26 +289,42 @@ where
     }
 }
 
-fn gpio_read(_pin: i32) -> anyhow::Result<u8> {
-    // TODO: implement input pin read — requires storing InputPin drivers per pin
-    Ok(0)
+fn arg_u64(args: &serde_json::Value, key: &str) -> anyhow::Result<u64> {
+    args.get(key)
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| anyhow::anyhow!("Missing '{}'", key))
+}
+
+fn arg_bytes(args: &serde_json::Value, key: &str) -> anyhow::Result<Vec<u8>> {
+    let items = args
+        .get(key)
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| anyhow::anyhow!("Missing '{}'", key))?;
+    if items.len() > MAX_TRANSFER_BYTES {
+        anyhow::bail!("Transfer too long");
+    }
+    Ok(items
+        .iter()
+        .map(|b| b.as_u64().unwrap_or(0) as u8)
+        .collect())
+}
+
+fn gpio_read(board: &Board<'_>, pin: u64) -> anyhow::Result<u8> {
+    // Output drivers report the level they are driving.
+    let high = match pin {
+        2 => board.gpio2.is_set_high(),
+        13 => board.gpio13.is_set_high(),
+        _ => anyhow::bail!("Pin {} not configured (add to gpio_read)", pin),
+    };
+    Ok(u8::from(high))
 }
 
-fn gpio_write<G2, G13>(
-    gpio2: &mut PinDriver<'_, G2>,
-    gpio13: &mut PinDriver<'_, G13>,
-    pin: i32,
-    value: u64,
-) -> anyhow::Result<()>
-where
-    G2: esp_idf_svc::hal::gpio::OutputMode,
-    G13: esp_idf_svc::hal::gpio::OutputMode,
-{
+fn gpio_write(board: &mut Board<'_>, pin: u64, value: u64) -> anyhow::Result<()> {
     let level = esp_idf_svc::hal::gpio::Level::from(value != 0);
 
     match pin {
-        2 => gpio2.set_level(level)?,
-        13 => gpio13.set_level(level)?,
+        2 => board.gpio2.set_level(level)?,
+        13 => board.gpio13.set_level(level)?,
         _ => anyhow::bail!("Pin {} not configured (add to gpio_write)", pin),
     }
     Ok(())
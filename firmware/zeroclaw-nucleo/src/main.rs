@@ -3,7 +3,8 @@
 //! Listens for newline-delimited JSON on USART2 (PA2=TX, PA3=RX).
 //! USART2 is connected to ST-Link VCP — host sees /dev/ttyACM0 (Linux) or /dev/cu.usbmodem* (macOS).
 //!
-//! Protocol: same as Arduino/ESP32 — see docs/hardware-peripherals-design.md
+//! Protocol: same as Arduino/ESP32 (version 2, ADC feature on A0/PA0) —
+//! see docs/hardware-peripherals-design.md
 
 #![no_std]
 #![no_main]
@@ -12,6 +13,7 @@ use core::fmt::Write;
 use core::str;
 use defmt::info;
 use embassy_executor::Spawner;
+use embassy_stm32::adc::Adc;
 use embassy_stm32::gpio::{Level, Output, Speed};
 use embassy_stm32::usart::{Config, Uart};
 use heapless::String;
@@ -20,6 +22,9 @@ use {defmt_rtt as _, panic_probe as _};
 /// Arduino-style pin 13 = PA5 (User LED LD2 on Nucleo-F401RE)
 const LED_PIN: u8 = 13;
 
+/// Arduino-style pin 14 = A0 = PA0 (ADC1 channel 0)
+const ADC_PIN: u8 = 14;
+
 /// Parse integer from JSON: "pin":13 or "value":1
 fn parse_arg(line: &[u8], key: &[u8]) -> Option<i32> {
     // key like b"pin" -> search for b"\"pin\":"
@@ -113,12 +118,14 @@ async fn main(_spawner: Spawner) {
 
     let mut usart = Uart::new_blocking(p.USART2, p.PA3, p.PA2, config).unwrap();
     let mut led = Output::new(p.PA5, Level::Low, Speed::Low);
+    let mut adc = Adc::new(p.ADC1);
+    let mut a0 = p.PA0;
 
     info!("ZeroClaw Nucleo firmware ready on USART2 (115200)");
 
     let mut line_buf: heapless::Vec<u8, 256> = heapless::Vec::new();
     let mut id_buf = [0u8; 16];
-    let mut resp_buf: String<128> = String::new();
+    let mut resp_buf: String<256> = String::new();
 
     loop {
         let mut byte = [0u8; 1];
@@ -135,7 +142,7 @@ async fn main(_spawner: Spawner) {
                     } else if has_cmd(&line_buf, b"capabilities") {
                         let _ = write!(
                             resp_buf,
-                            "{{\"id\":\"{}\",\"ok\":true,\"result\":\"{{\\\"gpio\\\":[0,1,2,3,4,5,6,7,8,9,10,11,12,13],\\\"led_pin\\\":13}}\"}}",
+                            "{{\"id\":\"{}\",\"ok\":true,\"result\":\"{{\\\"protocol\\\":2,\\\"gpio\\\":[0,1,2,3,4,5,6,7,8,9,10,11,12,13],\\\"led_pin\\\":13,\\\"features\\\":[\\\"adc\\\"],\\\"adc\\\":[14]}}\"}}",
                             id_str
                         );
                     } else if has_cmd(&line_buf, b"gpio_read") {
@@ -167,6 +174,18 @@ async fn main(_spawner: Spawner) {
                                 id_str, pin
                             );
                         }
+                    } else if has_cmd(&line_buf, b"adc_read") {
+                        let pin = parse_arg(&line_buf, b"pin").unwrap_or(-1);
+                        if pin == ADC_PIN as i32 {
+                            let value = adc.blocking_read(&mut a0);
+                            let _ = write!(resp_buf, "{{\"id\":\"{}\",\"ok\":true,\"result\":\"{}\"}}", id_str, value);
+                        } else {
+                            let _ = write!(
+                                resp_buf,
+                                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Not an ADC pin {}\"}}",
+                                id_str, pin
+                            );
+                        }
                     } else {
                         let _ = write!(
                             resp_buf,
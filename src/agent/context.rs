@@ -0,0 +1,407 @@
+//! Token-budget-aware context window management.
+//!
+//! Message counts are a poor proxy for how much of a model's context window a
+//! conversation uses: one large tool result can overflow it while dozens of
+//! short messages barely register. This module resolves each model's context
+//! window (config overrides → cached provider catalog → built-in table) and
+//! estimates token usage so compaction, tool-output truncation and memory
+//! injection can be budgeted against what is actually left.
+
+use crate::config::Config;
+use crate::providers::ChatMessage;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Window assumed for models that are neither configured nor recognised.
+pub const DEFAULT_CONTEXT_WINDOW_TOKENS: usize = 32_000;
+
+/// Rough characters-per-token ratio for English text and code.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Per-message framing overhead (role markers, separators).
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
+/// Compact once history uses more than this share of the usable window.
+const COMPACTION_TRIGGER_PERCENT: usize = 75;
+
+/// Messages kept verbatim after compaction may use at most this share.
+const COMPACTION_KEEP_PERCENT: usize = 40;
+
+/// Tool results from one LLM response may use at most this share of what remains.
+const TOOL_OUTPUT_SHARE_PERCENT: usize = 50;
+
+/// Injected memory/RAG context may use at most this share of the usable window.
+const MEMORY_CONTEXT_SHARE_PERCENT: usize = 10;
+
+/// Never truncate a tool result below this many tokens, even when the window
+/// is nearly full; compaction handles the rest on the next turn.
+const MIN_TOOL_OUTPUT_TOKENS: usize = 256;
+
+/// Built-in context windows by model family, matched as substrings of the
+/// lowercased model id. More specific entries come first.
+const BUILTIN_CONTEXT_WINDOWS: &[(&str, usize)] = &[
+    ("gpt-4.1", 1_047_576),
+    ("gpt-5", 400_000),
+    ("gpt-4o", 128_000),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-3.5", 16_385),
+    ("o1", 200_000),
+    ("o3", 200_000),
+    ("o4", 200_000),
+    ("claude", 200_000),
+    ("gemini", 1_048_576),
+    ("grok", 131_072),
+    ("llama-3", 128_000),
+    ("llama3", 128_000),
+    ("mistral-large", 128_000),
+    ("deepseek", 128_000),
+    ("glm", 128_000),
+    ("qwen", 32_768),
+];
+
+/// Estimate the token count of a piece of text.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Estimate the token count of a message list, including framing overhead.
+pub fn estimate_message_tokens(messages: &[ChatMessage]) -> usize {
+    messages
+        .iter()
+        .map(|m| estimate_tokens(&m.content) + MESSAGE_OVERHEAD_TOKENS)
+        .sum()
+}
+
+fn builtin_context_window(model: &str) -> Option<usize> {
+    let model = model.to_ascii_lowercase();
+    // Match on the last path segment so OpenRouter-style `vendor/model` ids
+    // resolve too; short families like `o1` must be a prefix of the name.
+    let name = model.rsplit('/').next().unwrap_or(&model);
+    BUILTIN_CONTEXT_WINDOWS
+        .iter()
+        .find(|(family, _)| {
+            if family.len() <= 2 {
+                name.starts_with(family)
+            } else {
+                name.contains(family)
+            }
+        })
+        .map(|(_, window)| *window)
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ModelCacheState {
+    #[serde(default)]
+    entries: Vec<ModelCacheEntry>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ModelCacheEntry {
+    provider: String,
+    #[serde(default)]
+    context_windows: HashMap<String, usize>,
+}
+
+/// Context windows recorded by `zeroclaw models refresh`, keyed by provider.
+fn load_catalog_windows(workspace_dir: &Path) -> HashMap<String, HashMap<String, usize>> {
+    let path = workspace_dir.join("state").join("models_cache.json");
+    let Ok(raw) = std::fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    serde_json::from_str::<ModelCacheState>(&raw)
+        .unwrap_or_default()
+        .entries
+        .into_iter()
+        .filter(|entry| !entry.context_windows.is_empty())
+        .map(|entry| (entry.provider, entry.context_windows))
+        .collect()
+}
+
+/// Resolves the context window for a provider/model pair.
+#[derive(Debug, Clone, Default)]
+pub struct ContextWindowRegistry {
+    overrides: HashMap<String, usize>,
+    catalog: HashMap<String, HashMap<String, usize>>,
+    reserve_tokens: usize,
+}
+
+impl ContextWindowRegistry {
+    pub fn new(overrides: HashMap<String, usize>, reserve_tokens: usize) -> Self {
+        Self {
+            overrides,
+            catalog: HashMap::new(),
+            reserve_tokens,
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            overrides: config.agent.context_windows.clone(),
+            catalog: load_catalog_windows(&config.workspace_dir),
+            reserve_tokens: config.agent.context_reserve_tokens,
+        }
+    }
+
+    /// Record catalog windows for a provider (mainly for tests and callers that
+    /// fetch catalogs themselves).
+    pub fn with_catalog(mut self, provider: &str, windows: HashMap<String, usize>) -> Self {
+        self.catalog.insert(provider.to_string(), windows);
+        self
+    }
+
+    /// Context window in tokens for `model` served by `provider`.
+    ///
+    /// Lookup order: config override (`provider/model` or `model`), the
+    /// provider's cached catalog, any other provider's catalog, the built-in
+    /// family table, then [`DEFAULT_CONTEXT_WINDOW_TOKENS`].
+    pub fn window_for(&self, provider: &str, model: &str) -> usize {
+        self.overrides
+            .get(&format!("{provider}/{model}"))
+            .or_else(|| self.overrides.get(model))
+            .or_else(|| self.catalog.get(provider).and_then(|m| m.get(model)))
+            .or_else(|| self.catalog.values().find_map(|m| m.get(model)))
+            .copied()
+            .filter(|window| *window > 0)
+            .or_else(|| builtin_context_window(model))
+            .unwrap_or(DEFAULT_CONTEXT_WINDOW_TOKENS)
+    }
+
+    pub fn budget_for(&self, provider: &str, model: &str) -> ContextBudget {
+        ContextBudget::new(self.window_for(provider, model), self.reserve_tokens)
+    }
+}
+
+/// Token budget for one model's context window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextBudget {
+    pub window_tokens: usize,
+    /// Tokens held back for the model's response.
+    pub reserve_tokens: usize,
+}
+
+impl ContextBudget {
+    pub fn new(window_tokens: usize, reserve_tokens: usize) -> Self {
+        Self {
+            window_tokens,
+            reserve_tokens,
+        }
+    }
+
+    /// Tokens available for the prompt. A reserve larger than half the window
+    /// is capped so small local models still get room for history.
+    pub fn usable_tokens(&self) -> usize {
+        let reserve = self.reserve_tokens.min(self.window_tokens / 2);
+        self.window_tokens.saturating_sub(reserve).max(1)
+    }
+
+    pub fn remaining_tokens(&self, history: &[ChatMessage]) -> usize {
+        self.usable_tokens()
+            .saturating_sub(estimate_message_tokens(history))
+    }
+
+    pub fn should_compact(&self, history: &[ChatMessage]) -> bool {
+        estimate_message_tokens(history) > self.usable_tokens() * COMPACTION_TRIGGER_PERCENT / 100
+    }
+
+    /// How many of the most recent messages in `messages` fit in the share of
+    /// the window kept verbatim after compaction (at least one).
+    pub fn recent_messages_to_keep(&self, messages: &[ChatMessage]) -> usize {
+        let limit = self.usable_tokens() * COMPACTION_KEEP_PERCENT / 100;
+        let mut used = 0;
+        let mut keep = 0;
+        for message in messages.iter().rev() {
+            used += estimate_tokens(&message.content) + MESSAGE_OVERHEAD_TOKENS;
+            if used > limit {
+                break;
+            }
+            keep += 1;
+        }
+        keep.max(1)
+    }
+
+    /// Character limit for each of `result_count` tool results about to be
+    /// appended to `history`.
+    pub fn tool_output_char_limit(&self, history: &[ChatMessage], result_count: usize) -> usize {
+        let share = self.remaining_tokens(history) * TOOL_OUTPUT_SHARE_PERCENT / 100;
+        let per_result = share / result_count.max(1);
+        per_result.max(MIN_TOOL_OUTPUT_TOKENS) * CHARS_PER_TOKEN
+    }
+
+    /// Character limit for memory/RAG context injected ahead of a user message.
+    pub fn memory_context_char_limit(&self, history: &[ChatMessage]) -> usize {
+        let share = self.usable_tokens() * MEMORY_CONTEXT_SHARE_PERCENT / 100;
+        share.min(self.remaining_tokens(history)) * CHARS_PER_TOKEN
+    }
+
+    /// Drop the oldest non-system messages until the history fits the usable
+    /// window. The leading system prompt and the newest message are always kept.
+    pub fn trim_history(&self, history: &mut Vec<ChatMessage>) {
+        let start = usize::from(history.first().is_some_and(|m| m.role == "system"));
+        let usable = self.usable_tokens();
+        let mut total = estimate_message_tokens(history);
+        let mut remove = 0;
+        while total > usable && start + remove + 1 < history.len() {
+            total -= estimate_tokens(&history[start + remove].content) + MESSAGE_OVERHEAD_TOKENS;
+            remove += 1;
+        }
+        history.drain(start..start + remove);
+    }
+}
+
+/// Shorten `text` to at most `max_chars`, keeping the head and tail (where
+/// command output usually carries the most signal) around a truncation marker.
+pub fn truncate_to_budget(text: &str, max_chars: usize) -> String {
+    let total = text.chars().count();
+    if total <= max_chars {
+        return text.to_string();
+    }
+
+    let marker = format!(
+        "\n[... {} characters truncated to fit the context window ...]\n",
+        total - max_chars
+    );
+    let keep = max_chars.saturating_sub(marker.chars().count());
+    let head_len = keep * 2 / 3;
+    let tail_len = keep - head_len;
+    let head: String = text.chars().take(head_len).collect();
+    let tail: String = text.chars().skip(total - tail_len).collect();
+    format!("{head}{marker}{tail}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_tokens_from_characters() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        let history = vec![ChatMessage::user("abcd"), ChatMessage::assistant("abcd")];
+        assert_eq!(
+            estimate_message_tokens(&history),
+            2 + 2 * MESSAGE_OVERHEAD_TOKENS
+        );
+    }
+
+    #[test]
+    fn window_lookup_prefers_overrides_then_catalog_then_builtin() {
+        let overrides = HashMap::from([
+            ("my-local-model".to_string(), 8_192),
+            ("ollama/llama3.2".to_string(), 16_384),
+        ]);
+        let registry = ContextWindowRegistry::new(overrides, 4_096).with_catalog(
+            "openrouter",
+            HashMap::from([("anthropic/claude-sonnet-4".to_string(), 1_000_000)]),
+        );
+
+        assert_eq!(registry.window_for("ollama", "my-local-model"), 8_192);
+        assert_eq!(registry.window_for("ollama", "llama3.2"), 16_384);
+        assert_eq!(
+            registry.window_for("openrouter", "anthropic/claude-sonnet-4"),
+            1_000_000
+        );
+        assert_eq!(registry.window_for("anthropic", "claude-sonnet-4"), 200_000);
+        assert_eq!(registry.window_for("openai", "gpt-4o-mini"), 128_000);
+        assert_eq!(registry.window_for("openai", "o3-mini"), 200_000);
+        assert_eq!(
+            registry.window_for("custom", "totally-unknown"),
+            DEFAULT_CONTEXT_WINDOW_TOKENS
+        );
+    }
+
+    #[test]
+    fn catalog_windows_load_from_model_cache() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join("state")).unwrap();
+        std::fs::write(
+            tmp.path().join("state/models_cache.json"),
+            r#"{"entries":[
+                {"provider":"openrouter","fetched_at_unix":1,"models":["x/y"],"context_windows":{"x/y":65536}},
+                {"provider":"openai","fetched_at_unix":1,"models":["gpt-5"]}
+            ]}"#,
+        )
+        .unwrap();
+
+        let catalog = load_catalog_windows(tmp.path());
+        assert_eq!(catalog.len(), 1);
+        assert_eq!(catalog["openrouter"]["x/y"], 65_536);
+    }
+
+    #[test]
+    fn compaction_triggers_on_tokens_not_message_count() {
+        let budget = ContextBudget::new(8_000, 0);
+        let small: Vec<_> = (0..49)
+            .map(|i| ChatMessage::user(format!("hi {i}")))
+            .collect();
+        assert!(!budget.should_compact(&small));
+
+        let big = vec![ChatMessage::tool("x".repeat(30_000))];
+        assert!(budget.should_compact(&big));
+    }
+
+    #[test]
+    fn reserve_is_capped_for_small_windows() {
+        let budget = ContextBudget::new(4_096, 8_000);
+        assert_eq!(budget.usable_tokens(), 2_048);
+    }
+
+    #[test]
+    fn recent_messages_to_keep_respects_token_share() {
+        let budget = ContextBudget::new(10_000, 0);
+        let history = vec![
+            ChatMessage::user("x".repeat(20_000)),
+            ChatMessage::user("short"),
+            ChatMessage::assistant("short"),
+        ];
+        assert_eq!(budget.recent_messages_to_keep(&history), 2);
+        assert_eq!(budget.recent_messages_to_keep(&history[..1]), 1);
+    }
+
+    #[test]
+    fn tool_output_limit_shrinks_as_window_fills() {
+        let budget = ContextBudget::new(10_000, 0);
+        let empty = budget.tool_output_char_limit(&[], 1);
+        let full = budget.tool_output_char_limit(&[ChatMessage::user("x".repeat(36_000))], 1);
+        assert_eq!(empty, 5_000 * CHARS_PER_TOKEN);
+        assert!(full < empty);
+        assert_eq!(
+            budget.tool_output_char_limit(&[ChatMessage::user("x".repeat(40_000))], 4),
+            MIN_TOOL_OUTPUT_TOKENS * CHARS_PER_TOKEN
+        );
+    }
+
+    #[test]
+    fn trim_history_keeps_system_and_newest_message() {
+        let budget = ContextBudget::new(1_000, 0);
+        let mut history = vec![
+            ChatMessage::system("system"),
+            ChatMessage::user("x".repeat(4_000)),
+            ChatMessage::assistant("x".repeat(4_000)),
+            ChatMessage::user("latest"),
+        ];
+        budget.trim_history(&mut history);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].role, "system");
+        assert_eq!(history[1].content, "latest");
+
+        let mut oversized = vec![
+            ChatMessage::system("system"),
+            ChatMessage::user("x".repeat(10_000)),
+        ];
+        budget.trim_history(&mut oversized);
+        assert_eq!(oversized.len(), 2);
+    }
+
+    #[test]
+    fn truncate_to_budget_keeps_head_and_tail() {
+        let text = format!("{}{}", "a".repeat(500), "z".repeat(500));
+        let out = truncate_to_budget(&text, 200);
+        assert!(out.chars().count() <= 200);
+        assert!(out.starts_with('a'));
+        assert!(out.ends_with('z'));
+        assert!(out.contains("characters truncated"));
+        assert_eq!(truncate_to_budget("short", 200), "short");
+    }
+}
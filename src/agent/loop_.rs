@@ -1,13 +1,21 @@
+use crate::agent::context::{
+    estimate_message_tokens, estimate_tokens, truncate_to_budget, ContextBudget,
+    ContextWindowRegistry,
+};
 use crate::approval::{ApprovalManager, ApprovalRequest, ApprovalResponse};
 use crate::config::Config;
 use crate::memory::{self, Memory, MemoryCategory};
+use crate::observability::trace::{self, TurnUsage};
 use crate::observability::{self, Observer, ObserverEvent};
 use crate::providers::{self, ChatMessage, ChatRequest, Provider, ToolCall};
 use crate::runtime;
 use crate::security::SecurityPolicy;
+use crate::sessions::{Resume, Session, SessionStore};
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
+use opentelemetry::trace::{SpanKind, TraceContextExt};
+use opentelemetry::KeyValue;
 use regex::{Regex, RegexSet};
 use std::fmt::Write;
 use std::io::Write as _;
@@ -155,11 +163,14 @@ fn apply_compaction_summary(
     history.splice(start..compact_end, std::iter::once(summary_msg));
 }
 
+/// Summarize older history once it exceeds `max_history` messages or, when a
+/// budget is given, once it fills most of the model's context window.
 async fn auto_compact_history(
     history: &mut Vec<ChatMessage>,
     provider: &dyn Provider,
     model: &str,
     max_history: usize,
+    budget: Option<ContextBudget>,
 ) -> Result<bool> {
     let has_system = history.first().map_or(false, |m| m.role == "system");
     let non_system_count = if has_system {
@@ -168,12 +179,16 @@ async fn auto_compact_history(
         history.len()
     };
 
-    if non_system_count <= max_history {
+    let over_budget = budget.is_some_and(|b| b.should_compact(history));
+    if non_system_count <= max_history && !over_budget {
         return Ok(false);
     }
 
     let start = if has_system { 1 } else { 0 };
-    let keep_recent = COMPACTION_KEEP_RECENT_MESSAGES.min(non_system_count);
+    let mut keep_recent = COMPACTION_KEEP_RECENT_MESSAGES.min(non_system_count);
+    if let Some(budget) = budget {
+        keep_recent = keep_recent.min(budget.recent_messages_to_keep(&history[start..]));
+    }
     let compact_count = non_system_count.saturating_sub(keep_recent);
     if compact_count == 0 {
         return Ok(false);
@@ -232,9 +247,51 @@ async fn build_context(mem: &dyn Memory, user_msg: &str, min_relevance_score: f6
     context
 }
 
+/// Cap injected memory/RAG context to its share of the remaining context window.
+fn fit_context_to_budget(
+    context: String,
+    budget: ContextBudget,
+    history: &[ChatMessage],
+) -> String {
+    let limit = budget.memory_context_char_limit(history);
+    if limit == 0 {
+        String::new()
+    } else {
+        truncate_with_ellipsis(&context, limit)
+    }
+}
+
+/// Load the datasheet index from `peripherals.datasheet_dir`, embedding it
+/// with the `[memory]` embedding provider for hybrid retrieval.
+async fn load_hardware_rag(config: &Config) -> Option<crate::rag::HardwareRag> {
+    let dir = config
+        .peripherals
+        .datasheet_dir
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty())?;
+    let embedding = crate::rag::RagEmbedding::from_parts(
+        &config.memory.embedding_provider,
+        config.api_key.as_deref(),
+        &config.memory.embedding_model,
+        config.memory.embedding_dimensions,
+        config.memory.vector_weight,
+        config.memory.keyword_weight,
+    );
+    match crate::rag::HardwareRag::load_with_embeddings(&config.workspace_dir, dir, embedding).await
+    {
+        Ok(rag) if !rag.is_empty() => Some(rag),
+        Ok(_) => None,
+        Err(e) => {
+            tracing::warn!("Failed to load hardware datasheets: {e}");
+            None
+        }
+    }
+}
+
 /// Build hardware datasheet context from RAG when peripherals are enabled.
 /// Includes pin-alias lookup (e.g. "red_led" → 13) when query matches, plus retrieved chunks.
-fn build_hardware_context(
+async fn build_hardware_context(
     rag: &crate::rag::HardwareRag,
     user_msg: &str,
     boards: &[String],
@@ -252,7 +309,12 @@ fn build_hardware_context(
         context.push_str(&pin_ctx);
     }
 
-    let chunks = rag.retrieve(user_msg, boards, chunk_limit);
+    let chunks: Vec<_> = rag
+        .search(user_msg, boards, chunk_limit)
+        .await
+        .into_iter()
+        .map(|hit| hit.chunk)
+        .collect();
     if chunks.is_empty() && pin_ctx.is_empty() {
         return String::new();
     }
@@ -827,6 +889,7 @@ pub(crate) async fn agent_turn(
     temperature: f64,
     silent: bool,
     max_tool_iterations: usize,
+    context_budget: Option<ContextBudget>,
 ) -> Result<String> {
     run_tool_call_loop(
         provider,
@@ -841,12 +904,16 @@ pub(crate) async fn agent_turn(
         "channel",
         max_tool_iterations,
         None,
+        context_budget,
     )
     .await
 }
 
 /// Execute a single turn of the agent loop: send messages, parse tool calls,
 /// execute tools, and loop until the LLM produces a final text response.
+///
+/// The turn runs under an `agent.turn` span whose children are one `llm.call`
+/// span per provider round-trip and one `tool.call` span per executed tool.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn run_tool_call_loop(
     provider: &dyn Provider,
@@ -861,6 +928,68 @@ pub(crate) async fn run_tool_call_loop(
     channel_name: &str,
     max_tool_iterations: usize,
     on_delta: Option<tokio::sync::mpsc::Sender<String>>,
+    context_budget: Option<ContextBudget>,
+) -> Result<String> {
+    let is_root = !opentelemetry::Context::current().has_active_span();
+    let turn_cx = trace::start_span(
+        "agent.turn",
+        SpanKind::Internal,
+        vec![
+            KeyValue::new("gen_ai.system", provider_name.to_string()),
+            KeyValue::new("gen_ai.request.model", model.to_string()),
+            KeyValue::new("zeroclaw.channel", channel_name.to_string()),
+        ],
+    );
+    let mut usage = TurnUsage::default();
+    let turn = execute_tool_call_loop(
+        provider,
+        history,
+        tools_registry,
+        observer,
+        provider_name,
+        model,
+        temperature,
+        silent,
+        approval,
+        channel_name,
+        max_tool_iterations,
+        on_delta,
+        context_budget,
+        &mut usage,
+    );
+    let result = if is_root {
+        trace::in_root_span(turn_cx.clone(), turn).await
+    } else {
+        trace::in_span(turn_cx.clone(), turn).await
+    };
+
+    trace::set_attributes(&turn_cx, usage.attributes());
+    trace::end_span(
+        &turn_cx,
+        result
+            .as_ref()
+            .map(|_| ())
+            .map_err(|e| providers::sanitize_api_error(&e.to_string())),
+    );
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn execute_tool_call_loop(
+    provider: &dyn Provider,
+    history: &mut Vec<ChatMessage>,
+    tools_registry: &[Box<dyn Tool>],
+    observer: &dyn Observer,
+    provider_name: &str,
+    model: &str,
+    temperature: f64,
+    silent: bool,
+    approval: Option<&ApprovalManager>,
+    channel_name: &str,
+    max_tool_iterations: usize,
+    on_delta: Option<tokio::sync::mpsc::Sender<String>>,
+    context_budget: Option<ContextBudget>,
+    usage: &mut TurnUsage,
 ) -> Result<String> {
     let max_iterations = if max_tool_iterations == 0 {
         DEFAULT_MAX_TOOL_ITERATIONS
@@ -873,6 +1002,14 @@ pub(crate) async fn run_tool_call_loop(
     let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
 
     for _iteration in 0..max_iterations {
+        if let Some(budget) = context_budget {
+            observer.record_event(&ObserverEvent::ContextUsage {
+                provider: provider_name.to_string(),
+                model: model.to_string(),
+                estimated_tokens: estimate_message_tokens(history),
+                window_tokens: budget.window_tokens,
+            });
+        }
         observer.record_event(&ObserverEvent::LlmRequest {
             provider: provider_name.to_string(),
             model: model.to_string(),
@@ -880,6 +1017,16 @@ pub(crate) async fn run_tool_call_loop(
         });
 
         let llm_started_at = Instant::now();
+        let llm_cx = trace::start_span(
+            "llm.call",
+            SpanKind::Client,
+            vec![
+                KeyValue::new("gen_ai.system", provider_name.to_string()),
+                KeyValue::new("gen_ai.request.model", model.to_string()),
+                KeyValue::new("zeroclaw.messages", history.len() as i64),
+            ],
+        );
+        let input_tokens = estimate_message_tokens(history) as u64;
 
         // Unified path via Provider::chat so provider-specific native tool logic
         // (OpenAI/Anthropic/OpenRouter/compatible adapters) is honored.
@@ -890,16 +1037,18 @@ pub(crate) async fn run_tool_call_loop(
         };
 
         let (response_text, parsed_text, tool_calls, assistant_history_content, native_tool_calls) =
-            match provider
-                .chat(
+            match trace::in_span(
+                llm_cx.clone(),
+                provider.chat(
                     ChatRequest {
                         messages: history,
                         tools: request_tools,
                     },
                     model,
                     temperature,
-                )
-                .await
+                ),
+            )
+            .await
             {
                 Ok(resp) => {
                     observer.record_event(&ObserverEvent::LlmResponse {
@@ -940,17 +1089,35 @@ pub(crate) async fn run_tool_call_loop(
                     )
                 }
                 Err(e) => {
+                    let error_message = crate::providers::sanitize_api_error(&e.to_string());
                     observer.record_event(&ObserverEvent::LlmResponse {
                         provider: provider_name.to_string(),
                         model: model.to_string(),
                         duration: llm_started_at.elapsed(),
                         success: false,
-                        error_message: Some(crate::providers::sanitize_api_error(&e.to_string())),
+                        error_message: Some(error_message.clone()),
                     });
+                    trace::end_span(&llm_cx, Err(error_message));
                     return Err(e);
                 }
             };
 
+        usage.record_llm_call(
+            &llm_cx,
+            provider_name,
+            model,
+            input_tokens,
+            estimate_tokens(&assistant_history_content) as u64,
+        );
+        trace::set_attributes(
+            &llm_cx,
+            vec![KeyValue::new(
+                "zeroclaw.tool_calls",
+                tool_calls.len() as i64,
+            )],
+        );
+        trace::end_span(&llm_cx, Ok(()));
+
         let display_text = if parsed_text.is_empty() {
             response_text.clone()
         } else {
@@ -992,20 +1159,35 @@ pub(crate) async fn run_tool_call_loop(
         // can emit one `role: tool` message per tool call with the correct ID.
         let mut tool_results = String::new();
         let mut individual_results: Vec<String> = Vec::new();
+        // Split what is left of the context window across this batch of results.
+        let tool_output_limit =
+            context_budget.map(|b| b.tool_output_char_limit(history, tool_calls.len()));
         for call in &tool_calls {
             // ── Approval hook ────────────────────────────────
             if let Some(mgr) = approval {
+                if !mgr.is_tool_allowed(&call.name) {
+                    let refused = format!("Tool `{}` is not permitted for this user.", call.name);
+                    individual_results.push(refused.clone());
+                    let _ = writeln!(
+                        tool_results,
+                        "<tool_result name=\"{}\">\n{refused}\n</tool_result>",
+                        call.name
+                    );
+                    continue;
+                }
+
                 if mgr.needs_approval(&call.name) {
                     let request = ApprovalRequest {
                         tool_name: call.name.clone(),
                         arguments: call.arguments.clone(),
                     };
 
-                    // Only prompt interactively on CLI; auto-approve on other channels.
+                    // Prompt on CLI, or through the channel's own prompt if it
+                    // has one; other channels auto-approve.
                     let decision = if channel_name == "cli" {
                         mgr.prompt_cli(&request)
                     } else {
-                        ApprovalResponse::Yes
+                        mgr.prompt_remote(&request).await
                     };
 
                     mgr.record_decision(&call.name, &call.arguments, decision, channel_name);
@@ -1027,8 +1209,14 @@ pub(crate) async fn run_tool_call_loop(
                 tool: call.name.clone(),
             });
             let start = Instant::now();
+            let tool_cx = trace::start_span(
+                "tool.call",
+                SpanKind::Internal,
+                vec![KeyValue::new("gen_ai.tool.name", call.name.clone())],
+            );
+            usage.tool_calls += 1;
             let result = if let Some(tool) = find_tool(tools_registry, &call.name) {
-                match tool.execute(call.arguments.clone()).await {
+                match trace::in_span(tool_cx.clone(), tool.execute(call.arguments.clone())).await {
                     Ok(r) => {
                         observer.record_event(&ObserverEvent::ToolCall {
                             tool: call.name.clone(),
@@ -1036,9 +1224,12 @@ pub(crate) async fn run_tool_call_loop(
                             success: r.success,
                         });
                         if r.success {
+                            trace::end_span(&tool_cx, Ok(()));
                             scrub_credentials(&r.output)
                         } else {
-                            format!("Error: {}", r.error.unwrap_or_else(|| r.output))
+                            let error = r.error.unwrap_or_else(|| r.output);
+                            trace::end_span(&tool_cx, Err(scrub_credentials(&error)));
+                            format!("Error: {error}")
                         }
                     }
                     Err(e) => {
@@ -1047,12 +1238,18 @@ pub(crate) async fn run_tool_call_loop(
                             duration: start.elapsed(),
                             success: false,
                         });
+                        trace::end_span(&tool_cx, Err(scrub_credentials(&e.to_string())));
                         format!("Error executing {}: {e}", call.name)
                     }
                 }
             } else {
+                trace::end_span(&tool_cx, Err("unknown tool".to_string()));
                 format!("Unknown tool: {}", call.name)
             };
+            let result = match tool_output_limit {
+                Some(limit) => truncate_to_budget(&result, limit),
+                None => result,
+            };
 
             individual_results.push(result.clone());
             let _ = writeln!(
@@ -1113,6 +1310,45 @@ pub(crate) fn build_tool_instructions(tools_registry: &[Box<dyn Tool>]) -> Strin
     instructions
 }
 
+/// Rebuild provider history from a saved session, swapping in the current
+/// system prompt so tool/skill changes since the session was saved apply.
+fn resume_history(session: &Session, system_prompt: &str) -> Vec<ChatMessage> {
+    let mut history = vec![ChatMessage::system(system_prompt)];
+    history.extend(
+        session
+            .messages
+            .iter()
+            .skip_while(|m| m.role == "system")
+            .cloned(),
+    );
+    history
+}
+
+/// Append one turn's messages to the saved transcript. The transcript is kept
+/// in full even when the in-memory history is compacted or trimmed.
+/// `turn` starts with the context-enriched prompt; the transcript stores the
+/// raw `user_input` in its place so memory and hardware context are rebuilt
+/// for the current turn on resume instead of replaying stale recalls.
+/// Failures are logged, never fatal.
+fn persist_session(
+    store: &SessionStore,
+    session: &mut Session,
+    turn: &[ChatMessage],
+    provider_name: &str,
+    model_name: &str,
+    user_input: &str,
+) {
+    session.set_title_from(user_input);
+    session.provider = provider_name.to_string();
+    session.model = model_name.to_string();
+    session.updated_at = chrono::Utc::now();
+    session.messages.push(ChatMessage::user(user_input));
+    session.messages.extend(turn.iter().skip(1).cloned());
+    if let Err(e) = store.save(session) {
+        tracing::warn!("Failed to save session {}: {e}", session.id);
+    }
+}
+
 #[allow(clippy::too_many_lines)]
 pub async fn run(
     config: Config,
@@ -1121,10 +1357,16 @@ pub async fn run(
     model_override: Option<String>,
     temperature: f64,
     peripheral_overrides: Vec<String>,
+    resume: Option<Resume>,
 ) -> Result<String> {
+    // ── Saved session (resume/continue) ──────────────────────────
+    let session_store = SessionStore::new(&config.workspace_dir);
+    let resumed = resume.map(|r| session_store.open(&r)).transpose()?;
+
     // ── Wire up agnostic subsystems ──────────────────────────────
     let base_observer = observability::create_observer(&config.observability);
     let observer: Arc<dyn Observer> = Arc::from(base_observer);
+    trace::set_model_pricing(&config.cost.prices);
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -1174,23 +1416,35 @@ pub async fn run(
     );
 
     let peripheral_tools: Vec<Box<dyn Tool>> =
-        crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
+        crate::peripherals::create_peripheral_tools(&config.peripherals, &security).await?;
     if !peripheral_tools.is_empty() {
         tracing::info!(count = peripheral_tools.len(), "Peripheral tools added");
         tools_registry.extend(peripheral_tools);
     }
 
     // ── Resolve provider ─────────────────────────────────────────
+    // A resumed session keeps its original provider/model unless overridden.
+    let resumed_route = resumed
+        .as_ref()
+        .filter(|_| provider_override.is_none() && model_override.is_none())
+        .map(|s| (s.provider.clone(), s.model.clone()));
     let provider_name = provider_override
         .as_deref()
+        .or(resumed_route
+            .as_ref()
+            .map(|(provider, _)| provider.as_str()))
         .or(config.default_provider.as_deref())
         .unwrap_or("openrouter");
 
     let model_name = model_override
         .as_deref()
+        .or(resumed_route.as_ref().map(|(_, model)| model.as_str()))
         .or(config.default_model.as_deref())
         .unwrap_or("anthropic/claude-sonnet-4");
 
+    let context_budget =
+        ContextWindowRegistry::from_config(&config).budget_for(provider_name, model_name);
+
     let provider: Box<dyn Provider> = providers::create_routed_provider(
         provider_name,
         config.api_key.as_deref(),
@@ -1206,16 +1460,14 @@ pub async fn run(
     });
 
     // ── Hardware RAG (datasheet retrieval when peripherals + datasheet_dir) ──
-    let hardware_rag: Option<crate::rag::HardwareRag> = config
-        .peripherals
-        .datasheet_dir
-        .as_ref()
-        .filter(|d| !d.trim().is_empty())
-        .map(|dir| crate::rag::HardwareRag::load(&config.workspace_dir, dir.trim()))
-        .and_then(Result::ok)
-        .filter(|r: &crate::rag::HardwareRag| !r.is_empty());
+    let hardware_rag = load_hardware_rag(&config).await;
     if let Some(ref rag) = hardware_rag {
-        tracing::info!(chunks = rag.len(), "Hardware RAG loaded");
+        tracing::info!(
+            chunks = rag.len(),
+            table_rows = rag.table_row_count(),
+            embedding = rag.embedding_model().unwrap_or("none"),
+            "Hardware RAG loaded"
+        );
     }
 
     let board_names: Vec<String> = config
@@ -1232,6 +1484,10 @@ pub async fn run(
             "shell",
             "Execute terminal commands. Use when: running local checks, build/test commands, diagnostics. Don't use when: a safer dedicated tool exists, or command is destructive without approval.",
         ),
+        (
+            "process",
+            "Manage background processes started with shell(background=true): list, poll output, tail, write stdin, kill. Use when: running dev servers, long builds, or REPLs. Don't use when: a command finishes quickly; run it in the foreground.",
+        ),
         (
             "file_read",
             "Read file contents. Use when: inspecting project files, configs, logs. Don't use when: a targeted search is enough.",
@@ -1240,6 +1496,22 @@ pub async fn run(
             "file_write",
             "Write file contents. Use when: applying focused edits, scaffolding files, updating docs/code. Don't use when: side effects are unclear or file ownership is uncertain.",
         ),
+        (
+            "file_edit",
+            "Edit an existing file by exact string replacement or unified diff. Use when: changing a few lines of a larger file. Don't use when: creating a new file or rewriting it wholesale.",
+        ),
+        (
+            "glob",
+            "Find files by glob pattern (respects .gitignore). Use when: locating files by name or extension. Don't use when: you already know the exact path.",
+        ),
+        (
+            "content_search",
+            "Regex search across workspace files with optional context lines (respects .gitignore). Use when: finding definitions, usages, or strings. Don't use when: you need the whole file; use file_read.",
+        ),
+        (
+            "list_dir",
+            "List directory contents, optionally as a tree. Use when: exploring project layout. Don't use when: searching for a specific file; use glob.",
+        ),
         (
             "memory_store",
             "Save to memory. Use when: preserving durable preferences, decisions, key context. Don't use when: information is transient/noisy/sensitive without need.",
@@ -1291,6 +1563,18 @@ pub async fn run(
             "Execute actions on 1000+ apps via Composio (Gmail, Notion, GitHub, Slack, etc.). Use action='list' to discover, 'execute' to run (optionally with connected_account_id), 'connect' to OAuth.",
         ));
     }
+    if !config.memory.obsidian.vault_path.trim().is_empty() {
+        tool_descs.push((
+            "obsidian_notes",
+            "Create, append to, link ([[wikilinks]]) or read notes in the team's Obsidian vault. Use memory_recall to search the vault first.",
+        ));
+    }
+    if config.home_assistant.enabled {
+        tool_descs.push((
+            "home_assistant",
+            "Query and control Home Assistant (list_entities/list_areas/get_state/call_service/history) on allowlisted entities. Lock/alarm services need approved=true.",
+        ));
+    }
     tool_descs.push((
         "schedule",
         "Manage scheduled tasks (create/list/get/cancel/pause/resume). Supports recurring cron and one-shot delays.",
@@ -1369,21 +1653,30 @@ pub async fn run(
         let mem_context =
             build_context(mem.as_ref(), &msg, config.memory.min_relevance_score).await;
         let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-        let hw_context = hardware_rag
-            .as_ref()
-            .map(|r| build_hardware_context(r, &msg, &board_names, rag_limit))
-            .unwrap_or_default();
-        let context = format!("{mem_context}{hw_context}");
+        let hw_context = match &hardware_rag {
+            Some(rag) => build_hardware_context(rag, &msg, &board_names, rag_limit).await,
+            None => String::new(),
+        };
+        let mut session = resumed;
+        let mut history = match &session {
+            Some(saved) => resume_history(saved, &system_prompt),
+            None => vec![ChatMessage::system(&system_prompt)],
+        };
+        trim_history(&mut history, config.agent.max_history_messages);
+        context_budget.trim_history(&mut history);
+
+        let context = fit_context_to_budget(
+            format!("{mem_context}{hw_context}"),
+            context_budget,
+            &history,
+        );
         let enriched = if context.is_empty() {
             msg.clone()
         } else {
             format!("{context}{msg}")
         };
-
-        let mut history = vec![
-            ChatMessage::system(&system_prompt),
-            ChatMessage::user(&enriched),
-        ];
+        let turn_start = history.len();
+        history.push(ChatMessage::user(&enriched));
 
         let response = run_tool_call_loop(
             provider.as_ref(),
@@ -1398,12 +1691,25 @@ pub async fn run(
             "cli",
             config.agent.max_tool_iterations,
             None,
+            Some(context_budget),
         )
         .await?;
         final_output = response.clone();
         println!("{response}");
         observer.record_event(&ObserverEvent::TurnComplete);
 
+        // One-shot runs only persist when continuing an existing session.
+        if let Some(session) = session.as_mut() {
+            persist_session(
+                &session_store,
+                session,
+                &history[turn_start..],
+                provider_name,
+                model_name,
+                &msg,
+            );
+        }
+
         // Auto-save assistant response to daily log
         if config.memory.auto_save {
             let summary = truncate_with_ellipsis(&response, 100);
@@ -1418,7 +1724,21 @@ pub async fn run(
         let cli = crate::channels::CliChannel::new();
 
         // Persistent conversation history across turns
-        let mut history = vec![ChatMessage::system(&system_prompt)];
+        let mut session = match resumed {
+            Some(saved) => {
+                println!(
+                    "↩️  Resumed session {} ({} turn(s)): {}\n",
+                    saved.id,
+                    saved.user_turns(),
+                    saved.title
+                );
+                saved
+            }
+            None => Session::new(provider_name, model_name),
+        };
+        let mut history = resume_history(&session, &system_prompt);
+        trim_history(&mut history, config.agent.max_history_messages);
+        context_budget.trim_history(&mut history);
 
         loop {
             print!("> ");
@@ -1466,6 +1786,8 @@ pub async fn run(
 
                     history.clear();
                     history.push(ChatMessage::system(&system_prompt));
+                    // The cleared conversation stays saved; start a fresh session.
+                    session = Session::new(provider_name, model_name);
                     // Clear conversation and daily memory
                     let mut cleared = 0;
                     for category in [MemoryCategory::Conversation, MemoryCategory::Daily] {
@@ -1498,17 +1820,24 @@ pub async fn run(
             let mem_context =
                 build_context(mem.as_ref(), &user_input, config.memory.min_relevance_score).await;
             let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-            let hw_context = hardware_rag
-                .as_ref()
-                .map(|r| build_hardware_context(r, &user_input, &board_names, rag_limit))
-                .unwrap_or_default();
-            let context = format!("{mem_context}{hw_context}");
+            let hw_context = match &hardware_rag {
+                Some(rag) => {
+                    build_hardware_context(rag, &user_input, &board_names, rag_limit).await
+                }
+                None => String::new(),
+            };
+            let context = fit_context_to_budget(
+                format!("{mem_context}{hw_context}"),
+                context_budget,
+                &history,
+            );
             let enriched = if context.is_empty() {
                 user_input.clone()
             } else {
                 format!("{context}{user_input}")
             };
 
+            let turn_start = history.len();
             history.push(ChatMessage::user(&enriched));
 
             let response = match run_tool_call_loop(
@@ -1524,6 +1853,7 @@ pub async fn run(
                 "cli",
                 config.agent.max_tool_iterations,
                 None,
+                Some(context_budget),
             )
             .await
             {
@@ -1544,12 +1874,22 @@ pub async fn run(
             }
             observer.record_event(&ObserverEvent::TurnComplete);
 
+            persist_session(
+                &session_store,
+                &mut session,
+                &history[turn_start..],
+                provider_name,
+                model_name,
+                &user_input,
+            );
+
             // Auto-compaction before hard trimming to preserve long-context signal.
             if let Ok(compacted) = auto_compact_history(
                 &mut history,
                 provider.as_ref(),
                 model_name,
                 config.agent.max_history_messages,
+                Some(context_budget),
             )
             .await
             {
@@ -1558,8 +1898,9 @@ pub async fn run(
                 }
             }
 
-            // Hard cap as a safety net.
+            // Hard caps as a safety net.
             trim_history(&mut history, config.agent.max_history_messages);
+            context_budget.trim_history(&mut history);
 
             if config.memory.auto_save {
                 let summary = truncate_with_ellipsis(&response, 100);
@@ -1569,6 +1910,13 @@ pub async fn run(
                     .await;
             }
         }
+
+        if !session.messages.is_empty() {
+            println!(
+                "Session saved: {} (resume with `zeroclaw agent --resume {}`)",
+                session.id, session.id
+            );
+        }
     }
 
     let duration = start.elapsed();
@@ -1588,6 +1936,7 @@ pub async fn run(
 pub async fn process_message(config: Config, message: &str) -> Result<String> {
     let observer: Arc<dyn Observer> =
         Arc::from(observability::create_observer(&config.observability));
+    trace::set_model_pricing(&config.cost.prices);
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -1624,7 +1973,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         &config,
     );
     let peripheral_tools: Vec<Box<dyn Tool>> =
-        crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
+        crate::peripherals::create_peripheral_tools(&config.peripherals, &security).await?;
     tools_registry.extend(peripheral_tools);
 
     let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
@@ -1641,14 +1990,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         &model_name,
     )?;
 
-    let hardware_rag: Option<crate::rag::HardwareRag> = config
-        .peripherals
-        .datasheet_dir
-        .as_ref()
-        .filter(|d| !d.trim().is_empty())
-        .map(|dir| crate::rag::HardwareRag::load(&config.workspace_dir, dir.trim()))
-        .and_then(Result::ok)
-        .filter(|r: &crate::rag::HardwareRag| !r.is_empty());
+    let hardware_rag = load_hardware_rag(&config).await;
     let board_names: Vec<String> = config
         .peripherals
         .boards
@@ -1659,8 +2001,13 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
     let skills = crate::skills::load_skills(&config.workspace_dir);
     let mut tool_descs: Vec<(&str, &str)> = vec![
         ("shell", "Execute terminal commands."),
+        ("process", "Manage background shell processes."),
         ("file_read", "Read file contents."),
         ("file_write", "Write file contents."),
+        ("file_edit", "Edit part of a file."),
+        ("glob", "Find files by glob pattern."),
+        ("content_search", "Regex search in file contents."),
+        ("list_dir", "List directory contents."),
         ("memory_store", "Save to memory."),
         ("memory_recall", "Search memory."),
         ("memory_forget", "Delete a memory entry."),
@@ -1673,6 +2020,15 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
     if config.composio.enabled {
         tool_descs.push(("composio", "Execute actions on 1000+ apps via Composio."));
     }
+    if !config.memory.obsidian.vault_path.trim().is_empty() {
+        tool_descs.push((
+            "obsidian_notes",
+            "Create, append, link or read vault notes.",
+        ));
+    }
+    if config.home_assistant.enabled {
+        tool_descs.push(("home_assistant", "Query and control Home Assistant."));
+    }
     if config.peripherals.enabled && !config.peripherals.boards.is_empty() {
         tool_descs.push(("gpio_read", "Read GPIO pin value on connected hardware."));
         tool_descs.push((
@@ -1717,21 +2073,24 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
 
     let mem_context = build_context(mem.as_ref(), message, config.memory.min_relevance_score).await;
     let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-    let hw_context = hardware_rag
-        .as_ref()
-        .map(|r| build_hardware_context(r, message, &board_names, rag_limit))
-        .unwrap_or_default();
-    let context = format!("{mem_context}{hw_context}");
+    let hw_context = match &hardware_rag {
+        Some(rag) => build_hardware_context(rag, message, &board_names, rag_limit).await,
+        None => String::new(),
+    };
+    let context_budget =
+        ContextWindowRegistry::from_config(&config).budget_for(provider_name, &model_name);
+    let mut history = vec![ChatMessage::system(&system_prompt)];
+    let context = fit_context_to_budget(
+        format!("{mem_context}{hw_context}"),
+        context_budget,
+        &history,
+    );
     let enriched = if context.is_empty() {
         message.to_string()
     } else {
         format!("{context}{message}")
     };
-
-    let mut history = vec![
-        ChatMessage::system(&system_prompt),
-        ChatMessage::user(&enriched),
-    ];
+    history.push(ChatMessage::user(&enriched));
 
     agent_turn(
         provider.as_ref(),
@@ -1743,6 +2102,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         config.default_temperature,
         true,
         config.agent.max_tool_iterations,
+        Some(context_budget),
     )
     .await
 }
@@ -2215,6 +2575,37 @@ Done."#;
         assert!(recalled.iter().any(|entry| entry.content.contains("45")));
     }
 
+    #[test]
+    fn persisted_turn_keeps_raw_user_input_without_recalled_context() {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::new(tmp.path());
+        let mut session = Session::new("openrouter", "model");
+        let turn = vec![
+            ChatMessage::user("[Memory context]\n- fact: I'm Paul\n\nwhat's my name?"),
+            ChatMessage::assistant("Paul"),
+        ];
+
+        persist_session(
+            &store,
+            &mut session,
+            &turn,
+            "openrouter",
+            "model",
+            "what's my name?",
+        );
+
+        let saved = store.load(&session.id).unwrap();
+        assert_eq!(saved.messages.len(), 2);
+        assert_eq!(saved.messages[0].role, "user");
+        assert_eq!(saved.messages[0].content, "what's my name?");
+        assert_eq!(saved.messages[1].content, "Paul");
+
+        let resumed = resume_history(&saved, "system");
+        assert!(resumed
+            .iter()
+            .all(|m| !m.content.contains("[Memory context]")));
+    }
+
     // ═══════════════════════════════════════════════════════════════════════
     // Recovery Tests - Tool Call Parsing Edge Cases
     // ═══════════════════════════════════════════════════════════════════════
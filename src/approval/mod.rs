@@ -5,11 +5,13 @@
 
 use crate::config::AutonomyConfig;
 use crate::security::AutonomyLevel;
+use async_trait::async_trait;
 use chrono::Utc;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::io::{self, BufRead, Write};
+use std::sync::Arc;
 
 // ── Types ────────────────────────────────────────────────────────
 
@@ -42,6 +44,15 @@ pub struct ApprovalLogEntry {
     pub channel: String,
 }
 
+/// Asks a user on a non-CLI channel to approve a tool call (e.g. WebChat
+/// approval buttons).
+#[async_trait]
+pub trait ApprovalPrompt: Send + Sync {
+    /// Wait for the user's decision. Implementations should return `No` when
+    /// the user cannot be reached or does not answer in time.
+    async fn ask(&self, request: &ApprovalRequest) -> ApprovalResponse;
+}
+
 // ── ApprovalManager ──────────────────────────────────────────────
 
 /// Manages the interactive approval workflow.
@@ -60,6 +71,10 @@ pub struct ApprovalManager {
     session_allowlist: Mutex<HashSet<String>>,
     /// Audit trail of approval decisions.
     audit_log: Mutex<Vec<ApprovalLogEntry>>,
+    /// Interactive prompt for non-CLI channels.
+    remote_prompt: Option<Arc<dyn ApprovalPrompt>>,
+    /// When set, only these tools may run at all.
+    tool_allowlist: Option<HashSet<String>>,
 }
 
 impl ApprovalManager {
@@ -71,9 +86,38 @@ impl ApprovalManager {
             autonomy_level: config.level,
             session_allowlist: Mutex::new(HashSet::new()),
             audit_log: Mutex::new(Vec::new()),
+            remote_prompt: None,
+            tool_allowlist: None,
         }
     }
 
+    /// Prompt through `prompt` on channels other than CLI instead of
+    /// auto-approving.
+    #[must_use]
+    pub fn with_remote_prompt(mut self, prompt: Arc<dyn ApprovalPrompt>) -> Self {
+        self.remote_prompt = Some(prompt);
+        self
+    }
+
+    /// Refuse every tool not in `tools` (e.g. a per-user allowlist).
+    #[must_use]
+    pub fn with_tool_allowlist(mut self, tools: impl IntoIterator<Item = String>) -> Self {
+        self.tool_allowlist = Some(tools.into_iter().collect());
+        self
+    }
+
+    /// The interactive prompt used on non-CLI channels, if any.
+    pub fn remote_prompt(&self) -> Option<Arc<dyn ApprovalPrompt>> {
+        self.remote_prompt.clone()
+    }
+
+    /// Whether `tool_name` may run at all. Checked before [`Self::needs_approval`].
+    pub fn is_tool_allowed(&self, tool_name: &str) -> bool {
+        self.tool_allowlist
+            .as_ref()
+            .map_or(true, |allowed| allowed.contains(tool_name))
+    }
+
     /// Check whether a tool call requires interactive approval.
     ///
     /// Returns `true` if the call needs a prompt, `false` if it can proceed.
@@ -146,12 +190,20 @@ impl ApprovalManager {
     }
 
     /// Prompt the user on the CLI and return their decision.
-    ///
-    /// For non-CLI channels, returns `Yes` automatically (interactive
-    /// approval is only supported on CLI for now).
     pub fn prompt_cli(&self, request: &ApprovalRequest) -> ApprovalResponse {
         prompt_cli_interactive(request)
     }
+
+    /// Ask the user on a non-CLI channel.
+    ///
+    /// Returns `Yes` automatically when the channel has no interactive
+    /// prompt.
+    pub async fn prompt_remote(&self, request: &ApprovalRequest) -> ApprovalResponse {
+        match &self.remote_prompt {
+            Some(prompt) => prompt.ask(request).await,
+            None => ApprovalResponse::Yes,
+        }
+    }
 }
 
 // ── CLI prompt ───────────────────────────────────────────────────
@@ -179,7 +231,7 @@ fn prompt_cli_interactive(request: &ApprovalRequest) -> ApprovalResponse {
 }
 
 /// Produce a short human-readable summary of tool arguments.
-fn summarize_args(args: &serde_json::Value) -> String {
+pub(crate) fn summarize_args(args: &serde_json::Value) -> String {
     match args {
         serde_json::Value::Object(map) => {
             let parts: Vec<String> = map
@@ -277,6 +329,16 @@ mod tests {
         assert!(!mgr.needs_approval("shell"));
     }
 
+    #[test]
+    fn tool_allowlist_refuses_unlisted_tools() {
+        let mgr = ApprovalManager::from_config(&full_config());
+        assert!(mgr.is_tool_allowed("shell"));
+
+        let mgr = mgr.with_tool_allowlist(vec!["file_read".to_string()]);
+        assert!(mgr.is_tool_allowed("file_read"));
+        assert!(!mgr.is_tool_allowed("shell"));
+    }
+
     // ── session allowlist ────────────────────────────────────
 
     #[test]
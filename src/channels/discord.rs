@@ -1,11 +1,68 @@
 use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::approval::{
+    summarize_args, ApprovalManager, ApprovalPrompt, ApprovalRequest, ApprovalResponse,
+};
+use crate::config::AutonomyConfig;
+use crate::util::truncate_with_ellipsis;
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
 use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
 use tokio_tungstenite::tungstenite::Message;
 use uuid::Uuid;
 
+const DISCORD_API: &str = "https://discord.com/api/v10";
+
+/// Command name, description and optional string argument (name, description).
+type SlashCommandSpec = (
+    &'static str,
+    &'static str,
+    Option<(&'static str, &'static str)>,
+);
+
+/// Application commands registered when `slash_commands` is enabled. Each
+/// maps onto the text runtime command of the same name.
+const SLASH_COMMANDS: &[SlashCommandSpec] = &[
+    (
+        "model",
+        "Show or switch the model for this conversation",
+        Some(("model", "Model ID to switch to")),
+    ),
+    (
+        "models",
+        "List providers or switch provider",
+        Some(("provider", "Provider to switch to")),
+    ),
+    ("reset", "Clear this conversation's history", None),
+    (
+        "memory",
+        "Show recent memories or search them",
+        Some(("query", "Search text")),
+    ),
+    ("cron", "List scheduled jobs", None),
+];
+
+/// `custom_id` prefix of the buttons attached to tool approval requests.
+const APPROVAL_CUSTOM_ID_PREFIX: &str = "zeroclaw_approval";
+
+/// Interaction tokens stay valid for 15 minutes.
+const INTERACTION_TOKEN_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Message flag that makes an interaction response visible only to its user.
+const EPHEMERAL_FLAG: u64 = 1 << 6;
+
+/// An approval prompt waiting for a button click from `requester`.
+struct PendingApproval {
+    requester: String,
+    reply: oneshot::Sender<ApprovalResponse>,
+}
+
+type PendingApprovals = Arc<Mutex<HashMap<String, PendingApproval>>>;
+
 /// Discord channel — connects via Gateway WebSocket for real-time messages
 pub struct DiscordChannel {
     bot_token: String,
@@ -14,6 +71,16 @@ pub struct DiscordChannel {
     listen_to_bots: bool,
     mention_only: bool,
     typing_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
+    slash_commands: bool,
+    command_permissions: Option<String>,
+    application_id: Mutex<Option<String>>,
+    /// Interaction tokens whose deferred "thinking" reply was already
+    /// replaced; later chunks go out as follow-ups.
+    answered_interactions: Mutex<HashMap<String, Instant>>,
+    autonomy: Option<AutonomyConfig>,
+    approval_timeout: Duration,
+    approvals: Mutex<HashMap<String, Arc<ApprovalManager>>>,
+    pending_approvals: PendingApprovals,
 }
 
 impl DiscordChannel {
@@ -31,9 +98,32 @@ impl DiscordChannel {
             listen_to_bots,
             mention_only,
             typing_handle: Mutex::new(None),
+            slash_commands: false,
+            command_permissions: None,
+            application_id: Mutex::new(None),
+            answered_interactions: Mutex::new(HashMap::new()),
+            autonomy: None,
+            approval_timeout: Duration::from_secs(120),
+            approvals: Mutex::new(HashMap::new()),
+            pending_approvals: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// Register the slash commands on connect. `permissions` is the
+    /// `default_member_permissions` bitfield applied to every command.
+    pub fn with_slash_commands(mut self, enabled: bool, permissions: Option<String>) -> Self {
+        self.slash_commands = enabled;
+        self.command_permissions = permissions;
+        self
+    }
+
+    /// Ask for tool approvals with message buttons.
+    pub fn with_approvals(mut self, autonomy: &AutonomyConfig, timeout_secs: u64) -> Self {
+        self.autonomy = Some(autonomy.clone());
+        self.approval_timeout = Duration::from_secs(timeout_secs.max(1));
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.discord")
     }
@@ -50,6 +140,403 @@ impl DiscordChannel {
         let part = token.split('.').next()?;
         base64_decode(part)
     }
+
+    /// Application ID from the gateway `READY` event. For bots created
+    /// through the developer portal it equals the bot user ID.
+    fn application_id(&self) -> Option<String> {
+        self.application_id
+            .lock()
+            .clone()
+            .or_else(|| Self::bot_user_id_from_token(&self.bot_token))
+            .filter(|id| !id.is_empty())
+    }
+
+    async fn register_slash_commands(&self, application_id: &str) -> anyhow::Result<()> {
+        let url = match &self.guild_id {
+            Some(guild) => {
+                format!("{DISCORD_API}/applications/{application_id}/guilds/{guild}/commands")
+            }
+            None => format!("{DISCORD_API}/applications/{application_id}/commands"),
+        };
+        let resp = self
+            .http_client()
+            .put(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .json(&slash_command_definitions(
+                self.command_permissions.as_deref(),
+            ))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            anyhow::bail!("Discord command registration failed ({status}): {err}");
+        }
+        Ok(())
+    }
+
+    async fn respond_to_interaction(
+        &self,
+        interaction_id: &str,
+        token: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        let url = format!("{DISCORD_API}/interactions/{interaction_id}/{token}/callback");
+        let resp = self.http_client().post(&url).json(body).send().await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            anyhow::bail!("Discord interaction response failed ({status}): {err}");
+        }
+        Ok(())
+    }
+
+    /// Handle an `INTERACTION_CREATE` event. Slash commands are acknowledged
+    /// with a deferred response and returned as a message for the agent;
+    /// button clicks resolve pending tool approvals.
+    async fn handle_interaction(&self, d: &serde_json::Value) -> Option<ChannelMessage> {
+        let interaction_id = d.get("id").and_then(serde_json::Value::as_str)?;
+        let token = d.get("token").and_then(serde_json::Value::as_str)?;
+        let user_id = interaction_user_id(d)?;
+
+        if let (Some(filter), Some(guild)) = (
+            self.guild_id.as_deref(),
+            d.get("guild_id").and_then(serde_json::Value::as_str),
+        ) {
+            if guild != filter {
+                return None;
+            }
+        }
+
+        if !self.is_user_allowed(user_id) {
+            tracing::warn!("Discord: ignoring interaction from unauthorized user: {user_id}");
+            let body = json!({
+                "type": 4,
+                "data": {
+                    "content": "You are not allowed to use this bot.",
+                    "flags": EPHEMERAL_FLAG,
+                }
+            });
+            if let Err(e) = self
+                .respond_to_interaction(interaction_id, token, &body)
+                .await
+            {
+                tracing::warn!("Discord: {e}");
+            }
+            return None;
+        }
+
+        match d.get("type").and_then(serde_json::Value::as_u64) {
+            // APPLICATION_COMMAND
+            Some(2) => {
+                let content = parse_command_interaction(d)?;
+                let channel_id = d.get("channel_id").and_then(serde_json::Value::as_str)?;
+                // DEFERRED_CHANNEL_MESSAGE_WITH_SOURCE: shows "thinking…" until
+                // the reply replaces it.
+                if let Err(e) = self
+                    .respond_to_interaction(interaction_id, token, &json!({ "type": 5 }))
+                    .await
+                {
+                    tracing::warn!("Discord: {e}");
+                    return None;
+                }
+                Some(ChannelMessage {
+                    id: format!("discord_{interaction_id}"),
+                    sender: user_id.to_string(),
+                    reply_target: format!("{channel_id}:{token}"),
+                    content,
+                    channel: "discord".to_string(),
+                    timestamp: std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs(),
+                })
+            }
+            // MESSAGE_COMPONENT
+            Some(3) => {
+                let body = resolve_approval_click(&self.pending_approvals, d, user_id);
+                if let Err(e) = self
+                    .respond_to_interaction(interaction_id, token, &body)
+                    .await
+                {
+                    tracing::warn!("Discord: {e}");
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether `token`'s deferred response still needs replacing. Marks it
+    /// as answered and forgets tokens that have expired.
+    fn claim_original_response(&self, token: &str) -> bool {
+        let mut answered = self.answered_interactions.lock();
+        answered.retain(|_, at| at.elapsed() < INTERACTION_TOKEN_TTL);
+        answered.insert(token.to_string(), Instant::now()).is_none()
+    }
+
+    async fn send_interaction_reply(&self, token: &str, content: &str) -> anyhow::Result<()> {
+        let application_id = self
+            .application_id()
+            .ok_or_else(|| anyhow::anyhow!("Discord application ID is unknown"))?;
+        let client = self.http_client();
+        let request = if self.claim_original_response(token) {
+            client.patch(format!(
+                "{DISCORD_API}/webhooks/{application_id}/{token}/messages/@original"
+            ))
+        } else {
+            client.post(format!("{DISCORD_API}/webhooks/{application_id}/{token}"))
+        };
+
+        let resp = request.json(&json!({ "content": content })).send().await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            anyhow::bail!("Discord interaction reply failed ({status}): {err}");
+        }
+        Ok(())
+    }
+}
+
+/// Split a reply target into the channel ID and, for slash commands, the
+/// interaction token (`channel_id:token`).
+fn parse_reply_target(reply_target: &str) -> (&str, Option<&str>) {
+    match reply_target.split_once(':') {
+        Some((channel, token)) if !token.is_empty() => (channel, Some(token)),
+        _ => (reply_target, None),
+    }
+}
+
+fn slash_command_definitions(permissions: Option<&str>) -> serde_json::Value {
+    let commands: Vec<serde_json::Value> = SLASH_COMMANDS
+        .iter()
+        .map(|(name, description, option)| {
+            let options: Vec<serde_json::Value> = option
+                .iter()
+                .map(|(option_name, option_description)| {
+                    json!({
+                        "type": 3,
+                        "name": option_name,
+                        "description": option_description,
+                        "required": false,
+                    })
+                })
+                .collect();
+            json!({
+                "type": 1,
+                "name": name,
+                "description": description,
+                "options": options,
+                "default_member_permissions": permissions,
+            })
+        })
+        .collect();
+    json!(commands)
+}
+
+/// The invoking user: `member.user` in guilds, `user` in DMs.
+fn interaction_user_id(d: &serde_json::Value) -> Option<&str> {
+    d.get("member")
+        .and_then(|m| m.get("user"))
+        .or_else(|| d.get("user"))
+        .and_then(|u| u.get("id"))
+        .and_then(serde_json::Value::as_str)
+}
+
+/// Turn a slash command into the equivalent text command, e.g.
+/// `/model model:gpt-4o` becomes `/model gpt-4o`.
+fn parse_command_interaction(d: &serde_json::Value) -> Option<String> {
+    let data = d.get("data")?;
+    let name = data.get("name").and_then(serde_json::Value::as_str)?;
+    if !SLASH_COMMANDS.iter().any(|(known, _, _)| *known == name) {
+        return None;
+    }
+
+    let mut content = format!("/{name}");
+    let options = data
+        .get("options")
+        .and_then(serde_json::Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for value in options
+        .iter()
+        .filter_map(|option| option.get("value").and_then(serde_json::Value::as_str))
+    {
+        let value = value.trim();
+        if !value.is_empty() {
+            content.push(' ');
+            content.push_str(value);
+        }
+    }
+    Some(content)
+}
+
+fn approval_custom_id(id: &str, decision: ApprovalResponse) -> String {
+    let decision = match decision {
+        ApprovalResponse::Yes => "yes",
+        ApprovalResponse::Always => "always",
+        ApprovalResponse::No => "no",
+    };
+    format!("{APPROVAL_CUSTOM_ID_PREFIX}:{id}:{decision}")
+}
+
+fn parse_approval_custom_id(custom_id: &str) -> Option<(&str, ApprovalResponse)> {
+    let rest = custom_id
+        .strip_prefix(APPROVAL_CUSTOM_ID_PREFIX)?
+        .strip_prefix(':')?;
+    let (id, decision) = rest.rsplit_once(':')?;
+    let decision = match decision {
+        "yes" => ApprovalResponse::Yes,
+        "always" => ApprovalResponse::Always,
+        "no" => ApprovalResponse::No,
+        _ => return None,
+    };
+    Some((id, decision))
+}
+
+fn approval_message(id: &str, request: &ApprovalRequest) -> serde_json::Value {
+    let summary = truncate_with_ellipsis(&summarize_args(&request.arguments), 1500);
+    let button = |label: &str, style: u64, decision| {
+        json!({
+            "type": 2,
+            "style": style,
+            "label": label,
+            "custom_id": approval_custom_id(id, decision),
+        })
+    };
+    json!({
+        "content": format!("🔧 Agent wants to run `{}`\n{summary}", request.tool_name),
+        "components": [{
+            "type": 1,
+            "components": [
+                button("Approve", 3, ApprovalResponse::Yes),
+                button("Always", 1, ApprovalResponse::Always),
+                button("Deny", 4, ApprovalResponse::No),
+            ]
+        }]
+    })
+}
+
+/// Resolve the pending approval a button belongs to and build the response
+/// that replaces the prompt (buttons removed, decision noted). Clicks from
+/// anyone but the user whose message triggered the tool call are refused.
+fn resolve_approval_click(
+    pending: &PendingApprovals,
+    d: &serde_json::Value,
+    user_id: &str,
+) -> serde_json::Value {
+    let custom_id = d
+        .get("data")
+        .and_then(|data| data.get("custom_id"))
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or("");
+    let Some((id, decision)) = parse_approval_custom_id(custom_id) else {
+        return json!({
+            "type": 4,
+            "data": { "content": "Unknown action.", "flags": EPHEMERAL_FLAG }
+        });
+    };
+
+    let original = d
+        .get("message")
+        .and_then(|m| m.get("content"))
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or("");
+    let mut pending = pending.lock();
+    if let Some(entry) = pending.get(id) {
+        if entry.requester != user_id {
+            return json!({
+                "type": 4,
+                "data": {
+                    "content": format!("Only <@{}> can answer this approval.", entry.requester),
+                    "flags": EPHEMERAL_FLAG
+                }
+            });
+        }
+    }
+    let outcome = match pending.remove(id) {
+        Some(entry) => {
+            let _ = entry.reply.send(decision);
+            match decision {
+                ApprovalResponse::Yes => format!("✅ Approved by <@{user_id}>"),
+                ApprovalResponse::Always => {
+                    format!("✅ Approved for this session by <@{user_id}>")
+                }
+                ApprovalResponse::No => format!("❌ Denied by <@{user_id}>"),
+            }
+        }
+        None => "⌛ This approval request has expired.".to_string(),
+    };
+
+    json!({
+        "type": 7,
+        "data": { "content": format!("{original}\n\n{outcome}"), "components": [] }
+    })
+}
+
+struct DiscordApprovalPrompt {
+    client: reqwest::Client,
+    bot_token: String,
+    channel_id: String,
+    requester: String,
+    pending: PendingApprovals,
+    timeout: Duration,
+}
+
+#[async_trait]
+impl ApprovalPrompt for DiscordApprovalPrompt {
+    async fn ask(&self, request: &ApprovalRequest) -> ApprovalResponse {
+        let id = Uuid::new_v4().simple().to_string();
+        let (reply, answer) = oneshot::channel();
+        self.pending.lock().insert(
+            id.clone(),
+            PendingApproval {
+                requester: self.requester.clone(),
+                reply,
+            },
+        );
+
+        let sent = self
+            .client
+            .post(format!(
+                "{DISCORD_API}/channels/{}/messages",
+                self.channel_id
+            ))
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .json(&approval_message(&id, request))
+            .send()
+            .await;
+        match sent {
+            Ok(resp) if resp.status().is_success() => {}
+            Ok(resp) => {
+                tracing::warn!("Discord: approval prompt failed ({})", resp.status());
+                self.pending.lock().remove(&id);
+                return ApprovalResponse::No;
+            }
+            Err(e) => {
+                tracing::warn!("Discord: approval prompt failed: {e}");
+                self.pending.lock().remove(&id);
+                return ApprovalResponse::No;
+            }
+        }
+
+        let decision = tokio::time::timeout(self.timeout, answer).await;
+        self.pending.lock().remove(&id);
+        match decision {
+            Ok(Ok(decision)) => decision,
+            _ => ApprovalResponse::No,
+        }
+    }
 }
 
 const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
@@ -189,13 +676,16 @@ impl Channel for DiscordChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        let (channel_id, interaction_token) = parse_reply_target(&message.recipient);
         let chunks = split_message_for_discord(&message.content);
 
         for (i, chunk) in chunks.iter().enumerate() {
-            let url = format!(
-                "https://discord.com/api/v10/channels/{}/messages",
-                message.recipient
-            );
+            if let Some(token) = interaction_token {
+                self.send_interaction_reply(token, chunk).await?;
+                continue;
+            }
+
+            let url = format!("{DISCORD_API}/channels/{channel_id}/messages");
 
             let body = json!({ "content": chunk });
 
@@ -232,7 +722,7 @@ impl Channel for DiscordChannel {
         // Get Gateway URL
         let gw_resp: serde_json::Value = self
             .http_client()
-            .get("https://discord.com/api/v10/gateway/bot")
+            .get(format!("{DISCORD_API}/gateway/bot"))
             .header("Authorization", format!("Bot {}", self.bot_token))
             .send()
             .await?
@@ -347,10 +837,39 @@ impl Channel for DiscordChannel {
                         _ => {}
                     }
 
-                    // Only handle MESSAGE_CREATE (opcode 0, type "MESSAGE_CREATE")
                     let event_type = event.get("t").and_then(|t| t.as_str()).unwrap_or("");
-                    if event_type != "MESSAGE_CREATE" {
-                        continue;
+                    match event_type {
+                        "READY" => {
+                            let application_id = event
+                                .pointer("/d/application/id")
+                                .and_then(serde_json::Value::as_str)
+                                .map(str::to_string);
+                            if let Some(id) = application_id {
+                                *self.application_id.lock() = Some(id);
+                            }
+                            if self.slash_commands {
+                                if let Some(id) = self.application_id() {
+                                    match self.register_slash_commands(&id).await {
+                                        Ok(()) => tracing::info!("Discord: slash commands registered"),
+                                        Err(e) => tracing::warn!("Discord: {e}"),
+                                    }
+                                }
+                            }
+                            continue;
+                        }
+                        "INTERACTION_CREATE" => {
+                            let Some(d) = event.get("d") else {
+                                continue;
+                            };
+                            if let Some(channel_msg) = self.handle_interaction(d).await {
+                                if tx.send(channel_msg).await.is_err() {
+                                    break;
+                                }
+                            }
+                            continue;
+                        }
+                        "MESSAGE_CREATE" => {}
+                        _ => continue,
                     }
 
                     let Some(d) = event.get("d") else {
@@ -427,7 +946,7 @@ impl Channel for DiscordChannel {
 
     async fn health_check(&self) -> bool {
         self.http_client()
-            .get("https://discord.com/api/v10/users/@me")
+            .get(format!("{DISCORD_API}/users/@me"))
             .header("Authorization", format!("Bot {}", self.bot_token))
             .send()
             .await
@@ -440,10 +959,10 @@ impl Channel for DiscordChannel {
 
         let client = self.http_client();
         let token = self.bot_token.clone();
-        let channel_id = recipient.to_string();
+        let channel_id = parse_reply_target(recipient).0.to_string();
 
         let handle = tokio::spawn(async move {
-            let url = format!("https://discord.com/api/v10/channels/{channel_id}/typing");
+            let url = format!("{DISCORD_API}/channels/{channel_id}/typing");
             loop {
                 let _ = client
                     .post(&url)
@@ -467,6 +986,28 @@ impl Channel for DiscordChannel {
         }
         Ok(())
     }
+
+    fn approval_manager(&self, recipient: &str, sender: &str) -> Option<Arc<ApprovalManager>> {
+        let autonomy = self.autonomy.as_ref()?;
+        let channel_id = parse_reply_target(recipient).0;
+        let mut approvals = self.approvals.lock();
+        let key = format!("{channel_id}:{sender}");
+        let manager = approvals.entry(key).or_insert_with(|| {
+            Arc::new(
+                ApprovalManager::from_config(autonomy).with_remote_prompt(Arc::new(
+                    DiscordApprovalPrompt {
+                        client: self.http_client(),
+                        bot_token: self.bot_token.clone(),
+                        channel_id: channel_id.to_string(),
+                        requester: sender.to_string(),
+                        pending: Arc::clone(&self.pending_approvals),
+                        timeout: self.approval_timeout,
+                    },
+                )),
+            )
+        });
+        Some(Arc::clone(manager))
+    }
 }
 
 #[cfg(test)]
@@ -840,4 +1381,174 @@ mod tests {
         // Should have UUID dashes
         assert!(id.contains('-'));
     }
+
+    #[test]
+    fn reply_target_carries_interaction_token() {
+        assert_eq!(parse_reply_target("123"), ("123", None));
+        assert_eq!(
+            parse_reply_target("123:aW50ZXJhY3Rpb24"),
+            ("123", Some("aW50ZXJhY3Rpb24"))
+        );
+    }
+
+    #[test]
+    fn slash_command_definitions_cover_runtime_commands() {
+        let defs = slash_command_definitions(Some("32"));
+        let names: Vec<&str> = defs
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| d["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["model", "models", "reset", "memory", "cron"]);
+        assert_eq!(defs[0]["options"][0]["name"], "model");
+        assert_eq!(defs[0]["default_member_permissions"], "32");
+        assert!(defs[2]["options"].as_array().unwrap().is_empty());
+        assert!(slash_command_definitions(None)[0]["default_member_permissions"].is_null());
+    }
+
+    #[test]
+    fn command_interaction_becomes_text_command() {
+        let d = json!({
+            "type": 2,
+            "data": {
+                "name": "model",
+                "options": [{ "name": "model", "type": 3, "value": " gpt-4o " }]
+            }
+        });
+        assert_eq!(
+            parse_command_interaction(&d).as_deref(),
+            Some("/model gpt-4o")
+        );
+
+        let d = json!({ "type": 2, "data": { "name": "reset" } });
+        assert_eq!(parse_command_interaction(&d).as_deref(), Some("/reset"));
+
+        let d = json!({ "type": 2, "data": { "name": "ban" } });
+        assert!(parse_command_interaction(&d).is_none());
+    }
+
+    #[test]
+    fn interaction_user_prefers_guild_member() {
+        let guild = json!({ "member": { "user": { "id": "111" } } });
+        let dm = json!({ "user": { "id": "222" } });
+        assert_eq!(interaction_user_id(&guild), Some("111"));
+        assert_eq!(interaction_user_id(&dm), Some("222"));
+        assert_eq!(interaction_user_id(&json!({})), None);
+    }
+
+    #[test]
+    fn approval_custom_id_roundtrip() {
+        for decision in [
+            ApprovalResponse::Yes,
+            ApprovalResponse::Always,
+            ApprovalResponse::No,
+        ] {
+            let custom_id = approval_custom_id("abc123", decision);
+            assert_eq!(
+                parse_approval_custom_id(&custom_id),
+                Some(("abc123", decision))
+            );
+        }
+        assert!(parse_approval_custom_id("other:abc:yes").is_none());
+        assert!(parse_approval_custom_id("zeroclaw_approval:abc:maybe").is_none());
+    }
+
+    #[test]
+    fn approval_message_has_three_buttons() {
+        let request = ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: json!({ "command": "ls" }),
+        };
+        let msg = approval_message("abc", &request);
+        assert!(msg["content"].as_str().unwrap().contains("`shell`"));
+        let buttons = msg["components"][0]["components"].as_array().unwrap();
+        assert_eq!(buttons.len(), 3);
+        assert_eq!(buttons[0]["custom_id"], "zeroclaw_approval:abc:yes");
+    }
+
+    #[tokio::test]
+    async fn approval_click_resolves_pending_request() {
+        let pending: PendingApprovals = Arc::new(Mutex::new(HashMap::new()));
+        let (reply, answer) = oneshot::channel();
+        pending.lock().insert(
+            "abc".into(),
+            PendingApproval {
+                requester: "111".into(),
+                reply,
+            },
+        );
+
+        let d = json!({
+            "data": { "custom_id": "zeroclaw_approval:abc:always" },
+            "message": { "content": "🔧 Agent wants to run `shell`" }
+        });
+        let body = resolve_approval_click(&pending, &d, "111");
+
+        assert_eq!(answer.await.unwrap(), ApprovalResponse::Always);
+        assert_eq!(body["type"], 7);
+        assert!(body["data"]["content"]
+            .as_str()
+            .unwrap()
+            .contains("Approved for this session by <@111>"));
+        assert!(body["data"]["components"].as_array().unwrap().is_empty());
+
+        let again = resolve_approval_click(&pending, &d, "111");
+        assert!(again["data"]["content"]
+            .as_str()
+            .unwrap()
+            .contains("expired"));
+    }
+
+    #[tokio::test]
+    async fn approval_click_from_another_user_is_refused() {
+        let pending: PendingApprovals = Arc::new(Mutex::new(HashMap::new()));
+        let (reply, answer) = oneshot::channel();
+        pending.lock().insert(
+            "abc".into(),
+            PendingApproval {
+                requester: "111".into(),
+                reply,
+            },
+        );
+
+        let d = json!({
+            "data": { "custom_id": "zeroclaw_approval:abc:yes" },
+            "message": { "content": "🔧 Agent wants to run `shell`" }
+        });
+        let body = resolve_approval_click(&pending, &d, "222");
+        assert_eq!(body["type"], 4);
+        assert_eq!(body["data"]["flags"], EPHEMERAL_FLAG);
+        assert!(body["data"]["content"]
+            .as_str()
+            .unwrap()
+            .contains("Only <@111>"));
+        assert!(pending.lock().contains_key("abc"));
+
+        let body = resolve_approval_click(&pending, &d, "111");
+        assert_eq!(body["type"], 7);
+        assert_eq!(answer.await.unwrap(), ApprovalResponse::Yes);
+    }
+
+    #[test]
+    fn original_response_is_claimed_once() {
+        let ch = DiscordChannel::new("fake".into(), None, vec![], false, false);
+        assert!(ch.claim_original_response("tok"));
+        assert!(!ch.claim_original_response("tok"));
+        assert!(ch.claim_original_response("other"));
+    }
+
+    #[test]
+    fn approvals_require_autonomy_config() {
+        let ch = DiscordChannel::new("fake".into(), None, vec![], false, false);
+        assert!(ch.approval_manager("123", "111").is_none());
+
+        let ch = ch.with_approvals(&AutonomyConfig::default(), 30);
+        let first = ch.approval_manager("123", "111").unwrap();
+        let same_channel = ch.approval_manager("123:token", "111").unwrap();
+        assert!(Arc::ptr_eq(&first, &same_channel));
+        assert!(first.remote_prompt().is_some());
+        let other_user = ch.approval_manager("123", "222").unwrap();
+        assert!(!Arc::ptr_eq(&first, &other_user));
+    }
 }
@@ -14,14 +14,18 @@ use async_imap::types::Fetch;
 use async_imap::Session;
 use async_trait::async_trait;
 use futures::TryStreamExt;
-use lettre::message::SinglePart;
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, MultiPart, SinglePart};
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
 use mail_parser::{MessageParser, MimeHeaders};
 use rustls::{ClientConfig, RootCertStore};
 use rustls_pki_types::DnsName;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::net::TcpStream;
@@ -33,6 +37,14 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::webchat::sanitize_file_name;
+use crate::security::SecurityPolicy;
+
+/// Workspace-relative folder that receives inbound email attachments.
+const ATTACHMENT_DIR: &str = "uploads/email";
+
+/// Length of the thread token appended to reply targets (`addr#token`).
+const THREAD_TOKEN_LEN: usize = 12;
 
 /// Email channel configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,6 +78,21 @@ pub struct EmailConfig {
     /// Allowed sender addresses/domains (empty = deny all, ["*"] = allow all)
     #[serde(default)]
     pub allowed_senders: Vec<String>,
+    /// Send replies as multipart/alternative with the Markdown rendered to HTML
+    #[serde(default = "default_true")]
+    pub html_replies: bool,
+    /// Largest inbound attachment saved into the workspace (0 = never save)
+    #[serde(default = "default_max_attachment_bytes")]
+    pub max_attachment_bytes: usize,
+    /// Only accept mail whose `Authentication-Results` show DKIM, SPF or
+    /// DMARC passing for the sender's domain
+    #[serde(default)]
+    pub require_authentication: bool,
+    /// `Authentication-Results` authserv-ids to trust (your receiving mail
+    /// server); required by `require_authentication`, which rejects all mail
+    /// while this is empty
+    #[serde(default)]
+    pub trusted_authserv_ids: Vec<String>,
 }
 
 fn default_imap_port() -> u16 {
@@ -83,6 +110,9 @@ fn default_idle_timeout() -> u64 {
 fn default_true() -> bool {
     true
 }
+fn default_max_attachment_bytes() -> usize {
+    10 * 1024 * 1024
+}
 
 impl Default for EmailConfig {
     fn default() -> Self {
@@ -98,16 +128,32 @@ impl Default for EmailConfig {
             from_address: String::new(),
             idle_timeout_secs: default_idle_timeout(),
             allowed_senders: Vec::new(),
+            html_replies: true,
+            max_attachment_bytes: default_max_attachment_bytes(),
+            require_authentication: false,
+            trusted_authserv_ids: Vec::new(),
         }
     }
 }
 
 type ImapSession = Session<TlsStream<TcpStream>>;
 
+/// What a reply needs to land in the sender's existing thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EmailThread {
+    subject: String,
+    /// Message-ID of the latest inbound message (without angle brackets)
+    parent_message_id: String,
+    /// Full `References` chain, oldest first
+    references: Vec<String>,
+}
+
 /// Email channel — IMAP IDLE for instant push notifications, SMTP for outbound
 pub struct EmailChannel {
     pub config: EmailConfig,
     seen_messages: Arc<Mutex<HashSet<String>>>,
+    threads: Arc<Mutex<HashMap<String, EmailThread>>>,
+    workspace: Option<Arc<SecurityPolicy>>,
 }
 
 impl EmailChannel {
@@ -115,9 +161,18 @@ impl EmailChannel {
         Self {
             config,
             seen_messages: Arc::new(Mutex::new(HashSet::new())),
+            threads: Arc::new(Mutex::new(HashMap::new())),
+            workspace: None,
         }
     }
 
+    /// Save inbound attachments under, and resolve outbound attachment
+    /// markers against, the workspace of `security`.
+    pub fn with_workspace(mut self, security: Arc<SecurityPolicy>) -> Self {
+        self.workspace = Some(security);
+        self
+    }
+
     /// Check if a sender email is in the allowlist
     pub fn is_sender_allowed(&self, email: &str) -> bool {
         if self.config.allowed_senders.is_empty() {
@@ -172,7 +227,7 @@ impl EmailChannel {
             return text.to_string();
         }
         if let Some(html) = parsed.body_html(0) {
-            return Self::strip_html(html.as_ref());
+            return html_to_text(html.as_ref());
         }
         for part in parsed.attachments() {
             let part: &mail_parser::MessagePart = part;
@@ -253,6 +308,9 @@ impl EmailChannel {
                         .message_id()
                         .map(|s| s.to_string())
                         .unwrap_or_else(|| format!("gen-{}", Uuid::new_v4()));
+                    let thread = inbound_thread(&parsed, &subject, &msg_id);
+                    let auth_results = authentication_results(&parsed);
+                    let attachments = Self::extract_attachments(&parsed);
 
                     #[allow(clippy::cast_sign_loss)]
                     let ts = parsed
@@ -285,6 +343,9 @@ impl EmailChannel {
                         sender,
                         content,
                         timestamp: ts,
+                        thread,
+                        auth_results,
+                        attachments,
                     });
                 }
             }
@@ -426,6 +487,19 @@ impl EmailChannel {
                 warn!("Blocked email from {}", email.sender);
                 continue;
             }
+            if self.config.require_authentication
+                && !authentication_passes(
+                    &email.auth_results,
+                    &email.sender,
+                    &self.config.trusted_authserv_ids,
+                )
+            {
+                warn!(
+                    "Blocked email from {}: no passing DKIM/SPF/DMARC result",
+                    email.sender
+                );
+                continue;
+            }
 
             let is_new = {
                 let mut seen = self.seen_messages.lock().await;
@@ -435,11 +509,24 @@ impl EmailChannel {
                 continue;
             }
 
+            let token = thread_token(email.thread.references.first().unwrap_or(&email.msg_id));
+            self.threads
+                .lock()
+                .await
+                .insert(token.clone(), email.thread);
+
+            let mut content = email.content;
+            let notes = self.save_attachments(email.attachments).await;
+            if !notes.is_empty() {
+                content.push_str("\n\n");
+                content.push_str(&notes.join("\n"));
+            }
+
             let msg = ChannelMessage {
                 id: email.msg_id,
-                reply_target: email.sender.clone(),
+                reply_target: format!("{}#{token}", email.sender),
                 sender: email.sender,
-                content: email.content,
+                content,
                 channel: "email".to_string(),
                 timestamp: email.timestamp,
             };
@@ -453,6 +540,156 @@ impl EmailChannel {
         Ok(())
     }
 
+    /// Attachments worth handing to the agent (named parts with content).
+    fn extract_attachments(parsed: &mail_parser::Message) -> Vec<InboundAttachment> {
+        parsed
+            .attachments()
+            .filter_map(|part| {
+                let name = MimeHeaders::attachment_name(part)?;
+                let data = part.contents();
+                if data.is_empty() {
+                    return None;
+                }
+                Some(InboundAttachment {
+                    name: name.to_string(),
+                    data: data.to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// Write attachments into the workspace, returning the notes appended to
+    /// the message for the agent.
+    async fn save_attachments(&self, attachments: Vec<InboundAttachment>) -> Vec<String> {
+        let mut saved = Vec::new();
+        let mut notes = Vec::new();
+        for attachment in attachments {
+            let Some(workspace_dir) = self
+                .workspace
+                .as_ref()
+                .map(|security| &security.workspace_dir)
+                .filter(|_| attachment.data.len() <= self.config.max_attachment_bytes)
+            else {
+                notes.push(format!(
+                    "[Attachment not saved: {} ({} bytes)]",
+                    attachment.name,
+                    attachment.data.len()
+                ));
+                continue;
+            };
+
+            let relative = format!(
+                "{ATTACHMENT_DIR}/{}-{}",
+                &Uuid::new_v4().simple().to_string()[..8],
+                sanitize_file_name(&attachment.name)
+            );
+            let path = workspace_dir.join(&relative);
+            if let Some(parent) = path.parent() {
+                if let Err(e) = tokio::fs::create_dir_all(parent).await {
+                    warn!("Failed to create {}: {}", ATTACHMENT_DIR, e);
+                    continue;
+                }
+            }
+            match tokio::fs::write(&path, &attachment.data).await {
+                Ok(()) => saved.push(relative),
+                Err(e) => warn!("Failed to save email attachment {}: {}", relative, e),
+            }
+        }
+        if !saved.is_empty() {
+            notes.insert(0, format!("[Uploaded to workspace: {}]", saved.join(", ")));
+        }
+        notes
+    }
+
+    /// Resolve an outbound attachment marker to a file inside the workspace.
+    fn resolve_attachment(&self, target: &str) -> Option<PathBuf> {
+        resolve_outbound_attachment(self.workspace.as_deref()?, target)
+    }
+
+    /// Build the outgoing message, threaded under `thread` when known.
+    fn build_email(&self, message: &SendMessage, thread: Option<&EmailThread>) -> Result<Message> {
+        let (address, _) = split_reply_target(&message.recipient);
+
+        // Use explicit subject if provided, otherwise the thread's, then legacy parsing
+        let (subject, body) = if let Some(ref subj) = message.subject {
+            (subj.clone(), message.content.as_str())
+        } else if let Some(thread) = thread {
+            (reply_subject(&thread.subject), message.content.as_str())
+        } else if message.content.starts_with("Subject: ") {
+            if let Some(pos) = message.content.find('\n') {
+                (
+                    message.content[9..pos].to_string(),
+                    message.content[pos + 1..].trim(),
+                )
+            } else {
+                ("ZeroClaw Message".to_string(), message.content.as_str())
+            }
+        } else {
+            ("ZeroClaw Message".to_string(), message.content.as_str())
+        };
+
+        let (mut text, targets) = parse_attachment_markers(body);
+        let mut attachments = Vec::new();
+        for target in targets {
+            let Some(path) = self.resolve_attachment(&target) else {
+                warn!("Email attachment not found in workspace: {}", target);
+                let _ = write!(text, "\n\n[Attachment unavailable: {}]", target);
+                continue;
+            };
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "attachment".into());
+            let data = std::fs::read(&path)?;
+            let content_type = ContentType::parse(attachment_content_type(&name))
+                .unwrap_or(ContentType::TEXT_PLAIN);
+            attachments.push(Attachment::new(name).body(data, content_type));
+        }
+
+        let from_domain = self
+            .config
+            .from_address
+            .rsplit_once('@')
+            .map(|(_, domain)| domain.trim_end_matches('>'))
+            .unwrap_or("localhost");
+        let mut builder = Message::builder()
+            .from(self.config.from_address.parse()?)
+            .to(address.parse()?)
+            .subject(subject)
+            .message_id(Some(format!("<{}@{}>", Uuid::new_v4(), from_domain)));
+        if let Some(thread) = thread {
+            builder = builder
+                .in_reply_to(format!("<{}>", thread.parent_message_id))
+                .references(
+                    thread
+                        .references
+                        .iter()
+                        .map(|id| format!("<{id}>"))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                );
+        }
+
+        let html = self.config.html_replies.then(|| markdown_to_html(&text));
+        if attachments.is_empty() {
+            return Ok(match html {
+                Some(html) => builder.multipart(MultiPart::alternative_plain_html(text, html))?,
+                None => builder.singlepart(SinglePart::plain(text))?,
+            });
+        }
+
+        let mut mixed = match html {
+            Some(html) => {
+                MultiPart::mixed().multipart(MultiPart::alternative_plain_html(text, html))
+            }
+            None => MultiPart::mixed().singlepart(SinglePart::plain(text)),
+        };
+        for attachment in attachments {
+            mixed = mixed.singlepart(attachment);
+        }
+        Ok(builder.multipart(mixed)?)
+    }
+
     fn create_smtp_transport(&self) -> Result<SmtpTransport> {
         let creds = Credentials::new(self.config.username.clone(), self.config.password.clone());
         let transport = if self.config.smtp_tls {
@@ -477,6 +714,14 @@ struct ParsedEmail {
     sender: String,
     content: String,
     timestamp: u64,
+    thread: EmailThread,
+    auth_results: Vec<String>,
+    attachments: Vec<InboundAttachment>,
+}
+
+struct InboundAttachment {
+    name: String,
+    data: Vec<u8>,
 }
 
 /// Result from waiting on IDLE
@@ -486,6 +731,438 @@ enum IdleWaitResult {
     Interrupted,
 }
 
+/// Threading headers for a reply to `parsed` (RFC 5322 section 3.6.4): the
+/// reply answers this message and extends its `References` chain.
+fn inbound_thread(parsed: &mail_parser::Message, subject: &str, msg_id: &str) -> EmailThread {
+    let mut references: Vec<String> = parsed
+        .references()
+        .as_text_list()
+        .map(|ids| ids.iter().map(|id| id.to_string()).collect())
+        .unwrap_or_default();
+    if references.is_empty() {
+        if let Some(parent) = parsed.in_reply_to().as_text() {
+            references.push(parent.to_string());
+        }
+    }
+    if !references.iter().any(|id| id == msg_id) {
+        references.push(msg_id.to_string());
+    }
+    EmailThread {
+        subject: subject.to_string(),
+        parent_message_id: msg_id.to_string(),
+        references,
+    }
+}
+
+/// Short stable token for the thread rooted at `root_id`.
+fn thread_token(root_id: &str) -> String {
+    hex::encode(Sha256::digest(root_id.as_bytes()))[..THREAD_TOKEN_LEN].to_string()
+}
+
+/// Split `addr#token` into the address and thread token. Plain addresses
+/// (e.g. cron delivery targets) have no token.
+fn split_reply_target(target: &str) -> (&str, Option<&str>) {
+    match target.rsplit_once('#') {
+        Some((address, token))
+            if token.len() == THREAD_TOKEN_LEN && token.bytes().all(|b| b.is_ascii_hexdigit()) =>
+        {
+            (address, Some(token))
+        }
+        _ => (target, None),
+    }
+}
+
+fn reply_subject(subject: &str) -> String {
+    let trimmed = subject.trim();
+    if trimmed.len() >= 3 && trimmed[..3].eq_ignore_ascii_case("re:") {
+        trimmed.to_string()
+    } else {
+        format!("Re: {trimmed}")
+    }
+}
+
+fn authentication_results(parsed: &mail_parser::Message) -> Vec<String> {
+    parsed
+        .header_values("Authentication-Results")
+        .filter_map(|value| value.as_text())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether the topmost trusted `Authentication-Results` header (RFC 8601)
+/// records a DKIM, SPF or DMARC pass for the sender's domain or a parent
+/// domain.
+///
+/// Only the first header from a trusted authserv-id counts: that is the one
+/// the receiving server prepended, while any further copies below it came
+/// with the message and may be forged. Fails closed without trusted ids.
+fn authentication_passes(
+    results: &[String],
+    sender: &str,
+    trusted_authserv_ids: &[String],
+) -> bool {
+    let Some((_, domain)) = sender.rsplit_once('@') else {
+        return false;
+    };
+    let domain = domain.to_ascii_lowercase();
+
+    let trusted = results.iter().find_map(|header| {
+        let mut sections = header.split(';');
+        let authserv_id = sections
+            .next()
+            .and_then(|id| id.split_whitespace().next())
+            .unwrap_or("");
+        trusted_authserv_ids
+            .iter()
+            .any(|trusted| trusted.eq_ignore_ascii_case(authserv_id))
+            .then_some(sections)
+    });
+    trusted.is_some_and(|mut sections| sections.any(|result| result_passes_for(result, &domain)))
+}
+
+fn result_passes_for(result: &str, domain: &str) -> bool {
+    let mut tokens = result.split_whitespace();
+    let Some((method, verdict)) = tokens.next().and_then(|t| t.split_once('=')) else {
+        return false;
+    };
+    if !verdict.eq_ignore_ascii_case("pass") {
+        return false;
+    }
+    let properties: &[&str] = match method.to_ascii_lowercase().as_str() {
+        "dkim" => &["header.d", "header.i"],
+        "spf" => &["smtp.mailfrom"],
+        "dmarc" => &["header.from"],
+        _ => return false,
+    };
+    tokens
+        .filter_map(|token| token.split_once('='))
+        .any(|(property, value)| {
+            properties.contains(&property.to_ascii_lowercase().as_str())
+                && domain_aligned(value, domain)
+        })
+}
+
+/// Relaxed alignment: the authenticated domain equals the sender's domain
+/// or is one of its parents.
+fn domain_aligned(value: &str, sender_domain: &str) -> bool {
+    let value = value.trim_matches(|c| c == '"' || c == ';');
+    let authenticated = value
+        .rsplit_once('@')
+        .map_or(value, |(_, domain)| domain)
+        .to_ascii_lowercase();
+    !authenticated.is_empty()
+        && (sender_domain == authenticated || sender_domain.ends_with(&format!(".{authenticated}")))
+}
+
+/// Convert an HTML body to text, keeping paragraph and line breaks and
+/// dropping `<style>`/`<script>` content.
+fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(open) = rest.find('<') {
+        text.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[open + 1..open + close];
+        rest = &rest[open + close + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match name.as_str() {
+            "style" | "script" if !closing => {
+                let end = format!("</{name}");
+                let lower = rest.to_ascii_lowercase();
+                rest = lower.find(&end).map_or("", |pos| &rest[pos..]);
+            }
+            "li" if !closing => text.push_str("\n- "),
+            "br" | "p" | "div" | "tr" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "ul" | "ol"
+            | "blockquote" | "table" => text.push('\n'),
+            _ => {}
+        }
+    }
+    text.push_str(rest);
+
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() && lines.last().map_or(true, |last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Render the Markdown agents typically write (headings, lists, quotes,
+/// fenced code, emphasis, inline code and links) as an HTML document.
+fn markdown_to_html(markdown: &str) -> String {
+    let mut html = String::from("<html><body>\n");
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut list: Option<&'static str> = None;
+    let mut in_code = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            if in_code {
+                html.push_str("</code></pre>\n");
+            } else {
+                flush_paragraph(&mut html, &mut paragraph);
+                close_list(&mut html, &mut list);
+                html.push_str("<pre><code>");
+            }
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            html.push_str(&escape_html(line));
+            html.push('\n');
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_list(&mut html, &mut list);
+            continue;
+        }
+
+        let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_list(&mut html, &mut list);
+            let _ = writeln!(
+                html,
+                "<h{hashes}>{}</h{hashes}>",
+                render_inline(trimmed[hashes..].trim())
+            );
+            continue;
+        }
+
+        let item = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .map(|item| ("ul", item))
+            .or_else(|| {
+                let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+                (digits > 0)
+                    .then(|| trimmed[digits..].strip_prefix(". "))
+                    .flatten()
+                    .map(|item| ("ol", item))
+            });
+        if let Some((tag, item)) = item {
+            flush_paragraph(&mut html, &mut paragraph);
+            if list != Some(tag) {
+                close_list(&mut html, &mut list);
+                let _ = writeln!(html, "<{tag}>");
+                list = Some(tag);
+            }
+            let _ = writeln!(html, "<li>{}</li>", render_inline(item.trim()));
+            continue;
+        }
+
+        if let Some(quote) = trimmed.strip_prefix('>') {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_list(&mut html, &mut list);
+            let _ = writeln!(
+                html,
+                "<blockquote>{}</blockquote>",
+                render_inline(quote.trim())
+            );
+            continue;
+        }
+
+        close_list(&mut html, &mut list);
+        paragraph.push(render_inline(trimmed));
+    }
+
+    if in_code {
+        html.push_str("</code></pre>\n");
+    }
+    flush_paragraph(&mut html, &mut paragraph);
+    close_list(&mut html, &mut list);
+    html.push_str("</body></html>\n");
+    html
+}
+
+fn flush_paragraph(html: &mut String, paragraph: &mut Vec<String>) {
+    if !paragraph.is_empty() {
+        let _ = writeln!(html, "<p>{}</p>", paragraph.join("<br>\n"));
+        paragraph.clear();
+    }
+}
+
+fn close_list(html: &mut String, list: &mut Option<&'static str>) {
+    if let Some(tag) = list.take() {
+        let _ = writeln!(html, "</{tag}>");
+    }
+}
+
+fn render_inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '`' {
+            if let Some(end) = rest[1..].find('`') {
+                let _ = write!(out, "<code>{}</code>", escape_html(&rest[1..=end]));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+        if rest.starts_with("**") {
+            if let Some(end) = rest[2..].find("**").filter(|end| *end > 0) {
+                let _ = write!(out, "<strong>{}</strong>", render_inline(&rest[2..2 + end]));
+                rest = &rest[end + 4..];
+                continue;
+            }
+        }
+        if c == '*' && !rest[1..].starts_with(' ') {
+            if let Some(end) = rest[1..].find('*').filter(|end| *end > 0) {
+                let _ = write!(out, "<em>{}</em>", render_inline(&rest[1..=end]));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+        if c == '[' {
+            if let Some(link) = parse_link(rest) {
+                let (label, url, consumed) = link;
+                let _ = write!(
+                    out,
+                    "<a href=\"{}\">{}</a>",
+                    escape_html(url),
+                    render_inline(label)
+                );
+                rest = &rest[consumed..];
+                continue;
+            }
+        }
+        out.push_str(&escape_html(&rest[..c.len_utf8()]));
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// Parse `[label](url)` at the start of `text`, returning the label, the URL
+/// and the bytes consumed. Only web and mail links are rendered.
+fn parse_link(text: &str) -> Option<(&str, &str, usize)> {
+    let label_end = text.find("](")?;
+    let label = &text[1..label_end];
+    if label.contains('[') || label.contains(']') {
+        return None;
+    }
+    let url_start = label_end + 2;
+    let url_end = url_start + text[url_start..].find(')')?;
+    let url = &text[url_start..url_end];
+    let safe = ["http://", "https://", "mailto:"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme));
+    safe.then_some((label, url, url_end + 1))
+}
+
+/// Pull `[FILE:path]`-style markers out of a reply, returning the remaining
+/// text and the marker targets in order.
+pub(super) fn parse_attachment_markers(message: &str) -> (String, Vec<String>) {
+    let mut cleaned = String::with_capacity(message.len());
+    let mut targets = Vec::new();
+    let mut rest = message;
+
+    while let Some(open) = rest.find('[') {
+        cleaned.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find(']') else {
+            cleaned.push_str(&rest[open..]);
+            rest = "";
+            break;
+        };
+        let marker = &rest[open + 1..open + close];
+        let target = marker.split_once(':').and_then(|(kind, target)| {
+            let known = matches!(
+                kind.trim().to_ascii_uppercase().as_str(),
+                "FILE" | "ATTACHMENT" | "DOCUMENT" | "IMAGE" | "PHOTO" | "VIDEO" | "AUDIO"
+            );
+            let target = target.trim();
+            (known && !target.is_empty()).then(|| target.to_string())
+        });
+        match target {
+            Some(target) => targets.push(target),
+            None => cleaned.push_str(&rest[open..=open + close]),
+        }
+        rest = &rest[open + close + 1..];
+    }
+    cleaned.push_str(rest);
+
+    (cleaned.trim().to_string(), targets)
+}
+
+/// Resolve an outbound `[FILE:...]` target to a file the agent may send:
+/// inside the workspace once symlinks are followed and not under
+/// `forbidden_paths`.
+pub(super) fn resolve_outbound_attachment(
+    security: &SecurityPolicy,
+    target: &str,
+) -> Option<PathBuf> {
+    let workspace = security.workspace_dir.canonicalize().ok()?;
+    let candidate = Path::new(target);
+    let path = if candidate.is_absolute() {
+        candidate.to_path_buf()
+    } else {
+        workspace.join(candidate)
+    };
+    let path = path.canonicalize().ok()?;
+    (security.is_resolved_path_allowed(&path)
+        && !security.is_forbidden_in_workspace(&path)
+        && path.is_file())
+    .then_some(path)
+}
+
+pub(super) fn attachment_content_type(name: &str) -> &'static str {
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "json" => "application/json",
+        "zip" => "application/zip",
+        "mp3" => "audio/mpeg",
+        "mp4" => "video/mp4",
+        _ => "application/octet-stream",
+    }
+}
+
 #[async_trait]
 impl Channel for EmailChannel {
     fn name(&self) -> &str {
@@ -493,28 +1170,16 @@ impl Channel for EmailChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> Result<()> {
-        // Use explicit subject if provided, otherwise fall back to legacy parsing or default
-        let (subject, body) = if let Some(ref subj) = message.subject {
-            (subj.as_str(), message.content.as_str())
-        } else if message.content.starts_with("Subject: ") {
-            if let Some(pos) = message.content.find('\n') {
-                (&message.content[9..pos], message.content[pos + 1..].trim())
-            } else {
-                ("ZeroClaw Message", message.content.as_str())
-            }
-        } else {
-            ("ZeroClaw Message", message.content.as_str())
+        let (address, token) = split_reply_target(&message.recipient);
+        let thread = match token {
+            Some(token) => self.threads.lock().await.get(token).cloned(),
+            None => None,
         };
 
-        let email = Message::builder()
-            .from(self.config.from_address.parse()?)
-            .to(message.recipient.parse()?)
-            .subject(subject)
-            .singlepart(SinglePart::plain(body.to_string()))?;
-
+        let email = self.build_email(message, thread.as_ref())?;
         let transport = self.create_smtp_transport()?;
         transport.send(&email)?;
-        info!("Email sent to {}", message.recipient);
+        info!("Email sent to {}", address);
         Ok(())
     }
 
@@ -523,6 +1188,11 @@ impl Channel for EmailChannel {
             "Starting email channel with IDLE support on {}",
             self.config.imap_folder
         );
+        if self.config.require_authentication && self.config.trusted_authserv_ids.is_empty() {
+            warn!(
+                "Email require_authentication is set without trusted_authserv_ids; all mail will be rejected"
+            );
+        }
         self.listen_with_idle(tx).await
     }
 
@@ -617,6 +1287,10 @@ mod tests {
             from_address: "bot@example.com".to_string(),
             idle_timeout_secs: 1200,
             allowed_senders: vec!["allowed@example.com".to_string()],
+            html_replies: true,
+            max_attachment_bytes: 1024,
+            require_authentication: false,
+            trusted_authserv_ids: vec![],
         };
         assert_eq!(config.imap_host, "imap.example.com");
         assert_eq!(config.imap_folder, "Archive");
@@ -637,6 +1311,10 @@ mod tests {
             from_address: "bot@test.com".to_string(),
             idle_timeout_secs: 1740,
             allowed_senders: vec!["*".to_string()],
+            html_replies: true,
+            max_attachment_bytes: 1024,
+            require_authentication: false,
+            trusted_authserv_ids: vec![],
         };
         let cloned = config.clone();
         assert_eq!(cloned.imap_host, config.imap_host);
@@ -882,6 +1560,10 @@ mod tests {
             from_address: "bot@example.com".to_string(),
             idle_timeout_secs: 1740,
             allowed_senders: vec!["allowed@example.com".to_string()],
+            html_replies: true,
+            max_attachment_bytes: 1024,
+            require_authentication: false,
+            trusted_authserv_ids: vec![],
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -907,6 +1589,9 @@ mod tests {
         assert_eq!(config.smtp_port, 465); // default
         assert!(config.smtp_tls); // default
         assert_eq!(config.idle_timeout_secs, 1740); // default
+        assert!(config.html_replies); // default
+        assert_eq!(config.max_attachment_bytes, 10 * 1024 * 1024); // default
+        assert!(!config.require_authentication); // default
     }
 
     #[test]
@@ -956,4 +1641,290 @@ mod tests {
         let debug_str = format!("{:?}", config);
         assert!(debug_str.contains("imap.debug.com"));
     }
+
+    // Threading, rendering and authentication tests
+
+    const THREADED_REPLY: &str = "From: Alice <alice@example.com>\r\n\
+To: bot@example.com\r\n\
+Subject: Re: Weekly report\r\n\
+Message-ID: <msg-3@example.com>\r\n\
+In-Reply-To: <msg-2@bot.example.com>\r\n\
+References: <msg-1@example.com> <msg-2@bot.example.com>\r\n\
+Authentication-Results: mx.example.net; dkim=pass (2048-bit key) header.d=example.com; spf=fail smtp.mailfrom=alice@example.com\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+Looks good.\r\n";
+
+    #[test]
+    fn inbound_thread_extends_references() {
+        let parsed = MessageParser::default()
+            .parse(THREADED_REPLY.as_bytes())
+            .unwrap();
+        let thread = inbound_thread(&parsed, "Re: Weekly report", "msg-3@example.com");
+        assert_eq!(thread.parent_message_id, "msg-3@example.com");
+        assert_eq!(
+            thread.references,
+            vec![
+                "msg-1@example.com",
+                "msg-2@bot.example.com",
+                "msg-3@example.com"
+            ]
+        );
+
+        let results = authentication_results(&parsed);
+        assert_eq!(results.len(), 1);
+        let trusted = vec!["mx.example.net".to_string()];
+        assert!(authentication_passes(
+            &results,
+            "alice@example.com",
+            &trusted
+        ));
+    }
+
+    #[test]
+    fn inbound_thread_starts_with_own_id() {
+        let raw =
+            "From: a@example.com\r\nSubject: Hi\r\nMessage-ID: <root@example.com>\r\n\r\nhello\r\n";
+        let parsed = MessageParser::default().parse(raw.as_bytes()).unwrap();
+        let thread = inbound_thread(&parsed, "Hi", "root@example.com");
+        assert_eq!(thread.references, vec!["root@example.com"]);
+    }
+
+    #[test]
+    fn reply_target_carries_thread_token() {
+        let token = thread_token("root@example.com");
+        assert_eq!(token.len(), THREAD_TOKEN_LEN);
+        assert_eq!(token, thread_token("root@example.com"));
+
+        let target = format!("alice@example.com#{token}");
+        assert_eq!(
+            split_reply_target(&target),
+            ("alice@example.com", Some(token.as_str()))
+        );
+        assert_eq!(
+            split_reply_target("alice@example.com"),
+            ("alice@example.com", None)
+        );
+        assert_eq!(split_reply_target("odd#name@example.com").1, None);
+    }
+
+    #[test]
+    fn reply_subject_adds_single_prefix() {
+        assert_eq!(reply_subject("Weekly report"), "Re: Weekly report");
+        assert_eq!(reply_subject("RE: Weekly report"), "RE: Weekly report");
+    }
+
+    #[test]
+    fn authentication_requires_aligned_pass() {
+        let trusted = vec!["mx.example.net".to_string()];
+        let results = vec![
+            "mx.example.net; dkim=pass header.d=mail.example.com; spf=pass smtp.mailfrom=bounce@other.org"
+                .to_string(),
+        ];
+        assert!(!authentication_passes(
+            &results,
+            "alice@example.com",
+            &trusted
+        ));
+        assert!(authentication_passes(
+            &results,
+            "alice@mail.example.com",
+            &trusted
+        ));
+        assert!(authentication_passes(&results, "bob@other.org", &trusted));
+
+        let dmarc = vec!["mx.example.net; dmarc=pass header.from=example.com".to_string()];
+        assert!(authentication_passes(&dmarc, "alice@example.com", &trusted));
+        let failed = vec!["mx.example.net; dkim=fail header.d=example.com".to_string()];
+        assert!(!authentication_passes(
+            &failed,
+            "alice@example.com",
+            &trusted
+        ));
+    }
+
+    #[test]
+    fn authentication_ignores_untrusted_servers() {
+        let results = vec!["evil.example; dkim=pass header.d=example.com".to_string()];
+        let trusted = vec!["mx.example.net".to_string()];
+        assert!(!authentication_passes(
+            &results,
+            "alice@example.com",
+            &trusted
+        ));
+        // Without trusted ids nothing is trusted.
+        assert!(!authentication_passes(&results, "alice@example.com", &[]));
+    }
+
+    #[test]
+    fn authentication_ignores_forged_headers_below_the_trusted_one() {
+        // Our MX prepends its verdict; the sender pre-seeded a passing copy
+        // claiming the same authserv-id further down.
+        let raw = "Authentication-Results: mx.example.net; dkim=fail header.d=example.com; spf=fail smtp.mailfrom=alice@example.com\r\n\
+Authentication-Results: mx.example.net; dkim=pass header.d=example.com\r\n\
+From: alice@example.com\r\n\
+Subject: Forged\r\n\
+Message-ID: <forged@example.com>\r\n\
+\r\n\
+hello\r\n";
+        let parsed = MessageParser::default().parse(raw.as_bytes()).unwrap();
+        let results = authentication_results(&parsed);
+        assert_eq!(results.len(), 2);
+        let trusted = vec!["mx.example.net".to_string()];
+        assert!(!authentication_passes(
+            &results,
+            "alice@example.com",
+            &trusted
+        ));
+    }
+
+    #[test]
+    fn html_to_text_keeps_structure() {
+        let html = "<html><head><style>p { color: red; }</style></head><body>\
+            <p>Hello&nbsp;<b>there</b></p><p>Line one<br>Line two</p>\
+            <ul><li>first</li><li>second &amp; last</li></ul></body></html>";
+        assert_eq!(
+            html_to_text(html),
+            "Hello there\n\nLine one\nLine two\n\n- first\n- second & last"
+        );
+    }
+
+    #[test]
+    fn markdown_to_html_renders_common_blocks() {
+        let html = markdown_to_html(
+            "# Report\n\nAll **good** and *fine*, see `x < y` and [docs](https://example.com).\n\n- one\n- two\n\n1. first\n\n```\nlet a = <b>;\n```\n> quoted",
+        );
+        assert!(html.contains("<h1>Report</h1>"));
+        assert!(html.contains("<strong>good</strong>"));
+        assert!(html.contains("<em>fine</em>"));
+        assert!(html.contains("<code>x &lt; y</code>"));
+        assert!(html.contains("<a href=\"https://example.com\">docs</a>"));
+        assert!(html.contains("<ul>\n<li>one</li>\n<li>two</li>\n</ul>"));
+        assert!(html.contains("<ol>\n<li>first</li>\n</ol>"));
+        assert!(html.contains("<pre><code>let a = &lt;b&gt;;\n</code></pre>"));
+        assert!(html.contains("<blockquote>quoted</blockquote>"));
+    }
+
+    #[test]
+    fn markdown_to_html_escapes_and_rejects_unsafe_links() {
+        let html = markdown_to_html("<script>alert(1)</script> [x](javascript:alert(1))");
+        assert!(!html.contains("<script>"));
+        assert!(!html.contains("href"));
+    }
+
+    #[test]
+    fn attachment_markers_are_extracted() {
+        let (text, targets) =
+            parse_attachment_markers("Here you go [FILE:reports/q3.pdf] [note] [IMAGE: chart.png]");
+        assert_eq!(text, "Here you go  [note]");
+        assert_eq!(targets, vec!["reports/q3.pdf", "chart.png"]);
+        assert_eq!(attachment_content_type("q3.PDF"), "application/pdf");
+        assert_eq!(attachment_content_type("blob"), "application/octet-stream");
+    }
+
+    fn threaded_channel(workspace: &Path) -> EmailChannel {
+        EmailChannel::new(EmailConfig {
+            from_address: "bot@example.com".into(),
+            ..Default::default()
+        })
+        .with_workspace(Arc::new(SecurityPolicy {
+            workspace_dir: workspace.to_path_buf(),
+            forbidden_paths: vec!["secrets".into()],
+            ..SecurityPolicy::default()
+        }))
+    }
+
+    #[test]
+    fn build_email_threads_and_renders_html() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        std::fs::write(workspace.path().join("q3.csv"), "a,b\n1,2\n").unwrap();
+        let channel = threaded_channel(workspace.path());
+        let thread = EmailThread {
+            subject: "Weekly report".into(),
+            parent_message_id: "msg-3@example.com".into(),
+            references: vec!["msg-1@example.com".into(), "msg-3@example.com".into()],
+        };
+
+        let email = channel
+            .build_email(
+                &SendMessage::new("**Done** [FILE:q3.csv]", "alice@example.com#0123456789ab"),
+                Some(&thread),
+            )
+            .unwrap();
+        let raw = String::from_utf8(email.formatted()).unwrap();
+
+        assert!(raw.contains("Subject: Re: Weekly report"));
+        assert!(raw.contains("In-Reply-To: <msg-3@example.com>"));
+        assert!(raw.contains("References: <msg-1@example.com> <msg-3@example.com>"));
+        assert!(raw.contains("To: alice@example.com"));
+        assert!(raw.contains("multipart/alternative"));
+        assert!(raw.contains("<strong>Done</strong>"));
+        assert!(raw.contains("filename=\"q3.csv\""));
+    }
+
+    #[test]
+    fn build_email_refuses_files_outside_workspace() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        let channel = threaded_channel(workspace.path());
+
+        let email = channel
+            .build_email(
+                &SendMessage::new("see [FILE:/etc/hostname]", "alice@example.com"),
+                None,
+            )
+            .unwrap();
+        let raw = String::from_utf8(email.formatted()).unwrap();
+
+        assert!(raw.contains("Attachment unavailable: /etc/hostname"));
+        assert!(!raw.contains("multipart/mixed"));
+    }
+
+    #[test]
+    fn build_email_refuses_forbidden_workspace_files() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(workspace.path().join("secrets")).unwrap();
+        std::fs::write(workspace.path().join("secrets/key.txt"), "hunter2").unwrap();
+        let channel = threaded_channel(workspace.path());
+
+        let email = channel
+            .build_email(
+                &SendMessage::new("see [FILE:secrets/key.txt]", "alice@example.com"),
+                None,
+            )
+            .unwrap();
+        let raw = String::from_utf8(email.formatted()).unwrap();
+
+        assert!(raw.contains("Attachment unavailable: secrets/key.txt"));
+        assert!(!raw.contains("hunter2"));
+        assert!(!raw.contains("multipart/mixed"));
+    }
+
+    #[tokio::test]
+    async fn save_attachments_writes_into_workspace() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        let mut channel = threaded_channel(workspace.path());
+        channel.config.max_attachment_bytes = 8;
+
+        let notes = channel
+            .save_attachments(vec![
+                InboundAttachment {
+                    name: "../notes.txt".into(),
+                    data: b"hi".to_vec(),
+                },
+                InboundAttachment {
+                    name: "big.bin".into(),
+                    data: vec![0; 64],
+                },
+            ])
+            .await;
+
+        assert_eq!(notes.len(), 2);
+        let saved = notes[0]
+            .strip_prefix("[Uploaded to workspace: ")
+            .and_then(|rest| rest.strip_suffix(']'))
+            .unwrap();
+        assert!(saved.starts_with("uploads/email/") && saved.ends_with("-notes.txt"));
+        assert_eq!(std::fs::read(workspace.path().join(saved)).unwrap(), b"hi");
+        assert_eq!(notes[1], "[Attachment not saved: big.bin (64 bytes)]");
+    }
 }
@@ -1,23 +1,72 @@
+use super::email_channel::{
+    attachment_content_type, parse_attachment_markers, resolve_outbound_attachment,
+};
+use super::webchat::sanitize_file_name;
+use crate::approval::{
+    summarize_args, ApprovalManager, ApprovalPrompt, ApprovalRequest, ApprovalResponse,
+};
 use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::{AutonomyConfig, MatrixConfig, StreamMode};
+use crate::security::SecurityPolicy;
+use crate::util::truncate_with_ellipsis;
 use async_trait::async_trait;
 use matrix_sdk::{
+    attachment::AttachmentConfig,
     authentication::matrix::MatrixSession,
     config::SyncSettings,
+    media::MediaEventContent,
+    room::reply::{EnforceThread, Reply},
     ruma::{
-        events::room::message::{
-            MessageType, OriginalSyncRoomMessageEvent, RoomMessageEventContent,
+        events::{
+            reaction::{OriginalSyncReactionEvent, ReactionEventContent},
+            relation::{Annotation, Thread},
+            room::message::{
+                MessageType, OriginalSyncRoomMessageEvent, Relation, ReplacementMetadata,
+                ReplyWithinThread, RoomMessageEventContent,
+            },
         },
-        OwnedRoomId, OwnedUserId,
+        OwnedEventId, OwnedRoomId, OwnedUserId,
     },
     Client as MatrixSdkClient, LoopCtrl, Room, RoomState, SessionMeta, SessionTokens,
 };
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex, OnceCell, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot, Mutex, OnceCell, RwLock};
+use uuid::Uuid;
+
+/// Separates the room ID from the thread root in reply targets
+/// (`!room:server|$root`). Neither room nor event IDs contain it.
+const THREAD_SEPARATOR: char = '|';
+
+/// Workspace directory for inbound media.
+const MEDIA_DIR: &str = "uploads/matrix";
+
+/// Typing notices expire after a few seconds, so they are re-sent while the
+/// agent is still working.
+const TYPING_REFRESH_INTERVAL: Duration = Duration::from_secs(3);
+
+const APPROVE_REACTION: &str = "✅";
+const ALWAYS_REACTION: &str = "🔁";
+const DENY_REACTION: &str = "❌";
+
+/// An approval prompt waiting for a reaction from `requester`.
+struct PendingApproval {
+    requester: String,
+    reply: oneshot::Sender<ApprovalResponse>,
+}
+
+type PendingApprovals = Arc<parking_lot::Mutex<HashMap<String, PendingApproval>>>;
 
 /// Matrix channel for Matrix Client-Server API.
 /// Uses matrix-sdk for reliable sync and encrypted-room decryption.
+///
+/// Reply targets are the room ID, or `room_id|thread_root` for messages
+/// that belong to an `m.thread`, so each thread keeps its own history.
 #[derive(Clone)]
 pub struct MatrixChannel {
     homeserver: String,
@@ -29,6 +78,19 @@ pub struct MatrixChannel {
     resolved_room_id_cache: Arc<RwLock<Option<String>>>,
     sdk_client: Arc<OnceCell<MatrixSdkClient>>,
     http_client: Client,
+    /// When true, replies to top-level messages start a thread on them.
+    thread_replies: bool,
+    stream_mode: StreamMode,
+    draft_update_interval_ms: u64,
+    last_draft_edit: Arc<parking_lot::Mutex<HashMap<String, Instant>>>,
+    typing_handles: Arc<parking_lot::Mutex<HashMap<String, tokio::task::JoinHandle<()>>>>,
+    ack_reaction: Option<String>,
+    workspace: Option<Arc<SecurityPolicy>>,
+    max_media_bytes: usize,
+    autonomy: Option<AutonomyConfig>,
+    approval_timeout: Duration,
+    approvals: Arc<parking_lot::Mutex<HashMap<String, Arc<ApprovalManager>>>>,
+    pending_approvals: PendingApprovals,
 }
 
 #[derive(Debug, Deserialize)]
@@ -130,9 +192,46 @@ impl MatrixChannel {
             resolved_room_id_cache: Arc::new(RwLock::new(None)),
             sdk_client: Arc::new(OnceCell::new()),
             http_client: Client::new(),
+            thread_replies: false,
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            last_draft_edit: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            typing_handles: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            ack_reaction: None,
+            workspace: None,
+            max_media_bytes: 0,
+            autonomy: None,
+            approval_timeout: Duration::from_secs(120),
+            approvals: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            pending_approvals: Arc::new(parking_lot::Mutex::new(HashMap::new())),
         }
     }
 
+    /// Apply threading, streaming, reaction and media settings.
+    pub fn with_config(mut self, config: &MatrixConfig) -> Self {
+        self.thread_replies = config.thread_replies;
+        self.stream_mode = config.stream_mode;
+        self.draft_update_interval_ms = config.draft_update_interval_ms;
+        self.ack_reaction = Some(config.ack_reaction.trim().to_string()).filter(|r| !r.is_empty());
+        self.max_media_bytes = config.max_media_bytes;
+        self
+    }
+
+    /// Save inbound media under `uploads/matrix/` and allow replies to attach
+    /// workspace files with `[FILE:path]` markers. Files under the policy's
+    /// `forbidden_paths` are never attached.
+    pub fn with_workspace(mut self, security: Arc<SecurityPolicy>) -> Self {
+        self.workspace = Some(security);
+        self
+    }
+
+    /// Ask for tool approvals in the conversation, answered with reactions.
+    pub fn with_approvals(mut self, autonomy: &AutonomyConfig, timeout_secs: u64) -> Self {
+        self.autonomy = Some(autonomy.clone());
+        self.approval_timeout = Duration::from_secs(timeout_secs.max(1));
+        self
+    }
+
     fn encode_path_segment(value: &str) -> String {
         fn should_encode(byte: u8) -> bool {
             !matches!(
@@ -171,7 +270,10 @@ impl MatrixChannel {
     }
 
     fn is_supported_message_type(msgtype: &str) -> bool {
-        matches!(msgtype, "m.text" | "m.notice")
+        matches!(
+            msgtype,
+            "m.text" | "m.notice" | "m.image" | "m.file" | "m.audio" | "m.video"
+        )
     }
 
     fn has_non_empty_body(body: &str) -> bool {
@@ -438,17 +540,16 @@ impl MatrixChannel {
         })
         .to_string()
     }
-}
-
-#[async_trait]
-impl Channel for MatrixChannel {
-    fn name(&self) -> &str {
-        "matrix"
-    }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    /// Look up a joined room, syncing once if the client has not seen it yet.
+    /// Targets that are not room IDs fall back to the configured room.
+    async fn joined_room(&self, room_id: &str) -> anyhow::Result<Room> {
         let client = self.matrix_client().await?;
-        let target_room_id = self.target_room_id().await?;
+        let target_room_id = if room_id.starts_with('!') {
+            room_id.to_string()
+        } else {
+            self.target_room_id().await?
+        };
         let target_room: OwnedRoomId = target_room_id.parse()?;
 
         let mut room = client.get_room(&target_room);
@@ -465,12 +566,351 @@ impl Channel for MatrixChannel {
             anyhow::bail!("Matrix room '{}' is not in joined state", target_room_id);
         }
 
-        room.send(RoomMessageEventContent::text_plain(&message.content))
-            .await?;
+        Ok(room)
+    }
+
+    /// Text handed to the agent for an inbound message. Media is downloaded
+    /// (and decrypted in E2EE rooms) into the workspace. `None` for
+    /// unsupported or empty messages.
+    async fn inbound_content(&self, room: &Room, msgtype: &MessageType) -> Option<String> {
+        fn declared_size(size: Option<matrix_sdk::ruma::UInt>) -> Option<u64> {
+            size.map(u64::from)
+        }
+
+        let (name, caption, saved) = match msgtype {
+            MessageType::Text(content) => {
+                return Self::has_non_empty_body(&content.body).then(|| content.body.clone());
+            }
+            MessageType::Notice(content) => {
+                return Self::has_non_empty_body(&content.body).then(|| content.body.clone());
+            }
+            MessageType::Image(content) => {
+                let size = declared_size(content.info.as_ref().and_then(|i| i.size));
+                let saved = self
+                    .save_media(room, content, content.filename(), size)
+                    .await;
+                (content.filename(), content.caption(), saved)
+            }
+            MessageType::File(content) => {
+                let size = declared_size(content.info.as_ref().and_then(|i| i.size));
+                let saved = self
+                    .save_media(room, content, content.filename(), size)
+                    .await;
+                (content.filename(), content.caption(), saved)
+            }
+            MessageType::Audio(content) => {
+                let size = declared_size(content.info.as_ref().and_then(|i| i.size));
+                let saved = self
+                    .save_media(room, content, content.filename(), size)
+                    .await;
+                (content.filename(), content.caption(), saved)
+            }
+            MessageType::Video(content) => {
+                let size = declared_size(content.info.as_ref().and_then(|i| i.size));
+                let saved = self
+                    .save_media(room, content, content.filename(), size)
+                    .await;
+                (content.filename(), content.caption(), saved)
+            }
+            _ => return None,
+        };
+
+        Some(media_message_text(name, caption, saved.as_deref()))
+    }
+
+    /// Download a media event into the workspace, returning its path
+    /// relative to the workspace.
+    async fn save_media(
+        &self,
+        room: &Room,
+        content: &impl MediaEventContent,
+        name: &str,
+        declared_size: Option<u64>,
+    ) -> Option<String> {
+        let workspace_dir = &self.workspace.as_ref()?.workspace_dir;
+        let limit = u64::try_from(self.max_media_bytes).unwrap_or(u64::MAX);
+        if limit == 0 || declared_size.is_some_and(|size| size > limit) {
+            return None;
+        }
+
+        let data = match room.client().media().get_file(content, false).await {
+            Ok(Some(data)) => data,
+            Ok(None) => return None,
+            Err(e) => {
+                tracing::warn!("Matrix media download failed for {name}: {e}");
+                return None;
+            }
+        };
+        if data.len() > self.max_media_bytes {
+            return None;
+        }
+
+        let relative = format!(
+            "{MEDIA_DIR}/{}-{}",
+            &Uuid::new_v4().simple().to_string()[..8],
+            sanitize_file_name(name)
+        );
+        let path = workspace_dir.join(&relative);
+        if let Some(parent) = path.parent() {
+            if let Err(e) = tokio::fs::create_dir_all(parent).await {
+                tracing::warn!("Failed to create {MEDIA_DIR}: {e}");
+                return None;
+            }
+        }
+        match tokio::fs::write(&path, &data).await {
+            Ok(()) => Some(relative),
+            Err(e) => {
+                tracing::warn!("Failed to save Matrix media {relative}: {e}");
+                None
+            }
+        }
+    }
+
+    /// Resolve an outbound attachment marker to a file inside the workspace.
+    fn resolve_attachment(&self, target: &str) -> Option<PathBuf> {
+        resolve_outbound_attachment(self.workspace.as_deref()?, target)
+    }
+
+    /// Split a reply into its text and the workspace files it attaches.
+    /// Without a workspace the reply is sent unchanged.
+    fn prepare_reply(&self, content: &str) -> (String, Vec<PathBuf>) {
+        if self.workspace.is_none() {
+            return (content.to_string(), Vec::new());
+        }
+
+        let (mut text, targets) = parse_attachment_markers(content);
+        let mut files = Vec::new();
+        for target in targets {
+            match self.resolve_attachment(&target) {
+                Some(path) => files.push(path),
+                None => {
+                    tracing::warn!("Matrix attachment not found in workspace: {target}");
+                    let _ = write!(text, "\n\n[Attachment unavailable: {target}]");
+                }
+            }
+        }
+        (text, files)
+    }
+
+    /// Upload files to the room (encrypted in E2EE rooms), inside the thread
+    /// when there is one.
+    async fn send_attachments(
+        room: &Room,
+        thread_root: Option<&str>,
+        files: &[PathBuf],
+    ) -> anyhow::Result<()> {
+        for path in files {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "attachment".into());
+            let content_type: mime::Mime = attachment_content_type(&name)
+                .parse()
+                .unwrap_or(mime::APPLICATION_OCTET_STREAM);
+            let data = tokio::fs::read(path).await?;
+
+            let mut config = AttachmentConfig::new();
+            if let Some(root) = thread_root {
+                config.reply = Some(Reply {
+                    event_id: root.parse()?,
+                    enforce_thread: EnforceThread::Threaded(ReplyWithinThread::No),
+                });
+            }
+            room.send_attachment(name, &content_type, data, config)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Replace the content of a message we sent earlier (`m.replace`).
+    async fn edit_message(
+        &self,
+        recipient: &str,
+        event_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        let (room_id, _) = split_reply_target(recipient);
+        let room = self.joined_room(room_id).await?;
+        let event_id: OwnedEventId = event_id.parse()?;
+        let content = RoomMessageEventContent::text_plain(text)
+            .make_replacement(ReplacementMetadata::new(event_id, None));
+        room.send(content).await?;
+        Ok(())
+    }
 
+    async fn react(room: &Room, event_id: OwnedEventId, key: &str) -> anyhow::Result<()> {
+        room.send(ReactionEventContent::new(Annotation::new(
+            event_id,
+            key.to_string(),
+        )))
+        .await?;
         Ok(())
     }
 
+    /// Post a tool approval prompt with the answer reactions pre-filled,
+    /// returning the prompt's event ID.
+    async fn post_approval_prompt(
+        &self,
+        recipient: &str,
+        request: &ApprovalRequest,
+    ) -> anyhow::Result<String> {
+        let (room_id, thread_root) = split_reply_target(recipient);
+        let room = self.joined_room(room_id).await?;
+        let response = room
+            .send(thread_content(&approval_prompt_text(request), thread_root)?)
+            .await?;
+        for key in [APPROVE_REACTION, ALWAYS_REACTION, DENY_REACTION] {
+            if let Err(e) = Self::react(&room, response.event_id.clone(), key).await {
+                tracing::debug!("Matrix: failed to add approval reaction {key}: {e}");
+            }
+        }
+        Ok(response.event_id.to_string())
+    }
+}
+
+/// Split a reply target into the room ID and optional thread root.
+fn split_reply_target(target: &str) -> (&str, Option<&str>) {
+    match target.split_once(THREAD_SEPARATOR) {
+        Some((room_id, root)) if !root.is_empty() => (room_id, Some(root)),
+        Some((room_id, _)) => (room_id, None),
+        None => (target, None),
+    }
+}
+
+/// Reply target for an inbound event: the thread it belongs to, a new thread
+/// rooted at the event when `thread_replies` is on, or else the room.
+fn inbound_reply_target(
+    room_id: &str,
+    event_id: &str,
+    thread_root: Option<&str>,
+    thread_replies: bool,
+) -> String {
+    match thread_root.or(thread_replies.then_some(event_id)) {
+        Some(root) => format!("{room_id}{THREAD_SEPARATOR}{root}"),
+        None => room_id.to_string(),
+    }
+}
+
+/// Plain-text message, placed in the thread when `thread_root` is set.
+fn thread_content(
+    text: &str,
+    thread_root: Option<&str>,
+) -> anyhow::Result<RoomMessageEventContent> {
+    let mut content = RoomMessageEventContent::text_plain(text);
+    if let Some(root) = thread_root {
+        let root: OwnedEventId = root.parse()?;
+        content.relates_to = Some(Relation::Thread(Thread::plain(root.clone(), root)));
+    }
+    Ok(content)
+}
+
+fn media_message_text(name: &str, caption: Option<&str>, saved: Option<&str>) -> String {
+    let note = match saved {
+        Some(path) => format!("[Uploaded to workspace: {path}]"),
+        None => format!("[Attachment not saved: {name}]"),
+    };
+    match caption.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(caption) => format!("{caption}\n\n{note}"),
+        None => note,
+    }
+}
+
+fn approval_prompt_text(request: &ApprovalRequest) -> String {
+    let summary = truncate_with_ellipsis(&summarize_args(&request.arguments), 1500);
+    format!(
+        "🔧 Agent wants to run `{}`\n{summary}\n\nReact {APPROVE_REACTION} to approve, \
+         {ALWAYS_REACTION} to allow for this session or {DENY_REACTION} to deny.",
+        request.tool_name
+    )
+}
+
+/// Map a reaction key onto an approval decision. Clients may append the
+/// emoji presentation selector (U+FE0F).
+fn approval_reaction_decision(key: &str) -> Option<ApprovalResponse> {
+    match key.trim_end_matches('\u{fe0f}') {
+        APPROVE_REACTION | "👍" => Some(ApprovalResponse::Yes),
+        ALWAYS_REACTION => Some(ApprovalResponse::Always),
+        DENY_REACTION | "👎" => Some(ApprovalResponse::No),
+        _ => None,
+    }
+}
+
+/// Resolve the pending approval a reaction answers, if any. Reactions from
+/// anyone but the user whose message triggered the tool call are ignored.
+fn resolve_approval_reaction(
+    pending: &PendingApprovals,
+    event_id: &str,
+    key: &str,
+    sender: &str,
+) -> Option<ApprovalResponse> {
+    let decision = approval_reaction_decision(key)?;
+    let mut pending = pending.lock();
+    if pending.get(event_id)?.requester != sender {
+        tracing::debug!("Matrix: ignoring approval reaction from {sender}; not the requester");
+        return None;
+    }
+    let entry = pending.remove(event_id)?;
+    let _ = entry.reply.send(decision);
+    Some(decision)
+}
+
+/// Posts approval prompts into the conversation and waits for the requesting
+/// user to react to them.
+struct MatrixApprovalPrompt {
+    channel: MatrixChannel,
+    recipient: String,
+    requester: String,
+}
+
+#[async_trait]
+impl ApprovalPrompt for MatrixApprovalPrompt {
+    async fn ask(&self, request: &ApprovalRequest) -> ApprovalResponse {
+        let (reply, answer) = oneshot::channel();
+        let prompt_id = match self
+            .channel
+            .post_approval_prompt(&self.recipient, request)
+            .await
+        {
+            Ok(id) => id,
+            Err(e) => {
+                tracing::warn!("Matrix: approval prompt failed: {e}");
+                return ApprovalResponse::No;
+            }
+        };
+        self.channel.pending_approvals.lock().insert(
+            prompt_id.clone(),
+            PendingApproval {
+                requester: self.requester.clone(),
+                reply,
+            },
+        );
+
+        let decision = tokio::time::timeout(self.channel.approval_timeout, answer).await;
+        self.channel.pending_approvals.lock().remove(&prompt_id);
+        match decision {
+            Ok(Ok(decision)) => decision,
+            _ => ApprovalResponse::No,
+        }
+    }
+}
+
+#[async_trait]
+impl Channel for MatrixChannel {
+    fn name(&self) -> &str {
+        "matrix"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        let (room_id, thread_root) = split_reply_target(&message.recipient);
+        let room = self.joined_room(room_id).await?;
+
+        let (text, files) = self.prepare_reply(&message.content);
+        if !text.is_empty() || files.is_empty() {
+            room.send(thread_content(&text, thread_root)?).await?;
+        }
+        Self::send_attachments(&room, thread_root, &files).await
+    }
+
     async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         let target_room_id = self.target_room_id().await?;
         self.ensure_room_supported(&target_room_id).await?;
@@ -505,6 +945,7 @@ impl Channel for MatrixChannel {
         )));
 
         let tx_handler = tx.clone();
+        let channel_for_handler = self.clone();
         let target_room_for_handler = target_room.clone();
         let my_user_id_for_handler = my_user_id.clone();
         let allowed_users_for_handler = self.allowed_users.clone();
@@ -512,6 +953,7 @@ impl Channel for MatrixChannel {
 
         client.add_event_handler(move |event: OriginalSyncRoomMessageEvent, room: Room| {
             let tx = tx_handler.clone();
+            let channel = channel_for_handler.clone();
             let target_room = target_room_for_handler.clone();
             let my_user_id = my_user_id_for_handler.clone();
             let allowed_users = allowed_users_for_handler.clone();
@@ -531,16 +973,13 @@ impl Channel for MatrixChannel {
                     return;
                 }
 
-                let body = match &event.content.msgtype {
-                    MessageType::Text(content) => content.body.clone(),
-                    MessageType::Notice(content) => content.body.clone(),
-                    _ => return,
+                // Edits of earlier messages are not new requests.
+                let thread_root = match &event.content.relates_to {
+                    Some(Relation::Replacement(_)) => return,
+                    Some(Relation::Thread(thread)) => Some(thread.event_id.to_string()),
+                    _ => None,
                 };
 
-                if !MatrixChannel::has_non_empty_body(&body) {
-                    return;
-                }
-
                 let event_id = event.event_id.to_string();
                 {
                     let mut guard = dedupe.lock().await;
@@ -550,10 +989,26 @@ impl Channel for MatrixChannel {
                     }
                 }
 
+                let Some(body) = channel.inbound_content(&room, &event.content.msgtype).await
+                else {
+                    return;
+                };
+
+                if let Some(key) = channel.ack_reaction.as_deref() {
+                    if let Err(e) = MatrixChannel::react(&room, event.event_id.clone(), key).await {
+                        tracing::debug!("Matrix: failed to add acknowledgement reaction: {e}");
+                    }
+                }
+
                 let msg = ChannelMessage {
-                    id: event_id,
-                    sender: sender.clone(),
-                    reply_target: sender,
+                    id: event_id.clone(),
+                    sender,
+                    reply_target: inbound_reply_target(
+                        room.room_id().as_str(),
+                        &event_id,
+                        thread_root.as_deref(),
+                        channel.thread_replies,
+                    ),
                     content: body,
                     channel: "matrix".to_string(),
                     timestamp: std::time::SystemTime::now()
@@ -566,6 +1021,39 @@ impl Channel for MatrixChannel {
             }
         });
 
+        let pending_for_reactions = Arc::clone(&self.pending_approvals);
+        let target_room_for_reactions = target_room.clone();
+        let my_user_id_for_reactions = my_user_id.clone();
+        let allowed_users_for_reactions = self.allowed_users.clone();
+
+        client.add_event_handler(move |event: OriginalSyncReactionEvent, room: Room| {
+            let pending = Arc::clone(&pending_for_reactions);
+            let target_room = target_room_for_reactions.clone();
+            let my_user_id = my_user_id_for_reactions.clone();
+            let allowed_users = allowed_users_for_reactions.clone();
+
+            async move {
+                if room.room_id().as_str() != target_room.as_str() || event.sender == my_user_id {
+                    return;
+                }
+
+                let sender = event.sender.to_string();
+                if !MatrixChannel::is_sender_allowed(&allowed_users, &sender) {
+                    return;
+                }
+
+                let annotation = &event.content.relates_to;
+                if let Some(decision) = resolve_approval_reaction(
+                    &pending,
+                    annotation.event_id.as_str(),
+                    &annotation.key,
+                    &sender,
+                ) {
+                    tracing::info!("Matrix: tool approval answered {decision:?} by {sender}");
+                }
+            }
+        });
+
         let sync_settings = SyncSettings::new().timeout(std::time::Duration::from_secs(30));
         client
             .sync_with_result_callback(sync_settings, |sync_result| {
@@ -599,6 +1087,125 @@ impl Channel for MatrixChannel {
 
         self.matrix_client().await.is_ok()
     }
+
+    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        self.stop_typing(recipient).await?;
+
+        let (room_id, _) = split_reply_target(recipient);
+        let room = self.joined_room(room_id).await?;
+        let handle = tokio::spawn(async move {
+            loop {
+                if let Err(e) = room.typing_notice(true).await {
+                    tracing::debug!("Matrix typing notice failed: {e}");
+                    return;
+                }
+                tokio::time::sleep(TYPING_REFRESH_INTERVAL).await;
+            }
+        });
+        self.typing_handles
+            .lock()
+            .insert(recipient.to_string(), handle);
+        Ok(())
+    }
+
+    async fn stop_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        let Some(handle) = self.typing_handles.lock().remove(recipient) else {
+            return Ok(());
+        };
+        handle.abort();
+
+        let (room_id, _) = split_reply_target(recipient);
+        let room = self.joined_room(room_id).await?;
+        room.typing_notice(false).await?;
+        Ok(())
+    }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        if self.stream_mode == StreamMode::Off {
+            return Ok(None);
+        }
+
+        let (room_id, thread_root) = split_reply_target(&message.recipient);
+        let room = self.joined_room(room_id).await?;
+        let initial_text = if message.content.is_empty() {
+            "..."
+        } else {
+            message.content.as_str()
+        };
+        let response = room
+            .send(thread_content(initial_text, thread_root)?)
+            .await?;
+
+        self.last_draft_edit
+            .lock()
+            .insert(message.recipient.clone(), Instant::now());
+
+        Ok(Some(response.event_id.to_string()))
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        // Rate-limit edits per reply target; every edit is a new room event.
+        {
+            let mut last_edits = self.last_draft_edit.lock();
+            if let Some(last_time) = last_edits.get(recipient) {
+                let elapsed = u64::try_from(last_time.elapsed().as_millis()).unwrap_or(u64::MAX);
+                if elapsed < self.draft_update_interval_ms {
+                    return Ok(());
+                }
+            }
+            last_edits.insert(recipient.to_string(), Instant::now());
+        }
+
+        if let Err(e) = self.edit_message(recipient, message_id, text).await {
+            tracing::debug!("Matrix draft edit failed: {e}");
+        }
+        Ok(())
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+
+        let (text, files) = self.prepare_reply(text);
+        self.edit_message(recipient, message_id, &text).await?;
+        if files.is_empty() {
+            return Ok(());
+        }
+        let (room_id, thread_root) = split_reply_target(recipient);
+        let room = self.joined_room(room_id).await?;
+        Self::send_attachments(&room, thread_root, &files).await
+    }
+
+    fn approval_manager(&self, recipient: &str, sender: &str) -> Option<Arc<ApprovalManager>> {
+        let autonomy = self.autonomy.as_ref()?;
+        let mut approvals = self.approvals.lock();
+        let key = format!("{recipient}:{sender}");
+        let manager = approvals.entry(key).or_insert_with(|| {
+            Arc::new(
+                ApprovalManager::from_config(autonomy).with_remote_prompt(Arc::new(
+                    MatrixApprovalPrompt {
+                        channel: self.clone(),
+                        recipient: recipient.to_string(),
+                        requester: sender.to_string(),
+                    },
+                )),
+            )
+        });
+        Some(Arc::clone(manager))
+    }
 }
 
 #[cfg(test)]
@@ -713,8 +1320,202 @@ mod tests {
     fn supported_message_type_detection() {
         assert!(MatrixChannel::is_supported_message_type("m.text"));
         assert!(MatrixChannel::is_supported_message_type("m.notice"));
-        assert!(!MatrixChannel::is_supported_message_type("m.image"));
-        assert!(!MatrixChannel::is_supported_message_type("m.file"));
+        assert!(MatrixChannel::is_supported_message_type("m.image"));
+        assert!(MatrixChannel::is_supported_message_type("m.file"));
+        assert!(!MatrixChannel::is_supported_message_type("m.location"));
+    }
+
+    #[test]
+    fn with_config_applies_channel_options() {
+        let config: MatrixConfig = toml::from_str(
+            r#"
+homeserver = "https://matrix.org"
+access_token = "tok"
+room_id = "!r:m"
+allowed_users = []
+thread_replies = true
+stream_mode = "partial"
+ack_reaction = "  "
+"#,
+        )
+        .unwrap();
+        let ch = make_channel().with_config(&config);
+
+        assert!(ch.thread_replies);
+        assert!(ch.supports_draft_updates());
+        assert!(ch.ack_reaction.is_none());
+        assert!(!make_channel().supports_draft_updates());
+    }
+
+    #[test]
+    fn reply_targets_carry_thread_root() {
+        assert_eq!(
+            split_reply_target("!room:m|$root"),
+            ("!room:m", Some("$root"))
+        );
+        assert_eq!(split_reply_target("!room:m|"), ("!room:m", None));
+        assert_eq!(split_reply_target("!room:m"), ("!room:m", None));
+
+        assert_eq!(
+            inbound_reply_target("!room:m", "$event", Some("$root"), false),
+            "!room:m|$root"
+        );
+        assert_eq!(
+            inbound_reply_target("!room:m", "$event", None, true),
+            "!room:m|$event"
+        );
+        assert_eq!(
+            inbound_reply_target("!room:m", "$event", None, false),
+            "!room:m"
+        );
+    }
+
+    #[test]
+    fn thread_content_sets_thread_relation() {
+        let content = thread_content("hi", Some("$root:m")).unwrap();
+        match content.relates_to {
+            Some(Relation::Thread(thread)) => assert_eq!(thread.event_id.as_str(), "$root:m"),
+            other => panic!("expected thread relation, got {other:?}"),
+        }
+        assert!(thread_content("hi", None).unwrap().relates_to.is_none());
+        assert!(thread_content("hi", Some("not-an-event-id")).is_err());
+    }
+
+    #[test]
+    fn media_message_text_notes_saved_path() {
+        assert_eq!(
+            media_message_text("cat.png", Some("look"), Some("uploads/matrix/ab-cat.png")),
+            "look\n\n[Uploaded to workspace: uploads/matrix/ab-cat.png]"
+        );
+        assert_eq!(
+            media_message_text("big.mov", None, None),
+            "[Attachment not saved: big.mov]"
+        );
+    }
+
+    #[test]
+    fn prepare_reply_resolves_workspace_files() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        std::fs::write(workspace.path().join("report.pdf"), b"%PDF").unwrap();
+        std::fs::create_dir(workspace.path().join("secrets")).unwrap();
+        std::fs::write(workspace.path().join("secrets/key.txt"), b"hunter2").unwrap();
+        let ch = make_channel().with_workspace(Arc::new(SecurityPolicy {
+            workspace_dir: workspace.path().to_path_buf(),
+            forbidden_paths: vec!["secrets".into()],
+            ..SecurityPolicy::default()
+        }));
+
+        let (text, files) = ch.prepare_reply(
+            "Here it is [FILE:report.pdf] [FILE:../etc/passwd] [FILE:secrets/key.txt]",
+        );
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("report.pdf"));
+        assert!(text.starts_with("Here it is"));
+        assert!(text.contains("[Attachment unavailable: ../etc/passwd]"));
+        assert!(text.contains("[Attachment unavailable: secrets/key.txt]"));
+
+        let (text, files) = make_channel().prepare_reply("keep [FILE:report.pdf]");
+        assert_eq!(text, "keep [FILE:report.pdf]");
+        assert!(files.is_empty());
+    }
+
+    #[test]
+    fn approval_prompt_lists_reactions() {
+        let request = ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({ "command": "ls" }),
+        };
+        let text = approval_prompt_text(&request);
+        assert!(text.contains("`shell`"));
+        assert!(text.contains(APPROVE_REACTION));
+        assert!(text.contains(DENY_REACTION));
+    }
+
+    #[test]
+    fn approval_reactions_map_to_decisions() {
+        assert_eq!(
+            approval_reaction_decision("✅"),
+            Some(ApprovalResponse::Yes)
+        );
+        assert_eq!(
+            approval_reaction_decision("✅\u{fe0f}"),
+            Some(ApprovalResponse::Yes)
+        );
+        assert_eq!(
+            approval_reaction_decision("🔁"),
+            Some(ApprovalResponse::Always)
+        );
+        assert_eq!(approval_reaction_decision("👎"), Some(ApprovalResponse::No));
+        assert_eq!(approval_reaction_decision("🎉"), None);
+    }
+
+    #[tokio::test]
+    async fn approval_reaction_resolves_pending_request() {
+        let pending: PendingApprovals = Arc::new(parking_lot::Mutex::new(HashMap::new()));
+        let (reply, answer) = oneshot::channel();
+        pending.lock().insert(
+            "$prompt".into(),
+            PendingApproval {
+                requester: "@alice:example.org".into(),
+                reply,
+            },
+        );
+
+        assert_eq!(
+            resolve_approval_reaction(&pending, "$prompt", "🎉", "@alice:example.org"),
+            None
+        );
+        assert_eq!(
+            resolve_approval_reaction(&pending, "$other", "✅", "@alice:example.org"),
+            None
+        );
+        assert_eq!(
+            resolve_approval_reaction(&pending, "$prompt", "🔁", "@alice:example.org"),
+            Some(ApprovalResponse::Always)
+        );
+        assert_eq!(answer.await.unwrap(), ApprovalResponse::Always);
+        assert!(pending.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn approval_reaction_from_another_user_is_ignored() {
+        let pending: PendingApprovals = Arc::new(parking_lot::Mutex::new(HashMap::new()));
+        let (reply, answer) = oneshot::channel();
+        pending.lock().insert(
+            "$prompt".into(),
+            PendingApproval {
+                requester: "@alice:example.org".into(),
+                reply,
+            },
+        );
+
+        assert_eq!(
+            resolve_approval_reaction(&pending, "$prompt", "✅", "@mallory:example.org"),
+            None
+        );
+        assert!(pending.lock().contains_key("$prompt"));
+
+        assert_eq!(
+            resolve_approval_reaction(&pending, "$prompt", "❌", "@alice:example.org"),
+            Some(ApprovalResponse::No)
+        );
+        assert_eq!(answer.await.unwrap(), ApprovalResponse::No);
+    }
+
+    #[test]
+    fn approval_managers_are_kept_per_sender() {
+        let ch = make_channel().with_approvals(&AutonomyConfig::default(), 30);
+        let alice = ch
+            .approval_manager("!room:matrix.org", "@alice:matrix.org")
+            .unwrap();
+        let again = ch
+            .approval_manager("!room:matrix.org", "@alice:matrix.org")
+            .unwrap();
+        let bob = ch
+            .approval_manager("!room:matrix.org", "@bob:matrix.org")
+            .unwrap();
+        assert!(Arc::ptr_eq(&alice, &again));
+        assert!(!Arc::ptr_eq(&alice, &bob));
     }
 
     #[test]
@@ -7,11 +7,14 @@ pub mod irc;
 pub mod lark;
 pub mod matrix;
 pub mod mattermost;
+pub mod mqtt;
+pub mod nextcloud_talk;
 pub mod qq;
 pub mod signal;
 pub mod slack;
 pub mod telegram;
 pub mod traits;
+pub mod webchat;
 pub mod whatsapp;
 
 pub use cli::CliChannel;
@@ -23,14 +26,19 @@ pub use irc::IrcChannel;
 pub use lark::LarkChannel;
 pub use matrix::MatrixChannel;
 pub use mattermost::MattermostChannel;
+pub use mqtt::MqttChannel;
+pub use nextcloud_talk::NextcloudTalkChannel;
 pub use qq::QQChannel;
 pub use signal::SignalChannel;
 pub use slack::SlackChannel;
 pub use telegram::TelegramChannel;
 pub use traits::{Channel, SendMessage};
+pub use webchat::WebChatChannel;
 pub use whatsapp::WhatsAppChannel;
 
+use crate::agent::context::ContextWindowRegistry;
 use crate::agent::loop_::{build_tool_instructions, run_tool_call_loop};
+use crate::config::reload::{self, ConfigWatch};
 use crate::config::Config;
 use crate::identity;
 use crate::memory::{self, Memory};
@@ -38,9 +46,14 @@ use crate::observability::{self, Observer};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
 use crate::security::SecurityPolicy;
-use crate::tools::{self, Tool};
+use crate::tools::{self, ProcessManager, Tool};
+use crate::transcription::{self, Transcriber};
+use crate::tts::{self, SpeechSynthesizer, VoiceReplyPreferences};
+use crate::users::{self, UserDirectory, UserProfile};
 use crate::util::truncate_with_ellipsis;
 use anyhow::{Context, Result};
+use opentelemetry::trace::SpanKind;
+use opentelemetry::KeyValue;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fmt::Write;
@@ -69,6 +82,8 @@ const CHANNEL_MAX_IN_FLIGHT_MESSAGES: usize = 64;
 const CHANNEL_TYPING_REFRESH_INTERVAL_SECS: u64 = 4;
 const MODEL_CACHE_FILE: &str = "models_cache.json";
 const MODEL_CACHE_PREVIEW_LIMIT: usize = 10;
+/// Entries shown by the `/memory` runtime command.
+const MEMORY_PREVIEW_LIMIT: usize = 10;
 
 type ProviderCacheMap = Arc<Mutex<HashMap<String, Arc<dyn Provider>>>>;
 type RouteSelectionMap = Arc<Mutex<HashMap<String, ChannelRouteSelection>>>;
@@ -85,6 +100,11 @@ enum ChannelRuntimeCommand {
     SetProvider(String),
     ShowModel,
     SetModel(String),
+    SetVoiceMode(String),
+    ResetConversation,
+    ShowMemory(String),
+    ShowCron,
+    User(users::UserCommand),
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -105,12 +125,15 @@ struct ChannelRuntimeContext {
     default_provider: Arc<String>,
     memory: Arc<dyn Memory>,
     tools_registry: Arc<Vec<Box<dyn Tool>>>,
+    /// Background processes started by `tools_registry`, keyed by history key.
+    processes: Arc<ProcessManager>,
     observer: Arc<dyn Observer>,
     system_prompt: Arc<String>,
     model: Arc<String>,
     temperature: f64,
     auto_save_memory: bool,
     max_tool_iterations: usize,
+    context_windows: Arc<ContextWindowRegistry>,
     min_relevance_score: f64,
     conversation_histories: ConversationHistoryMap,
     provider_cache: ProviderCacheMap,
@@ -120,14 +143,36 @@ struct ChannelRuntimeContext {
     reliability: Arc<crate::config::ReliabilityConfig>,
     provider_runtime_options: providers::ProviderRuntimeOptions,
     workspace_dir: Arc<PathBuf>,
+    transcriber: Option<Arc<dyn Transcriber>>,
+    transcription_config: Arc<crate::config::TranscriptionConfig>,
+    synthesizer: Option<Arc<dyn SpeechSynthesizer>>,
+    tts_config: Arc<crate::config::TtsConfig>,
+    voice_reply_preferences: Arc<VoiceReplyPreferences>,
+    autonomy: Arc<crate::config::AutonomyConfig>,
+    users: Arc<UserDirectory>,
 }
 
 fn conversation_memory_key(msg: &traits::ChannelMessage) -> String {
     format!("{}_{}_{}", msg.channel, msg.sender, msg.id)
 }
 
-fn conversation_history_key(msg: &traits::ChannelMessage) -> String {
-    format!("{}_{}", msg.channel, msg.sender)
+/// Slack threads (`channel_id:thread_ts`) and email threads (`addr#token`)
+/// keep their own history; otherwise linked senders share one history across
+/// channels and others are per channel.
+fn conversation_history_key(msg: &traits::ChannelMessage, profile: Option<&UserProfile>) -> String {
+    let threaded = match msg.channel.as_str() {
+        "slack" => msg.reply_target.contains(':'),
+        "email" => msg.reply_target.contains('#'),
+        "matrix" => msg.reply_target.contains('|'),
+        _ => false,
+    };
+    if threaded {
+        return format!("{}_thread_{}", msg.channel, msg.reply_target);
+    }
+    match profile {
+        Some(profile) => profile.conversation_key(),
+        None => format!("{}_{}", msg.channel, msg.sender),
+    }
 }
 
 fn channel_delivery_instructions(channel_name: &str) -> Option<&'static str> {
@@ -135,6 +180,12 @@ fn channel_delivery_instructions(channel_name: &str) -> Option<&'static str> {
         "telegram" => Some(
             "When responding on Telegram, include media markers for files or URLs that should be sent as attachments. Use one marker per attachment with this exact syntax: [IMAGE:<path-or-url>], [DOCUMENT:<path-or-url>], [VIDEO:<path-or-url>], [AUDIO:<path-or-url>], or [VOICE:<path-or-url>]. Keep normal user-facing text outside markers and never wrap markers in code fences.",
         ),
+        "email" => Some(
+            "When responding by email, write Markdown; it is sent as formatted HTML alongside the plain text. To attach workspace files, add one marker per file with this exact syntax: [FILE:<workspace-relative-path>]. Keep markers outside code fences.",
+        ),
+        "matrix" => Some(
+            "When responding on Matrix, attach workspace files with one marker per file using this exact syntax: [FILE:<workspace-relative-path>]. Keep markers outside code fences.",
+        ),
         _ => None,
     }
 }
@@ -143,7 +194,21 @@ fn supports_runtime_model_switch(channel_name: &str) -> bool {
     matches!(channel_name, "telegram" | "discord")
 }
 
+fn supports_voice_reply_command(channel_name: &str) -> bool {
+    matches!(channel_name, "telegram" | "whatsapp")
+}
+
 fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRuntimeCommand> {
+    if let Some(command) = users::parse_user_command(content) {
+        return Some(ChannelRuntimeCommand::User(command));
+    }
+
+    if supports_voice_reply_command(channel_name) {
+        if let Some(argument) = tts::parse_voice_command(content) {
+            return Some(ChannelRuntimeCommand::SetVoiceMode(argument));
+        }
+    }
+
     if !supports_runtime_model_switch(channel_name) {
         return None;
     }
@@ -179,6 +244,11 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
                 Some(ChannelRuntimeCommand::SetModel(model))
             }
         }
+        "/reset" => Some(ChannelRuntimeCommand::ResetConversation),
+        "/memory" => Some(ChannelRuntimeCommand::ShowMemory(
+            parts.collect::<Vec<_>>().join(" "),
+        )),
+        "/cron" => Some(ChannelRuntimeCommand::ShowCron),
         _ => None,
     }
 }
@@ -204,24 +274,41 @@ fn resolve_provider_alias(name: &str) -> Option<String> {
     None
 }
 
-fn default_route_selection(ctx: &ChannelRuntimeContext) -> ChannelRouteSelection {
+/// The channel default route, or the linked user's preferred provider/model.
+fn default_route_selection(
+    ctx: &ChannelRuntimeContext,
+    profile: Option<&UserProfile>,
+) -> ChannelRouteSelection {
+    let preferred_provider = profile.and_then(|p| p.provider.as_deref());
+    let preferred_model = profile.and_then(|p| p.model.as_deref());
     ChannelRouteSelection {
-        provider: ctx.default_provider.as_str().to_string(),
-        model: ctx.model.as_str().to_string(),
+        provider: preferred_provider
+            .unwrap_or(ctx.default_provider.as_str())
+            .to_string(),
+        model: preferred_model.unwrap_or(ctx.model.as_str()).to_string(),
     }
 }
 
-fn get_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str) -> ChannelRouteSelection {
+fn get_route_selection(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+    profile: Option<&UserProfile>,
+) -> ChannelRouteSelection {
     ctx.route_overrides
         .lock()
         .unwrap_or_else(|e| e.into_inner())
         .get(sender_key)
         .cloned()
-        .unwrap_or_else(|| default_route_selection(ctx))
+        .unwrap_or_else(|| default_route_selection(ctx, profile))
 }
 
-fn set_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str, next: ChannelRouteSelection) {
-    let default_route = default_route_selection(ctx);
+fn set_route_selection(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+    profile: Option<&UserProfile>,
+    next: ChannelRouteSelection,
+) {
+    let default_route = default_route_selection(ctx, profile);
     let mut routes = ctx
         .route_overrides
         .lock()
@@ -337,6 +424,97 @@ fn build_models_help_response(current: &ChannelRouteSelection, workspace_dir: &P
     response
 }
 
+async fn build_memory_response(mem: &dyn Memory, query: &str, session_id: Option<&str>) -> String {
+    let query = query.trim();
+    let entries = if query.is_empty() {
+        mem.list(None, session_id).await.map(|mut entries| {
+            entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+            entries
+        })
+    } else {
+        mem.recall(query, MEMORY_PREVIEW_LIMIT, session_id).await
+    };
+
+    let entries = match entries {
+        Ok(entries) => entries,
+        Err(err) => return format!("Failed to read memory: {err}"),
+    };
+
+    if entries.is_empty() {
+        return if query.is_empty() {
+            "No memories stored yet.".to_string()
+        } else {
+            format!("No memories match `{query}`.")
+        };
+    }
+
+    let mut response = if query.is_empty() {
+        format!(
+            "Most recent memories ({} of {}):\n",
+            entries.len().min(MEMORY_PREVIEW_LIMIT),
+            entries.len()
+        )
+    } else {
+        format!("Memories matching `{query}`:\n")
+    };
+    for entry in entries.iter().take(MEMORY_PREVIEW_LIMIT) {
+        let _ = writeln!(
+            response,
+            "- `{}` ({}): {}",
+            entry.key,
+            entry.category,
+            truncate_with_ellipsis(&entry.content, 160)
+        );
+    }
+    response.push_str("\nSearch with `/memory <query>`.");
+    response
+}
+
+/// Lists scheduled jobs through the `cron_list` tool so the cron config
+/// (including `cron.enabled`) applies the same way it does to the agent.
+async fn build_cron_response(tools_registry: &[Box<dyn Tool>]) -> String {
+    let Some(tool) = tools_registry
+        .iter()
+        .find(|tool| tool.name() == "cron_list")
+    else {
+        return "Scheduled jobs are not available on this runtime.".to_string();
+    };
+
+    let result = match tool.execute(serde_json::json!({})).await {
+        Ok(result) => result,
+        Err(err) => return format!("Failed to list scheduled jobs: {err}"),
+    };
+    if !result.success {
+        return format!(
+            "Failed to list scheduled jobs: {}",
+            result.error.unwrap_or_default()
+        );
+    }
+
+    let jobs: Vec<crate::cron::CronJob> = serde_json::from_str(&result.output).unwrap_or_default();
+    if jobs.is_empty() {
+        return "No scheduled jobs.".to_string();
+    }
+
+    let mut response = format!("Scheduled jobs ({}):\n", jobs.len());
+    for job in &jobs {
+        let label = job
+            .name
+            .clone()
+            .or_else(|| job.prompt.clone())
+            .unwrap_or_else(|| job.command.clone());
+        let _ = writeln!(
+            response,
+            "- `{}` {}{}: next run {}",
+            job.id,
+            truncate_with_ellipsis(&label, 80),
+            if job.enabled { "" } else { " (paused)" },
+            job.next_run.to_rfc3339()
+        );
+    }
+    response
+}
+
 fn build_providers_help_response(current: &ChannelRouteSelection) -> String {
     let mut response = String::new();
     let _ = writeln!(
@@ -365,6 +543,7 @@ fn build_providers_help_response(current: &ChannelRouteSelection) -> String {
 async fn handle_runtime_command_if_needed(
     ctx: &ChannelRuntimeContext,
     msg: &traits::ChannelMessage,
+    profile: Option<&UserProfile>,
     target_channel: Option<&Arc<dyn Channel>>,
 ) -> bool {
     let Some(command) = parse_runtime_command(&msg.channel, &msg.content) else {
@@ -375,8 +554,8 @@ async fn handle_runtime_command_if_needed(
         return true;
     };
 
-    let sender_key = conversation_history_key(msg);
-    let mut current = get_route_selection(ctx, &sender_key);
+    let sender_key = conversation_history_key(msg, profile);
+    let mut current = get_route_selection(ctx, &sender_key, profile);
 
     let response = match command {
         ChannelRuntimeCommand::ShowProviders => build_providers_help_response(&current),
@@ -386,7 +565,7 @@ async fn handle_runtime_command_if_needed(
                     Ok(_) => {
                         if provider_name != current.provider {
                             current.provider = provider_name.clone();
-                            set_route_selection(ctx, &sender_key, current.clone());
+                            set_route_selection(ctx, &sender_key, profile, current.clone());
                             clear_sender_history(ctx, &sender_key);
                         }
 
@@ -416,7 +595,7 @@ async fn handle_runtime_command_if_needed(
                 "Model ID cannot be empty. Use `/model <model-id>`.".to_string()
             } else {
                 current.model = model.clone();
-                set_route_selection(ctx, &sender_key, current.clone());
+                set_route_selection(ctx, &sender_key, profile, current.clone());
                 clear_sender_history(ctx, &sender_key);
 
                 format!(
@@ -425,6 +604,25 @@ async fn handle_runtime_command_if_needed(
                 )
             }
         }
+        ChannelRuntimeCommand::SetVoiceMode(argument) => tts::voice_command_response(
+            ctx.voice_reply_preferences.as_ref(),
+            &sender_key,
+            &argument,
+            ctx.tts_config.as_ref(),
+        ),
+        ChannelRuntimeCommand::ResetConversation => {
+            clear_sender_history(ctx, &sender_key);
+            ctx.processes.kill_owned_by(&sender_key).await;
+            "Conversation history cleared for this sender session.".to_string()
+        }
+        ChannelRuntimeCommand::ShowMemory(query) => {
+            let session = profile.and_then(UserProfile::memory_session);
+            build_memory_response(ctx.memory.as_ref(), &query, session.as_deref()).await
+        }
+        ChannelRuntimeCommand::ShowCron => build_cron_response(&ctx.tools_registry).await,
+        ChannelRuntimeCommand::User(command) => {
+            users::user_command_response(&ctx.users, &msg.channel, &msg.sender, &command)
+        }
     };
 
     if let Err(err) = channel
@@ -444,10 +642,11 @@ async fn build_memory_context(
     mem: &dyn Memory,
     user_msg: &str,
     min_relevance_score: f64,
+    session_id: Option<&str>,
 ) -> String {
     let mut context = String::new();
 
-    if let Ok(entries) = mem.recall(user_msg, 5, None).await {
+    if let Ok(entries) = mem.recall(user_msg, 5, session_id).await {
         let relevant: Vec<_> = entries
             .iter()
             .filter(|e| match e.score {
@@ -553,7 +752,119 @@ fn spawn_scoped_typing_task(
     handle
 }
 
+/// Replace the markers of the voice notes downloaded for this message with
+/// transcripts before the agent sees the message.
+async fn transcribe_inbound_voice(
+    ctx: &ChannelRuntimeContext,
+    mut msg: traits::ChannelMessage,
+    voice_notes: &[PathBuf],
+) -> traits::ChannelMessage {
+    let Some(transcriber) = ctx.transcriber.as_ref() else {
+        return msg;
+    };
+    if voice_notes.is_empty() {
+        return msg;
+    }
+
+    let started_at = Instant::now();
+    let result = transcription::transcribe_voice_markers(
+        transcriber.as_ref(),
+        &msg.content,
+        voice_notes,
+        &transcription::voice_inbox_dir(ctx.workspace_dir.as_path()),
+        ctx.transcription_config.as_ref(),
+    )
+    .await;
+    println!(
+        "  🎙️ Transcribed {} voice message(s) via {} ({}ms)",
+        result.transcripts.len(),
+        transcriber.name(),
+        started_at.elapsed().as_millis()
+    );
+
+    if ctx.transcription_config.show_transcript {
+        if let Some(channel) = ctx.channels_by_name.get(&msg.channel) {
+            for transcript in result.transcripts.iter().filter(|t| !t.is_empty()) {
+                let _ = channel
+                    .send(&SendMessage::new(
+                        format!("🎙️ Transcript: {transcript}"),
+                        &msg.reply_target,
+                    ))
+                    .await;
+            }
+        }
+    }
+
+    msg.content = result.content;
+    msg
+}
+
+/// Whether this reply should go out as a synthesized voice message.
+fn wants_voice_reply(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    history_key: &str,
+    channel: Option<&Arc<dyn Channel>>,
+    user_sent_voice: bool,
+) -> bool {
+    if ctx.synthesizer.is_none() || !channel.is_some_and(|ch| ch.supports_voice_replies()) {
+        return false;
+    }
+    let mode = ctx
+        .voice_reply_preferences
+        .mode_for(history_key, ctx.tts_config.reply_mode);
+    tts::should_reply_with_voice(ctx.tts_config.as_ref(), mode, &msg.channel, user_sent_voice)
+}
+
+/// Speak `response` on `channel`; returns `false` so the caller sends text instead.
+async fn try_send_voice_reply(
+    ctx: &ChannelRuntimeContext,
+    channel: &Arc<dyn Channel>,
+    reply_target: &str,
+    response: &str,
+) -> bool {
+    let Some(synthesizer) = ctx.synthesizer.as_ref() else {
+        return false;
+    };
+
+    let started_at = Instant::now();
+    match tts::send_voice_reply(
+        synthesizer.as_ref(),
+        channel.as_ref(),
+        reply_target,
+        response,
+        ctx.workspace_dir.as_path(),
+        ctx.tts_config.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => {
+            println!(
+                "  🔊 Voice reply via {} ({}ms)",
+                synthesizer.name(),
+                started_at.elapsed().as_millis()
+            );
+            true
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Voice reply on {} failed, falling back to text: {e}",
+                channel.name()
+            );
+            false
+        }
+    }
+}
+
 async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::ChannelMessage) {
+    let voice_notes = ctx
+        .channels_by_name
+        .get(&msg.channel)
+        .map(|channel| channel.take_voice_notes(&msg.id))
+        .unwrap_or_default();
+    let user_sent_voice = !voice_notes.is_empty();
+    let msg = transcribe_inbound_voice(ctx.as_ref(), msg, &voice_notes).await;
+
     println!(
         "  💬 [{}] from {}: {}",
         msg.channel,
@@ -562,12 +873,21 @@ async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::C
     );
 
     let target_channel = ctx.channels_by_name.get(&msg.channel).cloned();
-    if handle_runtime_command_if_needed(ctx.as_ref(), &msg, target_channel.as_ref()).await {
+    let profile = ctx.users.resolve(&msg.channel, &msg.sender);
+    if handle_runtime_command_if_needed(
+        ctx.as_ref(),
+        &msg,
+        profile.as_ref(),
+        target_channel.as_ref(),
+    )
+    .await
+    {
         return;
     }
 
-    let history_key = conversation_history_key(&msg);
-    let route = get_route_selection(ctx.as_ref(), &history_key);
+    let history_key = conversation_history_key(&msg, profile.as_ref());
+    let memory_session = profile.as_ref().and_then(UserProfile::memory_session);
+    let route = get_route_selection(ctx.as_ref(), &history_key, profile.as_ref());
     let active_provider = match get_or_create_provider(ctx.as_ref(), &route.provider).await {
         Ok(provider) => provider,
         Err(err) => {
@@ -585,8 +905,16 @@ async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::C
         }
     };
 
-    let memory_context =
-        build_memory_context(ctx.memory.as_ref(), &msg.content, ctx.min_relevance_score).await;
+    let context_budget = ctx
+        .context_windows
+        .budget_for(&route.provider, &route.model);
+    let memory_context = build_memory_context(
+        ctx.memory.as_ref(),
+        &msg.content,
+        ctx.min_relevance_score,
+        memory_session.as_deref(),
+    )
+    .await;
 
     if ctx.auto_save_memory {
         let autosave_key = conversation_memory_key(&msg);
@@ -596,20 +924,11 @@ async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::C
                 &autosave_key,
                 &msg.content,
                 crate::memory::MemoryCategory::Conversation,
-                None,
+                memory_session.as_deref(),
             )
             .await;
     }
 
-    let enriched_message = if memory_context.is_empty() {
-        msg.content.clone()
-    } else {
-        format!("{memory_context}{}", msg.content)
-    };
-
-    println!("  ⏳ Processing message...");
-    let started_at = Instant::now();
-
     // Build history from per-sender conversation cache
     let mut prior_turns = ctx
         .conversation_histories
@@ -621,16 +940,43 @@ async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::C
 
     let mut history = vec![ChatMessage::system(ctx.system_prompt.as_str())];
     history.append(&mut prior_turns);
-    history.push(ChatMessage::user(&enriched_message));
+    history.push(ChatMessage::user(&msg.content));
+    context_budget.trim_history(&mut history);
+    let current_turn = history.len() - 1;
 
     if let Some(instructions) = channel_delivery_instructions(&msg.channel) {
         history.push(ChatMessage::system(instructions));
     }
 
-    // Determine if this channel supports streaming draft updates
-    let use_streaming = target_channel
-        .as_ref()
-        .map_or(false, |ch| ch.supports_draft_updates());
+    // Recalled memory gets what the trimmed history leaves of its share.
+    let memory_context = truncate_with_ellipsis(
+        &memory_context,
+        context_budget.memory_context_char_limit(&history),
+    );
+    let enriched_message = if memory_context.is_empty() {
+        msg.content.clone()
+    } else {
+        format!("{memory_context}{}", msg.content)
+    };
+    history[current_turn].content.clone_from(&enriched_message);
+
+    println!("  ⏳ Processing message...");
+    let started_at = Instant::now();
+
+    let voice_reply = wants_voice_reply(
+        ctx.as_ref(),
+        &msg,
+        &history_key,
+        target_channel.as_ref(),
+        user_sent_voice,
+    );
+
+    // Determine if this channel supports streaming draft updates. Voice replies
+    // are sent whole, so drafts are skipped for them.
+    let use_streaming = !voice_reply
+        && target_channel
+            .as_ref()
+            .map_or(false, |ch| ch.supports_draft_updates());
 
     // Set up streaming channel if supported
     let (delta_tx, delta_rx) = if use_streaming {
@@ -695,21 +1041,36 @@ async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::C
         _ => None,
     };
 
+    let approval = target_channel
+        .as_ref()
+        .and_then(|channel| channel.approval_manager(&msg.reply_target, &msg.sender));
+    // Per-user autonomy and tool allowlists replace the channel default.
+    let approval = match profile.as_ref() {
+        Some(profile) if profile.restricts_tools() => Some(Arc::new(
+            profile.approval_manager(ctx.autonomy.as_ref(), approval.as_deref()),
+        )),
+        _ => approval,
+    };
+
     let llm_result = tokio::time::timeout(
         Duration::from_secs(CHANNEL_MESSAGE_TIMEOUT_SECS),
-        run_tool_call_loop(
-            active_provider.as_ref(),
-            &mut history,
-            ctx.tools_registry.as_ref(),
-            ctx.observer.as_ref(),
-            route.provider.as_str(),
-            route.model.as_str(),
-            ctx.temperature,
-            true,
-            None,
-            msg.channel.as_str(),
-            ctx.max_tool_iterations,
-            delta_tx,
+        tools::with_process_owner(
+            history_key.as_str(),
+            run_tool_call_loop(
+                active_provider.as_ref(),
+                &mut history,
+                ctx.tools_registry.as_ref(),
+                ctx.observer.as_ref(),
+                route.provider.as_str(),
+                route.model.as_str(),
+                ctx.temperature,
+                true,
+                approval.as_deref(),
+                msg.channel.as_str(),
+                ctx.max_tool_iterations,
+                delta_tx,
+                Some(context_budget),
+            ),
         ),
     )
     .await;
@@ -758,11 +1119,23 @@ async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::C
                             .send(&SendMessage::new(&response, &msg.reply_target))
                             .await;
                     }
-                } else if let Err(e) = channel
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
-                {
-                    eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
+                } else {
+                    let spoken = voice_reply
+                        && try_send_voice_reply(
+                            ctx.as_ref(),
+                            channel,
+                            &msg.reply_target,
+                            &response,
+                        )
+                        .await;
+                    if !spoken {
+                        if let Err(e) = channel
+                            .send(&SendMessage::new(response, &msg.reply_target))
+                            .await
+                        {
+                            eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
+                        }
+                    }
                 }
             }
         }
@@ -815,7 +1188,7 @@ async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::C
 
 async fn run_message_dispatch_loop(
     mut rx: tokio::sync::mpsc::Receiver<traits::ChannelMessage>,
-    ctx: Arc<ChannelRuntimeContext>,
+    contexts: tokio::sync::watch::Receiver<Arc<ChannelRuntimeContext>>,
     max_in_flight_messages: usize,
 ) {
     let semaphore = Arc::new(tokio::sync::Semaphore::new(max_in_flight_messages));
@@ -827,10 +1200,25 @@ async fn run_message_dispatch_loop(
             Err(_) => break,
         };
 
-        let worker_ctx = Arc::clone(&ctx);
+        // Each message runs against the runtime current at dispatch time.
+        let worker_ctx = Arc::clone(&contexts.borrow());
+        // Root of the trace tree: the agent turn and its LLM/tool calls nest under it.
+        let message_cx = observability::trace::start_span(
+            "channel.message",
+            SpanKind::Consumer,
+            vec![
+                KeyValue::new("messaging.system", msg.channel.clone()),
+                KeyValue::new("messaging.message.id", msg.id.clone()),
+            ],
+        );
         workers.spawn(async move {
             let _permit = permit;
-            process_channel_message(worker_ctx, msg).await;
+            observability::trace::in_root_span(
+                message_cx.clone(),
+                process_channel_message(worker_ctx, msg),
+            )
+            .await;
+            observability::trace::end_span(&message_cx, Ok(()));
         });
 
         while let Some(result) = workers.try_join_next() {
@@ -1257,6 +1645,12 @@ pub fn handle_command(command: crate::ChannelCommands, config: &Config) -> Resul
                 ("Lark", config.channels_config.lark.is_some()),
                 ("DingTalk", config.channels_config.dingtalk.is_some()),
                 ("QQ", config.channels_config.qq.is_some()),
+                ("WebChat", config.channels_config.webchat.is_some()),
+                (
+                    "Nextcloud Talk",
+                    config.channels_config.nextcloud_talk.is_some(),
+                ),
+                ("MQTT", config.channels_config.mqtt.is_some()),
             ] {
                 println!("  {} {name}", if configured { "✅" } else { "❌" });
             }
@@ -1331,14 +1725,7 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
     }
 
     if let Some(ref sl) = config.channels_config.slack {
-        channels.push((
-            "Slack",
-            Arc::new(SlackChannel::new(
-                sl.bot_token.clone(),
-                sl.channel_id.clone(),
-                sl.allowed_users.clone(),
-            )),
-        ));
+        channels.push(("Slack", Arc::new(SlackChannel::from_config(sl))));
     }
 
     if let Some(ref im) = config.channels_config.imessage {
@@ -1436,6 +1823,17 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
         ));
     }
 
+    if let Some(ref nc) = config.channels_config.nextcloud_talk {
+        channels.push(("Nextcloud", Arc::new(NextcloudTalkChannel::from_config(nc))));
+    }
+
+    if let Some(ref mq) = config.channels_config.mqtt {
+        channels.push((
+            "MQTT",
+            Arc::new(MqttChannel::new(config.mqtt.clone(), mq.clone())),
+        ));
+    }
+
     if channels.is_empty() {
         println!("No real-time channels configured. Run `zeroclaw onboard` first.");
         return Ok(());
@@ -1472,14 +1870,26 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
         println!("  ℹ️  Webhook   check via `zeroclaw gateway` then GET /health");
     }
 
+    if config.channels_config.webchat.is_some() {
+        println!("  ℹ️  WebChat   open /chat on the `zeroclaw daemon` gateway");
+    }
+
     println!();
     println!("Summary: {healthy} healthy, {unhealthy} unhealthy, {timeout} timed out");
     Ok(())
 }
 
-/// Start all configured channels and route messages to the agent
+/// Build the per-message runtime (provider, tools, security policy, system
+/// prompt, voice backends) from `config`.
+///
+/// On reload, `previous` carries over conversation histories, route overrides,
+/// memory, the observer and background processes so in-flight sessions
+/// survive the swap.
 #[allow(clippy::too_many_lines)]
-pub async fn start_channels(config: Config) -> Result<()> {
+async fn build_runtime_context(
+    config: &Config,
+    previous: Option<&ChannelRuntimeContext>,
+) -> Result<ChannelRuntimeContext> {
     let provider_name = config
         .default_provider
         .clone()
@@ -1503,8 +1913,11 @@ pub async fn start_channels(config: Config) -> Result<()> {
         tracing::warn!("Provider warmup failed (non-fatal): {e}");
     }
 
-    let observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
+    let observer: Arc<dyn Observer> = match previous {
+        Some(previous) => Arc::clone(&previous.observer),
+        None => Arc::from(observability::create_observer(&config.observability)),
+    };
+    observability::trace::set_model_pricing(&config.cost.prices);
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -1516,12 +1929,15 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .clone()
         .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
     let temperature = config.default_temperature;
-    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
-        &config.memory,
-        Some(&config.storage.provider.config),
-        &config.workspace_dir,
-        config.api_key.as_deref(),
-    )?);
+    let mem: Arc<dyn Memory> = match previous {
+        Some(previous) => Arc::clone(&previous.memory),
+        None => Arc::from(memory::create_memory_with_storage(
+            &config.memory,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?),
+    };
     let (composio_key, composio_entity_id) = if config.composio.enabled {
         (
             config.composio.api_key.as_deref(),
@@ -1532,10 +1948,20 @@ pub async fn start_channels(config: Config) -> Result<()> {
     };
     // Build system prompt from workspace identity files + skills
     let workspace = config.workspace_dir.clone();
-    let tools_registry = Arc::new(tools::all_tools_with_runtime(
+    // Background processes outlive the registry they were started from; a
+    // fresh manager would kill them when the old registry is dropped.
+    let processes = match previous {
+        Some(previous) => {
+            previous.processes.reconfigure(&config.security);
+            Arc::clone(&previous.processes)
+        }
+        None => Arc::new(ProcessManager::from_config(&config.security)),
+    };
+    let tools_registry = Arc::new(tools::all_tools_with_processes(
         Arc::new(config.clone()),
         &security,
         runtime,
+        Arc::clone(&processes),
         Arc::clone(&mem),
         composio_key,
         composio_entity_id,
@@ -1544,7 +1970,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         &workspace,
         &config.agents,
         config.api_key.as_deref(),
-        &config,
+        config,
     ));
 
     let skills = crate::skills::load_skills(&workspace);
@@ -1555,6 +1981,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "shell",
             "Execute terminal commands. Use when: running local checks, build/test commands, diagnostics. Don't use when: a safer dedicated tool exists, or command is destructive without approval.",
         ),
+        (
+            "process",
+            "Manage background processes started with shell(background=true): list, poll output, tail, write stdin, kill. Use when: running dev servers, long builds, or REPLs. Don't use when: a command finishes quickly; run it in the foreground.",
+        ),
         (
             "file_read",
             "Read file contents. Use when: inspecting project files, configs, logs. Don't use when: a targeted search is enough.",
@@ -1563,6 +1993,22 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "file_write",
             "Write file contents. Use when: applying focused edits, scaffolding files, updating docs/code. Don't use when: side effects are unclear or file ownership is uncertain.",
         ),
+        (
+            "file_edit",
+            "Edit an existing file by exact string replacement or unified diff. Use when: changing a few lines of a larger file. Don't use when: creating a new file or rewriting it wholesale.",
+        ),
+        (
+            "glob",
+            "Find files by glob pattern (respects .gitignore). Use when: locating files by name or extension. Don't use when: you already know the exact path.",
+        ),
+        (
+            "content_search",
+            "Regex search across workspace files with optional context lines (respects .gitignore). Use when: finding definitions, usages, or strings. Don't use when: you need the whole file; use file_read.",
+        ),
+        (
+            "list_dir",
+            "List directory contents, optionally as a tree. Use when: exploring project layout. Don't use when: searching for a specific file; use glob.",
+        ),
         (
             "memory_store",
             "Save to memory. Use when: preserving durable preferences, decisions, key context. Don't use when: information is transient/noisy/sensitive without need.",
@@ -1589,6 +2035,18 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "Execute actions on 1000+ apps via Composio (Gmail, Notion, GitHub, Slack, etc.). Use action='list' to discover, 'execute' to run (optionally with connected_account_id), 'connect' to OAuth.",
         ));
     }
+    if !config.memory.obsidian.vault_path.trim().is_empty() {
+        tool_descs.push((
+            "obsidian_notes",
+            "Create, append to, link ([[wikilinks]]) or read notes in the team's Obsidian vault. Use memory_recall to search the vault first.",
+        ));
+    }
+    if config.home_assistant.enabled {
+        tool_descs.push((
+            "home_assistant",
+            "Query and control Home Assistant (list_entities/list_areas/get_state/call_service/history) on allowlisted entities. Lock/alarm services need approved=true.",
+        ));
+    }
     tool_descs.push((
         "schedule",
         "Manage scheduled tasks (create/list/get/cancel/pause/resume). Supports recurring cron and one-shot delays.",
@@ -1619,7 +2077,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
     );
     system_prompt.push_str(&build_tool_instructions(tools_registry.as_ref()));
 
-    if !skills.is_empty() {
+    if previous.is_none() && !skills.is_empty() {
         println!(
             "  🧩 Skills:   {}",
             skills
@@ -1630,36 +2088,104 @@ pub async fn start_channels(config: Config) -> Result<()> {
         );
     }
 
-    // Collect active channels
+    let transcriber: Option<Arc<dyn Transcriber>> =
+        transcription::create_transcriber(&config.transcription)?.map(Arc::from);
+    let synthesizer: Option<Arc<dyn SpeechSynthesizer>> =
+        tts::create_synthesizer(&config.tts)?.map(Arc::from);
+
+    let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
+    provider_cache_seed.insert(provider_name.clone(), Arc::clone(&provider));
+
+    Ok(ChannelRuntimeContext {
+        channels_by_name: Arc::new(HashMap::new()),
+        provider: Arc::clone(&provider),
+        default_provider: Arc::new(provider_name),
+        memory: Arc::clone(&mem),
+        tools_registry: Arc::clone(&tools_registry),
+        processes,
+        observer,
+        system_prompt: Arc::new(system_prompt),
+        model: Arc::new(model),
+        temperature,
+        auto_save_memory: config.memory.auto_save,
+        max_tool_iterations: config.agent.max_tool_iterations,
+        context_windows: Arc::new(ContextWindowRegistry::from_config(config)),
+        min_relevance_score: config.memory.min_relevance_score,
+        conversation_histories: previous.map_or_else(
+            || Arc::new(Mutex::new(HashMap::new())),
+            |previous| Arc::clone(&previous.conversation_histories),
+        ),
+        provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
+        route_overrides: previous.map_or_else(
+            || Arc::new(Mutex::new(HashMap::new())),
+            |previous| Arc::clone(&previous.route_overrides),
+        ),
+        api_key: config.api_key.clone(),
+        api_url: config.api_url.clone(),
+        reliability: Arc::new(config.reliability.clone()),
+        provider_runtime_options,
+        workspace_dir: Arc::new(config.workspace_dir.clone()),
+        transcriber,
+        transcription_config: Arc::new(config.transcription.clone()),
+        synthesizer,
+        tts_config: Arc::new(config.tts.clone()),
+        voice_reply_preferences: previous.map_or_else(
+            || Arc::new(VoiceReplyPreferences::default()),
+            |previous| Arc::clone(&previous.voice_reply_preferences),
+        ),
+        autonomy: Arc::new(config.autonomy.clone()),
+        users: previous.map_or_else(
+            || Arc::new(UserDirectory::new(&config.workspace_dir)),
+            |previous| Arc::clone(&previous.users),
+        ),
+    })
+}
+
+/// Inbox for downloaded voice notes, when a transcriber is configured.
+fn channel_voice_inbox(ctx: &ChannelRuntimeContext) -> Option<PathBuf> {
+    ctx.transcriber
+        .as_ref()
+        .map(|_| transcription::voice_inbox_dir(&ctx.workspace_dir))
+}
+
+/// Instantiate every channel enabled in `config`.
+fn configured_channels(config: &Config, voice_inbox: Option<&PathBuf>) -> Vec<Arc<dyn Channel>> {
     let mut channels: Vec<Arc<dyn Channel>> = Vec::new();
+    // Bounds the `[FILE:...]` attachments that Matrix and email replies may send.
+    let workspace = Arc::new(SecurityPolicy::from_config(
+        &config.autonomy,
+        &config.workspace_dir,
+    ));
 
     if let Some(ref tg) = config.channels_config.telegram {
-        channels.push(Arc::new(
-            TelegramChannel::new(
-                tg.bot_token.clone(),
-                tg.allowed_users.clone(),
-                tg.mention_only,
-            )
-            .with_streaming(tg.stream_mode, tg.draft_update_interval_ms),
-        ));
+        let mut telegram = TelegramChannel::new(
+            tg.bot_token.clone(),
+            tg.allowed_users.clone(),
+            tg.mention_only,
+        )
+        .with_streaming(tg.stream_mode, tg.draft_update_interval_ms);
+        if let Some(inbox) = voice_inbox {
+            telegram = telegram.with_voice_inbox(inbox.clone());
+        }
+        channels.push(Arc::new(telegram));
     }
 
     if let Some(ref dc) = config.channels_config.discord {
-        channels.push(Arc::new(DiscordChannel::new(
-            dc.bot_token.clone(),
-            dc.guild_id.clone(),
-            dc.allowed_users.clone(),
-            dc.listen_to_bots,
-            dc.mention_only,
-        )));
+        channels.push(Arc::new(
+            DiscordChannel::new(
+                dc.bot_token.clone(),
+                dc.guild_id.clone(),
+                dc.allowed_users.clone(),
+                dc.listen_to_bots,
+                dc.mention_only,
+            )
+            .with_slash_commands(dc.slash_commands, dc.command_permissions.clone())
+            .with_approvals(&config.autonomy, dc.approval_timeout_secs),
+        ));
     }
 
     if let Some(ref sl) = config.channels_config.slack {
-        channels.push(Arc::new(SlackChannel::new(
-            sl.bot_token.clone(),
-            sl.channel_id.clone(),
-            sl.allowed_users.clone(),
-        )));
+        channels.push(Arc::new(SlackChannel::from_config(sl)));
     }
 
     if let Some(ref mm) = config.channels_config.mattermost {
@@ -1678,14 +2204,19 @@ pub async fn start_channels(config: Config) -> Result<()> {
     }
 
     if let Some(ref mx) = config.channels_config.matrix {
-        channels.push(Arc::new(MatrixChannel::new_with_session_hint(
-            mx.homeserver.clone(),
-            mx.access_token.clone(),
-            mx.room_id.clone(),
-            mx.allowed_users.clone(),
-            mx.user_id.clone(),
-            mx.device_id.clone(),
-        )));
+        channels.push(Arc::new(
+            MatrixChannel::new_with_session_hint(
+                mx.homeserver.clone(),
+                mx.access_token.clone(),
+                mx.room_id.clone(),
+                mx.allowed_users.clone(),
+                mx.user_id.clone(),
+                mx.device_id.clone(),
+            )
+            .with_config(mx)
+            .with_workspace(Arc::clone(&workspace))
+            .with_approvals(&config.autonomy, mx.approval_timeout_secs),
+        ));
     }
 
     if let Some(ref sig) = config.channels_config.signal {
@@ -1700,16 +2231,22 @@ pub async fn start_channels(config: Config) -> Result<()> {
     }
 
     if let Some(ref wa) = config.channels_config.whatsapp {
-        channels.push(Arc::new(WhatsAppChannel::new(
+        let mut whatsapp = WhatsAppChannel::new(
             wa.access_token.clone(),
             wa.phone_number_id.clone(),
             wa.verify_token.clone(),
             wa.allowed_numbers.clone(),
-        )));
+        );
+        if let Some(inbox) = voice_inbox {
+            whatsapp = whatsapp.with_voice_inbox(inbox.clone());
+        }
+        channels.push(Arc::new(whatsapp));
     }
 
     if let Some(ref email_cfg) = config.channels_config.email {
-        channels.push(Arc::new(EmailChannel::new(email_cfg.clone())));
+        channels.push(Arc::new(
+            EmailChannel::new(email_cfg.clone()).with_workspace(Arc::clone(&workspace)),
+        ));
     }
 
     if let Some(ref irc) = config.channels_config.irc {
@@ -1747,13 +2284,68 @@ pub async fn start_channels(config: Config) -> Result<()> {
         )));
     }
 
+    if let Some(ref nc) = config.channels_config.nextcloud_talk {
+        channels.push(Arc::new(NextcloudTalkChannel::from_config(nc)));
+    }
+
+    if let Some(ref mq) = config.channels_config.mqtt {
+        channels.push(Arc::new(MqttChannel::new(config.mqtt.clone(), mq.clone())));
+    }
+
+    if let Some(ref wc) = config.channels_config.webchat {
+        channels.push(Arc::new(WebChatChannel::new(
+            wc,
+            &config.workspace_dir,
+            &config.autonomy,
+        )));
+    }
+
+    channels
+}
+
+/// Send `text` to `recipient` on the configured channel named `channel`
+/// (e.g. `telegram`), outside of a conversation.
+pub async fn send_to_configured_channel(
+    config: &Config,
+    channel: &str,
+    recipient: &str,
+    text: &str,
+) -> Result<()> {
+    let name = channel.to_ascii_lowercase();
+    let Some(target) = configured_channels(config, None)
+        .into_iter()
+        .find(|ch| ch.name() == name)
+    else {
+        anyhow::bail!("{channel} channel not configured");
+    };
+    target.send(&SendMessage::new(text, recipient)).await
+}
+
+/// Start all configured channels and route messages to the agent.
+/// WebChat is left out: it only works under `zeroclaw daemon`.
+pub async fn start_channels(mut config: Config) -> Result<()> {
+    webchat::disable_outside_daemon(&mut config, "channel start");
+    serve_channels(config, None).await
+}
+
+/// Like [`start_channels`], but applies config reloads published by the
+/// daemon without dropping in-flight conversations.
+pub async fn start_channels_with_reload(config: Config, updates: ConfigWatch) -> Result<()> {
+    serve_channels(config, Some(updates)).await
+}
+
+async fn serve_channels(config: Config, updates: Option<ConfigWatch>) -> Result<()> {
+    let mut runtime = build_runtime_context(&config, None).await?;
+    let voice_inbox = channel_voice_inbox(&runtime);
+    let channels = configured_channels(&config, voice_inbox.as_ref());
+
     if channels.is_empty() {
         println!("No channels configured. Run `zeroclaw onboard` to set up channels.");
         return Ok(());
     }
 
     println!("🦀 ZeroClaw Channel Server");
-    println!("  🤖 Model:    {model}");
+    println!("  🤖 Model:    {}", runtime.model);
     let effective_backend = memory::effective_memory_backend_name(
         &config.memory.backend,
         Some(&config.storage.provider.config),
@@ -1763,6 +2355,12 @@ pub async fn start_channels(config: Config) -> Result<()> {
         effective_backend,
         if config.memory.auto_save { "on" } else { "off" }
     );
+    if let Some(ref transcriber) = runtime.transcriber {
+        println!("  🎙️ Voice:    transcription via {}", transcriber.name());
+    }
+    if let Some(ref synthesizer) = runtime.synthesizer {
+        println!("  🔊 Speech:   voice replies via {}", synthesizer.name());
+    }
     println!(
         "  📡 Channels: {}",
         channels
@@ -1790,61 +2388,133 @@ pub async fn start_channels(config: Config) -> Result<()> {
     let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(100);
 
     // Spawn a listener for each channel
-    let mut handles = Vec::new();
+    let mut listeners = HashMap::new();
     for ch in &channels {
-        handles.push(spawn_supervised_listener(
-            ch.clone(),
-            tx.clone(),
-            initial_backoff_secs,
-            max_backoff_secs,
-        ));
+        listeners.insert(
+            ch.name().to_string(),
+            spawn_supervised_listener(
+                ch.clone(),
+                tx.clone(),
+                initial_backoff_secs,
+                max_backoff_secs,
+            ),
+        );
     }
-    drop(tx); // Drop our copy so rx closes when all channels stop
 
-    let channels_by_name = Arc::new(
-        channels
-            .iter()
-            .map(|ch| (ch.name().to_string(), Arc::clone(ch)))
-            .collect::<HashMap<_, _>>(),
-    );
-    let max_in_flight_messages = compute_max_in_flight_messages(channels.len());
+    runtime.channels_by_name = Arc::new(
+        channels
+            .iter()
+            .map(|ch| (ch.name().to_string(), Arc::clone(ch)))
+            .collect::<HashMap<_, _>>(),
+    );
+    let max_in_flight_messages = compute_max_in_flight_messages(channels.len());
+
+    println!("  🚦 In-flight message limit: {max_in_flight_messages}");
+
+    let (contexts_tx, contexts) = tokio::sync::watch::channel(Arc::new(runtime));
+
+    let Some(updates) = updates else {
+        drop(tx); // Drop our copy so rx closes when all channels stop
+        run_message_dispatch_loop(rx, contexts, max_in_flight_messages).await;
+
+        // Wait for all channel tasks
+        for (_, h) in listeners {
+            let _ = h.await;
+        }
+        return Ok(());
+    };
+
+    let reloader = ChannelReloader {
+        config,
+        contexts: contexts_tx,
+        listeners,
+        tx,
+        initial_backoff_secs,
+        max_backoff_secs,
+    };
+    let reloader = tokio::spawn(reloader.run(updates));
+    run_message_dispatch_loop(rx, contexts, max_in_flight_messages).await;
+    reloader.abort();
+
+    Ok(())
+}
+
+/// Applies daemon config reloads to a running channel server.
+///
+/// Messages already being processed keep the runtime they started with; new
+/// messages see the rebuilt one. Only channels whose `[channels_config.*]`
+/// settings changed are restarted.
+struct ChannelReloader {
+    config: Config,
+    contexts: tokio::sync::watch::Sender<Arc<ChannelRuntimeContext>>,
+    listeners: HashMap<String, tokio::task::JoinHandle<()>>,
+    tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+    initial_backoff_secs: u64,
+    max_backoff_secs: u64,
+}
 
-    println!("  🚦 In-flight message limit: {max_in_flight_messages}");
+impl ChannelReloader {
+    async fn run(mut self, mut updates: ConfigWatch) {
+        while updates.changed().await.is_ok() {
+            let next = updates.borrow_and_update().as_ref().clone();
+            if let Err(e) = self.apply(next).await {
+                tracing::error!("Channel runtime kept its previous config; reload failed: {e:#}");
+            }
+        }
+    }
 
-    let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
-    provider_cache_seed.insert(provider_name.clone(), Arc::clone(&provider));
+    async fn apply(&mut self, next: Config) -> Result<()> {
+        let changed = reload::changed_sections(&self.config, &next);
+        if changed.is_empty() {
+            return Ok(());
+        }
 
-    let runtime_ctx = Arc::new(ChannelRuntimeContext {
-        channels_by_name,
-        provider: Arc::clone(&provider),
-        default_provider: Arc::new(provider_name),
-        memory: Arc::clone(&mem),
-        tools_registry: Arc::clone(&tools_registry),
-        observer,
-        system_prompt: Arc::new(system_prompt),
-        model: Arc::new(model.clone()),
-        temperature,
-        auto_save_memory: config.memory.auto_save,
-        max_tool_iterations: config.agent.max_tool_iterations,
-        min_relevance_score: config.memory.min_relevance_score,
-        conversation_histories: Arc::new(Mutex::new(HashMap::new())),
-        provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
-        route_overrides: Arc::new(Mutex::new(HashMap::new())),
-        api_key: config.api_key.clone(),
-        api_url: config.api_url.clone(),
-        reliability: Arc::new(config.reliability.clone()),
-        provider_runtime_options,
-        workspace_dir: Arc::new(config.workspace_dir.clone()),
-    });
+        let previous = self.contexts.borrow().clone();
+        let mut runtime = build_runtime_context(&next, Some(&previous)).await?;
+        let rebuilt = configured_channels(&next, channel_voice_inbox(&runtime).as_ref());
 
-    run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
+        let mut restart = reload::changed_channels(&changed);
+        if changed.iter().any(|section| section == "transcription") {
+            // Voice inbox wiring is fixed at channel construction.
+            restart.extend(["telegram".to_string(), "whatsapp".to_string()]);
+        }
+        restart.sort();
+        restart.dedup();
+
+        let mut channels = previous.channels_by_name.as_ref().clone();
+        for name in restart {
+            let replacement = rebuilt.iter().find(|ch| ch.name() == name);
+            if let Some(handle) = self.listeners.remove(&name) {
+                handle.abort();
+            } else if replacement.is_none() {
+                continue;
+            }
+            channels.remove(&name);
+
+            match replacement {
+                Some(ch) => {
+                    self.listeners.insert(
+                        name.clone(),
+                        spawn_supervised_listener(
+                            Arc::clone(ch),
+                            self.tx.clone(),
+                            self.initial_backoff_secs,
+                            self.max_backoff_secs,
+                        ),
+                    );
+                    channels.insert(name.clone(), Arc::clone(ch));
+                    tracing::info!("Restarted channel '{name}' with updated settings");
+                }
+                None => tracing::info!("Stopped channel '{name}'; it was removed from config"),
+            }
+        }
 
-    // Wait for all channel tasks
-    for h in handles {
-        let _ = h.await;
+        runtime.channels_by_name = Arc::new(channels);
+        self.contexts.send_replace(Arc::new(runtime));
+        self.config = next;
+        tracing::info!("Channel runtime reloaded: {}", changed.join(", "));
+        Ok(())
     }
-
-    Ok(())
 }
 
 #[cfg(test)]
@@ -1954,6 +2624,65 @@ mod tests {
         }
     }
 
+    #[derive(Default)]
+    struct VoiceRecordingChannel {
+        sent_messages: tokio::sync::Mutex<Vec<String>>,
+        voice_replies: tokio::sync::Mutex<Vec<String>>,
+        fail_voice: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl Channel for VoiceRecordingChannel {
+        fn name(&self) -> &str {
+            "telegram"
+        }
+
+        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+            self.sent_messages
+                .lock()
+                .await
+                .push(format!("{}:{}", message.recipient, message.content));
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn supports_voice_replies(&self) -> bool {
+            true
+        }
+
+        async fn send_voice_reply(&self, recipient: &str, audio_path: &Path) -> anyhow::Result<()> {
+            if self.fail_voice {
+                anyhow::bail!("upload rejected");
+            }
+            let spoken = tokio::fs::read_to_string(audio_path).await?;
+            self.voice_replies
+                .lock()
+                .await
+                .push(format!("{recipient}:{spoken}"));
+            Ok(())
+        }
+    }
+
+    struct EchoSpeech;
+
+    #[async_trait::async_trait]
+    impl SpeechSynthesizer for EchoSpeech {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        async fn synthesize(&self, text: &str, output_path: &Path) -> anyhow::Result<()> {
+            tokio::fs::write(output_path, text).await?;
+            Ok(())
+        }
+    }
+
     struct SlowProvider {
         delay: Duration,
     }
@@ -2210,12 +2939,14 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            processes: Arc::new(ProcessManager::default()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
             max_tool_iterations: 10,
+            context_windows: Arc::new(ContextWindowRegistry::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -2225,6 +2956,13 @@ mod tests {
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
+            transcriber: None,
+            transcription_config: Arc::new(crate::config::TranscriptionConfig::default()),
+            synthesizer: None,
+            tts_config: Arc::new(crate::config::TtsConfig::default()),
+            voice_reply_preferences: Arc::new(VoiceReplyPreferences::default()),
+            autonomy: Arc::new(crate::config::AutonomyConfig::default()),
+            users: Arc::new(UserDirectory::new(Path::new("/nonexistent"))),
         });
 
         process_channel_message(
@@ -2262,12 +3000,14 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            processes: Arc::new(ProcessManager::default()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
             max_tool_iterations: 10,
+            context_windows: Arc::new(ContextWindowRegistry::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -2277,6 +3017,13 @@ mod tests {
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
+            transcriber: None,
+            transcription_config: Arc::new(crate::config::TranscriptionConfig::default()),
+            synthesizer: None,
+            tts_config: Arc::new(crate::config::TtsConfig::default()),
+            voice_reply_preferences: Arc::new(VoiceReplyPreferences::default()),
+            autonomy: Arc::new(crate::config::AutonomyConfig::default()),
+            users: Arc::new(UserDirectory::new(Path::new("/nonexistent"))),
         });
 
         process_channel_message(
@@ -2323,12 +3070,14 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            processes: Arc::new(ProcessManager::default()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
             max_tool_iterations: 5,
+            context_windows: Arc::new(ContextWindowRegistry::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
@@ -2338,6 +3087,13 @@ mod tests {
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
+            transcriber: None,
+            transcription_config: Arc::new(crate::config::TranscriptionConfig::default()),
+            synthesizer: None,
+            tts_config: Arc::new(crate::config::TtsConfig::default()),
+            voice_reply_preferences: Arc::new(VoiceReplyPreferences::default()),
+            autonomy: Arc::new(crate::config::AutonomyConfig::default()),
+            users: Arc::new(UserDirectory::new(Path::new("/nonexistent"))),
         });
 
         process_channel_message(
@@ -2372,6 +3128,166 @@ mod tests {
         assert_eq!(fallback_provider_impl.call_count.load(Ordering::SeqCst), 0);
     }
 
+    fn voice_reply_test_context(
+        channel: Arc<dyn Channel>,
+        workspace_dir: &Path,
+        reply_mode: crate::config::VoiceReplyMode,
+    ) -> Arc<ChannelRuntimeContext> {
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+        let provider: Arc<dyn Provider> = Arc::new(ModelCaptureProvider::default());
+
+        Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider,
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            processes: Arc::new(ProcessManager::default()),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            context_windows: Arc::new(ContextWindowRegistry::default()),
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(workspace_dir.to_path_buf()),
+            transcriber: None,
+            transcription_config: Arc::new(crate::config::TranscriptionConfig::default()),
+            synthesizer: Some(Arc::new(EchoSpeech)),
+            tts_config: Arc::new(crate::config::TtsConfig {
+                enabled: true,
+                reply_mode,
+                ..crate::config::TtsConfig::default()
+            }),
+            voice_reply_preferences: Arc::new(VoiceReplyPreferences::default()),
+            autonomy: Arc::new(crate::config::AutonomyConfig::default()),
+            users: Arc::new(UserDirectory::new(Path::new("/nonexistent"))),
+        })
+    }
+
+    fn telegram_message(id: &str, content: &str) -> traits::ChannelMessage {
+        traits::ChannelMessage {
+            id: id.to_string(),
+            sender: "alice".to_string(),
+            reply_target: "chat-1".to_string(),
+            content: content.to_string(),
+            channel: "telegram".to_string(),
+            timestamp: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_sends_voice_reply_when_enabled() {
+        let workspace = TempDir::new().unwrap();
+        let channel_impl = Arc::new(VoiceRecordingChannel::default());
+        let runtime_ctx = voice_reply_test_context(
+            channel_impl.clone(),
+            workspace.path(),
+            crate::config::VoiceReplyMode::Always,
+        );
+
+        process_channel_message(runtime_ctx, telegram_message("msg-v1", "hello")).await;
+
+        assert_eq!(*channel_impl.voice_replies.lock().await, vec!["chat-1:ok"]);
+        assert!(channel_impl.sent_messages.lock().await.is_empty());
+        let outbox = tts::tts_outbox_dir(workspace.path());
+        assert_eq!(std::fs::read_dir(outbox).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_falls_back_to_text_when_voice_fails() {
+        let workspace = TempDir::new().unwrap();
+        let channel_impl = Arc::new(VoiceRecordingChannel {
+            fail_voice: true,
+            ..VoiceRecordingChannel::default()
+        });
+        let runtime_ctx = voice_reply_test_context(
+            channel_impl.clone(),
+            workspace.path(),
+            crate::config::VoiceReplyMode::Always,
+        );
+
+        process_channel_message(runtime_ctx, telegram_message("msg-v2", "hello")).await;
+
+        assert!(channel_impl.voice_replies.lock().await.is_empty());
+        assert_eq!(*channel_impl.sent_messages.lock().await, vec!["chat-1:ok"]);
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_voice_command_sets_sender_mode() {
+        let workspace = TempDir::new().unwrap();
+        let channel_impl = Arc::new(VoiceRecordingChannel::default());
+        let runtime_ctx = voice_reply_test_context(
+            channel_impl.clone(),
+            workspace.path(),
+            crate::config::VoiceReplyMode::WhenVoice,
+        );
+
+        process_channel_message(runtime_ctx.clone(), telegram_message("msg-v3", "hello")).await;
+        process_channel_message(runtime_ctx.clone(), telegram_message("msg-v4", "/voice on")).await;
+        process_channel_message(runtime_ctx, telegram_message("msg-v5", "hello again")).await;
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0], "chat-1:ok");
+        assert!(sent[1].contains("Voice replies set to `on`"));
+        assert_eq!(*channel_impl.voice_replies.lock().await, vec!["chat-1:ok"]);
+    }
+
+    #[test]
+    fn parse_runtime_command_recognizes_session_commands() {
+        assert_eq!(
+            parse_runtime_command("discord", "/reset"),
+            Some(ChannelRuntimeCommand::ResetConversation)
+        );
+        assert_eq!(
+            parse_runtime_command("discord", "/memory coffee order"),
+            Some(ChannelRuntimeCommand::ShowMemory("coffee order".into()))
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/cron@zeroclaw_bot"),
+            Some(ChannelRuntimeCommand::ShowCron)
+        );
+        assert_eq!(parse_runtime_command("slack", "/reset"), None);
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_reset_command_clears_history() {
+        let workspace = TempDir::new().unwrap();
+        let channel_impl = Arc::new(VoiceRecordingChannel::default());
+        let runtime_ctx = voice_reply_test_context(
+            channel_impl.clone(),
+            workspace.path(),
+            crate::config::VoiceReplyMode::Off,
+        );
+
+        process_channel_message(runtime_ctx.clone(), telegram_message("msg-r1", "hello")).await;
+        assert!(runtime_ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key("telegram_alice"));
+
+        process_channel_message(runtime_ctx.clone(), telegram_message("msg-r2", "/reset")).await;
+
+        assert!(!runtime_ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key("telegram_alice"));
+        let sent = channel_impl.sent_messages.lock().await;
+        assert!(sent[1].contains("Conversation history cleared"));
+    }
+
     #[tokio::test]
     async fn process_channel_message_uses_route_override_provider_and_model() {
         let channel_impl = Arc::new(TelegramRecordingChannel::default());
@@ -2405,12 +3321,14 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            processes: Arc::new(ProcessManager::default()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
             max_tool_iterations: 5,
+            context_windows: Arc::new(ContextWindowRegistry::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
@@ -2420,6 +3338,13 @@ mod tests {
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
+            transcriber: None,
+            transcription_config: Arc::new(crate::config::TranscriptionConfig::default()),
+            synthesizer: None,
+            tts_config: Arc::new(crate::config::TtsConfig::default()),
+            voice_reply_preferences: Arc::new(VoiceReplyPreferences::default()),
+            autonomy: Arc::new(crate::config::AutonomyConfig::default()),
+            users: Arc::new(UserDirectory::new(Path::new("/nonexistent"))),
         });
 
         process_channel_message(
@@ -2463,12 +3388,14 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            processes: Arc::new(ProcessManager::default()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
             max_tool_iterations: 12,
+            context_windows: Arc::new(ContextWindowRegistry::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -2478,6 +3405,13 @@ mod tests {
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
+            transcriber: None,
+            transcription_config: Arc::new(crate::config::TranscriptionConfig::default()),
+            synthesizer: None,
+            tts_config: Arc::new(crate::config::TtsConfig::default()),
+            voice_reply_preferences: Arc::new(VoiceReplyPreferences::default()),
+            autonomy: Arc::new(crate::config::AutonomyConfig::default()),
+            users: Arc::new(UserDirectory::new(Path::new("/nonexistent"))),
         });
 
         process_channel_message(
@@ -2516,12 +3450,14 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            processes: Arc::new(ProcessManager::default()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
             max_tool_iterations: 3,
+            context_windows: Arc::new(ContextWindowRegistry::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -2531,6 +3467,13 @@ mod tests {
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
+            transcriber: None,
+            transcription_config: Arc::new(crate::config::TranscriptionConfig::default()),
+            synthesizer: None,
+            tts_config: Arc::new(crate::config::TtsConfig::default()),
+            voice_reply_preferences: Arc::new(VoiceReplyPreferences::default()),
+            autonomy: Arc::new(crate::config::AutonomyConfig::default()),
+            users: Arc::new(UserDirectory::new(Path::new("/nonexistent"))),
         });
 
         process_channel_message(
@@ -2620,12 +3563,14 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            processes: Arc::new(ProcessManager::default()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
             max_tool_iterations: 10,
+            context_windows: Arc::new(ContextWindowRegistry::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -2635,6 +3580,13 @@ mod tests {
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
+            transcriber: None,
+            transcription_config: Arc::new(crate::config::TranscriptionConfig::default()),
+            synthesizer: None,
+            tts_config: Arc::new(crate::config::TtsConfig::default()),
+            voice_reply_preferences: Arc::new(VoiceReplyPreferences::default()),
+            autonomy: Arc::new(crate::config::AutonomyConfig::default()),
+            users: Arc::new(UserDirectory::new(Path::new("/nonexistent"))),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -2661,7 +3613,8 @@ mod tests {
         drop(tx);
 
         let started = Instant::now();
-        run_message_dispatch_loop(rx, runtime_ctx, 2).await;
+        let (_contexts_tx, contexts) = tokio::sync::watch::channel(runtime_ctx);
+        run_message_dispatch_loop(rx, contexts, 2).await;
         let elapsed = started.elapsed();
 
         assert!(
@@ -2690,12 +3643,14 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            processes: Arc::new(ProcessManager::default()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
             max_tool_iterations: 10,
+            context_windows: Arc::new(ContextWindowRegistry::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -2705,6 +3660,13 @@ mod tests {
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
+            transcriber: None,
+            transcription_config: Arc::new(crate::config::TranscriptionConfig::default()),
+            synthesizer: None,
+            tts_config: Arc::new(crate::config::TtsConfig::default()),
+            voice_reply_preferences: Arc::new(VoiceReplyPreferences::default()),
+            autonomy: Arc::new(crate::config::AutonomyConfig::default()),
+            users: Arc::new(UserDirectory::new(Path::new("/nonexistent"))),
         });
 
         process_channel_message(
@@ -3006,6 +3968,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn slack_threads_keep_separate_histories() {
+        let message = |reply_target: &str| traits::ChannelMessage {
+            id: "msg_1".into(),
+            sender: "U123".into(),
+            reply_target: reply_target.into(),
+            content: "hi".into(),
+            channel: "slack".into(),
+            timestamp: 1,
+        };
+        let profile = UserProfile::new("alice", "");
+
+        assert_eq!(
+            conversation_history_key(&message("C456:1700000000.000100"), Some(&profile)),
+            "slack_thread_C456:1700000000.000100"
+        );
+        assert_ne!(
+            conversation_history_key(&message("C456:1700000000.000100"), None),
+            conversation_history_key(&message("C456:1700000099.000100"), None)
+        );
+        assert_eq!(
+            conversation_history_key(&message("D789"), None),
+            "slack_U123"
+        );
+    }
+
+    #[test]
+    fn email_threads_keep_separate_histories() {
+        let message = |reply_target: &str| traits::ChannelMessage {
+            id: "msg_1".into(),
+            sender: "alice@example.com".into(),
+            reply_target: reply_target.into(),
+            content: "hi".into(),
+            channel: "email".into(),
+            timestamp: 1,
+        };
+
+        assert_eq!(
+            conversation_history_key(&message("alice@example.com#0123456789ab"), None),
+            "email_thread_alice@example.com#0123456789ab"
+        );
+        assert_eq!(
+            conversation_history_key(&message("alice@example.com"), None),
+            "email_alice@example.com"
+        );
+    }
+
+    #[test]
+    fn matrix_threads_keep_separate_histories() {
+        let message = |reply_target: &str| traits::ChannelMessage {
+            id: "$event".into(),
+            sender: "@alice:matrix.org".into(),
+            reply_target: reply_target.into(),
+            content: "hi".into(),
+            channel: "matrix".into(),
+            timestamp: 1,
+        };
+
+        assert_eq!(
+            conversation_history_key(&message("!room:matrix.org|$root"), None),
+            "matrix_thread_!room:matrix.org|$root"
+        );
+        assert_eq!(
+            conversation_history_key(&message("!room:matrix.org"), None),
+            "matrix_@alice:matrix.org"
+        );
+    }
+
     #[tokio::test]
     async fn autosave_keys_preserve_multiple_conversation_facts() {
         let tmp = TempDir::new().unwrap();
@@ -3059,7 +4089,7 @@ mod tests {
             .await
             .unwrap();
 
-        let context = build_memory_context(&mem, "age", 0.0).await;
+        let context = build_memory_context(&mem, "age", 0.0, None).await;
         assert!(context.contains("[Memory context]"));
         assert!(context.contains("Age is 45"));
     }
@@ -3080,12 +4110,14 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            processes: Arc::new(ProcessManager::default()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
             max_tool_iterations: 5,
+            context_windows: Arc::new(ContextWindowRegistry::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -3095,6 +4127,13 @@ mod tests {
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
+            transcriber: None,
+            transcription_config: Arc::new(crate::config::TranscriptionConfig::default()),
+            synthesizer: None,
+            tts_config: Arc::new(crate::config::TtsConfig::default()),
+            voice_reply_preferences: Arc::new(VoiceReplyPreferences::default()),
+            autonomy: Arc::new(crate::config::AutonomyConfig::default()),
+            users: Arc::new(UserDirectory::new(Path::new("/nonexistent"))),
         });
 
         process_channel_message(
@@ -3141,6 +4180,95 @@ mod tests {
         assert!(calls[1][3].1.contains("follow up"));
     }
 
+    #[tokio::test]
+    async fn memory_context_only_fills_room_left_by_sender_history() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store(
+            "notes",
+            &format!("hello {}", "word ".repeat(1000)),
+            MemoryCategory::Conversation,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let mut histories = HashMap::new();
+        histories.insert(
+            "test-channel_alice".to_string(),
+            vec![
+                ChatMessage::user("earlier"),
+                ChatMessage::assistant("y".repeat(7600)),
+            ],
+        );
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: provider_impl.clone(),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(mem),
+            tools_registry: Arc::new(vec![]),
+            processes: Arc::new(ProcessManager::default()),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            context_windows: Arc::new(ContextWindowRegistry::new(
+                HashMap::from([("test-model".to_string(), 2000)]),
+                0,
+            )),
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(histories)),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            transcriber: None,
+            transcription_config: Arc::new(crate::config::TranscriptionConfig::default()),
+            synthesizer: None,
+            tts_config: Arc::new(crate::config::TtsConfig::default()),
+            voice_reply_preferences: Arc::new(VoiceReplyPreferences::default()),
+            autonomy: Arc::new(crate::config::AutonomyConfig::default()),
+            users: Arc::new(UserDirectory::new(Path::new("/nonexistent"))),
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-a".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-1".to_string(),
+                content: "hello".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 1,
+            },
+        )
+        .await;
+
+        let calls = provider_impl
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let (role, content) = calls[0].last().unwrap();
+        assert_eq!(role, "user");
+        assert!(content.starts_with("[Memory context]"));
+        assert!(content.ends_with("hello"));
+        // With an empty history the memory share alone would allow 800 chars;
+        // the 1900-token sender history leaves room for far less.
+        assert!(content.chars().count() < 400, "got {} chars", content.len());
+    }
+
     // ── AIEOS Identity Tests (Issue #168) ─────────────────────────
 
     #[test]
@@ -3363,4 +4491,12 @@ mod tests {
             .contains("listen boom"));
         assert!(calls.load(Ordering::SeqCst) >= 1);
     }
+
+    #[tokio::test]
+    async fn send_to_configured_channel_rejects_unconfigured_channel() {
+        let err = send_to_configured_channel(&Config::default(), "Telegram", "123", "hi")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Telegram channel not configured"));
+    }
 }
@@ -0,0 +1,299 @@
+use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::{MqttChannelConfig, MqttConfig};
+use crate::mqtt::{topic_matches, validate_filter, validate_topic, MqttClient, MqttOptions, QoS};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::{mpsc, Mutex};
+
+/// Monotonic counter to ensure unique message IDs under burst traffic.
+static MSG_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Our own recent publishes, remembered so a reply topic that the listen
+/// filters also match does not feed the agent its own answers.
+const ECHO_MEMORY: usize = 32;
+
+/// MQTT channel.
+///
+/// Subscribes to the configured topic filters on the `[mqtt]` broker and
+/// turns each message into an agent turn. The reply is published to
+/// `response_topic` (with `{topic}` replaced by the incoming topic), or to the
+/// message's own `reply_to` when the payload is JSON like
+/// `{"text": "...", "reply_to": "devices/42/answer"}` and that topic is in
+/// `mqtt.publish_topics`.
+pub struct MqttChannel {
+    mqtt: MqttConfig,
+    config: MqttChannelConfig,
+    /// Connection owned by `listen`, reused for replies.
+    client: Mutex<Option<MqttClient>>,
+    sent: parking_lot::Mutex<VecDeque<(String, Vec<u8>)>>,
+}
+
+/// Text and optional reply topic of an incoming payload.
+fn parse_payload(payload: &str) -> (String, Option<String>) {
+    if let Ok(Value::Object(object)) = serde_json::from_str::<Value>(payload) {
+        let text = ["text", "message"]
+            .iter()
+            .find_map(|key| object.get(*key).and_then(Value::as_str));
+        if let Some(text) = text {
+            let reply_to = object
+                .get("reply_to")
+                .and_then(Value::as_str)
+                .filter(|topic| validate_topic(topic).is_ok())
+                .map(str::to_string);
+            return (text.trim().to_string(), reply_to);
+        }
+    }
+    (payload.trim().to_string(), None)
+}
+
+impl MqttChannel {
+    pub fn new(mqtt: MqttConfig, config: MqttChannelConfig) -> Self {
+        Self {
+            mqtt,
+            config,
+            client: Mutex::new(None),
+            sent: parking_lot::Mutex::new(VecDeque::new()),
+        }
+    }
+
+    fn qos(&self) -> anyhow::Result<QoS> {
+        QoS::try_from(self.config.qos)
+    }
+
+    fn response_topic(&self, topic: &str) -> String {
+        self.config.response_topic.replace("{topic}", topic)
+    }
+
+    /// Reply topic for an incoming message. A payload's `reply_to` is only
+    /// honored inside `mqtt.publish_topics`, so senders cannot steer replies
+    /// onto arbitrary (e.g. device command) topics.
+    fn reply_target(&self, topic: &str, requested: Option<String>) -> String {
+        match requested {
+            Some(requested)
+                if self
+                    .mqtt
+                    .publish_topics
+                    .iter()
+                    .any(|allowed| topic_matches(allowed, &requested)) =>
+            {
+                requested
+            }
+            Some(requested) => {
+                tracing::warn!(
+                    "MQTT: ignoring reply_to '{requested}' from {topic}; not in mqtt.publish_topics"
+                );
+                self.response_topic(topic)
+            }
+            None => self.response_topic(topic),
+        }
+    }
+
+    /// Forget a message we published; true if it was ours.
+    fn take_echo(&self, topic: &str, payload: &[u8]) -> bool {
+        let mut sent = self.sent.lock();
+        match sent.iter().position(|(t, p)| t == topic && p == payload) {
+            Some(index) => {
+                sent.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn remember_sent(&self, topic: &str, payload: &[u8]) {
+        let mut sent = self.sent.lock();
+        if sent.len() == ECHO_MEMORY {
+            sent.pop_front();
+        }
+        sent.push_back((topic.to_string(), payload.to_vec()));
+    }
+}
+
+#[async_trait]
+impl Channel for MqttChannel {
+    fn name(&self) -> &str {
+        "mqtt"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        let topic = message.recipient.trim();
+        validate_topic(topic)?;
+        let qos = self.qos()?;
+        let payload = message.content.as_bytes();
+        self.remember_sent(topic, payload);
+
+        let live = self
+            .client
+            .lock()
+            .await
+            .clone()
+            .filter(MqttClient::is_connected);
+        if let Some(client) = live {
+            return client
+                .publish(topic, payload, qos, self.config.retain)
+                .await;
+        }
+        // Not listening (e.g. cron delivery): publish on a short connection.
+        let options = MqttOptions::from_config(&self.mqtt, "send")?;
+        let (client, _) = MqttClient::connect(&options).await?;
+        let result = client
+            .publish(topic, payload, qos, self.config.retain)
+            .await;
+        client.disconnect().await;
+        result
+    }
+
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        if self.config.topics.is_empty() {
+            anyhow::bail!("channels_config.mqtt.topics is empty");
+        }
+        let qos = self.qos()?;
+        let mut filters = Vec::new();
+        for filter in &self.config.topics {
+            validate_filter(filter)?;
+            filters.push((filter.clone(), qos));
+        }
+
+        let options = MqttOptions::from_config(&self.mqtt, "channel")?;
+        tracing::info!(
+            "MQTT channel connecting to {}:{} as {}...",
+            options.host,
+            options.port,
+            options.client_id
+        );
+        let (client, mut incoming) = MqttClient::connect(&options).await?;
+        client.subscribe(&filters).await?;
+        *self.client.lock().await = Some(client.clone());
+        tracing::info!(
+            "MQTT channel listening on {}",
+            self.config.topics.join(", ")
+        );
+
+        while let Some(publish) = incoming.recv().await {
+            if self.take_echo(&publish.topic, &publish.payload) {
+                continue;
+            }
+            let (content, reply_to) = parse_payload(&publish.payload_str());
+            if content.is_empty() {
+                continue;
+            }
+            let seq = MSG_SEQ.fetch_add(1, Ordering::Relaxed);
+            let reply_target = self.reply_target(&publish.topic, reply_to);
+            let message = ChannelMessage {
+                id: format!("mqtt_{}_{seq}", chrono::Utc::now().timestamp_millis()),
+                sender: publish.topic,
+                reply_target,
+                content,
+                channel: "mqtt".to_string(),
+                timestamp: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+            };
+            if tx.send(message).await.is_err() {
+                break;
+            }
+        }
+
+        self.client.lock().await.take();
+        client.disconnect().await;
+        anyhow::bail!("MQTT connection closed")
+    }
+
+    async fn health_check(&self) -> bool {
+        if let Some(client) = self.client.lock().await.as_ref() {
+            return client.is_connected();
+        }
+        let Ok(options) = MqttOptions::from_config(&self.mqtt, "health") else {
+            return false;
+        };
+        match MqttClient::connect(&options).await {
+            Ok((client, _)) => {
+                client.disconnect().await;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(response_topic: &str) -> MqttChannel {
+        MqttChannel::new(
+            MqttConfig::default(),
+            MqttChannelConfig {
+                topics: vec!["zeroclaw/in/#".into()],
+                response_topic: response_topic.into(),
+                qos: 1,
+                retain: false,
+            },
+        )
+    }
+
+    #[test]
+    fn payload_text_and_reply_to_are_extracted() {
+        assert_eq!(parse_payload("  hello  "), ("hello".into(), None));
+        assert_eq!(
+            parse_payload(r#"{"text":"status?","reply_to":"devices/42/answer"}"#),
+            ("status?".into(), Some("devices/42/answer".into()))
+        );
+        // Wildcard reply topics are ignored.
+        assert_eq!(
+            parse_payload(r#"{"message":"hi","reply_to":"devices/#"}"#),
+            ("hi".into(), None)
+        );
+        // JSON without a text field is passed through verbatim.
+        assert_eq!(
+            parse_payload(r#"{"temp":21.5}"#),
+            (r#"{"temp":21.5}"#.into(), None)
+        );
+    }
+
+    #[test]
+    fn response_topic_template_uses_incoming_topic() {
+        assert_eq!(
+            channel("{topic}/reply").response_topic("zeroclaw/in/kitchen"),
+            "zeroclaw/in/kitchen/reply"
+        );
+        assert_eq!(channel("zeroclaw/out").response_topic("x"), "zeroclaw/out");
+    }
+
+    #[test]
+    fn reply_to_is_limited_to_publish_topics() {
+        let mut channel = channel("{topic}/reply");
+        channel.mqtt.publish_topics = vec!["devices/+/answer".into()];
+        assert_eq!(
+            channel.reply_target("zeroclaw/in/a", Some("devices/42/answer".into())),
+            "devices/42/answer"
+        );
+        assert_eq!(
+            channel.reply_target("zeroclaw/in/a", Some("home/lock/set".into())),
+            "zeroclaw/in/a/reply"
+        );
+        assert_eq!(
+            channel.reply_target("zeroclaw/in/a", None),
+            "zeroclaw/in/a/reply"
+        );
+    }
+
+    #[test]
+    fn own_publishes_are_recognized_once() {
+        let channel = channel("{topic}/reply");
+        channel.remember_sent("zeroclaw/in/a/reply", b"done");
+        assert!(channel.take_echo("zeroclaw/in/a/reply", b"done"));
+        assert!(!channel.take_echo("zeroclaw/in/a/reply", b"done"));
+    }
+
+    #[test]
+    fn mqtt_channel_config_defaults() {
+        let parsed: MqttChannelConfig = toml::from_str(r#"topics = ["zeroclaw/in/#"]"#).unwrap();
+        assert_eq!(parsed.response_topic, "{topic}/reply");
+        assert_eq!(parsed.qos, 1);
+        assert!(!parsed.retain);
+    }
+}
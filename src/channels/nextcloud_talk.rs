@@ -0,0 +1,486 @@
+use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::NextcloudTalkConfig;
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use parking_lot::Mutex;
+use sha2::Sha256;
+use std::collections::{HashMap, HashSet};
+
+/// Reaction shown on the user's message while the agent is working.
+const TYPING_REACTION: &str = "👀";
+/// Talk rejects chat messages longer than this many characters.
+const MAX_MESSAGE_CHARS: usize = 32_000;
+
+/// Nextcloud Talk channel — uses the Talk bot webhook API
+///
+/// Like WhatsApp, this channel is push-based: Nextcloud posts signed chat
+/// events to the gateway's `/nextcloud-talk` endpoint, and replies and
+/// reactions are sent back through the bot API, signed with the same secret.
+pub struct NextcloudTalkChannel {
+    base_url: String,
+    bot_secret: String,
+    allowed_rooms: Vec<String>,
+    mention_only: bool,
+    bot_name: String,
+    /// Latest accepted message per room; typing reactions are put on it.
+    last_message: Mutex<HashMap<String, String>>,
+    /// Rooms whose latest message currently carries the typing reaction.
+    reacting: Mutex<HashSet<String>>,
+}
+
+impl NextcloudTalkChannel {
+    pub fn from_config(config: &NextcloudTalkConfig) -> Self {
+        let bot_secret = std::env::var("ZEROCLAW_NEXTCLOUD_TALK_BOT_SECRET")
+            .ok()
+            .map(|secret| secret.trim().to_string())
+            .filter(|secret| !secret.is_empty())
+            .unwrap_or_else(|| config.bot_secret.trim().to_string());
+        Self {
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            bot_secret,
+            allowed_rooms: config.allowed_rooms.clone(),
+            mention_only: config.mention_only,
+            bot_name: config.bot_name.clone(),
+            last_message: Mutex::new(HashMap::new()),
+            reacting: Mutex::new(HashSet::new()),
+        }
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client("channel.nextcloud_talk")
+    }
+
+    fn is_room_allowed(&self, room: &str) -> bool {
+        self.allowed_rooms.iter().any(|r| r == "*" || r == room)
+    }
+
+    fn mac(&self) -> Option<Hmac<Sha256>> {
+        Hmac::<Sha256>::new_from_slice(self.bot_secret.as_bytes()).ok()
+    }
+
+    /// Verify a webhook (`X-Nextcloud-Talk-Random` and
+    /// `X-Nextcloud-Talk-Signature` headers): HMAC-SHA256 of random + body.
+    pub fn verify_signature(&self, random: &str, body: &[u8], signature: &str) -> bool {
+        if self.bot_secret.is_empty() || random.is_empty() {
+            return false;
+        }
+        let Ok(expected) = hex::decode(signature.trim()) else {
+            return false;
+        };
+        let Some(mut mac) = self.mac() else {
+            return false;
+        };
+        mac.update(random.as_bytes());
+        mac.update(body);
+        mac.verify_slice(&expected).is_ok()
+    }
+
+    /// Sign an outgoing bot request; Talk signs the message or reaction text,
+    /// not the JSON body.
+    fn sign(&self, random: &str, text: &str) -> String {
+        let Some(mut mac) = self.mac() else {
+            return String::new();
+        };
+        mac.update(random.as_bytes());
+        mac.update(text.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn bot_url(&self, room: &str, path: &str) -> String {
+        format!(
+            "{}/ocs/v2.php/apps/spreed/api/v1/bot/{room}/{path}",
+            self.base_url
+        )
+    }
+
+    async fn signed_request(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        signed_text: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        let random = hex::encode(rand::random::<[u8; 32]>());
+        let resp = self
+            .http_client()
+            .request(method, url)
+            .header("OCS-APIRequest", "true")
+            .header("Accept", "application/json")
+            .header("X-Nextcloud-Talk-Bot-Random", &random)
+            .header(
+                "X-Nextcloud-Talk-Bot-Signature",
+                self.sign(&random, signed_text),
+            )
+            .json(body)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let error_body = resp.text().await.unwrap_or_default();
+            tracing::error!("Nextcloud Talk request failed: {status} — {error_body}");
+            anyhow::bail!("Nextcloud Talk API error: {status}");
+        }
+        Ok(())
+    }
+
+    /// Parse a Talk bot webhook (Activity Streams `Create` of a chat message)
+    /// into channel messages, applying the room allowlist and mention-only mode.
+    pub fn parse_webhook_payload(&self, payload: &serde_json::Value) -> Vec<ChannelMessage> {
+        let str_at = |pointer: &str| payload.pointer(pointer).and_then(|v| v.as_str());
+
+        // Joins, leaves and messages from other bots are ignored.
+        if str_at("/type") != Some("Create")
+            || str_at("/actor/type") != Some("Person")
+            || str_at("/object/name") != Some("message")
+        {
+            return Vec::new();
+        }
+        let (Some(room), Some(message_id), Some(actor)) = (
+            str_at("/target/id"),
+            str_at("/object/id"),
+            str_at("/actor/id"),
+        ) else {
+            return Vec::new();
+        };
+
+        if !self.is_room_allowed(room) {
+            tracing::warn!("Nextcloud Talk: ignoring message from unauthorized room: {room}");
+            return Vec::new();
+        }
+
+        let Some(text) = str_at("/object/content").and_then(render_message) else {
+            return Vec::new();
+        };
+        let content = if self.mention_only {
+            match strip_mention(&text, &self.bot_name) {
+                Some(content) => content,
+                None => return Vec::new(),
+            }
+        } else {
+            strip_mention(&text, &self.bot_name).unwrap_or(text)
+        };
+        if content.is_empty() {
+            return Vec::new();
+        }
+
+        self.last_message
+            .lock()
+            .insert(room.to_string(), message_id.to_string());
+
+        vec![ChannelMessage {
+            id: message_id.to_string(),
+            sender: actor.strip_prefix("users/").unwrap_or(actor).to_string(),
+            reply_target: room.to_string(),
+            content,
+            channel: "nextcloud_talk".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        }]
+    }
+}
+
+/// Decode the rich-object message Talk puts in `object.content`, replacing
+/// `{placeholder}` parameters with their display names (mentions become `@name`).
+fn render_message(content: &str) -> Option<String> {
+    let parsed: serde_json::Value = serde_json::from_str(content).ok()?;
+    let mut text = parsed.get("message")?.as_str()?.to_string();
+    if let Some(params) = parsed.get("parameters").and_then(|p| p.as_object()) {
+        for (key, param) in params {
+            let Some(name) = param.get("name").and_then(|n| n.as_str()) else {
+                continue;
+            };
+            let replacement = if key.starts_with("mention-") {
+                format!("@{name}")
+            } else {
+                name.to_string()
+            };
+            text = text.replace(&format!("{{{key}}}"), &replacement);
+        }
+    }
+    Some(text)
+}
+
+/// Remove `@bot_name` from `text`, or `None` if the bot is not mentioned.
+fn strip_mention(text: &str, bot_name: &str) -> Option<String> {
+    let mention = format!("@{}", bot_name.to_lowercase());
+    let start = text.to_lowercase().find(&mention)?;
+    // Lowercasing can change byte lengths; only strip when offsets line up.
+    let end = start + mention.len();
+    if !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+        return Some(text.trim().to_string());
+    }
+    let mut stripped = String::with_capacity(text.len());
+    stripped.push_str(&text[..start]);
+    stripped.push_str(&text[end..]);
+    Some(stripped.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+#[async_trait]
+impl Channel for NextcloudTalkChannel {
+    fn name(&self) -> &str {
+        "nextcloud_talk"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        let url = self.bot_url(&message.recipient, "message");
+        let chars: Vec<char> = message.content.chars().collect();
+        for chunk in chars.chunks(MAX_MESSAGE_CHARS) {
+            let text: String = chunk.iter().collect();
+            let body = serde_json::json!({
+                "message": text,
+                "referenceId": hex::encode(rand::random::<[u8; 32]>()),
+            });
+            self.signed_request(reqwest::Method::POST, &url, &text, &body)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        // Talk bots are webhook-based; messages arrive at the gateway's
+        // /nextcloud-talk endpoint. This keeps the channel "alive".
+        tracing::info!(
+            "Nextcloud Talk channel active (webhook mode). \
+            Install the bot with its URL pointing at your gateway's /nextcloud-talk endpoint."
+        );
+
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(3600)).await;
+        }
+    }
+
+    async fn health_check(&self) -> bool {
+        let url = format!("{}/status.php", self.base_url);
+        self.http_client()
+            .get(&url)
+            .send()
+            .await
+            .map(|r| r.status().is_success())
+            .unwrap_or(false)
+    }
+
+    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        let Some(message_id) = self.last_message.lock().get(recipient).cloned() else {
+            return Ok(());
+        };
+        // The typing task calls this repeatedly; react only once per reply.
+        if !self.reacting.lock().insert(recipient.to_string()) {
+            return Ok(());
+        }
+        let url = self.bot_url(recipient, &format!("reaction/{message_id}"));
+        let body = serde_json::json!({ "reaction": TYPING_REACTION });
+        self.signed_request(reqwest::Method::POST, &url, TYPING_REACTION, &body)
+            .await
+    }
+
+    async fn stop_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        if !self.reacting.lock().remove(recipient) {
+            return Ok(());
+        }
+        let Some(message_id) = self.last_message.lock().get(recipient).cloned() else {
+            return Ok(());
+        };
+        let url = self.bot_url(recipient, &format!("reaction/{message_id}"));
+        let body = serde_json::json!({ "reaction": TYPING_REACTION });
+        self.signed_request(reqwest::Method::DELETE, &url, TYPING_REACTION, &body)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::Path, http::HeaderMap, routing::post, Json, Router};
+    use std::sync::Arc;
+
+    fn config(base_url: &str) -> NextcloudTalkConfig {
+        NextcloudTalkConfig {
+            base_url: base_url.into(),
+            bot_secret: "s3cret-s3cret-s3cret-s3cret-s3cret-s3cret".into(),
+            allowed_rooms: vec!["room1".into()],
+            mention_only: false,
+            bot_name: "ZeroClaw".into(),
+        }
+    }
+
+    fn make_channel() -> NextcloudTalkChannel {
+        NextcloudTalkChannel::from_config(&config("https://cloud.example.com/"))
+    }
+
+    fn payload(room: &str, message: &str, parameters: serde_json::Value) -> serde_json::Value {
+        let content = serde_json::json!({"message": message, "parameters": parameters});
+        serde_json::json!({
+            "type": "Create",
+            "actor": {"type": "Person", "id": "users/alice", "name": "Alice"},
+            "object": {
+                "type": "Note",
+                "id": "1567",
+                "name": "message",
+                "content": content.to_string(),
+                "mediaType": "text/markdown"
+            },
+            "target": {"type": "Collection", "id": room, "name": "Team"}
+        })
+    }
+
+    #[test]
+    fn nextcloud_talk_channel_name() {
+        assert_eq!(make_channel().name(), "nextcloud_talk");
+    }
+
+    #[test]
+    fn verify_signature_accepts_valid_and_rejects_tampered() {
+        let ch = make_channel();
+        let body = br#"{"type":"Create"}"#;
+        let random = "a".repeat(64);
+        let mut mac = Hmac::<Sha256>::new_from_slice(ch.bot_secret.as_bytes()).unwrap();
+        mac.update(random.as_bytes());
+        mac.update(body);
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        assert!(ch.verify_signature(&random, body, &signature));
+        assert!(!ch.verify_signature(&random, br#"{"type":"Delete"}"#, &signature));
+        assert!(!ch.verify_signature("other", body, &signature));
+        assert!(!ch.verify_signature(&random, body, "not-hex"));
+        assert!(!ch.verify_signature("", body, &signature));
+    }
+
+    #[test]
+    fn parse_webhook_renders_parameters_and_strips_bot_mention() {
+        let ch = make_channel();
+        let msgs = ch.parse_webhook_payload(&payload(
+            "room1",
+            "{mention-user1} ask {mention-user2} about it",
+            serde_json::json!({
+                "mention-user1": {"type": "user", "id": "bot", "name": "ZeroClaw"},
+                "mention-user2": {"type": "user", "id": "bob", "name": "Bob"}
+            }),
+        ));
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].content, "ask @Bob about it");
+        assert_eq!(msgs[0].sender, "alice");
+        assert_eq!(msgs[0].reply_target, "room1");
+        assert_eq!(msgs[0].id, "1567");
+        assert_eq!(msgs[0].channel, "nextcloud_talk");
+    }
+
+    #[test]
+    fn parse_webhook_applies_room_allowlist() {
+        let ch = make_channel();
+        let msgs = ch.parse_webhook_payload(&payload("other", "hi", serde_json::json!({})));
+        assert!(msgs.is_empty());
+    }
+
+    #[test]
+    fn parse_webhook_ignores_non_person_actors_and_events() {
+        let ch = make_channel();
+        let mut bot = payload("room1", "hi", serde_json::json!({}));
+        bot["actor"]["type"] = "Application".into();
+        assert!(ch.parse_webhook_payload(&bot).is_empty());
+
+        let mut join = payload("room1", "hi", serde_json::json!({}));
+        join["type"] = "Join".into();
+        assert!(ch.parse_webhook_payload(&join).is_empty());
+    }
+
+    #[test]
+    fn mention_only_requires_bot_mention() {
+        let mut cfg = config("https://cloud.example.com");
+        cfg.mention_only = true;
+        let ch = NextcloudTalkChannel::from_config(&cfg);
+        assert!(ch
+            .parse_webhook_payload(&payload("room1", "hello all", serde_json::json!({})))
+            .is_empty());
+        let msgs = ch.parse_webhook_payload(&payload(
+            "room1",
+            "@zeroclaw status?",
+            serde_json::json!({}),
+        ));
+        assert_eq!(msgs[0].content, "status?");
+    }
+
+    #[tokio::test]
+    async fn send_and_typing_reactions_are_signed() {
+        type Seen = Arc<Mutex<Vec<(String, String, bool)>>>;
+        let seen: Seen = Arc::new(Mutex::new(Vec::new()));
+
+        let check = |seen: Seen, path: String, headers: HeaderMap, body: serde_json::Value| {
+            let ch = make_channel();
+            let random = headers["X-Nextcloud-Talk-Bot-Random"].to_str().unwrap();
+            let signature = headers["X-Nextcloud-Talk-Bot-Signature"].to_str().unwrap();
+            let text = body
+                .get("message")
+                .or_else(|| body.get("reaction"))
+                .and_then(|v| v.as_str())
+                .unwrap();
+            let valid = signature == ch.sign(random, text)
+                && headers["OCS-APIRequest"].to_str().unwrap() == "true";
+            seen.lock().push((path, text.to_string(), valid));
+        };
+
+        let message_seen = Arc::clone(&seen);
+        let reaction_seen = Arc::clone(&seen);
+        let app = Router::new()
+            .route(
+                "/ocs/v2.php/apps/spreed/api/v1/bot/{room}/message",
+                post(
+                    move |Path(room): Path<String>,
+                          headers: HeaderMap,
+                          Json(body): Json<serde_json::Value>| async move {
+                        check(message_seen, format!("message:{room}"), headers, body);
+                        Json(serde_json::json!({}))
+                    },
+                ),
+            )
+            .route(
+                "/ocs/v2.php/apps/spreed/api/v1/bot/{room}/reaction/{id}",
+                post({
+                    let seen = Arc::clone(&reaction_seen);
+                    move |Path((_, id)): Path<(String, String)>,
+                          headers: HeaderMap,
+                          Json(body): Json<serde_json::Value>| async move {
+                        check(seen, format!("react:{id}"), headers, body);
+                        Json(serde_json::json!({}))
+                    }
+                })
+                .delete(
+                    move |Path((_, id)): Path<(String, String)>,
+                          headers: HeaderMap,
+                          Json(body): Json<serde_json::Value>| async move {
+                        check(reaction_seen, format!("unreact:{id}"), headers, body);
+                        Json(serde_json::json!({}))
+                    },
+                ),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let ch = NextcloudTalkChannel::from_config(&config(&format!("http://{addr}")));
+        // No inbound message yet: typing is a no-op.
+        ch.start_typing("room1").await.unwrap();
+        ch.parse_webhook_payload(&payload("room1", "hi", serde_json::json!({})));
+        ch.start_typing("room1").await.unwrap();
+        ch.start_typing("room1").await.unwrap();
+        ch.send(&SendMessage::new("Hello!", "room1")).await.unwrap();
+        ch.stop_typing("room1").await.unwrap();
+        ch.stop_typing("room1").await.unwrap();
+
+        let seen = seen.lock().clone();
+        assert_eq!(
+            seen,
+            vec![
+                ("react:1567".to_string(), TYPING_REACTION.to_string(), true),
+                ("message:room1".to_string(), "Hello!".to_string(), true),
+                (
+                    "unreact:1567".to_string(),
+                    TYPING_REACTION.to_string(),
+                    true
+                ),
+            ]
+        );
+    }
+}
@@ -1,22 +1,66 @@
 use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::{SlackConfig, StreamMode};
 use async_trait::async_trait;
-
-/// Slack channel — polls conversations.history via Web API
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Recently delivered message IDs kept for de-duplication. Socket Mode sends
+/// both a `message` and an `app_mention` event when the bot is mentioned.
+const RECENT_EVENT_CAPACITY: usize = 256;
+const SOCKET_RECONNECT_DELAY_SECS: u64 = 1;
+const POLL_INTERVAL_SECS: u64 = 3;
+
+/// Slack channel — receives events over Socket Mode when an app-level token
+/// is configured, otherwise polls conversations.history via Web API.
+///
+/// Reply targets are `channel_id` or `channel_id:thread_ts`, so replies land
+/// in the thread the message came from.
 pub struct SlackChannel {
     bot_token: String,
+    app_token: Option<String>,
     channel_id: Option<String>,
     allowed_users: Vec<String>,
+    /// When true, replies to top-level channel messages start a thread on the
+    /// original message. Direct messages always reply inline.
+    thread_replies: bool,
+    stream_mode: StreamMode,
+    draft_update_interval_ms: u64,
+    last_draft_edit: Mutex<HashMap<String, Instant>>,
 }
 
 impl SlackChannel {
     pub fn new(bot_token: String, channel_id: Option<String>, allowed_users: Vec<String>) -> Self {
         Self {
             bot_token,
+            app_token: None,
             channel_id,
             allowed_users,
+            thread_replies: true,
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            last_draft_edit: Mutex::new(HashMap::new()),
         }
     }
 
+    pub fn from_config(config: &SlackConfig) -> Self {
+        let mut channel = Self::new(
+            config.bot_token.clone(),
+            config.channel_id.clone(),
+            config.allowed_users.clone(),
+        );
+        channel.app_token = config
+            .app_token
+            .clone()
+            .filter(|token| !token.trim().is_empty());
+        channel.thread_replies = config.thread_replies.unwrap_or(true);
+        channel.stream_mode = config.stream_mode;
+        channel.draft_update_interval_ms = config.draft_update_interval_ms;
+        channel
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.slack")
     }
@@ -28,6 +72,19 @@ impl SlackChannel {
         self.allowed_users.iter().any(|u| u == "*" || u == user_id)
     }
 
+    /// Split `channel_id:thread_ts` into its parts.
+    fn parse_reply_target(target: &str) -> (&str, Option<&str>) {
+        match target.split_once(':') {
+            Some((channel, thread_ts)) if !thread_ts.is_empty() => (channel, Some(thread_ts)),
+            _ => (target, None),
+        }
+    }
+
+    /// Direct-message conversation IDs start with `D`.
+    fn is_direct_message(channel_id: &str) -> bool {
+        channel_id.starts_with('D')
+    }
+
     /// Get the bot's own user ID so we can ignore our own messages
     async fn get_bot_user_id(&self) -> Option<String> {
         let resp: serde_json::Value = self
@@ -45,25 +102,19 @@ impl SlackChannel {
             .and_then(|u| u.as_str())
             .map(String::from)
     }
-}
-
-#[async_trait]
-impl Channel for SlackChannel {
-    fn name(&self) -> &str {
-        "slack"
-    }
-
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let body = serde_json::json!({
-            "channel": message.recipient,
-            "text": message.content
-        });
 
+    /// POST a Web API method and return the parsed body, failing on `"ok": false`.
+    async fn api_call(
+        &self,
+        method: &str,
+        token: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.postMessage")
-            .bearer_auth(&self.bot_token)
-            .json(&body)
+            .post(format!("https://slack.com/api/{method}"))
+            .bearer_auth(token)
+            .json(body)
             .send()
             .await?;
 
@@ -74,7 +125,7 @@ impl Channel for SlackChannel {
             .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
 
         if !status.is_success() {
-            anyhow::bail!("Slack chat.postMessage failed ({status}): {body}");
+            anyhow::bail!("Slack {method} failed ({status}): {body}");
         }
 
         // Slack returns 200 for most app-level errors; check JSON "ok" field
@@ -84,13 +135,208 @@ impl Channel for SlackChannel {
                 .get("error")
                 .and_then(|e| e.as_str())
                 .unwrap_or("unknown");
-            anyhow::bail!("Slack chat.postMessage failed: {err}");
+            anyhow::bail!("Slack {method} failed: {err}");
         }
 
-        Ok(())
+        Ok(parsed)
     }
 
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    /// Post `text` to a reply target, returning the new message's `ts`.
+    async fn post_message(&self, recipient: &str, text: &str) -> anyhow::Result<Option<String>> {
+        let (channel, thread_ts) = Self::parse_reply_target(recipient);
+        let mut body = serde_json::json!({
+            "channel": channel,
+            "text": text
+        });
+        if let Some(thread_ts) = thread_ts {
+            body["thread_ts"] = serde_json::Value::String(thread_ts.to_string());
+        }
+
+        let resp = self
+            .api_call("chat.postMessage", &self.bot_token, &body)
+            .await?;
+        Ok(resp.get("ts").and_then(|t| t.as_str()).map(String::from))
+    }
+
+    async fn edit_message(&self, recipient: &str, ts: &str, text: &str) -> anyhow::Result<()> {
+        let (channel, _) = Self::parse_reply_target(recipient);
+        let body = serde_json::json!({
+            "channel": channel,
+            "ts": ts,
+            "text": text
+        });
+        self.api_call("chat.update", &self.bot_token, &body)
+            .await
+            .map(|_| ())
+    }
+
+    /// Turn a Slack message event (Socket Mode or conversations.history) into
+    /// a channel message. `fallback_channel` is used when the event has no
+    /// `channel` field, as in conversations.history results.
+    fn parse_message_event(
+        &self,
+        event: &serde_json::Value,
+        bot_user_id: &str,
+        fallback_channel: Option<&str>,
+    ) -> Option<ChannelMessage> {
+        let event_type = event.get("type").and_then(|t| t.as_str()).unwrap_or("");
+        if event_type != "message" && event_type != "app_mention" {
+            return None;
+        }
+
+        // Edits, deletions, joins and bot posts arrive as subtypes.
+        let subtype = event.get("subtype").and_then(|s| s.as_str());
+        if !matches!(subtype, None | Some("thread_broadcast" | "file_share")) {
+            return None;
+        }
+        if event.get("bot_id").is_some() {
+            return None;
+        }
+
+        let user = event.get("user").and_then(|u| u.as_str()).unwrap_or("");
+        let text = event.get("text").and_then(|t| t.as_str()).unwrap_or("");
+        let ts = event.get("ts").and_then(|t| t.as_str()).unwrap_or("");
+        if user.is_empty() || user == bot_user_id || text.is_empty() || ts.is_empty() {
+            return None;
+        }
+
+        let channel_id = event
+            .get("channel")
+            .and_then(|c| c.as_str())
+            .or(fallback_channel)?;
+        if self
+            .channel_id
+            .as_deref()
+            .is_some_and(|configured| configured != channel_id)
+        {
+            return None;
+        }
+
+        // Sender validation
+        if !self.is_user_allowed(user) {
+            tracing::warn!("Slack: ignoring message from unauthorized user: {user}");
+            return None;
+        }
+
+        // Reply routing:
+        //   - Message inside a thread: stay in that thread.
+        //   - Top-level channel message + thread_replies: thread on the message.
+        //   - Direct messages, or thread_replies=false: reply at channel level.
+        let thread_ts = event
+            .get("thread_ts")
+            .and_then(|t| t.as_str())
+            .filter(|t| !t.is_empty());
+        let reply_target = match thread_ts {
+            Some(thread_ts) => format!("{channel_id}:{thread_ts}"),
+            None if self.thread_replies && !Self::is_direct_message(channel_id) => {
+                format!("{channel_id}:{ts}")
+            }
+            None => channel_id.to_string(),
+        };
+
+        let timestamp = ts
+            .split('.')
+            .next()
+            .and_then(|secs| secs.parse::<u64>().ok())
+            .unwrap_or_else(|| {
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs()
+            });
+
+        Some(ChannelMessage {
+            id: format!("slack_{channel_id}_{ts}"),
+            sender: user.to_string(),
+            reply_target,
+            content: text.to_string(),
+            channel: "slack".to_string(),
+            timestamp,
+        })
+    }
+
+    /// Request a Socket Mode WebSocket URL with the app-level token.
+    async fn open_socket_url(&self, app_token: &str) -> anyhow::Result<String> {
+        let resp = self
+            .api_call("apps.connections.open", app_token, &serde_json::json!({}))
+            .await?;
+        resp.get("url")
+            .and_then(|u| u.as_str())
+            .map(String::from)
+            .ok_or_else(|| anyhow::anyhow!("Slack apps.connections.open returned no url"))
+    }
+
+    /// Receive events over Socket Mode, acknowledging every envelope.
+    /// Reconnects when Slack asks to or the socket drops; returns an error
+    /// only when a new connection cannot be opened.
+    async fn listen_socket_mode(
+        &self,
+        app_token: &str,
+        tx: &tokio::sync::mpsc::Sender<ChannelMessage>,
+    ) -> anyhow::Result<()> {
+        let bot_user_id = self.get_bot_user_id().await.unwrap_or_default();
+        let mut recent: VecDeque<String> = VecDeque::with_capacity(RECENT_EVENT_CAPACITY);
+
+        loop {
+            let url = self.open_socket_url(app_token).await?;
+            let (ws_stream, _) = tokio_tungstenite::connect_async(&url).await?;
+            let (mut write, mut read) = ws_stream.split();
+            tracing::info!("Slack: connected via Socket Mode");
+
+            while let Some(frame) = read.next().await {
+                let text = match frame {
+                    Ok(Message::Text(text)) => text,
+                    Ok(Message::Close(_)) | Err(_) => break,
+                    Ok(_) => continue,
+                };
+                let Ok(envelope) = serde_json::from_str::<serde_json::Value>(&text) else {
+                    continue;
+                };
+
+                // Ack first: Slack redelivers envelopes not acked within 3s.
+                if let Some(envelope_id) = envelope.get("envelope_id").and_then(|id| id.as_str()) {
+                    let ack = serde_json::json!({ "envelope_id": envelope_id });
+                    if write.send(Message::Text(ack.to_string())).await.is_err() {
+                        break;
+                    }
+                }
+
+                match envelope.get("type").and_then(|t| t.as_str()) {
+                    Some("disconnect") => {
+                        let reason = envelope
+                            .get("reason")
+                            .and_then(|r| r.as_str())
+                            .unwrap_or("unknown");
+                        tracing::info!("Slack: Socket Mode disconnect requested ({reason})");
+                        break;
+                    }
+                    Some("events_api") => {}
+                    _ => continue,
+                }
+
+                let Some(msg) = envelope
+                    .pointer("/payload/event")
+                    .and_then(|event| self.parse_message_event(event, &bot_user_id, None))
+                else {
+                    continue;
+                };
+                if !remember_event(&mut recent, &msg.id) {
+                    continue;
+                }
+                if tx.send(msg).await.is_err() {
+                    return Ok(());
+                }
+            }
+
+            tracing::warn!("Slack: Socket Mode connection closed; reconnecting");
+            tokio::time::sleep(std::time::Duration::from_secs(SOCKET_RECONNECT_DELAY_SECS)).await;
+        }
+    }
+
+    async fn listen_polling(
+        &self,
+        tx: &tokio::sync::mpsc::Sender<ChannelMessage>,
+    ) -> anyhow::Result<()> {
         let channel_id = self
             .channel_id
             .clone()
@@ -102,7 +348,7 @@ impl Channel for SlackChannel {
         tracing::info!("Slack channel listening on #{channel_id}...");
 
         loop {
-            tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+            tokio::time::sleep(std::time::Duration::from_secs(POLL_INTERVAL_SECS)).await;
 
             let mut params = vec![("channel", channel_id.clone()), ("limit", "10".to_string())];
             if !last_ts.is_empty() {
@@ -136,40 +382,17 @@ impl Channel for SlackChannel {
                 // Messages come newest-first, reverse to process oldest first
                 for msg in messages.iter().rev() {
                     let ts = msg.get("ts").and_then(|t| t.as_str()).unwrap_or("");
-                    let user = msg
-                        .get("user")
-                        .and_then(|u| u.as_str())
-                        .unwrap_or("unknown");
-                    let text = msg.get("text").and_then(|t| t.as_str()).unwrap_or("");
-
-                    // Skip bot's own messages
-                    if user == bot_user_id {
-                        continue;
-                    }
 
-                    // Sender validation
-                    if !self.is_user_allowed(user) {
-                        tracing::warn!("Slack: ignoring message from unauthorized user: {user}");
+                    // Skip already-seen
+                    if ts <= last_ts.as_str() {
                         continue;
                     }
-
-                    // Skip empty or already-seen
-                    if text.is_empty() || ts <= last_ts.as_str() {
-                        continue;
-                    }
-
                     last_ts = ts.to_string();
 
-                    let channel_msg = ChannelMessage {
-                        id: format!("slack_{channel_id}_{ts}"),
-                        sender: user.to_string(),
-                        reply_target: channel_id.clone(),
-                        content: text.to_string(),
-                        channel: "slack".to_string(),
-                        timestamp: std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_secs(),
+                    let Some(channel_msg) =
+                        self.parse_message_event(msg, &bot_user_id, Some(&channel_id))
+                    else {
+                        continue;
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -179,6 +402,44 @@ impl Channel for SlackChannel {
             }
         }
     }
+}
+
+/// Record `id` as delivered; returns `false` if it was already seen.
+fn remember_event(recent: &mut VecDeque<String>, id: &str) -> bool {
+    if recent.iter().any(|seen| seen == id) {
+        return false;
+    }
+    if recent.len() == RECENT_EVENT_CAPACITY {
+        recent.pop_front();
+    }
+    recent.push_back(id.to_string());
+    true
+}
+
+#[async_trait]
+impl Channel for SlackChannel {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        self.post_message(&message.recipient, &message.content)
+            .await
+            .map(|_| ())
+    }
+
+    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        if let Some(app_token) = self.app_token.as_deref() {
+            match self.listen_socket_mode(app_token, &tx).await {
+                Ok(()) => return Ok(()),
+                Err(e) if self.channel_id.is_some() => {
+                    tracing::warn!("Slack: Socket Mode unavailable ({e}); falling back to polling");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        self.listen_polling(&tx).await
+    }
 
     async fn health_check(&self) -> bool {
         self.http_client()
@@ -189,6 +450,63 @@ impl Channel for SlackChannel {
             .map(|r| r.status().is_success())
             .unwrap_or(false)
     }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        if self.stream_mode == StreamMode::Off {
+            return Ok(None);
+        }
+
+        let initial_text = if message.content.is_empty() {
+            "..."
+        } else {
+            message.content.as_str()
+        };
+        let ts = self.post_message(&message.recipient, initial_text).await?;
+
+        self.last_draft_edit
+            .lock()
+            .insert(message.recipient.clone(), Instant::now());
+
+        Ok(ts)
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        // Rate-limit edits per reply target; chat.update is a Tier 3 method.
+        {
+            let mut last_edits = self.last_draft_edit.lock();
+            if let Some(last_time) = last_edits.get(recipient) {
+                let elapsed = u64::try_from(last_time.elapsed().as_millis()).unwrap_or(u64::MAX);
+                if elapsed < self.draft_update_interval_ms {
+                    return Ok(());
+                }
+            }
+            last_edits.insert(recipient.to_string(), Instant::now());
+        }
+
+        if let Err(e) = self.edit_message(recipient, message_id, text).await {
+            tracing::debug!("Slack draft edit failed: {e}");
+        }
+        Ok(())
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+        self.edit_message(recipient, message_id, text).await
+    }
 }
 
 #[cfg(test)]
@@ -303,4 +621,147 @@ mod tests {
         assert!(!id.contains('-')); // No UUID dashes
         assert!(id.starts_with("slack_"));
     }
+
+    fn threaded_channel() -> SlackChannel {
+        SlackChannel::new("xoxb-fake".into(), None, vec!["*".into()])
+    }
+
+    #[test]
+    fn from_config_reads_socket_mode_and_streaming() {
+        let config: SlackConfig = toml::from_str(
+            r#"
+bot_token = "xoxb-tok"
+app_token = "xapp-tok"
+stream_mode = "partial"
+thread_replies = false
+"#,
+        )
+        .unwrap();
+        let ch = SlackChannel::from_config(&config);
+        assert_eq!(ch.app_token.as_deref(), Some("xapp-tok"));
+        assert!(!ch.thread_replies);
+        assert!(ch.supports_draft_updates());
+        assert!(!SlackChannel::new("xoxb".into(), None, vec![]).supports_draft_updates());
+    }
+
+    #[test]
+    fn parse_reply_target_splits_thread() {
+        assert_eq!(
+            SlackChannel::parse_reply_target("C123:1700000000.000100"),
+            ("C123", Some("1700000000.000100"))
+        );
+        assert_eq!(SlackChannel::parse_reply_target("C123"), ("C123", None));
+    }
+
+    #[test]
+    fn top_level_channel_message_replies_in_new_thread() {
+        let event = serde_json::json!({
+            "type": "message",
+            "channel": "C123",
+            "user": "U111",
+            "text": "hello",
+            "ts": "1700000000.000100"
+        });
+        let msg = threaded_channel()
+            .parse_message_event(&event, "UBOT", None)
+            .unwrap();
+        assert_eq!(msg.reply_target, "C123:1700000000.000100");
+        assert_eq!(msg.id, "slack_C123_1700000000.000100");
+        assert_eq!(msg.timestamp, 1_700_000_000);
+    }
+
+    #[test]
+    fn thread_message_stays_in_thread() {
+        let event = serde_json::json!({
+            "type": "app_mention",
+            "channel": "C123",
+            "user": "U111",
+            "text": "<@UBOT> follow up",
+            "ts": "1700000050.000200",
+            "thread_ts": "1700000000.000100"
+        });
+        let msg = threaded_channel()
+            .parse_message_event(&event, "UBOT", None)
+            .unwrap();
+        assert_eq!(msg.reply_target, "C123:1700000000.000100");
+    }
+
+    #[test]
+    fn direct_messages_and_disabled_threading_reply_inline() {
+        let dm = serde_json::json!({
+            "type": "message",
+            "channel": "D999",
+            "user": "U111",
+            "text": "hi",
+            "ts": "1700000000.000100"
+        });
+        let msg = threaded_channel()
+            .parse_message_event(&dm, "UBOT", None)
+            .unwrap();
+        assert_eq!(msg.reply_target, "D999");
+
+        let mut ch = threaded_channel();
+        ch.thread_replies = false;
+        let polled = serde_json::json!({
+            "type": "message",
+            "user": "U111",
+            "text": "hi",
+            "ts": "1700000000.000100"
+        });
+        let msg = ch
+            .parse_message_event(&polled, "UBOT", Some("C123"))
+            .unwrap();
+        assert_eq!(msg.reply_target, "C123");
+    }
+
+    #[test]
+    fn bot_edited_and_foreign_channel_messages_are_skipped() {
+        let mut ch = threaded_channel();
+        ch.channel_id = Some("C123".into());
+        let base = serde_json::json!({
+            "type": "message",
+            "channel": "C123",
+            "user": "U111",
+            "text": "hi",
+            "ts": "1.0"
+        });
+        assert!(ch.parse_message_event(&base, "UBOT", None).is_some());
+
+        let mut own = base.clone();
+        own["user"] = "UBOT".into();
+        let mut bot = base.clone();
+        bot["bot_id"] = "B1".into();
+        let mut edited = base.clone();
+        edited["subtype"] = "message_changed".into();
+        let mut elsewhere = base.clone();
+        elsewhere["channel"] = "C999".into();
+        for event in [own, bot, edited, elsewhere] {
+            assert!(ch.parse_message_event(&event, "UBOT", None).is_none());
+        }
+    }
+
+    #[test]
+    fn unauthorized_sender_is_skipped() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec!["U111".into()]);
+        let event = serde_json::json!({
+            "type": "message",
+            "channel": "C123",
+            "user": "U333",
+            "text": "hi",
+            "ts": "1.0"
+        });
+        assert!(ch.parse_message_event(&event, "UBOT", None).is_none());
+    }
+
+    #[test]
+    fn remember_event_deduplicates_and_is_bounded() {
+        let mut recent = VecDeque::new();
+        assert!(remember_event(&mut recent, "slack_C1_1.0"));
+        assert!(!remember_event(&mut recent, "slack_C1_1.0"));
+        for i in 0..RECENT_EVENT_CAPACITY {
+            remember_event(&mut recent, &format!("id-{i}"));
+        }
+        assert_eq!(recent.len(), RECENT_EVENT_CAPACITY);
+        assert!(remember_event(&mut recent, "slack_C1_1.0"));
+    }
 }
@@ -7,7 +7,7 @@ use directories::UserDirs;
 use parking_lot::Mutex;
 use reqwest::multipart::{Form, Part};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
@@ -307,6 +307,9 @@ pub struct TelegramChannel {
     last_draft_edit: Mutex<std::collections::HashMap<String, std::time::Instant>>,
     mention_only: bool,
     bot_username: Mutex<Option<String>>,
+    voice_inbox: Option<PathBuf>,
+    /// Voice files downloaded per message id, awaiting transcription.
+    voice_notes: Mutex<std::collections::HashMap<String, Vec<PathBuf>>>,
 }
 
 impl TelegramChannel {
@@ -334,6 +337,8 @@ impl TelegramChannel {
             typing_handle: Mutex::new(None),
             mention_only,
             bot_username: Mutex::new(None),
+            voice_inbox: None,
+            voice_notes: Mutex::new(std::collections::HashMap::new()),
         }
     }
 
@@ -348,6 +353,12 @@ impl TelegramChannel {
         self
     }
 
+    /// Download inbound voice notes and audio files into `dir` so they can be transcribed.
+    pub fn with_voice_inbox(mut self, dir: PathBuf) -> Self {
+        self.voice_inbox = Some(dir);
+        self
+    }
+
     /// Parse reply_target into (chat_id, optional thread_id).
     fn parse_reply_target(reply_target: &str) -> (String, Option<String>) {
         if let Some((chat_id, thread_id)) = reply_target.split_once(':') {
@@ -451,6 +462,110 @@ impl TelegramChannel {
         format!("https://api.telegram.org/bot{}/{method}", self.bot_token)
     }
 
+    fn file_url(&self, file_path: &str) -> String {
+        format!(
+            "https://api.telegram.org/file/bot{}/{file_path}",
+            self.bot_token
+        )
+    }
+
+    /// `file_id` of an inbound voice note or audio file, if any.
+    fn voice_file_id(message: &serde_json::Value) -> Option<&str> {
+        ["voice", "audio"].iter().find_map(|kind| {
+            message
+                .get(*kind)
+                .and_then(|media| media.get("file_id"))
+                .and_then(serde_json::Value::as_str)
+        })
+    }
+
+    /// Download a Telegram file into the voice inbox and return the local path.
+    async fn download_voice_file(
+        &self,
+        file_id: &str,
+        local_name: &str,
+    ) -> anyhow::Result<PathBuf> {
+        let inbox = self
+            .voice_inbox
+            .as_ref()
+            .context("Telegram voice inbox is not configured")?;
+
+        let resp = self
+            .http_client()
+            .post(self.api_url("getFile"))
+            .json(&serde_json::json!({ "file_id": file_id }))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Telegram getFile failed: {}", resp.status());
+        }
+        let data: serde_json::Value = resp.json().await?;
+        let remote_path = data
+            .get("result")
+            .and_then(|r| r.get("file_path"))
+            .and_then(serde_json::Value::as_str)
+            .context("Telegram getFile response missing file_path")?;
+
+        let extension = Path::new(remote_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("ogg");
+
+        let resp = self
+            .http_client()
+            .get(self.file_url(remote_path))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Telegram file download failed: {}", resp.status());
+        }
+        let bytes = resp.bytes().await?;
+
+        tokio::fs::create_dir_all(inbox).await?;
+        let local_path = inbox.join(format!("{local_name}.{extension}"));
+        tokio::fs::write(&local_path, &bytes).await?;
+        Ok(local_path)
+    }
+
+    /// Replace an inbound voice/audio attachment with a local `[VOICE:<path>]` marker.
+    async fn attach_inbound_voice(
+        &self,
+        update: &serde_json::Value,
+        mut msg: ChannelMessage,
+    ) -> ChannelMessage {
+        let Some(file_id) = update
+            .get("message")
+            .and_then(Self::voice_file_id)
+            .filter(|_| self.voice_inbox.is_some())
+        else {
+            return msg;
+        };
+
+        match self.download_voice_file(file_id, &msg.id).await {
+            Ok(path) => {
+                let marker = crate::transcription::voice_marker(&path);
+                msg.content = if msg.content.trim().is_empty() {
+                    marker
+                } else {
+                    format!("{marker}\n{}", msg.content)
+                };
+                self.voice_notes
+                    .lock()
+                    .entry(msg.id.clone())
+                    .or_default()
+                    .push(path);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to download Telegram voice message: {e}");
+                if msg.content.trim().is_empty() {
+                    msg.content = "[Voice message could not be downloaded]".to_string();
+                }
+            }
+        }
+
+        msg
+    }
+
     async fn fetch_bot_username(&self) -> anyhow::Result<String> {
         let resp = self.http_client().get(self.api_url("getMe")).send().await?;
 
@@ -741,7 +856,16 @@ Allowlist Telegram username (without '@') or numeric user ID.",
     fn parse_update_message(&self, update: &serde_json::Value) -> Option<ChannelMessage> {
         let message = update.get("message")?;
 
-        let text = message.get("text").and_then(serde_json::Value::as_str)?;
+        let has_voice = self.voice_inbox.is_some() && Self::voice_file_id(message).is_some();
+        let text = match message.get("text").and_then(serde_json::Value::as_str) {
+            Some(text) => text,
+            // Voice notes carry no text; an optional caption rides along instead.
+            None if has_voice => message
+                .get("caption")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or(""),
+            None => return None,
+        };
 
         let username = message
             .get("from")
@@ -1665,6 +1789,23 @@ impl Channel for TelegramChannel {
         self.send_text_chunks(&content, chat_id, thread_id).await
     }
 
+    fn supports_voice_replies(&self) -> bool {
+        true
+    }
+
+    fn take_voice_notes(&self, message_id: &str) -> Vec<PathBuf> {
+        self.voice_notes
+            .lock()
+            .remove(message_id)
+            .unwrap_or_default()
+    }
+
+    async fn send_voice_reply(&self, recipient: &str, audio_path: &Path) -> anyhow::Result<()> {
+        let (chat_id, thread_id) = Self::parse_reply_target(recipient);
+        self.send_voice(&chat_id, thread_id.as_deref(), audio_path, None)
+            .await
+    }
+
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         let mut offset: i64 = 0;
 
@@ -1748,6 +1889,7 @@ Ensure only one `zeroclaw` process is using this bot token."
                         self.handle_unauthorized_message(update).await;
                         continue;
                     };
+                    let msg = self.attach_inbound_voice(update, msg).await;
                     // Send "typing" indicator immediately when we receive a message
                     let typing_body = serde_json::json!({
                         "chat_id": &msg.reply_target,
@@ -1878,6 +2020,12 @@ mod tests {
         assert!(guard.is_some());
     }
 
+    #[test]
+    fn supports_voice_replies_is_enabled() {
+        let ch = TelegramChannel::new("fake-token".into(), vec!["*".into()], false);
+        assert!(ch.supports_voice_replies());
+    }
+
     #[test]
     fn supports_draft_updates_respects_stream_mode() {
         let off = TelegramChannel::new("fake-token".into(), vec!["*".into()], false);
@@ -2123,6 +2271,49 @@ mod tests {
         assert_eq!(msg.id, "telegram_-100200300_33");
     }
 
+    #[test]
+    fn parse_update_message_accepts_voice_only_with_inbox() {
+        let update = serde_json::json!({
+            "update_id": 1,
+            "message": {
+                "message_id": 34,
+                "voice": { "file_id": "voice-file-1", "duration": 3 },
+                "caption": "from the car",
+                "from": { "id": 555, "username": "alice" },
+                "chat": { "id": 555 }
+            }
+        });
+
+        let ch = TelegramChannel::new("token".into(), vec!["*".into()], false);
+        assert!(ch.parse_update_message(&update).is_none());
+
+        let ch = TelegramChannel::new("token".into(), vec!["*".into()], false)
+            .with_voice_inbox(std::env::temp_dir().join("zeroclaw_voice_test"));
+        let msg = ch
+            .parse_update_message(&update)
+            .expect("voice message should parse when inbox is configured");
+        assert_eq!(msg.content, "from the car");
+        assert_eq!(
+            TelegramChannel::voice_file_id(&update["message"]),
+            Some("voice-file-1")
+        );
+    }
+
+    #[test]
+    fn take_voice_notes_drains_the_message_entry() {
+        let ch = TelegramChannel::new("token".into(), vec!["*".into()], false);
+        ch.voice_notes
+            .lock()
+            .insert("telegram_1".into(), vec![PathBuf::from("/inbox/1.ogg")]);
+
+        assert!(ch.take_voice_notes("telegram_2").is_empty());
+        assert_eq!(
+            ch.take_voice_notes("telegram_1"),
+            vec![PathBuf::from("/inbox/1.ogg")]
+        );
+        assert!(ch.take_voice_notes("telegram_1").is_empty());
+    }
+
     #[test]
     fn parse_update_message_allows_numeric_id_without_username() {
         let ch = TelegramChannel::new("token".into(), vec!["555".into()], false);
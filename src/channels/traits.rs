@@ -100,6 +100,38 @@ pub trait Channel: Send + Sync {
     ) -> anyhow::Result<()> {
         Ok(())
     }
+
+    /// Whether this channel can deliver synthesized voice replies.
+    fn supports_voice_replies(&self) -> bool {
+        false
+    }
+
+    /// Send an OGG/Opus audio file as a voice message.
+    async fn send_voice_reply(
+        &self,
+        _recipient: &str,
+        _audio_path: &std::path::Path,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("{} does not support voice replies", self.name())
+    }
+
+    /// Voice notes downloaded for the message `message_id`. Only these paths
+    /// are transcribed; `[VOICE:...]` markers typed by the sender are ignored.
+    /// Each call drains the list for that message.
+    fn take_voice_notes(&self, _message_id: &str) -> Vec<std::path::PathBuf> {
+        Vec::new()
+    }
+
+    /// Approval manager used for tool calls `sender` triggers in `recipient`.
+    /// Channels that can ask the user interactively return one with a remote
+    /// prompt; `None` skips approval checks.
+    fn approval_manager(
+        &self,
+        _recipient: &str,
+        _sender: &str,
+    ) -> Option<std::sync::Arc<crate::approval::ApprovalManager>> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -162,6 +194,7 @@ mod tests {
         assert!(channel.health_check().await);
         assert!(channel.start_typing("bob").await.is_ok());
         assert!(channel.stop_typing("bob").await.is_ok());
+        assert!(channel.take_voice_notes("1").is_empty());
         assert!(channel
             .send(&SendMessage::new("hello", "bob"))
             .await
@@ -185,6 +218,17 @@ mod tests {
             .is_ok());
     }
 
+    #[tokio::test]
+    async fn default_voice_reply_is_unsupported() {
+        let channel = DummyChannel;
+
+        assert!(!channel.supports_voice_replies());
+        assert!(channel
+            .send_voice_reply("bob", std::path::Path::new("/tmp/reply.ogg"))
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn listen_sends_message_to_channel() {
         let channel = DummyChannel;
@@ -0,0 +1,807 @@
+use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::approval::{
+    summarize_args, ApprovalManager, ApprovalPrompt, ApprovalRequest, ApprovalResponse,
+};
+use crate::config::{AutonomyConfig, Config, WebChatConfig};
+use async_trait::async_trait;
+use base64::Engine;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
+
+/// Workspace-relative folder that receives WebChat uploads.
+const UPLOAD_DIR: &str = "uploads/webchat";
+
+static SHARED_HUB: LazyLock<Arc<WebChatHub>> = LazyLock::new(|| Arc::new(WebChatHub::new()));
+
+/// Hub shared by the gateway (which owns the browser sockets) and the
+/// channel (which feeds the agent) when both run in the daemon.
+pub fn shared_hub() -> Arc<WebChatHub> {
+    Arc::clone(&SHARED_HUB)
+}
+
+/// Drop the WebChat section for a standalone `command` (`zeroclaw gateway` or
+/// `zeroclaw channel start`). The other half would live in another process
+/// and never see this one's hub, so chats would go unanswered. Returns whether
+/// WebChat was configured.
+pub fn disable_outside_daemon(config: &mut Config, command: &str) -> bool {
+    if config.channels_config.webchat.take().is_none() {
+        return false;
+    }
+    tracing::warn!(
+        "WebChat needs the gateway and channels in one process; run `zeroclaw daemon` \
+         to use it. Disabled for `zeroclaw {command}`."
+    );
+    true
+}
+
+/// Stable per-user ID derived from the pairing token, so every tab opened with
+/// the same token shares one conversation.
+pub fn client_id(token: &str, require_pairing: bool) -> String {
+    if !require_pairing {
+        return "local".to_string();
+    }
+    let digest = Sha256::digest(token.as_bytes());
+    format!("web-{}", &hex::encode(digest)[..16])
+}
+
+/// Frame sent from the gateway to the browser.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerFrame {
+    Ready {
+        client: String,
+    },
+    Message {
+        text: String,
+    },
+    Draft {
+        id: String,
+        text: String,
+    },
+    Final {
+        id: String,
+        text: String,
+    },
+    Typing {
+        active: bool,
+    },
+    Approval {
+        id: String,
+        tool: String,
+        summary: String,
+    },
+    Uploaded {
+        path: String,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Frame sent from the browser to the gateway.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientFrame {
+    /// Must be the first frame on a new socket.
+    Auth {
+        token: String,
+    },
+    Message {
+        content: String,
+    },
+    /// File contents are base64-encoded.
+    Upload {
+        name: String,
+        data: String,
+    },
+    Approval {
+        id: String,
+        decision: ApprovalResponse,
+    },
+}
+
+struct Inbound {
+    tx: mpsc::Sender<ChannelMessage>,
+    workspace_dir: PathBuf,
+    max_upload_bytes: usize,
+}
+
+struct Connection {
+    client_id: String,
+    frames: mpsc::UnboundedSender<ServerFrame>,
+    /// Uploads attached to the next message from this socket.
+    uploads: Vec<String>,
+}
+
+struct PendingApproval {
+    client_id: String,
+    reply: oneshot::Sender<ApprovalResponse>,
+}
+
+/// Routes frames between browser sockets and the running WebChat channel.
+pub struct WebChatHub {
+    inbound: Mutex<Option<Inbound>>,
+    connections: Mutex<HashMap<u64, Connection>>,
+    approvals: Mutex<HashMap<String, PendingApproval>>,
+    next_connection: AtomicU64,
+}
+
+impl Default for WebChatHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebChatHub {
+    pub fn new() -> Self {
+        Self {
+            inbound: Mutex::new(None),
+            connections: Mutex::new(HashMap::new()),
+            approvals: Mutex::new(HashMap::new()),
+            next_connection: AtomicU64::new(1),
+        }
+    }
+
+    /// Register an authenticated socket. The receiver yields frames to write
+    /// back to the browser, starting with `ready`.
+    pub fn connect(&self, client_id: &str) -> (u64, mpsc::UnboundedReceiver<ServerFrame>) {
+        let id = self.next_connection.fetch_add(1, Ordering::Relaxed);
+        let (frames, rx) = mpsc::unbounded_channel();
+        let _ = frames.send(ServerFrame::Ready {
+            client: client_id.to_string(),
+        });
+        self.connections.lock().insert(
+            id,
+            Connection {
+                client_id: client_id.to_string(),
+                frames,
+                uploads: Vec::new(),
+            },
+        );
+        (id, rx)
+    }
+
+    pub fn disconnect(&self, connection: u64) {
+        self.connections.lock().remove(&connection);
+    }
+
+    /// Handle one frame received on `connection`.
+    pub async fn handle(&self, connection: u64, frame: ClientFrame) {
+        let result = match frame {
+            ClientFrame::Auth { .. } => Err("already authenticated".to_string()),
+            ClientFrame::Message { content } => self.forward_message(connection, &content).await,
+            ClientFrame::Upload { name, data } => self.save_upload(connection, &name, &data).await,
+            ClientFrame::Approval { id, decision } => {
+                self.resolve_approval(connection, &id, decision);
+                Ok(())
+            }
+        };
+        if let Err(message) = result {
+            self.reply(connection, ServerFrame::Error { message });
+        }
+    }
+
+    /// Send `frame` to every socket of `client_id`. Returns how many sockets
+    /// received it.
+    pub fn send_to(&self, client_id: &str, frame: &ServerFrame) -> usize {
+        self.connections
+            .lock()
+            .values()
+            .filter(|conn| conn.client_id == client_id)
+            .filter(|conn| conn.frames.send(frame.clone()).is_ok())
+            .count()
+    }
+
+    fn reply(&self, connection: u64, frame: ServerFrame) {
+        if let Some(conn) = self.connections.lock().get(&connection) {
+            let _ = conn.frames.send(frame);
+        }
+    }
+
+    fn register(&self, inbound: Inbound) {
+        *self.inbound.lock() = Some(inbound);
+    }
+
+    fn unregister(&self, tx: &mpsc::Sender<ChannelMessage>) {
+        let mut inbound = self.inbound.lock();
+        if inbound
+            .as_ref()
+            .is_some_and(|current| current.tx.same_channel(tx))
+        {
+            *inbound = None;
+        }
+    }
+
+    fn inbound_target(&self) -> Result<(mpsc::Sender<ChannelMessage>, PathBuf, usize), String> {
+        self.inbound
+            .lock()
+            .as_ref()
+            .map(|inbound| {
+                (
+                    inbound.tx.clone(),
+                    inbound.workspace_dir.clone(),
+                    inbound.max_upload_bytes,
+                )
+            })
+            .ok_or_else(|| {
+                "WebChat is not connected to the agent; run `zeroclaw daemon`".to_string()
+            })
+    }
+
+    async fn forward_message(&self, connection: u64, content: &str) -> Result<(), String> {
+        let (tx, _, _) = self.inbound_target()?;
+        let (client_id, uploads) = {
+            let mut connections = self.connections.lock();
+            let conn = connections
+                .get_mut(&connection)
+                .ok_or_else(|| "connection closed".to_string())?;
+            (conn.client_id.clone(), std::mem::take(&mut conn.uploads))
+        };
+
+        let mut content = content.trim().to_string();
+        if !uploads.is_empty() {
+            if !content.is_empty() {
+                content.push_str("\n\n");
+            }
+            content.push_str("[Uploaded to workspace: ");
+            content.push_str(&uploads.join(", "));
+            content.push(']');
+        }
+        if content.is_empty() {
+            return Ok(());
+        }
+
+        let msg = ChannelMessage {
+            id: Uuid::new_v4().to_string(),
+            sender: client_id.clone(),
+            reply_target: client_id,
+            content,
+            channel: "webchat".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+        tx.send(msg)
+            .await
+            .map_err(|_| "WebChat channel stopped".to_string())
+    }
+
+    async fn save_upload(&self, connection: u64, name: &str, data: &str) -> Result<(), String> {
+        let (_, workspace_dir, max_upload_bytes) = self.inbound_target()?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .map_err(|e| format!("upload is not valid base64: {e}"))?;
+        if bytes.len() > max_upload_bytes {
+            return Err(format!(
+                "upload is {} bytes; the limit is {max_upload_bytes}",
+                bytes.len()
+            ));
+        }
+
+        let relative = format!(
+            "{UPLOAD_DIR}/{}-{}",
+            &Uuid::new_v4().simple().to_string()[..8],
+            sanitize_file_name(name)
+        );
+        let path = workspace_dir.join(&relative);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("failed to create upload folder: {e}"))?;
+        }
+        tokio::fs::write(&path, &bytes)
+            .await
+            .map_err(|e| format!("failed to save upload: {e}"))?;
+
+        if let Some(conn) = self.connections.lock().get_mut(&connection) {
+            conn.uploads.push(relative.clone());
+            let _ = conn.frames.send(ServerFrame::Uploaded { path: relative });
+        }
+        Ok(())
+    }
+
+    /// Ask `client_id` to approve a tool call, returning `No` if no socket is
+    /// open or nobody answers within `timeout`.
+    async fn request_approval(
+        &self,
+        client_id: &str,
+        request: &ApprovalRequest,
+        timeout: Duration,
+    ) -> ApprovalResponse {
+        let id = Uuid::new_v4().to_string();
+        let (reply, answer) = oneshot::channel();
+        self.approvals.lock().insert(
+            id.clone(),
+            PendingApproval {
+                client_id: client_id.to_string(),
+                reply,
+            },
+        );
+
+        let frame = ServerFrame::Approval {
+            id: id.clone(),
+            tool: request.tool_name.clone(),
+            summary: summarize_args(&request.arguments),
+        };
+        if self.send_to(client_id, &frame) == 0 {
+            self.approvals.lock().remove(&id);
+            return ApprovalResponse::No;
+        }
+
+        let decision = tokio::time::timeout(timeout, answer).await;
+        self.approvals.lock().remove(&id);
+        match decision {
+            Ok(Ok(decision)) => decision,
+            _ => ApprovalResponse::No,
+        }
+    }
+
+    fn resolve_approval(&self, connection: u64, id: &str, decision: ApprovalResponse) {
+        let Some(client_id) = self
+            .connections
+            .lock()
+            .get(&connection)
+            .map(|conn| conn.client_id.clone())
+        else {
+            return;
+        };
+        let mut approvals = self.approvals.lock();
+        // Only the user the request was sent to may answer it.
+        if approvals
+            .get(id)
+            .is_some_and(|pending| pending.client_id == client_id)
+        {
+            if let Some(pending) = approvals.remove(id) {
+                let _ = pending.reply.send(decision);
+            }
+        }
+    }
+}
+
+/// Keep only the final path component, with characters safe on any filesystem.
+pub(super) fn sanitize_file_name(name: &str) -> String {
+    let base = Path::new(name)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let cleaned: String = base
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.');
+    if cleaned.is_empty() {
+        "upload".to_string()
+    } else {
+        cleaned.chars().take(100).collect()
+    }
+}
+
+struct WebChatApprovalPrompt {
+    hub: Arc<WebChatHub>,
+    client_id: String,
+    timeout: Duration,
+}
+
+#[async_trait]
+impl ApprovalPrompt for WebChatApprovalPrompt {
+    async fn ask(&self, request: &ApprovalRequest) -> ApprovalResponse {
+        self.hub
+            .request_approval(&self.client_id, request, self.timeout)
+            .await
+    }
+}
+
+/// Browser chat served by the gateway at `/chat`.
+///
+/// The gateway accepts the WebSocket and authenticates it with a pairing
+/// token; this channel receives the messages through the shared
+/// [`WebChatHub`] and streams replies, typing state, and tool approval
+/// requests back to the browser.
+pub struct WebChatChannel {
+    hub: Arc<WebChatHub>,
+    workspace_dir: PathBuf,
+    max_upload_bytes: usize,
+    approval_timeout: Duration,
+    autonomy: AutonomyConfig,
+    approvals: Mutex<HashMap<String, Arc<ApprovalManager>>>,
+}
+
+impl WebChatChannel {
+    pub fn new(config: &WebChatConfig, workspace_dir: &Path, autonomy: &AutonomyConfig) -> Self {
+        Self {
+            hub: shared_hub(),
+            workspace_dir: workspace_dir.to_path_buf(),
+            max_upload_bytes: config.max_upload_bytes,
+            approval_timeout: Duration::from_secs(config.approval_timeout_secs.max(1)),
+            autonomy: autonomy.clone(),
+            approvals: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Use `hub` instead of the process-wide hub.
+    pub fn with_hub(mut self, hub: Arc<WebChatHub>) -> Self {
+        self.hub = hub;
+        self
+    }
+
+    fn deliver(&self, recipient: &str, frame: &ServerFrame) -> anyhow::Result<()> {
+        if self.hub.send_to(recipient, frame) == 0 {
+            anyhow::bail!("no open WebChat session for {recipient}");
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Channel for WebChatChannel {
+    fn name(&self) -> &str {
+        "webchat"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        self.deliver(
+            &message.recipient,
+            &ServerFrame::Message {
+                text: message.content.clone(),
+            },
+        )
+    }
+
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        self.hub.register(Inbound {
+            tx: tx.clone(),
+            workspace_dir: self.workspace_dir.clone(),
+            max_upload_bytes: self.max_upload_bytes,
+        });
+        tracing::info!("WebChat: accepting messages from the gateway at /chat");
+        tx.closed().await;
+        self.hub.unregister(&tx);
+        Ok(())
+    }
+
+    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        self.hub
+            .send_to(recipient, &ServerFrame::Typing { active: true });
+        Ok(())
+    }
+
+    async fn stop_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        self.hub
+            .send_to(recipient, &ServerFrame::Typing { active: false });
+        Ok(())
+    }
+
+    fn supports_draft_updates(&self) -> bool {
+        true
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        let id = Uuid::new_v4().to_string();
+        self.deliver(
+            &message.recipient,
+            &ServerFrame::Draft {
+                id: id.clone(),
+                text: String::new(),
+            },
+        )?;
+        Ok(Some(id))
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.deliver(
+            recipient,
+            &ServerFrame::Draft {
+                id: message_id.to_string(),
+                text: text.to_string(),
+            },
+        )
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.deliver(
+            recipient,
+            &ServerFrame::Final {
+                id: message_id.to_string(),
+                text: text.to_string(),
+            },
+        )
+    }
+
+    fn approval_manager(&self, recipient: &str, _sender: &str) -> Option<Arc<ApprovalManager>> {
+        let mut approvals = self.approvals.lock();
+        let manager = approvals.entry(recipient.to_string()).or_insert_with(|| {
+            Arc::new(
+                ApprovalManager::from_config(&self.autonomy).with_remote_prompt(Arc::new(
+                    WebChatApprovalPrompt {
+                        hub: Arc::clone(&self.hub),
+                        client_id: recipient.to_string(),
+                        timeout: self.approval_timeout,
+                    },
+                )),
+            )
+        });
+        Some(Arc::clone(manager))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn standalone_commands_disable_webchat() {
+        let mut config = Config::default();
+        assert!(!disable_outside_daemon(&mut config, "gateway"));
+
+        config.channels_config.webchat = Some(WebChatConfig::default());
+        assert!(disable_outside_daemon(&mut config, "gateway"));
+        assert!(config.channels_config.webchat.is_none());
+    }
+
+    fn channel(hub: &Arc<WebChatHub>, workspace: &Path) -> Arc<WebChatChannel> {
+        let config = WebChatConfig {
+            max_upload_bytes: 16,
+            approval_timeout_secs: 5,
+            allowed_origins: Vec::new(),
+        };
+        Arc::new(
+            WebChatChannel::new(&config, workspace, &AutonomyConfig::default())
+                .with_hub(Arc::clone(hub)),
+        )
+    }
+
+    async fn listening(
+        hub: &Arc<WebChatHub>,
+        workspace: &Path,
+    ) -> (Arc<WebChatChannel>, mpsc::Receiver<ChannelMessage>) {
+        let channel = channel(hub, workspace);
+        let (tx, rx) = mpsc::channel(8);
+        let listener = Arc::clone(&channel);
+        tokio::spawn(async move { listener.listen(tx).await });
+        while hub.inbound.lock().is_none() {
+            tokio::task::yield_now().await;
+        }
+        (channel, rx)
+    }
+
+    #[test]
+    fn client_id_is_stable_and_hides_token() {
+        let id = client_id("zc_secret", true);
+        assert_eq!(id, client_id("zc_secret", true));
+        assert_ne!(id, client_id("zc_other", true));
+        assert!(id.starts_with("web-"));
+        assert!(!id.contains("secret"));
+        assert_eq!(client_id("anything", false), "local");
+    }
+
+    #[test]
+    fn client_frames_parse_from_json() {
+        let frame: ClientFrame =
+            serde_json::from_str(r#"{"type":"approval","id":"a1","decision":"always"}"#).unwrap();
+        assert!(matches!(
+            frame,
+            ClientFrame::Approval { ref id, decision: ApprovalResponse::Always } if id == "a1"
+        ));
+        let json = serde_json::to_value(ServerFrame::Typing { active: true }).unwrap();
+        assert_eq!(json, serde_json::json!({"type": "typing", "active": true}));
+    }
+
+    #[test]
+    fn sanitize_file_name_strips_paths() {
+        assert_eq!(sanitize_file_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_file_name("my report.pdf"), "my_report.pdf");
+        assert_eq!(sanitize_file_name(".."), "upload");
+        assert_eq!(sanitize_file_name(".bashrc"), "bashrc");
+    }
+
+    #[tokio::test]
+    async fn message_without_running_channel_reports_error() {
+        let hub = Arc::new(WebChatHub::new());
+        let (conn, mut frames) = hub.connect("web-a");
+        assert_eq!(
+            frames.recv().await.unwrap(),
+            ServerFrame::Ready {
+                client: "web-a".into()
+            }
+        );
+        hub.handle(
+            conn,
+            ClientFrame::Message {
+                content: "hi".into(),
+            },
+        )
+        .await;
+        assert!(matches!(
+            frames.recv().await.unwrap(),
+            ServerFrame::Error { message } if message.contains("not connected")
+        ));
+    }
+
+    #[tokio::test]
+    async fn messages_reach_listener_and_replies_stream_back() {
+        let tmp = TempDir::new().unwrap();
+        let hub = Arc::new(WebChatHub::new());
+        let (channel, mut inbound) = listening(&hub, tmp.path()).await;
+        let (conn, mut frames) = hub.connect("web-a");
+        let (_other, mut other_frames) = hub.connect("web-b");
+        frames.recv().await.unwrap();
+        other_frames.recv().await.unwrap();
+
+        hub.handle(
+            conn,
+            ClientFrame::Message {
+                content: " hello ".into(),
+            },
+        )
+        .await;
+        let msg = inbound.recv().await.unwrap();
+        assert_eq!(msg.content, "hello");
+        assert_eq!(msg.channel, "webchat");
+        assert_eq!(msg.reply_target, "web-a");
+
+        let draft = channel
+            .send_draft(&SendMessage::new("...", "web-a"))
+            .await
+            .unwrap()
+            .unwrap();
+        channel.update_draft("web-a", &draft, "Hel").await.unwrap();
+        channel
+            .finalize_draft("web-a", &draft, "Hello!")
+            .await
+            .unwrap();
+        assert!(
+            matches!(frames.recv().await.unwrap(), ServerFrame::Draft { text, .. } if text.is_empty())
+        );
+        assert!(
+            matches!(frames.recv().await.unwrap(), ServerFrame::Draft { text, .. } if text == "Hel")
+        );
+        assert_eq!(
+            frames.recv().await.unwrap(),
+            ServerFrame::Final {
+                id: draft,
+                text: "Hello!".into()
+            }
+        );
+        assert!(other_frames.try_recv().is_err());
+        assert!(channel
+            .send(&SendMessage::new("hi", "web-missing"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn uploads_are_saved_and_attached_to_next_message() {
+        let tmp = TempDir::new().unwrap();
+        let hub = Arc::new(WebChatHub::new());
+        let (_channel, mut inbound) = listening(&hub, tmp.path()).await;
+        let (conn, mut frames) = hub.connect("web-a");
+        frames.recv().await.unwrap();
+
+        let data = base64::engine::general_purpose::STANDARD.encode(b"a,b\n1,2\n");
+        hub.handle(
+            conn,
+            ClientFrame::Upload {
+                name: "../data.csv".into(),
+                data,
+            },
+        )
+        .await;
+        let ServerFrame::Uploaded { path } = frames.recv().await.unwrap() else {
+            panic!("expected uploaded frame");
+        };
+        assert!(path.starts_with("uploads/webchat/"));
+        assert!(path.ends_with("-data.csv"));
+        assert_eq!(
+            std::fs::read(tmp.path().join(&path)).unwrap(),
+            b"a,b\n1,2\n"
+        );
+
+        hub.handle(
+            conn,
+            ClientFrame::Message {
+                content: "summarize".into(),
+            },
+        )
+        .await;
+        let msg = inbound.recv().await.unwrap();
+        assert_eq!(
+            msg.content,
+            format!("summarize\n\n[Uploaded to workspace: {path}]")
+        );
+
+        let too_big = base64::engine::general_purpose::STANDARD.encode([0_u8; 17]);
+        hub.handle(
+            conn,
+            ClientFrame::Upload {
+                name: "big.bin".into(),
+                data: too_big,
+            },
+        )
+        .await;
+        assert!(matches!(
+            frames.recv().await.unwrap(),
+            ServerFrame::Error { message } if message.contains("limit")
+        ));
+    }
+
+    #[tokio::test]
+    async fn approval_buttons_resolve_remote_prompt() {
+        let tmp = TempDir::new().unwrap();
+        let hub = Arc::new(WebChatHub::new());
+        let channel = channel(&hub, tmp.path());
+        let (conn, mut frames) = hub.connect("web-a");
+        let (intruder, _intruder_frames) = hub.connect("web-b");
+        frames.recv().await.unwrap();
+
+        let manager = channel.approval_manager("web-a", "web-a").unwrap();
+        let request = ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({"command": "ls"}),
+        };
+        let pending = tokio::spawn(async move { manager.prompt_remote(&request).await });
+
+        let ServerFrame::Approval { id, tool, summary } = frames.recv().await.unwrap() else {
+            panic!("expected approval frame");
+        };
+        assert_eq!(tool, "shell");
+        assert!(summary.contains("ls"));
+
+        // Another user cannot answer this request.
+        hub.handle(
+            intruder,
+            ClientFrame::Approval {
+                id: id.clone(),
+                decision: ApprovalResponse::Yes,
+            },
+        )
+        .await;
+        hub.handle(
+            conn,
+            ClientFrame::Approval {
+                id,
+                decision: ApprovalResponse::No,
+            },
+        )
+        .await;
+        assert_eq!(pending.await.unwrap(), ApprovalResponse::No);
+    }
+
+    #[tokio::test]
+    async fn approval_without_open_session_is_denied() {
+        let tmp = TempDir::new().unwrap();
+        let hub = Arc::new(WebChatHub::new());
+        let channel = channel(&hub, tmp.path());
+        let manager = channel.approval_manager("web-a", "web-a").unwrap();
+        let request = ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({}),
+        };
+        assert_eq!(manager.prompt_remote(&request).await, ApprovalResponse::No);
+    }
+}
@@ -1,5 +1,7 @@
 use super::traits::{Channel, ChannelMessage, SendMessage};
+use anyhow::Context;
 use async_trait::async_trait;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// `WhatsApp` channel — uses `WhatsApp` Business Cloud API
@@ -13,6 +15,7 @@ pub struct WhatsAppChannel {
     endpoint_id: String,
     verify_token: String,
     allowed_numbers: Vec<String>,
+    voice_inbox: Option<PathBuf>,
 }
 
 impl WhatsAppChannel {
@@ -27,9 +30,16 @@ impl WhatsAppChannel {
             endpoint_id,
             verify_token,
             allowed_numbers,
+            voice_inbox: None,
         }
     }
 
+    /// Download inbound voice notes and audio files into `dir` so they can be transcribed.
+    pub fn with_voice_inbox(mut self, dir: PathBuf) -> Self {
+        self.voice_inbox = Some(dir);
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.whatsapp")
     }
@@ -46,6 +56,19 @@ impl WhatsAppChannel {
 
     /// Parse an incoming webhook payload from Meta and extract messages
     pub fn parse_webhook_payload(&self, payload: &serde_json::Value) -> Vec<ChannelMessage> {
+        self.parse_webhook_payload_with_media(payload)
+            .into_iter()
+            .map(|(msg, _)| msg)
+            .collect()
+    }
+
+    /// Like [`Self::parse_webhook_payload`], but also returns the media ID of
+    /// inbound audio messages when a voice inbox is configured. Pass those to
+    /// [`Self::attach_inbound_voice`] to download the audio.
+    pub fn parse_webhook_payload_with_media(
+        &self,
+        payload: &serde_json::Value,
+    ) -> Vec<(ChannelMessage, Option<String>)> {
         let mut messages = Vec::new();
 
         // WhatsApp Cloud API webhook structure:
@@ -90,20 +113,28 @@ impl WhatsAppChannel {
                         continue;
                     }
 
-                    // Extract text content (support text messages only for now)
+                    // Extract text content; audio is only accepted for transcription
+                    let audio_media_id = msg
+                        .get("audio")
+                        .and_then(|audio| audio.get("id"))
+                        .and_then(|id| id.as_str())
+                        .filter(|_| self.voice_inbox.is_some())
+                        .map(ToOwned::to_owned);
                     let content = if let Some(text_obj) = msg.get("text") {
                         text_obj
                             .get("body")
                             .and_then(|b| b.as_str())
                             .unwrap_or("")
                             .to_string()
+                    } else if audio_media_id.is_some() {
+                        String::new()
                     } else {
-                        // Could be image, audio, etc. — skip for now
+                        // Could be image, video, etc. — skip for now
                         tracing::debug!("WhatsApp: skipping non-text message from {from}");
                         continue;
                     };
 
-                    if content.is_empty() {
+                    if content.is_empty() && audio_media_id.is_none() {
                         continue;
                     }
 
@@ -119,20 +150,135 @@ impl WhatsAppChannel {
                                 .as_secs()
                         });
 
-                    messages.push(ChannelMessage {
-                        id: Uuid::new_v4().to_string(),
-                        reply_target: normalized_from.clone(),
-                        sender: normalized_from,
-                        content,
-                        channel: "whatsapp".to_string(),
-                        timestamp,
-                    });
+                    messages.push((
+                        ChannelMessage {
+                            id: Uuid::new_v4().to_string(),
+                            reply_target: normalized_from.clone(),
+                            sender: normalized_from,
+                            content,
+                            channel: "whatsapp".to_string(),
+                            timestamp,
+                        },
+                        audio_media_id,
+                    ));
                 }
             }
         }
 
         messages
     }
+
+    /// Download a media object from the Cloud API into the voice inbox.
+    async fn download_voice_media(
+        &self,
+        media_id: &str,
+        local_name: &str,
+    ) -> anyhow::Result<PathBuf> {
+        let inbox = self
+            .voice_inbox
+            .as_ref()
+            .context("WhatsApp voice inbox is not configured")?;
+
+        let meta_url = format!("https://graph.facebook.com/v18.0/{media_id}");
+        let resp = self
+            .http_client()
+            .get(&meta_url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("WhatsApp media lookup failed: {}", resp.status());
+        }
+        let meta: serde_json::Value = resp.json().await?;
+        let download_url = meta
+            .get("url")
+            .and_then(|u| u.as_str())
+            .context("WhatsApp media response missing url")?;
+        let extension = match meta.get("mime_type").and_then(|m| m.as_str()) {
+            Some(mime) if mime.starts_with("audio/mpeg") => "mp3",
+            Some(mime) if mime.starts_with("audio/mp4") => "m4a",
+            Some(mime) if mime.starts_with("audio/amr") => "amr",
+            _ => "ogg",
+        };
+
+        let resp = self
+            .http_client()
+            .get(download_url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("WhatsApp media download failed: {}", resp.status());
+        }
+        let bytes = resp.bytes().await?;
+
+        tokio::fs::create_dir_all(inbox).await?;
+        let local_path = inbox.join(format!("whatsapp_{local_name}.{extension}"));
+        tokio::fs::write(&local_path, &bytes).await?;
+        Ok(local_path)
+    }
+
+    /// Download an inbound audio message and replace its content with a local
+    /// `[VOICE:<path>]` marker for the transcription pre-pass. Returns the
+    /// downloaded file, the only path the pre-pass may transcribe.
+    pub async fn attach_inbound_voice(
+        &self,
+        mut msg: ChannelMessage,
+        media_id: &str,
+    ) -> (ChannelMessage, Option<PathBuf>) {
+        match self.download_voice_media(media_id, &msg.id).await {
+            Ok(path) => {
+                msg.content = crate::transcription::voice_marker(&path);
+                (msg, Some(path))
+            }
+            Err(e) => {
+                tracing::warn!("Failed to download WhatsApp voice message: {e}");
+                msg.content = "[Voice message could not be downloaded]".to_string();
+                (msg, None)
+            }
+        }
+    }
+
+    /// Upload an OGG/Opus file to the Cloud API and return its media id.
+    async fn upload_voice_media(&self, audio_path: &Path) -> anyhow::Result<String> {
+        let url = format!(
+            "https://graph.facebook.com/v18.0/{}/media",
+            self.endpoint_id
+        );
+        let file_name = audio_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("voice.ogg")
+            .to_string();
+        let bytes = tokio::fs::read(audio_path).await?;
+        let part = reqwest::multipart::Part::bytes(bytes)
+            .file_name(file_name)
+            .mime_str("audio/ogg")?;
+        let form = reqwest::multipart::Form::new()
+            .text("messaging_product", "whatsapp")
+            .text("type", "audio/ogg")
+            .part("file", part);
+
+        let resp = self
+            .http_client()
+            .post(&url)
+            .bearer_auth(&self.access_token)
+            .multipart(form)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let error_body = resp.text().await.unwrap_or_default();
+            tracing::error!("WhatsApp media upload failed: {status} — {error_body}");
+            anyhow::bail!("WhatsApp media upload error: {status}");
+        }
+
+        let body: serde_json::Value = resp.json().await?;
+        body.get("id")
+            .and_then(|id| id.as_str())
+            .map(str::to_string)
+            .context("WhatsApp media upload response missing id")
+    }
 }
 
 #[async_trait]
@@ -184,6 +330,42 @@ impl Channel for WhatsAppChannel {
         Ok(())
     }
 
+    fn supports_voice_replies(&self) -> bool {
+        true
+    }
+
+    async fn send_voice_reply(&self, recipient: &str, audio_path: &Path) -> anyhow::Result<()> {
+        let media_id = self.upload_voice_media(audio_path).await?;
+        let url = format!(
+            "https://graph.facebook.com/v18.0/{}/messages",
+            self.endpoint_id
+        );
+        let to = recipient.strip_prefix('+').unwrap_or(recipient);
+        let body = serde_json::json!({
+            "messaging_product": "whatsapp",
+            "recipient_type": "individual",
+            "to": to,
+            "type": "audio",
+            "audio": { "id": media_id }
+        });
+
+        let resp = self
+            .http_client()
+            .post(&url)
+            .bearer_auth(&self.access_token)
+            .json(&body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let error_body = resp.text().await.unwrap_or_default();
+            tracing::error!("WhatsApp voice send failed: {status} — {error_body}");
+            anyhow::bail!("WhatsApp API error: {status}");
+        }
+
+        Ok(())
+    }
+
     async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         // WhatsApp uses webhooks (push-based), not polling.
         // Messages are received via the gateway's /whatsapp endpoint.
@@ -232,6 +414,11 @@ mod tests {
         assert_eq!(ch.name(), "whatsapp");
     }
 
+    #[test]
+    fn whatsapp_supports_voice_replies() {
+        assert!(make_channel().supports_voice_replies());
+    }
+
     #[test]
     fn whatsapp_verify_token() {
         let ch = make_channel();
@@ -734,6 +921,31 @@ mod tests {
         assert!(msgs.is_empty());
     }
 
+    #[test]
+    fn whatsapp_parse_audio_message_with_voice_inbox_returns_media_id() {
+        let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()])
+            .with_voice_inbox(std::env::temp_dir().join("zeroclaw_wa_voice_test"));
+        let payload = serde_json::json!({
+            "entry": [{
+                "changes": [{
+                    "value": {
+                        "messages": [{
+                            "from": "111",
+                            "timestamp": "1",
+                            "type": "audio",
+                            "audio": { "id": "audio123", "mime_type": "audio/ogg; codecs=opus", "voice": true }
+                        }]
+                    }
+                }]
+            }]
+        });
+        let msgs = ch.parse_webhook_payload_with_media(&payload);
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].0.sender, "+111");
+        assert!(msgs[0].0.content.is_empty());
+        assert_eq!(msgs[0].1.as_deref(), Some("audio123"));
+    }
+
     #[test]
     fn whatsapp_parse_video_message_skipped() {
         let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()]);
@@ -1,5 +1,6 @@
-pub mod schema;
 pub mod mcp_import;
+pub mod reload;
+pub mod schema;
 
 #[allow(unused_imports)]
 pub use schema::{
@@ -8,13 +9,17 @@ pub use schema::{
     AgentConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig, BrowserConfig,
     ChannelsConfig, ClassificationRule, ComposioConfig, Config, CostConfig, CronConfig,
     DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, GatewayConfig, HardwareConfig,
-    HardwareTransport, HeartbeatConfig, HttpRequestConfig, IMessageConfig, IdentityConfig,
-    LarkConfig, MatrixConfig, McpConfig, McpRetryPolicy, McpServerConfig, MemoryConfig,
-    ModelRouteConfig, ObservabilityConfig, PeripheralBoardConfig, PeripheralsConfig, ProxyConfig,
-    ProxyScope, QueryClassificationConfig, ReliabilityConfig, ResourceLimitsConfig, RuntimeConfig,
-    SandboxBackend, SandboxConfig, SchedulerConfig, SecretsConfig, SecurityConfig, SlackConfig,
-    StorageConfig, StorageProviderConfig, StorageProviderSection, StreamMode, TelegramConfig,
-    TunnelConfig, WebSearchConfig, WebhookConfig,
+    HardwareTransport, HeartbeatConfig, HomeAssistantConfig, HttpRequestConfig, IMessageConfig,
+    IdentityConfig, InboundHookConfig, LarkConfig, MatrixConfig, McpConfig, McpRetryPolicy,
+    McpServerConfig, MemoryConfig, ModbusConfig, ModbusDataType, ModbusParity,
+    ModbusRegisterConfig, ModbusRegisterKind, ModbusWordOrder, ModelRouteConfig, MqttChannelConfig,
+    MqttConfig, NextcloudTalkConfig, ObservabilityConfig, ObsidianConfig, PeripheralBoardConfig,
+    PeripheralEventAction, PeripheralEventRule, PeripheralsConfig, ProxyConfig, ProxyScope,
+    QueryClassificationConfig, ReliabilityConfig, ResourceLimitsConfig, RuntimeConfig,
+    SandboxBackend, SandboxConfig, SchedulerConfig, SecretsConfig, SecurityConfig, SensorGenerator,
+    SimBoardConfig, SimI2cDeviceConfig, SimPinConfig, SimSensorConfig, SlackConfig, StorageConfig,
+    StorageProviderConfig, StorageProviderSection, StreamMode, TelegramConfig, TranscriptionConfig,
+    TtsConfig, TunnelConfig, VoiceReplyMode, WebChatConfig, WebSearchConfig, WebhookConfig,
 };
 
 #[cfg(test)]
@@ -46,6 +51,9 @@ mod tests {
             allowed_users: vec![],
             listen_to_bots: false,
             mention_only: false,
+            slash_commands: true,
+            command_permissions: None,
+            approval_timeout_secs: 120,
         };
 
         let lark = LarkConfig {
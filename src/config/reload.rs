@@ -0,0 +1,195 @@
+//! Config hot-reload support for long-running components.
+//!
+//! The daemon watches `config.toml`, validates every change and publishes the
+//! accepted [`Config`] on a [`ConfigWatch`]. Components pick up the new value
+//! at their own safe points (between messages, between scheduler ticks) so
+//! in-flight work keeps the config it started with.
+
+use super::Config;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Receiving side of the daemon's live config channel.
+pub type ConfigWatch = watch::Receiver<Arc<Config>>;
+
+/// Sections that are read once at process start; changing them requires a
+/// restart and only produces a warning on reload.
+pub const RESTART_REQUIRED_SECTIONS: &[&str] = &["memory", "storage", "observability"];
+
+const CHANNELS_SECTION: &str = "channels_config";
+
+/// Fields the running daemon writes back to `config.toml` itself (the gateway
+/// persists tokens on every `/pair`); they never count as a reload.
+const RUNTIME_WRITTEN_FIELDS: &[(&str, &str)] = &[("gateway", "paired_tokens")];
+
+/// Top-level config sections that differ between `old` and `new`.
+///
+/// `channels_config` is reported per channel (`channels_config.telegram`) so
+/// only the affected listeners are restarted. Only section names are returned,
+/// never values, so the result is safe to log.
+pub fn changed_sections(old: &Config, new: &Config) -> Vec<String> {
+    let (Ok(mut old), Ok(mut new)) = (serde_json::to_value(old), serde_json::to_value(new)) else {
+        return Vec::new();
+    };
+    for (section, field) in RUNTIME_WRITTEN_FIELDS {
+        for value in [&mut old, &mut new] {
+            if let Some(section) = value.get_mut(*section).and_then(|v| v.as_object_mut()) {
+                section.remove(*field);
+            }
+        }
+    }
+    let empty = serde_json::Map::new();
+    let old = old.as_object().unwrap_or(&empty);
+    let new = new.as_object().unwrap_or(&empty);
+
+    let mut changed = Vec::new();
+    for key in sorted_keys(old, new) {
+        let (before, after) = (old.get(&key), new.get(&key));
+        if before == after {
+            continue;
+        }
+        if key == CHANNELS_SECTION {
+            let before = before.and_then(|v| v.as_object()).unwrap_or(&empty);
+            let after = after.and_then(|v| v.as_object()).unwrap_or(&empty);
+            changed.extend(
+                sorted_keys(before, after)
+                    .into_iter()
+                    .filter(|channel| before.get(channel) != after.get(channel))
+                    .map(|channel| format!("{CHANNELS_SECTION}.{channel}")),
+            );
+        } else {
+            changed.push(key);
+        }
+    }
+    changed
+}
+
+/// Channel names whose `[channels_config.<name>]` table changed.
+pub fn changed_channels(changed: &[String]) -> Vec<String> {
+    changed
+        .iter()
+        .filter_map(|section| section.strip_prefix("channels_config."))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether the gateway must be restarted to apply `section`.
+///
+/// The gateway rebuilds its routes, providers, pairing policy and limits in
+/// place; only the tunnel, started once next to the listener, needs a restart.
+pub fn restarts_gateway(section: &str) -> bool {
+    section == "tunnel"
+}
+
+/// Whether `section` only takes effect after a full restart.
+pub fn requires_restart(section: &str) -> bool {
+    RESTART_REQUIRED_SECTIONS.contains(&section)
+}
+
+/// Take the latest published config if it changed since the last call.
+pub fn take_update(updates: &mut ConfigWatch) -> Option<Config> {
+    if updates.has_changed().unwrap_or(false) {
+        Some(updates.borrow_and_update().as_ref().clone())
+    } else {
+        None
+    }
+}
+
+fn sorted_keys(
+    a: &serde_json::Map<String, serde_json::Value>,
+    b: &serde_json::Map<String, serde_json::Value>,
+) -> Vec<String> {
+    let mut keys: Vec<String> = a.keys().chain(b.keys()).cloned().collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TelegramConfig;
+    use std::path::PathBuf;
+
+    fn telegram(token: &str) -> TelegramConfig {
+        TelegramConfig {
+            bot_token: token.into(),
+            allowed_users: vec!["alice".into()],
+            stream_mode: crate::config::StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            mention_only: false,
+        }
+    }
+
+    #[test]
+    fn identical_configs_have_no_changes() {
+        let config = Config::default();
+        assert!(changed_sections(&config, &config.clone()).is_empty());
+    }
+
+    #[test]
+    fn computed_paths_are_not_reported() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.config_path = PathBuf::from("/elsewhere/config.toml");
+        new.workspace_dir = PathBuf::from("/elsewhere/workspace");
+        assert!(changed_sections(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn reports_top_level_sections_and_individual_channels() {
+        let mut old = Config::default();
+        old.channels_config.telegram = Some(telegram("old-token"));
+        let mut new = old.clone();
+        new.autonomy.max_actions_per_hour += 1;
+        new.default_temperature = 0.2;
+        new.channels_config.telegram = Some(telegram("new-token"));
+
+        let changed = changed_sections(&old, &new);
+        assert_eq!(
+            changed,
+            vec![
+                "autonomy".to_string(),
+                "channels_config.telegram".to_string(),
+                "default_temperature".to_string(),
+            ]
+        );
+        assert_eq!(changed_channels(&changed), vec!["telegram".to_string()]);
+    }
+
+    #[test]
+    fn classifies_sections_for_gateway_and_restart() {
+        assert!(restarts_gateway("tunnel"));
+        assert!(!restarts_gateway("gateway"));
+        assert!(!restarts_gateway("autonomy"));
+        assert!(!restarts_gateway("channels_config.whatsapp"));
+        assert!(!restarts_gateway("cron"));
+        assert!(requires_restart("memory"));
+        assert!(!requires_restart("reliability"));
+    }
+
+    #[test]
+    fn tokens_persisted_by_pairing_are_not_a_change() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.gateway.paired_tokens.push("a".repeat(64));
+        assert!(changed_sections(&old, &new).is_empty());
+
+        new.gateway.require_pairing = !old.gateway.require_pairing;
+        assert_eq!(changed_sections(&old, &new), vec!["gateway".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn take_update_returns_each_published_config_once() {
+        let (tx, mut rx) = watch::channel(Arc::new(Config::default()));
+        assert!(take_update(&mut rx).is_none());
+
+        let mut next = Config::default();
+        next.default_temperature = 1.3;
+        tx.send_replace(Arc::new(next));
+
+        let update = take_update(&mut rx).expect("published update");
+        assert!((update.default_temperature - 1.3).abs() < f64::EPSILON);
+        assert!(take_update(&mut rx).is_none());
+    }
+}
@@ -18,6 +18,8 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "provider.ollama",
     "provider.openai",
     "provider.openrouter",
+    "provider.transcription",
+    "provider.tts",
     "channel.dingtalk",
     "channel.discord",
     "channel.lark",
@@ -30,6 +32,7 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "channel.whatsapp",
     "tool.browser",
     "tool.composio",
+    "tool.home_assistant",
     "tool.http_request",
     "tool.pushover",
     "memory.embeddings",
@@ -122,6 +125,14 @@ pub struct Config {
     #[serde(default)]
     pub http_request: HttpRequestConfig,
 
+    /// Home Assistant REST API access for the `home_assistant` tool.
+    #[serde(default)]
+    pub home_assistant: HomeAssistantConfig,
+
+    /// MQTT broker connection for the `mqtt_*` tools and the MQTT channel.
+    #[serde(default)]
+    pub mqtt: MqttConfig,
+
     #[serde(default)]
     pub web_search: WebSearchConfig,
 
@@ -144,6 +155,18 @@ pub struct Config {
     /// Hardware configuration (wizard-driven physical world setup).
     #[serde(default)]
     pub hardware: HardwareConfig,
+
+    /// Speech-to-text for inbound voice messages.
+    #[serde(default)]
+    pub transcription: TranscriptionConfig,
+
+    /// Text-to-speech for spoken replies on voice-capable channels.
+    #[serde(default)]
+    pub tts: TtsConfig,
+
+    /// OS-level sandbox and resource limits for spawned processes.
+    #[serde(default)]
+    pub security: SecurityConfig,
 }
 
 // ── Delegate Agents ──────────────────────────────────────────────
@@ -256,6 +279,14 @@ pub struct AgentConfig {
     pub parallel_tools: bool,
     #[serde(default = "default_agent_tool_dispatcher")]
     pub tool_dispatcher: String,
+    /// Context window overrides in tokens, keyed by `model` or `provider/model`.
+    /// Models not listed fall back to the cached provider catalog, then to
+    /// built-in defaults.
+    #[serde(default)]
+    pub context_windows: HashMap<String, usize>,
+    /// Tokens held back from the context window for the model's response.
+    #[serde(default = "default_agent_context_reserve_tokens")]
+    pub context_reserve_tokens: usize,
 }
 
 fn default_agent_max_tool_iterations() -> usize {
@@ -270,6 +301,10 @@ fn default_agent_tool_dispatcher() -> String {
     "auto".into()
 }
 
+fn default_agent_context_reserve_tokens() -> usize {
+    4_096
+}
+
 impl Default for AgentConfig {
     fn default() -> Self {
         Self {
@@ -278,6 +313,8 @@ impl Default for AgentConfig {
             max_history_messages: default_agent_max_history_messages(),
             parallel_tools: false,
             tool_dispatcher: default_agent_tool_dispatcher(),
+            context_windows: HashMap::new(),
+            context_reserve_tokens: default_agent_context_reserve_tokens(),
         }
     }
 }
@@ -466,21 +503,51 @@ pub struct PeripheralsConfig {
     /// Place .md/.txt files named by board (e.g. nucleo-f401re.md, rpi-gpio.md).
     #[serde(default)]
     pub datasheet_dir: Option<String>,
+    /// Rules that turn board events (button presses, edges, thresholds) into
+    /// agent runs, scheduled jobs or channel notifications. Evaluated by the
+    /// daemon.
+    #[serde(default)]
+    pub event_rules: Vec<PeripheralEventRule>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PeripheralBoardConfig {
-    /// Board type: "nucleo-f401re", "rpi-gpio", "esp32", etc.
+    /// Board type: "nucleo-f401re", "rpi-gpio", "esp32", "sim", etc.
+    /// For Modbus devices, any name (used as the `device` tool argument).
     pub board: String,
-    /// Transport: "serial", "native", "websocket"
+    /// Transport: "serial", "native", "websocket", "sim" (in-process simulator),
+    /// "modbus-tcp", "modbus-rtu"
     #[serde(default = "default_peripheral_transport")]
     pub transport: String,
-    /// Path for serial: "/dev/ttyACM0", "/dev/ttyUSB0"
+    /// Path for serial and Modbus RTU: "/dev/ttyACM0", "/dev/ttyUSB0".
+    /// Modbus TCP: "host:port" (port defaults to 502).
     #[serde(default)]
     pub path: Option<String>,
     /// Baud rate for serial (default: 115200)
     #[serde(default = "default_peripheral_baud")]
     pub baud: u32,
+    /// Pins the agent may use for GPIO, ADC and PWM. Empty = every pin the
+    /// board advertises.
+    #[serde(default)]
+    pub allowed_pins: Vec<u32>,
+    /// I2C/SPI/UART bus indexes the agent may use. Empty = every advertised bus.
+    #[serde(default)]
+    pub allowed_buses: Vec<u32>,
+    /// 7-bit I2C device addresses the agent may read or write. Empty = any.
+    #[serde(default)]
+    pub allowed_i2c_addresses: Vec<u8>,
+    /// Native GPIO pins to watch for edges (RPi). Serial boards send their
+    /// own events.
+    #[serde(default)]
+    pub watch_pins: Vec<u32>,
+    /// Script for the simulated board (`board = "sim"`): pin states, sensor
+    /// generators and I2C devices.
+    #[serde(default)]
+    pub sim: Option<SimBoardConfig>,
+    /// Modbus unit and register map (`transport = "modbus-tcp"` or
+    /// `"modbus-rtu"`).
+    #[serde(default)]
+    pub modbus: Option<ModbusConfig>,
 }
 
 fn default_peripheral_transport() -> String {
@@ -498,10 +565,300 @@ impl Default for PeripheralBoardConfig {
             transport: default_peripheral_transport(),
             path: None,
             baud: default_peripheral_baud(),
+            allowed_pins: Vec::new(),
+            allowed_buses: Vec::new(),
+            allowed_i2c_addresses: Vec::new(),
+            watch_pins: Vec::new(),
+            sim: None,
+            modbus: None,
+        }
+    }
+}
+
+/// Initial state of a simulated board.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SimBoardConfig {
+    /// Initial digital pin levels
+    #[serde(default)]
+    pub pins: Vec<SimPinConfig>,
+    /// Analog inputs driven by value generators (read with `adc_read`)
+    #[serde(default)]
+    pub sensors: Vec<SimSensorConfig>,
+    /// Emulated I2C devices
+    #[serde(default)]
+    pub i2c_devices: Vec<SimI2cDeviceConfig>,
+    /// Text the simulated UART answers with (default: echo what was sent)
+    #[serde(default)]
+    pub uart_reply: Option<String>,
+    /// Request timeout in milliseconds (default: 1000)
+    #[serde(default = "default_sim_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_sim_timeout_ms() -> u64 {
+    1000
+}
+
+impl Default for SimBoardConfig {
+    fn default() -> Self {
+        Self {
+            pins: Vec::new(),
+            sensors: Vec::new(),
+            i2c_devices: Vec::new(),
+            uart_reply: None,
+            timeout_ms: default_sim_timeout_ms(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SimPinConfig {
+    pub pin: u32,
+    pub value: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SimSensorConfig {
+    pub pin: u32,
+    #[serde(flatten)]
+    pub generator: SensorGenerator,
+}
+
+/// How a simulated analog input produces values.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SensorGenerator {
+    /// Always the same value
+    Constant { value: f64 },
+    /// Sine wave between `min` and `max` over `period_ms`
+    Sine { min: f64, max: f64, period_ms: u64 },
+    /// Starts at `start`, adds `step` per read, wraps back after `max`
+    Ramp { start: f64, step: f64, max: f64 },
+    /// Cycles through `values`, one per read
+    Sequence { values: Vec<f64> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SimI2cDeviceConfig {
+    /// 7-bit address
+    pub address: u8,
+    /// Initial register contents, starting at register 0
+    #[serde(default)]
+    pub registers: Vec<u8>,
+}
+
+/// A Modbus device: unit id, line settings and the registers the agent may
+/// use. Only mapped registers are reachable through the tools.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModbusConfig {
+    /// Unit (slave) id (default: 1)
+    #[serde(default = "default_modbus_unit_id")]
+    pub unit_id: u8,
+    /// Response timeout in milliseconds (default: 1000)
+    #[serde(default = "default_modbus_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Order of the two registers in 32-bit values (default: big = high word first)
+    #[serde(default)]
+    pub word_order: ModbusWordOrder,
+    /// RTU parity (default: none); data bits are 8, stop bits 1
+    #[serde(default)]
+    pub parity: ModbusParity,
+    /// Named registers
+    #[serde(default)]
+    pub registers: Vec<ModbusRegisterConfig>,
+}
+
+fn default_modbus_unit_id() -> u8 {
+    1
+}
+
+fn default_modbus_timeout_ms() -> u64 {
+    1000
+}
+
+impl Default for ModbusConfig {
+    fn default() -> Self {
+        Self {
+            unit_id: default_modbus_unit_id(),
+            timeout_ms: default_modbus_timeout_ms(),
+            word_order: ModbusWordOrder::default(),
+            parity: ModbusParity::default(),
+            registers: Vec::new(),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModbusWordOrder {
+    #[default]
+    Big,
+    Little,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModbusParity {
+    #[default]
+    None,
+    Even,
+    Odd,
+}
+
+/// One named value in a Modbus register map.
+///
+/// ```toml
+/// [[peripherals.boards.modbus.registers]]
+/// name = "tank_temp"
+/// kind = "holding"
+/// address = 100
+/// data_type = "i16"
+/// scale = 0.1
+/// unit = "°C"
+/// writable = true
+/// min = 5.0
+/// max = 60.0
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModbusRegisterConfig {
+    /// Name the agent uses (e.g. "tank_temp")
+    pub name: String,
+    /// Register table: coil, discrete, input or holding
+    pub kind: ModbusRegisterKind,
+    /// Zero-based protocol address
+    pub address: u16,
+    /// Value type for input/holding registers (default: u16). Coils and
+    /// discrete inputs are always bool.
+    #[serde(default)]
+    pub data_type: ModbusDataType,
+    /// Engineering value = raw * scale + offset (default: 1.0)
+    #[serde(default = "default_modbus_scale")]
+    pub scale: f64,
+    #[serde(default)]
+    pub offset: f64,
+    /// Unit shown to the agent (e.g. "°C", "bar")
+    #[serde(default)]
+    pub unit: Option<String>,
+    /// What the value means, shown to the agent
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Allow `modbus_write` (coils and holding registers only; default: false)
+    #[serde(default)]
+    pub writable: bool,
+    /// Writes need `approved=true` in supervised mode (default: false)
+    #[serde(default)]
+    pub requires_approval: bool,
+    /// Lowest engineering value `modbus_write` accepts
+    #[serde(default)]
+    pub min: Option<f64>,
+    /// Highest engineering value `modbus_write` accepts
+    #[serde(default)]
+    pub max: Option<f64>,
+}
+
+fn default_modbus_scale() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModbusRegisterKind {
+    /// Read/write bit (function codes 1, 5)
+    Coil,
+    /// Read-only bit (function code 2)
+    Discrete,
+    /// Read-only 16-bit register (function code 4)
+    Input,
+    /// Read/write 16-bit register (function codes 3, 6, 16)
+    Holding,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModbusDataType {
+    Bool,
+    #[default]
+    U16,
+    I16,
+    U32,
+    I32,
+    F32,
+}
+
+/// Maps a peripheral event to an action.
+///
+/// ```toml
+/// [[peripherals.event_rules]]
+/// name = "doorbell"
+/// board = "nucleo-f401re"
+/// event = "button"
+/// pin = 2
+/// action = "notify"
+/// prompt = "Doorbell pressed on {board}"
+/// channel = "telegram"
+/// to = "123456789"
+/// debounce_ms = 2000
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PeripheralEventRule {
+    /// Rule name, used in logs and job names
+    pub name: String,
+    /// Board type to match (default: any board)
+    #[serde(default)]
+    pub board: Option<String>,
+    /// Event name to match, or "*" for any event (default: "*")
+    #[serde(default = "default_event_rule_event")]
+    pub event: String,
+    /// Pin to match (default: any pin)
+    #[serde(default)]
+    pub pin: Option<u32>,
+    /// What to do when the rule fires
+    pub action: PeripheralEventAction,
+    /// Prompt or message template; `{board}`, `{event}`, `{pin}` and
+    /// `{value}` are replaced with the event's fields
+    pub prompt: String,
+    /// Delay before a `job` action runs (e.g. "30s", "5m"; default: run now)
+    #[serde(default)]
+    pub delay: Option<String>,
+    /// Delivery channel for the result (telegram, discord, slack, mattermost).
+    /// Required for `notify`; optional for `agent` and `job`.
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Delivery target on `channel` (chat ID, channel ID, ...)
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Ignore matching events this soon after the rule last fired (default: 1000)
+    #[serde(default = "default_event_rule_debounce_ms")]
+    pub debounce_ms: u64,
+    /// Maximum firings per rolling hour (default: 30, 0 = unlimited)
+    #[serde(default = "default_event_rule_max_per_hour")]
+    pub max_per_hour: u32,
+}
+
+/// Action taken by a [`PeripheralEventRule`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PeripheralEventAction {
+    /// Run the agent with the rendered prompt immediately
+    Agent,
+    /// Schedule a one-shot cron agent job (after `delay`)
+    Job,
+    /// Send the rendered prompt as a message to `channel`/`to`
+    Notify,
+}
+
+fn default_event_rule_event() -> String {
+    "*".into()
+}
+
+fn default_event_rule_debounce_ms() -> u64 {
+    1000
+}
+
+fn default_event_rule_max_per_hour() -> u32 {
+    30
+}
+
 // ── Gateway security ─────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -867,6 +1224,155 @@ fn default_http_timeout_secs() -> u64 {
     30
 }
 
+// ── Home Assistant ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HomeAssistantConfig {
+    /// Enable the `home_assistant` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Base URL of the Home Assistant instance
+    #[serde(default = "default_home_assistant_url")]
+    pub url: String,
+    /// Long-lived access token (stored encrypted when secrets.encrypt = true)
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Entity IDs the agent may read or control (e.g. `light.kitchen`)
+    #[serde(default)]
+    pub allowed_entities: Vec<String>,
+    /// Domains whose entities are all allowed (e.g. `light`, `sensor`)
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+    /// Domains whose service calls need explicit approval in supervised mode
+    #[serde(default = "default_home_assistant_approval_domains")]
+    pub approval_domains: Vec<String>,
+    /// Request timeout in seconds
+    #[serde(default = "default_home_assistant_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_home_assistant_url() -> String {
+    "http://homeassistant.local:8123".into()
+}
+
+fn default_home_assistant_approval_domains() -> Vec<String> {
+    vec!["lock".into(), "alarm_control_panel".into()]
+}
+
+fn default_home_assistant_timeout_secs() -> u64 {
+    15
+}
+
+impl Default for HomeAssistantConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: default_home_assistant_url(),
+            token: None,
+            allowed_entities: Vec::new(),
+            allowed_domains: Vec::new(),
+            approval_domains: default_home_assistant_approval_domains(),
+            timeout_secs: default_home_assistant_timeout_secs(),
+        }
+    }
+}
+
+// ── MQTT ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MqttConfig {
+    /// Enable the `mqtt_publish` and `mqtt_subscribe_peek` tools
+    #[serde(default)]
+    pub enabled: bool,
+    /// Broker hostname
+    #[serde(default = "default_mqtt_host")]
+    pub host: String,
+    /// Broker port (default: 1883, or 8883 with TLS)
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Connect over TLS
+    #[serde(default)]
+    pub tls: bool,
+    /// PEM CA bundle for the broker certificate (default: public web roots)
+    #[serde(default)]
+    pub ca_cert: Option<String>,
+    /// PEM client certificate for mutual TLS (requires `client_key`)
+    #[serde(default)]
+    pub client_cert: Option<String>,
+    /// PEM private key for `client_cert`
+    #[serde(default)]
+    pub client_key: Option<String>,
+    /// Client id prefix; each connection appends its role (e.g. `-channel`)
+    #[serde(default = "default_mqtt_client_id")]
+    pub client_id: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Broker password (stored encrypted when secrets.encrypt = true)
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Keep-alive interval in seconds (0 disables pings)
+    #[serde(default = "default_mqtt_keep_alive_secs")]
+    pub keep_alive_secs: u16,
+    /// Connect and acknowledgement timeout in seconds
+    #[serde(default = "default_mqtt_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Topics `mqtt_publish` may publish to; MQTT filters such as `home/+/set`
+    #[serde(default)]
+    pub publish_topics: Vec<String>,
+    /// Topic filters `mqtt_subscribe_peek` may subscribe to
+    #[serde(default)]
+    pub subscribe_topics: Vec<String>,
+    /// Publish topics that need explicit approval in supervised mode
+    #[serde(default)]
+    pub approval_topics: Vec<String>,
+    /// Default QoS for tool publishes (0, 1 or 2)
+    #[serde(default = "default_mqtt_qos")]
+    pub qos: u8,
+}
+
+fn default_mqtt_host() -> String {
+    "localhost".into()
+}
+
+fn default_mqtt_client_id() -> String {
+    "zeroclaw".into()
+}
+
+fn default_mqtt_keep_alive_secs() -> u16 {
+    30
+}
+
+fn default_mqtt_timeout_secs() -> u64 {
+    10
+}
+
+fn default_mqtt_qos() -> u8 {
+    1
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: default_mqtt_host(),
+            port: None,
+            tls: false,
+            ca_cert: None,
+            client_cert: None,
+            client_key: None,
+            client_id: default_mqtt_client_id(),
+            username: None,
+            password: None,
+            keep_alive_secs: default_mqtt_keep_alive_secs(),
+            timeout_secs: default_mqtt_timeout_secs(),
+            publish_topics: Vec::new(),
+            subscribe_topics: Vec::new(),
+            approval_topics: Vec::new(),
+            qos: default_mqtt_qos(),
+        }
+    }
+}
+
 // ── Web search ───────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -912,6 +1418,209 @@ impl Default for WebSearchConfig {
     }
 }
 
+// ── Transcription (speech-to-text) ───────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionConfig {
+    /// Transcribe inbound voice/audio messages before they reach the agent
+    #[serde(default)]
+    pub enabled: bool,
+    /// Backend: "whisper_cpp" (local, offline) or "openai" (any `/audio/transcriptions` endpoint)
+    #[serde(default = "default_transcription_backend")]
+    pub backend: String,
+    /// whisper.cpp CLI binary (e.g. "whisper-cli" or an absolute path to `main`)
+    #[serde(default = "default_whisper_bin")]
+    pub whisper_bin: String,
+    /// Path to the ggml model used by whisper.cpp (supports `~`)
+    #[serde(default = "default_whisper_model_path")]
+    pub whisper_model_path: String,
+    /// ffmpeg binary used to convert OGG/Opus and other formats to 16 kHz WAV for whisper.cpp
+    #[serde(default = "default_ffmpeg_bin")]
+    pub ffmpeg_bin: String,
+    /// Base URL of the OpenAI-compatible API (default: "https://api.openai.com/v1")
+    #[serde(default = "default_transcription_api_url")]
+    pub api_url: String,
+    /// API key for the OpenAI-compatible backend (falls back to `OPENAI_API_KEY`)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Model for the OpenAI-compatible backend (default: "whisper-1")
+    #[serde(default = "default_transcription_model")]
+    pub model: String,
+    /// Optional ISO-639-1 language hint (e.g. "en"); auto-detected when unset
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Maximum accepted audio size in bytes (default: 25MB)
+    #[serde(default = "default_transcription_max_audio_bytes")]
+    pub max_audio_bytes: usize,
+    /// Timeout for a single transcription in seconds (default: 120)
+    #[serde(default = "default_transcription_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Echo the transcript back to the sender before the agent replies
+    #[serde(default)]
+    pub show_transcript: bool,
+}
+
+fn default_transcription_backend() -> String {
+    "whisper_cpp".into()
+}
+
+fn default_whisper_bin() -> String {
+    "whisper-cli".into()
+}
+
+fn default_whisper_model_path() -> String {
+    "~/.zeroclaw/models/ggml-base.bin".into()
+}
+
+fn default_ffmpeg_bin() -> String {
+    "ffmpeg".into()
+}
+
+fn default_transcription_api_url() -> String {
+    "https://api.openai.com/v1".into()
+}
+
+fn default_transcription_model() -> String {
+    "whisper-1".into()
+}
+
+fn default_transcription_max_audio_bytes() -> usize {
+    25 * 1024 * 1024 // 25MB (OpenAI upload limit)
+}
+
+fn default_transcription_timeout_secs() -> u64 {
+    120
+}
+
+impl Default for TranscriptionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: default_transcription_backend(),
+            whisper_bin: default_whisper_bin(),
+            whisper_model_path: default_whisper_model_path(),
+            ffmpeg_bin: default_ffmpeg_bin(),
+            api_url: default_transcription_api_url(),
+            api_key: None,
+            model: default_transcription_model(),
+            language: None,
+            max_audio_bytes: default_transcription_max_audio_bytes(),
+            timeout_secs: default_transcription_timeout_secs(),
+            show_transcript: false,
+        }
+    }
+}
+
+// ── Text-to-speech ───────────────────────────────────────────────
+
+/// When to answer with a voice message instead of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceReplyMode {
+    /// Always reply with text.
+    Off,
+    /// Reply with voice when the user's message was a voice note (default).
+    #[default]
+    WhenVoice,
+    /// Reply with voice to every message.
+    Always,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TtsConfig {
+    /// Enable spoken replies on voice-capable channels
+    #[serde(default)]
+    pub enabled: bool,
+    /// Backend: "piper" (local, offline) or "openai" (any `/audio/speech` endpoint)
+    #[serde(default = "default_tts_backend")]
+    pub backend: String,
+    /// Piper binary
+    #[serde(default = "default_piper_bin")]
+    pub piper_bin: String,
+    /// Path to the Piper `.onnx` voice model (supports `~`)
+    #[serde(default = "default_piper_model_path")]
+    pub piper_model_path: String,
+    /// ffmpeg binary used to encode Piper WAV output as OGG/Opus
+    #[serde(default = "default_ffmpeg_bin")]
+    pub ffmpeg_bin: String,
+    /// Base URL of the OpenAI-compatible API (default: "https://api.openai.com/v1")
+    #[serde(default = "default_transcription_api_url")]
+    pub api_url: String,
+    /// API key for the OpenAI-compatible backend (falls back to `OPENAI_API_KEY`)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Model for the OpenAI-compatible backend (default: "tts-1")
+    #[serde(default = "default_tts_model")]
+    pub model: String,
+    /// Voice for the OpenAI-compatible backend (default: "alloy")
+    #[serde(default = "default_tts_voice")]
+    pub voice: String,
+    /// Default reply mode; users can override it per chat with `/voice on|off|auto`
+    #[serde(default)]
+    pub reply_mode: VoiceReplyMode,
+    /// Channels allowed to send voice replies (default: telegram, whatsapp)
+    #[serde(default = "default_tts_channels")]
+    pub channels: Vec<String>,
+    /// Replies longer than this many characters are sent as text (default: 1500)
+    #[serde(default = "default_tts_max_chars")]
+    pub max_chars: usize,
+    /// Timeout for a single synthesis in seconds (default: 60)
+    #[serde(default = "default_tts_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_tts_backend() -> String {
+    "piper".into()
+}
+
+fn default_piper_bin() -> String {
+    "piper".into()
+}
+
+fn default_piper_model_path() -> String {
+    "~/.zeroclaw/models/piper/en_US-lessac-medium.onnx".into()
+}
+
+fn default_tts_model() -> String {
+    "tts-1".into()
+}
+
+fn default_tts_voice() -> String {
+    "alloy".into()
+}
+
+fn default_tts_channels() -> Vec<String> {
+    vec!["telegram".into(), "whatsapp".into()]
+}
+
+fn default_tts_max_chars() -> usize {
+    1500
+}
+
+fn default_tts_timeout_secs() -> u64 {
+    60
+}
+
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: default_tts_backend(),
+            piper_bin: default_piper_bin(),
+            piper_model_path: default_piper_model_path(),
+            ffmpeg_bin: default_ffmpeg_bin(),
+            api_url: default_transcription_api_url(),
+            api_key: None,
+            model: default_tts_model(),
+            voice: default_tts_voice(),
+            reply_mode: VoiceReplyMode::default(),
+            channels: default_tts_channels(),
+            max_chars: default_tts_max_chars(),
+            timeout_secs: default_tts_timeout_secs(),
+        }
+    }
+}
+
 // ── Proxy ───────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
@@ -1437,8 +2146,9 @@ impl Default for StorageProviderConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct MemoryConfig {
-    /// "sqlite" | "lucid" | "postgres" | "markdown" | "none" (`none` = explicit no-op memory)
+    /// "sqlite" | "lucid" | "postgres" | "markdown" | "obsidian" | "none" (`none` = explicit no-op memory)
     ///
+    /// `obsidian` requires `[memory.obsidian].vault_path`.
     /// `postgres` requires `[storage.provider.config]` with `db_url` (`dbURL` alias supported).
     pub backend: String,
     /// Auto-save conversation context to memory
@@ -1509,6 +2219,56 @@ pub struct MemoryConfig {
     /// None = wait indefinitely (default). Recommended max: 300.
     #[serde(default)]
     pub sqlite_open_timeout_secs: Option<u64>,
+
+    // ── Obsidian backend options ───────────────────────────────
+    /// Obsidian vault indexed by the `obsidian` backend and edited by the
+    /// `obsidian_notes` tool (`[memory.obsidian]`)
+    #[serde(default)]
+    pub obsidian: ObsidianConfig,
+}
+
+/// Obsidian vault settings (`[memory.obsidian]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObsidianConfig {
+    /// Path to the vault root (the folder containing `.obsidian/`). Empty = disabled.
+    #[serde(default)]
+    pub vault_path: String,
+    /// Vault folder where the agent creates notes and stores core memories
+    #[serde(default = "default_obsidian_notes_folder")]
+    pub notes_folder: String,
+    /// Vault-relative folders skipped by the indexer (hidden folders are always skipped)
+    #[serde(default)]
+    pub exclude_folders: Vec<String>,
+    /// Minimum seconds between vault rescans before recall
+    #[serde(default = "default_obsidian_sync_interval_secs")]
+    pub sync_interval_secs: u64,
+}
+
+fn default_obsidian_notes_folder() -> String {
+    "ZeroClaw".into()
+}
+
+fn default_obsidian_sync_interval_secs() -> u64 {
+    60
+}
+
+impl ObsidianConfig {
+    /// Vault root with `~` expanded, or `None` when no vault is configured.
+    pub fn vault_dir(&self) -> Option<PathBuf> {
+        let path = self.vault_path.trim();
+        (!path.is_empty()).then(|| PathBuf::from(shellexpand::tilde(path).to_string()))
+    }
+}
+
+impl Default for ObsidianConfig {
+    fn default() -> Self {
+        Self {
+            vault_path: String::new(),
+            notes_folder: default_obsidian_notes_folder(),
+            exclude_folders: Vec::new(),
+            sync_interval_secs: default_obsidian_sync_interval_secs(),
+        }
+    }
 }
 
 fn default_embedding_provider() -> String {
@@ -1578,6 +2338,7 @@ impl Default for MemoryConfig {
             snapshot_on_hygiene: false,
             auto_hydrate: true,
             sqlite_open_timeout_secs: None,
+            obsidian: ObsidianConfig::default(),
         }
     }
 }
@@ -2081,6 +2842,9 @@ pub struct ChannelsConfig {
     pub lark: Option<LarkConfig>,
     pub dingtalk: Option<DingTalkConfig>,
     pub qq: Option<QQConfig>,
+    pub webchat: Option<WebChatConfig>,
+    pub nextcloud_talk: Option<NextcloudTalkConfig>,
+    pub mqtt: Option<MqttChannelConfig>,
 }
 
 impl Default for ChannelsConfig {
@@ -2101,6 +2865,9 @@ impl Default for ChannelsConfig {
             lark: None,
             dingtalk: None,
             qq: None,
+            webchat: None,
+            nextcloud_talk: None,
+            mqtt: None,
         }
     }
 }
@@ -2150,15 +2917,42 @@ pub struct DiscordConfig {
     /// Other messages in the guild are silently ignored.
     #[serde(default)]
     pub mention_only: bool,
+    /// Register `/model`, `/models`, `/reset`, `/memory` and `/cron` as
+    /// application commands on connect (scoped to `guild_id` when set).
+    #[serde(default = "default_true")]
+    pub slash_commands: bool,
+    /// Permission bitfield a member needs to see the slash commands
+    /// (e.g. `"32"` for Manage Server). `allowed_users` still applies.
+    #[serde(default)]
+    pub command_permissions: Option<String>,
+    /// How long to wait for a tool approval button to be clicked
+    #[serde(default = "default_discord_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+}
+
+fn default_discord_approval_timeout_secs() -> u64 {
+    120
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SlackConfig {
     pub bot_token: String,
+    /// App-level token (`xapp-...`). When set, events arrive over Socket Mode
+    /// instead of polling `channel_id`.
     pub app_token: Option<String>,
     pub channel_id: Option<String>,
     #[serde(default)]
     pub allowed_users: Vec<String>,
+    /// When true (default), replies to top-level channel messages start a
+    /// thread on the original message. Direct messages always reply inline.
+    #[serde(default)]
+    pub thread_replies: Option<bool>,
+    /// Streaming mode for progressive response delivery via message edits.
+    #[serde(default)]
+    pub stream_mode: StreamMode,
+    /// Minimum interval (ms) between draft message edits to avoid rate limits.
+    #[serde(default = "default_draft_update_interval_ms")]
+    pub draft_update_interval_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2182,6 +2976,45 @@ pub struct MattermostConfig {
 pub struct WebhookConfig {
     pub port: u16,
     pub secret: Option<String>,
+    /// Named inbound hooks served at `/hooks/<name>`
+    #[serde(default)]
+    pub hooks: Vec<InboundHookConfig>,
+}
+
+/// Inbound webhook that maps an arbitrary JSON payload to an agent message.
+///
+/// Templates insert payload values with `{{ $.path.to[0].field }}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboundHookConfig {
+    /// URL path segment: the hook is served at `/hooks/<name>`
+    pub name: String,
+    /// Shared secret expected in the `X-Webhook-Secret` header
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// HMAC-SHA256 key for signed payloads (GitHub-style `sha256=<hex>`)
+    #[serde(default)]
+    pub hmac_secret: Option<String>,
+    /// Header carrying the HMAC signature
+    #[serde(default = "default_hook_signature_header")]
+    pub signature_header: String,
+    /// Template for the message sent to the agent
+    pub message: String,
+    /// Template for the sender shown to the agent (default: the hook name)
+    #[serde(default)]
+    pub sender: Option<String>,
+    /// POST the agent's reply as JSON to this URL
+    #[serde(default)]
+    pub reply_url: Option<String>,
+    /// Deliver the agent's reply on this configured channel (e.g. "telegram")
+    #[serde(default)]
+    pub reply_channel: Option<String>,
+    /// Recipient on `reply_channel` (chat ID, room, address)
+    #[serde(default)]
+    pub reply_recipient: Option<String>,
+}
+
+fn default_hook_signature_header() -> String {
+    "X-Hub-Signature-256".into()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2199,6 +3032,37 @@ pub struct MatrixConfig {
     pub device_id: Option<String>,
     pub room_id: String,
     pub allowed_users: Vec<String>,
+    /// When true, replies to top-level messages start an `m.thread` on the
+    /// original event. Messages already in a thread are always answered there.
+    #[serde(default)]
+    pub thread_replies: bool,
+    /// Streaming mode for progressive response delivery via `m.replace` edits.
+    #[serde(default)]
+    pub stream_mode: StreamMode,
+    /// Minimum interval (ms) between draft message edits to avoid rate limits.
+    #[serde(default = "default_draft_update_interval_ms")]
+    pub draft_update_interval_ms: u64,
+    /// Reaction added to accepted messages as an acknowledgement (empty = none)
+    #[serde(default = "default_matrix_ack_reaction")]
+    pub ack_reaction: String,
+    /// Largest inbound media file saved into the workspace (0 = never save)
+    #[serde(default = "default_matrix_max_media_bytes")]
+    pub max_media_bytes: usize,
+    /// How long to wait for a reaction on a tool approval prompt
+    #[serde(default = "default_matrix_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+}
+
+fn default_matrix_ack_reaction() -> String {
+    "👀".into()
+}
+
+fn default_matrix_max_media_bytes() -> usize {
+    20 * 1024 * 1024
+}
+
+fn default_matrix_approval_timeout_secs() -> u64 {
+    120
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2272,6 +3136,27 @@ fn default_irc_port() -> u16 {
     6697
 }
 
+/// MQTT channel: messages on `topics` start agent turns. Uses the broker
+/// connection from `[mqtt]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MqttChannelConfig {
+    /// Topic filters to listen on (e.g. `zeroclaw/in/#`)
+    pub topics: Vec<String>,
+    /// Reply topic; `{topic}` is the incoming topic (default: `{topic}/reply`)
+    #[serde(default = "default_mqtt_response_topic")]
+    pub response_topic: String,
+    /// QoS for subscriptions and replies (0, 1 or 2)
+    #[serde(default = "default_mqtt_qos")]
+    pub qos: u8,
+    /// Publish replies as retained messages
+    #[serde(default)]
+    pub retain: bool,
+}
+
+fn default_mqtt_response_topic() -> String {
+    "{topic}/reply".into()
+}
+
 /// How ZeroClaw receives events from Feishu / Lark.
 ///
 /// - `websocket` (default) — persistent WSS long-connection; no public URL required.
@@ -2391,6 +3276,10 @@ pub struct ResourceLimitsConfig {
     #[serde(default = "default_max_subprocesses")]
     pub max_subprocesses: u32,
 
+    /// Wall-clock ceiling in seconds for a background process (0 = unlimited)
+    #[serde(default = "default_max_background_runtime_seconds")]
+    pub max_background_runtime_seconds: u64,
+
     /// Enable memory monitoring
     #[serde(default = "default_memory_monitoring_enabled")]
     pub memory_monitoring: bool,
@@ -2408,6 +3297,10 @@ fn default_max_subprocesses() -> u32 {
     10
 }
 
+fn default_max_background_runtime_seconds() -> u64 {
+    3600
+}
+
 fn default_memory_monitoring_enabled() -> bool {
     true
 }
@@ -2418,6 +3311,7 @@ impl Default for ResourceLimitsConfig {
             max_memory_mb: default_max_memory_mb(),
             max_cpu_time_seconds: default_max_cpu_time_seconds(),
             max_subprocesses: default_max_subprocesses(),
+            max_background_runtime_seconds: default_max_background_runtime_seconds(),
             memory_monitoring: default_memory_monitoring_enabled(),
         }
     }
@@ -2490,6 +3384,62 @@ pub struct QQConfig {
     pub allowed_users: Vec<String>,
 }
 
+/// Nextcloud Talk bot (Talk bot webhook API)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NextcloudTalkConfig {
+    /// Nextcloud base URL, e.g. `https://cloud.example.com`
+    pub base_url: String,
+    /// Shared secret passed to `occ talk:bot:install` (signs webhooks and replies)
+    /// Can also be set via `ZEROCLAW_NEXTCLOUD_TALK_BOT_SECRET` environment variable
+    pub bot_secret: String,
+    /// Room tokens the bot answers in. Empty = deny all, "*" = allow all
+    #[serde(default)]
+    pub allowed_rooms: Vec<String>,
+    /// When true, only respond to messages that @-mention `bot_name`.
+    #[serde(default)]
+    pub mention_only: bool,
+    /// Display name the bot was installed with, used to detect mentions
+    #[serde(default = "default_nextcloud_talk_bot_name")]
+    pub bot_name: String,
+}
+
+fn default_nextcloud_talk_bot_name() -> String {
+    "ZeroClaw".into()
+}
+
+/// Browser chat served by the gateway at `/chat`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebChatConfig {
+    /// Largest file a user may upload into the workspace, in bytes
+    #[serde(default = "default_webchat_max_upload_bytes")]
+    pub max_upload_bytes: usize,
+    /// How long to wait for the user to answer a tool approval request
+    #[serde(default = "default_webchat_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+    /// Extra page origins (e.g. `https://chat.example.com`) allowed to open
+    /// the chat socket; the gateway's own host is always allowed
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+}
+
+fn default_webchat_max_upload_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
+fn default_webchat_approval_timeout_secs() -> u64 {
+    120
+}
+
+impl Default for WebChatConfig {
+    fn default() -> Self {
+        Self {
+            max_upload_bytes: default_webchat_max_upload_bytes(),
+            approval_timeout_secs: default_webchat_approval_timeout_secs(),
+            allowed_origins: Vec::new(),
+        }
+    }
+}
+
 // ── Config impl ──────────────────────────────────────────────────
 
 impl Default for Config {
@@ -2525,6 +3475,8 @@ impl Default for Config {
             secrets: SecretsConfig::default(),
             browser: BrowserConfig::default(),
             http_request: HttpRequestConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
+            mqtt: MqttConfig::default(),
             web_search: WebSearchConfig::default(),
             proxy: ProxyConfig::default(),
             identity: IdentityConfig::default(),
@@ -2532,6 +3484,9 @@ impl Default for Config {
             peripherals: PeripheralsConfig::default(),
             agents: HashMap::new(),
             hardware: HardwareConfig::default(),
+            tts: TtsConfig::default(),
+            security: SecurityConfig::default(),
+            transcription: TranscriptionConfig::default(),
             query_classification: QueryClassificationConfig::default(),
         }
     }
@@ -2762,44 +3717,7 @@ impl Config {
                 }
             }
 
-            let contents =
-                fs::read_to_string(&config_path).context("Failed to read config file")?;
-            let mut config: Config =
-                toml::from_str(&contents).context("Failed to parse config file")?;
-            // Set computed paths that are skipped during serialization
-            config.config_path = config_path.clone();
-            config.workspace_dir = workspace_dir;
-            let store = crate::security::SecretStore::new(&zeroclaw_dir, config.secrets.encrypt);
-            decrypt_optional_secret(&store, &mut config.api_key, "config.api_key")?;
-            decrypt_optional_secret(
-                &store,
-                &mut config.composio.api_key,
-                "config.composio.api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.browser.computer_use.api_key,
-                "config.browser.computer_use.api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.web_search.brave_api_key,
-                "config.web_search.brave_api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.storage.provider.config.db_url,
-                "config.storage.provider.config.db_url",
-            )?;
-
-            for agent in config.agents.values_mut() {
-                decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
-            }
-            config.apply_env_overrides();
-            Ok(config)
+            Self::load_from_path(&config_path, workspace_dir)
         } else {
             let mut config = Config::default();
             config.config_path = config_path.clone();
@@ -2818,6 +3736,66 @@ impl Config {
         }
     }
 
+    /// Read, decrypt and env-override an existing config file.
+    ///
+    /// Used at startup and by the daemon when `config.toml` changes on disk.
+    pub fn load_from_path(config_path: &Path, workspace_dir: PathBuf) -> Result<Self> {
+        let zeroclaw_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+        let contents = fs::read_to_string(config_path).context("Failed to read config file")?;
+        let mut config: Config =
+            toml::from_str(&contents).context("Failed to parse config file")?;
+        // Set computed paths that are skipped during serialization
+        config.config_path = config_path.to_path_buf();
+        config.workspace_dir = workspace_dir;
+        let store = crate::security::SecretStore::new(zeroclaw_dir, config.secrets.encrypt);
+        decrypt_optional_secret(&store, &mut config.api_key, "config.api_key")?;
+        decrypt_optional_secret(
+            &store,
+            &mut config.composio.api_key,
+            "config.composio.api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut config.browser.computer_use.api_key,
+            "config.browser.computer_use.api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut config.web_search.brave_api_key,
+            "config.web_search.brave_api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut config.home_assistant.token,
+            "config.home_assistant.token",
+        )?;
+
+        decrypt_optional_secret(&store, &mut config.mqtt.password, "config.mqtt.password")?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut config.storage.provider.config.db_url,
+            "config.storage.provider.config.db_url",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut config.transcription.api_key,
+            "config.transcription.api_key",
+        )?;
+
+        decrypt_optional_secret(&store, &mut config.tts.api_key, "config.tts.api_key")?;
+
+        for agent in config.agents.values_mut() {
+            decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
+        }
+        config.apply_env_overrides();
+        Ok(config)
+    }
+
     /// Apply environment variable overrides to config
     pub fn apply_env_overrides(&mut self) {
         // API Key: ZEROCLAW_API_KEY or API_KEY (generic)
@@ -3071,12 +4049,36 @@ impl Config {
             "config.web_search.brave_api_key",
         )?;
 
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.home_assistant.token,
+            "config.home_assistant.token",
+        )?;
+
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.mqtt.password,
+            "config.mqtt.password",
+        )?;
+
         encrypt_optional_secret(
             &store,
             &mut config_to_save.storage.provider.config.db_url,
             "config.storage.provider.config.db_url",
         )?;
 
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.transcription.api_key,
+            "config.transcription.api_key",
+        )?;
+
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.tts.api_key,
+            "config.tts.api_key",
+        )?;
+
         for agent in config_to_save.agents.values_mut() {
             encrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
         }
@@ -3345,6 +4347,9 @@ default_temperature = 0.7
                 lark: None,
                 dingtalk: None,
                 qq: None,
+                webchat: None,
+                nextcloud_talk: None,
+                mqtt: None,
             },
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -3355,6 +4360,8 @@ default_temperature = 0.7
             secrets: SecretsConfig::default(),
             browser: BrowserConfig::default(),
             http_request: HttpRequestConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
+            mqtt: MqttConfig::default(),
             web_search: WebSearchConfig::default(),
             proxy: ProxyConfig::default(),
             agent: AgentConfig::default(),
@@ -3363,6 +4370,9 @@ default_temperature = 0.7
             peripherals: PeripheralsConfig::default(),
             agents: HashMap::new(),
             hardware: HardwareConfig::default(),
+            tts: TtsConfig::default(),
+            security: SecurityConfig::default(),
+            transcription: TranscriptionConfig::default(),
         };
 
         let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -3496,6 +4506,8 @@ tool_dispatcher = "xml"
             secrets: SecretsConfig::default(),
             browser: BrowserConfig::default(),
             http_request: HttpRequestConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
+            mqtt: MqttConfig::default(),
             web_search: WebSearchConfig::default(),
             proxy: ProxyConfig::default(),
             agent: AgentConfig::default(),
@@ -3504,6 +4516,9 @@ tool_dispatcher = "xml"
             peripherals: PeripheralsConfig::default(),
             agents: HashMap::new(),
             hardware: HardwareConfig::default(),
+            tts: TtsConfig::default(),
+            security: SecurityConfig::default(),
+            transcription: TranscriptionConfig::default(),
         };
 
         config.save().unwrap();
@@ -3671,6 +4686,9 @@ tool_dispatcher = "xml"
             allowed_users: vec![],
             listen_to_bots: false,
             mention_only: false,
+            slash_commands: true,
+            command_permissions: None,
+            approval_timeout_secs: 120,
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
@@ -3678,6 +4696,15 @@ tool_dispatcher = "xml"
         assert_eq!(parsed.guild_id.as_deref(), Some("12345"));
     }
 
+    #[test]
+    fn discord_config_defaults_enable_slash_commands() {
+        let json = r#"{"bot_token":"tok","guild_id":null}"#;
+        let parsed: DiscordConfig = serde_json::from_str(json).unwrap();
+        assert!(parsed.slash_commands);
+        assert!(parsed.command_permissions.is_none());
+        assert_eq!(parsed.approval_timeout_secs, 120);
+    }
+
     #[test]
     fn discord_config_optional_guild() {
         let dc = DiscordConfig {
@@ -3686,6 +4713,9 @@ tool_dispatcher = "xml"
             allowed_users: vec![],
             listen_to_bots: false,
             mention_only: false,
+            slash_commands: true,
+            command_permissions: None,
+            approval_timeout_secs: 120,
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
@@ -3734,6 +4764,12 @@ tool_dispatcher = "xml"
             device_id: Some("DEVICE123".into()),
             room_id: "!room123:matrix.org".into(),
             allowed_users: vec!["@user:matrix.org".into()],
+            thread_replies: false,
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            ack_reaction: "👀".into(),
+            max_media_bytes: 20 * 1024 * 1024,
+            approval_timeout_secs: 120,
         };
         let json = serde_json::to_string(&mc).unwrap();
         let parsed: MatrixConfig = serde_json::from_str(&json).unwrap();
@@ -3754,6 +4790,12 @@ tool_dispatcher = "xml"
             device_id: None,
             room_id: "!abc:synapse.local".into(),
             allowed_users: vec!["@admin:synapse.local".into(), "*".into()],
+            thread_replies: false,
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            ack_reaction: "👀".into(),
+            max_media_bytes: 20 * 1024 * 1024,
+            approval_timeout_secs: 120,
         };
         let toml_str = toml::to_string(&mc).unwrap();
         let parsed: MatrixConfig = toml::from_str(&toml_str).unwrap();
@@ -3774,6 +4816,10 @@ allowed_users = ["@ops:matrix.org"]
         assert_eq!(parsed.homeserver, "https://matrix.org");
         assert!(parsed.user_id.is_none());
         assert!(parsed.device_id.is_none());
+        assert!(!parsed.thread_replies);
+        assert_eq!(parsed.stream_mode, StreamMode::Off);
+        assert_eq!(parsed.ack_reaction, "👀");
+        assert_eq!(parsed.approval_timeout_secs, 120);
     }
 
     #[test]
@@ -3843,6 +4889,12 @@ allowed_users = ["@ops:matrix.org"]
                 device_id: None,
                 room_id: "!r:m".into(),
                 allowed_users: vec!["@u:m".into()],
+                thread_replies: false,
+                stream_mode: StreamMode::default(),
+                draft_update_interval_ms: 1000,
+                ack_reaction: "👀".into(),
+                max_media_bytes: 20 * 1024 * 1024,
+                approval_timeout_secs: 120,
             }),
             signal: None,
             whatsapp: None,
@@ -3851,6 +4903,9 @@ allowed_users = ["@ops:matrix.org"]
             lark: None,
             dingtalk: None,
             qq: None,
+            webchat: None,
+            nextcloud_talk: None,
+            mqtt: None,
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -3918,6 +4973,9 @@ channel_id = "C123"
         let parsed: SlackConfig = toml::from_str(toml_str).unwrap();
         assert!(parsed.allowed_users.is_empty());
         assert_eq!(parsed.channel_id.as_deref(), Some("C123"));
+        assert_eq!(parsed.thread_replies, None);
+        assert_eq!(parsed.stream_mode, StreamMode::Off);
+        assert_eq!(parsed.draft_update_interval_ms, 1000);
     }
 
     #[test]
@@ -3933,6 +4991,28 @@ channel_id = "C123"
         let parsed: WebhookConfig = serde_json::from_str(json).unwrap();
         assert!(parsed.secret.is_none());
         assert_eq!(parsed.port, 8080);
+        assert!(parsed.hooks.is_empty());
+    }
+
+    #[test]
+    fn webhook_config_with_named_hooks() {
+        let toml_str = r#"
+port = 8080
+
+[[hooks]]
+name = "github"
+hmac_secret = "gh-secret"
+message = "{{ $.action }} on {{ $.repository.full_name }}"
+reply_channel = "telegram"
+reply_recipient = "123"
+"#;
+        let parsed: WebhookConfig = toml::from_str(toml_str).unwrap();
+        let hook = &parsed.hooks[0];
+        assert_eq!(hook.name, "github");
+        assert_eq!(hook.signature_header, "X-Hub-Signature-256");
+        assert!(hook.secret.is_none());
+        assert!(hook.reply_url.is_none());
+        assert_eq!(hook.reply_channel.as_deref(), Some("telegram"));
     }
 
     // ── WhatsApp config ──────────────────────────────────────
@@ -4014,6 +5094,9 @@ channel_id = "C123"
             lark: None,
             dingtalk: None,
             qq: None,
+            webchat: None,
+            nextcloud_talk: None,
+            mqtt: None,
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -4981,6 +6064,39 @@ default_model = "legacy-model"
         assert_eq!(b.transport, "serial");
         assert!(b.path.is_none());
         assert_eq!(b.baud, 115_200);
+        assert!(b.allowed_pins.is_empty());
+        assert!(b.allowed_buses.is_empty());
+        assert!(b.sim.is_none());
+    }
+
+    #[test]
+    fn sim_board_config_parses_generators() {
+        let b: PeripheralBoardConfig = toml::from_str(
+            r#"
+board = "sim"
+transport = "sim"
+
+[sim]
+pins = [{ pin = 13, value = 1 }]
+sensors = [
+  { pin = 14, kind = "sine", min = 0.0, max = 1023.0, period_ms = 2000 },
+  { pin = 15, kind = "sequence", values = [1.0, 2.0] },
+]
+"#,
+        )
+        .unwrap();
+        let sim = b.sim.unwrap();
+        assert_eq!(sim.pins, vec![SimPinConfig { pin: 13, value: 1 }]);
+        assert_eq!(
+            sim.sensors[0].generator,
+            SensorGenerator::Sine {
+                min: 0.0,
+                max: 1023.0,
+                period_ms: 2000
+            }
+        );
+        assert_eq!(sim.sensors[1].pin, 15);
+        assert_eq!(sim.timeout_ms, 1000);
     }
 
     #[test]
@@ -4992,8 +6108,15 @@ default_model = "legacy-model"
                 transport: "serial".into(),
                 path: Some("/dev/ttyACM0".into()),
                 baud: 115_200,
+                allowed_pins: vec![13],
+                allowed_buses: Vec::new(),
+                allowed_i2c_addresses: vec![0x3c],
+                watch_pins: vec![17],
+                sim: None,
+                modbus: None,
             }],
             datasheet_dir: None,
+            event_rules: Vec::new(),
         };
         let toml_str = toml::to_string(&p).unwrap();
         let parsed: PeripheralsConfig = toml::from_str(&toml_str).unwrap();
@@ -5001,6 +6124,91 @@ default_model = "legacy-model"
         assert_eq!(parsed.boards.len(), 1);
         assert_eq!(parsed.boards[0].board, "nucleo-f401re");
         assert_eq!(parsed.boards[0].path.as_deref(), Some("/dev/ttyACM0"));
+        assert_eq!(parsed.boards[0].allowed_pins, vec![13]);
+        assert_eq!(parsed.boards[0].allowed_i2c_addresses, vec![0x3c]);
+        assert_eq!(parsed.boards[0].watch_pins, vec![17]);
+    }
+
+    #[test]
+    fn modbus_register_map_parses_with_defaults() {
+        let parsed: PeripheralBoardConfig = toml::from_str(
+            r#"
+board = "boiler"
+transport = "modbus-tcp"
+path = "127.0.0.1:502"
+
+[modbus]
+unit_id = 3
+word_order = "little"
+
+[[modbus.registers]]
+name = "temperature"
+kind = "input"
+address = 10
+data_type = "i16"
+scale = 0.1
+unit = "°C"
+
+[[modbus.registers]]
+name = "pump"
+kind = "coil"
+address = 0
+writable = true
+requires_approval = true
+"#,
+        )
+        .unwrap();
+        let modbus = parsed.modbus.unwrap();
+        assert_eq!(modbus.unit_id, 3);
+        assert_eq!(modbus.timeout_ms, 1000);
+        assert_eq!(modbus.word_order, ModbusWordOrder::Little);
+        assert_eq!(modbus.parity, ModbusParity::None);
+        let temperature = &modbus.registers[0];
+        assert_eq!(temperature.kind, ModbusRegisterKind::Input);
+        assert_eq!(temperature.data_type, ModbusDataType::I16);
+        assert_eq!(temperature.scale, 0.1);
+        assert!(!temperature.writable);
+        let pump = &modbus.registers[1];
+        assert_eq!(pump.data_type, ModbusDataType::U16);
+        assert_eq!(pump.scale, 1.0);
+        assert!(pump.writable && pump.requires_approval);
+    }
+
+    #[test]
+    fn peripheral_event_rules_parse_with_defaults() {
+        let parsed: PeripheralsConfig = toml::from_str(
+            r#"
+enabled = true
+
+[[event_rules]]
+name = "doorbell"
+board = "nucleo-f401re"
+event = "button"
+pin = 2
+action = "notify"
+prompt = "Doorbell on {board}"
+channel = "telegram"
+to = "42"
+
+[[event_rules]]
+name = "anything"
+action = "job"
+prompt = "Look into {event}"
+delay = "5m"
+"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.event_rules.len(), 2);
+        let doorbell = &parsed.event_rules[0];
+        assert_eq!(doorbell.action, PeripheralEventAction::Notify);
+        assert_eq!(doorbell.pin, Some(2));
+        assert_eq!(doorbell.debounce_ms, 1000);
+        assert_eq!(doorbell.max_per_hour, 30);
+        let anything = &parsed.event_rules[1];
+        assert_eq!(anything.event, "*");
+        assert_eq!(anything.board, None);
+        assert_eq!(anything.action, PeripheralEventAction::Job);
+        assert_eq!(anything.delay.as_deref(), Some("5m"));
     }
 
     #[test]
@@ -1,6 +1,8 @@
 use crate::channels::{
-    Channel, DiscordChannel, MattermostChannel, SendMessage, SlackChannel, TelegramChannel,
+    Channel, DiscordChannel, MattermostChannel, MqttChannel, SendMessage, SlackChannel,
+    TelegramChannel,
 };
+use crate::config::reload::{take_update, ConfigWatch};
 use crate::config::Config;
 use crate::cron::{
     due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job, reschedule_after_run,
@@ -18,10 +20,12 @@ use tokio::time::{self, Duration};
 const MIN_POLL_SECONDS: u64 = 5;
 const SHELL_JOB_TIMEOUT_SECS: u64 = 120;
 
-pub async fn run(config: Config) -> Result<()> {
-    let poll_secs = config.reliability.scheduler_poll_secs.max(MIN_POLL_SECONDS);
+/// Run the scheduler loop. Reloaded config published on `updates` is applied
+/// between ticks, so a job that is already running keeps its original policy.
+pub async fn run(mut config: Config, mut updates: ConfigWatch) -> Result<()> {
+    let mut poll_secs = config.reliability.scheduler_poll_secs.max(MIN_POLL_SECONDS);
     let mut interval = time::interval(Duration::from_secs(poll_secs));
-    let security = Arc::new(SecurityPolicy::from_config(
+    let mut security = Arc::new(SecurityPolicy::from_config(
         &config.autonomy,
         &config.workspace_dir,
     ));
@@ -31,6 +35,21 @@ pub async fn run(config: Config) -> Result<()> {
     loop {
         interval.tick().await;
 
+        if let Some(next) = take_update(&mut updates) {
+            security = Arc::new(SecurityPolicy::from_config(
+                &next.autonomy,
+                &next.workspace_dir,
+            ));
+            let next_poll_secs = next.reliability.scheduler_poll_secs.max(MIN_POLL_SECONDS);
+            if next_poll_secs != poll_secs {
+                poll_secs = next_poll_secs;
+                interval = time::interval(Duration::from_secs(poll_secs));
+                interval.tick().await;
+            }
+            config = next;
+            tracing::info!("Scheduler picked up reloaded config");
+        }
+
         let jobs = match due_jobs(&config, Utc::now()) {
             Ok(jobs) => jobs,
             Err(e) => {
@@ -131,6 +150,7 @@ async fn run_agent_job(config: &Config, job: &CronJob) -> (bool, String) {
                 model_override,
                 config.default_temperature,
                 vec![],
+                None,
             )
             .await
         }
@@ -252,6 +272,16 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str) ->
         .as_deref()
         .ok_or_else(|| anyhow::anyhow!("delivery.to is required for announce mode"))?;
 
+    deliver_announcement(config, channel, target, output).await
+}
+
+/// Send `output` to `target` on a configured outbound channel.
+pub(crate) async fn deliver_announcement(
+    config: &Config,
+    channel: &str,
+    target: &str,
+    output: &str,
+) -> Result<()> {
     match channel.to_ascii_lowercase().as_str() {
         "telegram" => {
             let tg = config
@@ -310,6 +340,15 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str) ->
             );
             channel.send(&SendMessage::new(output, target)).await?;
         }
+        "mqtt" => {
+            let mq = config
+                .channels_config
+                .mqtt
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("mqtt channel not configured"))?;
+            let channel = MqttChannel::new(config.mqtt.clone(), mq.clone());
+            channel.send(&SendMessage::new(output, target)).await?;
+        }
         other => anyhow::bail!("unsupported delivery channel: {other}"),
     }
 
@@ -1,12 +1,19 @@
+use crate::config::reload::{self, ConfigWatch};
 use crate::config::Config;
 use anyhow::Result;
 use chrono::Utc;
+use std::collections::HashMap;
 use std::future::Future;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::watch;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
 
 const STATUS_FLUSH_SECONDS: u64 = 5;
+const CONFIG_POLL_SECONDS: u64 = 2;
 
 pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     let initial_backoff = config.reliability.channel_initial_backoff_secs.max(1);
@@ -23,78 +30,75 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
                 .await;
     }
 
-    let mut handles: Vec<JoinHandle<()>> = vec![spawn_state_writer(config.clone())];
-
-    {
-        let gateway_cfg = config.clone();
-        let gateway_host = host.clone();
-        handles.push(spawn_component_supervisor(
-            "gateway",
-            initial_backoff,
-            max_backoff,
-            move || {
-                let cfg = gateway_cfg.clone();
-                let host = gateway_host.clone();
-                async move { crate::gateway::run_gateway(&host, port, cfg).await }
-            },
-        ));
-    }
-
-    {
-        if has_supervised_channels(&config) {
-            let channels_cfg = config.clone();
-            handles.push(spawn_component_supervisor(
-                "channels",
-                initial_backoff,
-                max_backoff,
-                move || {
-                    let cfg = channels_cfg.clone();
-                    async move { crate::channels::start_channels(cfg).await }
-                },
-            ));
-        } else {
-            crate::health::mark_component_ok("channels");
-            tracing::info!("No real-time channels configured; channel supervisor disabled");
-        }
+    let (config_tx, mut updates) = watch::channel(Arc::new(config.clone()));
+    let mut handles: Vec<JoinHandle<()>> = vec![
+        spawn_state_writer(config.clone()),
+        spawn_config_watcher(config.clone(), config_tx),
+    ];
+
+    let launcher = ComponentLauncher {
+        host: host.clone(),
+        port,
+        initial_backoff,
+        max_backoff,
+        updates: updates.clone(),
+    };
+    let mut components = Components::new();
+    launcher.start(&mut components, Component::Gateway);
+
+    if has_supervised_channels(&config) {
+        launcher.start(&mut components, Component::Channels);
+    } else {
+        crate::health::mark_component_ok("channels");
+        tracing::info!("No real-time channels configured; channel supervisor disabled");
     }
 
     if config.heartbeat.enabled {
-        let heartbeat_cfg = config.clone();
-        handles.push(spawn_component_supervisor(
-            "heartbeat",
-            initial_backoff,
-            max_backoff,
-            move || {
-                let cfg = heartbeat_cfg.clone();
-                async move { run_heartbeat_worker(cfg).await }
-            },
-        ));
+        launcher.start(&mut components, Component::Heartbeat);
     }
 
     if config.cron.enabled {
-        let scheduler_cfg = config.clone();
-        handles.push(spawn_component_supervisor(
-            "scheduler",
-            initial_backoff,
-            max_backoff,
-            move || {
-                let cfg = scheduler_cfg.clone();
-                async move { crate::cron::scheduler::run(cfg).await }
-            },
-        ));
+        launcher.start(&mut components, Component::Scheduler);
     } else {
         crate::health::mark_component_ok("scheduler");
         tracing::info!("Cron disabled; scheduler supervisor not started");
     }
 
+    if has_peripheral_event_rules(&config) {
+        launcher.start(&mut components, Component::PeripheralEvents);
+    }
+
     println!("🧠 ZeroClaw daemon started");
     println!("   Gateway:  http://{host}:{port}");
     println!("   Components: gateway, channels, heartbeat, scheduler");
+    println!(
+        "   Config:   watching {} for changes",
+        config.config_path.display()
+    );
     println!("   Ctrl+C to stop");
 
-    tokio::signal::ctrl_c().await?;
+    let mut current = config;
+    loop {
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => {
+                result?;
+                break;
+            }
+            changed = updates.changed() => {
+                if changed.is_err() {
+                    // Watcher is gone; keep serving the last accepted config.
+                    tokio::signal::ctrl_c().await?;
+                    break;
+                }
+                let next = updates.borrow_and_update().as_ref().clone();
+                apply_reload(&launcher, &mut components, &current, &next);
+                current = next;
+            }
+        }
+    }
     crate::health::mark_component_error("daemon", "shutdown requested");
 
+    handles.extend(components.into_values());
     for handle in &handles {
         handle.abort();
     }
@@ -105,6 +109,229 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     Ok(())
 }
 
+/// Supervised daemon components that can be (re)started on config reload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Component {
+    Gateway,
+    Channels,
+    Heartbeat,
+    Scheduler,
+    PeripheralEvents,
+}
+
+impl Component {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Gateway => "gateway",
+            Self::Channels => "channels",
+            Self::Heartbeat => "heartbeat",
+            Self::Scheduler => "scheduler",
+            Self::PeripheralEvents => "peripherals",
+        }
+    }
+}
+
+type Components = HashMap<Component, JoinHandle<()>>;
+
+/// Spawns supervised daemon components from the latest accepted config.
+struct ComponentLauncher {
+    host: String,
+    port: u16,
+    initial_backoff: u64,
+    max_backoff: u64,
+    updates: ConfigWatch,
+}
+
+impl ComponentLauncher {
+    /// Each (re)start reads the latest accepted config from `updates`.
+    fn spawn(&self, component: Component) -> JoinHandle<()> {
+        let updates = self.updates.clone();
+        let host = self.host.clone();
+        let port = self.port;
+        spawn_component_supervisor(
+            component.name(),
+            self.initial_backoff,
+            self.max_backoff,
+            move || {
+                let mut updates = updates.clone();
+                let cfg = updates.borrow_and_update().as_ref().clone();
+                let host = host.clone();
+                let run: Pin<Box<dyn Future<Output = Result<()>> + Send>> = match component {
+                    Component::Gateway => Box::pin(async move {
+                        Box::pin(crate::gateway::run_gateway_with_reload(
+                            &host, port, cfg, updates,
+                        ))
+                        .await
+                    }),
+                    Component::Channels => {
+                        Box::pin(crate::channels::start_channels_with_reload(cfg, updates))
+                    }
+                    Component::Heartbeat => Box::pin(run_heartbeat_worker(cfg, updates)),
+                    Component::Scheduler => Box::pin(crate::cron::scheduler::run(cfg, updates)),
+                    Component::PeripheralEvents => {
+                        Box::pin(crate::peripherals::events::run(cfg, updates))
+                    }
+                };
+                run
+            },
+        )
+    }
+
+    fn start(&self, components: &mut Components, component: Component) {
+        components.insert(component, self.spawn(component));
+    }
+
+    fn stop(components: &mut Components, component: Component) {
+        if let Some(handle) = components.remove(&component) {
+            handle.abort();
+        }
+        crate::health::mark_component_ok(component.name());
+        tracing::info!(
+            "Daemon component '{}' disabled by config reload",
+            component.name()
+        );
+    }
+
+    fn restart(&self, components: &mut Components, component: Component) {
+        if let Some(handle) = components.remove(&component) {
+            handle.abort();
+        }
+        tracing::info!(
+            "Restarting daemon component '{}' to apply reloaded config",
+            component.name()
+        );
+        self.start(components, component);
+    }
+}
+
+/// Start, stop or restart components for an accepted config change.
+///
+/// Gateway, channels, heartbeat and scheduler swap security policy, routes,
+/// limits and tool settings in place; only components that read a changed
+/// section once at startup are restarted.
+fn apply_reload(
+    launcher: &ComponentLauncher,
+    components: &mut Components,
+    old: &Config,
+    new: &Config,
+) {
+    let changed = reload::changed_sections(old, new);
+
+    if changed
+        .iter()
+        .any(|section| reload::restarts_gateway(section))
+    {
+        launcher.restart(components, Component::Gateway);
+    }
+
+    if !components.contains_key(&Component::Channels) && has_supervised_channels(new) {
+        launcher.start(components, Component::Channels);
+    }
+
+    match (
+        new.heartbeat.enabled,
+        components.contains_key(&Component::Heartbeat),
+    ) {
+        (true, false) => launcher.start(components, Component::Heartbeat),
+        (true, true) if changed.iter().any(|section| section == "heartbeat") => {
+            launcher.restart(components, Component::Heartbeat);
+        }
+        (false, true) => ComponentLauncher::stop(components, Component::Heartbeat),
+        _ => {}
+    }
+
+    match (
+        new.cron.enabled,
+        components.contains_key(&Component::Scheduler),
+    ) {
+        (true, false) => launcher.start(components, Component::Scheduler),
+        (false, true) => ComponentLauncher::stop(components, Component::Scheduler),
+        _ => {}
+    }
+
+    match (
+        has_peripheral_event_rules(new),
+        components.contains_key(&Component::PeripheralEvents),
+    ) {
+        (true, false) => launcher.start(components, Component::PeripheralEvents),
+        (true, true) if changed.iter().any(|section| section == "peripherals") => {
+            launcher.restart(components, Component::PeripheralEvents);
+        }
+        (false, true) => ComponentLauncher::stop(components, Component::PeripheralEvents),
+        _ => {}
+    }
+}
+
+/// Poll `config.toml` and publish every valid change on `updates`.
+fn spawn_config_watcher(config: Config, updates: watch::Sender<Arc<Config>>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let path = config.config_path.clone();
+        let mut current = config;
+        let mut last_modified = config_modified_at(&path);
+        let mut interval = tokio::time::interval(Duration::from_secs(CONFIG_POLL_SECONDS));
+
+        loop {
+            interval.tick().await;
+            let modified = config_modified_at(&path);
+            if modified == last_modified {
+                continue;
+            }
+            last_modified = modified;
+
+            match reload_config(&current) {
+                Ok(Some((next, changed))) => {
+                    tracing::info!(
+                        "Reloaded {}; changed: {}",
+                        path.display(),
+                        changed.join(", ")
+                    );
+                    let restart_only: Vec<&str> = changed
+                        .iter()
+                        .map(String::as_str)
+                        .filter(|section| reload::requires_restart(section))
+                        .collect();
+                    if !restart_only.is_empty() {
+                        tracing::warn!(
+                            "Config changes to {} take effect after a daemon restart",
+                            restart_only.join(", ")
+                        );
+                    }
+                    current = next.clone();
+                    updates.send_replace(Arc::new(next));
+                }
+                Ok(None) => {
+                    tracing::debug!("{} touched without effective changes", path.display());
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Rejected config reload from {}: {e:#}; keeping previous configuration",
+                        path.display()
+                    );
+                }
+            }
+        }
+    })
+}
+
+fn config_modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+/// Re-read `config.toml` and validate it with the same checks as `zeroclaw
+/// doctor`. Returns `None` when nothing effective changed.
+fn reload_config(current: &Config) -> Result<Option<(Config, Vec<String>)>> {
+    let next = Config::load_from_path(&current.config_path, current.workspace_dir.clone())?;
+    let errors = crate::doctor::config_errors(&next);
+    if !errors.is_empty() {
+        anyhow::bail!("invalid config: {}", errors.join("; "));
+    }
+
+    let changed = reload::changed_sections(current, &next);
+    Ok((!changed.is_empty()).then_some((next, changed)))
+}
+
 pub fn state_file_path(config: &Config) -> PathBuf {
     config
         .config_path
@@ -173,7 +400,7 @@ where
     })
 }
 
-async fn run_heartbeat_worker(config: Config) -> Result<()> {
+async fn run_heartbeat_worker(mut config: Config, mut updates: ConfigWatch) -> Result<()> {
     let observer: std::sync::Arc<dyn crate::observability::Observer> =
         std::sync::Arc::from(crate::observability::create_observer(&config.observability));
     let engine = crate::heartbeat::engine::HeartbeatEngine::new(
@@ -188,6 +415,11 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
     loop {
         interval.tick().await;
 
+        // Interval changes restart the worker; everything else applies here.
+        if let Some(next) = reload::take_update(&mut updates) {
+            config = next;
+        }
+
         let tasks = engine.collect_tasks().await?;
         if tasks.is_empty() {
             continue;
@@ -197,7 +429,8 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
             let prompt = format!("[Heartbeat Task] {task}");
             let temp = config.default_temperature;
             if let Err(e) =
-                crate::agent::run(config.clone(), Some(prompt), None, None, temp, vec![]).await
+                crate::agent::run(config.clone(), Some(prompt), None, None, temp, vec![], None)
+                    .await
             {
                 crate::health::mark_component_error("heartbeat", e.to_string());
                 tracing::warn!("Heartbeat task failed: {e}");
@@ -208,6 +441,10 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
     }
 }
 
+fn has_peripheral_event_rules(config: &Config) -> bool {
+    config.peripherals.enabled && !config.peripherals.event_rules.is_empty()
+}
+
 fn has_supervised_channels(config: &Config) -> bool {
     config.channels_config.telegram.is_some()
         || config.channels_config.discord.is_some()
@@ -220,6 +457,9 @@ fn has_supervised_channels(config: &Config) -> bool {
         || config.channels_config.irc.is_some()
         || config.channels_config.lark.is_some()
         || config.channels_config.dingtalk.is_some()
+        || config.channels_config.webchat.is_some()
+        || config.channels_config.nextcloud_talk.is_some()
+        || config.channels_config.mqtt.is_some()
 }
 
 #[cfg(test)]
@@ -284,6 +524,43 @@ mod tests {
             .contains("component exited unexpectedly"));
     }
 
+    #[test]
+    fn reload_config_reports_changed_sections() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        config.save().unwrap();
+        let current =
+            Config::load_from_path(&config.config_path, config.workspace_dir.clone()).unwrap();
+        assert!(reload_config(&current).unwrap().is_none());
+
+        let mut edited = current.clone();
+        edited.autonomy.max_actions_per_hour += 5;
+        edited.save().unwrap();
+
+        let (next, changed) = reload_config(&current).unwrap().expect("changes detected");
+        assert_eq!(changed, vec!["autonomy".to_string()]);
+        assert_eq!(
+            next.autonomy.max_actions_per_hour,
+            current.autonomy.max_actions_per_hour + 5
+        );
+    }
+
+    #[test]
+    fn reload_config_rejects_invalid_changes() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        config.save().unwrap();
+
+        let mut edited = config.clone();
+        edited.default_temperature = 7.5;
+        edited.save().unwrap();
+        let err = reload_config(&config).unwrap_err().to_string();
+        assert!(err.contains("temperature"), "{err}");
+
+        std::fs::write(&config.config_path, "default_temperature = [").unwrap();
+        assert!(reload_config(&config).is_err());
+    }
+
     #[test]
     fn detects_no_supervised_channels() {
         let config = Config::default();
@@ -313,4 +590,11 @@ mod tests {
         });
         assert!(has_supervised_channels(&config));
     }
+
+    #[test]
+    fn detects_webchat_as_supervised_channel() {
+        let mut config = Config::default();
+        config.channels_config.webchat = Some(crate::config::WebChatConfig::default());
+        assert!(has_supervised_channels(&config));
+    }
 }
@@ -227,6 +227,18 @@ pub fn run_models(config: &Config, provider_override: Option<&str>, use_cache: b
 
 // ── Config semantic validation ───────────────────────────────────
 
+/// Error-level findings from the config semantic checks, used by the daemon
+/// to reject a hot-reloaded `config.toml`.
+pub fn config_errors(config: &Config) -> Vec<String> {
+    let mut items = Vec::new();
+    check_config_semantics(config, &mut items);
+    items
+        .into_iter()
+        .filter(|item| item.severity == Severity::Error)
+        .map(|item| item.message)
+        .collect()
+}
+
 fn check_config_semantics(config: &Config, items: &mut Vec<DiagItem>) {
     let cat = "config";
 
@@ -344,6 +356,29 @@ fn check_config_semantics(config: &Config, items: &mut Vec<DiagItem>) {
         }
     }
 
+    // Peripheral event rules: targets and delays
+    for rule in &config.peripherals.event_rules {
+        let has_target = rule.channel.is_some() && rule.to.is_some();
+        if rule.action == crate::config::PeripheralEventAction::Notify && !has_target {
+            items.push(DiagItem::error(
+                cat,
+                format!(
+                    "peripheral event rule \"{}\" notifies but has no channel and to",
+                    rule.name
+                ),
+            ));
+        }
+        if let Some(Err(e)) = rule.delay.as_deref().map(crate::cron::parse_delay) {
+            items.push(DiagItem::error(
+                cat,
+                format!(
+                    "peripheral event rule \"{}\" has an invalid delay: {e}",
+                    rule.name
+                ),
+            ));
+        }
+    }
+
     // Channel: at least one configured
     let cc = &config.channels_config;
     let has_channel = cc.telegram.is_some()
@@ -355,7 +390,10 @@ fn check_config_semantics(config: &Config, items: &mut Vec<DiagItem>) {
         || cc.email.is_some()
         || cc.irc.is_some()
         || cc.lark.is_some()
-        || cc.webhook.is_some();
+        || cc.webhook.is_some()
+        || cc.webchat.is_some()
+        || cc.nextcloud_talk.is_some()
+        || cc.mqtt.is_some();
 
     if has_channel {
         items.push(DiagItem::ok(cat, "at least one channel configured"));
@@ -798,6 +836,15 @@ mod tests {
         assert_eq!(temp_item.unwrap().severity, Severity::Ok);
     }
 
+    #[test]
+    fn config_errors_lists_only_error_findings() {
+        let mut config = Config::default();
+        config.default_temperature = 5.0;
+        let errors = config_errors(&config);
+        assert!(errors.iter().any(|e| e.contains("temperature 5.0")));
+        assert!(!errors.iter().any(|e| e.contains("no channels configured")));
+    }
+
     #[test]
     fn config_validation_warns_no_channels() {
         let config = Config::default();
@@ -891,6 +938,29 @@ mod tests {
         assert_eq!(route_item.unwrap().severity, Severity::Warn);
     }
 
+    #[test]
+    fn config_validation_rejects_incomplete_event_rules() {
+        let mut config = Config::default();
+        config.peripherals.event_rules = vec![crate::config::PeripheralEventRule {
+            name: "doorbell".into(),
+            board: None,
+            event: "button".into(),
+            pin: None,
+            action: crate::config::PeripheralEventAction::Notify,
+            prompt: "Doorbell".into(),
+            delay: Some("soon".into()),
+            channel: Some("telegram".into()),
+            to: None,
+            debounce_ms: 1000,
+            max_per_hour: 30,
+        }];
+        let errors = config_errors(&config);
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("\"doorbell\" notifies but has no channel and to")));
+        assert!(errors.iter().any(|e| e.contains("invalid delay")));
+    }
+
     #[test]
     fn environment_check_finds_git() {
         let mut items = Vec::new();
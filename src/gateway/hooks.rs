@@ -0,0 +1,223 @@
+//! Named inbound webhooks (`/hooks/<name>`): signature checks and
+//! payload-to-message templates.
+
+use crate::config::InboundHookConfig;
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+
+/// Header carrying the signature of outbound reply callbacks.
+pub const REPLY_SIGNATURE_HEADER: &str = "X-ZeroClaw-Signature";
+
+/// Render `{{ path }}` placeholders in `template` from `payload`.
+///
+/// Paths are a JSONPath subset: `$`, `.field`, `['field']` and `[index]`
+/// (the leading `$.` is optional). Strings are inserted as-is, other values
+/// as JSON, and missing values as an empty string.
+pub fn render_template(template: &str, payload: &Value) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        match lookup(payload, after[..end].trim()) {
+            Some(Value::String(s)) => out.push_str(s),
+            Some(Value::Null) | None => {}
+            Some(value) => out.push_str(&value.to_string()),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Resolve a JSONPath-style `path` against `payload`.
+fn lookup<'a>(payload: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut current = payload;
+    let mut chars = path.chars().peekable();
+    while let Some(c) = chars.next() {
+        let segment: String = match c {
+            '.' => {
+                let mut key = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next == '.' || next == '[' {
+                        break;
+                    }
+                    key.push(next);
+                    chars.next();
+                }
+                key
+            }
+            '[' => {
+                let mut inner = String::new();
+                for next in chars.by_ref() {
+                    if next == ']' {
+                        break;
+                    }
+                    inner.push(next);
+                }
+                inner.trim_matches(|c| c == '\'' || c == '"').to_string()
+            }
+            // A path without the leading `$.` starts with a bare key.
+            _ => {
+                let mut key = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next == '.' || next == '[' {
+                        break;
+                    }
+                    key.push(next);
+                    chars.next();
+                }
+                key
+            }
+        };
+        if segment.is_empty() {
+            continue;
+        }
+        current = match current {
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+            Value::Object(map) => map.get(&segment)?,
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+/// Verify an HMAC-SHA256 signature of `body`, given as `sha256=<hex>` or bare hex.
+pub fn verify_hmac(secret: &str, body: &[u8], signature: &str) -> bool {
+    let signature = signature.trim();
+    let hex_sig = signature.strip_prefix("sha256=").unwrap_or(signature);
+    let Ok(expected) = hex::decode(hex_sig) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// Sign `body` as `sha256=<hex>`.
+pub fn sign_body(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Whether the hook authenticates requests itself; hooks without a secret
+/// fall back to gateway pairing.
+pub fn has_own_auth(hook: &InboundHookConfig) -> bool {
+    let set = |value: &Option<String>| value.as_deref().is_some_and(|v| !v.trim().is_empty());
+    set(&hook.secret) || set(&hook.hmac_secret)
+}
+
+/// Whether the agent's reply goes somewhere other than the HTTP response.
+pub fn replies_async(hook: &InboundHookConfig) -> bool {
+    hook.reply_url.is_some() || hook.reply_channel.is_some()
+}
+
+/// POST the reply to `hook.reply_url`, signed with `hmac_secret` when set.
+pub async fn post_reply(hook: &InboundHookConfig, body: &Value) -> anyhow::Result<()> {
+    let Some(ref url) = hook.reply_url else {
+        return Ok(());
+    };
+    let bytes = serde_json::to_vec(body)?;
+    let client = crate::config::build_runtime_proxy_client_with_timeouts("channel.webhook", 30, 10);
+    let mut request = client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(bytes.clone());
+    if let Some(secret) = hook.hmac_secret.as_deref().filter(|s| !s.is_empty()) {
+        request = request.header(REPLY_SIGNATURE_HEADER, sign_body(secret, &bytes));
+    }
+    let resp = request.send().await?;
+    if !resp.status().is_success() {
+        anyhow::bail!("reply callback returned {}", resp.status());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn hook() -> InboundHookConfig {
+        InboundHookConfig {
+            name: "ci".into(),
+            secret: None,
+            hmac_secret: None,
+            signature_header: "X-Hub-Signature-256".into(),
+            message: "{{ $.status }}".into(),
+            sender: None,
+            reply_url: None,
+            reply_channel: None,
+            reply_recipient: None,
+        }
+    }
+
+    #[test]
+    fn render_template_resolves_paths() {
+        let payload = json!({
+            "action": "opened",
+            "repository": {"full_name": "acme/api"},
+            "alerts": [{"labels": {"alertname": "HighCPU"}}],
+            "count": 3,
+            "meta": {"build-id": "b7"}
+        });
+        assert_eq!(
+            render_template(
+                "{{ $.action }} on {{$.repository.full_name}}: {{ $.alerts[0].labels.alertname }} x{{ count }} ({{ $['meta']['build-id'] }})",
+                &payload
+            ),
+            "opened on acme/api: HighCPU x3 (b7)"
+        );
+    }
+
+    #[test]
+    fn render_template_handles_missing_and_structured_values() {
+        let payload = json!({"labels": {"a": 1}, "empty": null});
+        assert_eq!(render_template("[{{ $.nope.x }}]", &payload), "[]");
+        assert_eq!(render_template("{{ $.empty }}", &payload), "");
+        assert_eq!(render_template("{{ $.labels }}", &payload), r#"{"a":1}"#);
+        assert_eq!(
+            render_template("open {{ $.labels", &payload),
+            "open {{ $.labels"
+        );
+    }
+
+    #[test]
+    fn hmac_signatures_round_trip() {
+        let body = br#"{"ok":true}"#;
+        let signature = sign_body("key", body);
+        assert!(signature.starts_with("sha256="));
+        assert!(verify_hmac("key", body, &signature));
+        assert!(verify_hmac(
+            "key",
+            body,
+            signature.trim_start_matches("sha256=")
+        ));
+        assert!(!verify_hmac("other", body, &signature));
+        assert!(!verify_hmac("key", b"{}", &signature));
+        assert!(!verify_hmac("key", body, ""));
+    }
+
+    #[test]
+    fn hook_auth_and_reply_modes() {
+        let mut h = hook();
+        assert!(!has_own_auth(&h));
+        assert!(!replies_async(&h));
+        h.secret = Some("  ".into());
+        assert!(!has_own_auth(&h));
+        h.hmac_secret = Some("k".into());
+        assert!(has_own_auth(&h));
+        h.reply_channel = Some("telegram".into());
+        assert!(replies_async(&h));
+    }
+}
@@ -7,9 +7,15 @@
 //! - Request timeouts (30s) to prevent slow-loris attacks
 //! - Header sanitization (handled by axum/hyper)
 
-use crate::channels::{Channel, SendMessage, WhatsAppChannel};
+mod hooks;
+
+use crate::agent::context::estimate_tokens;
+use crate::channels::webchat::{self, ClientFrame, ServerFrame, WebChatHub};
+use crate::channels::{Channel, NextcloudTalkChannel, SendMessage, WhatsAppChannel};
+use crate::config::reload::ConfigWatch;
 use crate::config::Config;
 use crate::memory::{self, Memory, MemoryCategory};
+use crate::observability::trace;
 use crate::providers::{self, Provider};
 use crate::runtime;
 use crate::security::pairing::{constant_time_eq, is_public_bind, PairingGuard};
@@ -19,14 +25,19 @@ use crate::util::truncate_with_ellipsis;
 use anyhow::{Context, Result};
 use axum::{
     body::Bytes,
-    extract::{ConnectInfo, Query, State},
-    http::{header, HeaderMap, StatusCode},
-    response::{IntoResponse, Json},
+    extract::ws::{Message as WsMessage, WebSocket, WebSocketUpgrade},
+    extract::{ConnectInfo, Path, Query, Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
+use opentelemetry::trace::SpanKind;
+use opentelemetry::KeyValue;
 use parking_lot::Mutex;
 use std::collections::HashMap;
+use std::future::IntoFuture;
 use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -44,6 +55,11 @@ pub const RATE_LIMIT_WINDOW_SECS: u64 = 60;
 pub const RATE_LIMIT_MAX_KEYS_DEFAULT: usize = 10_000;
 /// Fallback max distinct idempotency keys retained in gateway memory.
 pub const IDEMPOTENCY_MAX_KEYS_DEFAULT: usize = 10_000;
+/// Time a new WebChat socket has to send its `auth` frame.
+pub const WEBCHAT_AUTH_TIMEOUT_SECS: u64 = 10;
+
+/// Browser UI served at `/chat`.
+const WEBCHAT_HTML: &str = include_str!("webchat.html");
 
 fn webhook_memory_key() -> String {
     format!("webhook_msg_{}", Uuid::new_v4())
@@ -53,6 +69,14 @@ fn whatsapp_memory_key(msg: &crate::channels::traits::ChannelMessage) -> String
     format!("whatsapp_{}_{}", msg.sender, msg.id)
 }
 
+fn hook_memory_key(name: &str) -> String {
+    format!("hook_{name}_{}", Uuid::new_v4())
+}
+
+fn nextcloud_talk_memory_key(msg: &crate::channels::traits::ChannelMessage) -> String {
+    format!("nextcloud_talk_{}_{}", msg.sender, msg.id)
+}
+
 fn hash_webhook_secret(value: &str) -> String {
     use sha2::{Digest, Sha256};
 
@@ -274,14 +298,40 @@ pub struct AppState {
     pub whatsapp: Option<Arc<WhatsAppChannel>>,
     /// `WhatsApp` app secret for webhook signature verification (`X-Hub-Signature-256`)
     pub whatsapp_app_secret: Option<Arc<str>>,
+    /// Nextcloud Talk bot (verifies its own webhook signatures)
+    pub nextcloud_talk: Option<Arc<NextcloudTalkChannel>>,
     /// Observability backend for metrics scraping
     pub observer: Arc<dyn crate::observability::Observer>,
+    /// Speech-to-text backend for inbound voice messages (if enabled)
+    pub transcriber: Option<Arc<dyn crate::transcription::Transcriber>>,
+    /// Text-to-speech backend for spoken replies (if enabled)
+    pub synthesizer: Option<Arc<dyn crate::tts::SpeechSynthesizer>>,
+    /// Per-sender `/voice` reply mode overrides
+    pub voice_reply_preferences: Arc<crate::tts::VoiceReplyPreferences>,
 }
 
-/// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
-#[allow(clippy::too_many_lines)]
-pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
-    // ── Security: refuse public bind without tunnel or explicit opt-in ──
+/// State for the `/chat` routes, kept apart from [`AppState`] because the
+/// socket outlives the request that opened it.
+#[derive(Clone)]
+pub struct WebChatState {
+    pub pairing: Arc<PairingGuard>,
+    pub hub: Arc<WebChatHub>,
+    /// Largest WebSocket message accepted (base64 upload plus framing).
+    pub max_message_size: usize,
+    /// Page origins besides the gateway's own host that may open the socket.
+    pub allowed_origins: Vec<String>,
+}
+
+/// Router for the WebChat page and its WebSocket.
+pub fn webchat_router(state: WebChatState) -> Router {
+    Router::new()
+        .route("/chat", get(handle_webchat_page))
+        .route("/chat/ws", get(handle_webchat_socket))
+        .with_state(state)
+}
+
+/// Refuse a public bind unless a tunnel is configured or it is explicitly allowed.
+fn check_public_bind(host: &str, config: &Config) -> Result<()> {
     if is_public_bind(host) && config.tunnel.provider == "none" && !config.gateway.allow_public_bind
     {
         anyhow::bail!(
@@ -290,13 +340,15 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
              [gateway] allow_public_bind = true in config.toml (NOT recommended)."
         );
     }
-    let config_state = Arc::new(Mutex::new(config.clone()));
-
-    let addr: SocketAddr = format!("{host}:{port}").parse()?;
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    let actual_port = listener.local_addr()?.port();
-    let display_addr = format!("{host}:{actual_port}");
+    Ok(())
+}
 
+/// Build handler state from `config`.
+///
+/// On reload, `previous` carries over memory, the observer, paired tokens,
+/// `/voice` preferences, and rate-limit and idempotency windows whose limits
+/// did not change, so a config edit never unpairs clients or resets limits.
+fn build_app_state(config: &Config, previous: Option<&AppState>) -> Result<AppState> {
     let provider: Arc<dyn Provider> = Arc::from(providers::create_resilient_provider_with_options(
         config.default_provider.as_deref().unwrap_or("openrouter"),
         config.api_key.as_deref(),
@@ -312,43 +364,16 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .default_model
         .clone()
         .unwrap_or_else(|| "anthropic/claude-sonnet-4".into());
-    let temperature = config.default_temperature;
-    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
-        &config.memory,
-        Some(&config.storage.provider.config),
-        &config.workspace_dir,
-        config.api_key.as_deref(),
-    )?);
-    let runtime: Arc<dyn runtime::RuntimeAdapter> =
-        Arc::from(runtime::create_runtime(&config.runtime)?);
-    let security = Arc::new(SecurityPolicy::from_config(
-        &config.autonomy,
-        &config.workspace_dir,
-    ));
-
-    let (composio_key, composio_entity_id) = if config.composio.enabled {
-        (
-            config.composio.api_key.as_deref(),
-            Some(config.composio.entity_id.as_str()),
-        )
-    } else {
-        (None, None)
+    let mem: Arc<dyn Memory> = match previous {
+        Some(previous) => Arc::clone(&previous.mem),
+        None => Arc::from(memory::create_memory_with_storage(
+            &config.memory,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?),
     };
 
-    let _tools_registry = Arc::new(tools::all_tools_with_runtime(
-        Arc::new(config.clone()),
-        &security,
-        runtime,
-        Arc::clone(&mem),
-        composio_key,
-        composio_entity_id,
-        &config.browser,
-        &config.http_request,
-        &config.workspace_dir,
-        &config.agents,
-        config.api_key.as_deref(),
-        &config,
-    ));
     // Extract webhook secret for authentication
     let webhook_secret_hash: Option<Arc<str>> =
         config.channels_config.webhook.as_ref().and_then(|webhook| {
@@ -359,15 +384,26 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             })
         });
 
+    let transcriber: Option<Arc<dyn crate::transcription::Transcriber>> =
+        crate::transcription::create_transcriber(&config.transcription)?.map(Arc::from);
+    let synthesizer: Option<Arc<dyn crate::tts::SpeechSynthesizer>> =
+        crate::tts::create_synthesizer(&config.tts)?.map(Arc::from);
+
     // WhatsApp channel (if configured)
     let whatsapp_channel: Option<Arc<WhatsAppChannel>> =
         config.channels_config.whatsapp.as_ref().map(|wa| {
-            Arc::new(WhatsAppChannel::new(
+            let channel = WhatsAppChannel::new(
                 wa.access_token.clone(),
                 wa.phone_number_id.clone(),
                 wa.verify_token.clone(),
                 wa.allowed_numbers.clone(),
-            ))
+            );
+            Arc::new(if transcriber.is_some() {
+                channel
+                    .with_voice_inbox(crate::transcription::voice_inbox_dir(&config.workspace_dir))
+            } else {
+                channel
+            })
         });
 
     // WhatsApp app secret for webhook signature verification
@@ -389,27 +425,237 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         })
         .map(Arc::from);
 
+    // Nextcloud Talk bot (if configured)
+    let nextcloud_talk_channel: Option<Arc<NextcloudTalkChannel>> = config
+        .channels_config
+        .nextcloud_talk
+        .as_ref()
+        .map(|nc| Arc::new(NextcloudTalkChannel::from_config(nc)));
+
+    // Tokens paired since startup live in the running guard; config.toml may
+    // not have been re-read since they were persisted.
+    let previous_config = previous.map(|previous| previous.config.lock().gateway.clone());
+    let mut config = config.clone();
+    if let Some(previous) = previous {
+        config.gateway.paired_tokens = previous.pairing.tokens();
+    }
+
     // ── Pairing guard ──────────────────────────────────────
-    let pairing = Arc::new(PairingGuard::new(
-        config.gateway.require_pairing,
-        &config.gateway.paired_tokens,
-    ));
-    let rate_limit_max_keys = normalize_max_keys(
-        config.gateway.rate_limit_max_keys,
-        RATE_LIMIT_MAX_KEYS_DEFAULT,
-    );
-    let rate_limiter = Arc::new(GatewayRateLimiter::new(
-        config.gateway.pair_rate_limit_per_minute,
-        config.gateway.webhook_rate_limit_per_minute,
-        rate_limit_max_keys,
+    let pairing = match previous {
+        Some(previous) if previous.pairing.require_pairing() == config.gateway.require_pairing => {
+            Arc::clone(&previous.pairing)
+        }
+        _ => Arc::new(PairingGuard::new(
+            config.gateway.require_pairing,
+            &config.gateway.paired_tokens,
+        )),
+    };
+    let same_rate_limits = previous_config.as_ref().is_some_and(|old| {
+        old.pair_rate_limit_per_minute == config.gateway.pair_rate_limit_per_minute
+            && old.webhook_rate_limit_per_minute == config.gateway.webhook_rate_limit_per_minute
+            && old.rate_limit_max_keys == config.gateway.rate_limit_max_keys
+    });
+    let rate_limiter = match previous {
+        Some(previous) if same_rate_limits => Arc::clone(&previous.rate_limiter),
+        _ => Arc::new(GatewayRateLimiter::new(
+            config.gateway.pair_rate_limit_per_minute,
+            config.gateway.webhook_rate_limit_per_minute,
+            normalize_max_keys(
+                config.gateway.rate_limit_max_keys,
+                RATE_LIMIT_MAX_KEYS_DEFAULT,
+            ),
+        )),
+    };
+    let same_idempotency = previous_config.as_ref().is_some_and(|old| {
+        old.idempotency_ttl_secs == config.gateway.idempotency_ttl_secs
+            && old.idempotency_max_keys == config.gateway.idempotency_max_keys
+    });
+    let idempotency_store = match previous {
+        Some(previous) if same_idempotency => Arc::clone(&previous.idempotency_store),
+        _ => Arc::new(IdempotencyStore::new(
+            Duration::from_secs(config.gateway.idempotency_ttl_secs.max(1)),
+            normalize_max_keys(
+                config.gateway.idempotency_max_keys,
+                IDEMPOTENCY_MAX_KEYS_DEFAULT,
+            ),
+        )),
+    };
+
+    let observer: Arc<dyn crate::observability::Observer> = match previous {
+        Some(previous) => Arc::clone(&previous.observer),
+        None => Arc::from(crate::observability::create_observer(&config.observability)),
+    };
+    crate::observability::trace::set_model_pricing(&config.cost.prices);
+
+    let config_state = match previous {
+        Some(previous) => {
+            *previous.config.lock() = config.clone();
+            Arc::clone(&previous.config)
+        }
+        None => Arc::new(Mutex::new(config.clone())),
+    };
+
+    Ok(AppState {
+        config: config_state,
+        provider,
+        model,
+        temperature: config.default_temperature,
+        mem,
+        auto_save: config.memory.auto_save,
+        webhook_secret_hash,
+        pairing,
+        trust_forwarded_headers: config.gateway.trust_forwarded_headers,
+        rate_limiter,
+        idempotency_store,
+        whatsapp: whatsapp_channel,
+        whatsapp_app_secret,
+        nextcloud_talk: nextcloud_talk_channel,
+        observer,
+        transcriber,
+        synthesizer,
+        voice_reply_preferences: previous.map_or_else(
+            || Arc::new(crate::tts::VoiceReplyPreferences::default()),
+            |previous| Arc::clone(&previous.voice_reply_preferences),
+        ),
+    })
+}
+
+/// Routes for `state`, without the request-limit layers.
+fn gateway_router(state: AppState) -> Router {
+    let webchat_state = state
+        .config
+        .lock()
+        .channels_config
+        .webchat
+        .as_ref()
+        .map(|webchat| WebChatState {
+            pairing: Arc::clone(&state.pairing),
+            hub: webchat::shared_hub(),
+            max_message_size: webchat.max_upload_bytes.div_ceil(3) * 4 + 4096,
+            allowed_origins: webchat.allowed_origins.clone(),
+        });
+
+    let mut app = Router::new()
+        .route("/health", get(handle_health))
+        .route("/metrics", get(handle_metrics))
+        .route("/pair", post(handle_pair))
+        .route("/webhook", post(handle_webhook))
+        .route("/hooks/{name}", post(handle_hook))
+        .route("/whatsapp", get(handle_whatsapp_verify))
+        .route("/whatsapp", post(handle_whatsapp_message))
+        .route("/nextcloud-talk", post(handle_nextcloud_talk_message))
+        .with_state(state);
+    if let Some(webchat_state) = webchat_state {
+        app = app.merge(webchat_router(webchat_state));
+    }
+    app
+}
+
+/// Router currently serving requests; replaced wholesale on config reload so
+/// in-flight requests finish against the state they started with.
+type LiveRouter = Arc<parking_lot::RwLock<Router>>;
+
+async fn dispatch_live(State(live): State<LiveRouter>, request: Request) -> Response {
+    let mut router = live.read().clone();
+    match tower::Service::call(&mut router, request).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    }
+}
+
+/// Rebuild the gateway state for every config accepted by the daemon.
+///
+/// The listener and tunnel stay up; only a change that would make the bind
+/// refused at startup stops the gateway.
+async fn reload_gateway(
+    host: &str,
+    mut updates: ConfigWatch,
+    mut state: AppState,
+    live: LiveRouter,
+) -> Result<()> {
+    while updates.changed().await.is_ok() {
+        let next = updates.borrow_and_update().as_ref().clone();
+        check_public_bind(host, &next)?;
+        match build_app_state(&next, Some(&state)) {
+            Ok(next_state) => {
+                if let Some(code) = next_state.pairing.pairing_code() {
+                    println!("  🔐 Pairing now required — one-time code: {code}");
+                }
+                *live.write() = gateway_router(next_state.clone());
+                state = next_state;
+                tracing::info!("Gateway applied reloaded config");
+            }
+            Err(e) => {
+                tracing::error!("Gateway kept previous config; reload failed: {e:#}");
+            }
+        }
+    }
+    // The daemon stopped publishing updates; keep serving the last config.
+    std::future::pending().await
+}
+
+/// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
+/// WebChat is left out: it only works under `zeroclaw daemon`.
+pub async fn run_gateway(host: &str, port: u16, mut config: Config) -> Result<()> {
+    webchat::disable_outside_daemon(&mut config, "gateway");
+    serve_gateway(host, port, config, None).await
+}
+
+/// Like [`run_gateway`], applying every config the daemon publishes on
+/// `updates` in place instead of restarting the listener.
+pub async fn run_gateway_with_reload(
+    host: &str,
+    port: u16,
+    config: Config,
+    updates: ConfigWatch,
+) -> Result<()> {
+    serve_gateway(host, port, config, Some(updates)).await
+}
+
+async fn serve_gateway(
+    host: &str,
+    port: u16,
+    config: Config,
+    updates: Option<ConfigWatch>,
+) -> Result<()> {
+    // ── Security: refuse public bind without tunnel or explicit opt-in ──
+    check_public_bind(host, &config)?;
+
+    let addr: SocketAddr = format!("{host}:{port}").parse()?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let actual_port = listener.local_addr()?.port();
+    let display_addr = format!("{host}:{actual_port}");
+
+    let state = build_app_state(&config, None)?;
+    let runtime: Arc<dyn runtime::RuntimeAdapter> =
+        Arc::from(runtime::create_runtime(&config.runtime)?);
+    let security = Arc::new(SecurityPolicy::from_config(
+        &config.autonomy,
+        &config.workspace_dir,
     ));
-    let idempotency_max_keys = normalize_max_keys(
-        config.gateway.idempotency_max_keys,
-        IDEMPOTENCY_MAX_KEYS_DEFAULT,
-    );
-    let idempotency_store = Arc::new(IdempotencyStore::new(
-        Duration::from_secs(config.gateway.idempotency_ttl_secs.max(1)),
-        idempotency_max_keys,
+
+    let (composio_key, composio_entity_id) = if config.composio.enabled {
+        (
+            config.composio.api_key.as_deref(),
+            Some(config.composio.entity_id.as_str()),
+        )
+    } else {
+        (None, None)
+    };
+
+    let _tools_registry = Arc::new(tools::all_tools_with_runtime(
+        Arc::new(config.clone()),
+        &security,
+        runtime,
+        Arc::clone(&state.mem),
+        composio_key,
+        composio_entity_id,
+        &config.browser,
+        &config.http_request,
+        &config.workspace_dir,
+        &config.agents,
+        config.api_key.as_deref(),
+        &config,
     ));
 
     // ── Tunnel ────────────────────────────────────────────────
@@ -436,20 +682,34 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     }
     println!("  POST /pair      — pair a new client (X-Pairing-Code header)");
     println!("  POST /webhook   — {{\"message\": \"your prompt\"}}");
-    if whatsapp_channel.is_some() {
+    for hook in config
+        .channels_config
+        .webhook
+        .iter()
+        .flat_map(|webhook| &webhook.hooks)
+    {
+        println!("  POST /hooks/{}", hook.name);
+    }
+    if state.whatsapp.is_some() {
         println!("  GET  /whatsapp  — Meta webhook verification");
         println!("  POST /whatsapp  — WhatsApp message webhook");
     }
+    if state.nextcloud_talk.is_some() {
+        println!("  POST /nextcloud-talk — Nextcloud Talk bot webhook");
+    }
+    if config.channels_config.webchat.is_some() {
+        println!("  GET  /chat      — WebChat UI");
+    }
     println!("  GET  /health    — health check");
     println!("  GET  /metrics   — Prometheus metrics");
-    if let Some(code) = pairing.pairing_code() {
+    if let Some(code) = state.pairing.pairing_code() {
         println!();
         println!("  🔐 PAIRING REQUIRED — use this one-time code:");
         println!("     ┌──────────────┐");
         println!("     │  {code}  │");
         println!("     └──────────────┘");
         println!("     Send: POST /pair with header X-Pairing-Code: {code}");
-    } else if pairing.require_pairing() {
+    } else if state.pairing.require_pairing() {
         println!("  🔒 Pairing: ACTIVE (bearer token required)");
     } else {
         println!("  ⚠️  Pairing: DISABLED (all requests accepted)");
@@ -458,48 +718,31 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
 
     crate::health::mark_component_ok("gateway");
 
-    // Build shared state
-    let observer: Arc<dyn crate::observability::Observer> =
-        Arc::from(crate::observability::create_observer(&config.observability));
-
-    let state = AppState {
-        config: config_state,
-        provider,
-        model,
-        temperature,
-        mem,
-        auto_save: config.memory.auto_save,
-        webhook_secret_hash,
-        pairing,
-        trust_forwarded_headers: config.gateway.trust_forwarded_headers,
-        rate_limiter,
-        idempotency_store,
-        whatsapp: whatsapp_channel,
-        whatsapp_app_secret,
-        observer,
-    };
-
     // Build router with middleware
+    let live: LiveRouter = Arc::new(parking_lot::RwLock::new(gateway_router(state.clone())));
     let app = Router::new()
-        .route("/health", get(handle_health))
-        .route("/metrics", get(handle_metrics))
-        .route("/pair", post(handle_pair))
-        .route("/webhook", post(handle_webhook))
-        .route("/whatsapp", get(handle_whatsapp_verify))
-        .route("/whatsapp", post(handle_whatsapp_message))
-        .with_state(state)
+        .fallback(dispatch_live)
+        .with_state(Arc::clone(&live))
         .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
         .layer(TimeoutLayer::with_status_code(
             StatusCode::REQUEST_TIMEOUT,
             Duration::from_secs(REQUEST_TIMEOUT_SECS),
-        ));
+        ))
+        .layer(middleware::from_fn(trace_request));
 
     // Run the server
-    axum::serve(
+    let serve = axum::serve(
         listener,
         app.into_make_service_with_connect_info::<SocketAddr>(),
     )
-    .await?;
+    .into_future();
+    match updates {
+        Some(updates) => tokio::select! {
+            result = serve => result?,
+            result = reload_gateway(host, updates, state, live) => result?,
+        },
+        None => serve.await?,
+    }
 
     Ok(())
 }
@@ -508,6 +751,99 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
 // AXUM HANDLERS
 // ══════════════════════════════════════════════════════════════════════════════
 
+/// Run each request under a `gateway.request` span that continues an incoming
+/// W3C `traceparent`, and return the trace id in the response headers.
+async fn trace_request(request: Request, next: Next) -> Response {
+    let parent = request
+        .headers()
+        .get(trace::TRACEPARENT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(trace::parent_from_traceparent)
+        .unwrap_or_default();
+    let request_cx = trace::start_span_with_parent(
+        &parent,
+        "gateway.request",
+        SpanKind::Server,
+        vec![
+            KeyValue::new("http.request.method", request.method().to_string()),
+            KeyValue::new("url.path", request.uri().path().to_string()),
+        ],
+    );
+
+    let mut response = trace::in_root_span(request_cx.clone(), next.run(request)).await;
+
+    let status = response.status();
+    trace::set_attributes(
+        &request_cx,
+        vec![KeyValue::new(
+            "http.response.status_code",
+            i64::from(status.as_u16()),
+        )],
+    );
+    trace::end_span(
+        &request_cx,
+        if status.is_server_error() {
+            Err(status.to_string())
+        } else {
+            Ok(())
+        },
+    );
+
+    let headers = response.headers_mut();
+    if let Some(value) =
+        trace::traceparent(&request_cx).and_then(|value| HeaderValue::from_str(&value).ok())
+    {
+        headers.insert(trace::TRACEPARENT_HEADER, value);
+    }
+    if let Some(value) =
+        trace::trace_id(&request_cx).and_then(|value| HeaderValue::from_str(&value).ok())
+    {
+        headers.insert(trace::TRACE_ID_HEADER, value);
+    }
+    response
+}
+
+/// One-shot `simple_chat` against the gateway provider under an `llm.call` span.
+async fn traced_simple_chat(state: &AppState, message: &str) -> Result<String> {
+    let provider_label = state
+        .config
+        .lock()
+        .default_provider
+        .clone()
+        .unwrap_or_else(|| "unknown".to_string());
+    let llm_cx = trace::start_span(
+        "llm.call",
+        SpanKind::Client,
+        vec![
+            KeyValue::new("gen_ai.system", provider_label.clone()),
+            KeyValue::new("gen_ai.request.model", state.model.clone()),
+        ],
+    );
+
+    let result = trace::in_span(
+        llm_cx.clone(),
+        state
+            .provider
+            .simple_chat(message, &state.model, state.temperature),
+    )
+    .await;
+
+    match &result {
+        Ok(response) => {
+            trace::TurnUsage::default().record_llm_call(
+                &llm_cx,
+                &provider_label,
+                &state.model,
+                estimate_tokens(message) as u64,
+                estimate_tokens(response) as u64,
+            );
+            trace::end_span(&llm_cx, Ok(()));
+        }
+        Err(e) => trace::end_span(&llm_cx, Err(providers::sanitize_api_error(&e.to_string()))),
+    }
+    result
+}
+
 /// GET /health — always public (no secrets leaked)
 async fn handle_health(State(state): State<AppState>) -> impl IntoResponse {
     let body = serde_json::json!({
@@ -732,11 +1068,7 @@ async fn handle_webhook(
             messages_count: 1,
         });
 
-    match state
-        .provider
-        .simple_chat(message, &state.model, state.temperature)
-        .await
-    {
+    match traced_simple_chat(&state, message).await {
         Ok(response) => {
             let duration = started_at.elapsed();
             state
@@ -803,6 +1135,194 @@ async fn handle_webhook(
     }
 }
 
+/// Check a named hook's own secret/HMAC, or gateway pairing when it has none.
+fn authorize_hook(
+    state: &AppState,
+    hook: &crate::config::InboundHookConfig,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> bool {
+    let header_str = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .unwrap_or("")
+    };
+
+    if !hooks::has_own_auth(hook) {
+        if !state.pairing.require_pairing() {
+            return true;
+        }
+        let token = header_str(header::AUTHORIZATION.as_str())
+            .strip_prefix("Bearer ")
+            .unwrap_or("");
+        return state.pairing.is_authenticated(token);
+    }
+
+    if let Some(secret) = hook.secret.as_deref().map(str::trim) {
+        if !secret.is_empty() && !constant_time_eq(header_str("X-Webhook-Secret"), secret) {
+            return false;
+        }
+    }
+    if let Some(key) = hook.hmac_secret.as_deref() {
+        if !key.is_empty() && !hooks::verify_hmac(key, body, header_str(&hook.signature_header)) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Run the agent for a hook and deliver the reply to its callback URL and/or channel.
+async fn reply_to_hook(
+    state: AppState,
+    hook: crate::config::InboundHookConfig,
+    sender: String,
+    message: String,
+) {
+    let response = match traced_simple_chat(&state, &message).await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::error!(
+                "Hook {}: provider error: {}",
+                hook.name,
+                providers::sanitize_api_error(&e.to_string())
+            );
+            return;
+        }
+    };
+
+    if hook.reply_url.is_some() {
+        let body = serde_json::json!({
+            "hook": hook.name,
+            "sender": sender,
+            "message": message,
+            "response": response,
+        });
+        if let Err(e) = hooks::post_reply(&hook, &body).await {
+            tracing::error!("Hook {}: reply callback failed: {e}", hook.name);
+        }
+    }
+
+    if let Some(ref channel) = hook.reply_channel {
+        let Some(ref recipient) = hook.reply_recipient else {
+            tracing::error!(
+                "Hook {}: reply_channel is set without reply_recipient",
+                hook.name
+            );
+            return;
+        };
+        let config = state.config.lock().clone();
+        if let Err(e) =
+            crate::channels::send_to_configured_channel(&config, channel, recipient, &response)
+                .await
+        {
+            tracing::error!("Hook {}: delivery to {channel} failed: {e}", hook.name);
+        }
+    }
+}
+
+/// POST /hooks/{name} — named inbound webhook with payload mapping
+async fn handle_hook(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let client_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&client_key) {
+        tracing::warn!("/hooks rate limit exceeded for key: {client_key}");
+        let err = serde_json::json!({
+            "error": "Too many webhook requests. Please retry later.",
+            "retry_after": RATE_LIMIT_WINDOW_SECS,
+        });
+        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
+    }
+
+    let hook = state
+        .config
+        .lock()
+        .channels_config
+        .webhook
+        .as_ref()
+        .and_then(|webhook| webhook.hooks.iter().find(|h| h.name == name).cloned());
+    let Some(hook) = hook else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Unknown hook"})),
+        );
+    };
+
+    if !authorize_hook(&state, &hook, &headers, &body) {
+        tracing::warn!("Hook {name}: rejected — invalid or missing secret/signature");
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Unauthorized"})),
+        );
+    }
+
+    let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
+        tracing::warn!("Hook {name}: JSON parse error");
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid JSON payload"})),
+        );
+    };
+
+    let mapped = hooks::render_template(&hook.message, &payload);
+    let mapped = mapped.trim();
+    if mapped.is_empty() {
+        return (
+            StatusCode::OK,
+            Json(serde_json::json!({"status": "ignored"})),
+        );
+    }
+    let sender = hook
+        .sender
+        .as_deref()
+        .map(|template| hooks::render_template(template, &payload))
+        .filter(|sender| !sender.trim().is_empty())
+        .unwrap_or_else(|| name.clone());
+    let message = format!("[{name} webhook from {sender}] {mapped}");
+    tracing::info!(
+        "Hook {name}: message from {sender}: {}",
+        truncate_with_ellipsis(mapped, 50)
+    );
+
+    if state.auto_save {
+        let key = hook_memory_key(&name);
+        let _ = state
+            .mem
+            .store(&key, &message, MemoryCategory::Conversation, None)
+            .await;
+    }
+
+    if hooks::replies_async(&hook) {
+        tokio::spawn(reply_to_hook(state, hook, sender, message));
+        return (
+            StatusCode::ACCEPTED,
+            Json(serde_json::json!({"status": "accepted"})),
+        );
+    }
+
+    match traced_simple_chat(&state, &message).await {
+        Ok(response) => {
+            let body = serde_json::json!({"response": response, "model": state.model});
+            (StatusCode::OK, Json(body))
+        }
+        Err(e) => {
+            tracing::error!(
+                "Hook {name}: provider error: {}",
+                providers::sanitize_api_error(&e.to_string())
+            );
+            let err = serde_json::json!({"error": "LLM request failed"});
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(err))
+        }
+    }
+}
+
 /// `WhatsApp` verification query params
 #[derive(serde::Deserialize)]
 pub struct WhatsAppVerifyQuery {
@@ -863,8 +1383,158 @@ pub fn verify_whatsapp_signature(app_secret: &str, body: &[u8], signature_header
     };
     mac.update(body);
 
-    // Constant-time comparison
-    mac.verify_slice(&expected).is_ok()
+    // Constant-time comparison
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// Replace the marker of a downloaded `WhatsApp` voice note with its transcript.
+async fn transcribe_whatsapp_voice(
+    state: &AppState,
+    wa: &WhatsAppChannel,
+    mut msg: crate::channels::traits::ChannelMessage,
+    voice_notes: &[std::path::PathBuf],
+) -> crate::channels::traits::ChannelMessage {
+    let Some(ref transcriber) = state.transcriber else {
+        return msg;
+    };
+    if voice_notes.is_empty() {
+        return msg;
+    }
+
+    let (transcription_config, workspace_dir) = {
+        let config = state.config.lock();
+        (config.transcription.clone(), config.workspace_dir.clone())
+    };
+    let result = crate::transcription::transcribe_voice_markers(
+        transcriber.as_ref(),
+        &msg.content,
+        voice_notes,
+        &crate::transcription::voice_inbox_dir(&workspace_dir),
+        &transcription_config,
+    )
+    .await;
+
+    if transcription_config.show_transcript {
+        for transcript in result.transcripts.iter().filter(|t| !t.is_empty()) {
+            let _ = wa
+                .send(&SendMessage::new(
+                    format!("🎙️ Transcript: {transcript}"),
+                    &msg.reply_target,
+                ))
+                .await;
+        }
+    }
+
+    msg.content = result.content;
+    msg
+}
+
+/// Reply on `WhatsApp`, speaking the response when the sender's voice mode asks for it.
+async fn send_whatsapp_reply(
+    state: &AppState,
+    wa: &WhatsAppChannel,
+    msg: &crate::channels::traits::ChannelMessage,
+    response: String,
+    user_sent_voice: bool,
+) {
+    if let Some(ref synthesizer) = state.synthesizer {
+        let (tts_config, workspace_dir) = {
+            let config = state.config.lock();
+            (config.tts.clone(), config.workspace_dir.clone())
+        };
+        let mode = state
+            .voice_reply_preferences
+            .mode_for(&whatsapp_sender_key(msg), tts_config.reply_mode);
+
+        if crate::tts::should_reply_with_voice(&tts_config, mode, wa.name(), user_sent_voice) {
+            match crate::tts::send_voice_reply(
+                synthesizer.as_ref(),
+                wa,
+                &msg.reply_target,
+                &response,
+                &workspace_dir,
+                &tts_config,
+            )
+            .await
+            {
+                Ok(()) => return,
+                Err(e) => {
+                    tracing::warn!("WhatsApp voice reply failed, falling back to text: {e}");
+                }
+            }
+        }
+    }
+
+    if let Err(e) = wa
+        .send(&SendMessage::new(response, &msg.reply_target))
+        .await
+    {
+        tracing::error!("Failed to send WhatsApp reply: {e}");
+    }
+}
+
+fn whatsapp_sender_key(msg: &crate::channels::traits::ChannelMessage) -> String {
+    format!("{}_{}", msg.channel, msg.sender)
+}
+
+/// Answer the messages from one `WhatsApp` webhook, transcribing voice notes first.
+async fn reply_whatsapp(
+    state: AppState,
+    wa: Arc<WhatsAppChannel>,
+    messages: Vec<(crate::channels::traits::ChannelMessage, Option<String>)>,
+) {
+    for (msg, audio_media_id) in messages {
+        let (msg, voice_note) = match audio_media_id {
+            Some(media_id) => wa.attach_inbound_voice(msg, &media_id).await,
+            None => (msg, None),
+        };
+        let voice_notes: Vec<_> = voice_note.into_iter().collect();
+        let user_sent_voice = !voice_notes.is_empty();
+        let msg = transcribe_whatsapp_voice(&state, &wa, msg, &voice_notes).await;
+
+        tracing::info!(
+            "WhatsApp message from {}: {}",
+            msg.sender,
+            truncate_with_ellipsis(&msg.content, 50)
+        );
+
+        if let Some(argument) = crate::tts::parse_voice_command(&msg.content) {
+            let tts_config = state.config.lock().tts.clone();
+            let reply = crate::tts::voice_command_response(
+                &state.voice_reply_preferences,
+                &whatsapp_sender_key(&msg),
+                &argument,
+                &tts_config,
+            );
+            let _ = wa.send(&SendMessage::new(reply, &msg.reply_target)).await;
+            continue;
+        }
+
+        // Auto-save to memory
+        if state.auto_save {
+            let key = whatsapp_memory_key(&msg);
+            let _ = state
+                .mem
+                .store(&key, &msg.content, MemoryCategory::Conversation, None)
+                .await;
+        }
+
+        // Call the LLM
+        match traced_simple_chat(&state, &msg.content).await {
+            Ok(response) => {
+                send_whatsapp_reply(&state, &wa, &msg, response, user_sent_voice).await;
+            }
+            Err(e) => {
+                tracing::error!("LLM error for WhatsApp message: {e:#}");
+                let _ = wa
+                    .send(&SendMessage::new(
+                        "Sorry, I couldn't process your message right now.",
+                        &msg.reply_target,
+                    ))
+                    .await;
+            }
+        }
+    }
 }
 
 /// POST /whatsapp — incoming message webhook
@@ -911,60 +1581,233 @@ async fn handle_whatsapp_message(
         );
     };
 
-    // Parse messages from the webhook payload
-    let messages = wa.parse_webhook_payload(&payload);
+    // Voice notes are downloaded and transcribed before the agent answers,
+    // which can outlast Meta's webhook timeout, so acknowledge right away and
+    // answer the batch in order in the background.
+    let messages = wa.parse_webhook_payload_with_media(&payload);
+    if !messages.is_empty() {
+        tokio::spawn(reply_whatsapp(state.clone(), Arc::clone(wa), messages));
+    }
+
+    // Acknowledge the webhook (also for status updates that carry no messages)
+    (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
+}
+
+/// Answer one Nextcloud Talk message, showing a reaction while the agent works.
+async fn reply_nextcloud_talk(
+    state: AppState,
+    nc: Arc<NextcloudTalkChannel>,
+    msg: crate::channels::traits::ChannelMessage,
+) {
+    if state.auto_save {
+        let key = nextcloud_talk_memory_key(&msg);
+        let _ = state
+            .mem
+            .store(&key, &msg.content, MemoryCategory::Conversation, None)
+            .await;
+    }
+
+    if let Err(e) = nc.start_typing(&msg.reply_target).await {
+        tracing::debug!("Nextcloud Talk typing reaction failed: {e}");
+    }
+    let reply = match traced_simple_chat(&state, &msg.content).await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::error!("LLM error for Nextcloud Talk message: {e:#}");
+            "Sorry, I couldn't process your message right now.".to_string()
+        }
+    };
+    if let Err(e) = nc.stop_typing(&msg.reply_target).await {
+        tracing::debug!("Nextcloud Talk typing reaction failed: {e}");
+    }
+    if let Err(e) = nc.send(&SendMessage::new(reply, &msg.reply_target)).await {
+        tracing::error!("Failed to send Nextcloud Talk reply: {e}");
+    }
+}
+
+/// POST /nextcloud-talk — Talk bot webhook
+async fn handle_nextcloud_talk_message(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let Some(ref nc) = state.nextcloud_talk else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Nextcloud Talk not configured"})),
+        );
+    };
 
-    if messages.is_empty() {
-        // Acknowledge the webhook even if no messages (could be status updates)
-        return (StatusCode::OK, Json(serde_json::json!({"status": "ok"})));
+    // ── Security: HMAC-SHA256 over X-Nextcloud-Talk-Random + body ──
+    let header_str = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+    };
+    let random = header_str("X-Nextcloud-Talk-Random");
+    let signature = header_str("X-Nextcloud-Talk-Signature");
+    if !nc.verify_signature(random, &body, signature) {
+        tracing::warn!(
+            "Nextcloud Talk webhook signature verification failed (signature: {})",
+            if signature.is_empty() {
+                "missing"
+            } else {
+                "invalid"
+            }
+        );
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Invalid signature"})),
+        );
     }
 
-    // Process each message
-    for msg in &messages {
+    let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid JSON payload"})),
+        );
+    };
+
+    // Talk delivers webhooks while the user's message is being posted, so
+    // acknowledge right away and answer in the background.
+    for msg in nc.parse_webhook_payload(&payload) {
         tracing::info!(
-            "WhatsApp message from {}: {}",
+            "Nextcloud Talk message from {}: {}",
             msg.sender,
             truncate_with_ellipsis(&msg.content, 50)
         );
+        tokio::spawn(reply_nextcloud_talk(state.clone(), Arc::clone(nc), msg));
+    }
 
-        // Auto-save to memory
-        if state.auto_save {
-            let key = whatsapp_memory_key(msg);
-            let _ = state
-                .mem
-                .store(&key, &msg.content, MemoryCategory::Conversation, None)
-                .await;
+    (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
+}
+
+/// GET /chat — WebChat UI (the socket authenticates, not the page)
+async fn handle_webchat_page() -> impl IntoResponse {
+    (
+        [(
+            header::CONTENT_SECURITY_POLICY,
+            "default-src 'self'; script-src 'unsafe-inline'; style-src 'unsafe-inline'; connect-src 'self'",
+        )],
+        Html(WEBCHAT_HTML),
+    )
+}
+
+/// Whether a browser page from the request's `Origin` may open the chat
+/// socket. Without pairing any token is accepted, so this is what stops other
+/// sites the operator visits from driving the agent. Clients that send no
+/// `Origin` are not browsers and are left to the `auth` frame.
+fn webchat_origin_allowed(headers: &HeaderMap, allowed_origins: &[String]) -> bool {
+    let Some(origin) = headers.get(header::ORIGIN) else {
+        return true;
+    };
+    let Ok(origin) = origin.to_str() else {
+        return false;
+    };
+    let origin = origin.trim_end_matches('/');
+    if allowed_origins
+        .iter()
+        .any(|allowed| allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+    {
+        return true;
+    }
+    let origin_host = origin.split_once("://").map_or(origin, |(_, host)| host);
+    headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .is_some_and(|host| host.eq_ignore_ascii_case(origin_host))
+}
+
+/// GET /chat/ws — WebChat socket; the first frame must be `auth`
+async fn handle_webchat_socket(
+    State(state): State<WebChatState>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Response {
+    if !webchat_origin_allowed(&headers, &state.allowed_origins) {
+        tracing::warn!("WebChat: rejected socket from foreign origin");
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    ws.max_message_size(state.max_message_size)
+        .on_upgrade(move |socket| run_webchat_session(state, socket))
+}
+
+async fn send_webchat_frame(socket: &mut WebSocket, frame: &ServerFrame) -> Result<()> {
+    let json = serde_json::to_string(frame)?;
+    socket.send(WsMessage::Text(json.into())).await?;
+    Ok(())
+}
+
+/// Wait for the `auth` frame and return the WebChat client ID for its token.
+async fn authenticate_webchat(state: &WebChatState, socket: &mut WebSocket) -> Option<String> {
+    let first = tokio::time::timeout(
+        Duration::from_secs(WEBCHAT_AUTH_TIMEOUT_SECS),
+        socket.recv(),
+    )
+    .await;
+    let token = match first {
+        Ok(Some(Ok(WsMessage::Text(text)))) => match serde_json::from_str(text.as_str()) {
+            Ok(ClientFrame::Auth { token }) => Some(token),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    match token {
+        Some(token) if state.pairing.is_authenticated(&token) => {
+            Some(webchat::client_id(&token, state.pairing.require_pairing()))
+        }
+        _ => {
+            tracing::warn!("WebChat: rejected unauthenticated socket");
+            let _ = send_webchat_frame(
+                socket,
+                &ServerFrame::Error {
+                    message: "unauthorized".into(),
+                },
+            )
+            .await;
+            None
         }
+    }
+}
 
-        // Call the LLM
-        match state
-            .provider
-            .simple_chat(&msg.content, &state.model, state.temperature)
-            .await
-        {
-            Ok(response) => {
-                // Send reply via WhatsApp
-                if let Err(e) = wa
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
-                {
-                    tracing::error!("Failed to send WhatsApp reply: {e}");
+async fn run_webchat_session(state: WebChatState, mut socket: WebSocket) {
+    let Some(client_id) = authenticate_webchat(&state, &mut socket).await else {
+        let _ = socket.send(WsMessage::Close(None)).await;
+        return;
+    };
+    let (connection, mut frames) = state.hub.connect(&client_id);
+
+    loop {
+        tokio::select! {
+            frame = frames.recv() => {
+                let Some(frame) = frame else { break };
+                if send_webchat_frame(&mut socket, &frame).await.is_err() {
+                    break;
                 }
             }
-            Err(e) => {
-                tracing::error!("LLM error for WhatsApp message: {e:#}");
-                let _ = wa
-                    .send(&SendMessage::new(
-                        "Sorry, I couldn't process your message right now.",
-                        &msg.reply_target,
-                    ))
-                    .await;
+            incoming = socket.recv() => match incoming {
+                Some(Ok(WsMessage::Text(text))) => {
+                    match serde_json::from_str::<ClientFrame>(text.as_str()) {
+                        Ok(frame) => state.hub.handle(connection, frame).await,
+                        Err(e) => {
+                            let frame = ServerFrame::Error {
+                                message: format!("invalid frame: {e}"),
+                            };
+                            if send_webchat_frame(&mut socket, &frame).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+                Some(Ok(WsMessage::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => {}
             }
         }
     }
 
-    // Acknowledge the webhook
-    (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
+    state.hub.disconnect(connection);
 }
 
 #[cfg(test)]
@@ -1018,6 +1861,40 @@ mod tests {
         assert_clone::<AppState>();
     }
 
+    #[test]
+    fn reloaded_state_keeps_pairing_and_unchanged_limits() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut config = Config {
+            workspace_dir: temp.path().to_path_buf(),
+            config_path: temp.path().join("config.toml"),
+            ..Config::default()
+        };
+        config.gateway.require_pairing = true;
+        let state = build_app_state(&config, None).unwrap();
+        let code = state.pairing.pairing_code().unwrap();
+        let token = state.pairing.try_pair(&code).unwrap().unwrap();
+
+        let mut next = config.clone();
+        next.default_model = Some("reloaded-model".into());
+        next.default_temperature = 0.1;
+        let reloaded = build_app_state(&next, Some(&state)).unwrap();
+        assert_eq!(reloaded.model, "reloaded-model");
+        assert!(reloaded.pairing.is_authenticated(&token));
+        assert!(Arc::ptr_eq(&reloaded.rate_limiter, &state.rate_limiter));
+        assert!(Arc::ptr_eq(&reloaded.config, &state.config));
+        assert_eq!(
+            reloaded.config.lock().gateway.paired_tokens,
+            state.pairing.tokens()
+        );
+
+        next.gateway.webhook_rate_limit_per_minute += 1;
+        next.gateway.require_pairing = false;
+        let relaxed = build_app_state(&next, Some(&reloaded)).unwrap();
+        assert!(!Arc::ptr_eq(&relaxed.rate_limiter, &reloaded.rate_limiter));
+        assert!(!relaxed.pairing.require_pairing());
+        assert!(relaxed.pairing.is_authenticated(&token));
+    }
+
     #[tokio::test]
     async fn metrics_endpoint_returns_hint_when_prometheus_is_disabled() {
         let state = AppState {
@@ -1034,7 +1911,11 @@ mod tests {
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
             whatsapp_app_secret: None,
+            nextcloud_talk: None,
             observer: Arc::new(crate::observability::NoopObserver),
+            transcriber: None,
+            synthesizer: None,
+            voice_reply_preferences: Arc::new(crate::tts::VoiceReplyPreferences::default()),
         };
 
         let response = handle_metrics(State(state)).await.into_response();
@@ -1075,7 +1956,11 @@ mod tests {
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
             whatsapp_app_secret: None,
+            nextcloud_talk: None,
             observer,
+            transcriber: None,
+            synthesizer: None,
+            voice_reply_preferences: Arc::new(crate::tts::VoiceReplyPreferences::default()),
         };
 
         let response = handle_metrics(State(state)).await.into_response();
@@ -1426,7 +2311,11 @@ mod tests {
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
             whatsapp_app_secret: None,
+            nextcloud_talk: None,
             observer: Arc::new(crate::observability::NoopObserver),
+            transcriber: None,
+            synthesizer: None,
+            voice_reply_preferences: Arc::new(crate::tts::VoiceReplyPreferences::default()),
         };
 
         let mut headers = HeaderMap::new();
@@ -1482,7 +2371,11 @@ mod tests {
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
             whatsapp_app_secret: None,
+            nextcloud_talk: None,
             observer: Arc::new(crate::observability::NoopObserver),
+            transcriber: None,
+            synthesizer: None,
+            voice_reply_preferences: Arc::new(crate::tts::VoiceReplyPreferences::default()),
         };
 
         let headers = HeaderMap::new();
@@ -1547,7 +2440,11 @@ mod tests {
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
             whatsapp_app_secret: None,
+            nextcloud_talk: None,
             observer: Arc::new(crate::observability::NoopObserver),
+            transcriber: None,
+            synthesizer: None,
+            voice_reply_preferences: Arc::new(crate::tts::VoiceReplyPreferences::default()),
         };
 
         let response = handle_webhook(
@@ -1585,7 +2482,11 @@ mod tests {
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
             whatsapp_app_secret: None,
+            nextcloud_talk: None,
             observer: Arc::new(crate::observability::NoopObserver),
+            transcriber: None,
+            synthesizer: None,
+            voice_reply_preferences: Arc::new(crate::tts::VoiceReplyPreferences::default()),
         };
 
         let mut headers = HeaderMap::new();
@@ -1626,7 +2527,11 @@ mod tests {
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
             whatsapp_app_secret: None,
+            nextcloud_talk: None,
             observer: Arc::new(crate::observability::NoopObserver),
+            transcriber: None,
+            synthesizer: None,
+            voice_reply_preferences: Arc::new(crate::tts::VoiceReplyPreferences::default()),
         };
 
         let mut headers = HeaderMap::new();
@@ -1837,4 +2742,373 @@ mod tests {
             &signature_header
         ));
     }
+
+    #[tokio::test]
+    async fn trace_middleware_continues_incoming_traceparent() {
+        use tower::Service;
+
+        let mut app = Router::new()
+            .route("/ping", get(|| async { "pong" }))
+            .layer(middleware::from_fn(trace_request));
+        let request = axum::http::Request::builder()
+            .uri("/ping")
+            .header(
+                trace::TRACEPARENT_HEADER,
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            )
+            .body(axum::body::Body::empty())
+            .unwrap();
+
+        let response = app.call(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(trace::TRACE_ID_HEADER).unwrap(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+        let traceparent = response
+            .headers()
+            .get(trace::TRACEPARENT_HEADER)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert!(traceparent.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"));
+    }
+
+    #[tokio::test]
+    async fn named_hook_maps_payload_and_checks_signature() {
+        #[derive(Default)]
+        struct RecordingProvider {
+            messages: Mutex<Vec<String>>,
+        }
+
+        #[async_trait]
+        impl Provider for RecordingProvider {
+            async fn chat_with_system(
+                &self,
+                _system_prompt: Option<&str>,
+                message: &str,
+                _model: &str,
+                _temperature: f64,
+            ) -> anyhow::Result<String> {
+                self.messages.lock().push(message.to_string());
+                Ok("triaged".into())
+            }
+        }
+
+        let mut config = Config::default();
+        config.channels_config.webhook = Some(crate::config::WebhookConfig {
+            port: 8080,
+            secret: None,
+            hooks: vec![
+                crate::config::InboundHookConfig {
+                    name: "alerts".into(),
+                    secret: None,
+                    hmac_secret: Some("hook-key".into()),
+                    signature_header: "X-Signature".into(),
+                    message: "{{ $.alerts[0].labels.alertname }} is {{ $.status }}".into(),
+                    sender: Some("{{ $.receiver }}".into()),
+                    reply_url: None,
+                    reply_channel: None,
+                    reply_recipient: None,
+                },
+                crate::config::InboundHookConfig {
+                    name: "notes".into(),
+                    secret: Some("notes-secret".into()),
+                    hmac_secret: None,
+                    signature_header: "X-Signature".into(),
+                    message: "{{ $.text }}".into(),
+                    sender: None,
+                    reply_url: None,
+                    reply_channel: None,
+                    reply_recipient: None,
+                },
+            ],
+        });
+        let provider_impl = Arc::new(RecordingProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+        let state = AppState {
+            config: Arc::new(Mutex::new(config)),
+            provider,
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(true, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            nextcloud_talk: None,
+            observer: Arc::new(crate::observability::NoopObserver),
+            transcriber: None,
+            synthesizer: None,
+            voice_reply_preferences: Arc::new(crate::tts::VoiceReplyPreferences::default()),
+        };
+        let body = Bytes::from_static(
+            br#"{"status":"firing","receiver":"oncall","alerts":[{"labels":{"alertname":"DiskFull"}}]}"#,
+        );
+        let call = |name: &str, signature: &str, body: Bytes| {
+            let mut headers = HeaderMap::new();
+            headers.insert("X-Signature", HeaderValue::from_str(signature).unwrap());
+            handle_hook(
+                State(state.clone()),
+                test_connect_info(),
+                Path(name.to_string()),
+                headers,
+                body,
+            )
+        };
+
+        let unknown = call("missing", "", body.clone()).await.into_response();
+        assert_eq!(unknown.status(), StatusCode::NOT_FOUND);
+
+        let unsigned = call("alerts", "sha256=00", body.clone())
+            .await
+            .into_response();
+        assert_eq!(unsigned.status(), StatusCode::UNAUTHORIZED);
+
+        let signature = hooks::sign_body("hook-key", &body);
+        let response = call("alerts", &signature, body).await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed["response"], "triaged");
+        assert_eq!(
+            provider_impl.messages.lock().as_slice(),
+            ["[alerts webhook from oncall] DiskFull is firing".to_string()]
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Webhook-Secret", HeaderValue::from_static("notes-secret"));
+        let ignored = handle_hook(
+            State(state.clone()),
+            test_connect_info(),
+            Path("notes".to_string()),
+            headers,
+            Bytes::from_static(br#"{"other":"field"}"#),
+        )
+        .await
+        .into_response();
+        assert_eq!(ignored.status(), StatusCode::OK);
+        let payload = ignored.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed["status"], "ignored");
+        assert_eq!(provider_impl.messages.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn nextcloud_talk_webhook_rejects_bad_signature_and_accepts_signed() {
+        use hmac::{Hmac, Mac};
+
+        let nc_config = crate::config::NextcloudTalkConfig {
+            base_url: "http://127.0.0.1:9".into(),
+            bot_secret: "talk-secret".into(),
+            allowed_rooms: vec!["*".into()],
+            mention_only: false,
+            bot_name: "ZeroClaw".into(),
+        };
+        let state = AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            nextcloud_talk: Some(Arc::new(NextcloudTalkChannel::from_config(&nc_config))),
+            observer: Arc::new(crate::observability::NoopObserver),
+            transcriber: None,
+            synthesizer: None,
+            voice_reply_preferences: Arc::new(crate::tts::VoiceReplyPreferences::default()),
+        };
+        let body = Bytes::from_static(br#"{"type":"Join"}"#);
+        let random = "r".repeat(64);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "X-Nextcloud-Talk-Random",
+            HeaderValue::from_str(&random).unwrap(),
+        );
+        headers.insert("X-Nextcloud-Talk-Signature", HeaderValue::from_static("00"));
+        let rejected =
+            handle_nextcloud_talk_message(State(state.clone()), headers.clone(), body.clone())
+                .await
+                .into_response();
+        assert_eq!(rejected.status(), StatusCode::UNAUTHORIZED);
+
+        let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"talk-secret").unwrap();
+        mac.update(random.as_bytes());
+        mac.update(&body);
+        let signature = hex::encode(mac.finalize().into_bytes());
+        headers.insert(
+            "X-Nextcloud-Talk-Signature",
+            HeaderValue::from_str(&signature).unwrap(),
+        );
+        let accepted = handle_nextcloud_talk_message(State(state), headers, body)
+            .await
+            .into_response();
+        assert_eq!(accepted.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn whatsapp_webhook_acknowledges_before_answering() {
+        struct BlockedProvider {
+            started: Arc<tokio::sync::Notify>,
+        }
+
+        #[async_trait]
+        impl Provider for BlockedProvider {
+            async fn chat_with_system(
+                &self,
+                _system_prompt: Option<&str>,
+                _message: &str,
+                _model: &str,
+                _temperature: f64,
+            ) -> anyhow::Result<String> {
+                self.started.notify_one();
+                std::future::pending().await
+            }
+        }
+
+        let started = Arc::new(tokio::sync::Notify::new());
+        let state = AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider: Arc::new(BlockedProvider {
+                started: Arc::clone(&started),
+            }),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: Some(Arc::new(WhatsAppChannel::new(
+                "test-token".into(),
+                "123456789".into(),
+                "verify-me".into(),
+                vec!["*".into()],
+            ))),
+            whatsapp_app_secret: None,
+            nextcloud_talk: None,
+            observer: Arc::new(crate::observability::NoopObserver),
+            transcriber: None,
+            synthesizer: None,
+            voice_reply_preferences: Arc::new(crate::tts::VoiceReplyPreferences::default()),
+        };
+        let body = Bytes::from_static(
+            br#"{"entry":[{"changes":[{"value":{"messages":[{"from":"1234567890","timestamp":"1699999999","type":"text","text":{"body":"hi"}}]}}]}]}"#,
+        );
+
+        let response = tokio::time::timeout(
+            Duration::from_secs(5),
+            handle_whatsapp_message(State(state), HeaderMap::new(), body),
+        )
+        .await
+        .expect("webhook must not wait for the agent")
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        tokio::time::timeout(Duration::from_secs(5), started.notified())
+            .await
+            .expect("message is answered in the background");
+    }
+
+    #[tokio::test]
+    async fn webchat_socket_rejects_foreign_origin() {
+        use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+        use tokio_tungstenite::tungstenite::Error as WsError;
+
+        let app = webchat_router(WebChatState {
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            hub: Arc::new(WebChatHub::new()),
+            max_message_size: 1024,
+            allowed_origins: vec!["https://chat.example.com".into()],
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        let connect = |origin: &str| {
+            let mut request = format!("ws://{addr}/chat/ws")
+                .into_client_request()
+                .unwrap();
+            request
+                .headers_mut()
+                .insert(header::ORIGIN, HeaderValue::from_str(origin).unwrap());
+            tokio_tungstenite::connect_async(request)
+        };
+
+        match connect("https://evil.example").await {
+            Err(WsError::Http(response)) => assert_eq!(response.status(), StatusCode::FORBIDDEN),
+            other => panic!("foreign origin was not rejected: {:?}", other.map(|_| ())),
+        }
+        assert!(connect(&format!("http://{addr}")).await.is_ok());
+        assert!(connect("https://chat.example.com").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn webchat_socket_requires_paired_token() {
+        use futures_util::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message as ClientMessage;
+
+        let hub = Arc::new(WebChatHub::new());
+        let app = webchat_router(WebChatState {
+            pairing: Arc::new(PairingGuard::new(true, &["zc_valid".into()])),
+            hub: Arc::clone(&hub),
+            max_message_size: 1024,
+            allowed_origins: Vec::new(),
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        let url = format!("ws://{addr}/chat/ws");
+
+        let (mut rejected, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        rejected
+            .send(ClientMessage::Text(
+                r#"{"type":"auth","token":"zc_wrong"}"#.into(),
+            ))
+            .await
+            .unwrap();
+        let reply = rejected.next().await.unwrap().unwrap();
+        assert_eq!(
+            reply.into_text().unwrap(),
+            r#"{"type":"error","message":"unauthorized"}"#
+        );
+
+        let (mut accepted, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        accepted
+            .send(ClientMessage::Text(
+                r#"{"type":"auth","token":"zc_valid"}"#.into(),
+            ))
+            .await
+            .unwrap();
+        let reply = accepted.next().await.unwrap().unwrap();
+        let ready: serde_json::Value = serde_json::from_str(&reply.into_text().unwrap()).unwrap();
+        let client = webchat::client_id("zc_valid", true);
+        assert_eq!(
+            ready,
+            serde_json::json!({"type": "ready", "client": client})
+        );
+        assert_eq!(
+            hub.send_to(&client, &ServerFrame::Typing { active: true }),
+            1
+        );
+        let typing = accepted.next().await.unwrap().unwrap();
+        assert_eq!(
+            typing.into_text().unwrap(),
+            r#"{"type":"typing","active":true}"#
+        );
+    }
 }
@@ -107,7 +107,117 @@ pub fn handle_command(cmd: crate::HardwareCommands, _config: &Config) -> Result<
         crate::HardwareCommands::Discover => run_discover(),
         crate::HardwareCommands::Introspect { path } => run_introspect(&path),
         crate::HardwareCommands::Info { chip } => run_info(&chip),
+        crate::HardwareCommands::Rag { .. } => {
+            anyhow::bail!("Rag must be handled in main.rs (requires async runtime)")
+        }
+    }
+}
+
+/// Handle `zeroclaw hardware rag` subcommands.
+pub async fn handle_rag_command(cmd: crate::HardwareRagCommands, config: &Config) -> Result<()> {
+    match cmd {
+        crate::HardwareRagCommands::Query {
+            query,
+            boards,
+            limit,
+            keyword_only,
+        } => run_rag_query(config, &query, boards, limit, keyword_only).await,
+    }
+}
+
+async fn run_rag_query(
+    config: &Config,
+    query: &str,
+    boards: Vec<String>,
+    limit: usize,
+    keyword_only: bool,
+) -> Result<()> {
+    use std::fmt::Write;
+
+    let Some(dir) = config
+        .peripherals
+        .datasheet_dir
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+    else {
+        anyhow::bail!("peripherals.datasheet_dir is not set");
+    };
+    let rag = if keyword_only {
+        crate::rag::HardwareRag::load(&config.workspace_dir, dir)?
+    } else {
+        let embedding = crate::rag::RagEmbedding::from_parts(
+            &config.memory.embedding_provider,
+            config.api_key.as_deref(),
+            &config.memory.embedding_model,
+            config.memory.embedding_dimensions,
+            config.memory.vector_weight,
+            config.memory.keyword_weight,
+        );
+        crate::rag::HardwareRag::load_with_embeddings(&config.workspace_dir, dir, embedding).await?
+    };
+    if rag.is_empty() {
+        println!(
+            "No datasheets indexed in {}",
+            config.workspace_dir.join(dir).display()
+        );
+        return Ok(());
+    }
+
+    let boards = if boards.is_empty() {
+        config
+            .peripherals
+            .boards
+            .iter()
+            .map(|b| b.board.clone())
+            .collect()
+    } else {
+        boards
+    };
+    println!(
+        "Index: {} chunks ({} table rows) from {dir}",
+        rag.len(),
+        rag.table_row_count()
+    );
+    match rag.embedding_model() {
+        Some(model) => println!("Retrieval: hybrid (embedding model {model})"),
+        None => println!("Retrieval: keyword"),
+    }
+    if !boards.is_empty() {
+        println!("Boards: {}", boards.join(", "));
+    }
+
+    let aliases = rag.pin_alias_context(query, &boards);
+    if !aliases.is_empty() {
+        println!();
+        print!("{aliases}");
+    }
+
+    let hits = rag.search(query, &boards, limit).await;
+    if hits.is_empty() {
+        println!();
+        println!("No matching chunks.");
     }
+    for (i, hit) in hits.iter().enumerate() {
+        let mut scores = format!("score {:.3}", hit.score);
+        if let Some(vector) = hit.vector_score {
+            let _ = write!(scores, ", vector {vector:.3}");
+        }
+        if let Some(keyword) = hit.keyword_score {
+            let _ = write!(scores, ", keyword {keyword:.3}");
+        }
+        println!();
+        println!(
+            "{}. {} ({}) — {scores}",
+            i + 1,
+            hit.chunk.source,
+            hit.chunk.board.as_deref().unwrap_or("generic")
+        );
+        for line in crate::util::truncate_with_ellipsis(&hit.chunk.content, 600).lines() {
+            println!("   {line}");
+        }
+    }
+    Ok(())
 }
 
 #[cfg(feature = "hardware")]
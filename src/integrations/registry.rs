@@ -121,13 +121,25 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
             name: "WebChat",
             description: "Browser-based chat UI",
             category: IntegrationCategory::Chat,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |c| {
+                if c.channels_config.webchat.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
         },
         IntegrationEntry {
             name: "Nextcloud Talk",
             description: "Self-hosted Nextcloud chat",
             category: IntegrationCategory::Chat,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |c| {
+                if c.channels_config.nextcloud_talk.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
         },
         IntegrationEntry {
             name: "Zalo",
@@ -513,7 +525,13 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
             name: "Obsidian",
             description: "Knowledge graph notes",
             category: IntegrationCategory::Productivity,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |c| {
+                if c.memory.obsidian.vault_path.trim().is_empty() {
+                    IntegrationStatus::Available
+                } else {
+                    IntegrationStatus::Active
+                }
+            },
         },
         IntegrationEntry {
             name: "Things 3",
@@ -563,7 +581,13 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
             name: "Home Assistant",
             description: "Home automation hub",
             category: IntegrationCategory::SmartHome,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |c| {
+                if c.home_assistant.enabled {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
         },
         IntegrationEntry {
             name: "Philips Hue",
@@ -840,6 +864,12 @@ mod tests {
             device_id: None,
             room_id: "!r:m".into(),
             allowed_users: vec![],
+            thread_replies: false,
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            ack_reaction: "👀".into(),
+            max_media_bytes: 20 * 1024 * 1024,
+            approval_timeout_secs: 120,
         });
         let entries = all_integrations();
         let mx = entries.iter().find(|e| e.name == "Matrix").unwrap();
@@ -861,7 +891,7 @@ mod tests {
     fn coming_soon_integrations_stay_coming_soon() {
         let config = Config::default();
         let entries = all_integrations();
-        for name in ["Nostr", "Spotify", "Home Assistant"] {
+        for name in ["Nostr", "Spotify"] {
             let entry = entries.iter().find(|e| e.name == name).unwrap();
             assert!(
                 matches!((entry.status_fn)(&config), IntegrationStatus::ComingSoon),
@@ -870,6 +900,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn home_assistant_active_when_enabled() {
+        let mut config = Config::default();
+        let entries = all_integrations();
+        let ha = entries.iter().find(|e| e.name == "Home Assistant").unwrap();
+        assert!(matches!(
+            (ha.status_fn)(&config),
+            IntegrationStatus::Available
+        ));
+        config.home_assistant.enabled = true;
+        assert!(matches!((ha.status_fn)(&config), IntegrationStatus::Active));
+    }
+
+    #[test]
+    fn webchat_active_when_configured() {
+        let mut config = Config::default();
+        let entries = all_integrations();
+        let webchat = entries.iter().find(|e| e.name == "WebChat").unwrap();
+        assert!(matches!(
+            (webchat.status_fn)(&config),
+            IntegrationStatus::Available
+        ));
+        config.channels_config.webchat = Some(crate::config::WebChatConfig::default());
+        assert!(matches!(
+            (webchat.status_fn)(&config),
+            IntegrationStatus::Active
+        ));
+    }
+
+    #[test]
+    fn nextcloud_talk_active_when_configured() {
+        let mut config = Config::default();
+        let entries = all_integrations();
+        let talk = entries.iter().find(|e| e.name == "Nextcloud Talk").unwrap();
+        assert!(matches!(
+            (talk.status_fn)(&config),
+            IntegrationStatus::Available
+        ));
+        config.channels_config.nextcloud_talk = Some(crate::config::NextcloudTalkConfig {
+            base_url: "https://cloud.example.com".into(),
+            bot_secret: "secret".into(),
+            allowed_rooms: vec!["*".into()],
+            mention_only: false,
+            bot_name: "ZeroClaw".into(),
+        });
+        assert!(matches!(
+            (talk.status_fn)(&config),
+            IntegrationStatus::Active
+        ));
+    }
+
+    #[test]
+    fn obsidian_active_when_vault_configured() {
+        let mut config = Config::default();
+        let entries = all_integrations();
+        let obsidian = entries.iter().find(|e| e.name == "Obsidian").unwrap();
+        assert!(matches!(
+            (obsidian.status_fn)(&config),
+            IntegrationStatus::Available
+        ));
+        config.memory.obsidian.vault_path = "~/Vault".into();
+        assert!(matches!(
+            (obsidian.status_fn)(&config),
+            IntegrationStatus::Active
+        ));
+    }
+
     #[test]
     fn whatsapp_available_when_not_configured() {
         let config = Config::default();
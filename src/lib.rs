@@ -55,6 +55,7 @@ pub mod identity;
 pub mod integrations;
 pub mod memory;
 pub mod migration;
+pub mod mqtt;
 pub mod observability;
 pub mod onboard;
 pub mod peripherals;
@@ -63,9 +64,13 @@ pub mod rag;
 pub mod runtime;
 pub mod security;
 pub mod service;
+pub mod sessions;
 pub mod skills;
 pub mod tools;
+pub mod transcription;
+pub mod tts;
 pub mod tunnel;
+pub mod users;
 pub mod util;
 
 pub use config::Config;
@@ -198,6 +203,107 @@ pub enum CronCommands {
     },
 }
 
+/// Agent session subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SessionCommands {
+    /// List saved agent sessions, most recent first
+    List {
+        /// Maximum number of sessions to show
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Print a saved session as Markdown
+    Show {
+        /// Session ID (or unique prefix)
+        id: String,
+    },
+    /// Export a saved session to Markdown or JSONL
+    Export {
+        /// Session ID (or unique prefix)
+        id: String,
+        /// Output format (markdown, jsonl)
+        #[arg(long, default_value = "markdown")]
+        format: String,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Delete a saved session
+    Delete {
+        /// Session ID (or unique prefix)
+        id: String,
+    },
+}
+
+/// User directory subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum UserCommands {
+    /// List users and their linked channel identities
+    List,
+    /// Show a user's profile
+    Show {
+        /// User ID
+        id: String,
+    },
+    /// Create a user profile
+    Create {
+        /// User ID (lowercase letters, digits, '-' and '_')
+        id: String,
+        /// Display name
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Delete a user profile
+    Delete {
+        /// User ID
+        id: String,
+    },
+    /// Link a channel identity to a user without a verification code
+    Link {
+        /// User ID
+        id: String,
+        /// Channel name (telegram, slack, email, signal, ...)
+        channel: String,
+        /// Sender as reported by the channel (user ID, address, number)
+        sender: String,
+    },
+    /// Unlink a channel identity from its user
+    Unlink {
+        /// Channel name
+        channel: String,
+        /// Sender as reported by the channel
+        sender: String,
+    },
+    /// Issue a one-time code the user sends as `/link <code>` to link an account
+    LinkCode {
+        /// User ID
+        id: String,
+    },
+    /// Update a user's display name, autonomy, model, memory scope or tool allowlist
+    Set {
+        /// User ID
+        id: String,
+        /// Display name
+        #[arg(long)]
+        name: Option<String>,
+        /// Autonomy level (read_only, supervised, full, inherit)
+        #[arg(long)]
+        autonomy: Option<String>,
+        /// Preferred provider (or inherit)
+        #[arg(long)]
+        provider: Option<String>,
+        /// Preferred model (or inherit)
+        #[arg(long)]
+        model: Option<String>,
+        /// Memory scope (shared, private)
+        #[arg(long)]
+        memory_scope: Option<String>,
+        /// Comma-separated tools the agent may use for this user (or all)
+        #[arg(long)]
+        tools: Option<String>,
+    },
+}
+
 /// Integration subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum IntegrationCommands {
@@ -224,6 +330,30 @@ pub enum HardwareCommands {
         #[arg(long, default_value = "STM32F401RETx")]
         chip: String,
     },
+    /// Inspect the datasheet index (peripherals.datasheet_dir)
+    Rag {
+        #[command(subcommand)]
+        rag_command: HardwareRagCommands,
+    },
+}
+
+/// Datasheet RAG subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HardwareRagCommands {
+    /// Show what the agent would retrieve for a question, with scores
+    Query {
+        /// Question or search text (e.g. "GPIOA mode register offset")
+        query: String,
+        /// Board to prefer (repeatable); defaults to the configured boards
+        #[arg(long = "board")]
+        boards: Vec<String>,
+        /// Number of chunks to show
+        #[arg(long, default_value_t = 5)]
+        limit: usize,
+        /// Skip embeddings and use keyword retrieval only
+        #[arg(long)]
+        keyword_only: bool,
+    },
 }
 
 /// Peripheral (hardware) management subcommands
@@ -252,4 +382,6 @@ pub enum PeripheralCommands {
     },
     /// Flash ZeroClaw firmware to Nucleo-F401RE (builds + probe-rs run)
     FlashNucleo,
+    /// Serve a simulated board on a PTY (no hardware needed)
+    Simulate,
 }
@@ -56,9 +56,10 @@ mod health;
 mod heartbeat;
 mod identity;
 mod integrations;
+mod mcp;
 mod memory;
 mod migration;
-mod mcp;
+mod mqtt;
 mod observability;
 mod onboard;
 mod peripherals;
@@ -66,16 +67,20 @@ mod providers;
 mod runtime;
 mod security;
 mod service;
+mod sessions;
 mod skillforge;
 mod skills;
 mod tools;
+mod transcription;
+mod tts;
 mod tunnel;
+mod users;
 mod util;
 
 use config::Config;
 
 // Re-export so binary's hardware/peripherals modules can use crate::HardwareCommands etc.
-pub use zeroclaw::{HardwareCommands, PeripheralCommands};
+pub use zeroclaw::{HardwareCommands, HardwareRagCommands, PeripheralCommands};
 
 /// `ZeroClaw` - Zero overhead. Zero compromise. 100% Rust.
 #[derive(Parser, Debug)]
@@ -148,6 +153,14 @@ enum Commands {
         /// Attach a peripheral (board:path, e.g. nucleo-f401re:/dev/ttyACM0)
         #[arg(long)]
         peripheral: Vec<String>,
+
+        /// Resume a saved session by ID (or unique prefix)
+        #[arg(long, conflicts_with = "continue_last")]
+        resume: Option<String>,
+
+        /// Resume the most recently updated session
+        #[arg(long = "continue")]
+        continue_last: bool,
     },
 
     /// Start the gateway server (webhooks, websockets)
@@ -193,6 +206,18 @@ enum Commands {
         cron_command: CronCommands,
     },
 
+    /// List, show, export and delete saved agent sessions
+    Sessions {
+        #[command(subcommand)]
+        session_command: SessionCommands,
+    },
+
+    /// Link channel identities to users and manage per-user settings
+    Users {
+        #[command(subcommand)]
+        user_command: UserCommands,
+    },
+
     /// Manage provider model catalogs
     Models {
         #[command(subcommand)]
@@ -400,6 +425,105 @@ enum CronCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum SessionCommands {
+    /// List saved agent sessions, most recent first
+    List {
+        /// Maximum number of sessions to show
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Print a saved session as Markdown
+    Show {
+        /// Session ID (or unique prefix)
+        id: String,
+    },
+    /// Export a saved session to Markdown or JSONL
+    Export {
+        /// Session ID (or unique prefix)
+        id: String,
+        /// Output format (markdown, jsonl)
+        #[arg(long, default_value = "markdown")]
+        format: String,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Delete a saved session
+    Delete {
+        /// Session ID (or unique prefix)
+        id: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum UserCommands {
+    /// List users and their linked channel identities
+    List,
+    /// Show a user's profile
+    Show {
+        /// User ID
+        id: String,
+    },
+    /// Create a user profile
+    Create {
+        /// User ID (lowercase letters, digits, '-' and '_')
+        id: String,
+        /// Display name
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Delete a user profile
+    Delete {
+        /// User ID
+        id: String,
+    },
+    /// Link a channel identity to a user without a verification code
+    Link {
+        /// User ID
+        id: String,
+        /// Channel name (telegram, slack, email, signal, ...)
+        channel: String,
+        /// Sender as reported by the channel (user ID, address, number)
+        sender: String,
+    },
+    /// Unlink a channel identity from its user
+    Unlink {
+        /// Channel name
+        channel: String,
+        /// Sender as reported by the channel
+        sender: String,
+    },
+    /// Issue a one-time code the user sends as `/link <code>` to link an account
+    LinkCode {
+        /// User ID
+        id: String,
+    },
+    /// Update a user's display name, autonomy, model, memory scope or tool allowlist
+    Set {
+        /// User ID
+        id: String,
+        /// Display name
+        #[arg(long)]
+        name: Option<String>,
+        /// Autonomy level (read_only, supervised, full, inherit)
+        #[arg(long)]
+        autonomy: Option<String>,
+        /// Preferred provider (or inherit)
+        #[arg(long)]
+        provider: Option<String>,
+        /// Preferred model (or inherit)
+        #[arg(long)]
+        model: Option<String>,
+        /// Memory scope (shared, private)
+        #[arg(long)]
+        memory_scope: Option<String>,
+        /// Comma-separated tools the agent may use for this user (or all)
+        #[arg(long)]
+        tools: Option<String>,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum ModelCommands {
     /// Refresh and cache provider models
@@ -538,7 +662,7 @@ async fn main() -> Result<()> {
         .await??;
         // Auto-start channels if user said yes during wizard
         if std::env::var("ZEROCLAW_AUTOSTART_CHANNELS").as_deref() == Ok("1") {
-            channels::start_channels(config).await?;
+            Box::pin(channels::start_channels(config)).await?;
         }
         return Ok(());
     }
@@ -556,9 +680,26 @@ async fn main() -> Result<()> {
             model,
             temperature,
             peripheral,
-        } => agent::run(config, message, provider, model, temperature, peripheral)
+            resume,
+            continue_last,
+        } => {
+            let resume = match resume {
+                Some(id) => Some(sessions::Resume::Id(id)),
+                None if continue_last => Some(sessions::Resume::Latest),
+                None => None,
+            };
+            agent::run(
+                config,
+                message,
+                provider,
+                model,
+                temperature,
+                peripheral,
+                resume,
+            )
             .await
-            .map(|_| ()),
+            .map(|_| ())
+        }
 
         Commands::Gateway { port, host } => {
             let port = port.unwrap_or(config.gateway.port);
@@ -568,7 +709,7 @@ async fn main() -> Result<()> {
             } else {
                 info!("🚀 Starting ZeroClaw Gateway on {host}:{port}");
             }
-            gateway::run_gateway(&host, port, config).await
+            Box::pin(gateway::run_gateway(&host, port, config)).await
         }
 
         Commands::Daemon { port, host } => {
@@ -668,6 +809,12 @@ async fn main() -> Result<()> {
 
         Commands::Cron { cron_command } => cron::handle_command(cron_command, &config),
 
+        Commands::Sessions { session_command } => {
+            sessions::handle_command(session_command, &config)
+        }
+
+        Commands::Users { user_command } => users::handle_command(user_command, &config),
+
         Commands::Models { model_command } => match model_command {
             ModelCommands::Refresh { provider, force } => {
                 let config_for_refresh = config.clone();
@@ -730,7 +877,7 @@ async fn main() -> Result<()> {
         },
 
         Commands::Channel { channel_command } => match channel_command {
-            ChannelCommands::Start => channels::start_channels(config).await,
+            ChannelCommands::Start => Box::pin(channels::start_channels(config)).await,
             ChannelCommands::Doctor => channels::doctor_channels(config).await,
             other => channels::handle_command(other, &config),
         },
@@ -749,13 +896,17 @@ async fn main() -> Result<()> {
 
         Commands::Auth { auth_command } => handle_auth_command(auth_command, &config).await,
 
-        Commands::Hardware { hardware_command } => {
-            hardware::handle_command(hardware_command.clone(), &config)
-        }
+        Commands::Hardware { hardware_command } => match hardware_command {
+            HardwareCommands::Rag { rag_command } => {
+                hardware::handle_rag_command(rag_command, &config).await
+            }
+            other => hardware::handle_command(other, &config),
+        },
 
-        Commands::Peripheral { peripheral_command } => {
-            peripherals::handle_command(peripheral_command.clone(), &config)
-        }
+        Commands::Peripheral { peripheral_command } => match peripheral_command {
+            PeripheralCommands::Simulate => peripherals::simulate(&config).await,
+            other => peripherals::handle_command(other, &config),
+        },
 
         Commands::MCP { mcp_command } => mcp::handle_command(mcp_command, &mut config),
     }
@@ -5,6 +5,7 @@ pub mod hygiene;
 pub mod lucid;
 pub mod markdown;
 pub mod none;
+pub mod obsidian;
 pub mod postgres;
 pub mod response_cache;
 pub mod snapshot;
@@ -20,6 +21,7 @@ pub use backend::{
 pub use lucid::LucidMemory;
 pub use markdown::MarkdownMemory;
 pub use none::NoneMemory;
+pub use obsidian::ObsidianMemory;
 pub use postgres::PostgresMemory;
 pub use response_cache::ResponseCache;
 pub use sqlite::SqliteMemory;
@@ -32,16 +34,18 @@ use anyhow::Context;
 use std::path::Path;
 use std::sync::Arc;
 
-fn create_memory_with_builders<F, G>(
+fn create_memory_with_builders<F, G, H>(
     backend_name: &str,
     workspace_dir: &Path,
     mut sqlite_builder: F,
     mut postgres_builder: G,
+    mut obsidian_builder: H,
     unknown_context: &str,
 ) -> anyhow::Result<Box<dyn Memory>>
 where
     F: FnMut() -> anyhow::Result<SqliteMemory>,
     G: FnMut() -> anyhow::Result<PostgresMemory>,
+    H: FnMut(SqliteMemory) -> anyhow::Result<ObsidianMemory>,
 {
     match classify_memory_backend(backend_name) {
         MemoryBackendKind::Sqlite => Ok(Box::new(sqlite_builder()?)),
@@ -51,6 +55,7 @@ where
         }
         MemoryBackendKind::Postgres => Ok(Box::new(postgres_builder()?)),
         MemoryBackendKind::Markdown => Ok(Box::new(MarkdownMemory::new(workspace_dir))),
+        MemoryBackendKind::Obsidian => Ok(Box::new(obsidian_builder(sqlite_builder()?)?)),
         MemoryBackendKind::None => Ok(Box::new(NoneMemory::new())),
         MemoryBackendKind::Unknown => {
             tracing::warn!(
@@ -104,7 +109,7 @@ pub fn create_memory_with_storage(
         && config.snapshot_on_hygiene
         && matches!(
             backend_kind,
-            MemoryBackendKind::Sqlite | MemoryBackendKind::Lucid
+            MemoryBackendKind::Sqlite | MemoryBackendKind::Lucid | MemoryBackendKind::Obsidian
         )
     {
         if let Err(e) = snapshot::export_snapshot(workspace_dir) {
@@ -117,7 +122,7 @@ pub fn create_memory_with_storage(
     if config.auto_hydrate
         && matches!(
             backend_kind,
-            MemoryBackendKind::Sqlite | MemoryBackendKind::Lucid
+            MemoryBackendKind::Sqlite | MemoryBackendKind::Lucid | MemoryBackendKind::Obsidian
         )
         && snapshot::should_hydrate(workspace_dir)
     {
@@ -186,6 +191,14 @@ pub fn create_memory_with_storage(
         workspace_dir,
         || build_sqlite_memory(config, workspace_dir, api_key),
         || build_postgres_memory(storage_provider),
+        |index| {
+            ObsidianMemory::new(
+                workspace_dir,
+                &config.obsidian,
+                config.chunk_max_tokens,
+                index,
+            )
+        },
         "",
     )
 }
@@ -209,11 +222,21 @@ pub fn create_memory_for_migration(
         );
     }
 
+    if matches!(
+        classify_memory_backend(backend),
+        MemoryBackendKind::Obsidian
+    ) {
+        anyhow::bail!(
+            "memory migration for backend 'obsidian' is unsupported; the vault is indexed from its notes"
+        );
+    }
+
     create_memory_with_builders(
         backend,
         workspace_dir,
         || SqliteMemory::new(workspace_dir),
         || anyhow::bail!("postgres backend is not available in migration context"),
+        |_| anyhow::bail!("obsidian backend is not available in migration context"),
         " during migration",
     )
 }
@@ -283,6 +306,24 @@ mod tests {
         assert_eq!(mem.name(), "lucid");
     }
 
+    #[test]
+    fn factory_obsidian_indexes_configured_vault() {
+        let tmp = TempDir::new().unwrap();
+        let vault = TempDir::new().unwrap();
+        let mut cfg = MemoryConfig {
+            backend: "obsidian".into(),
+            ..MemoryConfig::default()
+        };
+        let error = create_memory(&cfg, tmp.path(), None)
+            .err()
+            .expect("obsidian without vault_path should be rejected");
+        assert!(error.to_string().contains("vault_path"));
+
+        cfg.obsidian.vault_path = vault.path().display().to_string();
+        let mem = create_memory(&cfg, tmp.path(), None).unwrap();
+        assert_eq!(mem.name(), "obsidian");
+    }
+
     #[test]
     fn factory_none_uses_noop_memory() {
         let tmp = TempDir::new().unwrap();
@@ -0,0 +1,802 @@
+use super::chunker;
+use super::sqlite::SqliteMemory;
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use crate::config::ObsidianConfig;
+use async_trait::async_trait;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+use std::path::{Component, Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use tokio::sync::Mutex;
+
+/// Category assigned to indexed vault notes.
+pub const NOTE_CATEGORY: &str = "obsidian";
+/// Key prefix of indexed vault notes: `obsidian:<vault-relative path>#<chunk>`.
+pub const NOTE_KEY_PREFIX: &str = "obsidian:";
+
+const INDEX_STATE_FILE: &str = "obsidian_index.json";
+
+static WIKILINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[\[([^\]\|#\^]+)(?:[#\^][^\]\|]*)?(?:\|[^\]]*)?\]\]").unwrap());
+static INLINE_TAG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|\s)#([\p{L}\p{N}_/-]*[\p{L}_/-][\p{L}\p{N}_/-]*)").unwrap());
+
+/// A parsed Obsidian note.
+#[derive(Debug, Clone, Default)]
+pub struct Note {
+    /// Vault-relative path, always `/`-separated
+    pub path: String,
+    /// Frontmatter `title`, or the file name without `.md`
+    pub title: String,
+    /// Frontmatter fields; scalar values are stored as one-element lists
+    pub frontmatter: BTreeMap<String, Vec<String>>,
+    pub aliases: Vec<String>,
+    /// Frontmatter and inline `#tags`, without the leading `#`
+    pub tags: Vec<String>,
+    /// `[[wikilink]]` targets, without aliases or heading anchors
+    pub links: Vec<String>,
+    /// Note text after the frontmatter block
+    pub body: String,
+}
+
+/// Parse frontmatter, tags and wikilinks from a note's raw Markdown.
+pub fn parse_note(path: &str, raw: &str) -> Note {
+    let (frontmatter, body) = split_frontmatter(raw);
+    let frontmatter = frontmatter.map(parse_frontmatter).unwrap_or_default();
+
+    let title = frontmatter
+        .get("title")
+        .and_then(|v| v.first())
+        .cloned()
+        .unwrap_or_else(|| note_stem(path).to_string());
+    let aliases = ["aliases", "alias"]
+        .iter()
+        .filter_map(|key| frontmatter.get(*key))
+        .flatten()
+        .cloned()
+        .collect();
+
+    let mut tags = Vec::new();
+    let frontmatter_tags = ["tags", "tag"]
+        .iter()
+        .filter_map(|key| frontmatter.get(*key))
+        .flatten()
+        .flat_map(|v| v.split([',', ' ']))
+        .map(|t| t.trim().trim_start_matches('#').to_string());
+    for tag in frontmatter_tags.chain(inline_tags(body)) {
+        push_unique(&mut tags, tag);
+    }
+
+    let mut links = Vec::new();
+    for link in wikilinks(body) {
+        push_unique(&mut links, link);
+    }
+
+    Note {
+        path: path.to_string(),
+        title,
+        frontmatter,
+        aliases,
+        tags,
+        links,
+        body: body.to_string(),
+    }
+}
+
+/// `[[wikilink]]` targets in `text`, in order of appearance.
+pub fn wikilinks(text: &str) -> Vec<String> {
+    WIKILINK_RE
+        .captures_iter(text)
+        .map(|c| c[1].trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn inline_tags(body: &str) -> Vec<String> {
+    let mut in_code = false;
+    let mut tags = Vec::new();
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if !in_code {
+            tags.extend(INLINE_TAG_RE.captures_iter(line).map(|c| c[1].to_string()));
+        }
+    }
+    tags
+}
+
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !value.is_empty() && !values.iter().any(|v| v.eq_ignore_ascii_case(&value)) {
+        values.push(value);
+    }
+}
+
+fn split_frontmatter(raw: &str) -> (Option<&str>, &str) {
+    let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    let Some(rest) = raw
+        .strip_prefix("---\n")
+        .or_else(|| raw.strip_prefix("---\r\n"))
+    else {
+        return (None, raw);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, raw)
+}
+
+/// Minimal YAML frontmatter reader: `key: value`, `key: [a, b]` and
+/// `key:` followed by `- item` lines. Nested maps are ignored.
+fn parse_frontmatter(yaml: &str) -> BTreeMap<String, Vec<String>> {
+    let mut fields: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut current: Option<String> = None;
+
+    for line in yaml.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        if let Some(item) = line.trim_start().strip_prefix("- ") {
+            if let Some(key) = &current {
+                fields
+                    .entry(key.clone())
+                    .or_default()
+                    .push(unquote(item).to_string());
+            }
+            continue;
+        }
+        if line.starts_with([' ', '\t']) {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_string();
+        let value = value.trim();
+        let values = if value.is_empty() {
+            Vec::new()
+        } else if let Some(list) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            list.split(',')
+                .map(unquote)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+                .collect()
+        } else {
+            vec![unquote(value).to_string()]
+        };
+        fields.insert(key.clone(), values);
+        current = Some(key);
+    }
+    fields
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+/// Render a YAML frontmatter block; empty fields are omitted.
+pub fn render_frontmatter(fields: &[(&str, Vec<String>)]) -> String {
+    let mut out = String::from("---\n");
+    for (key, values) in fields.iter().filter(|(_, v)| !v.is_empty()) {
+        if values.len() == 1 && *key != "tags" && *key != "aliases" {
+            let _ = writeln!(out, "{key}: {}", yaml_scalar(&values[0]));
+        } else {
+            let _ = writeln!(out, "{key}:");
+            for value in values {
+                let _ = writeln!(out, "  - {}", yaml_scalar(value));
+            }
+        }
+    }
+    out.push_str("---\n");
+    out
+}
+
+fn yaml_scalar(value: &str) -> String {
+    if value.contains([':', '#', '[', ']', '"', '\'']) || value.trim() != value {
+        format!("\"{}\"", value.replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn note_stem(path: &str) -> &str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.strip_suffix(".md").unwrap_or(name)
+}
+
+/// File name for a note title, with characters Obsidian rejects removed.
+pub fn note_file_name(title: &str) -> anyhow::Result<String> {
+    let name: String = title
+        .chars()
+        .filter(|c| {
+            !matches!(
+                c,
+                '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']'
+            )
+        })
+        .collect();
+    let name = name.trim().trim_start_matches('.').trim();
+    if name.is_empty() {
+        anyhow::bail!("Note title '{title}' has no usable characters");
+    }
+    Ok(format!("{name}.md"))
+}
+
+/// Resolve a vault-relative note path, rejecting absolute paths, `..` and
+/// hidden components. `.md` is appended when missing.
+pub fn vault_path(vault: &Path, relative: &str) -> anyhow::Result<PathBuf> {
+    let relative = relative.trim().trim_start_matches("./");
+    let mut path = PathBuf::new();
+    for component in Path::new(relative).components() {
+        match component {
+            Component::Normal(part) if !part.to_string_lossy().starts_with('.') => {
+                path.push(part);
+            }
+            _ => anyhow::bail!("Invalid vault path '{relative}'"),
+        }
+    }
+    if path.as_os_str().is_empty() {
+        anyhow::bail!("Empty vault path");
+    }
+    if path.extension().and_then(|e| e.to_str()) != Some("md") {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".md");
+        path.set_file_name(name);
+    }
+    Ok(vault.join(path))
+}
+
+/// Find a note by vault-relative path, or by title/file name anywhere in the
+/// vault (case-insensitive, shortest path wins like Obsidian link resolution).
+pub fn find_note(vault: &Path, name: &str, exclude_folders: &[String]) -> Option<PathBuf> {
+    let name = name.trim();
+    if name.contains('/') {
+        return vault_path(vault, name).ok().filter(|p| p.is_file());
+    }
+    let wanted = name.strip_suffix(".md").unwrap_or(name).to_lowercase();
+    scan_vault(vault, exclude_folders)
+        .into_iter()
+        .map(|(rel, _)| rel)
+        .filter(|rel| note_stem(rel).to_lowercase() == wanted)
+        .min_by_key(|rel| (rel.matches('/').count(), rel.clone()))
+        .map(|rel| vault.join(rel))
+}
+
+/// Vault-relative `/`-separated path of a file inside the vault.
+pub fn relative_path(vault: &Path, path: &Path) -> String {
+    path.strip_prefix(vault)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// All Markdown notes in the vault with their modification time (ms).
+/// Hidden entries (`.obsidian`, `.trash`), symlinks and `exclude_folders`
+/// are skipped.
+pub fn scan_vault(vault: &Path, exclude_folders: &[String]) -> Vec<(String, u64)> {
+    let excluded: Vec<&str> = exclude_folders
+        .iter()
+        .map(|f| f.trim().trim_matches('/'))
+        .filter(|f| !f.is_empty())
+        .collect();
+    let mut notes = Vec::new();
+    let mut pending = vec![vault.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let rel = relative_path(vault, &path);
+            if file_type.is_dir() {
+                if !excluded
+                    .iter()
+                    .any(|f| rel == *f || rel.starts_with(&format!("{f}/")))
+                {
+                    pending.push(path);
+                }
+            } else if file_type.is_file() && path.extension().and_then(|e| e.to_str()) == Some("md")
+            {
+                let modified = entry
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX));
+                notes.push((rel, modified));
+            }
+        }
+    }
+    notes.sort();
+    notes
+}
+
+/// Indexed notes, persisted next to `brain.db` so restarts only re-index
+/// notes that changed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexState {
+    vault: String,
+    notes: BTreeMap<String, IndexedNote>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct IndexedNote {
+    modified: u64,
+    chunks: usize,
+}
+
+/// Result of one vault sync.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncStats {
+    pub indexed: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+/// Obsidian vault memory — notes are indexed into SQLite for hybrid recall
+///
+/// The vault stays the source of truth for notes: they are re-indexed when
+/// their modification time changes and dropped from the index when deleted.
+/// Memories stored by the agent live in `brain.db` alongside the index.
+pub struct ObsidianMemory {
+    vault: PathBuf,
+    exclude_folders: Vec<String>,
+    index: SqliteMemory,
+    state_path: PathBuf,
+    chunk_max_tokens: usize,
+    sync_interval: Duration,
+    last_sync: Mutex<Option<Instant>>,
+}
+
+impl ObsidianMemory {
+    pub fn new(
+        workspace_dir: &Path,
+        config: &ObsidianConfig,
+        chunk_max_tokens: usize,
+        index: SqliteMemory,
+    ) -> anyhow::Result<Self> {
+        let vault = config.vault_dir().ok_or_else(|| {
+            anyhow::anyhow!("memory backend 'obsidian' requires [memory.obsidian].vault_path")
+        })?;
+        if !vault.is_dir() {
+            anyhow::bail!("Obsidian vault '{}' is not a directory", vault.display());
+        }
+
+        Ok(Self {
+            vault,
+            exclude_folders: config.exclude_folders.clone(),
+            index,
+            state_path: workspace_dir.join("memory").join(INDEX_STATE_FILE),
+            chunk_max_tokens: chunk_max_tokens.max(64),
+            sync_interval: Duration::from_secs(config.sync_interval_secs),
+            last_sync: Mutex::new(None),
+        })
+    }
+
+    fn note_key(path: &str, chunk: usize) -> String {
+        format!("{NOTE_KEY_PREFIX}{path}#{chunk}")
+    }
+
+    fn note_category() -> MemoryCategory {
+        MemoryCategory::Custom(NOTE_CATEGORY.to_string())
+    }
+
+    fn is_note(entry: &MemoryEntry) -> bool {
+        entry.key.starts_with(NOTE_KEY_PREFIX)
+    }
+
+    /// Text indexed for each chunk: the note's title, tags and links followed
+    /// by the chunk, so keyword and vector search match on all of them.
+    fn chunk_texts(&self, note: &Note) -> Vec<String> {
+        let mut header = format!("{} ({})", note.title, note.path);
+        if !note.aliases.is_empty() {
+            let _ = write!(header, "\naliases: {}", note.aliases.join(", "));
+        }
+        if !note.tags.is_empty() {
+            let tags: Vec<String> = note.tags.iter().map(|t| format!("#{t}")).collect();
+            let _ = write!(header, "\ntags: {}", tags.join(" "));
+        }
+        if !note.links.is_empty() {
+            let links: Vec<String> = note.links.iter().map(|l| format!("[[{l}]]")).collect();
+            let _ = write!(header, "\nlinks: {}", links.join(" "));
+        }
+
+        let chunks = chunker::chunk_markdown(&note.body, self.chunk_max_tokens);
+        if chunks.is_empty() {
+            return vec![header];
+        }
+        chunks
+            .into_iter()
+            .map(|chunk| format!("{header}\n\n{}", chunk.content.trim()))
+            .collect()
+    }
+
+    fn load_state(&self) -> IndexState {
+        std::fs::read_to_string(&self.state_path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_state(&self, state: &IndexState) -> anyhow::Result<()> {
+        if let Some(parent) = self.state_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.state_path, serde_json::to_string_pretty(state)?)?;
+        Ok(())
+    }
+
+    async fn forget_chunks(&self, path: &str, from: usize, to: usize) -> anyhow::Result<()> {
+        for chunk in from..to {
+            self.index.forget(&Self::note_key(path, chunk)).await?;
+        }
+        Ok(())
+    }
+
+    /// Re-index notes that changed since the last sync and drop deleted ones.
+    pub async fn sync(&self) -> anyhow::Result<SyncStats> {
+        let mut last_sync = self.last_sync.lock().await;
+        let stats = self.sync_locked().await?;
+        *last_sync = Some(Instant::now());
+        Ok(stats)
+    }
+
+    async fn sync_if_due(&self) {
+        let mut last_sync = self.last_sync.lock().await;
+        if last_sync.is_some_and(|at| at.elapsed() < self.sync_interval) {
+            return;
+        }
+        match self.sync_locked().await {
+            Ok(stats) if stats.indexed > 0 || stats.removed > 0 => tracing::info!(
+                "Obsidian vault synced: {} indexed, {} removed",
+                stats.indexed,
+                stats.removed
+            ),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Obsidian vault sync failed: {e}"),
+        }
+        *last_sync = Some(Instant::now());
+    }
+
+    async fn sync_locked(&self) -> anyhow::Result<SyncStats> {
+        let vault = self.vault.clone();
+        let exclude = self.exclude_folders.clone();
+        let files = tokio::task::spawn_blocking(move || scan_vault(&vault, &exclude)).await?;
+
+        let mut state = self.load_state();
+        let vault_key = self.vault.display().to_string();
+        if state.vault != vault_key {
+            for (path, note) in std::mem::take(&mut state.notes) {
+                self.forget_chunks(&path, 0, note.chunks).await?;
+            }
+            state.vault = vault_key;
+        }
+
+        let mut stats = SyncStats::default();
+        let present: HashSet<&str> = files.iter().map(|(path, _)| path.as_str()).collect();
+        let removed: Vec<String> = state
+            .notes
+            .keys()
+            .filter(|path| !present.contains(path.as_str()))
+            .cloned()
+            .collect();
+        for path in removed {
+            if let Some(note) = state.notes.remove(&path) {
+                self.forget_chunks(&path, 0, note.chunks).await?;
+                stats.removed += 1;
+            }
+        }
+
+        for (path, modified) in &files {
+            let previous = state.notes.get(path).copied();
+            if previous.is_some_and(|p| p.modified == *modified) {
+                stats.unchanged += 1;
+                continue;
+            }
+            let Ok(raw) = tokio::fs::read_to_string(self.vault.join(path)).await else {
+                continue;
+            };
+            let note = parse_note(path, &raw);
+            let texts = self.chunk_texts(&note);
+            for (i, text) in texts.iter().enumerate() {
+                self.index
+                    .store(&Self::note_key(path, i), text, Self::note_category(), None)
+                    .await?;
+            }
+            if let Some(previous) = previous {
+                self.forget_chunks(path, texts.len(), previous.chunks)
+                    .await?;
+            }
+            state.notes.insert(
+                path.clone(),
+                IndexedNote {
+                    modified: *modified,
+                    chunks: texts.len(),
+                },
+            );
+            stats.indexed += 1;
+        }
+
+        self.save_state(&state)?;
+        Ok(stats)
+    }
+}
+
+#[async_trait]
+impl Memory for ObsidianMemory {
+    fn name(&self) -> &str {
+        "obsidian"
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        if key.starts_with(NOTE_KEY_PREFIX) {
+            anyhow::bail!("Keys starting with '{NOTE_KEY_PREFIX}' are reserved for vault notes");
+        }
+        self.index.store(key, content, category, session_id).await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.sync_if_due().await;
+        let Some(session_id) = session_id else {
+            return self.index.recall(query, limit, None).await;
+        };
+
+        // Vault notes are never session-scoped, so merge them into
+        // session-filtered results by score.
+        let mut results = self.index.recall(query, limit, Some(session_id)).await?;
+        let seen: HashSet<String> = results.iter().map(|e| e.id.clone()).collect();
+        results.extend(
+            self.index
+                .recall(query, limit, None)
+                .await?
+                .into_iter()
+                .filter(|e| Self::is_note(e) && !seen.contains(&e.id)),
+        );
+        results.sort_by(|a, b| {
+            b.score
+                .unwrap_or(0.0)
+                .partial_cmp(&a.score.unwrap_or(0.0))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        self.index.get(key).await
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.sync_if_due().await;
+        self.index.list(category, session_id).await
+    }
+
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        if key.starts_with(NOTE_KEY_PREFIX) {
+            // The vault owns its notes; deleting one from the index would be
+            // undone by the next sync.
+            return Ok(false);
+        }
+        self.index.forget(key).await
+    }
+
+    async fn count(&self) -> anyhow::Result<usize> {
+        self.sync_if_due().await;
+        self.index.count().await
+    }
+
+    async fn health_check(&self) -> bool {
+        self.vault.is_dir() && self.index.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const NOTE: &str = "---\ntitle: Release Process\naliases: [Shipping]\ntags:\n  - process\n  - \"#team/eng\"\n---\n# Release\n\nCut the branch, then see [[Deploy Checklist|the checklist]] and [[On-call#Escalation]].\nTagged #release and #2024 but not `#code`.\n\n```sh\n# not-a-tag\n```\n";
+
+    fn write(vault: &Path, rel: &str, content: &str) {
+        let path = vault.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn memory(workspace: &Path, vault: &Path) -> ObsidianMemory {
+        let config = ObsidianConfig {
+            vault_path: vault.display().to_string(),
+            exclude_folders: vec!["Templates".into()],
+            ..ObsidianConfig::default()
+        };
+        ObsidianMemory::new(
+            workspace,
+            &config,
+            512,
+            SqliteMemory::new(workspace).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_frontmatter_tags_and_wikilinks() {
+        let note = parse_note("Team/Release.md", NOTE);
+        assert_eq!(note.title, "Release Process");
+        assert_eq!(note.aliases, vec!["Shipping"]);
+        assert_eq!(note.tags, vec!["process", "team/eng", "release"]);
+        assert_eq!(note.links, vec!["Deploy Checklist", "On-call"]);
+        assert!(note.body.starts_with("# Release"));
+    }
+
+    #[test]
+    fn notes_without_frontmatter_use_file_name_as_title() {
+        let note = parse_note("Inbox/Quick idea.md", "Just text with [[Link]].");
+        assert_eq!(note.title, "Quick idea");
+        assert!(note.frontmatter.is_empty());
+        assert_eq!(note.links, vec!["Link"]);
+    }
+
+    #[test]
+    fn frontmatter_round_trips() {
+        let rendered = render_frontmatter(&[
+            ("created", vec!["2026-01-01T00:00:00Z".into()]),
+            ("tags", vec!["zeroclaw".into()]),
+            ("aliases", Vec::new()),
+        ]);
+        let note = parse_note("a.md", &format!("{rendered}body"));
+        assert_eq!(note.frontmatter["created"], vec!["2026-01-01T00:00:00Z"]);
+        assert_eq!(note.tags, vec!["zeroclaw"]);
+        assert_eq!(note.body, "body");
+    }
+
+    #[test]
+    fn vault_paths_stay_inside_the_vault() {
+        let vault = Path::new("/vault");
+        assert_eq!(
+            vault_path(vault, "Team/Notes").unwrap(),
+            PathBuf::from("/vault/Team/Notes.md")
+        );
+        assert!(vault_path(vault, "../etc/passwd").is_err());
+        assert!(vault_path(vault, "/etc/passwd").is_err());
+        assert!(vault_path(vault, ".obsidian/app.json").is_err());
+        assert_eq!(
+            note_file_name("Q3: plan / review?").unwrap(),
+            "Q3 plan  review.md"
+        );
+        assert!(note_file_name("///").is_err());
+    }
+
+    #[test]
+    fn scan_skips_hidden_and_excluded_folders() {
+        let tmp = TempDir::new().unwrap();
+        write(tmp.path(), "A.md", "a");
+        write(tmp.path(), "Team/B.md", "b");
+        write(tmp.path(), ".obsidian/workspace.md", "x");
+        write(tmp.path(), "Templates/T.md", "t");
+        write(tmp.path(), "Team/image.png", "png");
+
+        let paths: Vec<String> = scan_vault(tmp.path(), &["Templates".into()])
+            .into_iter()
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(paths, vec!["A.md", "Team/B.md"]);
+        assert_eq!(
+            find_note(tmp.path(), "b", &[]).unwrap(),
+            tmp.path().join("Team/B.md")
+        );
+    }
+
+    #[tokio::test]
+    async fn indexes_vault_for_recall_and_tracks_changes() {
+        let workspace = TempDir::new().unwrap();
+        let vault = TempDir::new().unwrap();
+        write(vault.path(), "Team/Release.md", NOTE);
+        write(vault.path(), "Other.md", "Lunch menu for Friday");
+        let mem = memory(workspace.path(), vault.path());
+
+        let stats = mem.sync().await.unwrap();
+        assert_eq!(stats.indexed, 2);
+
+        let hits = mem.recall("checklist", 5, None).await.unwrap();
+        assert_eq!(hits[0].key, "obsidian:Team/Release.md#0");
+        assert!(hits[0]
+            .content
+            .contains("tags: #process #team/eng #release"));
+        assert!(hits[0].content.contains("[[Deploy Checklist]]"));
+
+        assert_eq!(mem.sync().await.unwrap().unchanged, 2);
+
+        std::fs::remove_file(vault.path().join("Other.md")).unwrap();
+        let stats = mem.sync().await.unwrap();
+        assert_eq!(stats.removed, 1);
+        assert!(mem.get("obsidian:Other.md#0").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn session_recall_includes_vault_notes() {
+        let workspace = TempDir::new().unwrap();
+        let vault = TempDir::new().unwrap();
+        write(
+            vault.path(),
+            "Deploy.md",
+            "Deploy with the blue-green rollout",
+        );
+        let mem = memory(workspace.path(), vault.path());
+        mem.store(
+            "deploy_pref",
+            "User deploys on Tuesdays",
+            MemoryCategory::Conversation,
+            Some("s1"),
+        )
+        .await
+        .unwrap();
+
+        let hits = mem.recall("deploy", 10, Some("s1")).await.unwrap();
+        let keys: Vec<&str> = hits.iter().map(|e| e.key.as_str()).collect();
+        assert!(keys.contains(&"deploy_pref"));
+        assert!(keys.contains(&"obsidian:Deploy.md#0"));
+    }
+
+    #[tokio::test]
+    async fn vault_notes_cannot_be_overwritten_or_forgotten() {
+        let workspace = TempDir::new().unwrap();
+        let vault = TempDir::new().unwrap();
+        write(vault.path(), "Keep.md", "keep me");
+        let mem = memory(workspace.path(), vault.path());
+        mem.sync().await.unwrap();
+
+        assert!(mem
+            .store("obsidian:Keep.md#0", "x", MemoryCategory::Core, None)
+            .await
+            .is_err());
+        assert!(!mem.forget("obsidian:Keep.md#0").await.unwrap());
+        assert!(mem.get("obsidian:Keep.md#0").await.unwrap().is_some());
+    }
+
+    #[test]
+    fn missing_vault_is_rejected() {
+        let workspace = TempDir::new().unwrap();
+        let sqlite = SqliteMemory::new(workspace.path()).unwrap();
+        let err = ObsidianMemory::new(workspace.path(), &ObsidianConfig::default(), 512, sqlite)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("vault_path"));
+    }
+}
@@ -0,0 +1,450 @@
+//! Async MQTT client: one connection, driven by a background task.
+//!
+//! [`MqttClient::connect`] returns the client and a receiver for incoming
+//! publishes. The connection task answers the QoS 1/2 handshakes, sends
+//! keep-alive pings and resolves each `publish`/`subscribe` once the broker
+//! has acknowledged it. When the connection drops, pending calls fail and
+//! the receiver closes; callers reconnect by calling `connect` again.
+
+use super::packet::{connack_message, Connect, Packet, Publish, QoS};
+use crate::config::MqttConfig;
+use anyhow::{bail, Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{mpsc, oneshot};
+use tokio_rustls::rustls;
+
+const DEFAULT_PORT: u16 = 1883;
+const DEFAULT_TLS_PORT: u16 = 8883;
+
+/// Incoming publishes buffered for the receiver before new ones are dropped.
+const INCOMING_CAPACITY: usize = 256;
+
+trait Stream: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Stream for T {}
+
+/// Everything needed to open a connection.
+#[derive(Clone)]
+pub struct MqttOptions {
+    pub host: String,
+    pub port: u16,
+    /// Set to connect over TLS.
+    pub tls: Option<Arc<rustls::ClientConfig>>,
+    pub client_id: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub keep_alive: Duration,
+    /// Limit for connecting and for each acknowledged operation.
+    pub timeout: Duration,
+}
+
+impl MqttOptions {
+    /// Plain TCP options with defaults (tests, local brokers).
+    pub fn new(host: impl Into<String>, port: u16, client_id: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            port,
+            tls: None,
+            client_id: client_id.into(),
+            username: None,
+            password: None,
+            keep_alive: Duration::from_secs(30),
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Options for `[mqtt]`. `suffix` is appended to the client id so the
+    /// channel and tool connections do not take over each other's session.
+    pub fn from_config(config: &MqttConfig, suffix: &str) -> Result<Self> {
+        let host = config.host.trim();
+        if host.is_empty() {
+            bail!("mqtt.host is not set");
+        }
+        let tls = config.tls.then(|| tls_config(config)).transpose()?;
+        let port = config.port.unwrap_or(if config.tls {
+            DEFAULT_TLS_PORT
+        } else {
+            DEFAULT_PORT
+        });
+        let base = config.client_id.trim();
+        let client_id = match (base.is_empty(), suffix.is_empty()) {
+            (true, _) => format!("zeroclaw-{suffix}"),
+            (false, true) => base.to_string(),
+            (false, false) => format!("{base}-{suffix}"),
+        };
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            tls: tls.map(Arc::new),
+            client_id,
+            username: config.username.clone().filter(|u| !u.is_empty()),
+            password: config.password.clone().filter(|p| !p.is_empty()),
+            keep_alive: Duration::from_secs(u64::from(config.keep_alive_secs)),
+            timeout: Duration::from_secs(config.timeout_secs.max(1)),
+        })
+    }
+}
+
+/// rustls config for `[mqtt]`: `ca_cert` or the public web roots, plus an
+/// optional client certificate for mutual TLS.
+fn tls_config(config: &MqttConfig) -> Result<rustls::ClientConfig> {
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+
+    let mut roots = rustls::RootCertStore::empty();
+    if let Some(path) = config.ca_cert.as_deref() {
+        for cert in CertificateDer::pem_file_iter(path)
+            .with_context(|| format!("Failed to read mqtt.ca_cert {path}"))?
+        {
+            roots.add(cert.with_context(|| format!("Invalid certificate in {path}"))?)?;
+        }
+    } else {
+        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    }
+    let builder = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()?
+    .with_root_certificates(roots);
+
+    match (config.client_cert.as_deref(), config.client_key.as_deref()) {
+        (Some(cert), Some(key)) => {
+            let chain = CertificateDer::pem_file_iter(cert)
+                .with_context(|| format!("Failed to read mqtt.client_cert {cert}"))?
+                .collect::<Result<Vec<_>, _>>()?;
+            let key = PrivateKeyDer::from_pem_file(key)
+                .with_context(|| format!("Failed to read mqtt.client_key {key}"))?;
+            Ok(builder.with_client_auth_cert(chain, key)?)
+        }
+        (None, None) => Ok(builder.with_no_client_auth()),
+        _ => bail!("mqtt.client_cert and mqtt.client_key must be set together"),
+    }
+}
+
+enum Command {
+    Publish(Publish, oneshot::Sender<Result<()>>),
+    Subscribe(Vec<(String, QoS)>, oneshot::Sender<Result<Vec<u8>>>),
+    Disconnect(oneshot::Sender<()>),
+}
+
+/// Handle to a live connection. Cheap to clone; the connection closes when
+/// the last handle is dropped or [`disconnect`](Self::disconnect) is called.
+#[derive(Clone)]
+pub struct MqttClient {
+    commands: mpsc::Sender<Command>,
+    timeout: Duration,
+}
+
+impl MqttClient {
+    /// Connect and start the connection task.
+    pub async fn connect(options: &MqttOptions) -> Result<(Self, mpsc::Receiver<Publish>)> {
+        let addr = format!("{}:{}", options.host, options.port);
+        let session = async {
+            let tcp = tokio::net::TcpStream::connect(&addr)
+                .await
+                .with_context(|| format!("Failed to connect to MQTT broker {addr}"))?;
+            tcp.set_nodelay(true)?;
+            let mut stream: Box<dyn Stream> = match &options.tls {
+                Some(tls) => {
+                    let connector = tokio_rustls::TlsConnector::from(tls.clone());
+                    let domain = rustls::pki_types::ServerName::try_from(options.host.clone())?;
+                    Box::new(connector.connect(domain, tcp).await?)
+                }
+                None => Box::new(tcp),
+            };
+
+            let connect = Packet::Connect(Connect {
+                client_id: options.client_id.clone(),
+                keep_alive: u16::try_from(options.keep_alive.as_secs()).unwrap_or(u16::MAX),
+                clean_session: true,
+                username: options.username.clone(),
+                password: options.password.clone(),
+            });
+            stream.write_all(&connect.encode()?).await?;
+            stream.flush().await?;
+            match Packet::read(&mut stream).await? {
+                Some(Packet::ConnAck { code: 0, .. }) => Ok(stream),
+                Some(Packet::ConnAck { code, .. }) => {
+                    bail!("MQTT broker refused connection: {}", connack_message(code))
+                }
+                _ => bail!("MQTT broker did not acknowledge the connection"),
+            }
+        };
+        let stream = tokio::time::timeout(options.timeout, session)
+            .await
+            .map_err(|_| anyhow::anyhow!("Timed out connecting to MQTT broker {addr}"))??;
+
+        let (commands, command_rx) = mpsc::channel(32);
+        let (incoming, incoming_rx) = mpsc::channel(INCOMING_CAPACITY);
+        let keep_alive = options.keep_alive;
+        tokio::spawn(async move {
+            if let Err(e) = run(stream, command_rx, incoming, keep_alive).await {
+                tracing::debug!("MQTT connection closed: {e}");
+            }
+        });
+        Ok((
+            Self {
+                commands,
+                timeout: options.timeout,
+            },
+            incoming_rx,
+        ))
+    }
+
+    async fn request<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<Result<T>>) -> Command,
+    ) -> Result<T> {
+        let (tx, rx) = oneshot::channel();
+        self.commands
+            .send(command(tx))
+            .await
+            .map_err(|_| anyhow::anyhow!("MQTT connection closed"))?;
+        tokio::time::timeout(self.timeout, rx)
+            .await
+            .map_err(|_| anyhow::anyhow!("MQTT broker did not acknowledge in time"))?
+            .map_err(|_| anyhow::anyhow!("MQTT connection closed"))?
+    }
+
+    /// Publish and wait until the broker has taken responsibility for the
+    /// message (written for QoS 0, PUBACK for 1, PUBCOMP for 2).
+    pub async fn publish(
+        &self,
+        topic: &str,
+        payload: impl Into<Vec<u8>>,
+        qos: QoS,
+        retain: bool,
+    ) -> Result<()> {
+        let mut publish = Publish::new(topic, payload, qos);
+        publish.retain = retain;
+        self.request(|tx| Command::Publish(publish, tx)).await
+    }
+
+    /// Subscribe and return the QoS granted per filter. Fails if the broker
+    /// refuses any filter.
+    pub async fn subscribe(&self, filters: &[(String, QoS)]) -> Result<Vec<QoS>> {
+        let codes = self
+            .request(|tx| Command::Subscribe(filters.to_vec(), tx))
+            .await?;
+        if codes.len() != filters.len() {
+            bail!("MQTT broker answered the wrong number of subscriptions");
+        }
+        filters
+            .iter()
+            .zip(codes)
+            .map(|((filter, _), code)| {
+                QoS::try_from(code)
+                    .map_err(|_| anyhow::anyhow!("MQTT broker refused subscription to '{filter}'"))
+            })
+            .collect()
+    }
+
+    /// Send DISCONNECT and close the connection.
+    pub async fn disconnect(&self) {
+        let (tx, rx) = oneshot::channel();
+        if self.commands.send(Command::Disconnect(tx)).await.is_ok() {
+            let _ = tokio::time::timeout(self.timeout, rx).await;
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        !self.commands.is_closed()
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+async fn run(
+    stream: Box<dyn Stream>,
+    mut commands: mpsc::Receiver<Command>,
+    incoming: mpsc::Sender<Publish>,
+    keep_alive: Duration,
+) -> Result<()> {
+    let (mut reader, mut writer) = tokio::io::split(stream);
+    // Packet::read is not cancel-safe, so reading gets its own task.
+    let (packet_tx, mut packets) = mpsc::channel::<Result<Packet>>(64);
+    let reader_task = tokio::spawn(async move {
+        loop {
+            let packet = Packet::read(&mut reader).await.transpose();
+            let closed = !matches!(packet, Some(Ok(_)));
+            let packet =
+                packet.unwrap_or_else(|| Err(anyhow::anyhow!("MQTT broker closed the connection")));
+            if packet_tx.send(packet).await.is_err() || closed {
+                return;
+            }
+        }
+    });
+    let _abort_reader = AbortOnDrop(reader_task);
+
+    let mut next_pkid: u16 = 0;
+    let mut alloc_pkid = || {
+        next_pkid = next_pkid.checked_add(1).unwrap_or(1);
+        next_pkid
+    };
+    // Outgoing QoS 1/2 publishes waiting for PUBACK / PUBCOMP.
+    let mut in_flight: HashMap<u16, oneshot::Sender<Result<()>>> = HashMap::new();
+    let mut subscribing: HashMap<u16, oneshot::Sender<Result<Vec<u8>>>> = HashMap::new();
+    // QoS 2 publishes received but not yet released by the broker.
+    let mut received: HashSet<u16> = HashSet::new();
+
+    let ping_period = if keep_alive.is_zero() {
+        Duration::from_secs(3600)
+    } else {
+        keep_alive
+    };
+    let mut ping = tokio::time::interval_at(tokio::time::Instant::now() + ping_period, ping_period);
+    let mut awaiting_pong = false;
+
+    macro_rules! send {
+        ($packet:expr) => {{
+            writer.write_all(&$packet.encode()?).await?;
+            writer.flush().await?;
+        }};
+    }
+
+    loop {
+        tokio::select! {
+            command = commands.recv() => match command {
+                Some(Command::Publish(mut publish, done)) => {
+                    if publish.qos == QoS::Zero {
+                        let result = async {
+                            writer.write_all(&Packet::Publish(publish).encode()?).await?;
+                            writer.flush().await?;
+                            Ok(())
+                        }
+                        .await;
+                        let failed = result.is_err();
+                        let _ = done.send(result);
+                        if failed {
+                            bail!("MQTT write failed");
+                        }
+                    } else {
+                        publish.pkid = alloc_pkid();
+                        let pkid = publish.pkid;
+                        match Packet::Publish(publish).encode() {
+                            Ok(bytes) => {
+                                writer.write_all(&bytes).await?;
+                                writer.flush().await?;
+                                in_flight.insert(pkid, done);
+                            }
+                            Err(e) => {
+                                let _ = done.send(Err(e));
+                            }
+                        }
+                    }
+                }
+                Some(Command::Subscribe(filters, done)) => {
+                    let pkid = alloc_pkid();
+                    send!(Packet::Subscribe { pkid, filters });
+                    subscribing.insert(pkid, done);
+                }
+                Some(Command::Disconnect(done)) => {
+                    let _ = writer.write_all(&Packet::Disconnect.encode()?).await;
+                    let _ = writer.shutdown().await;
+                    let _ = done.send(());
+                    return Ok(());
+                }
+                None => {
+                    let _ = writer.write_all(&Packet::Disconnect.encode()?).await;
+                    return Ok(());
+                }
+            },
+            packet = packets.recv() => {
+                let Some(packet) = packet else {
+                    bail!("MQTT reader stopped");
+                };
+                match packet? {
+                    Packet::Publish(publish) => {
+                        let pkid = publish.pkid;
+                        let duplicate = match publish.qos {
+                            QoS::Zero => false,
+                            QoS::One => {
+                                send!(Packet::PubAck(pkid));
+                                false
+                            }
+                            QoS::Two => {
+                                send!(Packet::PubRec(pkid));
+                                !received.insert(pkid)
+                            }
+                        };
+                        if !duplicate && incoming.try_send(publish).is_err() && !incoming.is_closed() {
+                            tracing::warn!("MQTT receiver is full; dropping message");
+                        }
+                    }
+                    Packet::PubRel(pkid) => {
+                        received.remove(&pkid);
+                        send!(Packet::PubComp(pkid));
+                    }
+                    Packet::PubAck(pkid) | Packet::PubComp(pkid) => {
+                        if let Some(done) = in_flight.remove(&pkid) {
+                            let _ = done.send(Ok(()));
+                        }
+                    }
+                    Packet::PubRec(pkid) => send!(Packet::PubRel(pkid)),
+                    Packet::SubAck { pkid, codes } => {
+                        if let Some(done) = subscribing.remove(&pkid) {
+                            let _ = done.send(Ok(codes));
+                        }
+                    }
+                    Packet::PingResp => awaiting_pong = false,
+                    other => tracing::debug!("Ignoring unexpected MQTT packet {other:?}"),
+                }
+            }
+            _ = ping.tick() => {
+                if awaiting_pong {
+                    bail!("MQTT broker did not answer keep-alive ping");
+                }
+                send!(Packet::PingReq);
+                awaiting_pong = true;
+            }
+        }
+    }
+}
+
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_default_port_follows_tls() {
+        let mut config = MqttConfig::default();
+        let options = MqttOptions::from_config(&config, "tools").unwrap();
+        assert_eq!(options.port, DEFAULT_PORT);
+        assert_eq!(options.client_id, "zeroclaw-tools");
+        assert!(options.tls.is_none());
+
+        config.tls = true;
+        config.client_id = "gateway".into();
+        let options = MqttOptions::from_config(&config, "").unwrap();
+        assert_eq!(options.port, DEFAULT_TLS_PORT);
+        assert_eq!(options.client_id, "gateway");
+        assert!(options.tls.is_some());
+    }
+
+    #[test]
+    fn tls_options_report_bad_certificate_paths() {
+        let config = MqttConfig {
+            tls: true,
+            ca_cert: Some("/nonexistent/ca.pem".into()),
+            ..MqttConfig::default()
+        };
+        let err = MqttOptions::from_config(&config, "x").err().unwrap();
+        assert!(err.to_string().contains("mqtt.ca_cert"));
+
+        let config = MqttConfig {
+            tls: true,
+            client_cert: Some("/nonexistent/client.pem".into()),
+            ..MqttConfig::default()
+        };
+        assert!(MqttOptions::from_config(&config, "x").is_err());
+    }
+}
@@ -0,0 +1,115 @@
+//! MQTT 3.1.1 client and topic rules.
+//!
+//! Used by the `mqtt_publish` / `mqtt_subscribe_peek` tools and the MQTT
+//! channel. Connection settings live in `[mqtt]`; which topics the agent may
+//! touch is governed by its allowlists, checked with [`filter_covers`].
+
+pub mod client;
+pub mod packet;
+
+pub use client::{MqttClient, MqttOptions};
+pub use packet::QoS;
+
+use anyhow::{bail, Result};
+
+/// Whether `topic` matches the subscription `filter` (`+` matches one level,
+/// a trailing `#` any number). Wildcards at the first level do not match
+/// `$`-topics such as `$SYS/...`.
+pub fn topic_matches(filter: &str, topic: &str) -> bool {
+    if topic.starts_with('$') && (filter.starts_with('+') || filter.starts_with('#')) {
+        return false;
+    }
+    let mut topic_levels = topic.split('/');
+    for level in filter.split('/') {
+        match (level, topic_levels.next()) {
+            ("#", _) => return true,
+            ("+", Some(_)) => {}
+            (l, Some(t)) if l == t => {}
+            _ => return false,
+        }
+    }
+    topic_levels.next().is_none()
+}
+
+/// Whether every topic matched by `requested` is also matched by `allowed`.
+/// Used to check tool subscriptions and publishes against allowlists.
+pub fn filter_covers(allowed: &str, requested: &str) -> bool {
+    let mut requested_levels = requested.split('/');
+    for level in allowed.split('/') {
+        match (level, requested_levels.next()) {
+            ("#", _) => return true,
+            // `#` in the request reaches deeper than a single-level rule.
+            (_, Some("#")) => return false,
+            ("+", Some(_)) => {}
+            (l, Some(r)) if l == r => {}
+            _ => return false,
+        }
+    }
+    requested_levels.next().is_none()
+}
+
+/// Reject topics that cannot be published to.
+pub fn validate_topic(topic: &str) -> Result<()> {
+    if topic.is_empty() {
+        bail!("MQTT topic must not be empty");
+    }
+    if topic.contains(['+', '#', '\0']) {
+        bail!("MQTT topic '{topic}' must not contain wildcards");
+    }
+    Ok(())
+}
+
+/// Reject malformed subscription filters (`#` only as the last level, `+`
+/// only as a whole level).
+pub fn validate_filter(filter: &str) -> Result<()> {
+    if filter.is_empty() || filter.contains('\0') {
+        bail!("MQTT topic filter must not be empty");
+    }
+    let levels: Vec<&str> = filter.split('/').collect();
+    for (i, level) in levels.iter().enumerate() {
+        let misplaced_hash = level.contains('#') && (*level != "#" || i + 1 != levels.len());
+        let misplaced_plus = level.contains('+') && *level != "+";
+        if misplaced_hash || misplaced_plus {
+            bail!("Invalid MQTT topic filter '{filter}'");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topic_matching_follows_wildcard_rules() {
+        assert!(topic_matches("home/+/temp", "home/kitchen/temp"));
+        assert!(!topic_matches("home/+/temp", "home/kitchen/sensor/temp"));
+        assert!(topic_matches("home/#", "home"));
+        assert!(topic_matches("home/#", "home/a/b"));
+        assert!(!topic_matches("home/kitchen", "home/kitchen/temp"));
+        assert!(!topic_matches("#", "$SYS/broker/uptime"));
+        assert!(topic_matches("$SYS/#", "$SYS/broker/uptime"));
+    }
+
+    #[test]
+    fn allowlist_coverage_handles_wildcards() {
+        assert!(filter_covers("home/#", "home/kitchen/+"));
+        assert!(filter_covers("home/+/temp", "home/kitchen/temp"));
+        assert!(filter_covers("home/+/temp", "home/+/temp"));
+        assert!(!filter_covers("home/+/temp", "home/#"));
+        assert!(!filter_covers("home/kitchen/temp", "home/+/temp"));
+        assert!(!filter_covers("home/+", "home/a/b"));
+        assert!(!filter_covers("home/+/temp", "office/x/temp"));
+    }
+
+    #[test]
+    fn topics_and_filters_are_validated() {
+        assert!(validate_topic("home/kitchen/light").is_ok());
+        assert!(validate_topic("home/+/light").is_err());
+        assert!(validate_topic("").is_err());
+        assert!(validate_filter("home/+/light").is_ok());
+        assert!(validate_filter("home/#").is_ok());
+        assert!(validate_filter("home/#/light").is_err());
+        assert!(validate_filter("home/kit+chen").is_err());
+    }
+}
@@ -0,0 +1,454 @@
+//! MQTT 3.1.1 control packets and their wire encoding.
+
+use anyhow::{bail, Context, Result};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Largest packet we accept; MQTT allows 256 MB but nothing we talk to
+/// needs more than this.
+pub const MAX_PACKET_SIZE: usize = 1024 * 1024;
+
+/// Delivery guarantee of a publish or subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum QoS {
+    /// At most once (fire and forget).
+    Zero = 0,
+    /// At least once (acknowledged with PUBACK).
+    One = 1,
+    /// Exactly once (PUBREC/PUBREL/PUBCOMP handshake).
+    Two = 2,
+}
+
+impl TryFrom<u8> for QoS {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Self::Zero),
+            1 => Ok(Self::One),
+            2 => Ok(Self::Two),
+            other => bail!("Invalid MQTT QoS {other} (expected 0, 1 or 2)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Connect {
+    pub client_id: String,
+    pub keep_alive: u16,
+    pub clean_session: bool,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Publish {
+    pub topic: String,
+    pub payload: Vec<u8>,
+    pub qos: QoS,
+    pub retain: bool,
+    pub dup: bool,
+    /// Zero for QoS 0.
+    pub pkid: u16,
+}
+
+impl Publish {
+    pub fn new(topic: impl Into<String>, payload: impl Into<Vec<u8>>, qos: QoS) -> Self {
+        Self {
+            topic: topic.into(),
+            payload: payload.into(),
+            qos,
+            retain: false,
+            dup: false,
+            pkid: 0,
+        }
+    }
+
+    /// Payload as text, replacing invalid UTF-8.
+    pub fn payload_str(&self) -> std::borrow::Cow<'_, str> {
+        String::from_utf8_lossy(&self.payload)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Packet {
+    Connect(Connect),
+    ConnAck {
+        session_present: bool,
+        code: u8,
+    },
+    Publish(Publish),
+    PubAck(u16),
+    PubRec(u16),
+    PubRel(u16),
+    PubComp(u16),
+    Subscribe {
+        pkid: u16,
+        filters: Vec<(String, QoS)>,
+    },
+    /// Return code per filter: granted QoS, or 0x80 for failure.
+    SubAck {
+        pkid: u16,
+        codes: Vec<u8>,
+    },
+    Unsubscribe {
+        pkid: u16,
+        filters: Vec<String>,
+    },
+    UnsubAck(u16),
+    PingReq,
+    PingResp,
+    Disconnect,
+}
+
+/// Human-readable CONNACK refusal.
+pub fn connack_message(code: u8) -> &'static str {
+    match code {
+        0 => "accepted",
+        1 => "unacceptable protocol version",
+        2 => "client identifier rejected",
+        3 => "server unavailable",
+        4 => "bad user name or password",
+        5 => "not authorized",
+        _ => "unknown refusal",
+    }
+}
+
+fn put_str(buf: &mut Vec<u8>, s: &str) -> Result<()> {
+    put_bytes(buf, s.as_bytes())
+}
+
+fn put_bytes(buf: &mut Vec<u8>, bytes: &[u8]) -> Result<()> {
+    let len = u16::try_from(bytes.len()).context("MQTT string longer than 65535 bytes")?;
+    buf.extend_from_slice(&len.to_be_bytes());
+    buf.extend_from_slice(bytes);
+    Ok(())
+}
+
+fn put_remaining_length(buf: &mut Vec<u8>, mut len: usize) -> Result<()> {
+    if len > MAX_PACKET_SIZE {
+        bail!("MQTT packet of {len} bytes exceeds the {MAX_PACKET_SIZE} byte limit");
+    }
+    loop {
+        let mut byte = u8::try_from(len % 128)?;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        buf.push(byte);
+        if len == 0 {
+            return Ok(());
+        }
+    }
+}
+
+impl Packet {
+    /// Wire bytes of this packet.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut body = Vec::new();
+        let header: u8 = match self {
+            Self::Connect(c) => {
+                put_str(&mut body, "MQTT")?;
+                body.push(4); // protocol level 3.1.1
+                let mut flags = 0u8;
+                if c.clean_session {
+                    flags |= 0x02;
+                }
+                if c.username.is_some() {
+                    flags |= 0x80;
+                }
+                if c.password.is_some() {
+                    flags |= 0x40;
+                }
+                body.push(flags);
+                body.extend_from_slice(&c.keep_alive.to_be_bytes());
+                put_str(&mut body, &c.client_id)?;
+                if let Some(username) = &c.username {
+                    put_str(&mut body, username)?;
+                }
+                if let Some(password) = &c.password {
+                    put_str(&mut body, password)?;
+                }
+                0x10
+            }
+            Self::ConnAck {
+                session_present,
+                code,
+            } => {
+                body.extend_from_slice(&[u8::from(*session_present), *code]);
+                0x20
+            }
+            Self::Publish(p) => {
+                put_str(&mut body, &p.topic)?;
+                if p.qos != QoS::Zero {
+                    body.extend_from_slice(&p.pkid.to_be_bytes());
+                }
+                body.extend_from_slice(&p.payload);
+                0x30 | (u8::from(p.dup) << 3) | ((p.qos as u8) << 1) | u8::from(p.retain)
+            }
+            Self::PubAck(pkid) => {
+                body.extend_from_slice(&pkid.to_be_bytes());
+                0x40
+            }
+            Self::PubRec(pkid) => {
+                body.extend_from_slice(&pkid.to_be_bytes());
+                0x50
+            }
+            Self::PubRel(pkid) => {
+                body.extend_from_slice(&pkid.to_be_bytes());
+                0x62
+            }
+            Self::PubComp(pkid) => {
+                body.extend_from_slice(&pkid.to_be_bytes());
+                0x70
+            }
+            Self::Subscribe { pkid, filters } => {
+                body.extend_from_slice(&pkid.to_be_bytes());
+                for (filter, qos) in filters {
+                    put_str(&mut body, filter)?;
+                    body.push(*qos as u8);
+                }
+                0x82
+            }
+            Self::SubAck { pkid, codes } => {
+                body.extend_from_slice(&pkid.to_be_bytes());
+                body.extend_from_slice(codes);
+                0x90
+            }
+            Self::Unsubscribe { pkid, filters } => {
+                body.extend_from_slice(&pkid.to_be_bytes());
+                for filter in filters {
+                    put_str(&mut body, filter)?;
+                }
+                0xA2
+            }
+            Self::UnsubAck(pkid) => {
+                body.extend_from_slice(&pkid.to_be_bytes());
+                0xB0
+            }
+            Self::PingReq => 0xC0,
+            Self::PingResp => 0xD0,
+            Self::Disconnect => 0xE0,
+        };
+        let mut out = Vec::with_capacity(body.len() + 5);
+        out.push(header);
+        put_remaining_length(&mut out, body.len())?;
+        out.extend_from_slice(&body);
+        Ok(out)
+    }
+
+    /// Read one packet. `Ok(None)` on a clean end of stream.
+    pub async fn read<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Option<Self>> {
+        let mut header = [0u8; 1];
+        if reader.read(&mut header).await? == 0 {
+            return Ok(None);
+        }
+        let mut len = 0usize;
+        let mut shift = 0;
+        loop {
+            let byte = reader.read_u8().await?;
+            len |= usize::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+            if shift > 21 {
+                bail!("Malformed MQTT remaining length");
+            }
+        }
+        if len > MAX_PACKET_SIZE {
+            bail!("MQTT packet of {len} bytes exceeds the {MAX_PACKET_SIZE} byte limit");
+        }
+        let mut body = vec![0u8; len];
+        reader.read_exact(&mut body).await?;
+        Self::decode(header[0], &body).map(Some)
+    }
+
+    /// Parse a packet from its first header byte and body.
+    pub fn decode(header: u8, body: &[u8]) -> Result<Self> {
+        let mut r = Reader { buf: body, pos: 0 };
+        let packet = match header >> 4 {
+            1 => {
+                if r.string()? != "MQTT" || r.u8()? != 4 {
+                    bail!("Unsupported MQTT protocol (only 3.1.1)");
+                }
+                let flags = r.u8()?;
+                let keep_alive = r.u16()?;
+                let client_id = r.string()?;
+                if flags & 0x04 != 0 {
+                    // Will topic and message: accepted, not used.
+                    r.string()?;
+                    r.bytes()?;
+                }
+                let username = (flags & 0x80 != 0).then(|| r.string()).transpose()?;
+                let password = (flags & 0x40 != 0)
+                    .then(|| r.bytes().map(|b| String::from_utf8_lossy(&b).into_owned()))
+                    .transpose()?;
+                Self::Connect(Connect {
+                    client_id,
+                    keep_alive,
+                    clean_session: flags & 0x02 != 0,
+                    username,
+                    password,
+                })
+            }
+            2 => Self::ConnAck {
+                session_present: r.u8()? & 1 == 1,
+                code: r.u8()?,
+            },
+            3 => {
+                let qos = QoS::try_from((header >> 1) & 0x03)?;
+                let topic = r.string()?;
+                let pkid = if qos == QoS::Zero { 0 } else { r.u16()? };
+                Self::Publish(Publish {
+                    topic,
+                    payload: r.rest().to_vec(),
+                    qos,
+                    retain: header & 0x01 != 0,
+                    dup: header & 0x08 != 0,
+                    pkid,
+                })
+            }
+            4 => Self::PubAck(r.u16()?),
+            5 => Self::PubRec(r.u16()?),
+            6 => Self::PubRel(r.u16()?),
+            7 => Self::PubComp(r.u16()?),
+            8 => {
+                let pkid = r.u16()?;
+                let mut filters = Vec::new();
+                while !r.is_empty() {
+                    let filter = r.string()?;
+                    filters.push((filter, QoS::try_from(r.u8()? & 0x03)?));
+                }
+                Self::Subscribe { pkid, filters }
+            }
+            9 => Self::SubAck {
+                pkid: r.u16()?,
+                codes: r.rest().to_vec(),
+            },
+            10 => {
+                let pkid = r.u16()?;
+                let mut filters = Vec::new();
+                while !r.is_empty() {
+                    filters.push(r.string()?);
+                }
+                Self::Unsubscribe { pkid, filters }
+            }
+            11 => Self::UnsubAck(r.u16()?),
+            12 => Self::PingReq,
+            13 => Self::PingResp,
+            14 => Self::Disconnect,
+            other => bail!("Unknown MQTT packet type {other}"),
+        };
+        Ok(packet)
+    }
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> Result<&[u8]> {
+        let end = self.pos + n;
+        let slice = self
+            .buf
+            .get(self.pos..end)
+            .ok_or_else(|| anyhow::anyhow!("Truncated MQTT packet"))?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn bytes(&mut self) -> Result<Vec<u8>> {
+        let len = usize::from(self.u16()?);
+        Ok(self.take(len)?.to_vec())
+    }
+
+    fn string(&mut self) -> Result<String> {
+        String::from_utf8(self.bytes()?).context("MQTT string is not valid UTF-8")
+    }
+
+    fn rest(&mut self) -> &[u8] {
+        let rest = &self.buf[self.pos..];
+        self.pos = self.buf.len();
+        rest
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.buf.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn round_trip(packet: Packet) -> Packet {
+        let bytes = packet.encode().unwrap();
+        Packet::read(&mut bytes.as_slice()).await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn packets_round_trip() {
+        let packets = vec![
+            Packet::Connect(Connect {
+                client_id: "zeroclaw".into(),
+                keep_alive: 30,
+                clean_session: true,
+                username: Some("user".into()),
+                password: Some("secret".into()),
+            }),
+            Packet::ConnAck {
+                session_present: false,
+                code: 0,
+            },
+            Packet::Publish(Publish {
+                topic: "home/kitchen/light".into(),
+                payload: b"on".to_vec(),
+                qos: QoS::Two,
+                retain: true,
+                dup: false,
+                pkid: 7,
+            }),
+            Packet::Publish(Publish::new("a/b", vec![0u8; 300], QoS::Zero)),
+            Packet::PubRel(7),
+            Packet::Subscribe {
+                pkid: 1,
+                filters: vec![("home/#".into(), QoS::One)],
+            },
+            Packet::SubAck {
+                pkid: 1,
+                codes: vec![1, 0x80],
+            },
+            Packet::PingReq,
+            Packet::Disconnect,
+        ];
+        for packet in packets {
+            assert_eq!(round_trip(packet.clone()).await, packet);
+        }
+    }
+
+    #[test]
+    fn remaining_length_uses_variable_byte_integer() {
+        let publish = Packet::Publish(Publish::new("t", vec![0u8; 200], QoS::Zero));
+        let bytes = publish.encode().unwrap();
+        // 2 (topic length) + 1 (topic) + 200 = 203 → 0xCB 0x01
+        assert_eq!(&bytes[..3], &[0x30, 0xCB, 0x01]);
+    }
+
+    #[test]
+    fn truncated_and_unknown_packets_are_rejected() {
+        assert!(Packet::decode(0x40, &[0x01]).is_err());
+        assert!(Packet::decode(0xF0, &[]).is_err());
+        assert!(QoS::try_from(3).is_err());
+    }
+}
@@ -62,6 +62,20 @@ impl Observer for LogObserver {
                     "llm.request"
                 );
             }
+            ObserverEvent::ContextUsage {
+                provider,
+                model,
+                estimated_tokens,
+                window_tokens,
+            } => {
+                info!(
+                    provider = %provider,
+                    model = %model,
+                    estimated_tokens = estimated_tokens,
+                    window_tokens = window_tokens,
+                    "llm.context"
+                );
+            }
             ObserverEvent::LlmResponse {
                 provider,
                 model,
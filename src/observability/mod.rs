@@ -3,6 +3,7 @@ pub mod multi;
 pub mod noop;
 pub mod otel;
 pub mod prometheus;
+pub mod trace;
 pub mod traits;
 pub mod verbose;
 
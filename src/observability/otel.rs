@@ -1,12 +1,11 @@
 use super::traits::{Observer, ObserverEvent, ObserverMetric};
 use opentelemetry::metrics::{Counter, Gauge, Histogram};
-use opentelemetry::trace::{Span, SpanKind, Status, Tracer};
-use opentelemetry::{global, KeyValue};
+use opentelemetry::trace::{Span, SpanKind, Status, TraceContextExt, Tracer};
+use opentelemetry::{global, Context, KeyValue};
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::metrics::SdkMeterProvider;
 use opentelemetry_sdk::trace::SdkTracerProvider;
 use std::any::Any;
-use std::time::SystemTime;
 
 /// OpenTelemetry-backed observer — exports traces and metrics via OTLP.
 pub struct OtelObserver {
@@ -185,6 +184,7 @@ impl Observer for OtelObserver {
                 );
             }
             ObserverEvent::LlmRequest { .. }
+            | ObserverEvent::ContextUsage { .. }
             | ObserverEvent::ToolCallStart { .. }
             | ObserverEvent::TurnComplete => {}
             ObserverEvent::LlmResponse {
@@ -202,28 +202,6 @@ impl Observer for OtelObserver {
                 ];
                 self.llm_calls.add(1, &attrs);
                 self.llm_duration.record(secs, &attrs);
-
-                // Create a completed span for visibility in trace backends.
-                let start_time = SystemTime::now()
-                    .checked_sub(*duration)
-                    .unwrap_or(SystemTime::now());
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("llm.call")
-                        .with_kind(SpanKind::Internal)
-                        .with_start_time(start_time)
-                        .with_attributes(vec![
-                            KeyValue::new("provider", provider.clone()),
-                            KeyValue::new("model", model.clone()),
-                            KeyValue::new("success", *success),
-                            KeyValue::new("duration_s", secs),
-                        ]),
-                );
-                if *success {
-                    span.set_status(Status::Ok);
-                } else {
-                    span.set_status(Status::error(""));
-                }
-                span.end();
             }
             ObserverEvent::AgentEnd {
                 provider,
@@ -233,28 +211,19 @@ impl Observer for OtelObserver {
                 cost_usd,
             } => {
                 let secs = duration.as_secs_f64();
-                let start_time = SystemTime::now()
-                    .checked_sub(*duration)
-                    .unwrap_or(SystemTime::now());
-
-                // Create a completed span with correct timing
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("agent.invocation")
-                        .with_kind(SpanKind::Internal)
-                        .with_start_time(start_time)
-                        .with_attributes(vec![
-                            KeyValue::new("provider", provider.clone()),
-                            KeyValue::new("model", model.clone()),
-                            KeyValue::new("duration_s", secs),
-                        ]),
-                );
+                let mut attrs = vec![
+                    KeyValue::new("provider", provider.clone()),
+                    KeyValue::new("model", model.clone()),
+                ];
                 if let Some(t) = tokens_used {
-                    span.set_attribute(KeyValue::new("tokens_used", *t as i64));
+                    attrs.push(KeyValue::new("tokens_used", *t as i64));
                 }
                 if let Some(c) = cost_usd {
-                    span.set_attribute(KeyValue::new("cost_usd", *c));
+                    attrs.push(KeyValue::new("cost_usd", *c));
                 }
-                span.end();
+                // Live `agent.turn` spans are built by `observability::trace`;
+                // attach the invocation totals to whichever span is current.
+                Context::current().span().set_attributes(attrs);
 
                 self.agent_duration.record(
                     secs,
@@ -272,29 +241,6 @@ impl Observer for OtelObserver {
                 success,
             } => {
                 let secs = duration.as_secs_f64();
-                let start_time = SystemTime::now()
-                    .checked_sub(*duration)
-                    .unwrap_or(SystemTime::now());
-
-                let status = if *success {
-                    Status::Ok
-                } else {
-                    Status::error("")
-                };
-
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("tool.call")
-                        .with_kind(SpanKind::Internal)
-                        .with_start_time(start_time)
-                        .with_attributes(vec![
-                            KeyValue::new("tool.name", tool.clone()),
-                            KeyValue::new("tool.success", *success),
-                            KeyValue::new("duration_s", secs),
-                        ]),
-                );
-                span.set_status(status);
-                span.end();
-
                 let attrs = [
                     KeyValue::new("tool", tool.clone()),
                     KeyValue::new("success", success.to_string()),
@@ -316,14 +262,15 @@ impl Observer for OtelObserver {
                 self.heartbeat_ticks.add(1, &[]);
             }
             ObserverEvent::Error { component, message } => {
-                // Create an error span for visibility in trace backends
-                let mut span = tracer.build(
+                // Error span nested under the current turn/call, if any
+                let mut span = tracer.build_with_context(
                     opentelemetry::trace::SpanBuilder::from_name("error")
                         .with_kind(SpanKind::Internal)
                         .with_attributes(vec![
                             KeyValue::new("component", component.clone()),
                             KeyValue::new("error.message", message.clone()),
                         ]),
+                    &Context::current(),
                 );
                 span.set_status(Status::error(message.clone()));
                 span.end();
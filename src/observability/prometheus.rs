@@ -21,6 +21,8 @@ pub struct PrometheusObserver {
 
     // Gauges
     tokens_used: prometheus::IntGauge,
+    context_tokens: GaugeVec,
+    context_window_tokens: GaugeVec,
     active_sessions: GaugeVec,
     queue_depth: GaugeVec,
 }
@@ -92,6 +94,24 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let context_tokens = GaugeVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_context_tokens",
+                "Estimated prompt tokens in the last LLM request",
+            ),
+            &["provider", "model"],
+        )
+        .expect("valid metric");
+
+        let context_window_tokens = GaugeVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_context_window_tokens",
+                "Context window of the model used in the last LLM request",
+            ),
+            &["provider", "model"],
+        )
+        .expect("valid metric");
+
         let active_sessions = GaugeVec::new(
             prometheus::Opts::new("zeroclaw_active_sessions", "Number of active sessions"),
             &[],
@@ -114,6 +134,10 @@ impl PrometheusObserver {
         registry.register(Box::new(tool_duration.clone())).ok();
         registry.register(Box::new(request_latency.clone())).ok();
         registry.register(Box::new(tokens_used.clone())).ok();
+        registry.register(Box::new(context_tokens.clone())).ok();
+        registry
+            .register(Box::new(context_window_tokens.clone()))
+            .ok();
         registry.register(Box::new(active_sessions.clone())).ok();
         registry.register(Box::new(queue_depth.clone())).ok();
 
@@ -128,6 +152,8 @@ impl PrometheusObserver {
             tool_duration,
             request_latency,
             tokens_used,
+            context_tokens,
+            context_window_tokens,
             active_sessions,
             queue_depth,
         }
@@ -166,6 +192,19 @@ impl Observer for PrometheusObserver {
                     self.tokens_used.set(i64::try_from(*t).unwrap_or(i64::MAX));
                 }
             }
+            ObserverEvent::ContextUsage {
+                provider,
+                model,
+                estimated_tokens,
+                window_tokens,
+            } => {
+                self.context_tokens
+                    .with_label_values(&[provider, model])
+                    .set(*estimated_tokens as f64);
+                self.context_window_tokens
+                    .with_label_values(&[provider, model])
+                    .set(*window_tokens as f64);
+            }
             ObserverEvent::ToolCallStart { tool: _ }
             | ObserverEvent::TurnComplete
             | ObserverEvent::LlmRequest { .. }
@@ -381,4 +420,23 @@ mod tests {
         let output = obs.encode();
         assert!(output.contains("zeroclaw_tokens_used_last 200"));
     }
+
+    #[test]
+    fn context_usage_sets_labeled_gauges() {
+        let obs = PrometheusObserver::new();
+        obs.record_event(&ObserverEvent::ContextUsage {
+            provider: "openrouter".into(),
+            model: "claude-sonnet".into(),
+            estimated_tokens: 1500,
+            window_tokens: 200_000,
+        });
+
+        let output = obs.encode();
+        assert!(output.contains(
+            r#"zeroclaw_context_tokens{model="claude-sonnet",provider="openrouter"} 1500"#
+        ));
+        assert!(output.contains(
+            r#"zeroclaw_context_window_tokens{model="claude-sonnet",provider="openrouter"} 200000"#
+        ));
+    }
 }
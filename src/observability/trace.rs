@@ -0,0 +1,300 @@
+//! Hierarchical OpenTelemetry spans for the agent runtime.
+//!
+//! Spans are opened as children of the current OTel [`Context`] and made
+//! current for the wrapped future, which yields the tree
+//! `gateway.request` / `channel.message` → `agent.turn` → `llm.call` /
+//! `tool.call` → `agent.delegate`. When the `otel` backend is not configured
+//! the global tracer is a no-op, so these helpers are cheap everywhere else
+//! while an incoming `traceparent` is still propagated to responses.
+
+use crate::config::schema::ModelPricing;
+use opentelemetry::context::FutureExt;
+use opentelemetry::trace::{
+    SpanContext, SpanId, SpanKind, Status, TraceContextExt, TraceFlags, TraceId, TraceState, Tracer,
+};
+use opentelemetry::{global, Context, KeyValue};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{LazyLock, RwLock};
+use tracing::Instrument;
+
+/// W3C trace context request/response header.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+/// Response header carrying the bare trace id for log correlation.
+pub const TRACE_ID_HEADER: &str = "x-trace-id";
+
+const TRACER_NAME: &str = "zeroclaw";
+
+static MODEL_PRICING: LazyLock<RwLock<HashMap<String, ModelPricing>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Install the `[cost.prices]` table used to price `llm.call` spans.
+#[allow(clippy::implicit_hasher)]
+pub fn set_model_pricing(prices: &HashMap<String, ModelPricing>) {
+    if let Ok(mut table) = MODEL_PRICING.write() {
+        table.clone_from(prices);
+    }
+}
+
+/// Estimated USD cost of a call, looked up by `provider/model` then `model`.
+pub fn estimate_cost_usd(
+    provider: &str,
+    model: &str,
+    input_tokens: u64,
+    output_tokens: u64,
+) -> Option<f64> {
+    let table = MODEL_PRICING.read().ok()?;
+    let pricing = table
+        .get(&format!("{provider}/{model}"))
+        .or_else(|| table.get(model))?;
+    #[allow(clippy::cast_precision_loss)]
+    let cost =
+        (input_tokens as f64 * pricing.input + output_tokens as f64 * pricing.output) / 1_000_000.0;
+    Some(cost)
+}
+
+/// Start a span as a child of the current context and return a context
+/// holding it. Run work under it with [`in_span`] and close it with
+/// [`end_span`].
+pub fn start_span(name: &'static str, kind: SpanKind, attributes: Vec<KeyValue>) -> Context {
+    start_span_with_parent(&Context::current(), name, kind, attributes)
+}
+
+/// Start a span under an explicit parent (e.g. a remote `traceparent`).
+pub fn start_span_with_parent(
+    parent: &Context,
+    name: &'static str,
+    kind: SpanKind,
+    attributes: Vec<KeyValue>,
+) -> Context {
+    let tracer = global::tracer(TRACER_NAME);
+    let span = tracer
+        .span_builder(name)
+        .with_kind(kind)
+        .with_attributes(attributes)
+        .start_with_context(&tracer, parent);
+    parent.with_span(span)
+}
+
+/// Run `fut` with `cx` as the current OTel context.
+pub fn in_span<F: Future>(cx: Context, fut: F) -> impl Future<Output = F::Output> {
+    fut.with_context(cx)
+}
+
+/// Like [`in_span`] for root spans: also opens a `tracing` span carrying the
+/// trace id so every log line emitted by the work can be correlated.
+pub fn in_root_span<F: Future>(cx: Context, fut: F) -> impl Future<Output = F::Output> {
+    let log_span = match trace_id(&cx) {
+        Some(id) => tracing::info_span!("trace", trace_id = %id),
+        None => tracing::Span::none(),
+    };
+    fut.with_context(cx).instrument(log_span)
+}
+
+/// Add attributes to the span held by `cx`.
+pub fn set_attributes(cx: &Context, attributes: Vec<KeyValue>) {
+    cx.span().set_attributes(attributes);
+}
+
+/// Set the span status from an outcome and end it.
+pub fn end_span(cx: &Context, outcome: Result<(), String>) {
+    let span = cx.span();
+    match outcome {
+        Ok(()) => span.set_status(Status::Ok),
+        Err(message) => span.set_status(Status::error(message)),
+    }
+    span.end();
+}
+
+/// Trace id of the span held by `cx`, if it is valid.
+pub fn trace_id(cx: &Context) -> Option<String> {
+    let span_context = cx.span().span_context().clone();
+    span_context
+        .is_valid()
+        .then(|| span_context.trace_id().to_string())
+}
+
+/// Trace id of the current context, if any.
+pub fn current_trace_id() -> Option<String> {
+    trace_id(&Context::current())
+}
+
+/// W3C `traceparent` value for the span held by `cx`.
+pub fn traceparent(cx: &Context) -> Option<String> {
+    let span_context = cx.span().span_context().clone();
+    span_context.is_valid().then(|| {
+        format!(
+            "00-{}-{}-{:02x}",
+            span_context.trace_id(),
+            span_context.span_id(),
+            span_context.trace_flags().to_u8()
+        )
+    })
+}
+
+/// Parse a W3C `traceparent` header into a remote parent context.
+pub fn parent_from_traceparent(value: &str) -> Option<Context> {
+    let mut parts = value.trim().split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let span_id = parts.next()?;
+    let flags = parts.next()?;
+    if version.len() != 2
+        || version == "ff"
+        || trace_id.len() != 32
+        || span_id.len() != 16
+        || flags.len() != 2
+        || (version == "00" && parts.next().is_some())
+    {
+        return None;
+    }
+
+    let span_context = SpanContext::new(
+        TraceId::from_hex(trace_id).ok()?,
+        SpanId::from_hex(span_id).ok()?,
+        TraceFlags::new(u8::from_str_radix(flags, 16).ok()?),
+        true,
+        TraceState::default(),
+    );
+    span_context
+        .is_valid()
+        .then(|| Context::new().with_remote_span_context(span_context))
+}
+
+/// Token and cost totals accumulated over one agent turn.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TurnUsage {
+    pub llm_calls: u64,
+    pub tool_calls: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: Option<f64>,
+}
+
+impl TurnUsage {
+    /// Record one LLM call on its span and fold it into the turn totals.
+    ///
+    /// Providers do not report usage, so token counts are estimates and the
+    /// span is flagged with `zeroclaw.usage.estimated`.
+    pub fn record_llm_call(
+        &mut self,
+        cx: &Context,
+        provider: &str,
+        model: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+    ) {
+        self.llm_calls += 1;
+        self.input_tokens += input_tokens;
+        self.output_tokens += output_tokens;
+
+        let mut attributes = usage_attributes(input_tokens, output_tokens);
+        if let Some(cost) = estimate_cost_usd(provider, model, input_tokens, output_tokens) {
+            self.cost_usd = Some(self.cost_usd.unwrap_or(0.0) + cost);
+            attributes.push(KeyValue::new("zeroclaw.cost_usd", cost));
+        }
+        set_attributes(cx, attributes);
+    }
+
+    /// Attributes summarising the turn, for the `agent.turn` span.
+    pub fn attributes(&self) -> Vec<KeyValue> {
+        let mut attributes = usage_attributes(self.input_tokens, self.output_tokens);
+        attributes.push(KeyValue::new("zeroclaw.llm_calls", to_i64(self.llm_calls)));
+        attributes.push(KeyValue::new(
+            "zeroclaw.tool_calls",
+            to_i64(self.tool_calls),
+        ));
+        if let Some(cost) = self.cost_usd {
+            attributes.push(KeyValue::new("zeroclaw.cost_usd", cost));
+        }
+        attributes
+    }
+}
+
+/// Estimated token usage attributes following the GenAI semantic conventions.
+pub fn usage_attributes(input_tokens: u64, output_tokens: u64) -> Vec<KeyValue> {
+    vec![
+        KeyValue::new("gen_ai.usage.input_tokens", to_i64(input_tokens)),
+        KeyValue::new("gen_ai.usage.output_tokens", to_i64(output_tokens)),
+        KeyValue::new("zeroclaw.usage.estimated", true),
+    ]
+}
+
+fn to_i64(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn traceparent_round_trips_remote_parent() {
+        let cx = parent_from_traceparent(PARENT).expect("valid traceparent");
+        assert_eq!(
+            trace_id(&cx).as_deref(),
+            Some("4bf92f3577b34da6a3ce929d0e0e4736")
+        );
+        assert_eq!(traceparent(&cx).as_deref(), Some(PARENT));
+    }
+
+    #[test]
+    fn traceparent_rejects_malformed_values() {
+        for value in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "00-xyz92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        ] {
+            assert!(parent_from_traceparent(value).is_none(), "{value}");
+        }
+    }
+
+    #[tokio::test]
+    async fn child_spans_inherit_remote_trace_id() {
+        let parent = parent_from_traceparent(PARENT).unwrap();
+        let turn = start_span_with_parent(&parent, "agent.turn", SpanKind::Internal, vec![]);
+        let seen = in_span(turn.clone(), async {
+            let llm = start_span("llm.call", SpanKind::Client, vec![]);
+            let id = trace_id(&llm);
+            end_span(&llm, Ok(()));
+            (id, current_trace_id())
+        })
+        .await;
+        end_span(&turn, Ok(()));
+
+        assert_eq!(seen.0.as_deref(), Some("4bf92f3577b34da6a3ce929d0e0e4736"));
+        assert_eq!(seen.1, seen.0);
+    }
+
+    #[test]
+    fn turn_usage_accumulates_estimates_and_cost() {
+        let mut prices = HashMap::new();
+        prices.insert(
+            "acme/model-x".to_string(),
+            ModelPricing {
+                input: 2.0,
+                output: 10.0,
+            },
+        );
+        set_model_pricing(&prices);
+
+        let cx = Context::new();
+        let mut usage = TurnUsage::default();
+        usage.record_llm_call(&cx, "acme", "model-x", 1_000, 100);
+        usage.record_llm_call(&cx, "acme", "model-x", 2_000, 200);
+        usage.record_llm_call(&cx, "other", "unpriced-model", 10, 10);
+
+        assert_eq!(usage.llm_calls, 3);
+        assert_eq!(usage.input_tokens, 3_010);
+        assert_eq!(usage.output_tokens, 310);
+        let cost = usage.cost_usd.expect("priced calls");
+        assert!((cost - 0.009).abs() < 1e-9, "{cost}");
+        assert!(estimate_cost_usd("other", "unpriced-model", 1, 1).is_none());
+    }
+}
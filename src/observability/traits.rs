@@ -16,6 +16,14 @@ pub enum ObserverEvent {
         model: String,
         messages_count: usize,
     },
+    /// Estimated prompt size relative to the model's context window, emitted
+    /// alongside each `LlmRequest` when context budgeting is active.
+    ContextUsage {
+        provider: String,
+        model: String,
+        estimated_tokens: usize,
+        window_tokens: usize,
+    },
     /// Result of a single LLM provider call.
     LlmResponse {
         provider: String,
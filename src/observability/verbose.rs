@@ -27,6 +27,13 @@ impl Observer for VerboseObserver {
                     provider, model, messages_count
                 );
             }
+            ObserverEvent::ContextUsage {
+                estimated_tokens,
+                window_tokens,
+                ..
+            } => {
+                eprintln!("> Context (~{estimated_tokens}/{window_tokens} tokens)");
+            }
             ObserverEvent::LlmResponse {
                 duration, success, ..
             } => {
@@ -2,7 +2,8 @@ use crate::config::schema::{DingTalkConfig, IrcConfig, QQConfig, StreamMode, Wha
 use crate::config::{
     AutonomyConfig, BrowserConfig, ChannelsConfig, ComposioConfig, Config, DiscordConfig,
     HeartbeatConfig, IMessageConfig, MatrixConfig, MemoryConfig, ObservabilityConfig,
-    RuntimeConfig, SecretsConfig, SlackConfig, StorageConfig, TelegramConfig, WebhookConfig,
+    ObsidianConfig, RuntimeConfig, SecretsConfig, SlackConfig, StorageConfig, TelegramConfig,
+    WebhookConfig,
 };
 use crate::hardware::{self, HardwareConfig};
 use crate::memory::{
@@ -17,7 +18,7 @@ use console::style;
 use dialoguer::{Confirm, Input, Select};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -133,6 +134,8 @@ pub fn run_wizard() -> Result<Config> {
         secrets: secrets_config,
         browser: BrowserConfig::default(),
         http_request: crate::config::HttpRequestConfig::default(),
+        home_assistant: crate::config::HomeAssistantConfig::default(),
+        mqtt: crate::config::MqttConfig::default(),
         web_search: crate::config::WebSearchConfig::default(),
         proxy: crate::config::ProxyConfig::default(),
         identity: crate::config::IdentityConfig::default(),
@@ -140,6 +143,9 @@ pub fn run_wizard() -> Result<Config> {
         peripherals: crate::config::PeripheralsConfig::default(),
         agents: std::collections::HashMap::new(),
         hardware: hardware_config,
+        tts: crate::config::TtsConfig::default(),
+        security: crate::config::SecurityConfig::default(),
+        transcription: crate::config::TranscriptionConfig::default(),
         query_classification: crate::config::QueryClassificationConfig::default(),
     };
 
@@ -295,6 +301,7 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         snapshot_on_hygiene: false,
         auto_hydrate: true,
         sqlite_open_timeout_secs: None,
+        obsidian: ObsidianConfig::default(),
     }
 }
 
@@ -358,6 +365,8 @@ pub fn run_quick_setup(
         secrets: SecretsConfig::default(),
         browser: BrowserConfig::default(),
         http_request: crate::config::HttpRequestConfig::default(),
+        home_assistant: crate::config::HomeAssistantConfig::default(),
+        mqtt: crate::config::MqttConfig::default(),
         web_search: crate::config::WebSearchConfig::default(),
         proxy: crate::config::ProxyConfig::default(),
         identity: crate::config::IdentityConfig::default(),
@@ -365,6 +374,9 @@ pub fn run_quick_setup(
         peripherals: crate::config::PeripheralsConfig::default(),
         agents: std::collections::HashMap::new(),
         hardware: crate::config::HardwareConfig::default(),
+        tts: crate::config::TtsConfig::default(),
+        security: crate::config::SecurityConfig::default(),
+        transcription: crate::config::TranscriptionConfig::default(),
         query_classification: crate::config::QueryClassificationConfig::default(),
     };
 
@@ -976,6 +988,42 @@ fn parse_gemini_model_ids(payload: &Value) -> Vec<String> {
     normalize_model_ids(ids)
 }
 
+/// Extract per-model context windows from a catalog payload. Understands
+/// OpenRouter/OpenAI-compatible `context_length`/`context_window`, Anthropic
+/// `max_input_tokens` and Gemini `inputTokenLimit` fields.
+fn parse_model_context_windows(payload: &Value) -> BTreeMap<String, usize> {
+    let models = payload
+        .get("data")
+        .or_else(|| payload.get("models"))
+        .unwrap_or(payload)
+        .as_array();
+
+    let mut windows = BTreeMap::new();
+    for model in models.into_iter().flatten() {
+        let id = model
+            .get("id")
+            .or_else(|| model.get("name"))
+            .and_then(Value::as_str)
+            .map(|id| id.trim().trim_start_matches("models/"));
+        let window = [
+            "context_length",
+            "context_window",
+            "max_input_tokens",
+            "inputTokenLimit",
+        ]
+        .iter()
+        .find_map(|key| model.get(*key).and_then(Value::as_u64))
+        .and_then(|w| usize::try_from(w).ok())
+        .filter(|w| *w > 0);
+        if let (Some(id), Some(window)) = (id, window) {
+            if !id.is_empty() {
+                windows.insert(id.to_string(), window);
+            }
+        }
+    }
+    windows
+}
+
 fn parse_ollama_model_ids(payload: &Value) -> Vec<String> {
     let Some(models) = payload.get("models").and_then(Value::as_array) else {
         return Vec::new();
@@ -991,11 +1039,34 @@ fn parse_ollama_model_ids(payload: &Value) -> Vec<String> {
     normalize_model_ids(ids)
 }
 
+/// Model ids from a live catalog fetch, plus any context windows it reported.
+#[derive(Debug, Default)]
+struct LiveModels {
+    ids: Vec<String>,
+    context_windows: BTreeMap<String, usize>,
+}
+
+impl LiveModels {
+    fn from_ids(ids: Vec<String>) -> Self {
+        Self {
+            ids,
+            context_windows: BTreeMap::new(),
+        }
+    }
+
+    fn from_payload(ids: Vec<String>, payload: &Value) -> Self {
+        Self {
+            ids,
+            context_windows: parse_model_context_windows(payload),
+        }
+    }
+}
+
 fn fetch_openai_compatible_models(
     endpoint: &str,
     api_key: Option<&str>,
     allow_unauthenticated: bool,
-) -> Result<Vec<String>> {
+) -> Result<LiveModels> {
     let client = build_model_fetch_client()?;
     let mut request = client.get(endpoint);
 
@@ -1012,10 +1083,13 @@ fn fetch_openai_compatible_models(
         .json()
         .context("failed to parse model list response")?;
 
-    Ok(parse_openai_compatible_model_ids(&payload))
+    Ok(LiveModels::from_payload(
+        parse_openai_compatible_model_ids(&payload),
+        &payload,
+    ))
 }
 
-fn fetch_openrouter_models(api_key: Option<&str>) -> Result<Vec<String>> {
+fn fetch_openrouter_models(api_key: Option<&str>) -> Result<LiveModels> {
     let client = build_model_fetch_client()?;
     let mut request = client.get("https://openrouter.ai/api/v1/models");
     if let Some(api_key) = api_key {
@@ -1029,10 +1103,13 @@ fn fetch_openrouter_models(api_key: Option<&str>) -> Result<Vec<String>> {
         .json()
         .context("failed to parse OpenRouter model list response")?;
 
-    Ok(parse_openai_compatible_model_ids(&payload))
+    Ok(LiveModels::from_payload(
+        parse_openai_compatible_model_ids(&payload),
+        &payload,
+    ))
 }
 
-fn fetch_anthropic_models(api_key: Option<&str>) -> Result<Vec<String>> {
+fn fetch_anthropic_models(api_key: Option<&str>) -> Result<LiveModels> {
     let Some(api_key) = api_key else {
         bail!("Anthropic model fetch requires API key or OAuth token");
     };
@@ -1064,10 +1141,13 @@ fn fetch_anthropic_models(api_key: Option<&str>) -> Result<Vec<String>> {
         .json()
         .context("failed to parse Anthropic model list response")?;
 
-    Ok(parse_openai_compatible_model_ids(&payload))
+    Ok(LiveModels::from_payload(
+        parse_openai_compatible_model_ids(&payload),
+        &payload,
+    ))
 }
 
-fn fetch_gemini_models(api_key: Option<&str>) -> Result<Vec<String>> {
+fn fetch_gemini_models(api_key: Option<&str>) -> Result<LiveModels> {
     let Some(api_key) = api_key else {
         bail!("Gemini model fetch requires API key");
     };
@@ -1082,7 +1162,10 @@ fn fetch_gemini_models(api_key: Option<&str>) -> Result<Vec<String>> {
         .json()
         .context("failed to parse Gemini model list response")?;
 
-    Ok(parse_gemini_model_ids(&payload))
+    Ok(LiveModels::from_payload(
+        parse_gemini_model_ids(&payload),
+        &payload,
+    ))
 }
 
 fn fetch_ollama_models() -> Result<Vec<String>> {
@@ -1098,7 +1181,7 @@ fn fetch_ollama_models() -> Result<Vec<String>> {
     Ok(parse_ollama_model_ids(&payload))
 }
 
-fn fetch_live_models_for_provider(provider_name: &str, api_key: &str) -> Result<Vec<String>> {
+fn fetch_live_models_for_provider(provider_name: &str, api_key: &str) -> Result<LiveModels> {
     let requested_provider_name = provider_name;
     let provider_name = canonical_provider_name(provider_name);
     let api_key = if api_key.trim().is_empty() {
@@ -1127,10 +1210,10 @@ fn fetch_live_models_for_provider(provider_name: &str, api_key: &str) -> Result<
         "ollama" => {
             if api_key.as_deref().map_or(true, |k| k.trim().is_empty()) {
                 // Key is None or empty, assume local Ollama
-                fetch_ollama_models()?
+                LiveModels::from_ids(fetch_ollama_models()?)
             } else {
                 // Key is present, assume Ollama Cloud and return hardcoded list
-                vec![
+                LiveModels::from_ids(vec![
                     "glm-5:cloud".to_string(),
                     "glm-4.7:cloud".to_string(),
                     "gpt-oss:cloud".to_string(),
@@ -1140,7 +1223,7 @@ fn fetch_live_models_for_provider(provider_name: &str, api_key: &str) -> Result<
                     "qwen2.5:cloud".to_string(),
                     "minimax-m2.5:cloud".to_string(),
                     "deepseek-v3.1:cloud".to_string(),
-                ]
+                ])
             }
         }
         _ => {
@@ -1149,7 +1232,7 @@ fn fetch_live_models_for_provider(provider_name: &str, api_key: &str) -> Result<
                     allows_unauthenticated_model_fetch(requested_provider_name);
                 fetch_openai_compatible_models(endpoint, api_key.as_deref(), allow_unauthenticated)?
             } else {
-                Vec::new()
+                LiveModels::default()
             }
         }
     };
@@ -1162,6 +1245,10 @@ struct ModelCacheEntry {
     provider: String,
     fetched_at_unix: u64,
     models: Vec<String>,
+    /// Context windows (tokens) reported by the catalog, read by the agent's
+    /// context budgeting.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    context_windows: BTreeMap<String, usize>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -1221,9 +1308,9 @@ fn save_model_cache_state(workspace_dir: &Path, state: &ModelCacheState) -> Resu
 fn cache_live_models_for_provider(
     workspace_dir: &Path,
     provider_name: &str,
-    models: &[String],
+    live: &LiveModels,
 ) -> Result<()> {
-    let normalized_models = normalize_model_ids(models.to_vec());
+    let normalized_models = normalize_model_ids(live.ids.clone());
     if normalized_models.is_empty() {
         return Ok(());
     }
@@ -1238,11 +1325,13 @@ fn cache_live_models_for_provider(
     {
         entry.fetched_at_unix = now;
         entry.models = normalized_models;
+        entry.context_windows.clone_from(&live.context_windows);
     } else {
         state.entries.push(ModelCacheEntry {
             provider: provider_name.to_string(),
             fetched_at_unix: now,
             models: normalized_models,
+            context_windows: live.context_windows.clone(),
         });
     }
 
@@ -1372,14 +1461,14 @@ pub fn run_models_refresh(
     let api_key = config.api_key.clone().unwrap_or_default();
 
     match fetch_live_models_for_provider(&provider_name, &api_key) {
-        Ok(models) if !models.is_empty() => {
-            cache_live_models_for_provider(&config.workspace_dir, &provider_name, &models)?;
+        Ok(live) if !live.ids.is_empty() => {
+            cache_live_models_for_provider(&config.workspace_dir, &provider_name, &live)?;
             println!(
                 "Refreshed '{}' model cache with {} models.",
                 provider_name,
-                models.len()
+                live.ids.len()
             );
-            print_model_preview(&models);
+            print_model_preview(&live.ids);
             Ok(())
         }
         Ok(_) => {
@@ -1867,19 +1956,13 @@ fn setup_provider(workspace_dir: &Path) -> Result<(String, String, String, Optio
 
             if should_fetch_now {
                 match fetch_live_models_for_provider(provider_name, &api_key) {
-                    Ok(live_model_ids) if !live_model_ids.is_empty() => {
-                        cache_live_models_for_provider(
-                            workspace_dir,
-                            provider_name,
-                            &live_model_ids,
-                        )?;
-
-                        let fetched_count = live_model_ids.len();
+                    Ok(live) if !live.ids.is_empty() => {
+                        cache_live_models_for_provider(workspace_dir, provider_name, &live)?;
+
+                        let fetched_count = live.ids.len();
                         let shown_count = fetched_count.min(LIVE_MODEL_MAX_OPTIONS);
-                        let shown_models: Vec<String> = live_model_ids
-                            .into_iter()
-                            .take(LIVE_MODEL_MAX_OPTIONS)
-                            .collect();
+                        let shown_models: Vec<String> =
+                            live.ids.into_iter().take(LIVE_MODEL_MAX_OPTIONS).collect();
 
                         if shown_count < fetched_count {
                             print_bullet(&format!(
@@ -2462,6 +2545,9 @@ fn setup_channels() -> Result<ChannelsConfig> {
         lark: None,
         dingtalk: None,
         qq: None,
+        webchat: None,
+        nextcloud_talk: None,
+        mqtt: None,
     };
 
     loop {
@@ -2750,6 +2836,9 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     allowed_users,
                     listen_to_bots: false,
                     mention_only: false,
+                    slash_commands: true,
+                    command_permissions: None,
+                    approval_timeout_secs: 120,
                 });
             }
             2 => {
@@ -2875,6 +2964,9 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         Some(channel)
                     },
                     allowed_users,
+                    thread_replies: None,
+                    stream_mode: StreamMode::default(),
+                    draft_update_interval_ms: 1000,
                 });
             }
             3 => {
@@ -3031,6 +3123,12 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     device_id: detected_device_id,
                     room_id,
                     allowed_users,
+                    thread_replies: false,
+                    stream_mode: StreamMode::default(),
+                    draft_update_interval_ms: 1000,
+                    ack_reaction: "👀".into(),
+                    max_media_bytes: 20 * 1024 * 1024,
+                    approval_timeout_secs: 120,
                 });
             }
             5 => {
@@ -3292,6 +3390,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     } else {
                         Some(secret)
                     },
+                    hooks: Vec::new(),
                 });
                 println!(
                     "  {} Webhook on port {}",
@@ -3809,12 +3908,27 @@ fn scaffold_workspace(workspace_dir: &Path, ctx: &ProjectContext) -> Result<()>
          - **shell** — Execute terminal commands\n\
            - Use when: running local checks, build/test commands, or diagnostics.\n\
            - Don't use when: a safer dedicated tool exists, or command is destructive without approval.\n\
+         - **process** — Manage background processes started with `shell` (`background: true`)\n\
+           - Use when: running dev servers, long builds, or REPLs; poll output, write stdin, or kill them.\n\
+           - Don't use when: the command finishes quickly; run it in the foreground instead.\n\
          - **file_read** — Read file contents\n\
            - Use when: inspecting project files, configs, or logs.\n\
            - Don't use when: you only need a quick string search (prefer targeted search first).\n\
          - **file_write** — Write file contents\n\
            - Use when: applying focused edits, scaffolding files, or updating docs/code.\n\
            - Don't use when: unsure about side effects or when the file should remain user-owned.\n\
+         - **file_edit** — Edit part of an existing file\n\
+           - Use when: changing specific lines via exact string replacement or a unified diff.\n\
+           - Don't use when: creating a new file or replacing its entire contents.\n\
+         - **glob** — Find files by glob pattern\n\
+           - Use when: locating files by name or extension (respects .gitignore).\n\
+           - Don't use when: you already know the exact path.\n\
+         - **content_search** — Regex search across file contents\n\
+           - Use when: finding definitions, usages, or strings, with optional context lines.\n\
+           - Don't use when: you need the whole file; use file_read instead.\n\
+         - **list_dir** — List directory contents\n\
+           - Use when: exploring project layout, optionally as a tree.\n\
+           - Don't use when: searching for a specific file; use glob instead.\n\
          - **memory_store** — Save to memory\n\
            - Use when: preserving durable preferences, decisions, or key context.\n\
            - Don't use when: info is transient, noisy, or sensitive without explicit need.\n\
@@ -4510,6 +4624,10 @@ mod tests {
             "shell",
             "file_read",
             "file_write",
+            "file_edit",
+            "glob",
+            "content_search",
+            "list_dir",
             "memory_store",
             "memory_recall",
             "memory_forget",
@@ -4928,12 +5046,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_model_context_windows_reads_catalog_fields() {
+        let openrouter = json!({
+            "data": [
+                {"id": "anthropic/claude-sonnet-4", "context_length": 1_000_000},
+                {"id": "no-window"}
+            ]
+        });
+        let windows = parse_model_context_windows(&openrouter);
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows["anthropic/claude-sonnet-4"], 1_000_000);
+
+        let gemini = json!({
+            "models": [
+                {"name": "models/gemini-2.5-pro", "inputTokenLimit": 1_048_576}
+            ]
+        });
+        assert_eq!(
+            parse_model_context_windows(&gemini)["gemini-2.5-pro"],
+            1_048_576
+        );
+    }
+
     #[test]
     fn model_cache_round_trip_returns_fresh_entry() {
         let tmp = TempDir::new().unwrap();
         let models = vec!["gpt-5.1".to_string(), "gpt-5-mini".to_string()];
 
-        cache_live_models_for_provider(tmp.path(), "openai", &models).unwrap();
+        cache_live_models_for_provider(tmp.path(), "openai", &LiveModels::from_ids(models))
+            .unwrap();
 
         let cached =
             load_cached_models_for_provider(tmp.path(), "openai", MODEL_CACHE_TTL_SECS).unwrap();
@@ -4952,6 +5094,7 @@ mod tests {
                 provider: "openai".to_string(),
                 fetched_at_unix: now_unix_secs().saturating_sub(MODEL_CACHE_TTL_SECS + 120),
                 models: vec!["gpt-5.1".to_string()],
+                context_windows: BTreeMap::new(),
             }],
         };
 
@@ -4969,7 +5112,12 @@ mod tests {
     fn run_models_refresh_uses_fresh_cache_without_network() {
         let tmp = TempDir::new().unwrap();
 
-        cache_live_models_for_provider(tmp.path(), "openai", &["gpt-5.1".to_string()]).unwrap();
+        cache_live_models_for_provider(
+            tmp.path(),
+            "openai",
+            &LiveModels::from_ids(vec!["gpt-5.1".to_string()]),
+        )
+        .unwrap();
 
         let config = Config {
             workspace_dir: tmp.path().to_path_buf(),
@@ -0,0 +1,420 @@
+//! Tools for protocol v2 features — ADC, PWM, I2C, SPI and UART passthrough.
+//!
+//! Generated per board from the features it advertises in `capabilities`
+//! (see [`super::protocol`]). Every call is checked against the board's
+//! advertised resources and configured allowlists before it goes out.
+
+use super::protocol::{byte_array, transfer_len, BoardAccess, Feature, MAX_TRANSFER_BYTES};
+use super::serial::{rejected, SerialTransport};
+use crate::tools::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Tools that implement `feature` on one board.
+pub(crate) fn feature_tools(
+    feature: Feature,
+    transport: &Arc<SerialTransport>,
+    access: &Arc<BoardAccess>,
+) -> Vec<Box<dyn Tool>> {
+    let board = || BoardHandle {
+        transport: transport.clone(),
+        access: access.clone(),
+    };
+    match feature {
+        Feature::Adc => vec![Box::new(AdcReadTool(board()))],
+        Feature::Pwm => vec![Box::new(PwmSetTool(board()))],
+        Feature::I2c => vec![
+            Box::new(I2cScanTool(board())),
+            Box::new(I2cReadTool(board())),
+            Box::new(I2cWriteTool(board())),
+        ],
+        Feature::Spi => vec![Box::new(SpiTransferTool(board()))],
+        Feature::Uart => vec![Box::new(UartTransferTool(board()))],
+    }
+}
+
+/// Transport plus access rules shared by a board's tools.
+struct BoardHandle {
+    transport: Arc<SerialTransport>,
+    access: Arc<BoardAccess>,
+}
+
+impl BoardHandle {
+    /// Send a command, turning validation errors into a failed tool result.
+    async fn run(
+        &self,
+        cmd: &str,
+        build_args: impl FnOnce(&BoardAccess) -> anyhow::Result<Value>,
+    ) -> anyhow::Result<ToolResult> {
+        match build_args(&self.access) {
+            Ok(args) => self.transport.request(cmd, args).await,
+            Err(e) => Ok(rejected(&e)),
+        }
+    }
+}
+
+/// Tool: read an analog input.
+struct AdcReadTool(BoardHandle);
+
+#[async_trait]
+impl Tool for AdcReadTool {
+    fn name(&self) -> &str {
+        "adc_read"
+    }
+
+    fn description(&self) -> &str {
+        "Read the raw value of an analog (ADC) input pin on a connected peripheral"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pin": {
+                    "type": "integer",
+                    "description": "ADC-capable pin number (see hardware_capabilities)"
+                }
+            },
+            "required": ["pin"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        self.0
+            .run("adc_read", |access| {
+                let pin = access.feature_pin(Feature::Adc, &args)?;
+                Ok(json!({ "pin": pin }))
+            })
+            .await
+    }
+}
+
+/// Tool: set a PWM output.
+struct PwmSetTool(BoardHandle);
+
+#[async_trait]
+impl Tool for PwmSetTool {
+    fn name(&self) -> &str {
+        "pwm_set"
+    }
+
+    fn description(&self) -> &str {
+        "Set the PWM duty cycle (0-100%) and optional frequency of a pin on a connected peripheral (e.g. dim an LED, drive a servo)"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pin": {
+                    "type": "integer",
+                    "description": "PWM-capable pin number"
+                },
+                "duty": {
+                    "type": "number",
+                    "description": "Duty cycle in percent, 0 to 100"
+                },
+                "frequency_hz": {
+                    "type": "integer",
+                    "description": "Optional PWM frequency in Hz (board default when omitted)"
+                }
+            },
+            "required": ["pin", "duty"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        self.0
+            .run("pwm_set", |access| {
+                let pin = access.feature_pin(Feature::Pwm, &args)?;
+                let duty = args
+                    .get("duty")
+                    .and_then(Value::as_f64)
+                    .filter(|d| (0.0..=100.0).contains(d))
+                    .ok_or_else(|| anyhow::anyhow!("'duty' must be a number from 0 to 100"))?;
+                let mut request = json!({ "pin": pin, "duty": duty });
+                if let Some(freq) = args.get("frequency_hz").and_then(Value::as_u64) {
+                    request["frequency_hz"] = json!(freq);
+                }
+                Ok(request)
+            })
+            .await
+    }
+}
+
+/// Tool: list devices that acknowledge on an I2C bus.
+struct I2cScanTool(BoardHandle);
+
+#[async_trait]
+impl Tool for I2cScanTool {
+    fn name(&self) -> &str {
+        "i2c_scan"
+    }
+
+    fn description(&self) -> &str {
+        "Scan an I2C bus on a connected peripheral and list the 7-bit addresses of devices that respond"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "bus": {
+                    "type": "integer",
+                    "description": "I2C bus index (default 0)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        self.0
+            .run("i2c_scan", |access| {
+                let bus = access.bus(Feature::I2c, &args)?;
+                Ok(json!({ "bus": bus }))
+            })
+            .await
+    }
+}
+
+/// Tool: read bytes from an I2C device, optionally from a register.
+struct I2cReadTool(BoardHandle);
+
+#[async_trait]
+impl Tool for I2cReadTool {
+    fn name(&self) -> &str {
+        "i2c_read"
+    }
+
+    fn description(&self) -> &str {
+        "Read bytes from an I2C device on a connected peripheral, optionally starting at a register (write-then-read)"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "bus": {
+                    "type": "integer",
+                    "description": "I2C bus index (default 0)"
+                },
+                "address": {
+                    "type": "integer",
+                    "description": "7-bit device address (e.g. 60 for 0x3C)"
+                },
+                "register": {
+                    "type": "integer",
+                    "description": "Optional register to read from"
+                },
+                "len": {
+                    "type": "integer",
+                    "description": format!("Number of bytes to read (1-{MAX_TRANSFER_BYTES}, default 1)")
+                }
+            },
+            "required": ["address"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        self.0
+            .run("i2c_read", |access| {
+                let bus = access.bus(Feature::I2c, &args)?;
+                let address = access.i2c_address(&args)?;
+                let len = transfer_len(&args, "len", 1)?;
+                let mut request = json!({ "bus": bus, "address": address, "len": len });
+                if let Some(register) = optional_byte(&args, "register")? {
+                    request["register"] = json!(register);
+                }
+                Ok(request)
+            })
+            .await
+    }
+}
+
+/// Tool: write bytes to an I2C device, optionally to a register.
+struct I2cWriteTool(BoardHandle);
+
+#[async_trait]
+impl Tool for I2cWriteTool {
+    fn name(&self) -> &str {
+        "i2c_write"
+    }
+
+    fn description(&self) -> &str {
+        "Write bytes to an I2C device on a connected peripheral, optionally prefixed by a register"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "bus": {
+                    "type": "integer",
+                    "description": "I2C bus index (default 0)"
+                },
+                "address": {
+                    "type": "integer",
+                    "description": "7-bit device address"
+                },
+                "register": {
+                    "type": "integer",
+                    "description": "Optional register written before the data"
+                },
+                "data": {
+                    "type": "array",
+                    "items": { "type": "integer" },
+                    "description": "Bytes to write (0-255 each)"
+                }
+            },
+            "required": ["address", "data"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        self.0
+            .run("i2c_write", |access| {
+                let bus = access.bus(Feature::I2c, &args)?;
+                let address = access.i2c_address(&args)?;
+                let data = byte_array(&args, "data")?;
+                let mut request = json!({ "bus": bus, "address": address, "data": data });
+                if let Some(register) = optional_byte(&args, "register")? {
+                    request["register"] = json!(register);
+                }
+                Ok(request)
+            })
+            .await
+    }
+}
+
+/// Tool: full-duplex SPI transfer.
+struct SpiTransferTool(BoardHandle);
+
+#[async_trait]
+impl Tool for SpiTransferTool {
+    fn name(&self) -> &str {
+        "spi_transfer"
+    }
+
+    fn description(&self) -> &str {
+        "Clock bytes out on an SPI bus of a connected peripheral and return the bytes read back (full duplex)"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "bus": {
+                    "type": "integer",
+                    "description": "SPI bus index (default 0)"
+                },
+                "cs_pin": {
+                    "type": "integer",
+                    "description": "Optional chip-select GPIO pin (board default when omitted)"
+                },
+                "data": {
+                    "type": "array",
+                    "items": { "type": "integer" },
+                    "description": "Bytes to send (0-255 each); as many bytes are read back"
+                }
+            },
+            "required": ["data"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        self.0
+            .run("spi_transfer", |access| {
+                let bus = access.bus(Feature::Spi, &args)?;
+                let data = byte_array(&args, "data")?;
+                let mut request = json!({ "bus": bus, "data": data });
+                if args.get("cs_pin").is_some_and(|v| !v.is_null()) {
+                    let cs = access.gpio_pin(&json!({ "pin": args["cs_pin"] }))?;
+                    request["cs_pin"] = json!(cs);
+                }
+                Ok(request)
+            })
+            .await
+    }
+}
+
+/// Tool: UART bridge — write text to a secondary UART and read the reply.
+struct UartTransferTool(BoardHandle);
+
+#[async_trait]
+impl Tool for UartTransferTool {
+    fn name(&self) -> &str {
+        "uart_transfer"
+    }
+
+    fn description(&self) -> &str {
+        "Bridge to a device on a secondary UART of a connected peripheral: send text and return what it answers within the timeout"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "bus": {
+                    "type": "integer",
+                    "description": "UART index (default 0; the host link itself is never exposed)"
+                },
+                "data": {
+                    "type": "string",
+                    "description": "Text to send (may be empty to only read)"
+                },
+                "baud": {
+                    "type": "integer",
+                    "description": "Optional baud rate for the UART"
+                },
+                "read_len": {
+                    "type": "integer",
+                    "description": format!("Maximum bytes to read back (1-{MAX_TRANSFER_BYTES}, default {MAX_TRANSFER_BYTES})")
+                },
+                "timeout_ms": {
+                    "type": "integer",
+                    "description": "How long to wait for the reply (default 500, max 3000)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        self.0
+            .run("uart_transfer", |access| {
+                let bus = access.bus(Feature::Uart, &args)?;
+                let data = args.get("data").and_then(Value::as_str).unwrap_or("");
+                if data.len() > MAX_TRANSFER_BYTES {
+                    anyhow::bail!("'data' is limited to {MAX_TRANSFER_BYTES} bytes per transfer");
+                }
+                let read_len = transfer_len(&args, "read_len", MAX_TRANSFER_BYTES)?;
+                let timeout_ms = args
+                    .get("timeout_ms")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(500)
+                    .min(3000);
+                let mut request = json!({
+                    "bus": bus,
+                    "data": data,
+                    "read_len": read_len,
+                    "timeout_ms": timeout_ms,
+                });
+                if let Some(baud) = args.get("baud").and_then(Value::as_u64) {
+                    request["baud"] = json!(baud);
+                }
+                Ok(request)
+            })
+            .await
+    }
+}
+
+fn optional_byte(args: &Value, key: &str) -> anyhow::Result<Option<u8>> {
+    match args.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .and_then(|v| u8::try_from(v).ok())
+            .map(Some)
+            .ok_or_else(|| anyhow::anyhow!("'{key}' must be a byte (0-255)")),
+    }
+}
@@ -0,0 +1,365 @@
+//! Peripheral event rules — board events that trigger the agent.
+//!
+//! Boards raise [`PeripheralEvent`]s on their own: serial firmware sends
+//! `{"event":...}` lines, RPi GPIO reports edges on `watch_pins`. The daemon's
+//! `peripherals` component matches each event against
+//! `[[peripherals.event_rules]]` and runs the agent, schedules a one-shot
+//! cron job or sends a channel notification. Every rule is debounced and rate
+//! limited on its own, so a bouncing button or a chatty sensor cannot flood
+//! the agent.
+
+use super::traits::PeripheralEvent;
+use crate::config::reload::{take_update, ConfigWatch};
+use crate::config::{Config, PeripheralEventAction, PeripheralEventRule};
+use crate::cron::{self, DeliveryConfig, Schedule, SessionTarget};
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+
+/// Window for `max_per_hour`.
+const RATE_WINDOW: Duration = Duration::from_secs(3600);
+
+/// Events buffered between the board readers and the rule dispatcher.
+const EVENT_QUEUE_CAPACITY: usize = 256;
+
+/// Whether `rule` applies to `event`, ignoring debounce and rate limits.
+pub fn matches(rule: &PeripheralEventRule, event: &PeripheralEvent) -> bool {
+    rule.board.as_deref().is_none_or(|b| b == event.board)
+        && (rule.event == "*" || rule.event == event.event)
+        && rule.pin.is_none_or(|p| event.pin == Some(p))
+}
+
+/// Fill `{board}`, `{event}`, `{pin}` and `{value}` in a rule template.
+pub fn render(template: &str, event: &PeripheralEvent) -> String {
+    let pin = event.pin.map(|p| p.to_string()).unwrap_or_default();
+    let value = match &event.value {
+        Some(Value::String(s)) => s.clone(),
+        Some(value) => value.to_string(),
+        None => String::new(),
+    };
+    template
+        .replace("{board}", &event.board)
+        .replace("{event}", &event.event)
+        .replace("{pin}", &pin)
+        .replace("{value}", &value)
+}
+
+struct RuleState {
+    rule: PeripheralEventRule,
+    last_fired: Option<Instant>,
+    /// Firings within the last [`RATE_WINDOW`], oldest first.
+    fired: VecDeque<Instant>,
+}
+
+impl RuleState {
+    /// Record a firing at `now` unless debounce or the hourly limit say no.
+    fn try_fire(&mut self, now: Instant) -> bool {
+        let debounce = Duration::from_millis(self.rule.debounce_ms);
+        if self
+            .last_fired
+            .is_some_and(|last| now.saturating_duration_since(last) < debounce)
+        {
+            return false;
+        }
+        while self
+            .fired
+            .front()
+            .is_some_and(|t| now.saturating_duration_since(*t) >= RATE_WINDOW)
+        {
+            self.fired.pop_front();
+        }
+        let limit = usize::try_from(self.rule.max_per_hour).unwrap_or(usize::MAX);
+        if limit > 0 && self.fired.len() >= limit {
+            tracing::debug!(rule = %self.rule.name, "Event rule rate limit reached");
+            return false;
+        }
+        self.last_fired = Some(now);
+        self.fired.push_back(now);
+        true
+    }
+}
+
+/// Matches events to rules and applies per-rule debounce and rate limits.
+pub struct EventRouter {
+    rules: Vec<RuleState>,
+}
+
+impl EventRouter {
+    pub fn new(rules: &[PeripheralEventRule]) -> Self {
+        Self {
+            rules: rules
+                .iter()
+                .map(|rule| RuleState {
+                    rule: rule.clone(),
+                    last_fired: None,
+                    fired: VecDeque::new(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Rules that fire for `event` received at `now`.
+    pub fn route(&mut self, event: &PeripheralEvent, now: Instant) -> Vec<PeripheralEventRule> {
+        self.rules
+            .iter_mut()
+            .filter(|state| matches(&state.rule, event))
+            .filter_map(|state| state.try_fire(now).then(|| state.rule.clone()))
+            .collect()
+    }
+}
+
+/// Daemon component: watch every configured board and dispatch matching
+/// rules until the event streams close.
+///
+/// Rule and board changes restart the component; other sections (channels,
+/// model, autonomy) are picked up between events.
+pub async fn run(mut config: Config, mut updates: ConfigWatch) -> Result<()> {
+    let mut router = EventRouter::new(&config.peripherals.event_rules);
+    let receivers = super::subscribe_events(&config.peripherals).await;
+    if receivers.is_empty() {
+        anyhow::bail!("no connected peripheral provides events");
+    }
+    tracing::info!(
+        "Watching {} peripheral event stream(s) for {} rule(s)",
+        receivers.len(),
+        config.peripherals.event_rules.len()
+    );
+
+    let (tx, mut rx) = mpsc::channel(EVENT_QUEUE_CAPACITY);
+    // Dropping the set stops the forwarders with the component.
+    let mut forwarders = tokio::task::JoinSet::new();
+    for receiver in receivers {
+        forwarders.spawn(forward_events(receiver, tx.clone()));
+    }
+    drop(tx);
+
+    while let Some(event) = rx.recv().await {
+        if let Some(next) = take_update(&mut updates) {
+            config = next;
+        }
+        for rule in router.route(&event, Instant::now()) {
+            tracing::info!(
+                rule = %rule.name,
+                board = %event.board,
+                event = %event.event,
+                "Peripheral event rule fired"
+            );
+            let config = config.clone();
+            let event = event.clone();
+            // Agent runs can take a while; keep reading events meanwhile.
+            tokio::spawn(async move {
+                if let Err(e) = fire(&config, &rule, &event).await {
+                    crate::health::mark_component_error("peripherals", e.to_string());
+                    tracing::warn!("Peripheral event rule '{}' failed: {e}", rule.name);
+                }
+            });
+        }
+    }
+    anyhow::bail!("peripheral event streams closed")
+}
+
+async fn forward_events(
+    mut receiver: broadcast::Receiver<PeripheralEvent>,
+    tx: mpsc::Sender<PeripheralEvent>,
+) {
+    loop {
+        match receiver.recv().await {
+            Ok(event) => {
+                if tx.send(event).await.is_err() {
+                    return;
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                tracing::warn!("Dropped {missed} peripheral events (dispatcher too slow)");
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+/// Run the rule's action for `event`.
+async fn fire(config: &Config, rule: &PeripheralEventRule, event: &PeripheralEvent) -> Result<()> {
+    let text = render(&rule.prompt, event);
+    match rule.action {
+        PeripheralEventAction::Agent => {
+            let prompt = format!("[Peripheral Event: {}] {text}", rule.name);
+            let temp = config.default_temperature;
+            let output =
+                crate::agent::run(config.clone(), Some(prompt), None, None, temp, vec![], None)
+                    .await?;
+            if let (Some(channel), Some(to)) = (&rule.channel, &rule.to) {
+                cron::scheduler::deliver_announcement(config, channel, to, &output).await?;
+            }
+        }
+        PeripheralEventAction::Job => {
+            if !config.cron.enabled {
+                tracing::warn!(
+                    "Event rule '{}' scheduled a job but cron is disabled",
+                    rule.name
+                );
+            }
+            let delay = rule
+                .delay
+                .as_deref()
+                .map(cron::parse_delay)
+                .transpose()?
+                .unwrap_or_default()
+                .max(chrono::Duration::seconds(1));
+            let delivery = match (&rule.channel, &rule.to) {
+                (Some(channel), Some(to)) => Some(DeliveryConfig {
+                    mode: "announce".into(),
+                    channel: Some(channel.clone()),
+                    to: Some(to.clone()),
+                    best_effort: true,
+                }),
+                _ => None,
+            };
+            let job = cron::add_agent_job(
+                config,
+                Some(format!("event:{}", rule.name)),
+                Schedule::At {
+                    at: chrono::Utc::now() + delay,
+                },
+                &text,
+                SessionTarget::Isolated,
+                None,
+                delivery,
+                true,
+            )?;
+            tracing::info!(rule = %rule.name, job = %job.id, "Scheduled event job");
+        }
+        PeripheralEventAction::Notify => {
+            let (Some(channel), Some(to)) = (&rule.channel, &rule.to) else {
+                anyhow::bail!("notify rules need both channel and to");
+            };
+            cron::scheduler::deliver_announcement(config, channel, to, &text).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(name: &str) -> PeripheralEventRule {
+        PeripheralEventRule {
+            name: name.into(),
+            board: None,
+            event: "*".into(),
+            pin: None,
+            action: PeripheralEventAction::Notify,
+            prompt: "{event} on {board} pin {pin} = {value}".into(),
+            delay: None,
+            channel: None,
+            to: None,
+            debounce_ms: 0,
+            max_per_hour: 0,
+        }
+    }
+
+    fn button(pin: u32) -> PeripheralEvent {
+        PeripheralEvent {
+            board: "nucleo-f401re".into(),
+            event: "button".into(),
+            pin: Some(pin),
+            value: Some(json!(1)),
+        }
+    }
+
+    #[test]
+    fn rules_match_on_board_event_and_pin() {
+        let mut specific = rule("specific");
+        specific.board = Some("nucleo-f401re".into());
+        specific.event = "button".into();
+        specific.pin = Some(2);
+
+        assert!(matches(&rule("any"), &button(7)));
+        assert!(matches(&specific, &button(2)));
+        assert!(!matches(&specific, &button(3)));
+
+        let mut other_board = button(2);
+        other_board.board = "esp32".into();
+        assert!(!matches(&specific, &other_board));
+
+        let mut other_event = button(2);
+        other_event.event = "threshold".into();
+        assert!(!matches(&specific, &other_event));
+    }
+
+    #[test]
+    fn render_fills_event_fields() {
+        assert_eq!(
+            render(&rule("r").prompt, &button(2)),
+            "button on nucleo-f401re pin 2 = 1"
+        );
+
+        let event = PeripheralEvent {
+            board: "sim".into(),
+            event: "alarm".into(),
+            pin: None,
+            value: Some(json!("overheat")),
+        };
+        assert_eq!(
+            render("{event}: {value} ({pin})", &event),
+            "alarm: overheat ()"
+        );
+    }
+
+    #[test]
+    fn router_debounces_each_rule() {
+        let mut slow = rule("slow");
+        slow.debounce_ms = 500;
+        let mut router = EventRouter::new(&[slow, rule("fast")]);
+        let start = Instant::now();
+
+        let names = |fired: Vec<PeripheralEventRule>| -> Vec<String> {
+            fired.into_iter().map(|r| r.name).collect()
+        };
+        assert_eq!(names(router.route(&button(2), start)), ["slow", "fast"]);
+        assert_eq!(
+            names(router.route(&button(2), start + Duration::from_millis(100))),
+            ["fast"]
+        );
+        assert_eq!(
+            names(router.route(&button(2), start + Duration::from_millis(600))),
+            ["slow", "fast"]
+        );
+    }
+
+    #[test]
+    fn router_enforces_hourly_limit() {
+        let mut limited = rule("limited");
+        limited.max_per_hour = 2;
+        let mut router = EventRouter::new(&[limited]);
+        let start = Instant::now();
+
+        assert_eq!(router.route(&button(2), start).len(), 1);
+        assert_eq!(
+            router
+                .route(&button(2), start + Duration::from_secs(60))
+                .len(),
+            1
+        );
+        assert!(router
+            .route(&button(2), start + Duration::from_secs(120))
+            .is_empty());
+        // The first firing has left the window.
+        assert_eq!(
+            router
+                .route(&button(2), start + Duration::from_secs(3601))
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn notify_rule_requires_a_target() {
+        let config = Config::default();
+        let err = fire(&config, &rule("r"), &button(2)).await.unwrap_err();
+        assert!(err.to_string().contains("channel and to"));
+    }
+}
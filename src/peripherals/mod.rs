@@ -3,16 +3,27 @@
 //! Peripherals extend the agent with physical capabilities. See
 //! `docs/hardware-peripherals-design.md` for the full design.
 
+pub mod events;
 pub mod traits;
 
+#[cfg(feature = "hardware")]
+pub mod modbus;
+#[cfg(feature = "hardware")]
+pub mod modbus_sim;
+#[cfg(feature = "hardware")]
+pub mod protocol;
 #[cfg(feature = "hardware")]
 pub mod serial;
+#[cfg(feature = "hardware")]
+pub mod sim;
 
 #[cfg(feature = "hardware")]
 pub mod arduino_flash;
 #[cfg(feature = "hardware")]
 pub mod arduino_upload;
 #[cfg(feature = "hardware")]
+pub mod bus_tools;
+#[cfg(feature = "hardware")]
 pub mod capabilities_tool;
 #[cfg(feature = "hardware")]
 pub mod nucleo_flash;
@@ -24,13 +35,21 @@ pub mod uno_q_setup;
 #[cfg(all(feature = "peripheral-rpi", target_os = "linux"))]
 pub mod rpi;
 
-pub use traits::Peripheral;
+pub use traits::{Peripheral, PeripheralEvent};
 
 use crate::config::{Config, PeripheralBoardConfig, PeripheralsConfig};
+use crate::security::SecurityPolicy;
 #[cfg(feature = "hardware")]
 use crate::tools::HardwareMemoryMapTool;
 use crate::tools::Tool;
 use anyhow::Result;
+#[cfg(feature = "hardware")]
+use std::collections::HashMap;
+use std::sync::Arc;
+#[cfg(feature = "hardware")]
+use std::sync::LazyLock;
+#[cfg(feature = "hardware")]
+use tokio::sync::broadcast;
 
 /// List configured boards from config (no connection yet).
 pub fn list_configured_boards(config: &PeripheralsConfig) -> Vec<&PeripheralBoardConfig> {
@@ -94,6 +113,12 @@ pub fn handle_command(cmd: crate::PeripheralCommands, config: &Config) -> Result
                 transport: transport.to_string(),
                 path: path_opt,
                 baud: 115_200,
+                allowed_pins: Vec::new(),
+                allowed_buses: Vec::new(),
+                allowed_i2c_addresses: Vec::new(),
+                watch_pins: Vec::new(),
+                sim: None,
+                modbus: None,
             });
             cfg.save()?;
             println!("Added {} at {}. Restart daemon to apply.", board, path);
@@ -130,22 +155,189 @@ pub fn handle_command(cmd: crate::PeripheralCommands, config: &Config) -> Result
             println!("Nucleo flash requires the 'hardware' feature.");
             println!("Build with: cargo build --features hardware");
         }
+        crate::PeripheralCommands::Simulate => {
+            anyhow::bail!("Simulate must be handled in main.rs (requires async runtime)")
+        }
     }
     Ok(())
 }
 
+/// Serve a simulated board on a PTY until Ctrl-C (`zeroclaw peripheral simulate`).
+/// Uses the `sim` script of the first `board = "sim"` entry, if any.
+#[cfg(all(feature = "hardware", unix))]
+pub async fn simulate(config: &Config) -> Result<()> {
+    let script = config
+        .peripherals
+        .boards
+        .iter()
+        .find(|b| b.board == sim::SIM_BOARD)
+        .and_then(|b| b.sim.as_ref());
+    let board =
+        std::sync::Arc::new(script.map_or_else(sim::SimBoard::new, sim::SimBoard::from_config));
+    let pty = board.open_pty()?;
+
+    println!("Simulated board listening on {}", pty.path());
+    println!();
+    println!("Connect with:");
+    println!("  [[peripherals.boards]]");
+    println!("  board = \"{}\"", sim::SIM_BOARD);
+    println!("  transport = \"serial\"");
+    println!("  path = \"{}\"", pty.path());
+    println!();
+    println!("Press Ctrl-C to stop.");
+
+    tokio::signal::ctrl_c().await?;
+    println!("Stopped after {} commands.", board.commands().len());
+    Ok(())
+}
+
+#[cfg(not(all(feature = "hardware", unix)))]
+pub async fn simulate(_config: &Config) -> Result<()> {
+    println!("The PTY simulator requires the 'hardware' feature on a Unix host.");
+    println!("Use transport = \"sim\" to run the simulated board in-process.");
+    Ok(())
+}
+
+/// A board connected in this process.
+#[cfg(feature = "hardware")]
+#[derive(Clone)]
+struct ConnectedBoard {
+    config: PeripheralBoardConfig,
+    peripheral: Arc<dyn Peripheral>,
+    /// Set for serial and simulated boards (used by `hardware_capabilities`).
+    transport: Option<Arc<serial::SerialTransport>>,
+}
+
+#[cfg(feature = "hardware")]
+impl ConnectedBoard {
+    fn is_current(&self, config: &PeripheralBoardConfig) -> bool {
+        self.config == *config && self.transport.as_ref().is_none_or(|t| t.is_open())
+    }
+}
+
+/// Boards connected in this process, shared by agent runs and the daemon's
+/// event watcher: a serial port can only be opened once, and events must
+/// come from the same link the tools use.
+#[cfg(feature = "hardware")]
+static CONNECTED: LazyLock<tokio::sync::Mutex<HashMap<String, ConnectedBoard>>> =
+    LazyLock::new(|| tokio::sync::Mutex::new(HashMap::new()));
+
+#[cfg(feature = "hardware")]
+fn board_key(board: &PeripheralBoardConfig) -> String {
+    format!(
+        "{}:{}:{}",
+        board.transport,
+        board.board,
+        board.path.as_deref().unwrap_or_default()
+    )
+}
+
+/// Connect `board`, or reuse the live connection from an earlier call.
+/// `Ok(None)` for transports that are not connected here (Uno Q bridge,
+/// Modbus).
+#[cfg(feature = "hardware")]
+async fn connect_board(board: &PeripheralBoardConfig) -> Result<Option<ConnectedBoard>> {
+    let mut connected = CONNECTED.lock().await;
+    let key = board_key(board);
+    if let Some(existing) = connected.get(&key).filter(|c| c.is_current(board)) {
+        return Ok(Some(existing.clone()));
+    }
+
+    let entry = match board.transport.as_str() {
+        // Native transport: RPi GPIO (Linux only)
+        #[cfg(all(feature = "peripheral-rpi", target_os = "linux"))]
+        "native" if board.board == "rpi-gpio" || board.board == "raspberry-pi" => {
+            let peripheral = rpi::RpiGpioPeripheral::connect_from_config(board).await?;
+            tracing::info!(board = %board.board, "RPi GPIO peripheral connected");
+            ConnectedBoard {
+                config: board.clone(),
+                peripheral: Arc::new(peripheral),
+                transport: None,
+            }
+        }
+        // Simulated board, in-process (development and CI)
+        "sim" => {
+            let peripheral = sim::SimPeripheral::connect(board).await;
+            tracing::info!(board = %board.board, "Simulated peripheral connected");
+            ConnectedBoard {
+                config: board.clone(),
+                transport: Some(peripheral.transport()),
+                peripheral: Arc::new(peripheral),
+            }
+        }
+        // Serial transport (STM32, ESP32, Arduino, etc.)
+        "serial" => {
+            // Release a stale link before reopening the same port.
+            connected.remove(&key);
+            let mut peripheral = serial::SerialPeripheral::connect(board).await?;
+            if peripheral.connect().await.is_err() {
+                tracing::warn!(
+                    "Peripheral {} connect warning (continuing)",
+                    peripheral.name()
+                );
+            }
+            tracing::info!(board = %board.board, "Serial peripheral connected");
+            ConnectedBoard {
+                config: board.clone(),
+                transport: Some(peripheral.transport()),
+                peripheral: Arc::new(peripheral),
+            }
+        }
+        _ => return Ok(None),
+    };
+    connected.insert(key, entry.clone());
+    Ok(Some(entry))
+}
+
+/// Event streams of every configured board that can raise events.
+#[cfg(feature = "hardware")]
+pub async fn subscribe_events(
+    config: &PeripheralsConfig,
+) -> Vec<broadcast::Receiver<PeripheralEvent>> {
+    if !config.enabled {
+        return Vec::new();
+    }
+    let mut receivers = Vec::new();
+    for board in &config.boards {
+        match connect_board(board).await {
+            Ok(Some(connected)) => receivers.extend(connected.peripheral.events()),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to connect {} for events: {}", board.board, e),
+        }
+    }
+    receivers
+}
+
 /// Create and connect peripherals from config, returning their tools.
 /// Returns empty vec if peripherals disabled or hardware feature off.
+/// `security` gates actuating tools such as `modbus_write`.
 #[cfg(feature = "hardware")]
-pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<Box<dyn Tool>>> {
+pub async fn create_peripheral_tools(
+    config: &PeripheralsConfig,
+    security: &Arc<SecurityPolicy>,
+) -> Result<Vec<Box<dyn Tool>>> {
     if !config.enabled || config.boards.is_empty() {
         return Ok(Vec::new());
     }
 
     let mut tools: Vec<Box<dyn Tool>> = Vec::new();
-    let mut serial_transports: Vec<(String, std::sync::Arc<serial::SerialTransport>)> = Vec::new();
+    let mut serial_transports: Vec<(String, Arc<serial::SerialTransport>)> = Vec::new();
+    let mut modbus_devices: Vec<Arc<modbus::ModbusDevice>> = Vec::new();
 
     for board in &config.boards {
+        // Modbus TCP/RTU: one shared modbus_read/modbus_write pair for all
+        // devices; links open lazily on first use.
+        if modbus::is_modbus_transport(&board.transport) {
+            match modbus::ModbusDevice::from_config(board) {
+                Ok(device) => {
+                    tracing::info!(board = %board.board, "Modbus device configured");
+                    modbus_devices.push(Arc::new(device));
+                }
+                Err(e) => tracing::warn!("Invalid Modbus board {}: {}", board.board, e),
+            }
+            continue;
+        }
+
         // Arduino Uno Q: Bridge transport (socket to local Bridge app)
         if board.transport == "bridge" && (board.board == "arduino-uno-q" || board.board == "uno-q")
         {
@@ -155,59 +347,36 @@ pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<B
             continue;
         }
 
-        // Native transport: RPi GPIO (Linux only)
-        #[cfg(all(feature = "peripheral-rpi", target_os = "linux"))]
-        if board.transport == "native"
-            && (board.board == "rpi-gpio" || board.board == "raspberry-pi")
-        {
-            match rpi::RpiGpioPeripheral::connect_from_config(board).await {
-                Ok(peripheral) => {
-                    tools.extend(peripheral.tools());
-                    tracing::info!(board = %board.board, "RPi GPIO peripheral connected");
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to connect RPi GPIO {}: {}", board.board, e);
-                }
-            }
-            continue;
-        }
-
-        // Serial transport (STM32, ESP32, Arduino, etc.)
-        if board.transport != "serial" {
-            continue;
-        }
-        if board.path.is_none() {
-            tracing::warn!("Skipping serial board {}: no path", board.board);
-            continue;
-        }
-
-        match serial::SerialPeripheral::connect(board).await {
-            Ok(peripheral) => {
-                let mut p = peripheral;
-                if p.connect().await.is_err() {
-                    tracing::warn!("Peripheral {} connect warning (continuing)", p.name());
-                }
-                serial_transports.push((board.board.clone(), p.transport()));
-                tools.extend(p.tools());
-                if board.board == "arduino-uno" {
-                    if let Some(ref path) = board.path {
-                        tools.push(Box::new(arduino_upload::ArduinoUploadTool::new(
-                            path.clone(),
-                        )));
-                        tracing::info!("Arduino upload tool added (port: {})", path);
-                    }
-                }
-                tracing::info!(board = %board.board, "Serial peripheral connected");
-            }
+        let connected = match connect_board(board).await {
+            Ok(Some(connected)) => connected,
+            Ok(None) => continue,
             Err(e) => {
                 tracing::warn!("Failed to connect {}: {}", board.board, e);
+                continue;
+            }
+        };
+        if let Some(transport) = connected.transport {
+            serial_transports.push((board.board.clone(), transport));
+        }
+        tools.extend(connected.peripheral.tools());
+        if board.transport == "serial" && board.board == "arduino-uno" {
+            if let Some(ref path) = board.path {
+                tools.push(Box::new(arduino_upload::ArduinoUploadTool::new(
+                    path.clone(),
+                )));
+                tracing::info!("Arduino upload tool added (port: {})", path);
             }
         }
     }
 
     // Phase B: Add hardware tools when any boards configured
     if !tools.is_empty() {
-        let board_names: Vec<String> = config.boards.iter().map(|b| b.board.clone()).collect();
+        let board_names: Vec<String> = config
+            .boards
+            .iter()
+            .filter(|b| !modbus::is_modbus_transport(&b.transport))
+            .map(|b| b.board.clone())
+            .collect();
         tools.push(Box::new(HardwareMemoryMapTool::new(board_names.clone())));
         tools.push(Box::new(crate::tools::HardwareBoardInfoTool::new(
             board_names.clone(),
@@ -224,10 +393,24 @@ pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<B
         )));
     }
 
+    if !modbus_devices.is_empty() {
+        tools.extend(modbus::modbus_tools(modbus_devices, security));
+    }
+
     Ok(tools)
 }
 
 #[cfg(not(feature = "hardware"))]
-pub async fn create_peripheral_tools(_config: &PeripheralsConfig) -> Result<Vec<Box<dyn Tool>>> {
+pub async fn create_peripheral_tools(
+    _config: &PeripheralsConfig,
+    _security: &Arc<SecurityPolicy>,
+) -> Result<Vec<Box<dyn Tool>>> {
     Ok(Vec::new())
 }
+
+#[cfg(not(feature = "hardware"))]
+pub async fn subscribe_events(
+    _config: &PeripheralsConfig,
+) -> Vec<tokio::sync::broadcast::Receiver<PeripheralEvent>> {
+    Vec::new()
+}
@@ -0,0 +1,1124 @@
+//! Modbus peripheral — industrial sensors and PLCs over Modbus TCP or RTU.
+//!
+//! A board with `transport = "modbus-tcp"` (`path = "host:port"`) or
+//! `transport = "modbus-rtu"` (serial `path` and `baud`) exposes the
+//! registers in its `[peripherals.boards.modbus]` map through `modbus_read`
+//! and `modbus_write`. Values are converted between raw registers and
+//! engineering units with each register's type, scale and offset. Only mapped
+//! registers are reachable; writes need `writable = true`, respect `min`/`max`
+//! and go through [`SecurityPolicy`].
+//!
+//! The link is opened on first use and reopened after any transport error, so
+//! a PLC that drops idle connections or a replugged adapter recovers on the
+//! next call.
+
+use super::serial::SerialLink;
+use super::traits::Peripheral;
+use crate::config::{
+    ModbusConfig, ModbusDataType, ModbusParity, ModbusRegisterConfig, ModbusRegisterKind,
+    ModbusWordOrder, PeripheralBoardConfig,
+};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use crate::tools::traits::{Tool, ToolResult};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+use tokio_serial::SerialPortBuilderExt;
+
+pub const MODBUS_TCP: &str = "modbus-tcp";
+pub const MODBUS_RTU: &str = "modbus-rtu";
+
+const DEFAULT_TCP_PORT: u16 = 502;
+
+/// Largest PDU (function code + data) a Modbus frame can carry.
+pub(crate) const MAX_PDU_LEN: usize = 253;
+
+pub(crate) const READ_COILS: u8 = 0x01;
+pub(crate) const READ_DISCRETE_INPUTS: u8 = 0x02;
+pub(crate) const READ_HOLDING_REGISTERS: u8 = 0x03;
+pub(crate) const READ_INPUT_REGISTERS: u8 = 0x04;
+pub(crate) const WRITE_SINGLE_COIL: u8 = 0x05;
+pub(crate) const WRITE_SINGLE_REGISTER: u8 = 0x06;
+pub(crate) const WRITE_MULTIPLE_REGISTERS: u8 = 0x10;
+
+/// Set on the function code of an exception response.
+pub(crate) const EXCEPTION_FLAG: u8 = 0x80;
+
+/// Whether `transport` is one of the Modbus transports.
+pub fn is_modbus_transport(transport: &str) -> bool {
+    transport == MODBUS_TCP || transport == MODBUS_RTU
+}
+
+/// CRC-16/MODBUS of an RTU frame (sent low byte first).
+pub(crate) fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for &byte in data {
+        crc ^= u16::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xA001
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    crc
+}
+
+fn exception_message(code: u8) -> &'static str {
+    match code {
+        0x01 => "illegal function",
+        0x02 => "illegal data address",
+        0x03 => "illegal data value",
+        0x04 => "server device failure",
+        0x05 => "acknowledge (processing)",
+        0x06 => "server device busy",
+        0x0A => "gateway path unavailable",
+        0x0B => "gateway target failed to respond",
+        _ => "unknown exception",
+    }
+}
+
+// ── Value conversion ─────────────────────────────────────────────
+
+/// Registers a value of `data_type` occupies.
+pub fn register_count(data_type: ModbusDataType) -> u16 {
+    match data_type {
+        ModbusDataType::U32 | ModbusDataType::I32 | ModbusDataType::F32 => 2,
+        _ => 1,
+    }
+}
+
+fn join_words(words: &[u16], order: ModbusWordOrder) -> u32 {
+    let (high, low) = match order {
+        ModbusWordOrder::Big => (words[0], words[1]),
+        ModbusWordOrder::Little => (words[1], words[0]),
+    };
+    (u32::from(high) << 16) | u32::from(low)
+}
+
+fn split_words(value: u32, order: ModbusWordOrder) -> Vec<u16> {
+    let [a, b, c, d] = value.to_be_bytes();
+    let (high, low) = (u16::from_be_bytes([a, b]), u16::from_be_bytes([c, d]));
+    match order {
+        ModbusWordOrder::Big => vec![high, low],
+        ModbusWordOrder::Little => vec![low, high],
+    }
+}
+
+/// Raw register contents as a number, before scaling.
+pub fn decode_raw(words: &[u16], data_type: ModbusDataType, order: ModbusWordOrder) -> f64 {
+    match data_type {
+        ModbusDataType::Bool => f64::from(u8::from(words[0] != 0)),
+        ModbusDataType::U16 => f64::from(words[0]),
+        ModbusDataType::I16 => f64::from(i16::from_be_bytes(words[0].to_be_bytes())),
+        ModbusDataType::U32 => f64::from(join_words(words, order)),
+        ModbusDataType::I32 => {
+            f64::from(i32::from_be_bytes(join_words(words, order).to_be_bytes()))
+        }
+        ModbusDataType::F32 => f64::from(f32::from_bits(join_words(words, order))),
+    }
+}
+
+/// Round to the nearest integer if `value` fits in `[min, max]`.
+#[allow(clippy::cast_possible_truncation)]
+fn integral(value: f64, min: f64, max: f64) -> Option<i64> {
+    let rounded = value.round();
+    (rounded >= min && rounded <= max).then(|| rounded as i64)
+}
+
+/// Registers holding `raw` (an unscaled value) as `data_type`.
+pub fn encode_raw(raw: f64, data_type: ModbusDataType, order: ModbusWordOrder) -> Result<Vec<u16>> {
+    if !raw.is_finite() {
+        bail!("value must be a finite number");
+    }
+    let out_of_range = || anyhow::anyhow!("raw value {raw} does not fit in {data_type:?}");
+    Ok(match data_type {
+        ModbusDataType::Bool => vec![u16::from(raw != 0.0)],
+        ModbusDataType::U16 => {
+            let v = integral(raw, 0.0, f64::from(u16::MAX)).ok_or_else(out_of_range)?;
+            vec![u16::try_from(v)?]
+        }
+        ModbusDataType::I16 => {
+            let v =
+                integral(raw, f64::from(i16::MIN), f64::from(i16::MAX)).ok_or_else(out_of_range)?;
+            vec![u16::from_be_bytes(i16::try_from(v)?.to_be_bytes())]
+        }
+        ModbusDataType::U32 => {
+            let v = integral(raw, 0.0, f64::from(u32::MAX)).ok_or_else(out_of_range)?;
+            split_words(u32::try_from(v)?, order)
+        }
+        ModbusDataType::I32 => {
+            let v =
+                integral(raw, f64::from(i32::MIN), f64::from(i32::MAX)).ok_or_else(out_of_range)?;
+            split_words(u32::from_be_bytes(i32::try_from(v)?.to_be_bytes()), order)
+        }
+        ModbusDataType::F32 => {
+            #[allow(clippy::cast_possible_truncation)]
+            let v = raw as f32;
+            if !v.is_finite() {
+                return Err(out_of_range());
+            }
+            split_words(v.to_bits(), order)
+        }
+    })
+}
+
+/// Engineering value of a register, as shown to the agent.
+fn to_engineering(register: &ModbusRegisterConfig, raw: f64) -> Value {
+    if register.data_type == ModbusDataType::Bool {
+        return Value::Bool(raw != 0.0);
+    }
+    let value = raw * register.scale + register.offset;
+    if register.data_type != ModbusDataType::F32 && value.fract() == 0.0 {
+        if let Some(v) = integral(value, -9.0e15, 9.0e15) {
+            return json!(v);
+        }
+    }
+    // Hide float noise from scaling (0.1 * 234 = 23.400000000000002).
+    json!((value * 1e6).round() / 1e6)
+}
+
+/// Raw value to write for an engineering value from the agent.
+fn from_engineering(register: &ModbusRegisterConfig, value: &Value) -> Result<f64> {
+    let value = match value {
+        Value::Bool(b) => f64::from(u8::from(*b)),
+        Value::Number(n) => n
+            .as_f64()
+            .ok_or_else(|| anyhow::anyhow!("'value' must be a number"))?,
+        _ => bail!("'value' must be a number or boolean"),
+    };
+    if let Some(min) = register.min {
+        if value < min {
+            bail!("{} must be at least {min}", register.name);
+        }
+    }
+    if let Some(max) = register.max {
+        if value > max {
+            bail!("{} must be at most {max}", register.name);
+        }
+    }
+    if register.data_type == ModbusDataType::Bool {
+        return Ok(value);
+    }
+    Ok((value - register.offset) / register.scale)
+}
+
+/// Reject register maps the tools cannot serve safely.
+pub fn validate_register_map(config: &ModbusConfig) -> Result<()> {
+    let mut names = HashSet::new();
+    for register in &config.registers {
+        let name = register.name.trim();
+        if name.is_empty() {
+            bail!(
+                "Modbus register with empty name at address {}",
+                register.address
+            );
+        }
+        if !names.insert(name) {
+            bail!("Duplicate Modbus register name '{name}'");
+        }
+        let read_only = matches!(
+            register.kind,
+            ModbusRegisterKind::Input | ModbusRegisterKind::Discrete
+        );
+        if register.writable && read_only {
+            bail!(
+                "Modbus register '{name}' is {:?} and cannot be writable",
+                register.kind
+            );
+        }
+        if register.scale == 0.0 || !register.scale.is_finite() {
+            bail!("Modbus register '{name}' needs a non-zero scale");
+        }
+        if let (Some(min), Some(max)) = (register.min, register.max) {
+            if min > max {
+                bail!("Modbus register '{name}' has min > max");
+            }
+        }
+        let last = u32::from(register.address) + u32::from(register_count(register.data_type));
+        if last > 0x1_0000 {
+            bail!("Modbus register '{name}' runs past address 65535");
+        }
+    }
+    Ok(())
+}
+
+// ── Client ───────────────────────────────────────────────────────
+
+/// How requests are framed on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framing {
+    /// MBAP header, no checksum
+    Tcp,
+    /// Unit id + PDU + CRC-16
+    Rtu,
+}
+
+/// Where the link is (re)opened from.
+enum Endpoint {
+    Tcp(String),
+    Rtu {
+        path: String,
+        baud: u32,
+        parity: ModbusParity,
+    },
+    /// A link handed in by the caller (tests); cannot be reopened.
+    Fixed,
+}
+
+/// Request/response Modbus master over one link. Requests are serialized.
+pub struct ModbusClient {
+    framing: Framing,
+    endpoint: Endpoint,
+    unit_id: u8,
+    timeout: Duration,
+    link: Mutex<Option<Box<dyn SerialLink>>>,
+    transaction: AtomicU16,
+}
+
+impl ModbusClient {
+    /// Client for the board's transport; the link opens on first use.
+    pub fn from_config(board: &PeripheralBoardConfig, modbus: &ModbusConfig) -> Result<Self> {
+        let path = board
+            .path
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Modbus peripheral requires path"))?;
+        let (framing, endpoint) = match board.transport.as_str() {
+            MODBUS_TCP => {
+                let addr = if path.contains(':') {
+                    path.to_string()
+                } else {
+                    format!("{path}:{DEFAULT_TCP_PORT}")
+                };
+                (Framing::Tcp, Endpoint::Tcp(addr))
+            }
+            MODBUS_RTU => {
+                if !super::serial::is_path_allowed(path) {
+                    bail!("Serial path not allowed for Modbus RTU: {path}");
+                }
+                (
+                    Framing::Rtu,
+                    Endpoint::Rtu {
+                        path: path.to_string(),
+                        baud: board.baud,
+                        parity: modbus.parity,
+                    },
+                )
+            }
+            other => bail!("Not a Modbus transport: {other}"),
+        };
+        Ok(Self::new(framing, endpoint, modbus))
+    }
+
+    /// Client over an already-open link (e.g. a simulator pipe).
+    pub(crate) fn with_link(
+        framing: Framing,
+        modbus: &ModbusConfig,
+        link: Box<dyn SerialLink>,
+    ) -> Self {
+        let client = Self::new(framing, Endpoint::Fixed, modbus);
+        *client.link.try_lock().expect("new client is unlocked") = Some(link);
+        client
+    }
+
+    fn new(framing: Framing, endpoint: Endpoint, modbus: &ModbusConfig) -> Self {
+        Self {
+            framing,
+            endpoint,
+            unit_id: modbus.unit_id,
+            timeout: Duration::from_millis(modbus.timeout_ms.max(1)),
+            link: Mutex::new(None),
+            transaction: AtomicU16::new(0),
+        }
+    }
+
+    async fn open(&self) -> Result<Box<dyn SerialLink>> {
+        match &self.endpoint {
+            Endpoint::Tcp(addr) => {
+                let stream =
+                    tokio::time::timeout(self.timeout, tokio::net::TcpStream::connect(addr))
+                        .await
+                        .map_err(|_| anyhow::anyhow!("Timed out connecting to {addr}"))?
+                        .with_context(|| format!("Failed to connect to {addr}"))?;
+                stream.set_nodelay(true)?;
+                Ok(Box::new(stream))
+            }
+            Endpoint::Rtu { path, baud, parity } => {
+                let parity = match parity {
+                    ModbusParity::None => tokio_serial::Parity::None,
+                    ModbusParity::Even => tokio_serial::Parity::Even,
+                    ModbusParity::Odd => tokio_serial::Parity::Odd,
+                };
+                let port = tokio_serial::new(path, *baud)
+                    .parity(parity)
+                    .open_native_async()
+                    .with_context(|| format!("Failed to open {path}"))?;
+                Ok(Box::new(port))
+            }
+            Endpoint::Fixed => bail!("Modbus link closed"),
+        }
+    }
+
+    /// Open the link now rather than on the first request.
+    pub async fn connect(&self) -> Result<()> {
+        let mut link = self.link.lock().await;
+        if link.is_none() {
+            *link = Some(self.open().await?);
+        }
+        Ok(())
+    }
+
+    /// Close the link; the next request reopens it.
+    pub async fn disconnect(&self) {
+        if !matches!(self.endpoint, Endpoint::Fixed) {
+            self.link.lock().await.take();
+        }
+    }
+
+    /// Send a request PDU and return the response PDU. Exception responses
+    /// become errors.
+    pub async fn call(&self, request: &[u8]) -> Result<Vec<u8>> {
+        let mut guard = self.link.lock().await;
+        let link = match guard.as_mut() {
+            Some(link) => link,
+            None => guard.insert(self.open().await?),
+        };
+        let response = match tokio::time::timeout(self.timeout, self.exchange(link, request)).await
+        {
+            Ok(Ok(response)) => response,
+            outcome => {
+                // A late or partial reply would corrupt the next exchange;
+                // start over on a fresh link.
+                if !matches!(self.endpoint, Endpoint::Fixed) {
+                    guard.take();
+                }
+                return Err(outcome
+                    .unwrap_or_else(|_| {
+                        Err(anyhow::anyhow!(
+                            "Modbus request timed out after {:?}",
+                            self.timeout
+                        ))
+                    })
+                    .unwrap_err());
+            }
+        };
+        drop(guard);
+
+        match response.first() {
+            Some(&fc) if fc == request[0] | EXCEPTION_FLAG => {
+                let code = response.get(1).copied().unwrap_or(0);
+                bail!("Modbus exception {code:#04x}: {}", exception_message(code));
+            }
+            Some(&fc) if fc == request[0] => Ok(response),
+            _ => bail!("Modbus response for wrong function code"),
+        }
+    }
+
+    async fn exchange(&self, link: &mut Box<dyn SerialLink>, request: &[u8]) -> Result<Vec<u8>> {
+        match self.framing {
+            Framing::Tcp => self.exchange_tcp(link, request).await,
+            Framing::Rtu => self.exchange_rtu(link, request).await,
+        }
+    }
+
+    async fn exchange_tcp(
+        &self,
+        link: &mut Box<dyn SerialLink>,
+        request: &[u8],
+    ) -> Result<Vec<u8>> {
+        let transaction = self.transaction.fetch_add(1, Ordering::Relaxed);
+        let length = u16::try_from(request.len() + 1)?;
+        let mut frame = Vec::with_capacity(request.len() + 7);
+        frame.extend_from_slice(&transaction.to_be_bytes());
+        frame.extend_from_slice(&[0, 0]);
+        frame.extend_from_slice(&length.to_be_bytes());
+        frame.push(self.unit_id);
+        frame.extend_from_slice(request);
+        link.write_all(&frame).await?;
+        link.flush().await?;
+
+        loop {
+            let mut header = [0u8; 7];
+            link.read_exact(&mut header).await?;
+            let length = usize::from(u16::from_be_bytes([header[4], header[5]]));
+            if !(2..=MAX_PDU_LEN + 1).contains(&length) {
+                bail!("Invalid Modbus TCP length {length}");
+            }
+            let mut pdu = vec![0u8; length - 1];
+            link.read_exact(&mut pdu).await?;
+            if u16::from_be_bytes([header[0], header[1]]) == transaction {
+                return Ok(pdu);
+            }
+            tracing::debug!("Skipping stale Modbus TCP response");
+        }
+    }
+
+    async fn exchange_rtu(
+        &self,
+        link: &mut Box<dyn SerialLink>,
+        request: &[u8],
+    ) -> Result<Vec<u8>> {
+        let mut frame = Vec::with_capacity(request.len() + 3);
+        frame.push(self.unit_id);
+        frame.extend_from_slice(request);
+        frame.extend_from_slice(&crc16(&frame).to_le_bytes());
+        link.write_all(&frame).await?;
+        link.flush().await?;
+
+        let mut head = [0u8; 2];
+        link.read_exact(&mut head).await?;
+        let mut reply = head.to_vec();
+        let body_len = if head[1] & EXCEPTION_FLAG != 0 {
+            1
+        } else {
+            match head[1] {
+                READ_COILS..=READ_INPUT_REGISTERS => {
+                    let count = link.read_u8().await?;
+                    reply.push(count);
+                    usize::from(count)
+                }
+                WRITE_SINGLE_COIL | WRITE_SINGLE_REGISTER | WRITE_MULTIPLE_REGISTERS => 4,
+                other => bail!("Unexpected Modbus RTU function code {other:#04x}"),
+            }
+        };
+        let start = reply.len();
+        reply.resize(start + body_len + 2, 0);
+        link.read_exact(&mut reply[start..]).await?;
+
+        let (payload, crc) = reply.split_at(reply.len() - 2);
+        if crc16(payload).to_le_bytes() != [crc[0], crc[1]] {
+            bail!("Modbus RTU CRC mismatch");
+        }
+        if payload[0] != self.unit_id {
+            bail!(
+                "Modbus RTU reply from unit {} (expected {})",
+                payload[0],
+                self.unit_id
+            );
+        }
+        Ok(payload[1..].to_vec())
+    }
+
+    fn read_request(function: u8, address: u16, count: u16) -> Vec<u8> {
+        let mut pdu = vec![function];
+        pdu.extend_from_slice(&address.to_be_bytes());
+        pdu.extend_from_slice(&count.to_be_bytes());
+        pdu
+    }
+
+    /// Read `count` coils or discrete inputs.
+    pub async fn read_bits(&self, function: u8, address: u16, count: u16) -> Result<Vec<bool>> {
+        let response = self
+            .call(&Self::read_request(function, address, count))
+            .await?;
+        let expected = usize::from(count).div_ceil(8);
+        if response.len() != expected + 2 || usize::from(response[1]) != expected {
+            bail!("Malformed Modbus bit response");
+        }
+        Ok((0..usize::from(count))
+            .map(|i| response[2 + i / 8] & (1 << (i % 8)) != 0)
+            .collect())
+    }
+
+    /// Read `count` holding or input registers.
+    pub async fn read_registers(&self, function: u8, address: u16, count: u16) -> Result<Vec<u16>> {
+        let response = self
+            .call(&Self::read_request(function, address, count))
+            .await?;
+        let expected = usize::from(count) * 2;
+        if response.len() != expected + 2 || usize::from(response[1]) != expected {
+            bail!("Malformed Modbus register response");
+        }
+        Ok(response[2..]
+            .chunks_exact(2)
+            .map(|w| u16::from_be_bytes([w[0], w[1]]))
+            .collect())
+    }
+
+    pub async fn write_coil(&self, address: u16, value: bool) -> Result<()> {
+        let mut pdu = vec![WRITE_SINGLE_COIL];
+        pdu.extend_from_slice(&address.to_be_bytes());
+        pdu.extend_from_slice(if value { &[0xFF, 0x00] } else { &[0x00, 0x00] });
+        let response = self.call(&pdu).await?;
+        if response != pdu {
+            bail!("Modbus coil write was not echoed");
+        }
+        Ok(())
+    }
+
+    pub async fn write_registers(&self, address: u16, values: &[u16]) -> Result<()> {
+        let mut pdu;
+        if let [value] = values {
+            pdu = vec![WRITE_SINGLE_REGISTER];
+            pdu.extend_from_slice(&address.to_be_bytes());
+            pdu.extend_from_slice(&value.to_be_bytes());
+        } else {
+            let count = u16::try_from(values.len())?;
+            pdu = vec![WRITE_MULTIPLE_REGISTERS];
+            pdu.extend_from_slice(&address.to_be_bytes());
+            pdu.extend_from_slice(&count.to_be_bytes());
+            pdu.push(u8::try_from(values.len() * 2)?);
+            for value in values {
+                pdu.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+        let response = self.call(&pdu).await?;
+        if response.len() != 5 || response[..5] != pdu[..5] {
+            bail!("Modbus register write was not acknowledged");
+        }
+        Ok(())
+    }
+}
+
+// ── Device ───────────────────────────────────────────────────────
+
+/// A Modbus unit and its register map.
+pub struct ModbusDevice {
+    name: String,
+    config: ModbusConfig,
+    client: ModbusClient,
+}
+
+impl ModbusDevice {
+    /// Validate the board's register map; the link opens on first use.
+    pub fn from_config(board: &PeripheralBoardConfig) -> Result<Self> {
+        let config = board.modbus.clone().unwrap_or_default();
+        let client = ModbusClient::from_config(board, &config)?;
+        Self::new(board.board.clone(), config, client)
+    }
+
+    pub fn new(name: String, config: ModbusConfig, client: ModbusClient) -> Result<Self> {
+        validate_register_map(&config)?;
+        Ok(Self {
+            name,
+            config,
+            client,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn registers(&self) -> &[ModbusRegisterConfig] {
+        &self.config.registers
+    }
+
+    pub fn register(&self, name: &str) -> Option<&ModbusRegisterConfig> {
+        self.config.registers.iter().find(|r| r.name == name)
+    }
+
+    pub fn client(&self) -> &ModbusClient {
+        &self.client
+    }
+
+    /// Read a mapped register in engineering units.
+    pub async fn read(&self, register: &ModbusRegisterConfig) -> Result<Value> {
+        let address = register.address;
+        let raw = match register.kind {
+            ModbusRegisterKind::Coil | ModbusRegisterKind::Discrete => {
+                let function = if register.kind == ModbusRegisterKind::Coil {
+                    READ_COILS
+                } else {
+                    READ_DISCRETE_INPUTS
+                };
+                let bits = self.client.read_bits(function, address, 1).await?;
+                return Ok(Value::Bool(bits[0]));
+            }
+            ModbusRegisterKind::Input | ModbusRegisterKind::Holding => {
+                let function = if register.kind == ModbusRegisterKind::Input {
+                    READ_INPUT_REGISTERS
+                } else {
+                    READ_HOLDING_REGISTERS
+                };
+                let count = register_count(register.data_type);
+                let words = self.client.read_registers(function, address, count).await?;
+                decode_raw(&words, register.data_type, self.config.word_order)
+            }
+        };
+        Ok(to_engineering(register, raw))
+    }
+
+    /// Write an engineering value to a mapped register. Checks `writable`
+    /// and `min`/`max`; the caller enforces security policy.
+    pub async fn write(&self, register: &ModbusRegisterConfig, value: &Value) -> Result<()> {
+        if !register.writable {
+            bail!("Modbus register '{}' is read-only", register.name);
+        }
+        let raw = from_engineering(register, value)?;
+        match register.kind {
+            ModbusRegisterKind::Coil => self.client.write_coil(register.address, raw != 0.0).await,
+            ModbusRegisterKind::Holding => {
+                let words = encode_raw(raw, register.data_type, self.config.word_order)?;
+                self.client.write_registers(register.address, &words).await
+            }
+            ModbusRegisterKind::Input | ModbusRegisterKind::Discrete => {
+                bail!("Modbus register '{}' is read-only", register.name)
+            }
+        }
+    }
+}
+
+/// Modbus device as a [`Peripheral`].
+pub struct ModbusPeripheral {
+    device: Arc<ModbusDevice>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl ModbusPeripheral {
+    pub fn new(device: Arc<ModbusDevice>, security: Arc<SecurityPolicy>) -> Self {
+        Self { device, security }
+    }
+
+    pub fn device(&self) -> &Arc<ModbusDevice> {
+        &self.device
+    }
+}
+
+#[async_trait]
+impl Peripheral for ModbusPeripheral {
+    fn name(&self) -> &str {
+        self.device.name()
+    }
+
+    fn board_type(&self) -> &str {
+        "modbus"
+    }
+
+    async fn connect(&mut self) -> anyhow::Result<()> {
+        self.device.client.connect().await
+    }
+
+    async fn disconnect(&mut self) -> anyhow::Result<()> {
+        self.device.client.disconnect().await;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> bool {
+        // Modbus has no ping; read the first mapped register instead.
+        match self.device.registers().first() {
+            Some(register) => self.device.read(register).await.is_ok(),
+            None => self.device.client.connect().await.is_ok(),
+        }
+    }
+
+    fn tools(&self) -> Vec<Box<dyn Tool>> {
+        modbus_tools(vec![self.device.clone()], &self.security)
+    }
+}
+
+/// `modbus_read` and `modbus_write` over every device in `devices`.
+pub fn modbus_tools(
+    devices: Vec<Arc<ModbusDevice>>,
+    security: &Arc<SecurityPolicy>,
+) -> Vec<Box<dyn Tool>> {
+    vec![
+        Box::new(ModbusReadTool::new(devices.clone())),
+        Box::new(ModbusWriteTool::new(devices, security.clone())),
+    ]
+}
+
+// ── Tools ────────────────────────────────────────────────────────
+
+/// Register map summary appended to tool descriptions.
+fn describe_registers(devices: &[Arc<ModbusDevice>], writable_only: bool) -> String {
+    let mut lines = Vec::new();
+    for device in devices {
+        for register in device.registers() {
+            if writable_only && !register.writable {
+                continue;
+            }
+            let mut line = format!("{}/{}", device.name(), register.name);
+            if let Some(unit) = &register.unit {
+                let _ = write!(line, " [{unit}]");
+            }
+            if !writable_only && register.writable {
+                line.push_str(" (writable)");
+            }
+            if writable_only && (register.min.is_some() || register.max.is_some()) {
+                let bound = |b: Option<f64>| b.map_or_else(|| "…".to_string(), |v| v.to_string());
+                let _ = write!(
+                    line,
+                    " range {}..{}",
+                    bound(register.min),
+                    bound(register.max)
+                );
+            }
+            if let Some(description) = &register.description {
+                let _ = write!(line, ": {description}");
+            }
+            lines.push(line);
+        }
+    }
+    if lines.is_empty() {
+        "No registers mapped.".into()
+    } else {
+        format!("Registers (device/name):\n{}", lines.join("\n"))
+    }
+}
+
+/// Find a register by name, narrowing to `device` when given.
+fn resolve<'a>(
+    devices: &'a [Arc<ModbusDevice>],
+    args: &Value,
+) -> Result<(&'a Arc<ModbusDevice>, &'a ModbusRegisterConfig), String> {
+    let name = args
+        .get("register")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .ok_or("Missing 'register' parameter")?;
+    let device = args.get("device").and_then(Value::as_str).map(str::trim);
+    let mut found = devices
+        .iter()
+        .filter(|d| device.is_none_or(|name| d.name() == name))
+        .filter_map(|d| d.register(name).map(|r| (d, r)));
+    let first = found.next().ok_or_else(|| match device {
+        Some(device) => format!("Unknown Modbus register '{name}' on device '{device}'"),
+        None => format!("Unknown Modbus register '{name}'"),
+    })?;
+    if found.next().is_some() {
+        return Err(format!(
+            "Register '{name}' exists on several devices; pass 'device'"
+        ));
+    }
+    Ok(first)
+}
+
+fn success(value: &Value) -> ToolResult {
+    ToolResult {
+        success: true,
+        output: value.to_string(),
+        error: None,
+    }
+}
+
+fn failure(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+/// Tool: read named Modbus registers in engineering units.
+pub struct ModbusReadTool {
+    devices: Vec<Arc<ModbusDevice>>,
+    description: String,
+}
+
+impl ModbusReadTool {
+    pub fn new(devices: Vec<Arc<ModbusDevice>>) -> Self {
+        let description = format!(
+            "Read a named register from a Modbus device (PLC, sensor, meter). Returns the value in engineering units. Omit 'register' to read every mapped register.\n{}",
+            describe_registers(&devices, false)
+        );
+        Self {
+            devices,
+            description,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ModbusReadTool {
+    fn name(&self) -> &str {
+        "modbus_read"
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "register": {
+                    "type": "string",
+                    "description": "Register name from the map (omit to read all)"
+                },
+                "device": {
+                    "type": "string",
+                    "description": "Device name; needed only when several devices map the same register name"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        if args.get("register").is_none() {
+            let device = args.get("device").and_then(Value::as_str);
+            let mut values = Map::new();
+            for d in self
+                .devices
+                .iter()
+                .filter(|d| device.is_none_or(|name| d.name() == name))
+            {
+                for register in d.registers() {
+                    values.insert(
+                        format!("{}/{}", d.name(), register.name),
+                        d.read(register).await?,
+                    );
+                }
+            }
+            return Ok(success(&Value::Object(values)));
+        }
+
+        let (device, register) = match resolve(&self.devices, &args) {
+            Ok(found) => found,
+            Err(e) => return Ok(failure(e)),
+        };
+        let value = device.read(register).await?;
+        Ok(success(&json!({
+            "device": device.name(),
+            "register": register.name,
+            "value": value,
+            "unit": register.unit,
+        })))
+    }
+}
+
+/// Tool: write a named, writable Modbus register.
+pub struct ModbusWriteTool {
+    devices: Vec<Arc<ModbusDevice>>,
+    security: Arc<SecurityPolicy>,
+    description: String,
+}
+
+impl ModbusWriteTool {
+    pub fn new(devices: Vec<Arc<ModbusDevice>>, security: Arc<SecurityPolicy>) -> Self {
+        let description = format!(
+            "Write a value (engineering units) to a writable Modbus register or coil. Registers marked as requiring approval need approved=true.\n{}",
+            describe_registers(&devices, true)
+        );
+        Self {
+            devices,
+            security,
+            description,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ModbusWriteTool {
+    fn name(&self) -> &str {
+        "modbus_write"
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "register": {
+                    "type": "string",
+                    "description": "Writable register name from the map"
+                },
+                "value": {
+                    "description": "New value in engineering units (number), or true/false for coils"
+                },
+                "device": {
+                    "type": "string",
+                    "description": "Device name; needed only when several devices map the same register name"
+                },
+                "approved": {
+                    "type": "boolean",
+                    "description": "Set true once the user has approved this write"
+                }
+            },
+            "required": ["register", "value"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let (device, register) = match resolve(&self.devices, &args) {
+            Ok(found) => found,
+            Err(e) => return Ok(failure(e)),
+        };
+        if !register.writable {
+            return Ok(failure(format!(
+                "Modbus register '{}' is read-only",
+                register.name
+            )));
+        }
+        let Some(value) = args.get("value") else {
+            return Ok(failure("Missing 'value' parameter"));
+        };
+
+        let operation = format!("modbus_write {}/{}", device.name(), register.name);
+        if register.requires_approval {
+            let approved = args
+                .get("approved")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            if let Err(e) = self.security.enforce_approval(&operation, approved) {
+                return Ok(failure(e));
+            }
+        }
+        if let Err(e) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "modbus_write")
+        {
+            return Ok(failure(e));
+        }
+
+        if let Err(e) = from_engineering(register, value) {
+            return Ok(failure(e.to_string()));
+        }
+        device.write(register, value).await?;
+        tracing::info!(device = %device.name(), register = %register.name, %value, "Modbus write");
+        Ok(success(&json!({
+            "device": device.name(),
+            "register": register.name,
+            "written": value,
+            "unit": register.unit,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peripherals::modbus_sim::ModbusSimulator;
+
+    fn register(
+        name: &str,
+        kind: ModbusRegisterKind,
+        data_type: ModbusDataType,
+    ) -> ModbusRegisterConfig {
+        ModbusRegisterConfig {
+            name: name.into(),
+            kind,
+            address: 0,
+            data_type,
+            scale: 1.0,
+            offset: 0.0,
+            unit: None,
+            description: None,
+            writable: false,
+            requires_approval: false,
+            min: None,
+            max: None,
+        }
+    }
+
+    #[test]
+    fn crc16_matches_reference_frame() {
+        // Read holding registers 0..2 from unit 1: 01 03 00 00 00 02 C4 0B
+        let frame = [0x01, 0x03, 0x00, 0x00, 0x00, 0x02];
+        assert_eq!(crc16(&frame).to_le_bytes(), [0xC4, 0x0B]);
+    }
+
+    #[test]
+    fn values_round_trip_through_registers() {
+        for (data_type, raw) in [
+            (ModbusDataType::U16, 65_535.0),
+            (ModbusDataType::I16, -1234.0),
+            (ModbusDataType::U32, 4_000_000_000.0),
+            (ModbusDataType::I32, -70_000.0),
+            (ModbusDataType::F32, 21.5),
+        ] {
+            for order in [ModbusWordOrder::Big, ModbusWordOrder::Little] {
+                let words = encode_raw(raw, data_type, order).unwrap();
+                assert_eq!(words.len(), usize::from(register_count(data_type)));
+                assert_eq!(decode_raw(&words, data_type, order), raw, "{data_type:?}");
+            }
+        }
+        assert_eq!(
+            encode_raw(1.5, ModbusDataType::F32, ModbusWordOrder::Big).unwrap(),
+            vec![0x3FC0, 0x0000]
+        );
+        assert_eq!(
+            encode_raw(1.5, ModbusDataType::F32, ModbusWordOrder::Little).unwrap(),
+            vec![0x0000, 0x3FC0]
+        );
+        assert!(encode_raw(70_000.0, ModbusDataType::U16, ModbusWordOrder::Big).is_err());
+        assert!(encode_raw(-1.0, ModbusDataType::U32, ModbusWordOrder::Big).is_err());
+    }
+
+    #[test]
+    fn scaling_and_limits_apply_to_engineering_values() {
+        let mut temp = register("temp", ModbusRegisterKind::Holding, ModbusDataType::I16);
+        temp.scale = 0.1;
+        temp.offset = -40.0;
+        temp.min = Some(0.0);
+        temp.max = Some(80.0);
+
+        assert_eq!(to_engineering(&temp, 634.0), json!(23.4));
+        assert_eq!(
+            from_engineering(&temp, &json!(23.4)).unwrap().round(),
+            634.0
+        );
+        assert!(from_engineering(&temp, &json!(81)).is_err());
+        assert!(from_engineering(&temp, &json!("hot")).is_err());
+
+        let count = register("count", ModbusRegisterKind::Input, ModbusDataType::U16);
+        assert_eq!(to_engineering(&count, 42.0), json!(42));
+    }
+
+    #[test]
+    fn register_map_validation_rejects_unsafe_maps() {
+        let mut config = ModbusConfig::default();
+        let mut input = register("level", ModbusRegisterKind::Input, ModbusDataType::U16);
+        input.writable = true;
+        config.registers = vec![input];
+        assert!(validate_register_map(&config).is_err());
+
+        let a = register("a", ModbusRegisterKind::Holding, ModbusDataType::U16);
+        config.registers = vec![a.clone(), a];
+        assert!(validate_register_map(&config).is_err());
+
+        let mut wide = register("wide", ModbusRegisterKind::Holding, ModbusDataType::F32);
+        wide.address = u16::MAX;
+        config.registers = vec![wide];
+        assert!(validate_register_map(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn rtu_client_talks_to_simulator() {
+        let sim = Arc::new(ModbusSimulator::new(7));
+        sim.set_holding(10, 0x3FC0);
+        sim.set_holding(11, 0x0000);
+        sim.set_coil(3, false);
+        let (host, device) = tokio::io::duplex(1024);
+        tokio::spawn(sim.clone().serve_rtu(device));
+
+        let config = ModbusConfig {
+            unit_id: 7,
+            ..ModbusConfig::default()
+        };
+        let client = ModbusClient::with_link(Framing::Rtu, &config, Box::new(host));
+        assert_eq!(
+            client
+                .read_registers(READ_HOLDING_REGISTERS, 10, 2)
+                .await
+                .unwrap(),
+            vec![0x3FC0, 0x0000]
+        );
+        client.write_coil(3, true).await.unwrap();
+        assert_eq!(sim.coil(3), Some(true));
+        client.write_registers(10, &[1, 2]).await.unwrap();
+        assert_eq!(sim.holding(11), Some(2));
+
+        let err = client
+            .read_registers(READ_HOLDING_REGISTERS, 500, 1)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("illegal data address"), "{err}");
+    }
+}
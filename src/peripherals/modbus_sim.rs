@@ -0,0 +1,260 @@
+//! Simulated Modbus unit for development and tests.
+//!
+//! [`ModbusSimulator`] holds coil, discrete input, input and holding register
+//! tables and answers Modbus TCP (on a local listener) or RTU (on any byte
+//! stream). Reads or writes of addresses that were never set answer with
+//! exception 2 (illegal data address), like a device with a fixed map.
+
+use super::modbus::{
+    crc16, EXCEPTION_FLAG, MAX_PDU_LEN, READ_COILS, READ_DISCRETE_INPUTS, READ_HOLDING_REGISTERS,
+    READ_INPUT_REGISTERS, WRITE_MULTIPLE_REGISTERS, WRITE_SINGLE_COIL, WRITE_SINGLE_REGISTER,
+};
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+const ILLEGAL_FUNCTION: u8 = 0x01;
+const ILLEGAL_DATA_ADDRESS: u8 = 0x02;
+const ILLEGAL_DATA_VALUE: u8 = 0x03;
+
+#[derive(Default)]
+struct Tables {
+    coils: BTreeMap<u16, bool>,
+    discrete: BTreeMap<u16, bool>,
+    input: BTreeMap<u16, u16>,
+    holding: BTreeMap<u16, u16>,
+}
+
+/// In-memory Modbus unit.
+pub struct ModbusSimulator {
+    unit_id: u8,
+    tables: Mutex<Tables>,
+}
+
+/// A simulator served on a local TCP port until dropped.
+pub struct ModbusTcpServer {
+    addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl ModbusTcpServer {
+    /// Address for `path = ...` in the board config.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for ModbusTcpServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl ModbusSimulator {
+    /// A unit with empty tables; every address is unmapped until set.
+    pub fn new(unit_id: u8) -> Self {
+        Self {
+            unit_id,
+            tables: Mutex::new(Tables::default()),
+        }
+    }
+
+    pub fn set_coil(&self, address: u16, value: bool) {
+        self.tables.lock().coils.insert(address, value);
+    }
+
+    pub fn set_discrete(&self, address: u16, value: bool) {
+        self.tables.lock().discrete.insert(address, value);
+    }
+
+    pub fn set_input(&self, address: u16, value: u16) {
+        self.tables.lock().input.insert(address, value);
+    }
+
+    pub fn set_holding(&self, address: u16, value: u16) {
+        self.tables.lock().holding.insert(address, value);
+    }
+
+    pub fn coil(&self, address: u16) -> Option<bool> {
+        self.tables.lock().coils.get(&address).copied()
+    }
+
+    pub fn holding(&self, address: u16) -> Option<u16> {
+        self.tables.lock().holding.get(&address).copied()
+    }
+
+    /// Listen on `addr` (e.g. "127.0.0.1:0") and answer Modbus TCP.
+    pub async fn bind_tcp(self: &Arc<Self>, addr: &str) -> std::io::Result<ModbusTcpServer> {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let sim = self.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(sim.clone().serve_tcp(stream));
+            }
+        });
+        Ok(ModbusTcpServer { addr, task })
+    }
+
+    /// Answer Modbus TCP frames on one connection until it closes.
+    pub async fn serve_tcp(
+        self: Arc<Self>,
+        mut stream: impl AsyncRead + AsyncWrite + Unpin,
+    ) -> std::io::Result<()> {
+        loop {
+            let mut header = [0u8; 7];
+            if stream.read_exact(&mut header).await.is_err() {
+                return Ok(());
+            }
+            let length = usize::from(u16::from_be_bytes([header[4], header[5]]));
+            if !(2..=MAX_PDU_LEN + 1).contains(&length) {
+                return Ok(());
+            }
+            let mut pdu = vec![0u8; length - 1];
+            stream.read_exact(&mut pdu).await?;
+
+            let response = self.handle_pdu(&pdu);
+            let mut frame = header[..4].to_vec();
+            frame.extend_from_slice(&u16::try_from(response.len() + 1).unwrap_or(0).to_be_bytes());
+            frame.push(header[6]);
+            frame.extend_from_slice(&response);
+            stream.write_all(&frame).await?;
+            stream.flush().await?;
+        }
+    }
+
+    /// Answer Modbus RTU frames addressed to this unit until the stream
+    /// closes. Frames with a bad CRC are ignored, as on a real bus.
+    pub async fn serve_rtu(
+        self: Arc<Self>,
+        mut stream: impl AsyncRead + AsyncWrite + Unpin,
+    ) -> std::io::Result<()> {
+        loop {
+            let mut head = [0u8; 2];
+            if stream.read_exact(&mut head).await.is_err() {
+                return Ok(());
+            }
+            let mut frame = head.to_vec();
+            let mut fixed = [0u8; 4];
+            stream.read_exact(&mut fixed).await?;
+            frame.extend_from_slice(&fixed);
+            if head[1] == WRITE_MULTIPLE_REGISTERS {
+                let count = stream.read_u8().await?;
+                let mut data = vec![0u8; usize::from(count)];
+                stream.read_exact(&mut data).await?;
+                frame.push(count);
+                frame.extend_from_slice(&data);
+            }
+            let mut crc = [0u8; 2];
+            stream.read_exact(&mut crc).await?;
+            if crc16(&frame).to_le_bytes() != crc || frame[0] != self.unit_id {
+                continue;
+            }
+
+            let mut reply = vec![self.unit_id];
+            reply.extend_from_slice(&self.handle_pdu(&frame[1..]));
+            reply.extend_from_slice(&crc16(&reply).to_le_bytes());
+            stream.write_all(&reply).await?;
+            stream.flush().await?;
+        }
+    }
+
+    /// Response PDU for a request PDU.
+    pub fn handle_pdu(&self, pdu: &[u8]) -> Vec<u8> {
+        let function = pdu.first().copied().unwrap_or(0);
+        match self.execute(pdu) {
+            Ok(response) => response,
+            Err(code) => vec![function | EXCEPTION_FLAG, code],
+        }
+    }
+
+    fn execute(&self, pdu: &[u8]) -> Result<Vec<u8>, u8> {
+        if pdu.len() < 5 {
+            return Err(ILLEGAL_DATA_VALUE);
+        }
+        let function = pdu[0];
+        let address = u16::from_be_bytes([pdu[1], pdu[2]]);
+        let field = u16::from_be_bytes([pdu[3], pdu[4]]);
+        let addresses = || address..address.saturating_add(field);
+        let mut tables = self.tables.lock();
+
+        match function {
+            READ_COILS | READ_DISCRETE_INPUTS => {
+                let table = if function == READ_COILS {
+                    &tables.coils
+                } else {
+                    &tables.discrete
+                };
+                let bits = addresses()
+                    .map(|a| table.get(&a).copied().ok_or(ILLEGAL_DATA_ADDRESS))
+                    .collect::<Result<Vec<bool>, u8>>()?;
+                let mut bytes = vec![0u8; bits.len().div_ceil(8)];
+                for (i, bit) in bits.iter().enumerate() {
+                    if *bit {
+                        bytes[i / 8] |= 1 << (i % 8);
+                    }
+                }
+                let mut response = vec![
+                    function,
+                    u8::try_from(bytes.len()).map_err(|_| ILLEGAL_DATA_VALUE)?,
+                ];
+                response.extend_from_slice(&bytes);
+                Ok(response)
+            }
+            READ_HOLDING_REGISTERS | READ_INPUT_REGISTERS => {
+                let table = if function == READ_HOLDING_REGISTERS {
+                    &tables.holding
+                } else {
+                    &tables.input
+                };
+                let words = addresses()
+                    .map(|a| table.get(&a).copied().ok_or(ILLEGAL_DATA_ADDRESS))
+                    .collect::<Result<Vec<u16>, u8>>()?;
+                let count = u8::try_from(words.len() * 2).map_err(|_| ILLEGAL_DATA_VALUE)?;
+                let mut response = vec![function, count];
+                for word in words {
+                    response.extend_from_slice(&word.to_be_bytes());
+                }
+                Ok(response)
+            }
+            WRITE_SINGLE_COIL => {
+                let value = match field {
+                    0xFF00 => true,
+                    0x0000 => false,
+                    _ => return Err(ILLEGAL_DATA_VALUE),
+                };
+                let coil = tables.coils.get_mut(&address).ok_or(ILLEGAL_DATA_ADDRESS)?;
+                *coil = value;
+                Ok(pdu[..5].to_vec())
+            }
+            WRITE_SINGLE_REGISTER => {
+                let register = tables
+                    .holding
+                    .get_mut(&address)
+                    .ok_or(ILLEGAL_DATA_ADDRESS)?;
+                *register = field;
+                Ok(pdu[..5].to_vec())
+            }
+            WRITE_MULTIPLE_REGISTERS => {
+                let data = pdu.get(6..).ok_or(ILLEGAL_DATA_VALUE)?;
+                if data.len() != usize::from(field) * 2 {
+                    return Err(ILLEGAL_DATA_VALUE);
+                }
+                if addresses().any(|a| !tables.holding.contains_key(&a)) {
+                    return Err(ILLEGAL_DATA_ADDRESS);
+                }
+                for (a, word) in addresses().zip(data.chunks_exact(2)) {
+                    tables
+                        .holding
+                        .insert(a, u16::from_be_bytes([word[0], word[1]]));
+                }
+                Ok(pdu[..5].to_vec())
+            }
+            _ => Err(ILLEGAL_FUNCTION),
+        }
+    }
+}
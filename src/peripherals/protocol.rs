@@ -0,0 +1,376 @@
+//! Serial protocol versions and feature negotiation.
+//!
+//! Version 1 firmware answers `capabilities` with `{"gpio":[...],"led_pin":13}`
+//! and implements only `ping`, `gpio_read` and `gpio_write`. Version 2 adds a
+//! `protocol` number and a `features` list to that reply; each feature unlocks
+//! a group of commands and names the pins or buses it may be used with:
+//!
+//! | Feature | Commands                            | Resources key |
+//! |---------|-------------------------------------|---------------|
+//! | `adc`   | `adc_read`                          | `adc` (pins)  |
+//! | `pwm`   | `pwm_set`                           | `pwm` (pins)  |
+//! | `i2c`   | `i2c_scan`, `i2c_read`, `i2c_write` | `i2c` (buses) |
+//! | `spi`   | `spi_transfer`                      | `spi` (buses) |
+//! | `uart`  | `uart_transfer`                     | `uart` (buses)|
+//!
+//! The host sends `{"protocol":2}` as the `capabilities` arguments; older
+//! firmware ignores it. Tools are only generated for advertised features.
+
+use crate::config::PeripheralBoardConfig;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Highest protocol version this host speaks.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// Largest payload for one bus transfer; firmware line buffers are small.
+pub const MAX_TRANSFER_BYTES: usize = 64;
+
+/// Optional command groups a board can advertise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    Adc,
+    Pwm,
+    I2c,
+    Spi,
+    Uart,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 5] = [
+        Feature::Adc,
+        Feature::Pwm,
+        Feature::I2c,
+        Feature::Spi,
+        Feature::Uart,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Feature::Adc => "adc",
+            Feature::Pwm => "pwm",
+            Feature::I2c => "i2c",
+            Feature::Spi => "spi",
+            Feature::Uart => "uart",
+        }
+    }
+
+    /// Whether the feature's resources are pins (as opposed to bus indexes).
+    fn uses_pins(self) -> bool {
+        matches!(self, Feature::Adc | Feature::Pwm)
+    }
+}
+
+fn legacy_protocol() -> u32 {
+    1
+}
+
+/// Parsed `capabilities` reply.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DeviceCapabilities {
+    #[serde(default = "legacy_protocol")]
+    pub protocol: u32,
+    #[serde(default)]
+    pub gpio: Vec<u32>,
+    #[serde(default)]
+    pub led_pin: Option<u32>,
+    #[serde(default)]
+    pub features: Vec<String>,
+    #[serde(default)]
+    pub adc: Vec<u32>,
+    #[serde(default)]
+    pub pwm: Vec<u32>,
+    #[serde(default)]
+    pub i2c: Vec<u32>,
+    #[serde(default)]
+    pub spi: Vec<u32>,
+    #[serde(default)]
+    pub uart: Vec<u32>,
+}
+
+impl Default for DeviceCapabilities {
+    /// A version 1 board that did not report anything.
+    fn default() -> Self {
+        Self {
+            protocol: legacy_protocol(),
+            gpio: Vec::new(),
+            led_pin: None,
+            features: Vec::new(),
+            adc: Vec::new(),
+            pwm: Vec::new(),
+            i2c: Vec::new(),
+            spi: Vec::new(),
+            uart: Vec::new(),
+        }
+    }
+}
+
+impl DeviceCapabilities {
+    /// Parse the `result` of a `capabilities` response.
+    pub fn parse(result: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(result.trim())?)
+    }
+
+    /// Whether the board advertises `feature` over a protocol that has it.
+    pub fn supports(&self, feature: Feature) -> bool {
+        self.protocol >= 2
+            && self
+                .features
+                .iter()
+                .any(|f| f.eq_ignore_ascii_case(feature.as_str()))
+    }
+
+    /// Pins or buses the board offers for `feature`.
+    pub fn resources(&self, feature: Feature) -> &[u32] {
+        match feature {
+            Feature::Adc => &self.adc,
+            Feature::Pwm => &self.pwm,
+            Feature::I2c => &self.i2c,
+            Feature::Spi => &self.spi,
+            Feature::Uart => &self.uart,
+        }
+    }
+
+    /// Features that tools will be generated for.
+    pub fn supported_features(&self) -> Vec<Feature> {
+        Feature::ALL
+            .into_iter()
+            .filter(|f| self.supports(*f))
+            .collect()
+    }
+
+    /// One-line description for `hardware_capabilities`.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![
+            format!("protocol {}", self.protocol),
+            format!("gpio {:?}", self.gpio),
+        ];
+        if let Some(led) = self.led_pin {
+            parts.push(format!("led_pin {led}"));
+        }
+        for feature in self.supported_features() {
+            let label = if feature.uses_pins() { "pins" } else { "buses" };
+            parts.push(format!(
+                "{} {label} {:?}",
+                feature.as_str(),
+                self.resources(feature)
+            ));
+        }
+        parts.join(", ")
+    }
+}
+
+/// What the agent may touch on one board: the resources the device advertises,
+/// narrowed by the allowlists in its `PeripheralBoardConfig`.
+#[derive(Debug, Clone)]
+pub struct BoardAccess {
+    capabilities: DeviceCapabilities,
+    allowed_pins: Vec<u32>,
+    allowed_buses: Vec<u32>,
+    allowed_i2c_addresses: Vec<u8>,
+}
+
+impl BoardAccess {
+    pub fn new(capabilities: DeviceCapabilities, config: &PeripheralBoardConfig) -> Self {
+        Self {
+            capabilities,
+            allowed_pins: config.allowed_pins.clone(),
+            allowed_buses: config.allowed_buses.clone(),
+            allowed_i2c_addresses: config.allowed_i2c_addresses.clone(),
+        }
+    }
+
+    pub fn capabilities(&self) -> &DeviceCapabilities {
+        &self.capabilities
+    }
+
+    /// Validate the `pin` argument of a GPIO command.
+    pub fn gpio_pin(&self, args: &Value) -> anyhow::Result<u32> {
+        let pin = required_u32(args, "pin")?;
+        check_allowed("GPIO pin", pin, &self.capabilities.gpio, &self.allowed_pins)?;
+        Ok(pin)
+    }
+
+    /// Validate the `pin` argument of an ADC or PWM command.
+    pub fn feature_pin(&self, feature: Feature, args: &Value) -> anyhow::Result<u32> {
+        let pin = required_u32(args, "pin")?;
+        let label = format!("{} pin", feature.as_str().to_uppercase());
+        check_allowed(
+            &label,
+            pin,
+            self.capabilities.resources(feature),
+            &self.allowed_pins,
+        )?;
+        Ok(pin)
+    }
+
+    /// Validate the optional `bus` argument (default 0) of an I2C, SPI or
+    /// UART command.
+    pub fn bus(&self, feature: Feature, args: &Value) -> anyhow::Result<u32> {
+        let bus = match args.get("bus") {
+            None | Some(Value::Null) => 0,
+            Some(_) => required_u32(args, "bus")?,
+        };
+        let label = format!("{} bus", feature.as_str().to_uppercase());
+        if !self.capabilities.resources(feature).contains(&bus) {
+            anyhow::bail!("{label} {bus} is not available on this board");
+        }
+        if !self.allowed_buses.is_empty() && !self.allowed_buses.contains(&bus) {
+            anyhow::bail!("{label} {bus} is not in allowed_buses for this board");
+        }
+        Ok(bus)
+    }
+
+    /// Validate the 7-bit `address` argument of an I2C command.
+    pub fn i2c_address(&self, args: &Value) -> anyhow::Result<u8> {
+        let address = required_u32(args, "address")?;
+        let address = u8::try_from(address)
+            .ok()
+            .filter(|a| *a <= 0x7f)
+            .ok_or_else(|| anyhow::anyhow!("I2C address must be 0x00-0x7f, got {address}"))?;
+        if !self.allowed_i2c_addresses.is_empty() && !self.allowed_i2c_addresses.contains(&address)
+        {
+            anyhow::bail!("I2C address 0x{address:02x} is not in allowed_i2c_addresses");
+        }
+        Ok(address)
+    }
+}
+
+fn required_u32(args: &Value, key: &str) -> anyhow::Result<u32> {
+    let value = args
+        .get(key)
+        .and_then(Value::as_u64)
+        .ok_or_else(|| anyhow::anyhow!("Missing '{key}' parameter"))?;
+    u32::try_from(value).map_err(|_| anyhow::anyhow!("'{key}' out of range: {value}"))
+}
+
+/// A pin must be advertised by the device (when it reports a list) and
+/// allowed by config (when it has an allowlist).
+fn check_allowed(label: &str, pin: u32, advertised: &[u32], allowed: &[u32]) -> anyhow::Result<()> {
+    if !advertised.is_empty() && !advertised.contains(&pin) {
+        anyhow::bail!("{label} {pin} is not available on this board");
+    }
+    if !allowed.is_empty() && !allowed.contains(&pin) {
+        anyhow::bail!("{label} {pin} is not in allowed_pins for this board");
+    }
+    Ok(())
+}
+
+/// Read a byte array argument (`[0, 255, ...]`), capped at
+/// [`MAX_TRANSFER_BYTES`].
+pub fn byte_array(args: &Value, key: &str) -> anyhow::Result<Vec<u8>> {
+    let items = args
+        .get(key)
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow::anyhow!("Missing '{key}' parameter (array of bytes)"))?;
+    if items.len() > MAX_TRANSFER_BYTES {
+        anyhow::bail!(
+            "'{key}' has {} bytes; at most {MAX_TRANSFER_BYTES} per transfer",
+            items.len()
+        );
+    }
+    items
+        .iter()
+        .map(|item| {
+            item.as_u64()
+                .and_then(|b| u8::try_from(b).ok())
+                .ok_or_else(|| anyhow::anyhow!("'{key}' must contain bytes (0-255), got {item}"))
+        })
+        .collect()
+}
+
+/// Read an optional length argument, defaulting to `default` and capped at
+/// [`MAX_TRANSFER_BYTES`].
+pub fn transfer_len(args: &Value, key: &str, default: usize) -> anyhow::Result<usize> {
+    let len = match args.get(key).and_then(Value::as_u64) {
+        Some(len) => usize::try_from(len).unwrap_or(usize::MAX),
+        None => default,
+    };
+    if len == 0 || len > MAX_TRANSFER_BYTES {
+        anyhow::bail!("'{key}' must be 1-{MAX_TRANSFER_BYTES}, got {len}");
+    }
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn v2_capabilities() -> DeviceCapabilities {
+        DeviceCapabilities::parse(
+            r#"{"protocol":2,"gpio":[2,13],"led_pin":2,"features":["adc","i2c"],"adc":[34],"i2c":[0]}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn legacy_capabilities_have_no_features() {
+        let caps = DeviceCapabilities::parse(r#"{"gpio":[0,1,13],"led_pin":13}"#).unwrap();
+        assert_eq!(caps.protocol, 1);
+        assert!(caps.supported_features().is_empty());
+        assert_eq!(caps.summary(), "protocol 1, gpio [0, 1, 13], led_pin 13");
+    }
+
+    #[test]
+    fn features_require_protocol_two() {
+        let caps = v2_capabilities();
+        assert_eq!(caps.supported_features(), vec![Feature::Adc, Feature::I2c]);
+
+        let old = DeviceCapabilities {
+            protocol: 1,
+            ..caps
+        };
+        assert!(!old.supports(Feature::Adc));
+    }
+
+    #[test]
+    fn config_allowlist_narrows_advertised_pins() {
+        let config = PeripheralBoardConfig {
+            allowed_pins: vec![13, 34],
+            ..PeripheralBoardConfig::default()
+        };
+        let access = BoardAccess::new(v2_capabilities(), &config);
+
+        assert_eq!(access.gpio_pin(&json!({"pin": 13})).unwrap(), 13);
+        assert!(access.gpio_pin(&json!({"pin": 2})).is_err());
+        assert!(access.gpio_pin(&json!({"pin": 5})).is_err());
+        assert_eq!(
+            access
+                .feature_pin(Feature::Adc, &json!({"pin": 34}))
+                .unwrap(),
+            34
+        );
+        assert!(access
+            .feature_pin(Feature::Adc, &json!({"pin": 13}))
+            .is_err());
+    }
+
+    #[test]
+    fn buses_and_i2c_addresses_are_checked() {
+        let config = PeripheralBoardConfig {
+            allowed_i2c_addresses: vec![0x3c],
+            ..PeripheralBoardConfig::default()
+        };
+        let access = BoardAccess::new(v2_capabilities(), &config);
+
+        assert_eq!(access.bus(Feature::I2c, &json!({})).unwrap(), 0);
+        assert!(access.bus(Feature::I2c, &json!({"bus": 1})).is_err());
+        assert!(access.bus(Feature::Spi, &json!({})).is_err());
+        assert_eq!(access.i2c_address(&json!({"address": 60})).unwrap(), 0x3c);
+        assert!(access.i2c_address(&json!({"address": 0x3d})).is_err());
+        assert!(access.i2c_address(&json!({"address": 200})).is_err());
+    }
+
+    #[test]
+    fn byte_arguments_are_validated() {
+        assert_eq!(
+            byte_array(&json!({"data": [1, 255]}), "data").unwrap(),
+            vec![1, 255]
+        );
+        assert!(byte_array(&json!({"data": [256]}), "data").is_err());
+        assert!(byte_array(&json!({"data": vec![0; 65]}), "data").is_err());
+        assert_eq!(transfer_len(&json!({}), "len", 1).unwrap(), 1);
+        assert!(transfer_len(&json!({"len": 0}), "len", 1).is_err());
+    }
+}
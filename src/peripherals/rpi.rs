@@ -1,23 +1,38 @@
 //! Raspberry Pi GPIO peripheral — native rppal access.
 //!
 //! Only compiled when `peripheral-rpi` feature is enabled and target is Linux.
-//! Uses BCM pin numbering (e.g. GPIO 17, 27).
+//! Uses BCM pin numbering (e.g. GPIO 17, 27). Pins listed in `watch_pins`
+//! are claimed as inputs with edge interrupts and reported as `edge` events;
+//! the GPIO tools cannot use them while connected.
 
 use crate::config::PeripheralBoardConfig;
-use crate::peripherals::traits::Peripheral;
+use crate::peripherals::traits::{Peripheral, PeripheralEvent};
 use crate::tools::{Tool, ToolResult};
 use async_trait::async_trait;
+use rppal::gpio::{Gpio, InputPin, Trigger};
 use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Contact bounce filtered out by rppal before an edge is reported.
+const EDGE_DEBOUNCE: Duration = Duration::from_millis(5);
 
 /// RPi GPIO peripheral — direct access via rppal.
 pub struct RpiGpioPeripheral {
     board: PeripheralBoardConfig,
+    events: broadcast::Sender<PeripheralEvent>,
+    /// Pins with edge interrupts; dropping one stops its interrupt thread.
+    watched: parking_lot::Mutex<Vec<InputPin>>,
 }
 
 impl RpiGpioPeripheral {
     /// Create a new RPi GPIO peripheral from config.
     pub fn new(board: PeripheralBoardConfig) -> Self {
-        Self { board }
+        Self {
+            board,
+            events: broadcast::channel(64).0,
+            watched: parking_lot::Mutex::new(Vec::new()),
+        }
     }
 
     /// Attempt to connect (init rppal). Returns Ok if GPIO is available.
@@ -42,10 +57,20 @@ impl Peripheral for RpiGpioPeripheral {
         // Verify GPIO is accessible by doing a no-op init
         let result = tokio::task::spawn_blocking(|| rppal::gpio::Gpio::new()).await??;
         drop(result);
+
+        if !self.board.watch_pins.is_empty() {
+            let board = self.board.board.clone();
+            let pins = self.board.watch_pins.clone();
+            let events = self.events.clone();
+            let watched =
+                tokio::task::spawn_blocking(move || watch_edges(&board, &pins, &events)).await??;
+            *self.watched.lock() = watched;
+        }
         Ok(())
     }
 
     async fn disconnect(&mut self) -> anyhow::Result<()> {
+        self.watched.lock().clear();
         Ok(())
     }
 
@@ -58,6 +83,40 @@ impl Peripheral for RpiGpioPeripheral {
     fn tools(&self) -> Vec<Box<dyn Tool>> {
         vec![Box::new(RpiGpioReadTool), Box::new(RpiGpioWriteTool)]
     }
+
+    fn events(&self) -> Option<broadcast::Receiver<PeripheralEvent>> {
+        (!self.board.watch_pins.is_empty()).then(|| self.events.subscribe())
+    }
+}
+
+/// Claim `pins` as inputs and report both edges as `edge` events
+/// (value 1 = rising, 0 = falling).
+fn watch_edges(
+    board: &str,
+    pins: &[u32],
+    events: &broadcast::Sender<PeripheralEvent>,
+) -> anyhow::Result<Vec<InputPin>> {
+    let gpio = Gpio::new()?;
+    pins.iter()
+        .map(|&pin| {
+            let bcm = u8::try_from(pin)
+                .map_err(|_| anyhow::anyhow!("Invalid BCM pin in watch_pins: {pin}"))?;
+            let mut input = gpio.get(bcm)?.into_input();
+            let board = board.to_string();
+            let events = events.clone();
+            input.set_async_interrupt(Trigger::Both, Some(EDGE_DEBOUNCE), move |event| {
+                let value = u8::from(event.trigger == Trigger::RisingEdge);
+                // Nobody listening is fine; events are fire-and-forget.
+                let _ = events.send(PeripheralEvent {
+                    board: board.clone(),
+                    event: "edge".into(),
+                    pin: Some(pin),
+                    value: Some(json!(value)),
+                });
+            })?;
+            Ok(input)
+        })
+        .collect()
 }
 
 /// Tool: read GPIO pin value (BCM numbering).
@@ -3,17 +3,27 @@
 //! Protocol: newline-delimited JSON.
 //! Request:  {"id":"1","cmd":"gpio_write","args":{"pin":13,"value":1}}
 //! Response: {"id":"1","ok":true,"result":"done"}
+//! Event:    {"event":"button","pin":2,"value":1}   (unsolicited, any time)
+//!
+//! Commands beyond GPIO are negotiated from `capabilities`; see
+//! [`super::protocol`].
 
-use super::traits::Peripheral;
+use super::bus_tools;
+use super::protocol::{BoardAccess, DeviceCapabilities, PROTOCOL_VERSION};
+use super::traits::{Peripheral, PeripheralEvent};
 use crate::config::PeripheralBoardConfig;
 use crate::tools::traits::{Tool, ToolResult};
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::sync::Mutex;
-use tokio_serial::{SerialPortBuilderExt, SerialStream};
+use std::time::Duration;
+use tokio::io::{
+    AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, ReadHalf, WriteHalf,
+};
+use tokio::sync::{broadcast, oneshot, Mutex};
+use tokio::task::JoinHandle;
+use tokio_serial::SerialPortBuilderExt;
 
 /// Allowed serial path patterns (security: deny arbitrary paths).
 const ALLOWED_PATH_PREFIXES: &[&str] = &[
@@ -26,62 +36,119 @@ const ALLOWED_PATH_PREFIXES: &[&str] = &[
     "COM",               // Windows
 ];
 
-fn is_path_allowed(path: &str) -> bool {
+/// Pseudo-terminals are only accepted for the simulated board
+/// (`zeroclaw peripheral simulate`).
+const SIM_PTY_PREFIX: &str = "/dev/pts/";
+
+pub(crate) fn is_path_allowed(path: &str) -> bool {
     ALLOWED_PATH_PREFIXES.iter().any(|p| path.starts_with(p))
 }
 
-/// JSON request/response over serial.
-async fn send_request(port: &mut SerialStream, cmd: &str, args: Value) -> anyhow::Result<Value> {
-    static ID: AtomicU64 = AtomicU64::new(0);
-    let id = ID.fetch_add(1, Ordering::Relaxed);
-    let id_str = id.to_string();
+fn is_path_allowed_for(board: &str, path: &str) -> bool {
+    is_path_allowed(path) || (board == super::sim::SIM_BOARD && path.starts_with(SIM_PTY_PREFIX))
+}
 
-    let req = json!({
-        "id": id_str,
-        "cmd": cmd,
-        "args": args
-    });
-    let line = format!("{}\n", req);
+/// Byte stream a board is reached over: a serial port, a PTY or an
+/// in-process simulator pipe.
+pub(crate) trait SerialLink: AsyncRead + AsyncWrite + Unpin + Send {}
 
-    port.write_all(line.as_bytes()).await?;
-    port.flush().await?;
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> SerialLink for T {}
 
-    let mut buf = Vec::new();
-    let mut b = [0u8; 1];
-    while port.read_exact(&mut b).await.is_ok() {
-        if b[0] == b'\n' {
-            break;
-        }
-        buf.push(b[0]);
-    }
-    let line_str = String::from_utf8_lossy(&buf);
-    let resp: Value = serde_json::from_str(line_str.trim())?;
-    let resp_id = resp["id"].as_str().unwrap_or("");
-    if resp_id != id_str {
-        anyhow::bail!("Response id mismatch: expected {}, got {}", id_str, resp_id);
-    }
-    Ok(resp)
-}
+/// Reply slot for the request currently in flight.
+type PendingReply = Arc<parking_lot::Mutex<Option<oneshot::Sender<anyhow::Result<Value>>>>>;
+
+/// Capacity of the per-board event channel; slow subscribers miss the oldest.
+const EVENT_CHANNEL_CAPACITY: usize = 64;
 
 /// Shared serial transport for tools. Pub(crate) for capabilities tool.
+///
+/// A background task owns the read half: `{"event":...}` lines go to event
+/// subscribers, everything else answers the request in flight. Requests are
+/// serialized by the writer lock.
 pub(crate) struct SerialTransport {
-    port: Mutex<SerialStream>,
+    writer: Mutex<WriteHalf<Box<dyn SerialLink>>>,
+    pending: PendingReply,
+    /// Template for new subscribers. The reader task owns the only sender,
+    /// so subscribers see the stream close with the link.
+    events: broadcast::Receiver<PeripheralEvent>,
+    reader: JoinHandle<()>,
+    timeout: Duration,
 }
 
 /// Timeout for serial request/response (seconds).
 const SERIAL_TIMEOUT_SECS: u64 = 5;
 
 impl SerialTransport {
-    async fn request(&self, cmd: &str, args: Value) -> anyhow::Result<ToolResult> {
-        let mut port = self.port.lock().await;
-        let resp = tokio::time::timeout(
-            std::time::Duration::from_secs(SERIAL_TIMEOUT_SECS),
-            send_request(&mut port, cmd, args),
-        )
-        .await
-        .map_err(|_| {
-            anyhow::anyhow!("Serial request timed out after {}s", SERIAL_TIMEOUT_SECS)
-        })??;
+    /// Start reading from `link`; events are tagged with `board`.
+    pub(crate) fn new(board: &str, link: Box<dyn SerialLink>, timeout: Duration) -> Self {
+        let (reader, writer) = tokio::io::split(link);
+        let pending = PendingReply::default();
+        let (sender, events) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        let reader = tokio::spawn(read_lines(
+            reader,
+            board.to_string(),
+            pending.clone(),
+            sender,
+        ));
+        Self {
+            writer: Mutex::new(writer),
+            pending,
+            events,
+            reader,
+            timeout,
+        }
+    }
+
+    /// Whether the link is still being read (the port has not closed).
+    pub(crate) fn is_open(&self) -> bool {
+        !self.reader.is_finished()
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<PeripheralEvent> {
+        self.events.resubscribe()
+    }
+
+    /// JSON request/response over serial.
+    async fn send_request(&self, cmd: &str, args: Value) -> anyhow::Result<Value> {
+        static ID: AtomicU64 = AtomicU64::new(0);
+        let id = ID.fetch_add(1, Ordering::Relaxed);
+        let id_str = id.to_string();
+
+        let req = json!({
+            "id": id_str,
+            "cmd": cmd,
+            "args": args
+        });
+        let line = format!("{}\n", req);
+
+        let mut writer = self.writer.lock().await;
+        let (tx, rx) = oneshot::channel();
+        *self.pending.lock() = Some(tx);
+
+        let exchange = async {
+            writer.write_all(line.as_bytes()).await?;
+            writer.flush().await?;
+            rx.await
+                .map_err(|_| anyhow::anyhow!("Serial link closed"))?
+        };
+        let resp = match tokio::time::timeout(self.timeout, exchange).await {
+            Ok(resp) => resp?,
+            Err(_) => {
+                // A late reply must not answer the next request.
+                self.pending.lock().take();
+                anyhow::bail!("Serial request timed out after {:?}", self.timeout);
+            }
+        };
+
+        let resp_id = resp["id"].as_str().unwrap_or("");
+        if resp_id != id_str {
+            anyhow::bail!("Response id mismatch: expected {}, got {}", id_str, resp_id);
+        }
+        Ok(resp)
+    }
+
+    pub(crate) async fn request(&self, cmd: &str, args: Value) -> anyhow::Result<ToolResult> {
+        let resp = self.send_request(cmd, args).await?;
 
         let ok = resp["ok"].as_bool().unwrap_or(false);
         let result = resp["result"]
@@ -97,29 +164,122 @@ impl SerialTransport {
         })
     }
 
-    /// Phase C: fetch capabilities from device (gpio pins, led_pin).
+    /// Phase C: fetch capabilities from device (gpio pins, led_pin, features).
     pub async fn capabilities(&self) -> anyhow::Result<ToolResult> {
-        self.request("capabilities", json!({})).await
+        self.request("capabilities", json!({ "protocol": PROTOCOL_VERSION }))
+            .await
+    }
+
+    /// Negotiate the protocol version and features. Boards that fail to
+    /// answer are treated as version 1 (GPIO only).
+    pub async fn negotiate(&self) -> DeviceCapabilities {
+        match self.capabilities().await {
+            Ok(result) if result.success => DeviceCapabilities::parse(&result.output)
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Unparseable capabilities reply ({e}); assuming protocol 1");
+                    DeviceCapabilities::default()
+                }),
+            Ok(result) => {
+                tracing::debug!(
+                    "capabilities not supported ({}); assuming protocol 1",
+                    result.error.as_deref().unwrap_or("unknown")
+                );
+                DeviceCapabilities::default()
+            }
+            Err(e) => {
+                tracing::warn!("capabilities request failed ({e}); assuming protocol 1");
+                DeviceCapabilities::default()
+            }
+        }
+    }
+}
+
+impl Drop for SerialTransport {
+    fn drop(&mut self) {
+        self.reader.abort();
     }
 }
 
+/// Dispatch lines from the board until the link closes.
+async fn read_lines(
+    reader: ReadHalf<Box<dyn SerialLink>>,
+    board: String,
+    pending: PendingReply,
+    events: broadcast::Sender<PeripheralEvent>,
+) {
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf).await {
+            Ok(0) => break,
+            Ok(_) => dispatch_line(&String::from_utf8_lossy(&buf), &board, &pending, &events),
+            Err(e) => {
+                tracing::warn!(board = %board, "Serial read failed: {e}");
+                break;
+            }
+        }
+    }
+    // Wake a request still waiting on a closed link.
+    pending.lock().take();
+}
+
+fn dispatch_line(
+    line: &str,
+    board: &str,
+    pending: &PendingReply,
+    events: &broadcast::Sender<PeripheralEvent>,
+) {
+    let line = line.trim();
+    if line.is_empty() {
+        return;
+    }
+    let parsed = serde_json::from_str::<Value>(line);
+    if let Some(event) = parsed.as_ref().ok().and_then(|v| parse_event(board, v)) {
+        tracing::debug!(board = %board, event = %event.event, "Peripheral event");
+        // No subscribers is fine; events are fire-and-forget.
+        let _ = events.send(event);
+        return;
+    }
+    match pending.lock().take() {
+        Some(reply) => {
+            let _ = reply.send(parsed.map_err(Into::into));
+        }
+        None => tracing::debug!(board = %board, "Dropping unsolicited serial line: {line}"),
+    }
+}
+
+/// An unsolicited line: `{"event":"button","pin":2,"value":1}`.
+fn parse_event(board: &str, line: &Value) -> Option<PeripheralEvent> {
+    let event = line.get("event")?.as_str()?;
+    Some(PeripheralEvent {
+        board: board.to_string(),
+        event: event.to_string(),
+        pin: line
+            .get("pin")
+            .and_then(Value::as_u64)
+            .and_then(|p| u32::try_from(p).ok()),
+        value: line.get("value").cloned(),
+    })
+}
+
 /// Serial peripheral for STM32, Arduino, etc. over USB CDC.
 pub struct SerialPeripheral {
     name: String,
     board_type: String,
     transport: Arc<SerialTransport>,
+    access: Arc<BoardAccess>,
 }
 
 impl SerialPeripheral {
-    /// Create and connect to a serial peripheral.
-    #[allow(clippy::unused_async)]
+    /// Create and connect to a serial peripheral, negotiating its features.
     pub async fn connect(config: &PeripheralBoardConfig) -> anyhow::Result<Self> {
         let path = config
             .path
             .as_deref()
             .ok_or_else(|| anyhow::anyhow!("Serial peripheral requires path"))?;
 
-        if !is_path_allowed(path) {
+        if !is_path_allowed_for(&config.board, path) {
             anyhow::bail!(
                 "Serial path not allowed: {}. Allowed: /dev/ttyACM*, /dev/ttyUSB*, /dev/tty.usbmodem*, /dev/cu.usbmodem*",
                 path
@@ -131,15 +291,35 @@ impl SerialPeripheral {
             .map_err(|e| anyhow::anyhow!("Failed to open {}: {}", path, e))?;
 
         let name = format!("{}-{}", config.board, path.replace('/', "_"));
-        let transport = Arc::new(SerialTransport {
-            port: Mutex::new(port),
-        });
+        let transport = SerialTransport::new(
+            &config.board,
+            Box::new(port),
+            Duration::from_secs(SERIAL_TIMEOUT_SECS),
+        );
+        Ok(Self::attach(name, config, transport).await)
+    }
 
-        Ok(Self {
-            name: name.clone(),
+    /// Wrap an already-open transport, negotiating the board's features.
+    pub(crate) async fn attach(
+        name: String,
+        config: &PeripheralBoardConfig,
+        transport: SerialTransport,
+    ) -> Self {
+        let transport = Arc::new(transport);
+        let capabilities = transport.negotiate().await;
+        tracing::info!(
+            board = %config.board,
+            "Serial peripheral capabilities: {}",
+            capabilities.summary()
+        );
+        let access = Arc::new(BoardAccess::new(capabilities, config));
+
+        Self {
+            name,
             board_type: config.board.clone(),
             transport,
-        })
+            access,
+        }
     }
 }
 
@@ -169,15 +349,29 @@ impl Peripheral for SerialPeripheral {
             .unwrap_or(false)
     }
 
+    fn events(&self) -> Option<broadcast::Receiver<PeripheralEvent>> {
+        Some(self.transport.subscribe())
+    }
+
     fn tools(&self) -> Vec<Box<dyn Tool>> {
-        vec![
+        let mut tools: Vec<Box<dyn Tool>> = vec![
             Box::new(GpioReadTool {
                 transport: self.transport.clone(),
+                access: self.access.clone(),
             }),
             Box::new(GpioWriteTool {
                 transport: self.transport.clone(),
+                access: self.access.clone(),
             }),
-        ]
+        ];
+        for feature in self.access.capabilities().supported_features() {
+            tools.extend(bus_tools::feature_tools(
+                feature,
+                &self.transport,
+                &self.access,
+            ));
+        }
+        tools
     }
 }
 
@@ -191,6 +385,7 @@ impl SerialPeripheral {
 /// Tool: read GPIO pin value.
 struct GpioReadTool {
     transport: Arc<SerialTransport>,
+    access: Arc<BoardAccess>,
 }
 
 #[async_trait]
@@ -217,10 +412,10 @@ impl Tool for GpioReadTool {
     }
 
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
-        let pin = args
-            .get("pin")
-            .and_then(|v| v.as_u64())
-            .ok_or_else(|| anyhow::anyhow!("Missing 'pin' parameter"))?;
+        let pin = match self.access.gpio_pin(&args) {
+            Ok(pin) => pin,
+            Err(e) => return Ok(rejected(&e)),
+        };
         self.transport
             .request("gpio_read", json!({ "pin": pin }))
             .await
@@ -230,6 +425,7 @@ impl Tool for GpioReadTool {
 /// Tool: write GPIO pin value.
 struct GpioWriteTool {
     transport: Arc<SerialTransport>,
+    access: Arc<BoardAccess>,
 }
 
 #[async_trait]
@@ -260,10 +456,10 @@ impl Tool for GpioWriteTool {
     }
 
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
-        let pin = args
-            .get("pin")
-            .and_then(|v| v.as_u64())
-            .ok_or_else(|| anyhow::anyhow!("Missing 'pin' parameter"))?;
+        let pin = match self.access.gpio_pin(&args) {
+            Ok(pin) => pin,
+            Err(e) => return Ok(rejected(&e)),
+        };
         let value = args
             .get("value")
             .and_then(|v| v.as_u64())
@@ -273,3 +469,12 @@ impl Tool for GpioWriteTool {
             .await
     }
 }
+
+/// Tool result for a call refused before reaching the board.
+pub(crate) fn rejected(error: &anyhow::Error) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.to_string()),
+    }
+}
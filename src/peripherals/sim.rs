@@ -0,0 +1,636 @@
+//! Simulated peripheral board — the serial JSON protocol without hardware.
+//!
+//! `board = "sim"` with `transport = "sim"` runs a [`SimBoard`] in-process
+//! behind the same tools a real serial board gets; `zeroclaw peripheral
+//! simulate` serves one on a PTY instead, so anything that speaks the
+//! protocol can connect to it. Pin levels, sensor generators and I2C devices
+//! are scriptable from config or code, faults can be injected per request and
+//! every command the board receives is recorded. Pin changes made with
+//! [`SimBoard::set_pin`] and [`SimBoard::emit_event`] reach the host as
+//! unsolicited `{"event": ...}` lines, like a button on real firmware.
+
+use super::protocol::MAX_TRANSFER_BYTES;
+use super::serial::{SerialPeripheral, SerialTransport};
+use super::traits::{Peripheral, PeripheralEvent};
+use crate::config::{PeripheralBoardConfig, SensorGenerator, SimBoardConfig};
+use crate::tools::Tool;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::broadcast;
+
+/// Board type of the simulator.
+pub const SIM_BOARD: &str = "sim";
+
+/// Digital pins (Arduino Uno layout).
+const GPIO_PINS: [u32; 14] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13];
+const LED_PIN: u32 = 13;
+/// Analog inputs A0-A5.
+const ADC_PINS: [u32; 6] = [14, 15, 16, 17, 18, 19];
+const PWM_PINS: [u32; 6] = [3, 5, 6, 9, 10, 11];
+/// 10-bit ADC.
+const ADC_MAX: f64 = 1023.0;
+
+/// A misbehaviour the board shows instead of (or while) answering a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// Swallow the request; the host times out.
+    Timeout,
+    /// Execute the command but answer with a different id.
+    IdMismatch,
+    /// Answer `ok: false` with this message.
+    Error(String),
+    /// Answer with a line that is not JSON.
+    Malformed,
+}
+
+/// One request as the board received it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimCommand {
+    pub id: String,
+    pub cmd: String,
+    pub args: Value,
+    /// Fault injected into this request, if any.
+    pub fault: Option<Fault>,
+}
+
+struct Sensor {
+    generator: SensorGenerator,
+    reads: usize,
+}
+
+#[derive(Default)]
+struct SimState {
+    pins: BTreeMap<u32, u8>,
+    pwm: BTreeMap<u32, f64>,
+    sensors: BTreeMap<u32, Sensor>,
+    i2c: BTreeMap<u8, Vec<u8>>,
+    uart_reply: Option<String>,
+    /// Pending faults, optionally limited to one command.
+    faults: VecDeque<(Option<String>, Fault)>,
+    log: Vec<SimCommand>,
+}
+
+/// In-memory board implementing serial protocol version 2.
+pub struct SimBoard {
+    state: Mutex<SimState>,
+    started: Instant,
+    /// Event lines for every connected host.
+    events: broadcast::Sender<String>,
+}
+
+impl Default for SimBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimBoard {
+    /// A board with every pin low, no sensors and no I2C devices.
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(SimState::default()),
+            started: Instant::now(),
+            events: broadcast::channel(64).0,
+        }
+    }
+
+    /// A board in the state described by `[peripherals.boards.sim]`.
+    pub fn from_config(config: &SimBoardConfig) -> Self {
+        let board = Self::new();
+        for pin in &config.pins {
+            board.set_pin(pin.pin, pin.value);
+        }
+        for sensor in &config.sensors {
+            board.set_sensor(sensor.pin, sensor.generator.clone());
+        }
+        for device in &config.i2c_devices {
+            board.add_i2c_device(device.address, device.registers.clone());
+        }
+        if let Some(reply) = &config.uart_reply {
+            board.set_uart_reply(reply);
+        }
+        board
+    }
+
+    /// Drive a pin; a level change is reported to the host as an `edge`
+    /// event.
+    pub fn set_pin(&self, pin: u32, value: u8) {
+        let value = u8::from(value != 0);
+        let previous = self.state.lock().pins.insert(pin, value).unwrap_or(0);
+        if previous != value {
+            self.emit_event("edge", Some(pin), json!(value));
+        }
+    }
+
+    /// Send an unsolicited event line to every connected host.
+    pub fn emit_event(&self, event: &str, pin: Option<u32>, value: Value) {
+        let mut line = json!({ "event": event, "value": value });
+        if let Some(pin) = pin {
+            line["pin"] = json!(pin);
+        }
+        // No connected host is not an error: the event is simply lost, as
+        // on a real board with nothing listening.
+        let _ = self.events.send(line.to_string());
+    }
+
+    pub fn pin(&self, pin: u32) -> u8 {
+        self.state.lock().pins.get(&pin).copied().unwrap_or(0)
+    }
+
+    /// Last duty cycle set with `pwm_set`.
+    pub fn pwm_duty(&self, pin: u32) -> Option<f64> {
+        self.state.lock().pwm.get(&pin).copied()
+    }
+
+    pub fn set_sensor(&self, pin: u32, generator: SensorGenerator) {
+        self.state.lock().sensors.insert(
+            pin,
+            Sensor {
+                generator,
+                reads: 0,
+            },
+        );
+    }
+
+    pub fn add_i2c_device(&self, address: u8, registers: Vec<u8>) {
+        self.state.lock().i2c.insert(address, registers);
+    }
+
+    /// Register contents of an I2C device.
+    pub fn i2c_registers(&self, address: u8) -> Option<Vec<u8>> {
+        self.state.lock().i2c.get(&address).cloned()
+    }
+
+    /// Make `uart_transfer` answer with `reply` instead of echoing.
+    pub fn set_uart_reply(&self, reply: &str) {
+        self.state.lock().uart_reply = Some(reply.to_string());
+    }
+
+    /// Apply `fault` to the next request.
+    pub fn inject_fault(&self, fault: Fault) {
+        self.state.lock().faults.push_back((None, fault));
+    }
+
+    /// Apply `fault` to the next `cmd` request.
+    pub fn inject_fault_for(&self, cmd: &str, fault: Fault) {
+        self.state
+            .lock()
+            .faults
+            .push_back((Some(cmd.to_string()), fault));
+    }
+
+    /// Every request received so far, oldest first.
+    pub fn commands(&self) -> Vec<SimCommand> {
+        self.state.lock().log.clone()
+    }
+
+    pub fn clear_commands(&self) {
+        self.state.lock().log.clear();
+    }
+
+    /// Handle one request line; `None` means the board stays silent.
+    pub fn handle_line(&self, line: &str) -> Option<String> {
+        let Ok(request) = serde_json::from_str::<Value>(line.trim()) else {
+            return Some(reply("0", Err("Invalid JSON".into())));
+        };
+        let id = request["id"].as_str().unwrap_or("0").to_string();
+        let cmd = request["cmd"].as_str().unwrap_or_default().to_string();
+        let args = request.get("args").cloned().unwrap_or(Value::Null);
+
+        let mut state = self.state.lock();
+        let fault = state.take_fault(&cmd);
+        state.log.push(SimCommand {
+            id: id.clone(),
+            cmd: cmd.clone(),
+            args: args.clone(),
+            fault: fault.clone(),
+        });
+
+        let reply_id = match fault {
+            Some(Fault::Timeout) => return None,
+            Some(Fault::Malformed) => return Some("{\"id\":".into()),
+            Some(Fault::Error(message)) => return Some(reply(&id, Err(message))),
+            Some(Fault::IdMismatch) => format!("{id}-stale"),
+            None => id,
+        };
+        let result = state.execute(&cmd, &args, self.started.elapsed());
+        Some(reply(&reply_id, result))
+    }
+
+    /// Open an in-process link to the board; the returned stream is the
+    /// host end.
+    pub fn link(self: &Arc<Self>) -> tokio::io::DuplexStream {
+        let (host, device) = tokio::io::duplex(4096);
+        tokio::spawn(serve(self.clone(), device));
+        host
+    }
+
+    /// Serve the board on a new pseudo-terminal until the returned handle
+    /// is dropped.
+    #[cfg(unix)]
+    pub fn open_pty(self: &Arc<Self>) -> anyhow::Result<SimPty> {
+        use tokio_serial::SerialPort;
+
+        let (master, mut slave) = tokio_serial::SerialStream::pair()?;
+        // `pair` locks the slave to this process; let the host open it.
+        slave.set_exclusive(false)?;
+        let path = slave
+            .name()
+            .ok_or_else(|| anyhow::anyhow!("PTY has no device path"))?;
+        let task = tokio::spawn(serve(self.clone(), master));
+        Ok(SimPty {
+            path,
+            task,
+            _slave: slave,
+        })
+    }
+}
+
+/// A simulated board served on a PTY.
+#[cfg(unix)]
+pub struct SimPty {
+    path: String,
+    task: tokio::task::JoinHandle<std::io::Result<()>>,
+    // Keeping the slave open stops reads on the master from failing while
+    // no host is connected.
+    _slave: tokio_serial::SerialStream,
+}
+
+#[cfg(unix)]
+impl SimPty {
+    /// Device path for `path = ...` in the board config.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+#[cfg(unix)]
+impl Drop for SimPty {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Answer newline-delimited requests on `stream` until it closes, writing
+/// board events in between.
+async fn serve(
+    board: Arc<SimBoard>,
+    stream: impl AsyncRead + AsyncWrite + Unpin,
+) -> std::io::Result<()> {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    let mut events = board.events.subscribe();
+    loop {
+        let out = tokio::select! {
+            line = lines.next_line() => match line? {
+                Some(line) if line.trim().is_empty() => continue,
+                Some(line) => board.handle_line(&line),
+                None => return Ok(()),
+            },
+            event = events.recv() => match event {
+                Ok(event) => Some(event),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+        };
+        if let Some(out) = out {
+            writer.write_all(format!("{out}\n").as_bytes()).await?;
+            writer.flush().await?;
+        }
+    }
+}
+
+fn reply(id: &str, result: Result<String, String>) -> String {
+    match result {
+        Ok(result) => json!({ "id": id, "ok": true, "result": result }),
+        Err(error) => json!({ "id": id, "ok": false, "result": "", "error": error }),
+    }
+    .to_string()
+}
+
+impl SimState {
+    fn take_fault(&mut self, cmd: &str) -> Option<Fault> {
+        let index = self
+            .faults
+            .iter()
+            .position(|(only, _)| only.as_deref().is_none_or(|c| c == cmd))?;
+        self.faults.remove(index).map(|(_, fault)| fault)
+    }
+
+    fn execute(&mut self, cmd: &str, args: &Value, elapsed: Duration) -> Result<String, String> {
+        match cmd {
+            "ping" => Ok("pong".into()),
+            "capabilities" => Ok(json!({
+                "protocol": 2,
+                "gpio": GPIO_PINS,
+                "led_pin": LED_PIN,
+                "features": ["adc", "pwm", "i2c", "spi", "uart"],
+                "adc": ADC_PINS,
+                "pwm": PWM_PINS,
+                "i2c": [0],
+                "spi": [0],
+                "uart": [0],
+            })
+            .to_string()),
+            "gpio_read" => {
+                let pin = pin_arg(args, &GPIO_PINS)?;
+                Ok(self.pins.get(&pin).copied().unwrap_or(0).to_string())
+            }
+            "gpio_write" => {
+                let pin = pin_arg(args, &GPIO_PINS)?;
+                let value = u8::from(args["value"].as_u64().unwrap_or(0) != 0);
+                self.pins.insert(pin, value);
+                Ok("done".into())
+            }
+            "adc_read" => {
+                let pin = pin_arg(args, &ADC_PINS)?;
+                let value = self
+                    .sensors
+                    .get_mut(&pin)
+                    .map_or(0.0, |sensor| sensor.sample(elapsed));
+                Ok(format!("{}", value.clamp(0.0, ADC_MAX).round()))
+            }
+            "pwm_set" => {
+                let pin = pin_arg(args, &PWM_PINS)?;
+                let duty = args["duty"]
+                    .as_f64()
+                    .filter(|d| (0.0..=100.0).contains(d))
+                    .ok_or("Invalid duty")?;
+                self.pwm.insert(pin, duty);
+                Ok("done".into())
+            }
+            "i2c_scan" => Ok(json!(self.i2c.keys().collect::<Vec<_>>()).to_string()),
+            "i2c_read" => {
+                let registers = self.i2c_device(args)?;
+                let start = size_arg(args, "register", 0, 0xff);
+                let len = size_arg(args, "len", 1, MAX_TRANSFER_BYTES);
+                let data: Vec<u8> = (start..start + len)
+                    .map(|r| registers.get(r).copied().unwrap_or(0))
+                    .collect();
+                Ok(json!(data).to_string())
+            }
+            "i2c_write" => {
+                let start = size_arg(args, "register", 0, 0xff);
+                let data = bytes_arg(args)?;
+                let registers = self.i2c_device(args)?;
+                if registers.len() < start + data.len() {
+                    registers.resize(start + data.len(), 0);
+                }
+                registers[start..start + data.len()].copy_from_slice(&data);
+                Ok("done".into())
+            }
+            // MOSI wired to MISO.
+            "spi_transfer" => Ok(json!(bytes_arg(args)?).to_string()),
+            "uart_transfer" => {
+                let sent = args["data"].as_str().unwrap_or_default();
+                let read_len = size_arg(args, "read_len", MAX_TRANSFER_BYTES, MAX_TRANSFER_BYTES);
+                let answer = self.uart_reply.as_deref().unwrap_or(sent);
+                Ok(answer.chars().take(read_len).collect())
+            }
+            _ => Err(format!("Unknown command: {cmd}")),
+        }
+    }
+
+    fn i2c_device(&mut self, args: &Value) -> Result<&mut Vec<u8>, String> {
+        let address = args["address"].as_u64().unwrap_or(u64::MAX);
+        u8::try_from(address)
+            .ok()
+            .and_then(|a| self.i2c.get_mut(&a))
+            .ok_or_else(|| format!("No ACK from I2C address {address}"))
+    }
+}
+
+fn pin_arg(args: &Value, valid: &[u32]) -> Result<u32, String> {
+    args["pin"]
+        .as_u64()
+        .and_then(|p| u32::try_from(p).ok())
+        .filter(|p| valid.contains(p))
+        .ok_or_else(|| format!("Invalid pin {}", args["pin"]))
+}
+
+/// Numeric argument as a size, capped at `max`.
+fn size_arg(args: &Value, key: &str, default: usize, max: usize) -> usize {
+    args[key]
+        .as_u64()
+        .map_or(default, |v| usize::try_from(v).unwrap_or(max))
+        .min(max)
+}
+
+fn bytes_arg(args: &Value) -> Result<Vec<u8>, String> {
+    args["data"]
+        .as_array()
+        .ok_or("Missing data")?
+        .iter()
+        .map(|b| {
+            b.as_u64()
+                .and_then(|b| u8::try_from(b).ok())
+                .ok_or_else(|| "Invalid byte".to_string())
+        })
+        .collect()
+}
+
+impl Sensor {
+    fn sample(&mut self, elapsed: Duration) -> f64 {
+        let read = self.reads;
+        self.reads += 1;
+        match &self.generator {
+            SensorGenerator::Constant { value } => *value,
+            SensorGenerator::Sine {
+                min,
+                max,
+                period_ms,
+            } => {
+                let period = (*period_ms).max(1) as f64;
+                let phase = (elapsed.as_millis() as f64 % period) / period;
+                min + (max - min) * 0.5 * (1.0 + (std::f64::consts::TAU * phase).sin())
+            }
+            SensorGenerator::Ramp { start, step, max } => {
+                if *step <= 0.0 || max <= start {
+                    return *start;
+                }
+                let steps = ((max - start) / step).floor() + 1.0;
+                start + step * (read as f64 % steps)
+            }
+            SensorGenerator::Sequence { values } => {
+                if values.is_empty() {
+                    return 0.0;
+                }
+                values[read % values.len()]
+            }
+        }
+    }
+}
+
+/// Simulated board exposed through the serial peripheral tools.
+pub struct SimPeripheral {
+    inner: SerialPeripheral,
+    board: Arc<SimBoard>,
+}
+
+impl SimPeripheral {
+    /// Start a board scripted by `config.sim` and connect to it in-process.
+    pub async fn connect(config: &PeripheralBoardConfig) -> Self {
+        let board = config
+            .sim
+            .as_ref()
+            .map_or_else(SimBoard::new, SimBoard::from_config);
+        Self::with_board(config, Arc::new(board)).await
+    }
+
+    /// Connect to an existing board, e.g. one a test keeps scripting.
+    pub async fn with_board(config: &PeripheralBoardConfig, board: Arc<SimBoard>) -> Self {
+        let timeout_ms = config
+            .sim
+            .as_ref()
+            .map_or_else(|| SimBoardConfig::default().timeout_ms, |s| s.timeout_ms);
+        let transport = SerialTransport::new(
+            &config.board,
+            Box::new(board.link()),
+            Duration::from_millis(timeout_ms),
+        );
+        let inner = SerialPeripheral::attach(config.board.clone(), config, transport).await;
+        Self { inner, board }
+    }
+
+    pub fn board(&self) -> &Arc<SimBoard> {
+        &self.board
+    }
+
+    pub(crate) fn transport(&self) -> Arc<SerialTransport> {
+        self.inner.transport()
+    }
+}
+
+#[async_trait]
+impl Peripheral for SimPeripheral {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn board_type(&self) -> &str {
+        self.inner.board_type()
+    }
+
+    async fn connect(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+
+    fn tools(&self) -> Vec<Box<dyn Tool>> {
+        self.inner.tools()
+    }
+
+    fn events(&self) -> Option<broadcast::Receiver<PeripheralEvent>> {
+        self.inner.events()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(board: &SimBoard, cmd: &str, args: Value) -> Value {
+        let line = json!({ "id": "7", "cmd": cmd, "args": args }).to_string();
+        serde_json::from_str(&board.handle_line(&line).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn gpio_and_i2c_state_round_trip() {
+        let board = SimBoard::new();
+        board.add_i2c_device(0x3c, vec![0xaa, 0xbb]);
+
+        assert_eq!(
+            request(&board, "gpio_write", json!({"pin": 13, "value": 1}))["result"],
+            "done"
+        );
+        assert_eq!(board.pin(13), 1);
+        assert_eq!(
+            request(&board, "gpio_read", json!({"pin": 13}))["result"],
+            "1"
+        );
+        assert_eq!(
+            request(
+                &board,
+                "i2c_write",
+                json!({"address": 60, "register": 1, "data": [5, 6]})
+            )["ok"],
+            true
+        );
+        assert_eq!(board.i2c_registers(0x3c), Some(vec![0xaa, 5, 6]));
+        assert_eq!(
+            request(&board, "i2c_read", json!({"address": 60, "len": 3}))["result"],
+            "[170,5,6]"
+        );
+        assert_eq!(
+            request(&board, "i2c_read", json!({"address": 61}))["ok"],
+            false
+        );
+        assert_eq!(
+            request(&board, "gpio_read", json!({"pin": 40}))["ok"],
+            false
+        );
+    }
+
+    #[test]
+    fn sensor_generators_produce_expected_values() {
+        let board = SimBoard::new();
+        board.set_sensor(
+            14,
+            SensorGenerator::Ramp {
+                start: 10.0,
+                step: 5.0,
+                max: 20.0,
+            },
+        );
+        board.set_sensor(
+            15,
+            SensorGenerator::Sequence {
+                values: vec![1.0, 2000.0],
+            },
+        );
+
+        let reads: Vec<Value> = (0..4)
+            .map(|_| request(&board, "adc_read", json!({"pin": 14}))["result"].clone())
+            .collect();
+        assert_eq!(reads, vec!["10", "15", "20", "10"]);
+        assert_eq!(
+            request(&board, "adc_read", json!({"pin": 15}))["result"],
+            "1"
+        );
+        // Clamped to the 10-bit range.
+        assert_eq!(
+            request(&board, "adc_read", json!({"pin": 15}))["result"],
+            "1023"
+        );
+    }
+
+    #[test]
+    fn faults_apply_once_and_are_logged() {
+        let board = SimBoard::new();
+        board.inject_fault_for("gpio_read", Fault::Timeout);
+        board.inject_fault(Fault::IdMismatch);
+
+        assert_eq!(request(&board, "ping", json!({}))["id"], "7-stale");
+        let line = json!({ "id": "8", "cmd": "gpio_read", "args": {"pin": 1} }).to_string();
+        assert!(board.handle_line(&line).is_none());
+        assert_eq!(request(&board, "gpio_read", json!({"pin": 1}))["ok"], true);
+
+        let log = board.commands();
+        assert_eq!(log.len(), 3);
+        assert_eq!(log[0].fault, Some(Fault::IdMismatch));
+        assert_eq!(log[1].fault, Some(Fault::Timeout));
+        assert_eq!(log[2].fault, None);
+    }
+}
@@ -4,9 +4,27 @@
 //! firmware and expose capabilities (GPIO, sensors, actuators) as tools.
 
 use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::broadcast;
 
 use crate::tools::Tool;
 
+/// Unsolicited notification from a board: a button press, threshold crossing
+/// or interrupt. Serial firmware sends these as `{"event":"button","pin":2,"value":1}`
+/// lines between responses.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PeripheralEvent {
+    /// Board type that raised the event (e.g. "nucleo-f401re")
+    pub board: String,
+    /// Event name (e.g. "button", "edge", "threshold")
+    pub event: String,
+    /// Pin the event refers to, if any
+    pub pin: Option<u32>,
+    /// Reading or level that accompanied the event
+    pub value: Option<Value>,
+}
+
 /// A hardware peripheral that exposes capabilities as tools.
 ///
 /// Implement this for boards like Nucleo-F401RE (serial), RPi GPIO (native), etc.
@@ -30,4 +48,10 @@ pub trait Peripheral: Send + Sync {
 
     /// Tools this peripheral provides (e.g. gpio_read, gpio_write, sensor_read)
     fn tools(&self) -> Vec<Box<dyn Tool>>;
+
+    /// Subscribe to events the board raises on its own. `None` for boards
+    /// that only answer requests.
+    fn events(&self) -> Option<broadcast::Receiver<PeripheralEvent>> {
+        None
+    }
 }
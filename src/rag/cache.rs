@@ -0,0 +1,154 @@
+//! On-disk cache of the parsed datasheet index.
+//!
+//! Parsing PDFs and embedding chunks are the slow parts of loading the
+//! hardware RAG, so both are kept in `state/hardware_rag_index.json`. Each
+//! datasheet is keyed by its path relative to the workspace and fingerprinted
+//! with a SHA-256 of its bytes: an edited or replaced datasheet is re-parsed,
+//! a deleted one is dropped. Vectors remember which embedding model produced
+//! them and are discarded when the model changes.
+
+use super::tables::TableRow;
+use super::PinAliases;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const CACHE_FILE: &str = "hardware_rag_index.json";
+
+/// Bump when chunking or table extraction changes so old caches are rebuilt.
+const CACHE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct CachedChunk {
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row: Option<TableRow>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vector: Vec<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct CachedFile {
+    pub sha256: String,
+    pub board: Option<String>,
+    #[serde(default)]
+    pub pin_aliases: PinAliases,
+    pub chunks: Vec<CachedChunk>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(super) struct IndexCache {
+    pub version: u32,
+    pub datasheet_dir: String,
+    /// `provider:model:dimensions` of the stored vectors.
+    #[serde(default)]
+    pub embedder: Option<String>,
+    /// Keyed by source path relative to the workspace.
+    #[serde(default)]
+    pub files: BTreeMap<String, CachedFile>,
+}
+
+pub(super) fn cache_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(CACHE_FILE)
+}
+
+pub(super) fn fingerprint(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+impl IndexCache {
+    /// Cached index for `datasheet_dir`, or an empty one when the cache is
+    /// missing, unreadable, from another version or for another directory.
+    pub fn load(workspace_dir: &Path, datasheet_dir: &str) -> Self {
+        let fresh = Self {
+            version: CACHE_VERSION,
+            datasheet_dir: datasheet_dir.to_string(),
+            ..Self::default()
+        };
+        let Ok(raw) = std::fs::read_to_string(cache_path(workspace_dir)) else {
+            return fresh;
+        };
+        match serde_json::from_str::<Self>(&raw) {
+            Ok(cache) if cache.version == CACHE_VERSION && cache.datasheet_dir == datasheet_dir => {
+                cache
+            }
+            _ => fresh,
+        }
+    }
+
+    pub fn save(&self, workspace_dir: &Path) -> anyhow::Result<()> {
+        let path = cache_path(workspace_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(self)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// Drop stored vectors unless they came from `embedder`.
+    pub fn retain_vectors_from(&mut self, embedder: &str) -> bool {
+        if self.embedder.as_deref() == Some(embedder) {
+            return false;
+        }
+        for file in self.files.values_mut() {
+            for chunk in &mut file.chunks {
+                chunk.vector.clear();
+            }
+        }
+        self.embedder = Some(embedder.to_string());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_round_trips_and_rejects_other_directories() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut cache = IndexCache::load(tmp.path(), "datasheets");
+        cache.files.insert(
+            "datasheets/board.md".into(),
+            CachedFile {
+                sha256: fingerprint(b"# Board"),
+                board: Some("board".into()),
+                pin_aliases: PinAliases::new(),
+                chunks: vec![CachedChunk {
+                    content: "# Board".into(),
+                    row: None,
+                    vector: vec![0.5, 0.5],
+                }],
+            },
+        );
+        cache.save(tmp.path()).unwrap();
+
+        let loaded = IndexCache::load(tmp.path(), "datasheets");
+        assert_eq!(loaded.files.len(), 1);
+        assert!(IndexCache::load(tmp.path(), "other").files.is_empty());
+    }
+
+    #[test]
+    fn vectors_from_another_embedder_are_dropped() {
+        let mut cache = IndexCache::default();
+        cache.files.insert(
+            "a.md".into(),
+            CachedFile {
+                sha256: String::new(),
+                board: None,
+                pin_aliases: PinAliases::new(),
+                chunks: vec![CachedChunk {
+                    content: "x".into(),
+                    row: None,
+                    vector: vec![1.0],
+                }],
+            },
+        );
+        assert!(cache.retain_vectors_from("openai:text-embedding-3-small:1536"));
+        assert!(cache.files["a.md"].chunks[0].vector.is_empty());
+        assert!(!cache.retain_vectors_from("openai:text-embedding-3-small:1536"));
+    }
+}
@@ -4,11 +4,26 @@
 //! - Markdown and text datasheets (always)
 //! - PDF ingestion (with `rag-pdf` feature)
 //! - Pin/alias tables (e.g. `red_led: 13`) for explicit lookup
-//! - Keyword retrieval (default) or semantic search via embeddings (optional)
+//! - Register and pinout tables extracted as structured rows ([`tables`])
+//! - Keyword retrieval (default) or hybrid keyword + vector search using the
+//!   memory embedding provider ([`HardwareRag::load_with_embeddings`])
+//! - An on-disk index cache, invalidated per datasheet when its bytes change
+
+mod cache;
+pub mod tables;
+
+pub use tables::{TableKind, TableRow};
 
 use crate::memory::chunker;
+use crate::memory::embeddings::{create_embedding_provider, EmbeddingProvider};
+use crate::memory::vector::{cosine_similarity, hybrid_merge};
+use cache::{fingerprint, CachedChunk, CachedFile, IndexCache};
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
+
+/// Chunks embedded per provider request.
+const EMBED_BATCH: usize = 32;
 
 /// A chunk of datasheet content with board metadata.
 #[derive(Debug, Clone)]
@@ -19,6 +34,61 @@ pub struct DatasheetChunk {
     pub source: String,
     /// Chunk content.
     pub content: String,
+    /// Structured row when this chunk is one line of a register or pinout table.
+    pub row: Option<TableRow>,
+}
+
+/// Embedding provider and fusion weights for hybrid retrieval, normally the
+/// ones configured under `[memory]`.
+#[derive(Clone)]
+pub struct RagEmbedding {
+    pub embedder: Arc<dyn EmbeddingProvider>,
+    /// Model name; cached vectors from a different model are recomputed.
+    pub model: String,
+    pub vector_weight: f32,
+    pub keyword_weight: f32,
+}
+
+impl RagEmbedding {
+    /// Build from `[memory]` settings (`embedding_provider`, `embedding_model`, ...).
+    pub fn from_parts(
+        provider: &str,
+        api_key: Option<&str>,
+        model: &str,
+        dimensions: usize,
+        vector_weight: f64,
+        keyword_weight: f64,
+    ) -> Self {
+        #[allow(clippy::cast_possible_truncation)]
+        Self {
+            embedder: Arc::from(create_embedding_provider(
+                provider, api_key, model, dimensions,
+            )),
+            model: model.to_string(),
+            vector_weight: vector_weight as f32,
+            keyword_weight: keyword_weight as f32,
+        }
+    }
+
+    fn signature(&self) -> String {
+        format!(
+            "{}:{}:{}",
+            self.embedder.name(),
+            self.model,
+            self.embedder.dimensions()
+        )
+    }
+}
+
+/// A retrieved chunk with its scores.
+#[derive(Debug, Clone)]
+pub struct RagHit<'a> {
+    pub chunk: &'a DatasheetChunk,
+    pub score: f32,
+    /// Keyword score, normalized to 0–1 in hybrid mode.
+    pub keyword_score: Option<f32>,
+    /// Cosine similarity to the query, in hybrid mode.
+    pub vector_score: Option<f32>,
 }
 
 /// Pin alias: human-readable name → pin number (e.g. "red_led" → 13).
@@ -133,86 +203,225 @@ fn collect_pdf_paths(dir: &Path, out: &mut Vec<std::path::PathBuf>) {
 }
 
 #[cfg(feature = "rag-pdf")]
-fn extract_pdf_text(path: &Path) -> Option<String> {
-    let bytes = std::fs::read(path).ok()?;
-    pdf_extract::extract_text_from_mem(&bytes).ok()
+fn extract_pdf_text(bytes: &[u8]) -> Option<String> {
+    pdf_extract::extract_text_from_mem(bytes).ok()
+}
+
+fn extract_text(path: &Path, bytes: &[u8]) -> String {
+    if path.extension().and_then(|e| e.to_str()) == Some("pdf") {
+        #[cfg(feature = "rag-pdf")]
+        {
+            extract_pdf_text(bytes).unwrap_or_default()
+        }
+        #[cfg(not(feature = "rag-pdf"))]
+        {
+            String::new()
+        }
+    } else {
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+}
+
+/// Chunk one datasheet: prose chunks, then one chunk per table row.
+fn parse_datasheet(content: &str, board: Option<String>, sha256: String) -> CachedFile {
+    let max_tokens = 512;
+    let mut chunks: Vec<CachedChunk> = chunker::chunk_markdown(content, max_tokens)
+        .into_iter()
+        .map(|chunk| CachedChunk {
+            content: chunk.content,
+            row: None,
+            vector: Vec::new(),
+        })
+        .collect();
+    chunks.extend(
+        tables::extract_table_rows(content)
+            .into_iter()
+            .map(|row| CachedChunk {
+                content: row.render(),
+                row: Some(row),
+                vector: Vec::new(),
+            }),
+    );
+    CachedFile {
+        sha256,
+        board,
+        pin_aliases: parse_pin_aliases(content),
+        chunks,
+    }
+}
+
+/// Bring the cached index up to date with the datasheet directory.
+/// Returns the index and whether it differs from what is on disk.
+fn refresh_index(workspace_dir: &Path, datasheet_dir: &str) -> (IndexCache, bool) {
+    let base = workspace_dir.join(datasheet_dir);
+    let mut cache = IndexCache::load(workspace_dir, datasheet_dir);
+
+    let mut paths: Vec<std::path::PathBuf> = Vec::new();
+    collect_md_txt_paths(&base, &mut paths);
+    #[cfg(feature = "rag-pdf")]
+    collect_pdf_paths(&base, &mut paths);
+
+    let mut files = std::collections::BTreeMap::new();
+    let mut changed = false;
+    for path in paths {
+        let Ok(bytes) = std::fs::read(&path) else {
+            continue;
+        };
+        let source = path
+            .strip_prefix(workspace_dir)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        let sha256 = fingerprint(&bytes);
+        match cache.files.remove(&source) {
+            Some(cached) if cached.sha256 == sha256 => {
+                files.insert(source, cached);
+            }
+            _ => {
+                changed = true;
+                let content = extract_text(&path, &bytes);
+                let board = infer_board_from_path(&path, &base);
+                files.insert(source, parse_datasheet(&content, board, sha256));
+            }
+        }
+    }
+    // Anything left over was deleted from the directory.
+    changed |= !cache.files.is_empty();
+    cache.files = files;
+    (cache, changed)
+}
+
+fn save_index(cache: &IndexCache, workspace_dir: &Path) {
+    if let Err(e) = cache.save(workspace_dir) {
+        tracing::warn!("Failed to write hardware RAG index cache: {e}");
+    }
 }
 
 /// Hardware RAG index — loads and retrieves datasheet chunks.
 pub struct HardwareRag {
     chunks: Vec<DatasheetChunk>,
+    /// Embedding per chunk (same order); empty when not embedded.
+    vectors: Vec<Vec<f32>>,
     /// Per-board pin aliases (board -> alias -> pin).
     pin_aliases: HashMap<String, PinAliases>,
+    embedding: Option<RagEmbedding>,
 }
 
 impl HardwareRag {
+    fn empty() -> Self {
+        Self {
+            chunks: Vec::new(),
+            vectors: Vec::new(),
+            pin_aliases: HashMap::new(),
+            embedding: None,
+        }
+    }
+
+    fn from_index(cache: &IndexCache) -> Self {
+        let mut rag = Self::empty();
+        for (source, file) in &cache.files {
+            if let Some(ref b) = file.board {
+                if !file.pin_aliases.is_empty() {
+                    rag.pin_aliases.insert(b.clone(), file.pin_aliases.clone());
+                }
+            }
+            for chunk in &file.chunks {
+                rag.chunks.push(DatasheetChunk {
+                    board: file.board.clone(),
+                    source: source.clone(),
+                    content: chunk.content.clone(),
+                    row: chunk.row.clone(),
+                });
+                rag.vectors.push(chunk.vector.clone());
+            }
+        }
+        rag
+    }
+
     /// Load datasheets from a directory. Expects .md, .txt, and optionally .pdf (with rag-pdf).
     /// Filename (without extension) is used as board tag.
     /// Supports `## Pin Aliases` section for explicit alias→pin mapping.
+    /// Parsed datasheets are cached under `state/`; only changed files are re-read.
     pub fn load(workspace_dir: &Path, datasheet_dir: &str) -> anyhow::Result<Self> {
-        let base = workspace_dir.join(datasheet_dir);
-        if !base.exists() || !base.is_dir() {
-            return Ok(Self {
-                chunks: Vec::new(),
-                pin_aliases: HashMap::new(),
-            });
+        if !workspace_dir.join(datasheet_dir).is_dir() {
+            return Ok(Self::empty());
         }
+        let (cache, changed) = refresh_index(workspace_dir, datasheet_dir);
+        if changed {
+            save_index(&cache, workspace_dir);
+        }
+        Ok(Self::from_index(&cache))
+    }
 
-        let mut paths: Vec<std::path::PathBuf> = Vec::new();
-        collect_md_txt_paths(&base, &mut paths);
-        #[cfg(feature = "rag-pdf")]
-        collect_pdf_paths(&base, &mut paths);
-
-        let mut chunks = Vec::new();
-        let mut pin_aliases: HashMap<String, PinAliases> = HashMap::new();
-        let max_tokens = 512;
+    /// Like [`load`](Self::load), and embed every chunk for hybrid retrieval.
+    /// Only chunks without a cached vector from the same model are sent to the
+    /// provider. With the noop provider, or if embedding fails, retrieval
+    /// stays keyword-only.
+    pub async fn load_with_embeddings(
+        workspace_dir: &Path,
+        datasheet_dir: &str,
+        embedding: RagEmbedding,
+    ) -> anyhow::Result<Self> {
+        if !workspace_dir.join(datasheet_dir).is_dir() {
+            return Ok(Self::empty());
+        }
+        let (mut cache, mut changed) = refresh_index(workspace_dir, datasheet_dir);
+        if embedding.embedder.dimensions() == 0 {
+            if changed {
+                save_index(&cache, workspace_dir);
+            }
+            return Ok(Self::from_index(&cache));
+        }
 
-        for path in paths {
-            let content = if path.extension().and_then(|e| e.to_str()) == Some("pdf") {
-                #[cfg(feature = "rag-pdf")]
-                {
-                    extract_pdf_text(&path).unwrap_or_default()
+        changed |= cache.retain_vectors_from(&embedding.signature());
+        let missing: Vec<(String, usize)> = cache
+            .files
+            .iter()
+            .flat_map(|(source, file)| {
+                file.chunks
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, chunk)| chunk.vector.is_empty())
+                    .map(move |(i, _)| (source.clone(), i))
+            })
+            .collect();
+        let mut complete = true;
+        for batch in missing.chunks(EMBED_BATCH) {
+            let texts: Vec<String> = batch
+                .iter()
+                .map(|(source, i)| cache.files[source].chunks[*i].content.clone())
+                .collect();
+            let refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+            match embedding.embedder.embed(&refs).await {
+                Ok(vectors) if vectors.len() == batch.len() => {
+                    for ((source, i), vector) in batch.iter().zip(vectors) {
+                        if let Some(file) = cache.files.get_mut(source) {
+                            file.chunks[*i].vector = vector;
+                        }
+                    }
+                    changed = true;
                 }
-                #[cfg(not(feature = "rag-pdf"))]
-                {
-                    String::new()
+                Ok(_) => {
+                    tracing::warn!("Embedding provider returned the wrong number of vectors");
+                    complete = false;
+                    break;
                 }
-            } else {
-                std::fs::read_to_string(&path).unwrap_or_default()
-            };
-
-            if content.trim().is_empty() {
-                continue;
-            }
-
-            let board = infer_board_from_path(&path, &base);
-            let source = path
-                .strip_prefix(workspace_dir)
-                .unwrap_or(&path)
-                .display()
-                .to_string();
-
-            // Parse pin aliases from full content
-            let aliases = parse_pin_aliases(&content);
-            if let Some(ref b) = board {
-                if !aliases.is_empty() {
-                    pin_aliases.insert(b.clone(), aliases);
+                Err(e) => {
+                    tracing::warn!("Datasheet embedding failed, using keyword retrieval: {e}");
+                    complete = false;
+                    break;
                 }
             }
-
-            for chunk in chunker::chunk_markdown(&content, max_tokens) {
-                chunks.push(DatasheetChunk {
-                    board: board.clone(),
-                    source: source.clone(),
-                    content: chunk.content,
-                });
-            }
+        }
+        if changed {
+            save_index(&cache, workspace_dir);
         }
 
-        Ok(Self {
-            chunks,
-            pin_aliases,
-        })
+        let mut rag = Self::from_index(&cache);
+        if complete || rag.vectors.iter().any(|v| !v.is_empty()) {
+            rag.embedding = Some(embedding);
+        }
+        Ok(rag)
     }
 
     /// Get pin aliases for a board (e.g. "red_led" -> 13).
@@ -247,42 +456,138 @@ impl HardwareRag {
         format!("[Pin aliases for query]\n{}\n\n", lines.join("\n"))
     }
 
-    /// Retrieve chunks relevant to the query and boards.
-    /// Uses keyword matching and board filter. Pin-alias context is built separately via `pin_alias_context`.
-    pub fn retrieve(&self, query: &str, boards: &[String], limit: usize) -> Vec<&DatasheetChunk> {
-        if self.chunks.is_empty() || limit == 0 {
-            return Vec::new();
-        }
-
+    /// Keyword score per chunk index: one point per query term found. Table
+    /// rows get a small bonus so an exact register or pin row outranks prose
+    /// that merely mentions it.
+    fn keyword_scores(&self, query: &str) -> Vec<(usize, f32)> {
         let query_lower = query.to_lowercase();
         let query_terms: Vec<&str> = query_lower
             .split_whitespace()
             .filter(|w| w.len() > 2)
             .collect();
 
-        let mut scored: Vec<(&DatasheetChunk, f32)> = Vec::new();
-        for chunk in &self.chunks {
+        let mut scored = Vec::new();
+        for (i, chunk) in self.chunks.iter().enumerate() {
             let content_lower = chunk.content.to_lowercase();
             let mut score = 0.0f32;
-
             for term in &query_terms {
                 if content_lower.contains(term) {
                     score += 1.0;
                 }
             }
-
             if score > 0.0 {
-                let board_match = chunk.board.as_ref().map_or(false, |b| boards.contains(b));
+                if chunk.row.is_some() {
+                    score += 0.5;
+                }
+                scored.push((i, score));
+            }
+        }
+        scored
+    }
+
+    fn keyword_hits(&self, query: &str, boards: &[String], limit: usize) -> Vec<RagHit<'_>> {
+        let mut hits: Vec<RagHit<'_>> = self
+            .keyword_scores(query)
+            .into_iter()
+            .map(|(i, mut score)| {
+                let chunk = &self.chunks[i];
+                let board_match = chunk.board.as_ref().is_some_and(|b| boards.contains(b));
                 if board_match {
                     score += 2.0;
                 }
-                scored.push((chunk, score));
-            }
+                RagHit {
+                    chunk,
+                    score,
+                    keyword_score: Some(score),
+                    vector_score: None,
+                }
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        hits.truncate(limit);
+        hits
+    }
+
+    /// Retrieve chunks relevant to the query and boards.
+    /// Uses keyword matching and board filter. Pin-alias context is built separately via `pin_alias_context`.
+    pub fn retrieve(&self, query: &str, boards: &[String], limit: usize) -> Vec<&DatasheetChunk> {
+        if self.chunks.is_empty() || limit == 0 {
+            return Vec::new();
         }
+        self.keyword_hits(query, boards, limit)
+            .into_iter()
+            .map(|hit| hit.chunk)
+            .collect()
+    }
+
+    /// Retrieve with scores. Hybrid (vector + keyword) when loaded with
+    /// embeddings, otherwise the same keyword ranking as [`retrieve`](Self::retrieve).
+    /// Chunks for the given boards are boosted; chunks for other boards are
+    /// demoted.
+    pub async fn search(&self, query: &str, boards: &[String], limit: usize) -> Vec<RagHit<'_>> {
+        if self.chunks.is_empty() || limit == 0 {
+            return Vec::new();
+        }
+        let Some(embedding) = &self.embedding else {
+            return self.keyword_hits(query, boards, limit);
+        };
+        let query_vector = match embedding.embedder.embed_one(query).await {
+            Ok(vector) => vector,
+            Err(e) => {
+                tracing::warn!("Query embedding failed, using keyword retrieval: {e}");
+                return self.keyword_hits(query, boards, limit);
+            }
+        };
+
+        let vector_results: Vec<(String, f32)> = self
+            .vectors
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| !v.is_empty())
+            .map(|(i, v)| (i.to_string(), cosine_similarity(&query_vector, v)))
+            .collect();
+        let keyword_results: Vec<(String, f32)> = self
+            .keyword_scores(query)
+            .into_iter()
+            .map(|(i, score)| (i.to_string(), score))
+            .collect();
+        let merged = hybrid_merge(
+            &vector_results,
+            &keyword_results,
+            embedding.vector_weight,
+            embedding.keyword_weight,
+            self.chunks.len(),
+        );
 
-        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-        scored.truncate(limit);
-        scored.into_iter().map(|(c, _)| c).collect()
+        let mut hits: Vec<RagHit<'_>> = merged
+            .into_iter()
+            .filter_map(|result| {
+                let chunk = self.chunks.get(result.id.parse::<usize>().ok()?)?;
+                let mut score = result.final_score;
+                match &chunk.board {
+                    Some(board) if boards.contains(board) => score += 0.25,
+                    Some(_) if !boards.is_empty() => score *= 0.5,
+                    _ => {}
+                }
+                Some(RagHit {
+                    chunk,
+                    score,
+                    keyword_score: result.keyword_score,
+                    vector_score: result.vector_score,
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        hits.truncate(limit);
+        hits
     }
 
     /// Number of indexed chunks.
@@ -294,6 +599,16 @@ impl HardwareRag {
     pub fn is_empty(&self) -> bool {
         self.chunks.is_empty()
     }
+
+    /// Number of chunks that are register or pinout table rows.
+    pub fn table_row_count(&self) -> usize {
+        self.chunks.iter().filter(|c| c.row.is_some()).count()
+    }
+
+    /// Embedding model in use, if retrieval is hybrid.
+    pub fn embedding_model(&self) -> Option<&str> {
+        self.embedding.as_ref().map(|e| e.model.as_str())
+    }
 }
 
 /// Infer board tag from file path. `nucleo-f401re.md` → Some("nucleo-f401re").
@@ -384,6 +699,151 @@ Pin 13: LED
         assert!(ctx.contains("13"));
     }
 
+    #[test]
+    fn register_table_rows_are_indexed_and_ranked_first() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        let content = "# Board\n\
+                       The MODER register selects the mode of each GPIO pin.\n\n\
+                       ## GPIO registers\n\
+                       | Register | Offset | Reset value |\n\
+                       |---|---|---|\n\
+                       | GPIOA_MODER | 0x00 | 0xA800 0000 |\n\
+                       | GPIOA_ODR | 0x14 | 0x0000 0000 |\n";
+        std::fs::write(base.join("board.md"), content).unwrap();
+
+        let rag = HardwareRag::load(tmp.path(), "datasheets").unwrap();
+        assert_eq!(rag.table_row_count(), 2);
+        let boards = vec!["board".to_string()];
+        let top = rag.retrieve("GPIOA_MODER offset", &boards, 1);
+        let row = top[0].row.as_ref().expect("table row ranked first");
+        assert_eq!(row.get("offset"), Some("0x00"));
+    }
+
+    #[test]
+    fn index_cache_is_reused_and_invalidated_on_change() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("a.md"), "# A\nalpha pin").unwrap();
+        std::fs::write(base.join("b.md"), "# B\nbeta pin").unwrap();
+
+        let (_, changed) = refresh_index(tmp.path(), "datasheets");
+        assert!(changed);
+        HardwareRag::load(tmp.path(), "datasheets").unwrap();
+        assert!(cache::cache_path(tmp.path()).exists());
+        let (_, changed) = refresh_index(tmp.path(), "datasheets");
+        assert!(!changed, "unchanged datasheets come from the cache");
+
+        std::fs::write(base.join("a.md"), "# A\ngamma pin").unwrap();
+        std::fs::remove_file(base.join("b.md")).unwrap();
+        let rag = HardwareRag::load(tmp.path(), "datasheets").unwrap();
+        assert!(rag.retrieve("gamma", &[], 5).len() == 1);
+        assert!(rag.retrieve("alpha", &[], 5).is_empty());
+        assert!(rag.retrieve("beta", &[], 5).is_empty());
+    }
+
+    /// Embeds text as presence of a few concepts, each matched by several
+    /// synonyms, and counts provider calls.
+    struct FakeEmbedding {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    const CONCEPTS: [&[&str]; 2] = [&["uart", "serial", "com port"], &["timer", "pwm"]];
+
+    #[async_trait::async_trait]
+    impl EmbeddingProvider for FakeEmbedding {
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        fn dimensions(&self) -> usize {
+            CONCEPTS.len()
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            self.calls
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let text = text.to_lowercase();
+                    CONCEPTS
+                        .iter()
+                        .map(|words| {
+                            if words.iter().any(|w| text.contains(w)) {
+                                1.0
+                            } else {
+                                0.0
+                            }
+                        })
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    fn fake_embedding(embedder: &Arc<FakeEmbedding>) -> RagEmbedding {
+        RagEmbedding {
+            embedder: embedder.clone(),
+            model: "fake-1".into(),
+            vector_weight: 0.7,
+            keyword_weight: 0.3,
+        }
+    }
+
+    #[tokio::test]
+    async fn hybrid_search_uses_embeddings_and_caches_vectors() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(
+            base.join("esp32.md"),
+            "# ESP32\n## UART\nUART0 is the serial console on GPIO1/GPIO3.",
+        )
+        .unwrap();
+        std::fs::write(
+            base.join("nucleo.md"),
+            "# Nucleo\n## USART2\nUSART2 is wired to the ST-Link virtual COM port.",
+        )
+        .unwrap();
+
+        let embedder = Arc::new(FakeEmbedding {
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        });
+        let rag =
+            HardwareRag::load_with_embeddings(tmp.path(), "datasheets", fake_embedding(&embedder))
+                .await
+                .unwrap();
+        assert_eq!(rag.embedding_model(), Some("fake-1"));
+
+        // "serial console" has no keyword hit in the Nucleo sheet but is
+        // semantically close; the board boost puts the requested board first.
+        let boards = vec!["nucleo".to_string()];
+        let hits = rag.search("serial console", &boards, 5).await;
+        assert_eq!(hits[0].chunk.board.as_deref(), Some("nucleo"));
+        assert!(hits[0].chunk.content.contains("COM port"));
+        assert!(hits[0].vector_score.unwrap() > 0.0);
+        assert!(hits[0].keyword_score.is_none());
+
+        // Reloading embeds nothing new: only the query is embedded.
+        let before = embedder.calls.load(std::sync::atomic::Ordering::Relaxed);
+        let rag =
+            HardwareRag::load_with_embeddings(tmp.path(), "datasheets", fake_embedding(&embedder))
+                .await
+                .unwrap();
+        assert_eq!(
+            embedder.calls.load(std::sync::atomic::Ordering::Relaxed),
+            before
+        );
+        rag.search("timer", &boards, 1).await;
+        assert_eq!(
+            embedder.calls.load(std::sync::atomic::Ordering::Relaxed),
+            before + 1
+        );
+    }
+
     #[test]
     fn hardware_rag_load_empty_dir() {
         let tmp = tempfile::tempdir().unwrap();
@@ -0,0 +1,287 @@
+//! Table detection for datasheets.
+//!
+//! Register maps and pinouts are tables, and chunking them as prose splits a
+//! register's name from its offset. This module finds tables in markdown
+//! (`| a | b |`) and in PDF text, where `pdf-extract` leaves columns separated
+//! by runs of spaces, and turns register/pinout tables into one structured
+//! row per line.
+
+use serde::{Deserialize, Serialize};
+
+/// What a table describes, judged from its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableKind {
+    Register,
+    Pinout,
+}
+
+impl TableKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Register => "register",
+            Self::Pinout => "pinout",
+        }
+    }
+}
+
+/// One row of a register or pinout table, as `(column, value)` pairs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableRow {
+    pub kind: TableKind,
+    /// Nearest heading or caption above the table (e.g. "GPIO registers").
+    pub section: Option<String>,
+    pub cells: Vec<(String, String)>,
+}
+
+impl TableRow {
+    /// Value of `column`, matched case-insensitively.
+    pub fn get(&self, column: &str) -> Option<&str> {
+        self.cells
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(column))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Single-line text used for retrieval and prompt context.
+    pub fn render(&self) -> String {
+        let cells = self
+            .cells
+            .iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        match &self.section {
+            Some(section) => format!("[{} table: {section}] {cells}", self.kind.label()),
+            None => format!("[{} table] {cells}", self.kind.label()),
+        }
+    }
+}
+
+const REGISTER_HEADERS: &[&str] = &[
+    "register", "offset", "address", "addr", "reset", "bit", "bits", "field", "access", "r/w",
+];
+const PINOUT_HEADERS: &[&str] = &[
+    "pin",
+    "gpio",
+    "signal",
+    "function",
+    "alternate",
+    "af",
+    "port",
+    "pad",
+    "ball",
+];
+
+/// Classify a table from its header, or `None` for tables that are neither
+/// register maps nor pinouts.
+fn classify(header: &[String]) -> Option<TableKind> {
+    let count = |keywords: &[&str]| {
+        header
+            .iter()
+            .filter(|cell| {
+                let cell = cell.to_lowercase();
+                cell.split(|c: char| !c.is_alphanumeric() && c != '/')
+                    .any(|word| keywords.contains(&word))
+            })
+            .count()
+    };
+    let register = count(REGISTER_HEADERS);
+    let pinout = count(PINOUT_HEADERS);
+    if register == 0 && pinout == 0 {
+        None
+    } else if register >= pinout {
+        Some(TableKind::Register)
+    } else {
+        Some(TableKind::Pinout)
+    }
+}
+
+fn markdown_cells(line: &str) -> Option<Vec<String>> {
+    let inner = line.trim().strip_prefix('|')?;
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    Some(inner.split('|').map(|c| c.trim().to_string()).collect())
+}
+
+fn is_separator(cells: &[String]) -> bool {
+    cells
+        .iter()
+        .all(|c| !c.is_empty() && c.chars().all(|ch| matches!(ch, '-' | ':' | ' ')))
+}
+
+/// Cells of a PDF text line whose columns are separated by two or more
+/// spaces or a tab.
+fn spaced_cells(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut spaces = 0;
+    for ch in line.trim().chars() {
+        if ch == '\t' {
+            spaces = 2;
+        } else if ch == ' ' {
+            spaces += 1;
+        } else {
+            if spaces >= 2 && !current.is_empty() {
+                cells.push(std::mem::take(&mut current));
+            } else if spaces == 1 {
+                current.push(' ');
+            }
+            spaces = 0;
+            current.push(ch);
+        }
+    }
+    if !current.is_empty() {
+        cells.push(current);
+    }
+    cells
+}
+
+fn heading(line: &str) -> Option<String> {
+    let title = line.trim_start().strip_prefix('#')?.trim_start_matches('#');
+    let title = title.trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Short non-table line above a table, e.g. "Table 12. GPIO register map".
+fn caption(line: &str) -> Option<String> {
+    let line = line.trim();
+    let captioned = line.to_lowercase().starts_with("table ") || line.ends_with(':');
+    (captioned && line.len() <= 80 && spaced_cells(line).len() == 1)
+        .then(|| line.trim_end_matches(':').to_string())
+}
+
+fn rows_from(
+    kind: TableKind,
+    section: Option<&String>,
+    header: &[String],
+    body: &[Vec<String>],
+) -> Vec<TableRow> {
+    body.iter()
+        .filter(|cells| cells.iter().any(|c| !c.is_empty()))
+        .map(|cells| TableRow {
+            kind,
+            section: section.cloned(),
+            cells: header
+                .iter()
+                .cloned()
+                .zip(
+                    cells
+                        .iter()
+                        .cloned()
+                        .chain(std::iter::repeat(String::new())),
+                )
+                .collect(),
+        })
+        .collect()
+}
+
+/// Register and pinout rows found in `content`.
+pub fn extract_table_rows(content: &str) -> Vec<TableRow> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut rows = Vec::new();
+    let mut section: Option<String> = None;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        if let Some(title) = heading(line) {
+            section = Some(title);
+            i += 1;
+            continue;
+        }
+
+        // Markdown table: header, separator, rows.
+        if let Some(header) = markdown_cells(line) {
+            let mut end = i + 1;
+            let mut body = Vec::new();
+            while let Some(cells) = lines.get(end).and_then(|l| markdown_cells(l)) {
+                if !is_separator(&cells) {
+                    body.push(cells);
+                }
+                end += 1;
+            }
+            if let Some(kind) = classify(&header) {
+                rows.extend(rows_from(kind, section.as_ref(), &header, &body));
+            }
+            i = end;
+            continue;
+        }
+
+        // Whitespace-aligned table: a header followed by at least two rows
+        // with the same number of columns.
+        let header = spaced_cells(line);
+        if header.len() >= 2 {
+            let mut end = i + 1;
+            while end < lines.len() && spaced_cells(lines[end]).len() == header.len() {
+                end += 1;
+            }
+            if end - i >= 3 {
+                if let Some(kind) = classify(&header) {
+                    let body: Vec<Vec<String>> =
+                        lines[i + 1..end].iter().map(|l| spaced_cells(l)).collect();
+                    rows.extend(rows_from(kind, section.as_ref(), &header, &body));
+                }
+                i = end;
+                continue;
+            }
+        }
+
+        if let Some(title) = caption(line) {
+            section = Some(title);
+        }
+        i += 1;
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_register_table_becomes_rows() {
+        let md = "## GPIO registers\n\
+                  | Register | Offset | Reset value |\n\
+                  |----------|--------|-------------|\n\
+                  | GPIOx_MODER | 0x00 | 0xA800 0000 |\n\
+                  | GPIOx_OTYPER | 0x04 | 0x0000 0000 |\n";
+        let rows = extract_table_rows(md);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].kind, TableKind::Register);
+        assert_eq!(rows[0].section.as_deref(), Some("GPIO registers"));
+        assert_eq!(rows[0].get("register"), Some("GPIOx_MODER"));
+        assert_eq!(rows[1].get("Offset"), Some("0x04"));
+        assert_eq!(
+            rows[0].render(),
+            "[register table: GPIO registers] Register: GPIOx_MODER | Offset: 0x00 | Reset value: 0xA800 0000"
+        );
+    }
+
+    #[test]
+    fn pdf_text_pinout_table_becomes_rows() {
+        let text = "Table 8. Pin definitions\n\
+                    Pin   Name      Alternate function\n\
+                    2     PC13      RTC_AF1\n\
+                    14    PA0       TIM2_CH1, USART2_CTS\n\
+                    \n\
+                    The device has 81 I/Os.";
+        let rows = extract_table_rows(text);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].kind, TableKind::Pinout);
+        assert_eq!(rows[0].section.as_deref(), Some("Table 8. Pin definitions"));
+        assert_eq!(rows[1].get("name"), Some("PA0"));
+        assert_eq!(
+            rows[1].get("alternate function"),
+            Some("TIM2_CH1, USART2_CTS")
+        );
+    }
+
+    #[test]
+    fn unrelated_tables_and_prose_are_ignored() {
+        let md = "| Version | Date |\n|---|---|\n| 1.0 | 2024 |\n\n\
+                  Plain  text  with  spacing\nbut only one line of it.";
+        assert!(extract_table_rows(md).is_empty());
+    }
+}
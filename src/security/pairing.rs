@@ -151,7 +151,7 @@ impl PairingGuard {
 }
 
 /// Generate a 6-digit numeric pairing code using cryptographically secure randomness.
-fn generate_code() -> String {
+pub(crate) fn generate_code() -> String {
     // UUID v4 uses getrandom (backed by /dev/urandom on Linux, BCryptGenRandom
     // on Windows) — a CSPRNG. We extract 4 bytes from it for a uniform random
     // number in [0, 1_000_000).
@@ -188,7 +188,7 @@ fn generate_token() -> String {
 }
 
 /// SHA-256 hash a bearer token for storage. Returns lowercase hex.
-fn hash_token(token: &str) -> String {
+pub(crate) fn hash_token(token: &str) -> String {
     format!("{:x}", Sha256::digest(token.as_bytes()))
 }
 
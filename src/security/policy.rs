@@ -499,24 +499,45 @@ impl SecurityPolicy {
         }
 
         // Block forbidden paths using path-component-aware matching
-        let expanded_path = Path::new(&expanded);
-        for forbidden in &self.forbidden_paths {
-            let forbidden_expanded = if let Some(stripped) = forbidden.strip_prefix("~/") {
+        !self.is_forbidden_path(Path::new(&expanded))
+    }
+
+    /// `forbidden_paths` with a leading `~/` expanded to `$HOME`.
+    fn forbidden_roots(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        self.forbidden_paths.iter().map(|forbidden| {
+            if let Some(stripped) = forbidden.strip_prefix("~/") {
                 if let Some(home) = std::env::var("HOME").ok().map(PathBuf::from) {
-                    home.join(stripped).to_string_lossy().to_string()
-                } else {
-                    forbidden.clone()
+                    return home.join(stripped);
                 }
-            } else {
-                forbidden.clone()
-            };
-            let forbidden_path = Path::new(&forbidden_expanded);
-            if expanded_path.starts_with(forbidden_path) {
-                return false;
             }
-        }
+            PathBuf::from(forbidden)
+        })
+    }
+
+    /// Check whether a path falls under one of `forbidden_paths`.
+    /// Matching is path-component-aware, so `/etc` does not block `/etcetera`.
+    pub fn is_forbidden_path(&self, path: &Path) -> bool {
+        self.forbidden_roots()
+            .any(|forbidden| path.starts_with(forbidden))
+    }
 
-        true
+    /// Check a canonicalized path inside the workspace against
+    /// `forbidden_paths`, both as-is and relative to the workspace. Entries
+    /// that contain the whole workspace (e.g. `/home` for a workspace under
+    /// `~/.zeroclaw`) are skipped; entries inside it still apply.
+    pub fn is_forbidden_in_workspace(&self, resolved: &Path) -> bool {
+        let workspace_root = self
+            .workspace_dir
+            .canonicalize()
+            .unwrap_or_else(|_| self.workspace_dir.clone());
+        let relative = resolved.strip_prefix(&workspace_root).ok();
+        self.forbidden_roots().any(|forbidden| {
+            if forbidden.is_absolute() {
+                !workspace_root.starts_with(&forbidden) && resolved.starts_with(&forbidden)
+            } else {
+                relative.is_some_and(|relative| relative.starts_with(&forbidden))
+            }
+        })
     }
 
     /// Validate that a resolved path is still inside the workspace.
@@ -564,6 +585,21 @@ impl SecurityPolicy {
         }
     }
 
+    /// Gate a side-effecting operation that is sensitive regardless of rate
+    /// budget (e.g. unlocking a door). Supervised mode needs `approved=true`;
+    /// read-only mode never allows it.
+    pub fn enforce_approval(&self, operation_name: &str, approved: bool) -> Result<(), String> {
+        match self.autonomy {
+            AutonomyLevel::ReadOnly => Err(format!(
+                "Security policy: read-only mode, cannot perform '{operation_name}'"
+            )),
+            AutonomyLevel::Supervised if !approved => Err(format!(
+                "'{operation_name}' requires explicit approval (approved=true)"
+            )),
+            _ => Ok(()),
+        }
+    }
+
     /// Record an action and check if the rate limit has been exceeded.
     /// Returns `true` if the action is allowed, `false` if rate-limited.
     pub fn record_action(&self) -> bool {
@@ -635,6 +671,21 @@ mod tests {
         assert_eq!(parsed2, AutonomyLevel::Supervised);
     }
 
+    #[test]
+    fn enforce_approval_depends_on_autonomy() {
+        assert!(readonly_policy()
+            .enforce_approval("lock.unlock", true)
+            .is_err());
+        let err = default_policy()
+            .enforce_approval("lock.unlock", false)
+            .unwrap_err();
+        assert!(err.contains("approved=true"));
+        assert!(default_policy()
+            .enforce_approval("lock.unlock", true)
+            .is_ok());
+        assert!(full_policy().enforce_approval("lock.unlock", false).is_ok());
+    }
+
     #[test]
     fn can_act_readonly_false() {
         assert!(!readonly_policy().can_act());
@@ -886,6 +937,18 @@ mod tests {
         assert!(!p.is_path_allowed("~/.gnupg/pubring.kbx"));
     }
 
+    #[test]
+    fn is_forbidden_path_matches_components() {
+        let p = SecurityPolicy {
+            forbidden_paths: vec!["/etc".into(), "secrets".into()],
+            ..SecurityPolicy::default()
+        };
+        assert!(p.is_forbidden_path(Path::new("/etc/passwd")));
+        assert!(p.is_forbidden_path(Path::new("secrets/api.key")));
+        assert!(!p.is_forbidden_path(Path::new("/etcetera")));
+        assert!(!p.is_forbidden_path(Path::new("src/secrets.rs")));
+    }
+
     #[test]
     fn empty_path_allowed() {
         let p = default_policy();
@@ -1361,6 +1424,22 @@ mod tests {
         assert!(!p.is_resolved_path_allowed(Path::new("/")));
     }
 
+    #[test]
+    fn forbidden_in_workspace_skips_entries_containing_the_workspace() {
+        let mut p = SecurityPolicy {
+            workspace_dir: PathBuf::from("/home/user/project"),
+            ..SecurityPolicy::default()
+        };
+        p.forbidden_paths.push("/home/user/project/secrets".into());
+        p.forbidden_paths.push(".env".into());
+
+        // `/home` covers the whole workspace, so it does not block files in it
+        assert!(!p.is_forbidden_in_workspace(Path::new("/home/user/project/src/main.rs")));
+        assert!(p.is_forbidden_in_workspace(Path::new("/home/user/project/secrets/key.pem")));
+        assert!(p.is_forbidden_in_workspace(Path::new("/home/user/project/.env")));
+        assert!(!p.is_forbidden_in_workspace(Path::new("/home/user/project/.envrc")));
+    }
+
     #[test]
     fn checklist_default_policy_is_workspace_only() {
         let p = SecurityPolicy::default();
@@ -0,0 +1,396 @@
+//! Persistent CLI agent sessions.
+//!
+//! Each interactive `zeroclaw agent` run is saved to
+//! `<workspace>/sessions/<id>.json` after every turn, including tool calls and
+//! tool results, so a conversation can be resumed with `--resume <id>` or
+//! `--continue` after the terminal is closed.
+
+use crate::config::Config;
+use crate::providers::ChatMessage;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const SESSIONS_DIR: &str = "sessions";
+const MAX_TITLE_CHARS: usize = 60;
+
+/// Which saved session `zeroclaw agent` should pick up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resume {
+    /// A session id or unique id prefix.
+    Id(String),
+    /// The most recently updated session.
+    Latest,
+}
+
+/// A saved conversation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub id: String,
+    pub title: String,
+    pub provider: String,
+    pub model: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Full conversation transcript, including tool calls and tool results.
+    /// The system prompt is not stored; it is rebuilt on resume.
+    pub messages: Vec<ChatMessage>,
+}
+
+impl Session {
+    pub fn new(provider: &str, model: &str) -> Self {
+        let now = Utc::now();
+        let suffix = uuid::Uuid::new_v4().simple().to_string();
+        Self {
+            id: format!("{}-{}", now.format("%Y%m%d-%H%M%S"), &suffix[..6]),
+            title: String::new(),
+            provider: provider.to_string(),
+            model: model.to_string(),
+            created_at: now,
+            updated_at: now,
+            messages: Vec::new(),
+        }
+    }
+
+    /// Set the title from the first user input, if not already set.
+    pub fn set_title_from(&mut self, user_input: &str) {
+        if !self.title.is_empty() {
+            return;
+        }
+        let first_line = user_input.lines().next().unwrap_or_default().trim();
+        self.title = crate::util::truncate_with_ellipsis(first_line, MAX_TITLE_CHARS);
+    }
+
+    /// Number of messages typed by the user (system and tool traffic excluded).
+    pub fn user_turns(&self) -> usize {
+        self.messages.iter().filter(|m| m.role == "user").count()
+    }
+
+    /// Render the conversation as Markdown. The system prompt is omitted.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let title = if self.title.is_empty() {
+            "Untitled session"
+        } else {
+            &self.title
+        };
+        let _ = writeln!(out, "# {title}\n");
+        let _ = writeln!(out, "- **Session:** `{}`", self.id);
+        let _ = writeln!(out, "- **Model:** {} ({})", self.model, self.provider);
+        let _ = writeln!(out, "- **Created:** {}", self.created_at.to_rfc3339());
+        let _ = writeln!(out, "- **Updated:** {}", self.updated_at.to_rfc3339());
+
+        for message in self.messages.iter().filter(|m| m.role != "system") {
+            let heading = match message.role.as_str() {
+                "user" => "User",
+                "assistant" => "Assistant",
+                "tool" => "Tool result",
+                other => other,
+            };
+            let _ = write!(out, "\n## {heading}\n\n");
+            if message.role == "tool" {
+                let _ = writeln!(out, "```\n{}\n```", message.content.trim_end());
+            } else {
+                let _ = writeln!(out, "{}", message.content.trim_end());
+            }
+        }
+        out
+    }
+
+    /// Render as JSON Lines: one metadata record followed by one record per message.
+    pub fn to_jsonl(&self) -> Result<String> {
+        let header = serde_json::json!({
+            "type": "session",
+            "id": self.id,
+            "title": self.title,
+            "provider": self.provider,
+            "model": self.model,
+            "created_at": self.created_at,
+            "updated_at": self.updated_at,
+        });
+        let mut out = serde_json::to_string(&header)?;
+        out.push('\n');
+        for message in &self.messages {
+            let record = serde_json::json!({
+                "type": "message",
+                "role": message.role,
+                "content": message.content,
+            });
+            out.push_str(&serde_json::to_string(&record)?);
+            out.push('\n');
+        }
+        Ok(out)
+    }
+}
+
+/// Directory-backed session storage under the workspace.
+pub struct SessionStore {
+    dir: PathBuf,
+}
+
+impl SessionStore {
+    pub fn new(workspace_dir: &Path) -> Self {
+        Self {
+            dir: workspace_dir.join(SESSIONS_DIR),
+        }
+    }
+
+    fn path_for(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    /// Persist a session, replacing the previous copy atomically.
+    pub fn save(&self, session: &Session) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.path_for(&session.id);
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, serde_json::to_vec_pretty(session)?)
+            .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+        std::fs::rename(&temp_path, &path)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    /// All saved sessions, most recently updated first.
+    pub fn list(&self) -> Result<Vec<Session>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut sessions = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            match std::fs::read(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|bytes| Ok(serde_json::from_slice::<Session>(&bytes)?))
+            {
+                Ok(session) => sessions.push(session),
+                Err(e) => tracing::warn!("Skipping unreadable session {}: {e}", path.display()),
+            }
+        }
+        sessions.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(sessions)
+    }
+
+    /// Resolve an exact id or a unique id prefix to a stored session id.
+    fn resolve_id(&self, id: &str) -> Result<String> {
+        if id.is_empty() || id.contains(['/', '\\']) || id.contains("..") {
+            bail!("Invalid session id: {id}");
+        }
+        if self.path_for(id).is_file() {
+            return Ok(id.to_string());
+        }
+
+        let matches: Vec<String> = self
+            .list()?
+            .into_iter()
+            .map(|s| s.id)
+            .filter(|candidate| candidate.starts_with(id))
+            .collect();
+        match matches.as_slice() {
+            [only] => Ok(only.clone()),
+            [] => bail!("No session matches '{id}'. Run `zeroclaw sessions list`."),
+            _ => bail!(
+                "Session id '{id}' is ambiguous ({} matches); use more characters",
+                matches.len()
+            ),
+        }
+    }
+
+    pub fn load(&self, id: &str) -> Result<Session> {
+        let id = self.resolve_id(id)?;
+        let path = self.path_for(&id);
+        let bytes =
+            std::fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("Failed to parse session {}", path.display()))
+    }
+
+    pub fn latest(&self) -> Result<Option<Session>> {
+        Ok(self.list()?.into_iter().next())
+    }
+
+    /// Load the session selected by `resume`.
+    pub fn open(&self, resume: &Resume) -> Result<Session> {
+        match resume {
+            Resume::Id(id) => self.load(id),
+            Resume::Latest => self
+                .latest()?
+                .context("No saved sessions to continue. Start one with `zeroclaw agent`."),
+        }
+    }
+
+    /// Delete a session; returns the resolved id.
+    pub fn delete(&self, id: &str) -> Result<String> {
+        let id = self.resolve_id(id)?;
+        std::fs::remove_file(self.path_for(&id))?;
+        Ok(id)
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn handle_command(command: crate::SessionCommands, config: &Config) -> Result<()> {
+    let store = SessionStore::new(&config.workspace_dir);
+    match command {
+        crate::SessionCommands::List { limit } => {
+            let sessions = store.list()?;
+            if sessions.is_empty() {
+                println!("No saved sessions yet. Start one with `zeroclaw agent`.");
+                return Ok(());
+            }
+
+            println!("💬 Saved sessions ({}):", sessions.len());
+            for session in sessions.iter().take(limit) {
+                println!(
+                    "- {} | {} | {} turn(s) | {} | {}",
+                    session.id,
+                    session.updated_at.format("%Y-%m-%d %H:%M"),
+                    session.user_turns(),
+                    session.model,
+                    if session.title.is_empty() {
+                        "(untitled)"
+                    } else {
+                        &session.title
+                    }
+                );
+            }
+            if sessions.len() > limit {
+                println!("  … {} more (use --limit)", sessions.len() - limit);
+            }
+            println!("\nResume with: zeroclaw agent --resume <id>");
+            Ok(())
+        }
+        crate::SessionCommands::Show { id } => {
+            print!("{}", store.load(&id)?.to_markdown());
+            Ok(())
+        }
+        crate::SessionCommands::Export { id, format, output } => {
+            let session = store.load(&id)?;
+            let rendered = match format.as_str() {
+                "markdown" | "md" => session.to_markdown(),
+                "jsonl" => session.to_jsonl()?,
+                other => bail!("Unknown export format '{other}'; use markdown or jsonl"),
+            };
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, rendered)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    println!("✅ Exported session {} to {}", session.id, path.display());
+                }
+                None => print!("{rendered}"),
+            }
+            Ok(())
+        }
+        crate::SessionCommands::Delete { id } => {
+            let id = store.delete(&id)?;
+            println!("✅ Deleted session {id}");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample(provider: &str) -> Session {
+        let mut session = Session::new(provider, "test-model");
+        session.messages = vec![
+            ChatMessage::system("system prompt"),
+            ChatMessage::user("Fix the failing build\nplease"),
+            ChatMessage::assistant("<tool_call>{\"name\":\"shell\"}</tool_call>"),
+            ChatMessage::tool("cargo build: ok"),
+            ChatMessage::assistant("Build fixed."),
+        ];
+        session.set_title_from("Fix the failing build\nplease");
+        session
+    }
+
+    #[test]
+    fn session_roundtrip_and_prefix_lookup() {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::new(tmp.path());
+        let session = sample("openrouter");
+        store.save(&session).unwrap();
+
+        let loaded = store.load(&session.id[..10]).unwrap();
+        assert_eq!(loaded.id, session.id);
+        assert_eq!(loaded.title, "Fix the failing build");
+        assert_eq!(loaded.messages.len(), 5);
+        assert_eq!(loaded.user_turns(), 1);
+    }
+
+    #[test]
+    fn latest_returns_most_recently_updated() {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::new(tmp.path());
+        let mut older = sample("a");
+        older.id = "older".into();
+        older.updated_at = Utc::now() - chrono::Duration::hours(1);
+        let mut newer = sample("b");
+        newer.id = "newer".into();
+        store.save(&older).unwrap();
+        store.save(&newer).unwrap();
+
+        assert_eq!(store.open(&Resume::Latest).unwrap().id, "newer");
+        assert_eq!(store.list().unwrap().len(), 2);
+
+        store.delete("older").unwrap();
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn resolve_rejects_ambiguous_missing_and_traversal() {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::new(tmp.path());
+        for id in ["abc-1", "abc-2"] {
+            let mut s = sample("p");
+            s.id = id.into();
+            store.save(&s).unwrap();
+        }
+        let err = |id: &str| store.load(id).unwrap_err().to_string();
+        assert!(err("abc").contains("ambiguous"));
+        assert!(err("zzz").contains("No session"));
+        assert!(err("../x").contains("Invalid"));
+        assert!(store.open(&Resume::Id("abc-2".into())).is_ok());
+    }
+
+    #[test]
+    fn latest_is_none_without_sessions() {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::new(tmp.path());
+        assert!(store.latest().unwrap().is_none());
+        assert!(store.open(&Resume::Latest).is_err());
+    }
+
+    #[test]
+    fn markdown_export_skips_system_prompt() {
+        let md = sample("p").to_markdown();
+        assert!(md.starts_with("# Fix the failing build\n"));
+        assert!(!md.contains("system prompt"));
+        assert!(md.contains("## Tool result\n\n```\ncargo build: ok\n```"));
+        assert!(md.contains("## Assistant\n\nBuild fixed."));
+    }
+
+    #[test]
+    fn jsonl_export_has_header_and_one_line_per_message() {
+        let jsonl = sample("p").to_jsonl().unwrap();
+        let lines: Vec<serde_json::Value> = jsonl
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0]["type"], "session");
+        assert_eq!(lines[0]["model"], "test-model");
+        assert_eq!(lines[4]["role"], "tool");
+    }
+}
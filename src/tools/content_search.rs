@@ -0,0 +1,421 @@
+use super::traits::{Tool, ToolResult};
+use super::workspace_walk::{self, GLOB_MATCH_OPTIONS};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use glob::Pattern;
+use regex::{Regex, RegexBuilder};
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+const DEFAULT_MAX_RESULTS: usize = 100;
+const MAX_RESULTS_CAP: usize = 500;
+const MAX_CONTEXT_LINES: usize = 10;
+/// Files larger than this are skipped rather than searched.
+const MAX_SEARCH_FILE_BYTES: u64 = 2 * 1024 * 1024;
+/// Matched lines longer than this are cut to keep output readable.
+const MAX_LINE_CHARS: usize = 500;
+/// Bytes inspected when deciding whether a file is binary.
+const BINARY_SNIFF_BYTES: usize = 8192;
+
+/// Regex search across workspace files, honoring `.gitignore`.
+pub struct ContentSearchTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl ContentSearchTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+struct SearchOptions {
+    regex: Regex,
+    include: Option<Pattern>,
+    context_lines: usize,
+    max_results: usize,
+}
+
+#[derive(Default)]
+struct SearchOutcome {
+    output: String,
+    matches: usize,
+    files: usize,
+    truncated: bool,
+}
+
+fn truncate_line(line: &str) -> String {
+    if line.chars().count() <= MAX_LINE_CHARS {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(MAX_LINE_CHARS).collect();
+    format!("{cut}…")
+}
+
+/// Append grep-style output for one file. Matching lines use `path:line:`,
+/// context lines use `path-line-`, and non-adjacent groups are split by `--`.
+fn search_file(
+    relative: &str,
+    contents: &str,
+    options: &SearchOptions,
+    outcome: &mut SearchOutcome,
+) {
+    let lines: Vec<&str> = contents.lines().collect();
+    let hits: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| options.regex.is_match(line))
+        .map(|(index, _)| index)
+        .collect();
+    if hits.is_empty() {
+        return;
+    }
+
+    outcome.files += 1;
+    let mut last_printed: Option<usize> = None;
+    for hit in hits {
+        if outcome.matches == options.max_results {
+            outcome.truncated = true;
+            return;
+        }
+        outcome.matches += 1;
+
+        let from = hit.saturating_sub(options.context_lines);
+        let to = (hit + options.context_lines).min(lines.len() - 1);
+        let from = last_printed.map_or(from, |last| from.max(last + 1));
+        if let Some(last) = last_printed {
+            if from > last + 1 {
+                outcome.output.push_str("--\n");
+            }
+        } else if !outcome.output.is_empty() {
+            outcome.output.push_str("--\n");
+        }
+
+        for (index, line) in lines.iter().enumerate().take(to + 1).skip(from) {
+            let separator = if index == hit || options.regex.is_match(line) {
+                ':'
+            } else {
+                '-'
+            };
+            let _ = writeln!(
+                outcome.output,
+                "{relative}{separator}{}{separator}{}",
+                index + 1,
+                truncate_line(line)
+            );
+        }
+        last_printed = Some(to.max(last_printed.unwrap_or(0)));
+    }
+}
+
+#[async_trait]
+impl Tool for ContentSearchTool {
+    fn name(&self) -> &str {
+        "content_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search file contents in the workspace with a regular expression. Respects .gitignore, skips binary files, and returns 'path:line:text' matches with optional context lines."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pattern": {
+                    "type": "string",
+                    "description": "Regular expression to search for (Rust regex syntax)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Directory to search, relative to the workspace (default: workspace root)"
+                },
+                "include": {
+                    "type": "string",
+                    "description": "Only search files whose path (relative to 'path') matches this glob, e.g. '**/*.rs'"
+                },
+                "case_insensitive": {
+                    "type": "boolean",
+                    "description": "Match case-insensitively (default: false)"
+                },
+                "context_lines": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": 10,
+                    "description": "Lines of context to show before and after each match (default: 0)"
+                },
+                "max_results": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Maximum number of matching lines to return (default: 100, max: 500)"
+                }
+            },
+            "required": ["pattern"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let pattern = args
+            .get("pattern")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pattern' parameter"))?;
+        let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+        let case_insensitive = args
+            .get("case_insensitive")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        let context_lines = args
+            .get("context_lines")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(0, |n| usize::try_from(n).unwrap_or(MAX_CONTEXT_LINES))
+            .min(MAX_CONTEXT_LINES);
+        let max_results = args
+            .get("max_results")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_MAX_RESULTS, |n| {
+                usize::try_from(n).unwrap_or(MAX_RESULTS_CAP)
+            })
+            .clamp(1, MAX_RESULTS_CAP);
+
+        let regex = match RegexBuilder::new(pattern)
+            .case_insensitive(case_insensitive)
+            .size_limit(1 << 20)
+            .build()
+        {
+            Ok(regex) => regex,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Invalid regex: {e}")),
+                });
+            }
+        };
+        let include = match args.get("include").and_then(|v| v.as_str()) {
+            Some(glob) => match Pattern::new(glob.trim_start_matches("./")) {
+                Ok(p) => Some(p),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid include glob: {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        let (workspace_root, start) =
+            match workspace_walk::resolve_search_root(&self.security, path) {
+                Ok(roots) => roots,
+                Err(message) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(message),
+                    });
+                }
+            };
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let options = SearchOptions {
+            regex,
+            include,
+            context_lines,
+            max_results,
+        };
+        let security = self.security.clone();
+        let outcome = tokio::task::spawn_blocking(move || {
+            let mut outcome = SearchOutcome::default();
+            workspace_walk::walk(
+                &security,
+                &workspace_root,
+                &start,
+                usize::MAX,
+                true,
+                |entry| {
+                    if entry.is_dir {
+                        return true;
+                    }
+                    if let Some(include) = &options.include {
+                        let scoped = entry
+                            .absolute
+                            .strip_prefix(&start)
+                            .unwrap_or(&entry.absolute)
+                            .to_string_lossy()
+                            .replace('\\', "/");
+                        if !include.matches_with(&scoped, GLOB_MATCH_OPTIONS) {
+                            return true;
+                        }
+                    }
+                    let too_large = std::fs::metadata(&entry.absolute)
+                        .map_or(true, |meta| meta.len() > MAX_SEARCH_FILE_BYTES);
+                    if too_large {
+                        return true;
+                    }
+                    let Ok(bytes) = std::fs::read(&entry.absolute) else {
+                        return true;
+                    };
+                    if bytes.iter().take(BINARY_SNIFF_BYTES).any(|b| *b == 0) {
+                        return true;
+                    }
+                    let contents = String::from_utf8_lossy(&bytes);
+                    search_file(&entry.relative, &contents, &options, &mut outcome);
+                    !outcome.truncated
+                },
+            );
+            outcome
+        })
+        .await?;
+
+        if outcome.matches == 0 {
+            return Ok(ToolResult {
+                success: true,
+                output: format!("No matches for '{pattern}'"),
+                error: None,
+            });
+        }
+
+        let mut output = outcome.output;
+        if outcome.truncated {
+            let _ = write!(
+                output,
+                "[Results truncated at {max_results} matches; narrow the pattern, path or include glob]"
+            );
+        } else {
+            let _ = write!(
+                output,
+                "[{} match(es) in {} file(s)]",
+                outcome.matches, outcome.files
+            );
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn options(pattern: &str, context_lines: usize, max_results: usize) -> SearchOptions {
+        SearchOptions {
+            regex: Regex::new(pattern).unwrap(),
+            include: None,
+            context_lines,
+            max_results,
+        }
+    }
+
+    #[test]
+    fn content_search_name_and_schema() {
+        let tool = ContentSearchTool::new(test_security(std::env::temp_dir()));
+        assert_eq!(tool.name(), "content_search");
+        assert!(tool.parameters_schema()["properties"]["context_lines"].is_object());
+    }
+
+    #[test]
+    fn search_file_renders_context_groups() {
+        let contents = "a\nfoo 1\nb\nc\nd\ne\nfoo 2\nf\n";
+        let mut outcome = SearchOutcome::default();
+        search_file("x.txt", contents, &options("foo", 1, 10), &mut outcome);
+        assert_eq!(outcome.matches, 2);
+        assert_eq!(
+            outcome.output,
+            "x.txt-1-a\nx.txt:2:foo 1\nx.txt-3-b\n--\nx.txt-6-e\nx.txt:7:foo 2\nx.txt-8-f\n"
+        );
+    }
+
+    #[test]
+    fn search_file_merges_overlapping_context_and_caps_results() {
+        let mut outcome = SearchOutcome::default();
+        search_file("x", "foo\nbar\nfoo\n", &options("foo", 1, 10), &mut outcome);
+        assert_eq!(outcome.output, "x:1:foo\nx-2-bar\nx:3:foo\n");
+
+        let mut outcome = SearchOutcome::default();
+        search_file("x", "foo\nfoo\nfoo\n", &options("foo", 0, 2), &mut outcome);
+        assert_eq!(outcome.matches, 2);
+        assert!(outcome.truncated);
+    }
+
+    #[tokio::test]
+    async fn content_search_finds_matches_and_respects_gitignore() {
+        let dir = std::env::temp_dir().join("zeroclaw_test_content_search");
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(dir.join("src")).await.unwrap();
+        tokio::fs::create_dir_all(dir.join("build")).await.unwrap();
+        tokio::fs::write(dir.join(".gitignore"), "build/\n")
+            .await
+            .unwrap();
+        tokio::fs::write(dir.join("src/main.rs"), "fn main() {\n    todo!()\n}\n")
+            .await
+            .unwrap();
+        tokio::fs::write(dir.join("build/gen.rs"), "todo!()\n")
+            .await
+            .unwrap();
+        tokio::fs::write(dir.join("blob.bin"), b"todo!\0\x01")
+            .await
+            .unwrap();
+
+        let tool = ContentSearchTool::new(test_security(dir.clone()));
+        let result = tool
+            .execute(json!({"pattern": "TODO!", "case_insensitive": true}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(
+            result.output,
+            "src/main.rs:2:    todo!()\n[1 match(es) in 1 file(s)]"
+        );
+
+        let result = tool
+            .execute(json!({"pattern": "todo", "include": "**/*.md"}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("No matches"));
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn content_search_rejects_invalid_regex_and_traversal() {
+        let tool = ContentSearchTool::new(test_security(std::env::temp_dir()));
+        let result = tool.execute(json!({"pattern": "("})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("Invalid regex"));
+
+        let result = tool
+            .execute(json!({"pattern": "x", "path": "../.."}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("not allowed"));
+    }
+}
@@ -1,9 +1,13 @@
 use super::traits::{Tool, ToolResult};
+use crate::agent::context::estimate_tokens;
 use crate::config::DelegateAgentConfig;
+use crate::observability::trace::{self, TurnUsage};
 use crate::providers::{self, Provider};
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
+use opentelemetry::trace::SpanKind;
+use opentelemetry::KeyValue;
 use serde_json::json;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -214,14 +218,29 @@ impl Tool for DelegateTool {
 
         let temperature = agent_config.temperature.unwrap_or(0.7);
 
+        // Sub-agent span, nested under the parent's `tool.call` span
+        let delegate_cx = trace::start_span(
+            "agent.delegate",
+            SpanKind::Client,
+            vec![
+                KeyValue::new("gen_ai.agent.name", agent_name.to_string()),
+                KeyValue::new("gen_ai.system", agent_config.provider.clone()),
+                KeyValue::new("gen_ai.request.model", agent_config.model.clone()),
+                KeyValue::new("zeroclaw.delegate.depth", i64::from(self.depth)),
+            ],
+        );
+
         // Wrap the provider call in a timeout to prevent indefinite blocking
-        let result = tokio::time::timeout(
-            Duration::from_secs(DELEGATE_TIMEOUT_SECS),
-            provider.chat_with_system(
-                agent_config.system_prompt.as_deref(),
-                &full_prompt,
-                &agent_config.model,
-                temperature,
+        let result = trace::in_span(
+            delegate_cx.clone(),
+            tokio::time::timeout(
+                Duration::from_secs(DELEGATE_TIMEOUT_SECS),
+                provider.chat_with_system(
+                    agent_config.system_prompt.as_deref(),
+                    &full_prompt,
+                    &agent_config.model,
+                    temperature,
+                ),
             ),
         )
         .await;
@@ -229,6 +248,7 @@ impl Tool for DelegateTool {
         let result = match result {
             Ok(inner) => inner,
             Err(_elapsed) => {
+                trace::end_span(&delegate_cx, Err("timed out".to_string()));
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
@@ -241,6 +261,20 @@ impl Tool for DelegateTool {
 
         match result {
             Ok(response) => {
+                let input_tokens = agent_config
+                    .system_prompt
+                    .as_deref()
+                    .map_or(0, estimate_tokens)
+                    + estimate_tokens(&full_prompt);
+                TurnUsage::default().record_llm_call(
+                    &delegate_cx,
+                    &agent_config.provider,
+                    &agent_config.model,
+                    input_tokens as u64,
+                    estimate_tokens(&response) as u64,
+                );
+                trace::end_span(&delegate_cx, Ok(()));
+
                 let mut rendered = response;
                 if rendered.trim().is_empty() {
                     rendered = "[Empty response]".to_string();
@@ -256,11 +290,17 @@ impl Tool for DelegateTool {
                     error: None,
                 })
             }
-            Err(e) => Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Agent '{agent_name}' failed: {e}",)),
-            }),
+            Err(e) => {
+                trace::end_span(
+                    &delegate_cx,
+                    Err(providers::sanitize_api_error(&e.to_string())),
+                );
+                Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Agent '{agent_name}' failed: {e}",)),
+                })
+            }
         }
     }
 }
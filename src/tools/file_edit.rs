@@ -0,0 +1,682 @@
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+const MAX_FILE_SIZE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Edit an existing workspace file in place with path sandboxing.
+///
+/// Supports exact string replacement (single or batched) and unified diff
+/// patches. A batch is applied in memory first and written only if every
+/// edit succeeds, so a failed edit never leaves a half-modified file.
+pub struct FileEditTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl FileEditTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+/// One exact-match replacement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StringEdit {
+    old_string: String,
+    new_string: String,
+    replace_all: bool,
+}
+
+fn parse_string_edit(value: &serde_json::Value) -> anyhow::Result<StringEdit> {
+    let old_string = value
+        .get("old_string")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("Missing 'old_string' parameter"))?;
+    let new_string = value
+        .get("new_string")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("Missing 'new_string' parameter"))?;
+    let replace_all = value
+        .get("replace_all")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false);
+
+    Ok(StringEdit {
+        old_string: old_string.to_string(),
+        new_string: new_string.to_string(),
+        replace_all,
+    })
+}
+
+/// Apply `edits` in order. Each `old_string` must match exactly once unless
+/// `replace_all` is set. Returns the new content and total replacement count.
+fn apply_string_edits(content: &str, edits: &[StringEdit]) -> Result<(String, usize), String> {
+    let mut updated = content.to_string();
+    let mut replacements = 0;
+
+    for (index, edit) in edits.iter().enumerate() {
+        let label = if edits.len() > 1 {
+            format!("Edit {}: ", index + 1)
+        } else {
+            String::new()
+        };
+
+        if edit.old_string.is_empty() {
+            return Err(format!("{label}old_string must not be empty"));
+        }
+        if edit.old_string == edit.new_string {
+            return Err(format!("{label}old_string and new_string are identical"));
+        }
+
+        let matches = updated.matches(edit.old_string.as_str()).count();
+        match matches {
+            0 => return Err(format!("{label}old_string not found in file")),
+            1 => {}
+            n if !edit.replace_all => {
+                return Err(format!(
+                    "{label}old_string matches {n} locations; add surrounding context to make it unique or set replace_all"
+                ));
+            }
+            _ => {}
+        }
+
+        updated = updated.replace(edit.old_string.as_str(), &edit.new_string);
+        replacements += matches;
+    }
+
+    Ok((updated, replacements))
+}
+
+/// A parsed `@@ -a,b +c,d @@` hunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Hunk {
+    old_start: usize,
+    old_lines: Vec<String>,
+    new_lines: Vec<String>,
+}
+
+fn parse_hunk_header(line: &str) -> Option<usize> {
+    let rest = line.strip_prefix("@@ -")?;
+    let old_range = rest.split_whitespace().next()?;
+    old_range.split(',').next()?.parse().ok()
+}
+
+fn parse_unified_diff(patch: &str) -> Result<Vec<Hunk>, String> {
+    let mut hunks = Vec::new();
+    let mut current: Option<Hunk> = None;
+
+    for line in patch.lines() {
+        if line.starts_with("@@") {
+            if let Some(hunk) = current.take() {
+                hunks.push(hunk);
+            }
+            let old_start =
+                parse_hunk_header(line).ok_or_else(|| format!("Malformed hunk header: {line}"))?;
+            current = Some(Hunk {
+                old_start,
+                old_lines: Vec::new(),
+                new_lines: Vec::new(),
+            });
+            continue;
+        }
+
+        let Some(hunk) = current.as_mut() else {
+            // File headers (`diff --git`, `---`, `+++`, `index`) before the first hunk.
+            continue;
+        };
+
+        if let Some(text) = line.strip_prefix('+') {
+            hunk.new_lines.push(text.to_string());
+        } else if let Some(text) = line.strip_prefix('-') {
+            hunk.old_lines.push(text.to_string());
+        } else if let Some(text) = line.strip_prefix(' ') {
+            hunk.old_lines.push(text.to_string());
+            hunk.new_lines.push(text.to_string());
+        } else if line.is_empty() {
+            // Some generators drop the leading space on blank context lines.
+            hunk.old_lines.push(String::new());
+            hunk.new_lines.push(String::new());
+        } else if line.starts_with('\\') {
+            // "\ No newline at end of file"
+        } else {
+            return Err(format!("Unexpected line in hunk: {line}"));
+        }
+    }
+
+    if let Some(hunk) = current {
+        hunks.push(hunk);
+    }
+    if hunks.is_empty() {
+        return Err("Patch contains no hunks".into());
+    }
+    Ok(hunks)
+}
+
+/// Find where `needle` occurs in `lines` at or after `from`, preferring the
+/// position closest to `expected`.
+fn locate_hunk(lines: &[String], needle: &[String], from: usize, expected: usize) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(expected.clamp(from, lines.len()));
+    }
+    if needle.len() > lines.len() {
+        return None;
+    }
+
+    (from..=lines.len() - needle.len())
+        .filter(|&start| lines[start..start + needle.len()] == *needle)
+        .min_by_key(|&start| start.abs_diff(expected))
+}
+
+/// Apply unified diff hunks. Context and removed lines must match the file
+/// exactly; hunks may have drifted from their stated line numbers.
+fn apply_unified_diff(content: &str, patch: &str) -> Result<(String, usize), String> {
+    let hunks = parse_unified_diff(patch)?;
+    let line_ending = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let trailing_newline = content.is_empty() || content.ends_with('\n');
+
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let mut cursor = 0;
+    let mut offset: isize = 0;
+
+    for (index, hunk) in hunks.iter().enumerate() {
+        // A zero-length old range names the line *after which* to insert.
+        let stated = if hunk.old_lines.is_empty() {
+            hunk.old_start
+        } else {
+            hunk.old_start.saturating_sub(1)
+        };
+        let expected = stated.saturating_add_signed(offset);
+
+        let start = locate_hunk(&lines, &hunk.old_lines, cursor, expected).ok_or_else(|| {
+            format!(
+                "Hunk {} (at line {}) does not match the file contents",
+                index + 1,
+                hunk.old_start
+            )
+        })?;
+
+        let removed = hunk.old_lines.len();
+        let added = hunk.new_lines.len();
+        lines.splice(start..start + removed, hunk.new_lines.iter().cloned());
+        cursor = start + added;
+        offset += isize::try_from(added).unwrap_or(0) - isize::try_from(removed).unwrap_or(0);
+    }
+
+    let mut updated = lines.join(line_ending);
+    if trailing_newline && !updated.is_empty() {
+        updated.push_str(line_ending);
+    }
+    Ok((updated, hunks.len()))
+}
+
+#[async_trait]
+impl Tool for FileEditTool {
+    fn name(&self) -> &str {
+        "file_edit"
+    }
+
+    fn description(&self) -> &str {
+        "Edit an existing file in the workspace. Replace an exact string (old_string must be unique unless replace_all), apply several replacements at once via 'edits', or apply a unified diff via 'patch'."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Relative path to the file within the workspace"
+                },
+                "old_string": {
+                    "type": "string",
+                    "description": "Exact text to replace"
+                },
+                "new_string": {
+                    "type": "string",
+                    "description": "Replacement text"
+                },
+                "replace_all": {
+                    "type": "boolean",
+                    "description": "Replace every occurrence of old_string (default: false)"
+                },
+                "edits": {
+                    "type": "array",
+                    "description": "Replacements applied in order; all succeed or none are written",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "old_string": { "type": "string" },
+                            "new_string": { "type": "string" },
+                            "replace_all": { "type": "boolean" }
+                        },
+                        "required": ["old_string", "new_string"]
+                    }
+                },
+                "patch": {
+                    "type": "string",
+                    "description": "Unified diff to apply to the file"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+
+        let patch = args.get("patch").and_then(|v| v.as_str());
+        let edits = match args.get("edits").and_then(|v| v.as_array()) {
+            Some(items) => Some(
+                items
+                    .iter()
+                    .map(parse_string_edit)
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+            ),
+            None => None,
+        };
+        let single_edit = args.get("old_string").is_some();
+
+        let modes =
+            usize::from(patch.is_some()) + usize::from(edits.is_some()) + usize::from(single_edit);
+        if modes != 1 {
+            anyhow::bail!("Provide exactly one of 'old_string'/'new_string', 'edits', or 'patch'");
+        }
+        let edits = match (edits, single_edit) {
+            (Some(edits), _) => Some(edits),
+            (None, true) => Some(vec![parse_string_edit(&args)?]),
+            (None, false) => None,
+        };
+        if edits.as_ref().is_some_and(Vec::is_empty) {
+            anyhow::bail!("'edits' must contain at least one edit");
+        }
+
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        // Security check: validate path is within workspace
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Path not allowed by security policy: {path}")),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let full_path = self.security.workspace_dir.join(path);
+
+        // Refuse to edit through a symlink, even one that points inside the workspace.
+        if let Ok(meta) = tokio::fs::symlink_metadata(&full_path).await {
+            if meta.file_type().is_symlink() {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Refusing to edit through symlink: {path}")),
+                });
+            }
+        }
+
+        let resolved_path = match tokio::fs::canonicalize(&full_path).await {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to resolve file path: {e}")),
+                });
+            }
+        };
+
+        if !self.security.is_resolved_path_allowed(&resolved_path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Resolved path escapes workspace: {}",
+                    resolved_path.display()
+                )),
+            });
+        }
+
+        match tokio::fs::metadata(&resolved_path).await {
+            Ok(meta) if meta.len() > MAX_FILE_SIZE_BYTES => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "File too large: {} bytes (limit: {MAX_FILE_SIZE_BYTES} bytes)",
+                        meta.len()
+                    )),
+                });
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to read file metadata: {e}")),
+                });
+            }
+        }
+
+        let content = match tokio::fs::read_to_string(&resolved_path).await {
+            Ok(content) => content,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to read file: {e}")),
+                });
+            }
+        };
+
+        let applied = if let Some(edits) = &edits {
+            apply_string_edits(&content, edits)
+                .map(|(updated, count)| (updated, format!("{count} replacement(s)")))
+        } else {
+            apply_unified_diff(&content, patch.unwrap_or_default())
+                .map(|(updated, count)| (updated, format!("{count} hunk(s)")))
+        };
+
+        let (updated, summary) = match applied {
+            Ok(result) => result,
+            Err(message) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(message),
+                });
+            }
+        };
+
+        match tokio::fs::write(&resolved_path, &updated).await {
+            Ok(()) => Ok(ToolResult {
+                success: true,
+                output: format!("Applied {summary} to {path}"),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to write file: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::{AutonomyLevel, SecurityPolicy};
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    async fn setup(name: &str, contents: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        tokio::fs::write(dir.join("file.txt"), contents)
+            .await
+            .unwrap();
+        dir
+    }
+
+    fn edit(old: &str, new: &str) -> StringEdit {
+        StringEdit {
+            old_string: old.into(),
+            new_string: new.into(),
+            replace_all: false,
+        }
+    }
+
+    #[test]
+    fn file_edit_name_and_schema() {
+        let tool = FileEditTool::new(test_security(std::env::temp_dir()));
+        assert_eq!(tool.name(), "file_edit");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["old_string"].is_object());
+        assert!(schema["properties"]["edits"].is_object());
+        assert!(schema["properties"]["patch"].is_object());
+    }
+
+    #[test]
+    fn string_edit_requires_unique_match() {
+        let err = apply_string_edits("a b a", &[edit("a", "c")]).unwrap_err();
+        assert!(err.contains("matches 2 locations"));
+
+        let (updated, count) = apply_string_edits(
+            "a b a",
+            &[StringEdit {
+                replace_all: true,
+                ..edit("a", "c")
+            }],
+        )
+        .unwrap();
+        assert_eq!(updated, "c b c");
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn string_edit_rejects_missing_empty_and_noop() {
+        assert!(apply_string_edits("abc", &[edit("x", "y")])
+            .unwrap_err()
+            .contains("not found"));
+        assert!(apply_string_edits("abc", &[edit("", "y")])
+            .unwrap_err()
+            .contains("must not be empty"));
+        assert!(apply_string_edits("abc", &[edit("a", "a")])
+            .unwrap_err()
+            .contains("identical"));
+    }
+
+    #[test]
+    fn string_edit_batch_applies_in_order_and_labels_failures() {
+        let (updated, _) =
+            apply_string_edits("fn a() {}\n", &[edit("a()", "b()"), edit("b()", "c()")]).unwrap();
+        assert_eq!(updated, "fn c() {}\n");
+
+        let err = apply_string_edits("abc", &[edit("a", "x"), edit("zzz", "y")]).unwrap_err();
+        assert!(err.starts_with("Edit 2:"));
+    }
+
+    #[test]
+    fn unified_diff_applies_multiple_hunks() {
+        let content = "one\ntwo\nthree\nfour\nfive\nsix\n";
+        let patch = "--- a/file.txt\n+++ b/file.txt\n@@ -1,2 +1,2 @@\n-one\n+ONE\n two\n@@ -5,2 +5,3 @@\n five\n+five-and-a-half\n six\n";
+        let (updated, hunks) = apply_unified_diff(content, patch).unwrap();
+        assert_eq!(hunks, 2);
+        assert_eq!(
+            updated,
+            "ONE\ntwo\nthree\nfour\nfive\nfive-and-a-half\nsix\n"
+        );
+    }
+
+    #[test]
+    fn unified_diff_tolerates_line_drift() {
+        let content = "header\nextra\nalpha\nbeta\n";
+        let patch = "@@ -1,2 +1,2 @@\n alpha\n-beta\n+gamma\n";
+        let (updated, _) = apply_unified_diff(content, patch).unwrap();
+        assert_eq!(updated, "header\nextra\nalpha\ngamma\n");
+    }
+
+    #[test]
+    fn unified_diff_rejects_mismatched_context() {
+        let err = apply_unified_diff("a\nb\n", "@@ -1,2 +1,2 @@\n a\n-c\n+d\n").unwrap_err();
+        assert!(err.contains("does not match"));
+        assert!(apply_unified_diff("a\n", "not a patch").is_err());
+    }
+
+    #[test]
+    fn unified_diff_preserves_crlf_line_endings() {
+        let (updated, _) = apply_unified_diff("a\r\nb\r\n", "@@ -2,1 +2,1 @@\n-b\n+c\n").unwrap();
+        assert_eq!(updated, "a\r\nc\r\n");
+    }
+
+    #[tokio::test]
+    async fn file_edit_replaces_string_on_disk() {
+        let dir = setup(
+            "zeroclaw_test_file_edit_replace",
+            "let x = 1;\nlet y = 2;\n",
+        )
+        .await;
+        let tool = FileEditTool::new(test_security(dir.clone()));
+
+        let result = tool
+            .execute(json!({"path": "file.txt", "old_string": "y = 2", "new_string": "y = 3"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("1 replacement(s)"));
+        let updated = tokio::fs::read_to_string(dir.join("file.txt"))
+            .await
+            .unwrap();
+        assert_eq!(updated, "let x = 1;\nlet y = 3;\n");
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn file_edit_failed_batch_leaves_file_untouched() {
+        let dir = setup("zeroclaw_test_file_edit_atomic", "alpha beta\n").await;
+        let tool = FileEditTool::new(test_security(dir.clone()));
+
+        let result = tool
+            .execute(json!({
+                "path": "file.txt",
+                "edits": [
+                    {"old_string": "alpha", "new_string": "ALPHA"},
+                    {"old_string": "missing", "new_string": "x"}
+                ]
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        let unchanged = tokio::fs::read_to_string(dir.join("file.txt"))
+            .await
+            .unwrap();
+        assert_eq!(unchanged, "alpha beta\n");
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn file_edit_applies_patch_on_disk() {
+        let dir = setup("zeroclaw_test_file_edit_patch", "a\nb\nc\n").await;
+        let tool = FileEditTool::new(test_security(dir.clone()));
+
+        let result = tool
+            .execute(json!({"path": "file.txt", "patch": "@@ -2,1 +2,1 @@\n-b\n+B\n"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let updated = tokio::fs::read_to_string(dir.join("file.txt"))
+            .await
+            .unwrap();
+        assert_eq!(updated, "a\nB\nc\n");
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn file_edit_requires_exactly_one_mode() {
+        let tool = FileEditTool::new(test_security(std::env::temp_dir()));
+        assert!(tool.execute(json!({"path": "file.txt"})).await.is_err());
+        assert!(tool
+            .execute(json!({
+                "path": "file.txt",
+                "old_string": "a",
+                "new_string": "b",
+                "patch": "@@ -1 +1 @@\n-a\n+b\n"
+            }))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn file_edit_blocks_path_traversal_and_readonly() {
+        let tool = FileEditTool::new(test_security(std::env::temp_dir()));
+        let result = tool
+            .execute(json!({"path": "../../etc/passwd", "old_string": "a", "new_string": "b"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("not allowed"));
+
+        let readonly = FileEditTool::new(Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        }));
+        let result = readonly
+            .execute(json!({"path": "file.txt", "old_string": "a", "new_string": "b"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("read-only"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn file_edit_blocks_symlink_target() {
+        use std::os::unix::fs::symlink;
+
+        let root = std::env::temp_dir().join("zeroclaw_test_file_edit_symlink");
+        let workspace = root.join("workspace");
+        let outside = root.join("outside");
+        let _ = tokio::fs::remove_dir_all(&root).await;
+        tokio::fs::create_dir_all(&workspace).await.unwrap();
+        tokio::fs::create_dir_all(&outside).await.unwrap();
+        tokio::fs::write(outside.join("target.txt"), "secret")
+            .await
+            .unwrap();
+        symlink(outside.join("target.txt"), workspace.join("link.txt")).unwrap();
+
+        let tool = FileEditTool::new(test_security(workspace.clone()));
+        let result = tool
+            .execute(json!({"path": "link.txt", "old_string": "secret", "new_string": "x"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("symlink"));
+        let untouched = tokio::fs::read_to_string(outside.join("target.txt"))
+            .await
+            .unwrap();
+        assert_eq!(untouched, "secret");
+
+        let _ = tokio::fs::remove_dir_all(&root).await;
+    }
+}
@@ -2,11 +2,17 @@ use super::traits::{Tool, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
+use std::fmt::Write;
 use std::sync::Arc;
 
 const MAX_FILE_SIZE_BYTES: u64 = 10 * 1024 * 1024;
+/// Default number of lines returned when only `offset` is given.
+const DEFAULT_LINE_LIMIT: usize = 2000;
 
-/// Read file contents with path sandboxing
+/// Read file contents with path sandboxing.
+///
+/// With `offset`/`limit`, returns a line window prefixed with 1-based line
+/// numbers so the model can page through large files and target edits.
 pub struct FileReadTool {
     security: Arc<SecurityPolicy>,
 }
@@ -24,7 +30,7 @@ impl Tool for FileReadTool {
     }
 
     fn description(&self) -> &str {
-        "Read the contents of a file in the workspace"
+        "Read the contents of a file in the workspace. Pass offset/limit to read a line range with line numbers."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -34,6 +40,16 @@ impl Tool for FileReadTool {
                 "path": {
                     "type": "string",
                     "description": "Relative path to the file within the workspace"
+                },
+                "offset": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "1-based line number to start reading from"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Maximum number of lines to return (default: 2000 when offset is set)"
                 }
             },
             "required": ["path"]
@@ -45,6 +61,8 @@ impl Tool for FileReadTool {
             .get("path")
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+        let offset = parse_line_arg(&args, "offset")?;
+        let limit = parse_line_arg(&args, "limit")?;
 
         if self.security.is_rate_limited() {
             return Ok(ToolResult {
@@ -123,11 +141,20 @@ impl Tool for FileReadTool {
         }
 
         match tokio::fs::read_to_string(&resolved_path).await {
-            Ok(contents) => Ok(ToolResult {
+            Ok(contents) if offset.is_none() && limit.is_none() => Ok(ToolResult {
                 success: true,
                 output: contents,
                 error: None,
             }),
+            Ok(contents) => Ok(ToolResult {
+                success: true,
+                output: numbered_line_window(
+                    &contents,
+                    offset.unwrap_or(1),
+                    limit.unwrap_or(DEFAULT_LINE_LIMIT),
+                ),
+                error: None,
+            }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -137,6 +164,41 @@ impl Tool for FileReadTool {
     }
 }
 
+fn parse_line_arg(args: &serde_json::Value, name: &str) -> anyhow::Result<Option<usize>> {
+    match args.get(name) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .filter(|n| *n >= 1)
+            .and_then(|n| usize::try_from(n).ok())
+            .map(Some)
+            .ok_or_else(|| anyhow::anyhow!("'{name}' must be a positive integer")),
+    }
+}
+
+/// Render lines `[offset, offset + limit)` with right-aligned line numbers.
+fn numbered_line_window(contents: &str, offset: usize, limit: usize) -> String {
+    let total = contents.lines().count();
+    if offset > total {
+        return format!("[File has {total} lines; offset {offset} is past the end]");
+    }
+
+    let end = offset.saturating_add(limit).saturating_sub(1).min(total);
+    let width = end.to_string().len();
+    let mut output = String::new();
+    for (index, line) in contents.lines().enumerate().skip(offset - 1).take(limit) {
+        let _ = writeln!(output, "{:>width$}\t{line}", index + 1);
+    }
+    if end < total {
+        let _ = write!(
+            output,
+            "[Showing lines {offset}-{end} of {total}. Use offset={} to continue.]",
+            end + 1
+        );
+    }
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -391,6 +453,66 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(&dir).await;
     }
 
+    #[tokio::test]
+    async fn file_read_line_range_has_line_numbers() {
+        let dir = std::env::temp_dir().join("zeroclaw_test_file_read_range");
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let contents = (1..=12).fold(String::new(), |mut acc, n| {
+            let _ = writeln!(acc, "line {n}");
+            acc
+        });
+        tokio::fs::write(dir.join("lines.txt"), contents)
+            .await
+            .unwrap();
+
+        let tool = FileReadTool::new(test_security(dir.clone()));
+        let result = tool
+            .execute(json!({"path": "lines.txt", "offset": 9, "limit": 2}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(
+            result.output,
+            " 9\tline 9\n10\tline 10\n[Showing lines 9-10 of 12. Use offset=11 to continue.]"
+        );
+
+        let result = tool
+            .execute(json!({"path": "lines.txt", "offset": 11}))
+            .await
+            .unwrap();
+        assert_eq!(result.output, "11\tline 11\n12\tline 12\n");
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn file_read_offset_past_end_reports_line_count() {
+        let dir = std::env::temp_dir().join("zeroclaw_test_file_read_past_end");
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        tokio::fs::write(dir.join("short.txt"), "a\nb\n")
+            .await
+            .unwrap();
+
+        let tool = FileReadTool::new(test_security(dir.clone()));
+        let result = tool
+            .execute(json!({"path": "short.txt", "offset": 5}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("File has 2 lines"));
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn file_read_rejects_invalid_limit() {
+        let tool = FileReadTool::new(test_security(std::env::temp_dir()));
+        let result = tool.execute(json!({"path": "a.txt", "limit": 0})).await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn file_read_rejects_oversized_file() {
         let dir = std::env::temp_dir().join("zeroclaw_test_file_read_large");
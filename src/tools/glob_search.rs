@@ -0,0 +1,246 @@
+use super::traits::{Tool, ToolResult};
+use super::workspace_walk::{self, GLOB_MATCH_OPTIONS};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use glob::Pattern;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+const DEFAULT_MAX_RESULTS: usize = 200;
+const MAX_RESULTS_CAP: usize = 1000;
+
+/// Find workspace files by glob pattern, honoring `.gitignore`.
+pub struct GlobSearchTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl GlobSearchTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[async_trait]
+impl Tool for GlobSearchTool {
+    fn name(&self) -> &str {
+        "glob"
+    }
+
+    fn description(&self) -> &str {
+        "Find files in the workspace by glob pattern (e.g. 'src/**/*.rs'). Respects .gitignore. Returns workspace-relative paths."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pattern": {
+                    "type": "string",
+                    "description": "Glob pattern relative to 'path'; '*' stays within a directory, '**' matches across directories"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Directory to search from, relative to the workspace (default: workspace root)"
+                },
+                "max_results": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Maximum number of paths to return (default: 200, max: 1000)"
+                }
+            },
+            "required": ["pattern"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let pattern = args
+            .get("pattern")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pattern' parameter"))?;
+        let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+        let max_results = args
+            .get("max_results")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_MAX_RESULTS, |n| {
+                usize::try_from(n).unwrap_or(MAX_RESULTS_CAP)
+            })
+            .clamp(1, MAX_RESULTS_CAP);
+
+        let matcher = match Pattern::new(pattern.trim_start_matches("./")) {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Invalid glob pattern: {e}")),
+                });
+            }
+        };
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        let (workspace_root, start) =
+            match workspace_walk::resolve_search_root(&self.security, path) {
+                Ok(roots) => roots,
+                Err(message) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(message),
+                    });
+                }
+            };
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let security = self.security.clone();
+        let (matches, truncated) = tokio::task::spawn_blocking(move || {
+            let mut matches = Vec::new();
+            let mut truncated = false;
+            workspace_walk::walk(
+                &security,
+                &workspace_root,
+                &start,
+                usize::MAX,
+                true,
+                |entry| {
+                    if entry.is_dir {
+                        return true;
+                    }
+                    let scoped = entry
+                        .absolute
+                        .strip_prefix(&start)
+                        .unwrap_or(&entry.absolute)
+                        .to_string_lossy()
+                        .replace('\\', "/");
+                    if matcher.matches_with(&scoped, GLOB_MATCH_OPTIONS) {
+                        if matches.len() == max_results {
+                            truncated = true;
+                            return false;
+                        }
+                        matches.push(entry.relative.clone());
+                    }
+                    true
+                },
+            );
+            (matches, truncated)
+        })
+        .await?;
+
+        if matches.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: format!("No files matched '{pattern}'"),
+                error: None,
+            });
+        }
+
+        let mut output = matches.join("\n");
+        if truncated {
+            let _ = write!(
+                output,
+                "\n[Results truncated at {max_results} paths; narrow the pattern or path]"
+            );
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    async fn setup(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(dir.join("src/nested"))
+            .await
+            .unwrap();
+        tokio::fs::write(dir.join("src/lib.rs"), "").await.unwrap();
+        tokio::fs::write(dir.join("src/nested/mod.rs"), "")
+            .await
+            .unwrap();
+        tokio::fs::write(dir.join("README.md"), "").await.unwrap();
+        dir
+    }
+
+    #[test]
+    fn glob_name_and_schema() {
+        let tool = GlobSearchTool::new(test_security(std::env::temp_dir()));
+        assert_eq!(tool.name(), "glob");
+        assert!(tool.parameters_schema()["required"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("pattern")));
+    }
+
+    #[tokio::test]
+    async fn glob_matches_recursive_pattern() {
+        let dir = setup("zeroclaw_test_glob_recursive").await;
+        let tool = GlobSearchTool::new(test_security(dir.clone()));
+
+        let result = tool.execute(json!({"pattern": "**/*.rs"})).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "src/lib.rs\nsrc/nested/mod.rs");
+
+        let result = tool
+            .execute(json!({"pattern": "*.rs", "path": "src"}))
+            .await
+            .unwrap();
+        assert_eq!(result.output, "src/lib.rs");
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn glob_truncates_at_max_results() {
+        let dir = setup("zeroclaw_test_glob_truncate").await;
+        let tool = GlobSearchTool::new(test_security(dir.clone()));
+
+        let result = tool
+            .execute(json!({"pattern": "**/*", "max_results": 1}))
+            .await
+            .unwrap();
+        assert!(result.output.starts_with("README.md\n"));
+        assert!(result.output.contains("truncated at 1"));
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn glob_blocks_path_traversal() {
+        let tool = GlobSearchTool::new(test_security(std::env::temp_dir()));
+        let result = tool
+            .execute(json!({"pattern": "*", "path": "../../etc"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("not allowed"));
+    }
+}
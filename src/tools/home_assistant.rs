@@ -0,0 +1,669 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::HomeAssistantConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use reqwest::Method;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+const DEFAULT_HISTORY_HOURS: i64 = 24;
+const MAX_HISTORY_HOURS: i64 = 24 * 7;
+const HOME_ASSISTANT_CONNECT_TIMEOUT_SECS: u64 = 10;
+const ERROR_BODY_PREVIEW_CHARS: usize = 300;
+/// Service-call targeting keys; stripped from `data` so a call cannot fan
+/// out beyond the allowlisted `entity_id`.
+const TARGET_KEYS: &[&str] = &["entity_id", "area_id", "device_id", "label_id", "floor_id"];
+
+/// The REST API has no area endpoint, so areas are rendered through the
+/// template API as JSON.
+const AREAS_TEMPLATE: &str = "{% set ns = namespace(areas=[]) %}\
+{% for area in areas() %}\
+{% set ns.areas = ns.areas + [{'id': area, 'name': area_name(area), 'entities': area_entities(area)}] %}\
+{% endfor %}{{ ns.areas | tojson }}";
+
+/// Home Assistant tool over the REST API: list entities and areas, read
+/// state and history, and call services on allowlisted entities.
+pub struct HomeAssistantTool {
+    security: Arc<SecurityPolicy>,
+    config: HomeAssistantConfig,
+}
+
+impl HomeAssistantTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: HomeAssistantConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn is_entity_allowed(&self, entity_id: &str) -> bool {
+        let domain = entity_domain(entity_id);
+        self.config.allowed_entities.iter().any(|e| e == entity_id)
+            || self.config.allowed_domains.iter().any(|d| d == domain)
+    }
+
+    fn requires_approval(&self, domain: &str) -> bool {
+        self.config.approval_domains.iter().any(|d| d == domain)
+    }
+
+    /// Validate an `entity_id` argument and check it against the allowlist.
+    fn allowed_entity<'a>(&self, args: &'a Value) -> Result<&'a str, String> {
+        let entity_id = args
+            .get("entity_id")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or("Missing 'entity_id' parameter")?;
+        if !is_valid_entity_id(entity_id) {
+            return Err(format!("Invalid entity_id '{entity_id}'"));
+        }
+        if !self.is_entity_allowed(entity_id) {
+            return Err(format!(
+                "Entity '{entity_id}' is not in home_assistant.allowed_entities or allowed_domains"
+            ));
+        }
+        Ok(entity_id)
+    }
+
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> anyhow::Result<Value> {
+        let token = self
+            .config
+            .token
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("home_assistant.token is not configured"))?;
+
+        let client = crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.home_assistant",
+            self.config.timeout_secs,
+            HOME_ASSISTANT_CONNECT_TIMEOUT_SECS,
+        );
+        let url = format!("{}{path}", self.config.url.trim_end_matches('/'));
+        let mut request = client.request(method, url).bearer_auth(token);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            anyhow::bail!(
+                "Home Assistant returned {status}: {}",
+                crate::util::truncate_with_ellipsis(&text, ERROR_BODY_PREVIEW_CHARS)
+            );
+        }
+        Ok(serde_json::from_str(&text).unwrap_or(Value::String(text)))
+    }
+
+    async fn list_entities(&self, args: &Value) -> anyhow::Result<ToolResult> {
+        let domain = args.get("domain").and_then(Value::as_str).map(str::trim);
+        let states = self.request(Method::GET, "/api/states", None).await?;
+
+        let entities: Vec<Value> = states
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|state| {
+                let entity_id = state.get("entity_id")?.as_str()?;
+                if !self.is_entity_allowed(entity_id)
+                    || domain.is_some_and(|d| entity_domain(entity_id) != d)
+                {
+                    return None;
+                }
+                Some(json!({
+                    "entity_id": entity_id,
+                    "state": state.get("state"),
+                    "name": state.pointer("/attributes/friendly_name"),
+                }))
+            })
+            .collect();
+
+        Ok(success(&Value::Array(entities)))
+    }
+
+    async fn list_areas(&self) -> anyhow::Result<ToolResult> {
+        let rendered = self
+            .request(
+                Method::POST,
+                "/api/template",
+                Some(json!({ "template": AREAS_TEMPLATE })),
+            )
+            .await?;
+
+        let areas: Vec<Value> = rendered
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(|area| {
+                let entities: Vec<&str> = area
+                    .get("entities")
+                    .and_then(Value::as_array)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .filter(|id| self.is_entity_allowed(id))
+                    .collect();
+                json!({
+                    "id": area.get("id"),
+                    "name": area.get("name"),
+                    "entities": entities,
+                })
+            })
+            .collect();
+
+        Ok(success(&Value::Array(areas)))
+    }
+
+    async fn get_state(&self, entity_id: &str) -> anyhow::Result<ToolResult> {
+        let state = self
+            .request(Method::GET, &format!("/api/states/{entity_id}"), None)
+            .await?;
+        Ok(success(&state))
+    }
+
+    async fn call_service(&self, args: &Value, entity_id: &str) -> anyhow::Result<ToolResult> {
+        let domain = args
+            .get("domain")
+            .and_then(Value::as_str)
+            .map_or_else(|| entity_domain(entity_id), str::trim);
+        let Some(service) = args.get("service").and_then(Value::as_str).map(str::trim) else {
+            return Ok(failure("Missing 'service' parameter"));
+        };
+        if !is_valid_slug(domain) || !is_valid_slug(service) {
+            return Ok(failure(format!("Invalid service '{domain}.{service}'")));
+        }
+        // A service from another domain (e.g. `lock.unlock` on a light) would
+        // reach entities outside the allowlist through its own targeting.
+        if domain != entity_domain(entity_id) {
+            return Ok(failure(format!(
+                "Service domain '{domain}' does not match entity '{entity_id}'"
+            )));
+        }
+
+        let operation = format!("{domain}.{service}");
+        let approved = args
+            .get("approved")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        if self.requires_approval(domain) || self.requires_approval(entity_domain(entity_id)) {
+            if let Err(e) = self.security.enforce_approval(&operation, approved) {
+                return Ok(failure(e));
+            }
+        }
+        if let Err(e) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "home_assistant.call_service")
+        {
+            return Ok(failure(e));
+        }
+
+        let mut body = match args.get("data") {
+            Some(Value::Object(data)) => data.clone(),
+            Some(Value::Null) | None => serde_json::Map::new(),
+            Some(_) => return Ok(failure("'data' must be an object")),
+        };
+        // Only the allowlisted entity may be targeted.
+        for key in TARGET_KEYS {
+            body.remove(*key);
+        }
+        body.insert("entity_id".into(), Value::String(entity_id.to_string()));
+
+        let changed = self
+            .request(
+                Method::POST,
+                &format!("/api/services/{domain}/{service}"),
+                Some(Value::Object(body)),
+            )
+            .await?;
+        Ok(success(
+            &json!({ "service": operation, "changed_states": changed }),
+        ))
+    }
+
+    async fn history(&self, args: &Value, entity_id: &str) -> anyhow::Result<ToolResult> {
+        let hours = args
+            .get("hours")
+            .and_then(Value::as_i64)
+            .unwrap_or(DEFAULT_HISTORY_HOURS)
+            .clamp(1, MAX_HISTORY_HOURS);
+        let end = Utc::now();
+        let start = end - Duration::hours(hours);
+        let path = format!(
+            "/api/history/period/{}?filter_entity_id={entity_id}&end_time={}&minimal_response&no_attributes",
+            start.format("%Y-%m-%dT%H:%M:%SZ"),
+            end.format("%Y-%m-%dT%H:%M:%SZ"),
+        );
+
+        let history = self.request(Method::GET, &path, None).await?;
+        let changes: Vec<Value> = history
+            .as_array()
+            .and_then(|series| series.first())
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(|point| {
+                json!({
+                    "state": point.get("state"),
+                    "last_changed": point.get("last_changed"),
+                })
+            })
+            .collect();
+
+        Ok(success(&json!({
+            "entity_id": entity_id,
+            "hours": hours,
+            "changes": changes,
+        })))
+    }
+}
+
+fn entity_domain(entity_id: &str) -> &str {
+    entity_id
+        .split_once('.')
+        .map_or(entity_id, |(domain, _)| domain)
+}
+
+fn is_valid_slug(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn is_valid_entity_id(entity_id: &str) -> bool {
+    entity_id
+        .split_once('.')
+        .is_some_and(|(domain, object_id)| is_valid_slug(domain) && is_valid_slug(object_id))
+}
+
+fn success(value: &Value) -> ToolResult {
+    ToolResult {
+        success: true,
+        output: serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string()),
+        error: None,
+    }
+}
+
+fn failure(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for HomeAssistantTool {
+    fn name(&self) -> &str {
+        "home_assistant"
+    }
+
+    fn description(&self) -> &str {
+        "Query and control Home Assistant: list entities and areas, read state and history, \
+         and call services on allowlisted entities. Lock/alarm services need approved=true in \
+         supervised mode."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list_entities", "list_areas", "get_state", "call_service", "history"],
+                    "description": "Operation to perform"
+                },
+                "entity_id": {
+                    "type": "string",
+                    "description": "Entity ID, e.g. 'light.kitchen' (get_state, call_service, history)"
+                },
+                "domain": {
+                    "type": "string",
+                    "description": "Domain filter for list_entities, or service domain for call_service (must match the entity's domain; defaults to it)"
+                },
+                "service": {
+                    "type": "string",
+                    "description": "Service name for call_service, e.g. 'turn_on'"
+                },
+                "data": {
+                    "type": "object",
+                    "description": "Extra service data for call_service, e.g. {\"brightness_pct\": 50}"
+                },
+                "hours": {
+                    "type": "integer",
+                    "description": "History window in hours (default 24, max 168)"
+                },
+                "approved": {
+                    "type": "boolean",
+                    "description": "Set true to confirm a service call in an approval-required domain",
+                    "default": false
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+
+        match action {
+            "list_entities" => self.list_entities(&args).await,
+            "list_areas" => self.list_areas().await,
+            "get_state" | "call_service" | "history" => {
+                let entity_id = match self.allowed_entity(&args) {
+                    Ok(entity_id) => entity_id,
+                    Err(e) => return Ok(failure(e)),
+                };
+                match action {
+                    "get_state" => self.get_state(entity_id).await,
+                    "call_service" => self.call_service(&args, entity_id).await,
+                    _ => self.history(&args, entity_id).await,
+                }
+            }
+            other => Ok(failure(format!(
+                "Unknown action '{other}'. Use list_entities, list_areas, get_state, call_service or history"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use axum::extract::Path;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::response::IntoResponse;
+    use axum::routing::{get, post};
+    use axum::{Json, Router};
+
+    const TOKEN: &str = "test-token";
+
+    fn authorized(headers: &HeaderMap) -> bool {
+        headers
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v == format!("Bearer {TOKEN}"))
+    }
+
+    async fn states(headers: HeaderMap) -> impl IntoResponse {
+        if !authorized(&headers) {
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(json!({"message": "unauthorized"})),
+            );
+        }
+        (
+            StatusCode::OK,
+            Json(json!([
+                {"entity_id": "light.kitchen", "state": "on", "attributes": {"friendly_name": "Kitchen"}},
+                {"entity_id": "light.porch", "state": "off", "attributes": {"friendly_name": "Porch"}},
+                {"entity_id": "lock.front_door", "state": "locked", "attributes": {}},
+                {"entity_id": "camera.garage", "state": "idle", "attributes": {}}
+            ])),
+        )
+    }
+
+    async fn mock_home_assistant() -> String {
+        let app = Router::new()
+            .route("/api/states", get(states))
+            .route(
+                "/api/states/{entity_id}",
+                get(|Path(entity_id): Path<String>| async move {
+                    Json(json!({"entity_id": entity_id, "state": "on"}))
+                }),
+            )
+            .route(
+                "/api/services/{domain}/{service}",
+                post(
+                    |Path((domain, service)): Path<(String, String)>, Json(body): Json<Value>| async move {
+                        Json(json!([{
+                            "entity_id": body["entity_id"],
+                            "state": format!("{domain}.{service}"),
+                            "brightness_pct": body.get("brightness_pct"),
+                            "request": body,
+                        }]))
+                    },
+                ),
+            )
+            .route(
+                "/api/history/period/{start}",
+                get(|| async {
+                    Json(json!([[
+                        {"state": "off", "last_changed": "2026-01-01T10:00:00+00:00"},
+                        {"state": "on", "last_changed": "2026-01-01T11:00:00+00:00"}
+                    ]]))
+                }),
+            )
+            .route(
+                "/api/template",
+                post(|| async {
+                    r#"[{"id": "kitchen", "name": "Kitchen", "entities": ["light.kitchen", "camera.garage"]}]"#
+                }),
+            );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+        format!("http://{addr}")
+    }
+
+    fn tool(url: &str, autonomy: AutonomyLevel) -> HomeAssistantTool {
+        let security = Arc::new(SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        });
+        HomeAssistantTool::new(
+            security,
+            HomeAssistantConfig {
+                enabled: true,
+                url: url.to_string(),
+                token: Some(TOKEN.into()),
+                allowed_entities: vec!["lock.front_door".into()],
+                allowed_domains: vec!["light".into()],
+                ..HomeAssistantConfig::default()
+            },
+        )
+    }
+
+    #[test]
+    fn validates_entity_ids_and_allowlist() {
+        let tool = tool("http://localhost", AutonomyLevel::Supervised);
+        assert!(is_valid_entity_id("light.kitchen"));
+        assert!(!is_valid_entity_id("light"));
+        assert!(!is_valid_entity_id("light.kitchen/../../config"));
+        assert!(tool.is_entity_allowed("light.anything"));
+        assert!(tool.is_entity_allowed("lock.front_door"));
+        assert!(!tool.is_entity_allowed("lock.back_door"));
+    }
+
+    #[tokio::test]
+    async fn list_entities_only_returns_allowlisted_entities() {
+        let url = mock_home_assistant().await;
+        let result = tool(&url, AutonomyLevel::Supervised)
+            .execute(json!({"action": "list_entities"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("light.kitchen"));
+        assert!(result.output.contains("lock.front_door"));
+        assert!(!result.output.contains("camera.garage"));
+
+        let lights = tool(&url, AutonomyLevel::Supervised)
+            .execute(json!({"action": "list_entities", "domain": "lock"}))
+            .await
+            .unwrap();
+        assert!(!lights.output.contains("light.kitchen"));
+    }
+
+    #[tokio::test]
+    async fn list_areas_filters_area_entities() {
+        let url = mock_home_assistant().await;
+        let result = tool(&url, AutonomyLevel::Supervised)
+            .execute(json!({"action": "list_areas"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("Kitchen"));
+        assert!(result.output.contains("light.kitchen"));
+        assert!(!result.output.contains("camera.garage"));
+    }
+
+    #[tokio::test]
+    async fn get_state_rejects_entities_outside_allowlist() {
+        let url = mock_home_assistant().await;
+        let tool = tool(&url, AutonomyLevel::Supervised);
+
+        let denied = tool
+            .execute(json!({"action": "get_state", "entity_id": "camera.garage"}))
+            .await
+            .unwrap();
+        assert!(!denied.success);
+        assert!(denied.error.unwrap().contains("not in home_assistant"));
+
+        let allowed = tool
+            .execute(json!({"action": "get_state", "entity_id": "light.porch"}))
+            .await
+            .unwrap();
+        assert!(allowed.success);
+        assert!(allowed.output.contains("light.porch"));
+    }
+
+    #[tokio::test]
+    async fn call_service_sends_entity_and_data() {
+        let url = mock_home_assistant().await;
+        let result = tool(&url, AutonomyLevel::Supervised)
+            .execute(json!({
+                "action": "call_service",
+                "entity_id": "light.kitchen",
+                "service": "turn_on",
+                "data": {"brightness_pct": 40}
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("light.turn_on"));
+        assert!(result.output.contains("40"));
+    }
+
+    #[tokio::test]
+    async fn call_service_rejects_cross_domain_services_and_extra_targets() {
+        let url = mock_home_assistant().await;
+        let tool = tool(&url, AutonomyLevel::Full);
+        let result = tool
+            .execute(json!({
+                "action": "call_service",
+                "entity_id": "light.kitchen",
+                "domain": "lock",
+                "service": "unlock"
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("does not match"));
+
+        let result = tool
+            .execute(json!({
+                "action": "call_service",
+                "entity_id": "light.kitchen",
+                "service": "turn_on",
+                "data": {
+                    "brightness_pct": 40,
+                    "area_id": "whole_house",
+                    "device_id": "abc",
+                    "label_id": "all",
+                    "floor_id": "ground"
+                }
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        for key in ["area_id", "device_id", "label_id", "floor_id"] {
+            assert!(!result.output.contains(key), "{key} was forwarded");
+        }
+    }
+
+    #[tokio::test]
+    async fn approval_domains_require_explicit_approval() {
+        let url = mock_home_assistant().await;
+        let tool = tool(&url, AutonomyLevel::Supervised);
+        let unlock = json!({
+            "action": "call_service",
+            "entity_id": "lock.front_door",
+            "service": "unlock"
+        });
+
+        let blocked = tool.execute(unlock.clone()).await.unwrap();
+        assert!(!blocked.success);
+        assert!(blocked.error.unwrap().contains("approved=true"));
+
+        let mut approved = unlock;
+        approved["approved"] = json!(true);
+        let result = tool.execute(approved).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("lock.unlock"));
+    }
+
+    #[tokio::test]
+    async fn read_only_autonomy_blocks_service_calls() {
+        let url = mock_home_assistant().await;
+        let result = tool(&url, AutonomyLevel::ReadOnly)
+            .execute(json!({
+                "action": "call_service",
+                "entity_id": "light.kitchen",
+                "service": "turn_off"
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn history_returns_state_changes() {
+        let url = mock_home_assistant().await;
+        let result = tool(&url, AutonomyLevel::Supervised)
+            .execute(json!({"action": "history", "entity_id": "light.kitchen", "hours": 2}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let parsed: Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(parsed["hours"], 2);
+        assert_eq!(parsed["changes"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn surfaces_api_errors_and_missing_token() {
+        let url = mock_home_assistant().await;
+        let mut bad_token = tool(&url, AutonomyLevel::Supervised);
+        bad_token.config.token = Some("wrong".into());
+        let err = bad_token
+            .execute(json!({"action": "list_entities"}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("401"));
+
+        let mut no_token = tool(&url, AutonomyLevel::Supervised);
+        no_token.config.token = None;
+        let err = no_token
+            .execute(json!({"action": "list_entities"}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("token is not configured"));
+    }
+}
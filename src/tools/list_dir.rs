@@ -0,0 +1,246 @@
+use super::traits::{Tool, ToolResult};
+use super::workspace_walk;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+const DEFAULT_MAX_ENTRIES: usize = 200;
+const MAX_ENTRIES_CAP: usize = 1000;
+const MAX_DEPTH: usize = 5;
+
+/// List directory contents inside the workspace as an indented tree.
+pub struct ListDirTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl ListDirTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[async_trait]
+impl Tool for ListDirTool {
+    fn name(&self) -> &str {
+        "list_dir"
+    }
+
+    fn description(&self) -> &str {
+        "List files and directories in the workspace. Directories end with '/', files show their size. Use depth > 1 for a tree view."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Directory to list, relative to the workspace (default: workspace root)"
+                },
+                "depth": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": 5,
+                    "description": "How many directory levels to descend (default: 1)"
+                },
+                "respect_gitignore": {
+                    "type": "boolean",
+                    "description": "Hide entries ignored by .gitignore (default: false)"
+                },
+                "max_entries": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Maximum number of entries to return (default: 200, max: 1000)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+        let depth = args
+            .get("depth")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(1, |n| usize::try_from(n).unwrap_or(MAX_DEPTH))
+            .clamp(1, MAX_DEPTH);
+        let respect_gitignore = args
+            .get("respect_gitignore")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        let max_entries = args
+            .get("max_entries")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_MAX_ENTRIES, |n| {
+                usize::try_from(n).unwrap_or(MAX_ENTRIES_CAP)
+            })
+            .clamp(1, MAX_ENTRIES_CAP);
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        let (workspace_root, start) =
+            match workspace_walk::resolve_search_root(&self.security, path) {
+                Ok(roots) => roots,
+                Err(message) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(message),
+                    });
+                }
+            };
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let security = self.security.clone();
+        let (listing, count, truncated) = tokio::task::spawn_blocking(move || {
+            let mut listing = String::new();
+            let mut count = 0;
+            let mut truncated = false;
+            workspace_walk::walk(
+                &security,
+                &workspace_root,
+                &start,
+                depth,
+                respect_gitignore,
+                |entry| {
+                    if count == max_entries {
+                        truncated = true;
+                        return false;
+                    }
+                    count += 1;
+
+                    let indent = "  ".repeat(entry.depth - 1);
+                    let name = entry.relative.rsplit('/').next().unwrap_or_default();
+                    if entry.is_dir {
+                        let _ = writeln!(listing, "{indent}{name}/");
+                    } else {
+                        let size = std::fs::metadata(&entry.absolute).map_or(0, |m| m.len());
+                        let _ = writeln!(listing, "{indent}{name} ({size} bytes)");
+                    }
+                    true
+                },
+            );
+            (listing, count, truncated)
+        })
+        .await?;
+
+        if count == 0 {
+            return Ok(ToolResult {
+                success: true,
+                output: format!("{path} is empty"),
+                error: None,
+            });
+        }
+
+        let mut output = listing;
+        if truncated {
+            let _ = write!(
+                output,
+                "[Listing truncated at {max_entries} entries; list a subdirectory or reduce depth]"
+            );
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    async fn setup(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(dir.join("docs/api"))
+            .await
+            .unwrap();
+        tokio::fs::write(dir.join("docs/api/index.md"), "# API")
+            .await
+            .unwrap();
+        tokio::fs::write(dir.join("notes.txt"), "hello")
+            .await
+            .unwrap();
+        dir
+    }
+
+    #[test]
+    fn list_dir_name_and_schema() {
+        let tool = ListDirTool::new(test_security(std::env::temp_dir()));
+        assert_eq!(tool.name(), "list_dir");
+        assert!(tool.parameters_schema()["properties"]["depth"].is_object());
+    }
+
+    #[tokio::test]
+    async fn list_dir_shows_top_level_by_default() {
+        let dir = setup("zeroclaw_test_list_dir_top").await;
+        let tool = ListDirTool::new(test_security(dir.clone()));
+
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "docs/\nnotes.txt (5 bytes)\n");
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn list_dir_renders_tree_with_depth() {
+        let dir = setup("zeroclaw_test_list_dir_tree").await;
+        let tool = ListDirTool::new(test_security(dir.clone()));
+
+        let result = tool
+            .execute(json!({"path": "docs", "depth": 3}))
+            .await
+            .unwrap();
+        assert_eq!(result.output, "api/\n  index.md (5 bytes)\n");
+
+        let result = tool
+            .execute(json!({"depth": 3, "max_entries": 2}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("truncated at 2 entries"));
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn list_dir_rejects_files_and_traversal() {
+        let dir = setup("zeroclaw_test_list_dir_reject").await;
+        let tool = ListDirTool::new(test_security(dir.clone()));
+
+        let result = tool.execute(json!({"path": "notes.txt"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("Not a directory"));
+
+        let result = tool.execute(json!({"path": "../.."})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("not allowed"));
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+}
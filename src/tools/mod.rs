@@ -1,6 +1,7 @@
 pub mod browser;
 pub mod browser_open;
 pub mod composio;
+pub mod content_search;
 pub mod cron_add;
 pub mod cron_list;
 pub mod cron_remove;
@@ -8,18 +9,25 @@ pub mod cron_run;
 pub mod cron_runs;
 pub mod cron_update;
 pub mod delegate;
+pub mod file_edit;
 pub mod file_read;
 pub mod file_write;
 pub mod git_operations;
+pub mod glob_search;
 pub mod hardware_board_info;
 pub mod hardware_memory_map;
 pub mod hardware_memory_read;
+pub mod home_assistant;
 pub mod http_request;
 pub mod image_info;
+pub mod list_dir;
 pub mod mcp;
 pub mod memory_forget;
 pub mod memory_recall;
 pub mod memory_store;
+pub mod mqtt;
+pub mod obsidian_notes;
+pub mod process;
 pub mod proxy_config;
 pub mod pushover;
 pub mod schedule;
@@ -28,10 +36,12 @@ pub mod screenshot;
 pub mod shell;
 pub mod traits;
 pub mod web_search_tool;
+mod workspace_walk;
 
 pub use browser::{BrowserTool, ComputerUseConfig};
 pub use browser_open::BrowserOpenTool;
 pub use composio::ComposioTool;
+pub use content_search::ContentSearchTool;
 pub use cron_add::CronAddTool;
 pub use cron_list::CronListTool;
 pub use cron_remove::CronRemoveTool;
@@ -39,18 +49,25 @@ pub use cron_run::CronRunTool;
 pub use cron_runs::CronRunsTool;
 pub use cron_update::CronUpdateTool;
 pub use delegate::DelegateTool;
+pub use file_edit::FileEditTool;
 pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
 pub use git_operations::GitOperationsTool;
+pub use glob_search::GlobSearchTool;
 pub use hardware_board_info::HardwareBoardInfoTool;
 pub use hardware_memory_map::HardwareMemoryMapTool;
 pub use hardware_memory_read::HardwareMemoryReadTool;
+pub use home_assistant::HomeAssistantTool;
 pub use http_request::HttpRequestTool;
 pub use image_info::ImageInfoTool;
+pub use list_dir::ListDirTool;
 pub use mcp::McpRegistry;
 pub use memory_forget::MemoryForgetTool;
 pub use memory_recall::MemoryRecallTool;
 pub use memory_store::MemoryStoreTool;
+pub use mqtt::{MqttPublishTool, MqttSubscribePeekTool};
+pub use obsidian_notes::ObsidianNotesTool;
+pub use process::{with_process_owner, ProcessManager, ProcessTool};
 pub use proxy_config::ProxyConfigTool;
 pub use pushover::PushoverTool;
 pub use schedule::ScheduleTool;
@@ -83,7 +100,8 @@ pub fn default_tools_with_runtime(
     vec![
         Box::new(ShellTool::new(security.clone(), runtime)),
         Box::new(FileReadTool::new(security.clone())),
-        Box::new(FileWriteTool::new(security)),
+        Box::new(FileWriteTool::new(security.clone())),
+        Box::new(FileEditTool::new(security)),
     ]
 }
 
@@ -133,11 +151,55 @@ pub fn all_tools_with_runtime(
     agents: &HashMap<String, DelegateAgentConfig>,
     fallback_api_key: Option<&str>,
     root_config: &crate::config::Config,
+) -> Vec<Box<dyn Tool>> {
+    all_tools_with_processes(
+        config,
+        security,
+        runtime,
+        Arc::new(ProcessManager::from_config(&root_config.security)),
+        memory,
+        composio_key,
+        composio_entity_id,
+        browser_config,
+        http_config,
+        workspace_dir,
+        agents,
+        fallback_api_key,
+        root_config,
+    )
+}
+
+/// Like [`all_tools_with_runtime`], sharing an existing background process
+/// table so callers can manage (and keep) the processes it tracks.
+#[allow(clippy::implicit_hasher, clippy::too_many_arguments)]
+pub fn all_tools_with_processes(
+    config: Arc<Config>,
+    security: &Arc<SecurityPolicy>,
+    runtime: Arc<dyn RuntimeAdapter>,
+    processes: Arc<ProcessManager>,
+    memory: Arc<dyn Memory>,
+    composio_key: Option<&str>,
+    composio_entity_id: Option<&str>,
+    browser_config: &crate::config::BrowserConfig,
+    http_config: &crate::config::HttpRequestConfig,
+    workspace_dir: &std::path::Path,
+    agents: &HashMap<String, DelegateAgentConfig>,
+    fallback_api_key: Option<&str>,
+    root_config: &crate::config::Config,
 ) -> Vec<Box<dyn Tool>> {
     let mut tools: Vec<Box<dyn Tool>> = vec![
-        Box::new(ShellTool::new(security.clone(), runtime)),
+        Box::new(ShellTool::with_processes(
+            security.clone(),
+            runtime,
+            processes.clone(),
+        )),
+        Box::new(ProcessTool::new(security.clone(), processes)),
         Box::new(FileReadTool::new(security.clone())),
         Box::new(FileWriteTool::new(security.clone())),
+        Box::new(FileEditTool::new(security.clone())),
+        Box::new(GlobSearchTool::new(security.clone())),
+        Box::new(ContentSearchTool::new(security.clone())),
+        Box::new(ListDirTool::new(security.clone())),
         Box::new(CronAddTool::new(config.clone(), security.clone())),
         Box::new(CronListTool::new(config.clone())),
         Box::new(CronRemoveTool::new(config.clone())),
@@ -209,6 +271,31 @@ pub fn all_tools_with_runtime(
     tools.push(Box::new(ScreenshotTool::new(security.clone())));
     tools.push(Box::new(ImageInfoTool::new(security.clone())));
 
+    if let Some(vault) = root_config.memory.obsidian.vault_dir() {
+        tools.push(Box::new(ObsidianNotesTool::new(
+            security.clone(),
+            vault,
+            &root_config.memory.obsidian,
+        )));
+    }
+
+    if root_config.home_assistant.enabled {
+        tools.push(Box::new(HomeAssistantTool::new(
+            security.clone(),
+            root_config.home_assistant.clone(),
+        )));
+    }
+
+    if root_config.mqtt.enabled {
+        tools.push(Box::new(MqttPublishTool::new(
+            security.clone(),
+            root_config.mqtt.clone(),
+        )));
+        tools.push(Box::new(MqttSubscribePeekTool::new(
+            root_config.mqtt.clone(),
+        )));
+    }
+
     if let Some(key) = composio_key {
         if !key.is_empty() {
             tools.push(Box::new(ComposioTool::new(
@@ -283,10 +370,10 @@ mod tests {
     }
 
     #[test]
-    fn default_tools_has_three() {
+    fn default_tools_has_four() {
         let security = Arc::new(SecurityPolicy::default());
         let tools = default_tools(security);
-        assert_eq!(tools.len(), 3);
+        assert_eq!(tools.len(), 4);
     }
 
     #[test]
@@ -366,6 +453,10 @@ mod tests {
         assert!(names.contains(&"browser_open"));
         assert!(names.contains(&"pushover"));
         assert!(names.contains(&"proxy_config"));
+        assert!(names.contains(&"glob"));
+        assert!(names.contains(&"content_search"));
+        assert!(names.contains(&"list_dir"));
+        assert!(names.contains(&"process"));
     }
 
     #[test]
@@ -376,6 +467,7 @@ mod tests {
         assert!(names.contains(&"shell"));
         assert!(names.contains(&"file_read"));
         assert!(names.contains(&"file_write"));
+        assert!(names.contains(&"file_edit"));
     }
 
     #[test]
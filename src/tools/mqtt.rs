@@ -0,0 +1,362 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::MqttConfig;
+use crate::mqtt::{filter_covers, validate_filter, validate_topic, MqttClient, MqttOptions, QoS};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEFAULT_PEEK_WAIT_MS: u64 = 2000;
+const MAX_PEEK_WAIT_MS: u64 = 30_000;
+const DEFAULT_PEEK_LIMIT: usize = 10;
+const MAX_PEEK_LIMIT: usize = 100;
+const PAYLOAD_PREVIEW_CHARS: usize = 2000;
+
+/// Client id suffix for tool connections; random so parallel tool calls do
+/// not replace each other's session.
+fn tool_client_suffix() -> String {
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    format!("tool-{}", &id[..8])
+}
+
+fn qos_arg(args: &Value, default: u8) -> Result<QoS, String> {
+    let qos = match args.get("qos") {
+        None | Some(Value::Null) => default,
+        Some(v) => v
+            .as_u64()
+            .and_then(|q| u8::try_from(q).ok())
+            .ok_or("'qos' must be 0, 1 or 2")?,
+    };
+    QoS::try_from(qos).map_err(|e| e.to_string())
+}
+
+/// Tool: publish a message to an allowlisted MQTT topic.
+pub struct MqttPublishTool {
+    security: Arc<SecurityPolicy>,
+    config: MqttConfig,
+}
+
+impl MqttPublishTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: MqttConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn allowed_topic<'a>(&self, args: &'a Value) -> Result<&'a str, String> {
+        let topic = args
+            .get("topic")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or("Missing 'topic' parameter")?;
+        validate_topic(topic).map_err(|e| e.to_string())?;
+        if !self
+            .config
+            .publish_topics
+            .iter()
+            .any(|allowed| filter_covers(allowed, topic))
+        {
+            return Err(format!("Topic '{topic}' is not in mqtt.publish_topics"));
+        }
+        Ok(topic)
+    }
+}
+
+#[async_trait]
+impl Tool for MqttPublishTool {
+    fn name(&self) -> &str {
+        "mqtt_publish"
+    }
+
+    fn description(&self) -> &str {
+        "Publish a message to an MQTT topic (device commands, set-points, notifications). Only topics in mqtt.publish_topics are allowed. Set retain=true to make the value the topic's last known state."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "topic": {
+                    "type": "string",
+                    "description": "Topic to publish to, without wildcards (e.g. 'home/kitchen/light/set')"
+                },
+                "payload": {
+                    "description": "Message body: a string is sent as-is, anything else as JSON"
+                },
+                "qos": {
+                    "type": "integer",
+                    "enum": [0, 1, 2],
+                    "description": "Delivery guarantee (default from mqtt.qos)"
+                },
+                "retain": {
+                    "type": "boolean",
+                    "description": "Ask the broker to keep this as the topic's retained message"
+                },
+                "approved": {
+                    "type": "boolean",
+                    "description": "Set true once the user has approved publishing to a topic that requires approval"
+                }
+            },
+            "required": ["topic", "payload"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let topic = match self.allowed_topic(&args) {
+            Ok(topic) => topic,
+            Err(e) => return Ok(failure(e)),
+        };
+        let payload = match args.get("payload") {
+            Some(Value::String(s)) => s.clone().into_bytes(),
+            Some(value) => value.to_string().into_bytes(),
+            None => return Ok(failure("Missing 'payload' parameter")),
+        };
+        let qos = match qos_arg(&args, self.config.qos) {
+            Ok(qos) => qos,
+            Err(e) => return Ok(failure(e)),
+        };
+        let retain = args.get("retain").and_then(Value::as_bool).unwrap_or(false);
+
+        if self
+            .config
+            .approval_topics
+            .iter()
+            .any(|filter| filter_covers(filter, topic))
+        {
+            let approved = args
+                .get("approved")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            if let Err(e) = self
+                .security
+                .enforce_approval(&format!("mqtt_publish {topic}"), approved)
+            {
+                return Ok(failure(e));
+            }
+        }
+        if let Err(e) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "mqtt_publish")
+        {
+            return Ok(failure(e));
+        }
+
+        let options = MqttOptions::from_config(&self.config, &tool_client_suffix())?;
+        let (client, _) = MqttClient::connect(&options).await?;
+        let result = client.publish(topic, payload.clone(), qos, retain).await;
+        client.disconnect().await;
+        result?;
+
+        Ok(success(&json!({
+            "topic": topic,
+            "bytes": payload.len(),
+            "qos": qos as u8,
+            "retain": retain,
+        })))
+    }
+}
+
+/// Tool: subscribe briefly and return what arrives (retained state first).
+pub struct MqttSubscribePeekTool {
+    config: MqttConfig,
+}
+
+impl MqttSubscribePeekTool {
+    pub fn new(config: MqttConfig) -> Self {
+        Self { config }
+    }
+
+    fn allowed_filter<'a>(&self, args: &'a Value) -> Result<&'a str, String> {
+        let filter = args
+            .get("topic")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or("Missing 'topic' parameter")?;
+        validate_filter(filter).map_err(|e| e.to_string())?;
+        if !self
+            .config
+            .subscribe_topics
+            .iter()
+            .any(|allowed| filter_covers(allowed, filter))
+        {
+            return Err(format!(
+                "Topic filter '{filter}' is not covered by mqtt.subscribe_topics"
+            ));
+        }
+        Ok(filter)
+    }
+}
+
+#[async_trait]
+impl Tool for MqttSubscribePeekTool {
+    fn name(&self) -> &str {
+        "mqtt_subscribe_peek"
+    }
+
+    fn description(&self) -> &str {
+        "Subscribe to an MQTT topic filter for a short time and return the messages received, starting with retained values (current sensor or device state). Wildcards + and # are allowed within mqtt.subscribe_topics."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "topic": {
+                    "type": "string",
+                    "description": "Topic filter (e.g. 'home/+/temperature' or 'sensors/#')"
+                },
+                "wait_ms": {
+                    "type": "integer",
+                    "description": "How long to listen, in milliseconds (default 2000, max 30000)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Stop after this many messages (default 10, max 100)"
+                }
+            },
+            "required": ["topic"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let filter = match self.allowed_filter(&args) {
+            Ok(filter) => filter,
+            Err(e) => return Ok(failure(e)),
+        };
+        let wait_ms = args
+            .get("wait_ms")
+            .and_then(Value::as_u64)
+            .unwrap_or(DEFAULT_PEEK_WAIT_MS)
+            .min(MAX_PEEK_WAIT_MS);
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .and_then(|l| usize::try_from(l).ok())
+            .unwrap_or(DEFAULT_PEEK_LIMIT)
+            .clamp(1, MAX_PEEK_LIMIT);
+
+        let options = MqttOptions::from_config(&self.config, &tool_client_suffix())?;
+        let (client, mut incoming) = MqttClient::connect(&options).await?;
+        let subscribed = client.subscribe(&[(filter.to_string(), QoS::One)]).await;
+        if let Err(e) = subscribed {
+            client.disconnect().await;
+            return Err(e);
+        }
+
+        let mut messages = Vec::new();
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(wait_ms);
+        while messages.len() < limit {
+            let Ok(Some(publish)) = tokio::time::timeout_at(deadline, incoming.recv()).await else {
+                break;
+            };
+            let text = publish.payload_str();
+            let payload = serde_json::from_str::<Value>(&text).unwrap_or_else(|_| {
+                Value::String(crate::util::truncate_with_ellipsis(
+                    &text,
+                    PAYLOAD_PREVIEW_CHARS,
+                ))
+            });
+            messages.push(json!({
+                "topic": publish.topic,
+                "payload": payload,
+                "retained": publish.retain,
+            }));
+        }
+        client.disconnect().await;
+
+        Ok(success(&json!({
+            "topic": filter,
+            "count": messages.len(),
+            "messages": messages,
+        })))
+    }
+}
+
+fn success(value: &Value) -> ToolResult {
+    ToolResult {
+        success: true,
+        output: serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string()),
+        error: None,
+    }
+}
+
+fn failure(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn config() -> MqttConfig {
+        MqttConfig {
+            enabled: true,
+            // Nothing listens here; checks must fail before connecting.
+            host: "127.0.0.1".into(),
+            port: Some(1),
+            publish_topics: vec!["home/+/set".into()],
+            subscribe_topics: vec!["home/#".into()],
+            approval_topics: vec!["home/lock/set".into()],
+            ..MqttConfig::default()
+        }
+    }
+
+    fn publish_tool(autonomy: AutonomyLevel) -> MqttPublishTool {
+        let security = Arc::new(SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        });
+        MqttPublishTool::new(security, config())
+    }
+
+    #[tokio::test]
+    async fn publish_rejects_topics_outside_allowlist() {
+        let tool = publish_tool(AutonomyLevel::Full);
+        for topic in ["office/light/set", "home/kitchen/light/set", "home/+/set"] {
+            let result = tool
+                .execute(json!({"topic": topic, "payload": "on"}))
+                .await
+                .unwrap();
+            assert!(!result.success, "{topic} should be rejected");
+        }
+    }
+
+    #[tokio::test]
+    async fn publish_requires_approval_for_approval_topics() {
+        let result = publish_tool(AutonomyLevel::Supervised)
+            .execute(json!({"topic": "home/lock/set", "payload": "unlock"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("requires explicit approval"));
+
+        let result = publish_tool(AutonomyLevel::ReadOnly)
+            .execute(json!({"topic": "home/light/set", "payload": "on"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn peek_rejects_filters_wider_than_allowlist() {
+        let tool = MqttSubscribePeekTool::new(config());
+        for topic in ["#", "office/+", "home/#/x"] {
+            let result = tool.execute(json!({"topic": topic})).await.unwrap();
+            assert!(!result.success, "{topic} should be rejected");
+        }
+    }
+
+    #[test]
+    fn qos_argument_is_validated() {
+        assert_eq!(qos_arg(&json!({}), 1).unwrap(), QoS::One);
+        assert_eq!(qos_arg(&json!({"qos": 2}), 1).unwrap(), QoS::Two);
+        assert!(qos_arg(&json!({"qos": 3}), 1).is_err());
+        assert!(qos_arg(&json!({"qos": "high"}), 1).is_err());
+    }
+}
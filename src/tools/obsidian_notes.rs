@@ -0,0 +1,403 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::ObsidianConfig;
+use crate::memory::obsidian::{
+    find_note, note_file_name, parse_note, relative_path, render_frontmatter, vault_path,
+};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const MAX_READ_CHARS: usize = 20_000;
+const RELATED_HEADING: &str = "## Related";
+
+/// Create, append to, link and read notes in the configured Obsidian vault.
+pub struct ObsidianNotesTool {
+    security: Arc<SecurityPolicy>,
+    vault: PathBuf,
+    notes_folder: String,
+    exclude_folders: Vec<String>,
+}
+
+impl ObsidianNotesTool {
+    pub fn new(security: Arc<SecurityPolicy>, vault: PathBuf, config: &ObsidianConfig) -> Self {
+        Self {
+            security,
+            vault,
+            notes_folder: config.notes_folder.clone(),
+            exclude_folders: config.exclude_folders.clone(),
+        }
+    }
+
+    fn string_arg<'a>(args: &'a Value, name: &str) -> Option<&'a str> {
+        args.get(name)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    }
+
+    fn string_list(args: &Value, name: &str) -> Vec<String> {
+        args.get(name)
+            .and_then(Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|v| v.trim().trim_start_matches('#').to_string())
+                    .filter(|v| !v.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn find(&self, name: &str) -> anyhow::Result<PathBuf> {
+        find_note(&self.vault, name, &self.exclude_folders)
+            .ok_or_else(|| anyhow::anyhow!("Note '{name}' not found in the vault"))
+    }
+
+    /// Reject writes that would escape the vault through a symlinked folder.
+    fn ensure_inside_vault(&self, path: &Path) -> anyhow::Result<()> {
+        let vault = self.vault.canonicalize()?;
+        let parent = path
+            .parent()
+            .ok_or_else(|| anyhow::anyhow!("Invalid note path"))?
+            .canonicalize()?;
+        if !parent.starts_with(&vault) {
+            anyhow::bail!("Note path resolves outside the vault");
+        }
+        Ok(())
+    }
+
+    async fn create(&self, args: &Value) -> anyhow::Result<ToolResult> {
+        let title = Self::string_arg(args, "title")
+            .ok_or_else(|| anyhow::anyhow!("Missing 'title' parameter"))?;
+        let folder = Self::string_arg(args, "folder").unwrap_or(&self.notes_folder);
+        let relative = format!("{}/{}", folder.trim_matches('/'), note_file_name(title)?);
+        let path = vault_path(&self.vault, &relative)?;
+        if path.exists() {
+            return Ok(failure(format!(
+                "Note '{}' already exists; use action 'append'",
+                relative_path(&self.vault, &path)
+            )));
+        }
+
+        let tags = Self::string_list(args, "tags");
+        let links = Self::string_list(args, "links");
+        let mut body = render_frontmatter(&[
+            ("created", vec![chrono::Utc::now().to_rfc3339()]),
+            ("tags", tags),
+        ]);
+        let _ = writeln!(body, "# {title}");
+        if let Some(content) = Self::string_arg(args, "content") {
+            let _ = writeln!(body, "\n{content}");
+        }
+        if !links.is_empty() {
+            let _ = writeln!(body, "\n{RELATED_HEADING}");
+            for link in &links {
+                let _ = writeln!(body, "- [[{link}]]");
+            }
+        }
+
+        tokio::fs::create_dir_all(path.parent().unwrap_or(&self.vault)).await?;
+        self.ensure_inside_vault(&path)?;
+        tokio::fs::write(&path, body).await?;
+        Ok(success(format!(
+            "Created {}",
+            relative_path(&self.vault, &path)
+        )))
+    }
+
+    async fn append(&self, args: &Value) -> anyhow::Result<ToolResult> {
+        let name = Self::string_arg(args, "note")
+            .ok_or_else(|| anyhow::anyhow!("Missing 'note' parameter"))?;
+        let content = Self::string_arg(args, "content")
+            .ok_or_else(|| anyhow::anyhow!("Missing 'content' parameter"))?;
+        let path = self.find(name)?;
+        self.ensure_inside_vault(&path)?;
+
+        let mut existing = tokio::fs::read_to_string(&path).await?;
+        if !existing.is_empty() && !existing.ends_with("\n\n") {
+            existing.push_str(if existing.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+        existing.push_str(content);
+        existing.push('\n');
+        tokio::fs::write(&path, existing).await?;
+        Ok(success(format!(
+            "Appended to {}",
+            relative_path(&self.vault, &path)
+        )))
+    }
+
+    async fn link(&self, args: &Value) -> anyhow::Result<ToolResult> {
+        let from = Self::string_arg(args, "note")
+            .ok_or_else(|| anyhow::anyhow!("Missing 'note' parameter"))?;
+        let to = Self::string_arg(args, "target")
+            .ok_or_else(|| anyhow::anyhow!("Missing 'target' parameter"))?;
+        let path = self.find(from)?;
+        self.ensure_inside_vault(&path)?;
+        let relative = relative_path(&self.vault, &path);
+        let target = to.strip_suffix(".md").unwrap_or(to);
+
+        let mut content = tokio::fs::read_to_string(&path).await?;
+        let note = parse_note(&relative, &content);
+        if note.links.iter().any(|l| l.eq_ignore_ascii_case(target)) {
+            return Ok(success(format!("{relative} already links to [[{target}]]")));
+        }
+
+        let last_heading = content
+            .lines()
+            .rfind(|line| line.starts_with('#') && line.trim_start_matches('#').starts_with(' '))
+            .map(str::trim)
+            .map(str::to_string);
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        if last_heading.as_deref() != Some(RELATED_HEADING) {
+            let _ = writeln!(content, "\n{RELATED_HEADING}");
+        }
+        let _ = writeln!(content, "- [[{target}]]");
+        tokio::fs::write(&path, content).await?;
+
+        let dangling = find_note(&self.vault, target, &self.exclude_folders).is_none();
+        Ok(success(format!(
+            "Linked {relative} → [[{target}]]{}",
+            if dangling {
+                " (target note does not exist yet)"
+            } else {
+                ""
+            }
+        )))
+    }
+
+    async fn read(&self, args: &Value) -> anyhow::Result<ToolResult> {
+        let name = Self::string_arg(args, "note")
+            .ok_or_else(|| anyhow::anyhow!("Missing 'note' parameter"))?;
+        let path = self.find(name)?;
+        let relative = relative_path(&self.vault, &path);
+        let raw = tokio::fs::read_to_string(&path).await?;
+        let note = parse_note(&relative, &raw);
+
+        let output = json!({
+            "path": relative,
+            "title": note.title,
+            "tags": note.tags,
+            "links": note.links,
+            "content": crate::util::truncate_with_ellipsis(&note.body, MAX_READ_CHARS),
+        });
+        Ok(success(serde_json::to_string_pretty(&output)?))
+    }
+}
+
+fn success(output: String) -> ToolResult {
+    ToolResult {
+        success: true,
+        output,
+        error: None,
+    }
+}
+
+fn failure(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for ObsidianNotesTool {
+    fn name(&self) -> &str {
+        "obsidian_notes"
+    }
+
+    fn description(&self) -> &str {
+        "Work with notes in the team's Obsidian vault: create a note, append to one, add a \
+         [[wikilink]] between notes, or read a note with its tags and links. Use memory_recall \
+         to search the vault."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["create", "append", "link", "read"],
+                    "description": "Operation to perform"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Title of the new note (create)"
+                },
+                "folder": {
+                    "type": "string",
+                    "description": "Vault folder for the new note (create, defaults to the configured notes folder)"
+                },
+                "note": {
+                    "type": "string",
+                    "description": "Existing note name or vault-relative path (append, link, read)"
+                },
+                "content": {
+                    "type": "string",
+                    "description": "Markdown to write (create, append)"
+                },
+                "target": {
+                    "type": "string",
+                    "description": "Note name to link to (link)"
+                },
+                "tags": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Tags for the new note (create)"
+                },
+                "links": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Notes to link from the new note (create)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+
+        if action != "read" {
+            if let Err(e) = self
+                .security
+                .enforce_tool_operation(ToolOperation::Act, "obsidian_notes")
+            {
+                return Ok(failure(e));
+            }
+        }
+
+        let result = match action {
+            "create" => self.create(&args).await,
+            "append" => self.append(&args).await,
+            "link" => self.link(&args).await,
+            "read" => self.read(&args).await,
+            other => {
+                return Ok(failure(format!(
+                    "Unknown action '{other}'. Use create, append, link or read"
+                )))
+            }
+        };
+        Ok(result.unwrap_or_else(|e| failure(e.to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn tool(vault: &Path, autonomy: AutonomyLevel) -> ObsidianNotesTool {
+        let security = Arc::new(SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        });
+        ObsidianNotesTool::new(security, vault.to_path_buf(), &ObsidianConfig::default())
+    }
+
+    #[tokio::test]
+    async fn create_writes_frontmatter_tags_and_links() {
+        let vault = TempDir::new().unwrap();
+        let tool = tool(vault.path(), AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({
+                "action": "create",
+                "title": "Incident 42",
+                "content": "Database failover at 03:00.",
+                "tags": ["#incident", "db"],
+                "links": ["Runbook"]
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("ZeroClaw/Incident 42.md"));
+
+        let raw = std::fs::read_to_string(vault.path().join("ZeroClaw/Incident 42.md")).unwrap();
+        let note = parse_note("ZeroClaw/Incident 42.md", &raw);
+        assert_eq!(note.tags, vec!["incident", "db"]);
+        assert_eq!(note.links, vec!["Runbook"]);
+        assert!(note.body.contains("Database failover"));
+
+        let again = tool
+            .execute(json!({"action": "create", "title": "Incident 42"}))
+            .await
+            .unwrap();
+        assert!(!again.success);
+        assert!(again.error.unwrap().contains("already exists"));
+    }
+
+    #[tokio::test]
+    async fn append_and_link_find_notes_by_name() {
+        let vault = TempDir::new().unwrap();
+        std::fs::create_dir_all(vault.path().join("Team")).unwrap();
+        std::fs::write(vault.path().join("Team/Standup.md"), "# Standup").unwrap();
+        let tool = tool(vault.path(), AutonomyLevel::Supervised);
+
+        let appended = tool
+            .execute(json!({"action": "append", "note": "standup", "content": "- shipped v2"}))
+            .await
+            .unwrap();
+        assert!(appended.success, "{:?}", appended.error);
+
+        for _ in 0..2 {
+            let linked = tool
+                .execute(json!({"action": "link", "note": "Team/Standup", "target": "Roadmap"}))
+                .await
+                .unwrap();
+            assert!(linked.success, "{:?}", linked.error);
+        }
+
+        let raw = std::fs::read_to_string(vault.path().join("Team/Standup.md")).unwrap();
+        assert_eq!(
+            raw,
+            "# Standup\n\n- shipped v2\n\n## Related\n- [[Roadmap]]\n"
+        );
+
+        let read = tool
+            .execute(json!({"action": "read", "note": "Standup"}))
+            .await
+            .unwrap();
+        assert!(read.output.contains("\"Roadmap\""));
+    }
+
+    #[tokio::test]
+    async fn rejects_paths_outside_vault_and_read_only_writes() {
+        let vault = TempDir::new().unwrap();
+        let result = tool(vault.path(), AutonomyLevel::Supervised)
+            .execute(json!({"action": "create", "title": "x", "folder": "../outside"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Invalid vault path"));
+
+        let result = tool(vault.path(), AutonomyLevel::ReadOnly)
+            .execute(json!({"action": "create", "title": "x"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+
+        let missing = tool(vault.path(), AutonomyLevel::ReadOnly)
+            .execute(json!({"action": "read", "note": "Nope"}))
+            .await
+            .unwrap();
+        assert!(!missing.success);
+        assert!(missing.error.unwrap().contains("not found"));
+    }
+}
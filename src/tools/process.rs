@@ -0,0 +1,1050 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{ResourceLimitsConfig, SandboxBackend, SecurityConfig};
+use crate::security::{create_sandbox, NoopSandbox, Sandbox, SecurityPolicy};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::future::Future;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::process::{Child, ChildStdin};
+
+/// Output retained per background process; older bytes are dropped first.
+const MAX_BUFFERED_OUTPUT_BYTES: usize = 256 * 1024;
+/// Maximum output returned by a single poll.
+const MAX_POLL_OUTPUT_BYTES: usize = 64 * 1024;
+const DEFAULT_TAIL_LINES: usize = 50;
+/// Exited processes kept for `poll`/`tail`; older ones are forgotten when a
+/// new process starts so a long-running daemon does not keep every output.
+const MAX_RETAINED_EXITED: usize = 16;
+
+/// Programs whose stdin is shell syntax; lines written to them must pass the
+/// same command policy as the `shell` tool.
+const SHELL_PROGRAMS: &[&str] = &[
+    "sh", "bash", "dash", "zsh", "ksh", "mksh", "fish", "csh", "tcsh", "busybox",
+];
+/// Programs whose stdin is code the command policy cannot vet; writing to
+/// them is refused.
+const INTERPRETER_PROGRAMS: &[&str] = &[
+    "python",
+    "node",
+    "nodejs",
+    "deno",
+    "bun",
+    "ruby",
+    "irb",
+    "perl",
+    "php",
+    "lua",
+    "luajit",
+    "tclsh",
+    "wish",
+    "pwsh",
+    "powershell",
+    "osascript",
+    "nu",
+    "guile",
+    "racket",
+    "ghci",
+    "R",
+];
+
+/// How stdin written to a background process is vetted.
+#[derive(Debug, PartialEq, Eq)]
+enum StdinPolicy {
+    Forward,
+    ValidateLines,
+    Refuse,
+}
+
+/// Stdin policy for `command`. Every word is checked, not just the first, so
+/// `cat | sh` or `env python3` are caught too; version suffixes
+/// (`python3.12`) are ignored.
+fn stdin_policy(command: &str) -> StdinPolicy {
+    let mut policy = StdinPolicy::Forward;
+    for word in command.split(|c: char| c.is_whitespace() || "|;&()".contains(c)) {
+        let program = word.rsplit('/').next().unwrap_or(word);
+        let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+        if INTERPRETER_PROGRAMS.contains(&program) {
+            return StdinPolicy::Refuse;
+        }
+        if SHELL_PROGRAMS.contains(&program) {
+            policy = StdinPolicy::ValidateLines;
+        }
+    }
+    policy
+}
+
+tokio::task_local! {
+    static PROCESS_OWNER: String;
+}
+
+/// Run `fut` with background processes owned by `owner`, typically the
+/// conversation key of the sender. Processes are only visible to, and can
+/// only be controlled from, the owner that started them.
+pub async fn with_process_owner<F: Future>(owner: impl Into<String>, fut: F) -> F::Output {
+    PROCESS_OWNER.scope(owner.into(), fut).await
+}
+
+/// Owner of the current task; empty outside `with_process_owner` (CLI agent).
+pub fn current_process_owner() -> String {
+    PROCESS_OWNER.try_with(Clone::clone).unwrap_or_default()
+}
+
+/// Captured stdout+stderr of a background process, addressed by absolute
+/// byte offsets so pollers can resume where they left off after truncation.
+#[derive(Debug, Default)]
+struct OutputBuffer {
+    data: Vec<u8>,
+    /// Bytes discarded from the front of `data`.
+    dropped: usize,
+    /// Absolute offset up to which `poll` has returned output.
+    read_pos: usize,
+}
+
+impl OutputBuffer {
+    fn push(&mut self, chunk: &[u8]) {
+        self.data.extend_from_slice(chunk);
+        if self.data.len() > MAX_BUFFERED_OUTPUT_BYTES {
+            let excess = self.data.len() - MAX_BUFFERED_OUTPUT_BYTES;
+            self.data.drain(..excess);
+            self.dropped += excess;
+        }
+    }
+
+    /// Output produced since the previous call, capped at `MAX_POLL_OUTPUT_BYTES`.
+    fn take_new(&mut self) -> String {
+        let mut out = String::new();
+        if self.read_pos < self.dropped {
+            let _ = writeln!(
+                out,
+                "[... {} bytes of earlier output dropped]",
+                self.dropped - self.read_pos
+            );
+            self.read_pos = self.dropped;
+        }
+        let start = self.read_pos - self.dropped;
+        let end = (start + MAX_POLL_OUTPUT_BYTES).min(self.data.len());
+        out.push_str(&String::from_utf8_lossy(&self.data[start..end]));
+        self.read_pos = self.dropped + end;
+        out
+    }
+
+    fn has_unread(&self) -> bool {
+        self.read_pos < self.dropped + self.data.len()
+    }
+
+    fn tail(&self, lines: usize) -> String {
+        let text = String::from_utf8_lossy(&self.data);
+        let all: Vec<&str> = text.lines().collect();
+        all[all.len().saturating_sub(lines)..].join("\n")
+    }
+}
+
+struct ManagedProcess {
+    owner: String,
+    command: String,
+    started: Instant,
+    /// Process group id (the child's pid), signalled as a whole on kill.
+    pgid: Option<u32>,
+    timed_out: AtomicBool,
+    child: tokio::sync::Mutex<Child>,
+    stdin: tokio::sync::Mutex<Option<ChildStdin>>,
+    output: Arc<Mutex<OutputBuffer>>,
+}
+
+impl ManagedProcess {
+    /// `None` while running, `Some(code)` once exited (`-1` when killed by a signal).
+    async fn exit_code(&self) -> Option<i32> {
+        let mut child = self.child.lock().await;
+        match child.try_wait() {
+            Ok(Some(status)) => Some(status.code().unwrap_or(-1)),
+            Ok(None) => None,
+            Err(_) => Some(-1),
+        }
+    }
+
+    async fn status_label(&self) -> String {
+        match self.exit_code().await {
+            None => "running".into(),
+            Some(_) if self.timed_out.load(Ordering::Relaxed) => {
+                "killed (max runtime exceeded)".into()
+            }
+            Some(code) => format!("exited ({code})"),
+        }
+    }
+
+    /// Kill the whole process group, then reap the child.
+    async fn terminate(&self) -> std::io::Result<()> {
+        if let Some(pgid) = self.pgid {
+            kill_process_group(pgid);
+        }
+        self.child.lock().await.kill().await
+    }
+}
+
+/// SIGKILL every process in group `pgid`, including grandchildren that
+/// `Child::kill` would leave behind.
+#[cfg(unix)]
+fn kill_process_group(pgid: u32) {
+    if let Ok(pgid) = i32::try_from(pgid) {
+        // SAFETY: kill(2) has no memory-safety preconditions; a negative pid
+        // addresses the process group.
+        unsafe {
+            libc::kill(-pgid, libc::SIGKILL);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_pgid: u32) {}
+
+/// Table of background processes started by the `shell` tool.
+///
+/// Every child leads its own process group. Groups are killed on `kill`, when
+/// they outlive `max_background_runtime_seconds`, when their owner's session
+/// is reset, and when the manager is dropped at the end of a session.
+pub struct ProcessManager {
+    limits: Mutex<ResourceLimitsConfig>,
+    sandbox: Mutex<Arc<dyn Sandbox>>,
+    next_id: AtomicU64,
+    processes: Mutex<BTreeMap<String, Arc<ManagedProcess>>>,
+}
+
+impl ProcessManager {
+    pub fn new(limits: ResourceLimitsConfig, sandbox: Arc<dyn Sandbox>) -> Self {
+        Self {
+            limits: Mutex::new(limits),
+            sandbox: Mutex::new(sandbox),
+            next_id: AtomicU64::new(1),
+            processes: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Prefix `command` with `ulimit` calls enforcing the configured CPU-time
+    /// and memory ceilings. Failures are ignored on shells that lack a limit.
+    pub fn limited_command(&self, command: &str) -> String {
+        let limits = self.limits.lock();
+        let mut prefix = String::new();
+        if limits.max_cpu_time_seconds > 0 {
+            let _ = write!(
+                prefix,
+                "ulimit -t {} 2>/dev/null; ",
+                limits.max_cpu_time_seconds
+            );
+        }
+        if limits.memory_monitoring && limits.max_memory_mb > 0 {
+            let _ = write!(
+                prefix,
+                "ulimit -v {} 2>/dev/null; ",
+                u64::from(limits.max_memory_mb) * 1024
+            );
+        }
+        format!("{prefix}{command}")
+    }
+
+    /// Running background processes across all owners.
+    async fn running_count(&self) -> usize {
+        let processes: Vec<_> = self.processes.lock().values().cloned().collect();
+        let mut running = 0;
+        for process in processes {
+            if process.exit_code().await.is_none() {
+                running += 1;
+            }
+        }
+        running
+    }
+
+    /// Forget the oldest exited processes beyond [`MAX_RETAINED_EXITED`].
+    async fn prune_exited(&self) {
+        let processes: Vec<_> = self
+            .processes
+            .lock()
+            .iter()
+            .map(|(id, p)| (id.clone(), p.clone()))
+            .collect();
+        let mut exited = Vec::new();
+        for (id, process) in processes {
+            if process.exit_code().await.is_some() {
+                exited.push((process.started, id));
+            }
+        }
+        if exited.len() <= MAX_RETAINED_EXITED {
+            return;
+        }
+        exited.sort();
+        let excess = exited.len() - MAX_RETAINED_EXITED;
+        let mut table = self.processes.lock();
+        for (_, id) in exited.into_iter().take(excess) {
+            table.remove(&id);
+        }
+    }
+
+    /// Spawn `cmd` in the background through the configured sandbox and
+    /// register it under `owner`. Returns the new process id.
+    pub async fn spawn(
+        &self,
+        owner: &str,
+        command: &str,
+        cmd: tokio::process::Command,
+    ) -> anyhow::Result<String> {
+        self.prune_exited().await;
+        let limits = self.limits.lock().clone();
+        let max = usize::try_from(limits.max_subprocesses).unwrap_or(usize::MAX);
+        if self.running_count().await >= max {
+            anyhow::bail!(
+                "Too many background processes running (limit: {max}); kill one before starting another"
+            );
+        }
+
+        // Sandbox backends may replace the command wholesale, so the working
+        // directory and environment are re-applied after wrapping. Only
+        // variables set explicitly on `cmd` are passed through.
+        let original = cmd.as_std();
+        let mut std_cmd = std::process::Command::new(original.get_program());
+        std_cmd.args(original.get_args());
+        let sandbox = Arc::clone(&self.sandbox.lock());
+        sandbox.wrap_command(&mut std_cmd)?;
+        if let Some(dir) = original.get_current_dir() {
+            std_cmd.current_dir(dir);
+        }
+        std_cmd.env_clear();
+        for (key, value) in original.get_envs() {
+            if let Some(value) = value {
+                std_cmd.env(key, value);
+            }
+        }
+
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut std_cmd, 0);
+
+        let mut cmd = tokio::process::Command::from(std_cmd);
+        cmd.stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        let mut child = cmd.spawn()?;
+
+        let output = Arc::new(Mutex::new(OutputBuffer::default()));
+        if let Some(stdout) = child.stdout.take() {
+            tokio::spawn(pump_output(stdout, output.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(pump_output(stderr, output.clone()));
+        }
+
+        let id = format!("p{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+        let process = Arc::new(ManagedProcess {
+            owner: owner.to_string(),
+            command: command.to_string(),
+            started: Instant::now(),
+            pgid: child.id(),
+            timed_out: AtomicBool::new(false),
+            stdin: tokio::sync::Mutex::new(child.stdin.take()),
+            child: tokio::sync::Mutex::new(child),
+            output,
+        });
+        if limits.max_background_runtime_seconds > 0 {
+            tokio::spawn(enforce_max_runtime(
+                Arc::downgrade(&process),
+                Duration::from_secs(limits.max_background_runtime_seconds),
+            ));
+        }
+        self.processes.lock().insert(id.clone(), process);
+        Ok(id)
+    }
+
+    /// Process `id`, if it belongs to `owner`.
+    fn get(&self, owner: &str, id: &str) -> Option<Arc<ManagedProcess>> {
+        self.processes
+            .lock()
+            .get(id)
+            .filter(|process| process.owner == owner)
+            .cloned()
+    }
+
+    async fn list(&self, owner: &str) -> String {
+        let processes: Vec<_> = self
+            .processes
+            .lock()
+            .iter()
+            .filter(|(_, p)| p.owner == owner)
+            .map(|(id, p)| (id.clone(), p.clone()))
+            .collect();
+        if processes.is_empty() {
+            return "No background processes".into();
+        }
+
+        let mut out = String::new();
+        for (id, process) in processes {
+            let _ = writeln!(
+                out,
+                "{id}\t{}\t{}s\t{}",
+                process.status_label().await,
+                process.started.elapsed().as_secs(),
+                process.command
+            );
+        }
+        out
+    }
+
+    async fn poll(&self, owner: &str, id: &str) -> Option<String> {
+        let process = self.get(owner, id)?;
+        let status = process.status_label().await;
+        let new_output = process.output.lock().take_new();
+        let more = if process.output.lock().has_unread() {
+            " (more output pending; poll again)"
+        } else {
+            ""
+        };
+        Some(format!("[{id}: {status}{more}]\n{new_output}"))
+    }
+
+    async fn tail(&self, owner: &str, id: &str, lines: usize) -> Option<String> {
+        let process = self.get(owner, id)?;
+        let status = process.status_label().await;
+        let tail = process.output.lock().tail(lines);
+        Some(format!("[{id}: {status}]\n{tail}"))
+    }
+
+    /// Write `input` to the stdin of process `id`. Input for a shell must
+    /// pass `security`'s command policy line by line; interpreters are refused.
+    async fn write_stdin(
+        &self,
+        security: &SecurityPolicy,
+        owner: &str,
+        id: &str,
+        input: &str,
+        close: bool,
+    ) -> Result<String, String> {
+        let process = self
+            .get(owner, id)
+            .ok_or_else(|| format!("Unknown process: {id}"))?;
+        match stdin_policy(&process.command) {
+            StdinPolicy::Forward => {}
+            StdinPolicy::ValidateLines => {
+                for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                    security.validate_command_execution(line, false)?;
+                }
+            }
+            StdinPolicy::Refuse if input.is_empty() => {}
+            StdinPolicy::Refuse => {
+                return Err(format!(
+                    "Writing to {id} is blocked: it runs an interpreter whose input bypasses the command policy"
+                ));
+            }
+        }
+        let mut stdin = process.stdin.lock().await;
+        let Some(pipe) = stdin.as_mut() else {
+            return Err(format!("stdin of {id} is closed"));
+        };
+        if !input.is_empty() {
+            pipe.write_all(input.as_bytes())
+                .await
+                .map_err(|e| format!("Failed to write to {id}: {e}"))?;
+            pipe.flush()
+                .await
+                .map_err(|e| format!("Failed to write to {id}: {e}"))?;
+        }
+        if close {
+            *stdin = None;
+        }
+        Ok(format!(
+            "Wrote {} bytes to {id}{}",
+            input.len(),
+            if close { " and closed stdin" } else { "" }
+        ))
+    }
+
+    /// Kill (if still running) and forget a process.
+    async fn kill(&self, owner: &str, id: &str) -> Result<String, String> {
+        let process = {
+            let mut processes = self.processes.lock();
+            let owned = processes.get(id).is_some_and(|p| p.owner == owner);
+            owned.then(|| processes.remove(id)).flatten()
+        }
+        .ok_or_else(|| format!("Unknown process: {id}"))?;
+        if process.exit_code().await.is_some() {
+            return Ok(format!("Removed exited process {id}"));
+        }
+        process
+            .terminate()
+            .await
+            .map_err(|e| format!("Failed to kill {id}: {e}"))?;
+        Ok(format!("Killed {id}"))
+    }
+
+    /// Kill and forget every process started by `owner`, e.g. when its
+    /// conversation is reset. Returns how many were removed.
+    pub async fn kill_owned_by(&self, owner: &str) -> usize {
+        let removed: Vec<_> = {
+            let mut processes = self.processes.lock();
+            let ids: Vec<String> = processes
+                .iter()
+                .filter(|(_, p)| p.owner == owner)
+                .map(|(id, _)| id.clone())
+                .collect();
+            ids.iter().filter_map(|id| processes.remove(id)).collect()
+        };
+        for process in &removed {
+            if process.exit_code().await.is_none() {
+                let _ = process.terminate().await;
+            }
+        }
+        removed.len()
+    }
+}
+
+impl Drop for ProcessManager {
+    fn drop(&mut self) {
+        // `kill_on_drop` only reaches the group leader.
+        for process in self.processes.get_mut().values() {
+            if let Some(pgid) = process.pgid {
+                kill_process_group(pgid);
+            }
+        }
+    }
+}
+
+/// Kill `process` once it has run for `limit`, unless it exited or was
+/// removed first.
+async fn enforce_max_runtime(process: Weak<ManagedProcess>, limit: Duration) {
+    tokio::time::sleep(limit).await;
+    let Some(process) = process.upgrade() else {
+        return;
+    };
+    if process.exit_code().await.is_none() {
+        process.timed_out.store(true, Ordering::Relaxed);
+        let _ = process.terminate().await;
+    }
+}
+
+impl ProcessManager {
+    /// Build from the `[security]` config section.
+    ///
+    /// An OS sandbox is applied only when one is requested explicitly (via
+    /// `sandbox.enabled = true` or a concrete backend), so existing setups
+    /// don't start wrapping commands in whatever tool happens to be installed.
+    pub fn from_config(config: &SecurityConfig) -> Self {
+        Self::new(config.resources.clone(), Self::sandbox_for(config))
+    }
+
+    /// Apply changed `[security]` limits and sandbox to processes started
+    /// from now on; running ones keep what they were started with.
+    pub fn reconfigure(&self, config: &SecurityConfig) {
+        *self.limits.lock() = config.resources.clone();
+        *self.sandbox.lock() = Self::sandbox_for(config);
+    }
+
+    fn sandbox_for(config: &SecurityConfig) -> Arc<dyn Sandbox> {
+        let requested = match config.sandbox.enabled {
+            Some(enabled) => enabled,
+            None => !matches!(
+                config.sandbox.backend,
+                SandboxBackend::Auto | SandboxBackend::None
+            ),
+        };
+        let mut sandbox = if requested {
+            create_sandbox(config)
+        } else {
+            Arc::new(NoopSandbox)
+        };
+        // Landlock restricts the calling process rather than the child, which
+        // would lock down the whole agent.
+        if sandbox.name() == "landlock" {
+            tracing::warn!("Landlock cannot be scoped to background processes; running them without an OS sandbox");
+            sandbox = Arc::new(NoopSandbox);
+        }
+        sandbox
+    }
+}
+
+impl Default for ProcessManager {
+    fn default() -> Self {
+        Self::new(ResourceLimitsConfig::default(), Arc::new(NoopSandbox))
+    }
+}
+
+async fn pump_output(mut pipe: impl AsyncRead + Unpin, output: Arc<Mutex<OutputBuffer>>) {
+    let mut buf = [0_u8; 8192];
+    loop {
+        match pipe.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => output.lock().push(&buf[..n]),
+        }
+    }
+}
+
+/// Inspect and control background processes started with `shell` + `background`.
+pub struct ProcessTool {
+    security: Arc<SecurityPolicy>,
+    processes: Arc<ProcessManager>,
+}
+
+impl ProcessTool {
+    pub fn new(security: Arc<SecurityPolicy>, processes: Arc<ProcessManager>) -> Self {
+        Self {
+            security,
+            processes,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ProcessTool {
+    fn name(&self) -> &str {
+        "process"
+    }
+
+    fn description(&self) -> &str {
+        "Manage background processes started with shell(background=true): list them, poll new output, tail recent output, write to stdin, or kill."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "poll", "tail", "write", "kill"],
+                    "description": "Operation to perform"
+                },
+                "id": {
+                    "type": "string",
+                    "description": "Process id returned by shell (required for all actions except list)"
+                },
+                "lines": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Number of trailing lines for 'tail' (default: 50)"
+                },
+                "input": {
+                    "type": "string",
+                    "description": "Text to send to stdin for 'write' (include a trailing newline to submit a line)"
+                },
+                "close_stdin": {
+                    "type": "boolean",
+                    "description": "Close stdin after writing, signalling end of input (default: false)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+
+        let owner = current_process_owner();
+        if action == "list" {
+            return Ok(ToolResult {
+                success: true,
+                output: self.processes.list(&owner).await,
+                error: None,
+            });
+        }
+
+        let id = args
+            .get("id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'id' parameter"))?;
+
+        let result = match action {
+            "poll" => self
+                .processes
+                .poll(&owner, id)
+                .await
+                .ok_or_else(|| format!("Unknown process: {id}")),
+            "tail" => {
+                let lines = args
+                    .get("lines")
+                    .and_then(serde_json::Value::as_u64)
+                    .and_then(|n| usize::try_from(n).ok())
+                    .unwrap_or(DEFAULT_TAIL_LINES)
+                    .max(1);
+                self.processes
+                    .tail(&owner, id, lines)
+                    .await
+                    .ok_or_else(|| format!("Unknown process: {id}"))
+            }
+            "write" | "kill" => {
+                if !self.security.can_act() {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some("Action blocked: autonomy is read-only".into()),
+                    });
+                }
+                if !self.security.record_action() {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some("Rate limit exceeded: action budget exhausted".into()),
+                    });
+                }
+                if action == "kill" {
+                    self.processes.kill(&owner, id).await
+                } else {
+                    let input = args.get("input").and_then(|v| v.as_str()).unwrap_or("");
+                    let close = args
+                        .get("close_stdin")
+                        .and_then(serde_json::Value::as_bool)
+                        .unwrap_or(false);
+                    self.processes
+                        .write_stdin(&self.security, &owner, id, input, close)
+                        .await
+                }
+            }
+            other => anyhow::bail!("Unknown action '{other}'; use list, poll, tail, write or kill"),
+        };
+
+        Ok(match result {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(error) => ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use std::time::Duration;
+
+    fn test_security(autonomy: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn sh(script: &str) -> tokio::process::Command {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(script);
+        if let Ok(path) = std::env::var("PATH") {
+            cmd.env("PATH", path);
+        }
+        cmd
+    }
+
+    async fn wait_for_output(manager: &ProcessManager, id: &str, needle: &str) -> String {
+        for _ in 0..100 {
+            let tail = manager.tail("", id, 100).await.unwrap();
+            if tail.contains(needle) {
+                return tail;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("timed out waiting for {needle:?}");
+    }
+
+    #[test]
+    fn output_buffer_drops_oldest_and_reports_gap() {
+        let mut buffer = OutputBuffer::default();
+        buffer.push(&vec![b'a'; MAX_BUFFERED_OUTPUT_BYTES]);
+        buffer.push(b"tail");
+        assert_eq!(buffer.dropped, 4);
+
+        let first = buffer.take_new();
+        assert!(first.starts_with("[... 4 bytes of earlier output dropped]"));
+        assert!(buffer.has_unread());
+        while buffer.has_unread() {
+            buffer.take_new();
+        }
+        assert_eq!(buffer.take_new(), "");
+    }
+
+    #[test]
+    fn limited_command_applies_resource_limits() {
+        let manager = ProcessManager::new(
+            ResourceLimitsConfig {
+                max_memory_mb: 64,
+                max_cpu_time_seconds: 5,
+                ..ResourceLimitsConfig::default()
+            },
+            Arc::new(NoopSandbox),
+        );
+        assert_eq!(
+            manager.limited_command("make"),
+            "ulimit -t 5 2>/dev/null; ulimit -v 65536 2>/dev/null; make"
+        );
+    }
+
+    #[tokio::test]
+    async fn reconfigure_keeps_running_processes() {
+        let manager = ProcessManager::default();
+        let id = manager.spawn("", "sleep", sh("sleep 5")).await.unwrap();
+
+        let mut config = SecurityConfig::default();
+        config.resources.max_cpu_time_seconds = 7;
+        config.resources.memory_monitoring = false;
+        manager.reconfigure(&config);
+
+        assert_eq!(
+            manager.limited_command("make"),
+            "ulimit -t 7 2>/dev/null; make"
+        );
+        let status = manager.tail("", &id, 1).await.unwrap();
+        assert!(status.contains("running"), "{status}");
+    }
+
+    #[test]
+    fn from_config_skips_sandbox_unless_requested() {
+        let manager = ProcessManager::from_config(&SecurityConfig::default());
+        assert_eq!(manager.sandbox.lock().name(), "none");
+
+        let mut config = SecurityConfig::default();
+        config.sandbox.enabled = Some(false);
+        config.sandbox.backend = SandboxBackend::Firejail;
+        assert_eq!(
+            ProcessManager::from_config(&config).sandbox.lock().name(),
+            "none"
+        );
+    }
+
+    #[tokio::test]
+    async fn background_process_output_is_pollable() {
+        let manager = ProcessManager::default();
+        let id = manager
+            .spawn("", "echo", sh("echo hello; echo oops >&2"))
+            .await
+            .unwrap();
+        wait_for_output(&manager, &id, "oops").await;
+
+        let polled = manager.poll("", &id).await.unwrap();
+        assert!(polled.contains("hello"));
+        let again = manager.poll("", &id).await.unwrap();
+        assert!(!again.contains("hello"));
+    }
+
+    #[tokio::test]
+    async fn interactive_session_reads_stdin_and_can_be_killed() {
+        let manager = Arc::new(ProcessManager::default());
+        let tool = ProcessTool::new(test_security(AutonomyLevel::Supervised), manager.clone());
+        let id = manager
+            .spawn(
+                "",
+                "cat",
+                sh("while read line; do echo \"got $line\"; done"),
+            )
+            .await
+            .unwrap();
+
+        let result = tool
+            .execute(json!({"action": "write", "id": id, "input": "ping\n"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        wait_for_output(&manager, &id, "got ping").await;
+
+        let listed = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert!(listed.output.contains(&id));
+        assert!(listed.output.contains("running"));
+
+        let killed = tool
+            .execute(json!({"action": "kill", "id": id}))
+            .await
+            .unwrap();
+        assert!(killed.success);
+        let listed = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert_eq!(listed.output, "No background processes");
+    }
+
+    #[tokio::test]
+    async fn exited_processes_are_pruned_past_the_retention_cap() {
+        let manager = ProcessManager::new(
+            ResourceLimitsConfig {
+                max_subprocesses: 1,
+                ..ResourceLimitsConfig::default()
+            },
+            Arc::new(NoopSandbox),
+        );
+        let mut last = String::new();
+        for _ in 0..MAX_RETAINED_EXITED + 5 {
+            last = manager.spawn("", "true", sh("true")).await.unwrap();
+            while manager.get("", &last).unwrap().exit_code().await.is_none() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+
+        // Pruning runs before each spawn: the retained exited ones plus the newest.
+        assert_eq!(manager.processes.lock().len(), MAX_RETAINED_EXITED + 1);
+        assert!(manager.poll("", &last).await.is_some());
+        assert!(manager.poll("", "p1").await.is_none());
+    }
+
+    #[test]
+    fn stdin_policy_spots_shells_and_interpreters() {
+        assert_eq!(stdin_policy("cat"), StdinPolicy::Forward);
+        assert_eq!(stdin_policy("npm run dev"), StdinPolicy::Forward);
+        assert_eq!(stdin_policy("bash"), StdinPolicy::ValidateLines);
+        assert_eq!(stdin_policy("cat | /bin/sh"), StdinPolicy::ValidateLines);
+        assert_eq!(stdin_policy("python3.12 -i"), StdinPolicy::Refuse);
+        assert_eq!(stdin_policy("env node"), StdinPolicy::Refuse);
+    }
+
+    #[tokio::test]
+    async fn stdin_to_shells_and_interpreters_is_vetted() {
+        let manager = Arc::new(ProcessManager::default());
+        let tool = ProcessTool::new(test_security(AutonomyLevel::Full), manager.clone());
+        let shell = manager
+            .spawn("", "sh", sh("while read line; do echo \"got $line\"; done"))
+            .await
+            .unwrap();
+        let blocked = tool
+            .execute(json!({"action": "write", "id": shell, "input": "echo ok\nrm -rf /\n"}))
+            .await
+            .unwrap();
+        assert!(!blocked.success);
+        assert!(blocked.error.unwrap().contains("not allowed"));
+        let allowed = tool
+            .execute(json!({"action": "write", "id": shell, "input": "echo ok\n"}))
+            .await
+            .unwrap();
+        assert!(allowed.success, "{:?}", allowed.error);
+        wait_for_output(&manager, &shell, "got echo ok").await;
+
+        let repl = manager.spawn("", "python3 -i", sh("cat")).await.unwrap();
+        let refused = tool
+            .execute(json!({"action": "write", "id": repl, "input": "import os\n"}))
+            .await
+            .unwrap();
+        assert!(!refused.success);
+        assert!(refused.error.unwrap().contains("interpreter"));
+        manager.kill_owned_by("").await;
+    }
+
+    #[tokio::test]
+    async fn spawn_enforces_max_subprocesses() {
+        let manager = ProcessManager::new(
+            ResourceLimitsConfig {
+                max_subprocesses: 1,
+                ..ResourceLimitsConfig::default()
+            },
+            Arc::new(NoopSandbox),
+        );
+        manager.spawn("", "sleep", sh("sleep 5")).await.unwrap();
+        let err = manager.spawn("", "sleep", sh("sleep 5")).await.unwrap_err();
+        assert!(err.to_string().contains("Too many background processes"));
+    }
+
+    #[tokio::test]
+    async fn processes_are_scoped_to_their_owner() {
+        let manager = Arc::new(ProcessManager::default());
+        let tool = ProcessTool::new(test_security(AutonomyLevel::Supervised), manager.clone());
+        let id = with_process_owner("telegram_alice", async {
+            let owner = current_process_owner();
+            manager.spawn(&owner, "sleep", sh("sleep 5")).await.unwrap()
+        })
+        .await;
+
+        with_process_owner("telegram_mallory", async {
+            let listed = tool.execute(json!({"action": "list"})).await.unwrap();
+            assert_eq!(listed.output, "No background processes");
+            for action in ["poll", "tail", "write", "kill"] {
+                let result = tool
+                    .execute(json!({"action": action, "id": id, "input": "x"}))
+                    .await
+                    .unwrap();
+                assert!(!result.success, "{action} crossed owners");
+                assert!(result.error.unwrap().contains("Unknown process"));
+            }
+        })
+        .await;
+
+        let listed = with_process_owner("telegram_alice", async {
+            tool.execute(json!({"action": "list"})).await.unwrap()
+        })
+        .await;
+        assert!(listed.output.contains(&id));
+        assert!(listed.output.contains("running"));
+
+        assert_eq!(manager.kill_owned_by("telegram_mallory").await, 0);
+        assert_eq!(manager.kill_owned_by("telegram_alice").await, 1);
+        assert!(manager.poll("telegram_alice", &id).await.is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn kill_terminates_the_whole_process_group() {
+        let manager = ProcessManager::default();
+        let id = manager
+            .spawn("", "sleep", sh("sleep 30 & echo \"child $!\"; wait"))
+            .await
+            .unwrap();
+        let output = wait_for_output(&manager, &id, "child ").await;
+        let grandchild: i32 = output
+            .lines()
+            .find_map(|line| line.strip_prefix("child "))
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+
+        manager.kill("", &id).await.unwrap();
+        for _ in 0..100 {
+            // SAFETY: signal 0 only checks whether the pid exists.
+            if unsafe { libc::kill(grandchild, 0) } != 0 {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("grandchild {grandchild} survived kill");
+    }
+
+    #[tokio::test]
+    async fn background_process_is_killed_after_max_runtime() {
+        let manager = ProcessManager::new(
+            ResourceLimitsConfig {
+                max_background_runtime_seconds: 1,
+                ..ResourceLimitsConfig::default()
+            },
+            Arc::new(NoopSandbox),
+        );
+        let id = manager.spawn("", "sleep", sh("sleep 30")).await.unwrap();
+        for _ in 0..150 {
+            let status = manager.tail("", &id, 1).await.unwrap();
+            if status.contains("max runtime exceeded") {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("process outlived max_background_runtime_seconds");
+    }
+
+    #[tokio::test]
+    async fn process_tool_blocks_writes_in_readonly_mode() {
+        let tool = ProcessTool::new(
+            test_security(AutonomyLevel::ReadOnly),
+            Arc::new(ProcessManager::default()),
+        );
+        let result = tool
+            .execute(json!({"action": "kill", "id": "p1"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("read-only"));
+
+        let result = tool
+            .execute(json!({"action": "poll", "id": "p9"}))
+            .await
+            .unwrap();
+        assert!(result.error.as_ref().unwrap().contains("Unknown process"));
+    }
+}
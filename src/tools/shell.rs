@@ -1,8 +1,10 @@
+use super::process::{current_process_owner, ProcessManager};
 use super::traits::{Tool, ToolResult};
 use crate::runtime::RuntimeAdapter;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -15,17 +17,102 @@ const MAX_OUTPUT_BYTES: usize = 1_048_576;
 const SAFE_ENV_VARS: &[&str] = &[
     "PATH", "HOME", "TERM", "LANG", "LC_ALL", "LC_CTYPE", "USER", "SHELL", "TMPDIR",
 ];
+/// Variables the caller may set with `env`. Anything else is rejected:
+/// `PATH`, `HOME`, `SHELL`, `GIT_*`, pagers, editors and loader variables can
+/// make an allowlisted command run an arbitrary program.
+const OVERRIDABLE_ENV_VARS: &[&str] = &[
+    "LANG",
+    "LC_ALL",
+    "LC_CTYPE",
+    "TZ",
+    "TERM",
+    "NO_COLOR",
+    "FORCE_COLOR",
+    "CI",
+    "DEBUG",
+    "NODE_ENV",
+    "RUST_LOG",
+    "RUST_BACKTRACE",
+    "PYTHONUNBUFFERED",
+    "PYTHONDONTWRITEBYTECODE",
+    "HOST",
+    "PORT",
+];
 
 /// Shell command execution tool with sandboxing
 pub struct ShellTool {
     security: Arc<SecurityPolicy>,
     runtime: Arc<dyn RuntimeAdapter>,
+    processes: Option<Arc<ProcessManager>>,
 }
 
 impl ShellTool {
     pub fn new(security: Arc<SecurityPolicy>, runtime: Arc<dyn RuntimeAdapter>) -> Self {
-        Self { security, runtime }
+        Self {
+            security,
+            runtime,
+            processes: None,
+        }
+    }
+
+    /// Enable `background: true`, registering long-running commands in
+    /// `processes` so the `process` tool can poll, feed and kill them.
+    pub fn with_processes(
+        security: Arc<SecurityPolicy>,
+        runtime: Arc<dyn RuntimeAdapter>,
+        processes: Arc<ProcessManager>,
+    ) -> Self {
+        Self {
+            security,
+            runtime,
+            processes: Some(processes),
+        }
+    }
+
+    /// Validate `workdir` and turn it into a `cd` prefix relative to the
+    /// workspace, so it works inside container runtimes as well.
+    fn workdir_prefix(&self, workdir: &str) -> Result<String, String> {
+        if !self.security.is_path_allowed(workdir) || Path::new(workdir).is_absolute() {
+            return Err(format!("Working directory not allowed: {workdir}"));
+        }
+        let resolved = self
+            .security
+            .workspace_dir
+            .join(workdir)
+            .canonicalize()
+            .map_err(|e| format!("Failed to resolve working directory: {e}"))?;
+        if !self.security.is_resolved_path_allowed(&resolved) || !resolved.is_dir() {
+            return Err(format!("Working directory not allowed: {workdir}"));
+        }
+        Ok(format!(
+            "cd '{}' || exit 1; ",
+            workdir.replace('\'', "'\\''")
+        ))
+    }
+}
+
+fn parse_env(args: &serde_json::Value) -> Result<Vec<(String, String)>, String> {
+    let Some(env) = args.get("env") else {
+        return Ok(Vec::new());
+    };
+    let Some(map) = env.as_object() else {
+        return Err("'env' must be an object of string values".into());
+    };
+
+    let mut vars = Vec::with_capacity(map.len());
+    for (key, value) in map {
+        if !OVERRIDABLE_ENV_VARS.contains(&key.as_str()) {
+            return Err(format!(
+                "Environment variable not allowed: {key} (allowed: {})",
+                OVERRIDABLE_ENV_VARS.join(", ")
+            ));
+        }
+        let Some(value) = value.as_str() else {
+            return Err(format!("Environment variable {key} must be a string"));
+        };
+        vars.push((key.clone(), value.to_string()));
     }
+    Ok(vars)
 }
 
 #[async_trait]
@@ -35,7 +122,7 @@ impl Tool for ShellTool {
     }
 
     fn description(&self) -> &str {
-        "Execute a shell command in the workspace directory"
+        "Execute a shell command in the workspace directory. Set background=true for long-running commands (builds, dev servers, REPLs) and manage them with the process tool."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -50,6 +137,20 @@ impl Tool for ShellTool {
                     "type": "boolean",
                     "description": "Set true to explicitly approve medium/high-risk commands in supervised mode",
                     "default": false
+                },
+                "workdir": {
+                    "type": "string",
+                    "description": "Working directory relative to the workspace (default: workspace root)"
+                },
+                "env": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" },
+                    "description": "Extra environment variables for the command (LANG, TZ, NODE_ENV, RUST_LOG, RUST_BACKTRACE, DEBUG, CI, PORT, ...)"
+                },
+                "background": {
+                    "type": "boolean",
+                    "description": "Start the command in the background and return a process id instead of waiting",
+                    "default": false
                 }
             },
             "required": ["command"]
@@ -65,6 +166,40 @@ impl Tool for ShellTool {
             .get("approved")
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
+        let background = args
+            .get("background")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        let env = match parse_env(&args) {
+            Ok(env) => env,
+            Err(reason) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(reason),
+                });
+            }
+        };
+        let workdir_prefix = match args.get("workdir").and_then(|v| v.as_str()) {
+            Some(workdir) => match self.workdir_prefix(workdir) {
+                Ok(prefix) => prefix,
+                Err(reason) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(reason),
+                    });
+                }
+            },
+            None => String::new(),
+        };
+        if background && self.processes.is_none() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Background processes are not enabled for this agent".into()),
+            });
+        }
 
         if self.security.is_rate_limited() {
             return Ok(ToolResult {
@@ -96,9 +231,13 @@ impl Tool for ShellTool {
         // Execute with timeout to prevent hanging commands.
         // Clear the environment to prevent leaking API keys and other secrets
         // (CWE-200), then re-add only safe, functional variables.
+        let mut script = format!("{workdir_prefix}{command}");
+        if let (true, Some(processes)) = (background, &self.processes) {
+            script = processes.limited_command(&script);
+        }
         let mut cmd = match self
             .runtime
-            .build_shell_command(command, &self.security.workspace_dir)
+            .build_shell_command(&script, &self.security.workspace_dir)
         {
             Ok(cmd) => cmd,
             Err(e) => {
@@ -116,6 +255,25 @@ impl Tool for ShellTool {
                 cmd.env(var, val);
             }
         }
+        cmd.envs(env);
+
+        if let (true, Some(processes)) = (background, &self.processes) {
+            let owner = current_process_owner();
+            return Ok(match processes.spawn(&owner, command, cmd).await {
+                Ok(id) => ToolResult {
+                    success: true,
+                    output: format!(
+                        "Started background process {id}. Use the process tool to poll output, write stdin or kill it."
+                    ),
+                    error: None,
+                },
+                Err(e) => ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to start background process: {e}")),
+                },
+            });
+        }
 
         let result =
             tokio::time::timeout(Duration::from_secs(SHELL_TIMEOUT_SECS), cmd.output()).await;
@@ -423,4 +581,123 @@ mod tests {
         assert!(!result.success);
         assert!(result.error.as_deref().unwrap_or("").contains("Rate limit"));
     }
+
+    #[tokio::test]
+    async fn shell_runs_in_workdir_with_extra_env() {
+        let dir = std::env::temp_dir().join("zeroclaw_test_shell_workdir");
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(dir.join("sub")).await.unwrap();
+        tokio::fs::write(dir.join("sub/marker.txt"), "")
+            .await
+            .unwrap();
+
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: dir.clone(),
+            ..SecurityPolicy::default()
+        });
+        let tool = ShellTool::new(security, test_runtime());
+        let result = tool
+            .execute(json!({"command": "ls", "workdir": "sub"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("marker.txt"));
+
+        let result = tool
+            .execute(json!({"command": "echo $NODE_ENV", "env": {"NODE_ENV": "test"}}))
+            .await
+            .unwrap();
+        assert_eq!(result.output.trim(), "test");
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn shell_rejects_bad_workdir_and_env() {
+        let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime());
+        let result = tool
+            .execute(json!({"command": "ls", "workdir": "../.."}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("not allowed"));
+
+        let result = tool
+            .execute(json!({"command": "ls", "env": {"LD_PRELOAD": "/tmp/x.so"}}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("LD_PRELOAD"));
+    }
+
+    #[test]
+    fn env_overrides_that_change_what_runs_are_rejected() {
+        for key in [
+            "PATH",
+            "HOME",
+            "SHELL",
+            "GIT_PAGER",
+            "GIT_EXTERNAL_DIFF",
+            "GIT_SSH_COMMAND",
+            "GIT_CONFIG_GLOBAL",
+            "PAGER",
+            "MANPAGER",
+            "EDITOR",
+            "VISUAL",
+            "GIT_EDITOR",
+            "BASH_ENV",
+            "IFS",
+            "LD_PRELOAD",
+            "DYLD_INSERT_LIBRARIES",
+            "NODE_OPTIONS",
+            "PYTHONPATH",
+        ] {
+            let err = parse_env(&json!({"env": {key: "./x"}})).unwrap_err();
+            assert!(err.contains(key), "{key}: {err}");
+        }
+        assert_eq!(
+            parse_env(&json!({"env": {"RUST_LOG": "debug", "TZ": "UTC"}})).unwrap(),
+            vec![
+                ("RUST_LOG".to_string(), "debug".to_string()),
+                ("TZ".to_string(), "UTC".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn shell_background_requires_process_manager() {
+        let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime());
+        let result = tool
+            .execute(json!({"command": "echo hi", "background": true}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("not enabled"));
+    }
+
+    #[tokio::test]
+    async fn shell_background_registers_process() {
+        let processes = Arc::new(ProcessManager::default());
+        let tool = ShellTool::with_processes(
+            test_security(AutonomyLevel::Supervised),
+            test_runtime(),
+            processes.clone(),
+        );
+        let result = tool
+            .execute(json!({"command": "echo started", "background": true}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("Started background process p1"));
+
+        let process_tool =
+            crate::tools::ProcessTool::new(test_security(AutonomyLevel::Supervised), processes);
+        let listed = process_tool
+            .execute(json!({"action": "list"}))
+            .await
+            .unwrap();
+        assert!(listed.output.contains("p1"));
+        assert!(listed.output.contains("echo started"));
+    }
 }
@@ -0,0 +1,388 @@
+//! Sandboxed directory traversal shared by the `glob`, `content_search` and
+//! `list_dir` tools.
+//!
+//! The walker never follows symlinks, always skips `.git`, drops entries that
+//! fall under `forbidden_paths`, and can optionally honor `.gitignore` files.
+
+use crate::security::SecurityPolicy;
+use glob::{MatchOptions, Pattern};
+use std::path::{Path, PathBuf};
+
+/// Hard cap on directory entries visited by a single walk, regardless of how
+/// many results the caller keeps.
+pub(super) const MAX_VISITED_ENTRIES: usize = 50_000;
+
+/// Glob options used for both `.gitignore` rules and user-supplied patterns:
+/// `*` stays within one path component, `**` crosses directories.
+pub(super) const GLOB_MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// A file or directory reached by the walk.
+#[derive(Debug, Clone)]
+pub(super) struct WalkEntry {
+    /// Path relative to the workspace root, using `/` separators.
+    pub relative: String,
+    /// Absolute path on disk.
+    pub absolute: PathBuf,
+    pub is_dir: bool,
+    pub depth: usize,
+}
+
+/// Validate a user-supplied directory and resolve it inside the workspace.
+///
+/// Returns the canonical workspace root and the canonical search root.
+pub(super) fn resolve_search_root(
+    security: &SecurityPolicy,
+    path: &str,
+) -> Result<(PathBuf, PathBuf), String> {
+    if !security.is_path_allowed(path) {
+        return Err(format!("Path not allowed by security policy: {path}"));
+    }
+
+    let workspace_root = security
+        .workspace_dir
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve workspace directory: {e}"))?;
+    let resolved = workspace_root
+        .join(path)
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve path: {e}"))?;
+
+    if !security.is_resolved_path_allowed(&resolved) {
+        return Err(format!(
+            "Resolved path escapes workspace: {}",
+            resolved.display()
+        ));
+    }
+    if !resolved.is_dir() {
+        return Err(format!("Not a directory: {path}"));
+    }
+
+    Ok((workspace_root, resolved))
+}
+
+/// One parsed `.gitignore` line.
+#[derive(Debug)]
+struct IgnoreRule {
+    pattern: Pattern,
+    /// Directory containing the `.gitignore`, relative to the workspace root.
+    base: String,
+    /// Pattern contains a `/`, so it matches relative to `base` only.
+    anchored: bool,
+    dir_only: bool,
+    negated: bool,
+}
+
+impl IgnoreRule {
+    fn parse(line: &str, base: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let line = line.trim_start_matches('/');
+        if line.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            pattern: Pattern::new(line).ok()?,
+            base: base.to_string(),
+            anchored,
+            dir_only,
+            negated,
+        })
+    }
+
+    fn matches(&self, relative: &str, name: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let scoped = if self.base.is_empty() {
+            relative
+        } else {
+            match relative
+                .strip_prefix(self.base.as_str())
+                .and_then(|rest| rest.strip_prefix('/'))
+            {
+                Some(rest) => rest,
+                None => return false,
+            }
+        };
+
+        if self.anchored {
+            self.pattern.matches_with(scoped, GLOB_MATCH_OPTIONS)
+        } else {
+            self.pattern.matches_with(name, GLOB_MATCH_OPTIONS)
+        }
+    }
+}
+
+fn load_gitignore(dir: &Path, base: &str, rules: &mut Vec<IgnoreRule>) {
+    if let Ok(contents) = std::fs::read_to_string(dir.join(".gitignore")) {
+        rules.extend(
+            contents
+                .lines()
+                .filter_map(|line| IgnoreRule::parse(line, base)),
+        );
+    }
+}
+
+/// Last matching rule wins, as in git.
+fn is_ignored(rules: &[IgnoreRule], relative: &str, name: &str, is_dir: bool) -> bool {
+    rules
+        .iter()
+        .rev()
+        .find(|rule| rule.matches(relative, name, is_dir))
+        .is_some_and(|rule| !rule.negated)
+}
+
+fn relative_string(workspace_root: &Path, path: &Path) -> String {
+    path.strip_prefix(workspace_root)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Walk `start` depth-first in sorted order, calling `visit` for each entry.
+///
+/// `visit` returns `false` to stop the walk early. Directories deeper than
+/// `max_depth` (1 = direct children only) are not descended into.
+pub(super) fn walk(
+    security: &SecurityPolicy,
+    workspace_root: &Path,
+    start: &Path,
+    max_depth: usize,
+    respect_gitignore: bool,
+    mut visit: impl FnMut(&WalkEntry) -> bool,
+) {
+    // Absolute forbidden paths are only meaningful when the workspace itself
+    // is not under one of them (e.g. a workspace inside a forbidden `/root`).
+    let check_absolute = !security.is_forbidden_path(workspace_root);
+
+    let mut rules = Vec::new();
+    if respect_gitignore {
+        // Pick up ignore files from the workspace root down to `start`.
+        let mut dir = workspace_root.to_path_buf();
+        load_gitignore(&dir, "", &mut rules);
+        if let Ok(rest) = start.strip_prefix(workspace_root) {
+            for component in rest.components() {
+                dir.push(component);
+                load_gitignore(&dir, &relative_string(workspace_root, &dir), &mut rules);
+            }
+        }
+    }
+
+    let mut visited = 0;
+    walk_dir(
+        security,
+        workspace_root,
+        start,
+        1,
+        max_depth,
+        check_absolute,
+        respect_gitignore,
+        &mut rules,
+        &mut visited,
+        &mut visit,
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn walk_dir(
+    security: &SecurityPolicy,
+    workspace_root: &Path,
+    dir: &Path,
+    depth: usize,
+    max_depth: usize,
+    check_absolute: bool,
+    respect_gitignore: bool,
+    rules: &mut Vec<IgnoreRule>,
+    visited: &mut usize,
+    visit: &mut impl FnMut(&WalkEntry) -> bool,
+) -> bool {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return true;
+    };
+    let mut children: Vec<_> = read_dir.filter_map(Result::ok).collect();
+    children.sort_by_key(std::fs::DirEntry::file_name);
+
+    for child in children {
+        *visited += 1;
+        if *visited > MAX_VISITED_ENTRIES {
+            return false;
+        }
+
+        let name = child.file_name().to_string_lossy().to_string();
+        if name == ".git" {
+            continue;
+        }
+        // `DirEntry::file_type` does not follow symlinks.
+        let Ok(file_type) = child.file_type() else {
+            continue;
+        };
+        if file_type.is_symlink() {
+            continue;
+        }
+
+        let absolute = child.path();
+        let relative = relative_string(workspace_root, &absolute);
+        if !security.is_path_allowed(&relative)
+            || (check_absolute && security.is_forbidden_path(&absolute))
+        {
+            continue;
+        }
+
+        let is_dir = file_type.is_dir();
+        if respect_gitignore && is_ignored(rules, &relative, &name, is_dir) {
+            continue;
+        }
+
+        let entry = WalkEntry {
+            relative,
+            absolute,
+            is_dir,
+            depth,
+        };
+        if !visit(&entry) {
+            return false;
+        }
+
+        if is_dir && depth < max_depth {
+            let rules_before = rules.len();
+            if respect_gitignore {
+                load_gitignore(&entry.absolute, &entry.relative, rules);
+            }
+            let keep_going = walk_dir(
+                security,
+                workspace_root,
+                &entry.absolute,
+                depth + 1,
+                max_depth,
+                check_absolute,
+                respect_gitignore,
+                rules,
+                visited,
+                visit,
+            );
+            rules.truncate(rules_before);
+            if !keep_going {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::{AutonomyLevel, SecurityPolicy};
+
+    fn test_security(workspace: PathBuf) -> SecurityPolicy {
+        SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        }
+    }
+
+    fn collect(security: &SecurityPolicy, respect_gitignore: bool) -> Vec<String> {
+        let (root, start) = resolve_search_root(security, ".").unwrap();
+        let mut seen = Vec::new();
+        walk(
+            security,
+            &root,
+            &start,
+            usize::MAX,
+            respect_gitignore,
+            |entry| {
+                seen.push(entry.relative.clone());
+                true
+            },
+        );
+        seen
+    }
+
+    #[test]
+    fn gitignore_rule_parsing() {
+        let rule = IgnoreRule::parse("/target/", "").unwrap();
+        assert!(rule.anchored && rule.dir_only && !rule.negated);
+        assert!(rule.matches("target", "target", true));
+        assert!(!rule.matches("sub/target", "target", true));
+
+        let rule = IgnoreRule::parse("*.log", "sub").unwrap();
+        assert!(rule.matches("sub/a/b.log", "b.log", false));
+        assert!(!rule.matches("other/b.log", "b.log", false));
+
+        assert!(IgnoreRule::parse("# comment", "").is_none());
+        assert!(IgnoreRule::parse("!keep.log", "").unwrap().negated);
+    }
+
+    #[test]
+    fn walk_honors_gitignore_and_skips_git_dir() {
+        let dir = std::env::temp_dir().join("zeroclaw_test_workspace_walk_ignore");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::create_dir_all(dir.join("target/debug")).unwrap();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join(".gitignore"), "target/\n*.log\n!keep.log\n").unwrap();
+        std::fs::write(dir.join(".git/HEAD"), "ref").unwrap();
+        std::fs::write(dir.join("target/debug/out"), "bin").unwrap();
+        std::fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::write(dir.join("src/debug.log"), "noise").unwrap();
+        std::fs::write(dir.join("src/keep.log"), "signal").unwrap();
+
+        let security = test_security(dir.clone());
+        let seen = collect(&security, true);
+        assert_eq!(
+            seen,
+            vec![".gitignore", "src", "src/keep.log", "src/main.rs"]
+        );
+
+        let all = collect(&security, false);
+        assert!(all.contains(&"target/debug/out".to_string()));
+        assert!(!all.iter().any(|p| p.starts_with(".git/")));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn walk_skips_forbidden_paths() {
+        let dir = std::env::temp_dir().join("zeroclaw_test_workspace_walk_forbidden");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("secrets")).unwrap();
+        std::fs::write(dir.join("secrets/key"), "k").unwrap();
+        std::fs::write(dir.join("notes.md"), "n").unwrap();
+
+        let security = SecurityPolicy {
+            forbidden_paths: vec!["secrets".into()],
+            ..test_security(dir.clone())
+        };
+        assert_eq!(collect(&security, false), vec!["notes.md"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn resolve_search_root_rejects_traversal() {
+        let security = test_security(std::env::temp_dir());
+        let err = resolve_search_root(&security, "../..").unwrap_err();
+        assert!(err.contains("not allowed"));
+    }
+}
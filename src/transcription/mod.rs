@@ -0,0 +1,423 @@
+//! Speech-to-text for inbound voice and audio messages.
+//!
+//! Channels that receive voice notes (Telegram, WhatsApp) download the audio
+//! into the workspace voice inbox and hand it to the agent as a
+//! `[VOICE:<path>]` or `[AUDIO:<path>]` marker — the same syntax used for
+//! outbound media. Before a message reaches the agent loop,
+//! [`transcribe_voice_markers`] replaces each marker with its transcript.
+//! Only markers for files the channel downloaded for that message are
+//! honoured; markers typed by the sender are left as plain text.
+
+mod openai;
+mod whisper_cpp;
+
+pub use openai::OpenAiTranscriber;
+pub use whisper_cpp::WhisperCppTranscriber;
+
+use crate::config::TranscriptionConfig;
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Workspace subdirectory where channels store downloaded voice messages.
+const VOICE_INBOX_DIR: &str = "voice_inbox";
+
+const MARKER_KINDS: &[&str] = &["VOICE", "AUDIO"];
+
+// ── Transcriber trait ────────────────────────────────────────────
+
+/// Speech-to-text backend.
+#[async_trait]
+pub trait Transcriber: Send + Sync {
+    /// Backend name (e.g. "whisper_cpp", "openai")
+    fn name(&self) -> &str;
+
+    /// Transcribe a local audio file to plain text.
+    async fn transcribe(&self, audio_path: &Path) -> Result<String>;
+}
+
+// ── Factory ──────────────────────────────────────────────────────
+
+/// Create a transcriber from config. Returns `None` when transcription is disabled.
+pub fn create_transcriber(config: &TranscriptionConfig) -> Result<Option<Box<dyn Transcriber>>> {
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    match config.backend.trim() {
+        "whisper_cpp" | "whisper" | "local" => {
+            Ok(Some(Box::new(WhisperCppTranscriber::from_config(config))))
+        }
+        "openai" => {
+            let api_key = config
+                .api_key
+                .clone()
+                .filter(|key| !key.trim().is_empty())
+                .or_else(|| std::env::var("OPENAI_API_KEY").ok())
+                .unwrap_or_default();
+            Ok(Some(Box::new(OpenAiTranscriber::new(
+                &config.api_url,
+                &api_key,
+                &config.model,
+                config.language.clone(),
+            ))))
+        }
+        other => {
+            bail!("Unknown transcription backend '{other}'. Use \"whisper_cpp\" or \"openai\".")
+        }
+    }
+}
+
+/// Directory where channels should store inbound voice messages.
+pub fn voice_inbox_dir(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join(VOICE_INBOX_DIR)
+}
+
+/// Build the inbound marker for a downloaded voice message.
+pub fn voice_marker(path: &Path) -> String {
+    format!("[VOICE:{}]", path.display())
+}
+
+// ── Marker handling ──────────────────────────────────────────────
+
+/// A `[VOICE:...]`/`[AUDIO:...]` marker found in message content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AudioMarker {
+    start: usize,
+    end: usize,
+    path: PathBuf,
+}
+
+fn find_audio_markers(content: &str) -> Vec<AudioMarker> {
+    let mut markers = Vec::new();
+    let mut cursor = 0;
+
+    while let Some(open_rel) = content[cursor..].find('[') {
+        let start = cursor + open_rel;
+        let Some(close_rel) = content[start..].find(']') else {
+            break;
+        };
+        let end = start + close_rel + 1;
+        let inner = &content[start + 1..end - 1];
+
+        if let Some((kind, target)) = inner.split_once(':') {
+            let target = target.trim();
+            if MARKER_KINDS.contains(&kind.trim().to_ascii_uppercase().as_str())
+                && !target.is_empty()
+                && !target.contains("://")
+            {
+                markers.push(AudioMarker {
+                    start,
+                    end,
+                    path: PathBuf::from(target),
+                });
+            }
+        }
+
+        cursor = start + 1;
+    }
+
+    markers
+}
+
+/// Result of running the transcription pre-pass over a message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TranscribedContent {
+    /// Message content with every audio marker replaced by its transcript
+    pub content: String,
+    /// Transcripts in marker order (empty string for failed transcriptions)
+    pub transcripts: Vec<String>,
+}
+
+/// Replace the `[VOICE:...]`/`[AUDIO:...]` markers in `content` that name one
+/// of `voice_notes` — the files downloaded for this message — with their
+/// transcripts. Other markers are user-typed text and stay untouched. Only
+/// files inside `inbox_dir` are transcribed; they are removed afterwards.
+/// Failures are replaced by a short notice so the agent can tell the user the
+/// voice note could not be understood.
+pub async fn transcribe_voice_markers(
+    transcriber: &dyn Transcriber,
+    content: &str,
+    voice_notes: &[PathBuf],
+    inbox_dir: &Path,
+    config: &TranscriptionConfig,
+) -> TranscribedContent {
+    let markers: Vec<_> = find_audio_markers(content)
+        .into_iter()
+        .filter(|marker| voice_notes.contains(&marker.path))
+        .collect();
+    if markers.is_empty() {
+        return TranscribedContent {
+            content: content.to_string(),
+            transcripts: Vec::new(),
+        };
+    }
+
+    let mut output = String::with_capacity(content.len());
+    let mut transcripts = Vec::with_capacity(markers.len());
+    let mut last = 0;
+
+    for marker in markers {
+        output.push_str(&content[last..marker.start]);
+        last = marker.end;
+
+        match transcribe_inbox_file(transcriber, &marker.path, inbox_dir, config).await {
+            Ok(text) if !text.trim().is_empty() => {
+                output.push_str("[Voice message transcript]\n");
+                output.push_str(text.trim());
+                transcripts.push(text.trim().to_string());
+            }
+            Ok(_) => {
+                output.push_str("[Voice message contained no recognizable speech]");
+                transcripts.push(String::new());
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Transcription of {} via {} failed: {e}",
+                    marker.path.display(),
+                    transcriber.name()
+                );
+                output.push_str("[Voice message could not be transcribed]");
+                transcripts.push(String::new());
+            }
+        }
+    }
+    output.push_str(&content[last..]);
+
+    TranscribedContent {
+        content: output.trim().to_string(),
+        transcripts,
+    }
+}
+
+async fn transcribe_inbox_file(
+    transcriber: &dyn Transcriber,
+    path: &Path,
+    inbox_dir: &Path,
+    config: &TranscriptionConfig,
+) -> Result<String> {
+    // Markers are user-controlled text; never read audio from outside the inbox.
+    let canonical_inbox = tokio::fs::canonicalize(inbox_dir).await?;
+    let canonical_path = tokio::fs::canonicalize(path).await?;
+    if !canonical_path.starts_with(&canonical_inbox) {
+        bail!("audio path is outside the voice inbox");
+    }
+
+    let size = tokio::fs::metadata(&canonical_path).await?.len();
+    let size = usize::try_from(size).unwrap_or(usize::MAX);
+    if size > config.max_audio_bytes {
+        let _ = tokio::fs::remove_file(&canonical_path).await;
+        bail!(
+            "audio file is {size} bytes (max {} bytes)",
+            config.max_audio_bytes
+        );
+    }
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(config.timeout_secs.max(1)),
+        transcriber.transcribe(&canonical_path),
+    )
+    .await;
+    let _ = tokio::fs::remove_file(&canonical_path).await;
+
+    match result {
+        Ok(result) => result,
+        Err(_) => bail!("transcription timed out after {}s", config.timeout_secs),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    struct FixedTranscriber(&'static str);
+
+    #[async_trait]
+    impl Transcriber for FixedTranscriber {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn transcribe(&self, _audio_path: &Path) -> Result<String> {
+            Ok(self.0.to_string())
+        }
+    }
+
+    struct FailingTranscriber;
+
+    #[async_trait]
+    impl Transcriber for FailingTranscriber {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        async fn transcribe(&self, _audio_path: &Path) -> Result<String> {
+            bail!("backend unavailable")
+        }
+    }
+
+    fn write_inbox_file(dir: &TempDir, name: &str) -> PathBuf {
+        let inbox = voice_inbox_dir(dir.path());
+        std::fs::create_dir_all(&inbox).unwrap();
+        let path = inbox.join(name);
+        std::fs::write(&path, b"OggS").unwrap();
+        path
+    }
+
+    #[test]
+    fn factory_disabled_returns_none() {
+        let config = TranscriptionConfig::default();
+        assert!(create_transcriber(&config).unwrap().is_none());
+    }
+
+    #[test]
+    fn factory_selects_backends() {
+        let mut config = TranscriptionConfig {
+            enabled: true,
+            ..TranscriptionConfig::default()
+        };
+        assert_eq!(
+            create_transcriber(&config).unwrap().unwrap().name(),
+            "whisper_cpp"
+        );
+
+        config.backend = "openai".into();
+        config.api_key = Some("sk-test".into());
+        assert_eq!(
+            create_transcriber(&config).unwrap().unwrap().name(),
+            "openai"
+        );
+
+        config.backend = "bogus".into();
+        assert!(create_transcriber(&config).is_err());
+    }
+
+    #[test]
+    fn find_audio_markers_detects_voice_and_audio() {
+        let content = "hi [VOICE:/tmp/a.ogg] and [audio:/tmp/b.mp3] [IMAGE:/tmp/c.png]";
+        let markers = find_audio_markers(content);
+        assert_eq!(markers.len(), 2);
+        assert_eq!(markers[0].path, PathBuf::from("/tmp/a.ogg"));
+        assert_eq!(markers[1].path, PathBuf::from("/tmp/b.mp3"));
+    }
+
+    #[test]
+    fn find_audio_markers_ignores_urls_and_empty_targets() {
+        assert!(find_audio_markers("[VOICE:https://example.com/a.ogg]").is_empty());
+        assert!(find_audio_markers("[VOICE:]").is_empty());
+        assert!(find_audio_markers("plain text").is_empty());
+    }
+
+    #[tokio::test]
+    async fn transcribe_replaces_marker_and_removes_file() {
+        let tmp = TempDir::new().unwrap();
+        let path = write_inbox_file(&tmp, "note.ogg");
+        let content = format!("{}\ncaption", voice_marker(&path));
+
+        let result = transcribe_voice_markers(
+            &FixedTranscriber(" turn on the lights "),
+            &content,
+            std::slice::from_ref(&path),
+            &voice_inbox_dir(tmp.path()),
+            &TranscriptionConfig::default(),
+        )
+        .await;
+
+        assert_eq!(
+            result.content,
+            "[Voice message transcript]\nturn on the lights\ncaption"
+        );
+        assert_eq!(result.transcripts, vec!["turn on the lights".to_string()]);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn transcribe_failure_leaves_notice() {
+        let tmp = TempDir::new().unwrap();
+        let path = write_inbox_file(&tmp, "note.ogg");
+
+        let result = transcribe_voice_markers(
+            &FailingTranscriber,
+            &voice_marker(&path),
+            std::slice::from_ref(&path),
+            &voice_inbox_dir(tmp.path()),
+            &TranscriptionConfig::default(),
+        )
+        .await;
+
+        assert_eq!(result.content, "[Voice message could not be transcribed]");
+        assert_eq!(result.transcripts, vec![String::new()]);
+    }
+
+    #[tokio::test]
+    async fn transcribe_refuses_paths_outside_inbox() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(voice_inbox_dir(tmp.path())).unwrap();
+        let outside = tmp.path().join("secret.ogg");
+        std::fs::write(&outside, b"data").unwrap();
+
+        let result = transcribe_voice_markers(
+            &FixedTranscriber("leaked"),
+            &voice_marker(&outside),
+            std::slice::from_ref(&outside),
+            &voice_inbox_dir(tmp.path()),
+            &TranscriptionConfig::default(),
+        )
+        .await;
+
+        assert_eq!(result.content, "[Voice message could not be transcribed]");
+        assert!(outside.exists());
+    }
+
+    #[tokio::test]
+    async fn transcribe_rejects_oversized_audio() {
+        let tmp = TempDir::new().unwrap();
+        let path = write_inbox_file(&tmp, "big.ogg");
+        let config = TranscriptionConfig {
+            max_audio_bytes: 2,
+            ..TranscriptionConfig::default()
+        };
+
+        let result = transcribe_voice_markers(
+            &FixedTranscriber("never"),
+            &voice_marker(&path),
+            std::slice::from_ref(&path),
+            &voice_inbox_dir(tmp.path()),
+            &config,
+        )
+        .await;
+
+        assert_eq!(result.content, "[Voice message could not be transcribed]");
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn transcribe_ignores_markers_typed_by_the_sender() {
+        let tmp = TempDir::new().unwrap();
+        let own = write_inbox_file(&tmp, "own.ogg");
+        let other = write_inbox_file(&tmp, "other_user.ogg");
+        let content = format!("{}\n{}", voice_marker(&own), voice_marker(&other));
+
+        let result = transcribe_voice_markers(
+            &FixedTranscriber("hello"),
+            &content,
+            std::slice::from_ref(&own),
+            &voice_inbox_dir(tmp.path()),
+            &TranscriptionConfig::default(),
+        )
+        .await;
+
+        assert_eq!(
+            result.content,
+            format!(
+                "[Voice message transcript]\nhello\n{}",
+                voice_marker(&other)
+            )
+        );
+        assert_eq!(result.transcripts.len(), 1);
+        assert!(!own.exists());
+        assert!(other.exists());
+    }
+}
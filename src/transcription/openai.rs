@@ -0,0 +1,147 @@
+use super::Transcriber;
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use std::path::Path;
+
+/// OpenAI-compatible `/audio/transcriptions` backend (OpenAI, Groq, LocalAI, faster-whisper-server, ...).
+pub struct OpenAiTranscriber {
+    base_url: String,
+    api_key: String,
+    model: String,
+    language: Option<String>,
+}
+
+impl OpenAiTranscriber {
+    pub fn new(base_url: &str, api_key: &str, model: &str, language: Option<String>) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            model: model.to_string(),
+            language,
+        }
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client("provider.transcription")
+    }
+
+    fn transcriptions_url(&self) -> String {
+        if self.base_url.ends_with("/audio/transcriptions") {
+            self.base_url.clone()
+        } else {
+            format!("{}/audio/transcriptions", self.base_url)
+        }
+    }
+
+    fn mime_type_for(path: &Path) -> &'static str {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("ogg" | "oga" | "opus") => "audio/ogg",
+            Some("mp3") => "audio/mpeg",
+            Some("m4a" | "mp4") => "audio/mp4",
+            Some("wav") => "audio/wav",
+            Some("webm") => "audio/webm",
+            Some("flac") => "audio/flac",
+            _ => "application/octet-stream",
+        }
+    }
+}
+
+#[async_trait]
+impl Transcriber for OpenAiTranscriber {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    async fn transcribe(&self, audio_path: &Path) -> Result<String> {
+        let file_name = audio_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("audio.ogg")
+            .to_string();
+        let bytes = tokio::fs::read(audio_path).await?;
+        let part = Part::bytes(bytes)
+            .file_name(file_name)
+            .mime_str(Self::mime_type_for(audio_path))?;
+
+        let mut form = Form::new()
+            .part("file", part)
+            .text("model", self.model.clone())
+            .text("response_format", "json");
+        if let Some(language) = self.language.as_deref().filter(|l| !l.is_empty()) {
+            form = form.text("language", language.to_string());
+        }
+
+        let mut request = self.http_client().post(self.transcriptions_url());
+        if !self.api_key.is_empty() {
+            request = request.bearer_auth(&self.api_key);
+        }
+        let resp = request.multipart(form).send().await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            bail!(
+                "Transcription API error {status}: {}",
+                crate::providers::sanitize_api_error(&text)
+            );
+        }
+
+        let json: serde_json::Value = resp.json().await?;
+        let text = json
+            .get("text")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("Invalid transcription response: missing 'text'"))?;
+
+        Ok(text.trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transcriptions_url_appends_endpoint() {
+        let t = OpenAiTranscriber::new("https://api.openai.com/v1/", "k", "whisper-1", None);
+        assert_eq!(
+            t.transcriptions_url(),
+            "https://api.openai.com/v1/audio/transcriptions"
+        );
+    }
+
+    #[test]
+    fn transcriptions_url_keeps_explicit_endpoint() {
+        let t = OpenAiTranscriber::new(
+            "http://localhost:8000/v1/audio/transcriptions",
+            "",
+            "whisper-1",
+            None,
+        );
+        assert_eq!(
+            t.transcriptions_url(),
+            "http://localhost:8000/v1/audio/transcriptions"
+        );
+    }
+
+    #[test]
+    fn mime_type_for_common_voice_formats() {
+        assert_eq!(
+            OpenAiTranscriber::mime_type_for(Path::new("a.oga")),
+            "audio/ogg"
+        );
+        assert_eq!(
+            OpenAiTranscriber::mime_type_for(Path::new("a.MP3")),
+            "audio/mpeg"
+        );
+        assert_eq!(
+            OpenAiTranscriber::mime_type_for(Path::new("a.bin")),
+            "application/octet-stream"
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use super::Transcriber;
+use crate::config::TranscriptionConfig;
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Local whisper.cpp backend.
+///
+/// Voice notes usually arrive as OGG/Opus, which whisper.cpp cannot read, so
+/// non-WAV input is first converted to 16 kHz mono PCM with ffmpeg.
+pub struct WhisperCppTranscriber {
+    whisper_bin: String,
+    model_path: PathBuf,
+    ffmpeg_bin: String,
+    language: Option<String>,
+}
+
+impl WhisperCppTranscriber {
+    pub fn new(
+        whisper_bin: &str,
+        model_path: &str,
+        ffmpeg_bin: &str,
+        language: Option<String>,
+    ) -> Self {
+        Self {
+            whisper_bin: whisper_bin.to_string(),
+            model_path: PathBuf::from(shellexpand::tilde(model_path).to_string()),
+            ffmpeg_bin: ffmpeg_bin.to_string(),
+            language,
+        }
+    }
+
+    pub fn from_config(config: &TranscriptionConfig) -> Self {
+        Self::new(
+            &config.whisper_bin,
+            &config.whisper_model_path,
+            &config.ffmpeg_bin,
+            config.language.clone(),
+        )
+    }
+
+    fn is_wav(path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"))
+    }
+
+    async fn convert_to_wav(&self, input: &Path) -> Result<PathBuf> {
+        let output_path = input.with_extension("16k.wav");
+        let output = Command::new(&self.ffmpeg_bin)
+            .arg("-y")
+            .arg("-loglevel")
+            .arg("error")
+            .arg("-i")
+            .arg(input)
+            .args(["-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le"])
+            .arg(&output_path)
+            .kill_on_drop(true)
+            .output()
+            .await
+            .with_context(|| format!("Failed to run ffmpeg ({})", self.ffmpeg_bin))?;
+
+        if !output.status.success() {
+            let _ = tokio::fs::remove_file(&output_path).await;
+            bail!(
+                "ffmpeg conversion failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(output_path)
+    }
+
+    fn build_args(&self, wav_path: &Path) -> Vec<String> {
+        let mut args = vec![
+            "-m".to_string(),
+            self.model_path.display().to_string(),
+            "-f".to_string(),
+            wav_path.display().to_string(),
+            "--no-timestamps".to_string(),
+            "--no-prints".to_string(),
+        ];
+        if let Some(language) = self.language.as_deref().filter(|l| !l.is_empty()) {
+            args.push("-l".to_string());
+            args.push(language.to_string());
+        }
+        args
+    }
+}
+
+#[async_trait]
+impl Transcriber for WhisperCppTranscriber {
+    fn name(&self) -> &str {
+        "whisper_cpp"
+    }
+
+    async fn transcribe(&self, audio_path: &Path) -> Result<String> {
+        if !self.model_path.exists() {
+            bail!(
+                "whisper.cpp model not found at {}",
+                self.model_path.display()
+            );
+        }
+
+        let converted = if Self::is_wav(audio_path) {
+            None
+        } else {
+            Some(self.convert_to_wav(audio_path).await?)
+        };
+        let wav_path = converted.as_deref().unwrap_or(audio_path);
+
+        let result = Command::new(&self.whisper_bin)
+            .args(self.build_args(wav_path))
+            .kill_on_drop(true)
+            .output()
+            .await;
+
+        if let Some(ref path) = converted {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+
+        let output =
+            result.with_context(|| format!("Failed to run whisper.cpp ({})", self.whisper_bin))?;
+        if !output.status.success() {
+            bail!(
+                "whisper.cpp failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(normalize_transcript(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+}
+
+/// Join whisper.cpp output lines and drop non-speech annotations like `[BLANK_AUDIO]`.
+fn normalize_transcript(raw: &str) -> String {
+    raw.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter(|line| !(line.starts_with('[') && line.ends_with(']')))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_transcript_drops_annotations() {
+        let raw = "\n [BLANK_AUDIO]\n Hello there.\n  How are you?\n";
+        assert_eq!(normalize_transcript(raw), "Hello there. How are you?");
+    }
+
+    #[test]
+    fn build_args_includes_language_hint() {
+        let t = WhisperCppTranscriber::new("whisper-cli", "/models/base.bin", "ffmpeg", None);
+        let args = t.build_args(Path::new("/tmp/a.wav"));
+        assert!(!args.contains(&"-l".to_string()));
+
+        let t = WhisperCppTranscriber::new(
+            "whisper-cli",
+            "/models/base.bin",
+            "ffmpeg",
+            Some("de".into()),
+        );
+        let args = t.build_args(Path::new("/tmp/a.wav"));
+        assert_eq!(&args[args.len() - 2..], ["-l", "de"]);
+    }
+
+    #[test]
+    fn is_wav_matches_extension_case_insensitively() {
+        assert!(WhisperCppTranscriber::is_wav(Path::new("a.WAV")));
+        assert!(!WhisperCppTranscriber::is_wav(Path::new("a.ogg")));
+    }
+
+    #[tokio::test]
+    async fn transcribe_fails_when_model_missing() {
+        let t =
+            WhisperCppTranscriber::new("whisper-cli", "/nonexistent/ggml-base.bin", "ffmpeg", None);
+        let err = t
+            .transcribe(Path::new("/tmp/a.wav"))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("model not found"));
+    }
+}
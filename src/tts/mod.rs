@@ -0,0 +1,457 @@
+//! Text-to-speech for spoken replies.
+//!
+//! Backends render agent replies to OGG/Opus, the format Telegram and
+//! WhatsApp expect for voice notes. Callers fall back to a text reply when
+//! synthesis or delivery fails.
+
+mod openai;
+mod piper;
+
+pub use openai::OpenAiSpeech;
+pub use piper::PiperSpeech;
+
+use crate::channels::Channel;
+use crate::config::{TtsConfig, VoiceReplyMode};
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Workspace subdirectory for synthesized voice replies awaiting delivery.
+const TTS_OUTBOX_DIR: &str = "tts_outbox";
+
+// ── SpeechSynthesizer trait ──────────────────────────────────────
+
+/// Text-to-speech backend.
+#[async_trait]
+pub trait SpeechSynthesizer: Send + Sync {
+    /// Backend name (e.g. "piper", "openai")
+    fn name(&self) -> &str;
+
+    /// Render `text` as an OGG/Opus file at `output_path`.
+    async fn synthesize(&self, text: &str, output_path: &Path) -> Result<()>;
+}
+
+// ── Factory ──────────────────────────────────────────────────────
+
+/// Create a speech synthesizer from config. Returns `None` when TTS is disabled.
+pub fn create_synthesizer(config: &TtsConfig) -> Result<Option<Box<dyn SpeechSynthesizer>>> {
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    match config.backend.trim() {
+        "piper" | "local" => Ok(Some(Box::new(PiperSpeech::from_config(config)))),
+        "openai" => {
+            let api_key = config
+                .api_key
+                .clone()
+                .filter(|key| !key.trim().is_empty())
+                .or_else(|| std::env::var("OPENAI_API_KEY").ok())
+                .unwrap_or_default();
+            Ok(Some(Box::new(OpenAiSpeech::new(
+                &config.api_url,
+                &api_key,
+                &config.model,
+                &config.voice,
+            ))))
+        }
+        other => bail!("Unknown TTS backend '{other}'. Use \"piper\" or \"openai\"."),
+    }
+}
+
+/// Directory where synthesized voice replies are written before sending.
+pub fn tts_outbox_dir(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join(TTS_OUTBOX_DIR)
+}
+
+// ── Per-user reply mode ──────────────────────────────────────────
+
+/// Per-sender voice reply overrides set with the `/voice` command.
+///
+/// Keys are the channel's conversation key (e.g. `telegram_alice`); senders
+/// without an override use `[tts].reply_mode`.
+#[derive(Debug, Default)]
+pub struct VoiceReplyPreferences {
+    overrides: Mutex<HashMap<String, VoiceReplyMode>>,
+}
+
+impl VoiceReplyPreferences {
+    pub fn mode_for(&self, key: &str, default: VoiceReplyMode) -> VoiceReplyMode {
+        self.overrides.lock().get(key).copied().unwrap_or(default)
+    }
+
+    pub fn set(&self, key: &str, mode: VoiceReplyMode, default: VoiceReplyMode) {
+        let mut overrides = self.overrides.lock();
+        if mode == default {
+            overrides.remove(key);
+        } else {
+            overrides.insert(key.to_string(), mode);
+        }
+    }
+}
+
+/// Parse a `/voice` argument into a reply mode.
+pub fn parse_voice_reply_mode(value: &str) -> Option<VoiceReplyMode> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "on" | "always" => Some(VoiceReplyMode::Always),
+        "off" | "text" => Some(VoiceReplyMode::Off),
+        "auto" | "when_voice" => Some(VoiceReplyMode::WhenVoice),
+        _ => None,
+    }
+}
+
+fn voice_reply_mode_label(mode: VoiceReplyMode) -> &'static str {
+    match mode {
+        VoiceReplyMode::Off => "off",
+        VoiceReplyMode::WhenVoice => "auto",
+        VoiceReplyMode::Always => "on",
+    }
+}
+
+/// If `content` is a `/voice [mode]` command, return its (possibly empty) argument.
+pub fn parse_voice_command(content: &str) -> Option<String> {
+    let trimmed = content.trim();
+    let mut parts = trimmed.split_whitespace();
+    let command_token = parts.next()?;
+    let base_command = command_token.split('@').next().unwrap_or(command_token);
+    if !base_command.eq_ignore_ascii_case("/voice") {
+        return None;
+    }
+    Some(parts.collect::<Vec<_>>().join(" "))
+}
+
+/// Apply a `/voice` command for `key` and build the user-facing response.
+pub fn voice_command_response(
+    preferences: &VoiceReplyPreferences,
+    key: &str,
+    argument: &str,
+    config: &TtsConfig,
+) -> String {
+    if !config.enabled {
+        return "Voice replies are not enabled on this server.".to_string();
+    }
+
+    let argument = argument.trim();
+    if argument.is_empty() {
+        let mode = preferences.mode_for(key, config.reply_mode);
+        return format!(
+            "Voice replies: `{}`.\nUse `/voice on`, `/voice off`, or `/voice auto` (speak only when you send a voice message).",
+            voice_reply_mode_label(mode)
+        );
+    }
+
+    match parse_voice_reply_mode(argument) {
+        Some(mode) => {
+            preferences.set(key, mode, config.reply_mode);
+            format!(
+                "Voice replies set to `{}` for this chat.",
+                voice_reply_mode_label(mode)
+            )
+        }
+        None => format!(
+            "Unknown voice mode `{argument}`. Use `/voice on`, `/voice off`, or `/voice auto`."
+        ),
+    }
+}
+
+/// Decide whether a reply should be spoken.
+pub fn should_reply_with_voice(
+    config: &TtsConfig,
+    mode: VoiceReplyMode,
+    channel: &str,
+    user_sent_voice: bool,
+) -> bool {
+    if !config.enabled || !config.channels.iter().any(|c| c == channel) {
+        return false;
+    }
+
+    match mode {
+        VoiceReplyMode::Off => false,
+        VoiceReplyMode::WhenVoice => user_sent_voice,
+        VoiceReplyMode::Always => true,
+    }
+}
+
+/// Strip Markdown and media markers so the reply reads naturally when spoken.
+pub fn speakable_text(text: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block || trimmed.is_empty() {
+            continue;
+        }
+
+        let without_prefix = trimmed
+            .trim_start_matches('#')
+            .trim_start_matches("> ")
+            .trim_start_matches("- ")
+            .trim_start_matches("* ");
+        let cleaned: String = without_prefix
+            .chars()
+            .filter(|c| !matches!(c, '*' | '_' | '`' | '~'))
+            .collect();
+        let cleaned = cleaned.trim();
+        // Media markers like [IMAGE:/tmp/a.png] are delivered separately, not read aloud.
+        if cleaned.starts_with('[') && cleaned.ends_with(']') && cleaned.contains(':') {
+            continue;
+        }
+        if !cleaned.is_empty() {
+            lines.push(cleaned.to_string());
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Synthesize a reply into the workspace TTS outbox and return the audio path.
+///
+/// Fails (so the caller can send text instead) when the reply is empty once
+/// Markdown is stripped, exceeds `max_chars`, or synthesis times out.
+pub async fn synthesize_reply(
+    synthesizer: &dyn SpeechSynthesizer,
+    text: &str,
+    workspace_dir: &Path,
+    config: &TtsConfig,
+) -> Result<PathBuf> {
+    let speakable = speakable_text(text);
+    if speakable.is_empty() {
+        bail!("reply has no speakable text");
+    }
+    let char_count = speakable.chars().count();
+    if char_count > config.max_chars {
+        bail!(
+            "reply is {char_count} characters (voice limit {})",
+            config.max_chars
+        );
+    }
+
+    let outbox = tts_outbox_dir(workspace_dir);
+    tokio::fs::create_dir_all(&outbox).await?;
+    let output_path = outbox.join(format!("reply_{}.ogg", uuid::Uuid::new_v4()));
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(config.timeout_secs.max(1)),
+        synthesizer.synthesize(&speakable, &output_path),
+    )
+    .await;
+
+    let outcome = match result {
+        Ok(Ok(())) if output_path.exists() => return Ok(output_path),
+        Ok(Ok(())) => anyhow::anyhow!("{} produced no audio", synthesizer.name()),
+        Ok(Err(e)) => e,
+        Err(_) => anyhow::anyhow!("speech synthesis timed out after {}s", config.timeout_secs),
+    };
+    let _ = tokio::fs::remove_file(&output_path).await;
+    Err(outcome)
+}
+
+/// Synthesize `text` and deliver it as a voice message on `channel`.
+///
+/// The temporary audio file is removed whether or not delivery succeeds.
+pub async fn send_voice_reply(
+    synthesizer: &dyn SpeechSynthesizer,
+    channel: &dyn Channel,
+    recipient: &str,
+    text: &str,
+    workspace_dir: &Path,
+    config: &TtsConfig,
+) -> Result<()> {
+    let audio_path = synthesize_reply(synthesizer, text, workspace_dir, config).await?;
+    let result = channel.send_voice_reply(recipient, &audio_path).await;
+    let _ = tokio::fs::remove_file(&audio_path).await;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    struct FakeSpeech;
+
+    #[async_trait]
+    impl SpeechSynthesizer for FakeSpeech {
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        async fn synthesize(&self, text: &str, output_path: &Path) -> Result<()> {
+            tokio::fs::write(output_path, text.as_bytes()).await?;
+            Ok(())
+        }
+    }
+
+    fn enabled_config() -> TtsConfig {
+        TtsConfig {
+            enabled: true,
+            ..TtsConfig::default()
+        }
+    }
+
+    #[test]
+    fn factory_disabled_returns_none() {
+        assert!(create_synthesizer(&TtsConfig::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn factory_selects_backends() {
+        let mut config = enabled_config();
+        assert_eq!(
+            create_synthesizer(&config).unwrap().unwrap().name(),
+            "piper"
+        );
+
+        config.backend = "openai".into();
+        config.api_key = Some("sk-test".into());
+        assert_eq!(
+            create_synthesizer(&config).unwrap().unwrap().name(),
+            "openai"
+        );
+
+        config.backend = "espeak".into();
+        assert!(create_synthesizer(&config).is_err());
+    }
+
+    #[test]
+    fn parse_voice_reply_mode_accepts_aliases() {
+        assert_eq!(parse_voice_reply_mode("on"), Some(VoiceReplyMode::Always));
+        assert_eq!(parse_voice_reply_mode("OFF"), Some(VoiceReplyMode::Off));
+        assert_eq!(
+            parse_voice_reply_mode("auto"),
+            Some(VoiceReplyMode::WhenVoice)
+        );
+        assert_eq!(parse_voice_reply_mode("loud"), None);
+    }
+
+    #[test]
+    fn parse_voice_command_extracts_argument() {
+        assert_eq!(parse_voice_command("/voice on"), Some("on".to_string()));
+        assert_eq!(
+            parse_voice_command("/voice@zeroclaw_bot"),
+            Some(String::new())
+        );
+        assert_eq!(parse_voice_command("/voices"), None);
+        assert_eq!(parse_voice_command("voice on"), None);
+    }
+
+    #[test]
+    fn voice_command_response_updates_preferences() {
+        let prefs = VoiceReplyPreferences::default();
+        let config = enabled_config();
+
+        let reply = voice_command_response(&prefs, "telegram_alice", "on", &config);
+        assert!(reply.contains("`on`"));
+        assert_eq!(
+            prefs.mode_for("telegram_alice", config.reply_mode),
+            VoiceReplyMode::Always
+        );
+        assert_eq!(
+            prefs.mode_for("telegram_bob", config.reply_mode),
+            VoiceReplyMode::WhenVoice
+        );
+
+        let reply = voice_command_response(&prefs, "telegram_alice", "", &config);
+        assert!(reply.contains("Voice replies: `on`"));
+
+        let reply = voice_command_response(&prefs, "telegram_alice", "shout", &config);
+        assert!(reply.contains("Unknown voice mode"));
+
+        voice_command_response(&prefs, "telegram_alice", "auto", &config);
+        assert!(prefs.overrides.lock().is_empty());
+    }
+
+    #[test]
+    fn voice_command_response_reports_disabled_tts() {
+        let prefs = VoiceReplyPreferences::default();
+        let reply = voice_command_response(&prefs, "k", "on", &TtsConfig::default());
+        assert!(reply.contains("not enabled"));
+        assert!(prefs.overrides.lock().is_empty());
+    }
+
+    #[test]
+    fn should_reply_with_voice_respects_mode_and_channel() {
+        let config = enabled_config();
+        assert!(should_reply_with_voice(
+            &config,
+            VoiceReplyMode::WhenVoice,
+            "telegram",
+            true
+        ));
+        assert!(!should_reply_with_voice(
+            &config,
+            VoiceReplyMode::WhenVoice,
+            "telegram",
+            false
+        ));
+        assert!(should_reply_with_voice(
+            &config,
+            VoiceReplyMode::Always,
+            "whatsapp",
+            false
+        ));
+        assert!(!should_reply_with_voice(
+            &config,
+            VoiceReplyMode::Always,
+            "slack",
+            true
+        ));
+        assert!(!should_reply_with_voice(
+            &TtsConfig::default(),
+            VoiceReplyMode::Always,
+            "telegram",
+            true
+        ));
+    }
+
+    #[test]
+    fn speakable_text_strips_markdown_code_and_markers() {
+        let text = "## Status\n**All good**, `3` tasks done.\n```\ncargo test\n```\n- next: deploy\n[IMAGE:/tmp/chart.png]";
+        assert_eq!(
+            speakable_text(text),
+            "Status\nAll good, 3 tasks done.\nnext: deploy"
+        );
+    }
+
+    #[tokio::test]
+    async fn synthesize_reply_writes_to_outbox() {
+        let tmp = TempDir::new().unwrap();
+        let path = synthesize_reply(
+            &FakeSpeech,
+            "Hello **there**",
+            tmp.path(),
+            &enabled_config(),
+        )
+        .await
+        .unwrap();
+        assert!(path.starts_with(tts_outbox_dir(tmp.path())));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Hello there");
+    }
+
+    #[tokio::test]
+    async fn synthesize_reply_rejects_long_or_empty_text() {
+        let tmp = TempDir::new().unwrap();
+        let config = TtsConfig {
+            max_chars: 5,
+            ..enabled_config()
+        };
+        assert!(
+            synthesize_reply(&FakeSpeech, "too long for voice", tmp.path(), &config)
+                .await
+                .is_err()
+        );
+        assert!(
+            synthesize_reply(&FakeSpeech, "```\ncode\n```", tmp.path(), &config)
+                .await
+                .is_err()
+        );
+    }
+}
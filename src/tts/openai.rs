@@ -0,0 +1,91 @@
+use super::SpeechSynthesizer;
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use std::path::Path;
+
+/// OpenAI-compatible `/audio/speech` backend. Requests Opus output directly,
+/// which the API returns in an OGG container.
+pub struct OpenAiSpeech {
+    base_url: String,
+    api_key: String,
+    model: String,
+    voice: String,
+}
+
+impl OpenAiSpeech {
+    pub fn new(base_url: &str, api_key: &str, model: &str, voice: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            model: model.to_string(),
+            voice: voice.to_string(),
+        }
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client("provider.tts")
+    }
+
+    fn speech_url(&self) -> String {
+        if self.base_url.ends_with("/audio/speech") {
+            self.base_url.clone()
+        } else {
+            format!("{}/audio/speech", self.base_url)
+        }
+    }
+}
+
+#[async_trait]
+impl SpeechSynthesizer for OpenAiSpeech {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    async fn synthesize(&self, text: &str, output_path: &Path) -> Result<()> {
+        let body = serde_json::json!({
+            "model": self.model,
+            "voice": self.voice,
+            "input": text,
+            "response_format": "opus",
+        });
+
+        let mut request = self.http_client().post(self.speech_url()).json(&body);
+        if !self.api_key.is_empty() {
+            request = request.bearer_auth(&self.api_key);
+        }
+        let resp = request.send().await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            bail!(
+                "Speech API error {status}: {}",
+                crate::providers::sanitize_api_error(&text)
+            );
+        }
+
+        let bytes = resp.bytes().await?;
+        if bytes.is_empty() {
+            bail!("Speech API returned empty audio");
+        }
+        tokio::fs::write(output_path, &bytes).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speech_url_appends_endpoint() {
+        let s = OpenAiSpeech::new("https://api.openai.com/v1/", "k", "tts-1", "alloy");
+        assert_eq!(s.speech_url(), "https://api.openai.com/v1/audio/speech");
+    }
+
+    #[test]
+    fn speech_url_keeps_explicit_endpoint() {
+        let s = OpenAiSpeech::new("http://localhost:8880/v1/audio/speech", "", "kokoro", "af");
+        assert_eq!(s.speech_url(), "http://localhost:8880/v1/audio/speech");
+    }
+}
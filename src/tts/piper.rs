@@ -0,0 +1,132 @@
+use super::SpeechSynthesizer;
+use crate::config::TtsConfig;
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Local Piper backend.
+///
+/// Piper writes WAV, so the output is re-encoded to OGG/Opus with ffmpeg.
+pub struct PiperSpeech {
+    piper_bin: String,
+    model_path: PathBuf,
+    ffmpeg_bin: String,
+}
+
+impl PiperSpeech {
+    pub fn new(piper_bin: &str, model_path: &str, ffmpeg_bin: &str) -> Self {
+        Self {
+            piper_bin: piper_bin.to_string(),
+            model_path: PathBuf::from(shellexpand::tilde(model_path).to_string()),
+            ffmpeg_bin: ffmpeg_bin.to_string(),
+        }
+    }
+
+    pub fn from_config(config: &TtsConfig) -> Self {
+        Self::new(
+            &config.piper_bin,
+            &config.piper_model_path,
+            &config.ffmpeg_bin,
+        )
+    }
+
+    async fn run_piper(&self, text: &str, wav_path: &Path) -> Result<()> {
+        let mut child = Command::new(&self.piper_bin)
+            .arg("--model")
+            .arg(&self.model_path)
+            .arg("--output_file")
+            .arg(wav_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to run Piper ({})", self.piper_bin))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes()).await?;
+            stdin.shutdown().await?;
+        }
+
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            bail!(
+                "Piper failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+
+    async fn encode_opus(&self, wav_path: &Path, output_path: &Path) -> Result<()> {
+        let output = Command::new(&self.ffmpeg_bin)
+            .arg("-y")
+            .arg("-loglevel")
+            .arg("error")
+            .arg("-i")
+            .arg(wav_path)
+            .args(["-ac", "1", "-c:a", "libopus", "-b:a", "32k"])
+            .arg(output_path)
+            .kill_on_drop(true)
+            .output()
+            .await
+            .with_context(|| format!("Failed to run ffmpeg ({})", self.ffmpeg_bin))?;
+
+        if !output.status.success() {
+            bail!(
+                "ffmpeg Opus encoding failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SpeechSynthesizer for PiperSpeech {
+    fn name(&self) -> &str {
+        "piper"
+    }
+
+    async fn synthesize(&self, text: &str, output_path: &Path) -> Result<()> {
+        if !self.model_path.exists() {
+            bail!(
+                "Piper voice model not found at {}",
+                self.model_path.display()
+            );
+        }
+
+        let wav_path = output_path.with_extension("wav");
+        let result = match self.run_piper(text, &wav_path).await {
+            Ok(()) => self.encode_opus(&wav_path, output_path).await,
+            Err(e) => Err(e),
+        };
+        let _ = tokio::fs::remove_file(&wav_path).await;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_expands_tilde_in_model_path() {
+        let piper = PiperSpeech::new("piper", "~/voices/a.onnx", "ffmpeg");
+        assert!(!piper.model_path.to_string_lossy().starts_with('~'));
+    }
+
+    #[tokio::test]
+    async fn synthesize_fails_when_model_missing() {
+        let piper = PiperSpeech::new("piper", "/nonexistent/voice.onnx", "ffmpeg");
+        let err = piper
+            .synthesize("hello", Path::new("/tmp/zeroclaw_piper_test.ogg"))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("model not found"));
+    }
+}
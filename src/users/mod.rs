@@ -0,0 +1,878 @@
+//! Cross-channel user directory.
+//!
+//! Each channel identifies senders its own way (Telegram user IDs, Slack
+//! member IDs, email addresses, Signal numbers). The directory links those
+//! channel identities to one canonical user so the same person shares
+//! conversation history, memory and settings on every platform.
+//!
+//! Profiles are stored in `<workspace>/state/users.json`. The operator links
+//! identities with `zeroclaw users link`, or issues a one-time code with
+//! `zeroclaw users link-code <user>` that the person sends as `/link <code>`
+//! from the account to link. An already-linked person can send `/link` to get
+//! a code for linking their next account themselves.
+
+use crate::approval::ApprovalManager;
+use crate::config::{AutonomyConfig, Config};
+use crate::security::pairing::{constant_time_eq, generate_code, hash_token};
+use crate::security::AutonomyLevel;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime};
+
+const USERS_FILE: &str = "users.json";
+/// How long a `/link` verification code stays valid.
+const LINK_CODE_TTL_MINUTES: i64 = 15;
+/// Failed `/link` attempts per identity before a lockout.
+const MAX_LINK_ATTEMPTS: u32 = 5;
+const LINK_LOCKOUT_SECS: u64 = 300;
+
+/// Which memories a user's conversations read and write.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryScope {
+    /// Shared with every other sender (the default).
+    #[default]
+    Shared,
+    /// Only this user's own memories.
+    Private,
+}
+
+/// A sender as seen by one channel, e.g. `telegram:123456789`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelIdentity {
+    pub channel: String,
+    pub sender: String,
+}
+
+impl ChannelIdentity {
+    pub fn new(channel: &str, sender: &str) -> Self {
+        Self {
+            channel: channel.trim().to_ascii_lowercase(),
+            sender: sender.trim().to_string(),
+        }
+    }
+
+    fn matches(&self, channel: &str, sender: &str) -> bool {
+        self.channel.eq_ignore_ascii_case(channel.trim())
+            && self.sender.eq_ignore_ascii_case(sender.trim())
+    }
+}
+
+impl std::fmt::Display for ChannelIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.channel, self.sender)
+    }
+}
+
+/// A canonical user and the settings that follow them across channels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserProfile {
+    pub id: String,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub identities: Vec<ChannelIdentity>,
+    /// Tools this user may have the agent call; empty allows every tool.
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+    /// Overrides `[autonomy].level` for this user's turns.
+    #[serde(default)]
+    pub autonomy: Option<AutonomyLevel>,
+    /// Preferred provider; the channel default when unset.
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Preferred model; the channel default when unset.
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub memory_scope: MemoryScope,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserProfile {
+    pub fn new(id: &str, display_name: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            display_name: display_name.trim().to_string(),
+            identities: Vec::new(),
+            allowed_tools: Vec::new(),
+            autonomy: None,
+            provider: None,
+            model: None,
+            memory_scope: MemoryScope::Shared,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Key shared by all of this user's channel conversations.
+    pub fn conversation_key(&self) -> String {
+        format!("user:{}", self.id)
+    }
+
+    /// Memory session to read and write, or `None` for shared memory.
+    pub fn memory_session(&self) -> Option<String> {
+        match self.memory_scope {
+            MemoryScope::Shared => None,
+            MemoryScope::Private => Some(self.conversation_key()),
+        }
+    }
+
+    /// Whether this profile narrows what the agent may do on the user's behalf.
+    pub fn restricts_tools(&self) -> bool {
+        self.autonomy.is_some() || !self.allowed_tools.is_empty()
+    }
+
+    /// Approval manager for this user's turns. A read-only user gets no tool
+    /// calls at all; otherwise `allowed_tools` (when set) limits which tools
+    /// may run. The channel's interactive prompt is kept when it has one.
+    pub fn approval_manager(
+        &self,
+        autonomy: &AutonomyConfig,
+        channel_manager: Option<&ApprovalManager>,
+    ) -> ApprovalManager {
+        let mut autonomy = autonomy.clone();
+        if let Some(level) = self.autonomy {
+            autonomy.level = level;
+        }
+        let mut manager = ApprovalManager::from_config(&autonomy);
+        if let Some(prompt) = channel_manager.and_then(ApprovalManager::remote_prompt) {
+            manager = manager.with_remote_prompt(prompt);
+        }
+        if autonomy.level == AutonomyLevel::ReadOnly {
+            manager.with_tool_allowlist(Vec::new())
+        } else if self.allowed_tools.is_empty() {
+            manager
+        } else {
+            manager.with_tool_allowlist(self.allowed_tools.clone())
+        }
+    }
+
+    fn label(&self) -> String {
+        if self.display_name.is_empty() {
+            format!("`{}`", self.id)
+        } else {
+            format!("`{}` ({})", self.id, self.display_name)
+        }
+    }
+}
+
+/// An unredeemed `/link` code. Only the hash of the code is stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingLink {
+    code_hash: String,
+    user_id: String,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct DirectoryState {
+    #[serde(default)]
+    users: Vec<UserProfile>,
+    #[serde(default)]
+    pending_links: Vec<PendingLink>,
+}
+
+impl DirectoryState {
+    fn user(&self, id: &str) -> Option<&UserProfile> {
+        self.users.iter().find(|u| u.id == id)
+    }
+
+    fn user_mut(&mut self, id: &str) -> Result<&mut UserProfile> {
+        self.users
+            .iter_mut()
+            .find(|u| u.id == id)
+            .with_context(|| format!("No user '{id}'. Run `zeroclaw users list`."))
+    }
+
+    fn resolve(&self, channel: &str, sender: &str) -> Option<&UserProfile> {
+        self.users
+            .iter()
+            .find(|u| u.identities.iter().any(|i| i.matches(channel, sender)))
+    }
+
+    /// Attach an identity to `user_id`, detaching it from any other user.
+    fn link(&mut self, user_id: &str, identity: ChannelIdentity) -> Result<()> {
+        self.user_mut(user_id)?;
+        for user in &mut self.users {
+            user.identities
+                .retain(|i| !i.matches(&identity.channel, &identity.sender));
+        }
+        self.user_mut(user_id)?.identities.push(identity);
+        Ok(())
+    }
+}
+
+/// File-backed user directory under the workspace.
+///
+/// The daemon and the `zeroclaw users` CLI share the same file; the daemon
+/// re-reads it whenever it changes on disk.
+pub struct UserDirectory {
+    path: PathBuf,
+    cache: Mutex<Option<(Option<SystemTime>, DirectoryState)>>,
+    /// Brute-force protection for `/link <code>`, keyed by channel identity.
+    failed_links: Mutex<HashMap<String, (u32, Instant)>>,
+}
+
+impl UserDirectory {
+    pub fn new(workspace_dir: &Path) -> Self {
+        Self {
+            path: workspace_dir.join("state").join(USERS_FILE),
+            cache: Mutex::new(None),
+            failed_links: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn modified_at(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok()
+    }
+
+    fn load(&self) -> Result<DirectoryState> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .with_context(|| format!("Failed to parse {}", self.path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(DirectoryState::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", self.path.display())),
+        }
+    }
+
+    /// Current directory contents, re-read only when the file changed.
+    fn snapshot(&self) -> DirectoryState {
+        let modified = self.modified_at();
+        let mut cache = self.cache.lock();
+        if let Some((cached_at, state)) = cache.as_ref() {
+            if *cached_at == modified {
+                return state.clone();
+            }
+        }
+        let state = self.load().unwrap_or_else(|e| {
+            tracing::warn!("User directory unavailable: {e:#}");
+            DirectoryState::default()
+        });
+        *cache = Some((modified, state.clone()));
+        state
+    }
+
+    /// Load, apply `change` and persist atomically.
+    fn update<T>(&self, change: impl FnOnce(&mut DirectoryState) -> Result<T>) -> Result<T> {
+        let mut cache = self.cache.lock();
+        let mut state = self.load()?;
+        state.pending_links.retain(|p| p.expires_at > Utc::now());
+        let value = change(&mut state)?;
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let temp_path = self.path.with_extension("json.tmp");
+        std::fs::write(&temp_path, serde_json::to_vec_pretty(&state)?)
+            .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+        std::fs::rename(&temp_path, &self.path)
+            .with_context(|| format!("Failed to write {}", self.path.display()))?;
+        *cache = Some((self.modified_at(), state));
+        Ok(value)
+    }
+
+    /// The user a channel sender is linked to, if any.
+    pub fn resolve(&self, channel: &str, sender: &str) -> Option<UserProfile> {
+        self.snapshot().resolve(channel, sender).cloned()
+    }
+
+    pub fn list(&self) -> Result<Vec<UserProfile>> {
+        let mut users = self.load()?.users;
+        users.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(users)
+    }
+
+    pub fn get(&self, id: &str) -> Result<UserProfile> {
+        self.load()?
+            .user(id)
+            .cloned()
+            .with_context(|| format!("No user '{id}'. Run `zeroclaw users list`."))
+    }
+
+    pub fn create(&self, id: &str, display_name: &str) -> Result<UserProfile> {
+        validate_user_id(id)?;
+        self.update(|state| {
+            if state.user(id).is_some() {
+                bail!("User '{id}' already exists");
+            }
+            let profile = UserProfile::new(id, display_name);
+            state.users.push(profile.clone());
+            Ok(profile)
+        })
+    }
+
+    pub fn delete(&self, id: &str) -> Result<()> {
+        self.update(|state| {
+            state.user_mut(id)?;
+            state.users.retain(|u| u.id != id);
+            state.pending_links.retain(|p| p.user_id != id);
+            Ok(())
+        })
+    }
+
+    /// Apply `change` to one profile and persist it.
+    pub fn edit(&self, id: &str, change: impl FnOnce(&mut UserProfile)) -> Result<UserProfile> {
+        self.update(|state| {
+            let profile = state.user_mut(id)?;
+            change(profile);
+            Ok(profile.clone())
+        })
+    }
+
+    /// Link a channel identity to `user_id` without a verification code.
+    pub fn link(&self, user_id: &str, channel: &str, sender: &str) -> Result<ChannelIdentity> {
+        let identity = ChannelIdentity::new(channel, sender);
+        if identity.channel.is_empty() || identity.sender.is_empty() {
+            bail!("Channel and sender cannot be empty");
+        }
+        self.update(|state| {
+            state.link(user_id, identity.clone())?;
+            Ok(identity)
+        })
+    }
+
+    /// Remove a channel identity; returns the user it was linked to.
+    pub fn unlink(&self, channel: &str, sender: &str) -> Result<String> {
+        self.update(|state| {
+            let user = state
+                .users
+                .iter_mut()
+                .find(|u| u.identities.iter().any(|i| i.matches(channel, sender)))
+                .with_context(|| format!("{channel}:{sender} is not linked to any user"))?;
+            user.identities.retain(|i| !i.matches(channel, sender));
+            Ok(user.id.clone())
+        })
+    }
+
+    /// Issue a one-time code that links the sending identity to `user_id`.
+    pub fn issue_link_code(&self, user_id: &str) -> Result<String> {
+        let code = generate_code();
+        let code_hash = hash_token(&code);
+        self.update(|state| {
+            state.user_mut(user_id)?;
+            state.pending_links.push(PendingLink {
+                code_hash,
+                user_id: user_id.to_string(),
+                expires_at: Utc::now() + Duration::minutes(LINK_CODE_TTL_MINUTES),
+            });
+            Ok(())
+        })?;
+        Ok(code)
+    }
+
+    /// Redeem a `/link` code for `channel:sender`.
+    pub fn redeem_link_code(&self, channel: &str, sender: &str, code: &str) -> Result<UserProfile> {
+        let identity = ChannelIdentity::new(channel, sender);
+        let attempt_key = identity.to_string();
+        if let Some((count, since)) = self.failed_links.lock().get(&attempt_key) {
+            let elapsed = since.elapsed().as_secs();
+            if *count >= MAX_LINK_ATTEMPTS && elapsed < LINK_LOCKOUT_SECS {
+                bail!(
+                    "Too many failed attempts. Try again in {}s.",
+                    LINK_LOCKOUT_SECS - elapsed
+                );
+            }
+        }
+
+        let code_hash = hash_token(code.trim());
+        let linked = self.update(|state| {
+            let Some(index) = state
+                .pending_links
+                .iter()
+                .position(|p| constant_time_eq(&p.code_hash, &code_hash))
+            else {
+                return Ok(None);
+            };
+            let pending = state.pending_links.remove(index);
+            state.link(&pending.user_id, identity.clone())?;
+            Ok(state.user(&pending.user_id).cloned())
+        })?;
+
+        let mut failed = self.failed_links.lock();
+        match linked {
+            Some(profile) => {
+                failed.remove(&attempt_key);
+                Ok(profile)
+            }
+            None => {
+                let entry = failed.entry(attempt_key).or_insert((0, Instant::now()));
+                if entry.1.elapsed().as_secs() >= LINK_LOCKOUT_SECS {
+                    *entry = (0, Instant::now());
+                }
+                entry.0 += 1;
+                entry.1 = Instant::now();
+                bail!("Invalid or expired link code")
+            }
+        }
+    }
+}
+
+fn validate_user_id(id: &str) -> Result<()> {
+    let valid = !id.is_empty()
+        && id.len() <= 64
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !valid {
+        bail!("Invalid user id '{id}': use lowercase letters, digits, '-' and '_' (max 64)");
+    }
+    Ok(())
+}
+
+/// Chat commands for managing the sender's own profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserCommand {
+    /// `/link` (issue a code) or `/link <code>` (redeem one).
+    Link(String),
+    /// `/whoami`
+    WhoAmI,
+}
+
+/// Parse `/link [code]` or `/whoami`.
+pub fn parse_user_command(content: &str) -> Option<UserCommand> {
+    let mut parts = content.split_whitespace();
+    let command_token = parts.next()?;
+    let base_command = command_token
+        .split('@')
+        .next()
+        .unwrap_or(command_token)
+        .to_ascii_lowercase();
+    match base_command.as_str() {
+        "/link" => Some(UserCommand::Link(parts.collect::<Vec<_>>().join(" "))),
+        "/whoami" => Some(UserCommand::WhoAmI),
+        _ => None,
+    }
+}
+
+/// Run a user command for `channel:sender` and build the reply.
+pub fn user_command_response(
+    directory: &UserDirectory,
+    channel: &str,
+    sender: &str,
+    command: &UserCommand,
+) -> String {
+    let current = directory.resolve(channel, sender);
+    match command {
+        UserCommand::WhoAmI => match current {
+            Some(profile) => describe_profile(&profile),
+            None => format!(
+                "You are `{channel}:{sender}` and not linked to a user profile.\nAsk the operator for a link code, then send `/link <code>`."
+            ),
+        },
+        UserCommand::Link(code) if code.trim().is_empty() => match current {
+            Some(profile) => match directory.issue_link_code(&profile.id) {
+                Ok(code) => format!(
+                    "🔗 Link code for {}: `{code}`\nSend `/link {code}` from your other account within {LINK_CODE_TTL_MINUTES} minutes.",
+                    profile.label()
+                ),
+                Err(e) => format!("⚠️ Could not issue a link code: {e}"),
+            },
+            None => "This account is not linked to a user profile yet.\nAsk the operator to run `zeroclaw users link-code <user>`, then send `/link <code>` here.".to_string(),
+        },
+        UserCommand::Link(code) => match directory.redeem_link_code(channel, sender, code) {
+            Ok(profile) => format!(
+                "✅ Linked `{channel}:{sender}` to user {}. Your conversations and settings are now shared across linked accounts.",
+                profile.label()
+            ),
+            Err(e) => format!("⚠️ {e}"),
+        },
+    }
+}
+
+fn describe_profile(profile: &UserProfile) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "You are {}.", profile.label());
+    let identities = profile
+        .identities
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let _ = writeln!(out, "Linked accounts: {identities}");
+    if let Some(model) = &profile.model {
+        let _ = writeln!(out, "Preferred model: `{model}`");
+    }
+    let _ = write!(
+        out,
+        "Memory: {}",
+        match profile.memory_scope {
+            MemoryScope::Shared => "shared",
+            MemoryScope::Private => "private",
+        }
+    );
+    out
+}
+
+/// Parse an autonomy level argument; `inherit` clears the override.
+fn parse_autonomy_override(value: &str) -> Result<Option<AutonomyLevel>> {
+    match value
+        .trim()
+        .to_ascii_lowercase()
+        .replace(['-', '_'], "")
+        .as_str()
+    {
+        "inherit" | "default" => Ok(None),
+        "readonly" => Ok(Some(AutonomyLevel::ReadOnly)),
+        "supervised" => Ok(Some(AutonomyLevel::Supervised)),
+        "full" => Ok(Some(AutonomyLevel::Full)),
+        other => {
+            bail!("Unknown autonomy level '{other}'; use read_only, supervised, full or inherit")
+        }
+    }
+}
+
+fn parse_memory_scope(value: &str) -> Result<MemoryScope> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "shared" => Ok(MemoryScope::Shared),
+        "private" => Ok(MemoryScope::Private),
+        other => bail!("Unknown memory scope '{other}'; use shared or private"),
+    }
+}
+
+/// `inherit` (or an empty value) clears an optional override.
+fn optional_override(value: String) -> Option<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("inherit") {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn print_profile(profile: &UserProfile) {
+    println!("👤 {}", profile.id);
+    if !profile.display_name.is_empty() {
+        println!("  Name:       {}", profile.display_name);
+    }
+    if profile.identities.is_empty() {
+        println!("  Identities: (none)");
+    } else {
+        println!("  Identities:");
+        for identity in &profile.identities {
+            println!("    - {identity}");
+        }
+    }
+    let autonomy = profile
+        .autonomy
+        .map_or_else(|| "inherit".to_string(), |level| format!("{level:?}"));
+    println!("  Autonomy:   {autonomy}");
+    println!(
+        "  Tools:      {}",
+        if profile.allowed_tools.is_empty() {
+            "all".to_string()
+        } else {
+            profile.allowed_tools.join(", ")
+        }
+    );
+    println!(
+        "  Provider:   {}",
+        profile.provider.as_deref().unwrap_or("inherit")
+    );
+    println!(
+        "  Model:      {}",
+        profile.model.as_deref().unwrap_or("inherit")
+    );
+    println!("  Memory:     {:?}", profile.memory_scope);
+}
+
+pub fn handle_command(command: crate::UserCommands, config: &Config) -> Result<()> {
+    let directory = UserDirectory::new(&config.workspace_dir);
+    match command {
+        crate::UserCommands::List => {
+            let users = directory.list()?;
+            if users.is_empty() {
+                println!("No users yet. Create one with `zeroclaw users create <id>`.");
+                return Ok(());
+            }
+            println!("👥 Users ({}):", users.len());
+            for user in &users {
+                let identities = user
+                    .identities
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                println!(
+                    "- {} | {} | {}",
+                    user.id,
+                    if user.display_name.is_empty() {
+                        "(no name)"
+                    } else {
+                        &user.display_name
+                    },
+                    if identities.is_empty() {
+                        "(no linked identities)"
+                    } else {
+                        &identities
+                    }
+                );
+            }
+            Ok(())
+        }
+        crate::UserCommands::Show { id } => {
+            print_profile(&directory.get(&id)?);
+            Ok(())
+        }
+        crate::UserCommands::Create { id, name } => {
+            let profile = directory.create(&id, name.as_deref().unwrap_or_default())?;
+            println!("✅ Created user {}", profile.id);
+            println!(
+                "   Link identities with: zeroclaw users link-code {}",
+                profile.id
+            );
+            Ok(())
+        }
+        crate::UserCommands::Delete { id } => {
+            directory.delete(&id)?;
+            println!("✅ Deleted user {id}");
+            Ok(())
+        }
+        crate::UserCommands::Link {
+            id,
+            channel,
+            sender,
+        } => {
+            let identity = directory.link(&id, &channel, &sender)?;
+            println!("✅ Linked {identity} to user {id}");
+            Ok(())
+        }
+        crate::UserCommands::Unlink { channel, sender } => {
+            let id = directory.unlink(&channel, &sender)?;
+            println!("✅ Unlinked {channel}:{sender} from user {id}");
+            Ok(())
+        }
+        crate::UserCommands::LinkCode { id } => {
+            let code = directory.issue_link_code(&id)?;
+            println!("🔗 One-time link code for {id}: {code}");
+            println!(
+                "   Send `/link {code}` from the account to link, within {LINK_CODE_TTL_MINUTES} minutes."
+            );
+            Ok(())
+        }
+        crate::UserCommands::Set {
+            id,
+            name,
+            autonomy,
+            provider,
+            model,
+            memory_scope,
+            tools,
+        } => {
+            let autonomy = autonomy
+                .as_deref()
+                .map(parse_autonomy_override)
+                .transpose()?;
+            let memory_scope = memory_scope
+                .as_deref()
+                .map(parse_memory_scope)
+                .transpose()?;
+            let profile = directory.edit(&id, |profile| {
+                if let Some(name) = name {
+                    profile.display_name = name.trim().to_string();
+                }
+                if let Some(level) = autonomy {
+                    profile.autonomy = level;
+                }
+                if let Some(provider) = provider {
+                    profile.provider = optional_override(provider);
+                }
+                if let Some(model) = model {
+                    profile.model = optional_override(model);
+                }
+                if let Some(scope) = memory_scope {
+                    profile.memory_scope = scope;
+                }
+                if let Some(tools) = tools {
+                    profile.allowed_tools = if tools.trim().eq_ignore_ascii_case("all") {
+                        Vec::new()
+                    } else {
+                        tools
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|t| !t.is_empty())
+                            .map(str::to_string)
+                            .collect()
+                    };
+                }
+            })?;
+            print_profile(&profile);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn link_resolves_identity_across_channels() {
+        let tmp = TempDir::new().unwrap();
+        let directory = UserDirectory::new(tmp.path());
+        directory.create("alice", "Alice").unwrap();
+        directory.link("alice", "Telegram", "123").unwrap();
+        directory
+            .link("alice", "email", "Alice@Example.com")
+            .unwrap();
+
+        let via_telegram = directory.resolve("telegram", "123").unwrap();
+        let via_email = directory.resolve("email", "alice@example.com").unwrap();
+        assert_eq!(via_telegram.id, "alice");
+        assert_eq!(
+            via_telegram.conversation_key(),
+            via_email.conversation_key()
+        );
+        assert!(directory.resolve("slack", "123").is_none());
+    }
+
+    #[test]
+    fn relinking_moves_identity_between_users() {
+        let tmp = TempDir::new().unwrap();
+        let directory = UserDirectory::new(tmp.path());
+        directory.create("alice", "").unwrap();
+        directory.create("bob", "").unwrap();
+        directory.link("alice", "signal", "+15550001").unwrap();
+        directory.link("bob", "signal", "+15550001").unwrap();
+
+        assert_eq!(directory.resolve("signal", "+15550001").unwrap().id, "bob");
+        assert!(directory.get("alice").unwrap().identities.is_empty());
+        assert_eq!(directory.unlink("signal", "+15550001").unwrap(), "bob");
+        assert!(directory.resolve("signal", "+15550001").is_none());
+    }
+
+    #[test]
+    fn link_code_is_single_use() {
+        let tmp = TempDir::new().unwrap();
+        let directory = UserDirectory::new(tmp.path());
+        directory.create("alice", "").unwrap();
+        let code = directory.issue_link_code("alice").unwrap();
+
+        let profile = directory.redeem_link_code("slack", "U123", &code).unwrap();
+        assert_eq!(profile.id, "alice");
+        assert_eq!(directory.resolve("slack", "U123").unwrap().id, "alice");
+        assert!(directory.redeem_link_code("discord", "42", &code).is_err());
+
+        let raw = std::fs::read_to_string(tmp.path().join("state").join(USERS_FILE)).unwrap();
+        assert!(!raw.contains(&format!("\"{code}\"")));
+    }
+
+    #[test]
+    fn repeated_bad_codes_lock_out_identity() {
+        let tmp = TempDir::new().unwrap();
+        let directory = UserDirectory::new(tmp.path());
+        directory.create("alice", "").unwrap();
+        let code = directory.issue_link_code("alice").unwrap();
+        for _ in 0..MAX_LINK_ATTEMPTS {
+            assert!(directory
+                .redeem_link_code("irc", "mallory", "000000x")
+                .is_err());
+        }
+        let err = directory
+            .redeem_link_code("irc", "mallory", &code)
+            .unwrap_err();
+        assert!(err.to_string().contains("Too many failed attempts"));
+    }
+
+    #[test]
+    fn directory_picks_up_external_changes() {
+        let tmp = TempDir::new().unwrap();
+        let daemon_view = UserDirectory::new(tmp.path());
+        assert!(daemon_view.resolve("telegram", "1").is_none());
+
+        let cli_view = UserDirectory::new(tmp.path());
+        cli_view.create("alice", "").unwrap();
+        cli_view.link("alice", "telegram", "1").unwrap();
+        assert_eq!(daemon_view.resolve("telegram", "1").unwrap().id, "alice");
+    }
+
+    #[test]
+    fn create_rejects_invalid_and_duplicate_ids() {
+        let tmp = TempDir::new().unwrap();
+        let directory = UserDirectory::new(tmp.path());
+        assert!(directory.create("Alice Smith", "").is_err());
+        assert!(directory.create("../x", "").is_err());
+        directory.create("alice", "").unwrap();
+        assert!(directory.create("alice", "").is_err());
+    }
+
+    #[test]
+    fn read_only_profile_blocks_every_tool() {
+        let mut profile = UserProfile::new("guest", "");
+        profile.autonomy = Some(AutonomyLevel::ReadOnly);
+        let manager = profile.approval_manager(&AutonomyConfig::default(), None);
+        assert!(!manager.is_tool_allowed("file_read"));
+
+        let mut profile = UserProfile::new("dev", "");
+        profile.allowed_tools = vec!["shell".into()];
+        let manager = profile.approval_manager(&AutonomyConfig::default(), None);
+        assert!(manager.is_tool_allowed("shell"));
+        assert!(!manager.is_tool_allowed("file_write"));
+        assert!(UserProfile::new("any", "")
+            .approval_manager(&AutonomyConfig::default(), None)
+            .is_tool_allowed("file_write"));
+    }
+
+    #[test]
+    fn private_memory_scope_uses_user_session() {
+        let mut profile = UserProfile::new("alice", "");
+        assert_eq!(profile.memory_session(), None);
+        profile.memory_scope = MemoryScope::Private;
+        assert_eq!(profile.memory_session().as_deref(), Some("user:alice"));
+    }
+
+    #[test]
+    fn parse_user_commands() {
+        assert_eq!(
+            parse_user_command("/link 123456"),
+            Some(UserCommand::Link("123456".into()))
+        );
+        assert_eq!(
+            parse_user_command("/link@zeroclaw_bot"),
+            Some(UserCommand::Link(String::new()))
+        );
+        assert_eq!(parse_user_command("/WhoAmI"), Some(UserCommand::WhoAmI));
+        assert_eq!(parse_user_command("link me"), None);
+    }
+
+    #[test]
+    fn unlinked_sender_link_without_code_explains_flow() {
+        let tmp = TempDir::new().unwrap();
+        let directory = UserDirectory::new(tmp.path());
+        let reply = user_command_response(
+            &directory,
+            "telegram",
+            "1",
+            &UserCommand::Link(String::new()),
+        );
+        assert!(reply.contains("zeroclaw users link-code"));
+
+        directory.create("alice", "").unwrap();
+        directory.link("alice", "telegram", "1").unwrap();
+        let reply = user_command_response(
+            &directory,
+            "telegram",
+            "1",
+            &UserCommand::Link(String::new()),
+        );
+        assert!(reply.contains("Link code for `alice`"));
+    }
+
+    #[test]
+    fn autonomy_override_parsing() {
+        assert_eq!(
+            parse_autonomy_override("read_only").unwrap(),
+            Some(AutonomyLevel::ReadOnly)
+        );
+        assert_eq!(parse_autonomy_override("inherit").unwrap(), None);
+        assert!(parse_autonomy_override("root").is_err());
+    }
+}
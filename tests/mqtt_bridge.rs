@@ -0,0 +1,266 @@
+//! MQTT tools and channel against the in-process test broker.
+//!
+//! Covers the broker itself, retained publishes read back through
+//! `mqtt_subscribe_peek`, the channel's listen → reply round trip with echo
+//! suppression, and QoS 2 delivery through a credentialed broker.
+
+mod support;
+
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use support::mqtt_broker::{BrokerServer, LocalBroker};
+use tokio::sync::mpsc;
+use zeroclaw::channels::traits::{Channel, ChannelMessage, SendMessage};
+use zeroclaw::channels::MqttChannel;
+use zeroclaw::config::{MqttChannelConfig, MqttConfig};
+use zeroclaw::mqtt::{MqttClient, MqttOptions, QoS};
+use zeroclaw::security::{AutonomyLevel, SecurityPolicy};
+use zeroclaw::tools::{MqttPublishTool, MqttSubscribePeekTool, Tool};
+
+async fn broker() -> (Arc<LocalBroker>, BrokerServer) {
+    let broker = Arc::new(LocalBroker::new());
+    let server = broker.bind("127.0.0.1:0").await.unwrap();
+    (broker, server)
+}
+
+fn config(server: &BrokerServer) -> MqttConfig {
+    MqttConfig {
+        enabled: true,
+        host: "127.0.0.1".into(),
+        port: Some(server.addr().port()),
+        publish_topics: vec!["home/+/set".into(), "zeroclaw/#".into()],
+        subscribe_topics: vec!["home/#".into()],
+        ..MqttConfig::default()
+    }
+}
+
+fn security() -> Arc<SecurityPolicy> {
+    Arc::new(SecurityPolicy {
+        autonomy: AutonomyLevel::Full,
+        ..SecurityPolicy::default()
+    })
+}
+
+async fn recv<T>(rx: &mut mpsc::Receiver<T>) -> T {
+    tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("timed out")
+        .expect("channel closed")
+}
+
+#[tokio::test]
+async fn retained_publish_is_visible_to_peek() {
+    let (broker, server) = broker().await;
+    let config = config(&server);
+
+    let publish = MqttPublishTool::new(security(), config.clone());
+    let result = publish
+        .execute(json!({
+            "topic": "home/thermostat/set",
+            "payload": {"target": 21.5},
+            "retain": true
+        }))
+        .await
+        .unwrap();
+    assert!(result.success, "{:?}", result.error);
+    assert_eq!(
+        broker.retained("home/thermostat/set").unwrap(),
+        br#"{"target":21.5}"#
+    );
+
+    let peek = MqttSubscribePeekTool::new(config);
+    let result = peek
+        .execute(json!({"topic": "home/+/set", "wait_ms": 300}))
+        .await
+        .unwrap();
+    assert!(result.success, "{:?}", result.error);
+    let output: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+    assert_eq!(output["count"], 1);
+    assert_eq!(output["messages"][0]["topic"], "home/thermostat/set");
+    assert_eq!(output["messages"][0]["payload"]["target"], 21.5);
+    assert_eq!(output["messages"][0]["retained"], true);
+}
+
+#[tokio::test]
+async fn channel_turns_messages_into_replies_without_echo() {
+    let (_broker, server) = broker().await;
+    let channel = Arc::new(MqttChannel::new(
+        config(&server),
+        MqttChannelConfig {
+            // Also matches the reply topic, which must not loop back.
+            topics: vec!["zeroclaw/in/#".into()],
+            response_topic: "{topic}/reply".into(),
+            qos: 1,
+            retain: false,
+        },
+    ));
+    let (tx, mut messages) = mpsc::channel::<ChannelMessage>(8);
+    let listener = {
+        let channel = channel.clone();
+        tokio::spawn(async move { channel.listen(tx).await })
+    };
+
+    let (device, mut replies) = MqttClient::connect(&MqttOptions::new(
+        "127.0.0.1",
+        server.addr().port(),
+        "device",
+    ))
+    .await
+    .unwrap();
+    device
+        .subscribe(&[("zeroclaw/in/+/reply".into(), QoS::One)])
+        .await
+        .unwrap();
+
+    // The channel subscribes asynchronously; retry until it is listening.
+    let message = loop {
+        device
+            .publish("zeroclaw/in/kitchen", "is the oven on?", QoS::One, false)
+            .await
+            .unwrap();
+        if let Ok(Some(message)) =
+            tokio::time::timeout(Duration::from_millis(200), messages.recv()).await
+        {
+            break message;
+        }
+    };
+    assert_eq!(message.channel, "mqtt");
+    assert_eq!(message.sender, "zeroclaw/in/kitchen");
+    assert_eq!(message.content, "is the oven on?");
+    assert_eq!(message.reply_target, "zeroclaw/in/kitchen/reply");
+    // Drop duplicates from retries that raced the subscription.
+    while let Ok(Some(extra)) =
+        tokio::time::timeout(Duration::from_millis(300), messages.recv()).await
+    {
+        assert_eq!(extra.content, "is the oven on?");
+    }
+
+    channel
+        .send(&SendMessage::new("No, it is off.", &message.reply_target))
+        .await
+        .unwrap();
+    let reply = recv(&mut replies).await;
+    assert_eq!(reply.topic, "zeroclaw/in/kitchen/reply");
+    assert_eq!(reply.payload_str(), "No, it is off.");
+
+    // JSON payloads can pick their own reply topic within publish_topics.
+    device
+        .publish(
+            "zeroclaw/in/hall",
+            r#"{"text":"lights?","reply_to":"zeroclaw/in/hall/reply"}"#,
+            QoS::One,
+            false,
+        )
+        .await
+        .unwrap();
+    let message = recv(&mut messages).await;
+    assert_eq!(message.content, "lights?");
+    assert_eq!(message.reply_target, "zeroclaw/in/hall/reply");
+
+    // Our own reply never came back as a message.
+    assert!(
+        tokio::time::timeout(Duration::from_millis(300), messages.recv())
+            .await
+            .is_err()
+    );
+    assert!(channel.health_check().await);
+    listener.abort();
+}
+
+#[tokio::test]
+async fn qos2_delivery_through_credentialed_broker() {
+    let broker = Arc::new(LocalBroker::new().with_credentials("zc", "secret"));
+    let server = broker.bind("127.0.0.1:0").await.unwrap();
+    let mut options = MqttOptions::new("127.0.0.1", server.addr().port(), "sensor");
+    options.username = Some("zc".into());
+    options.password = Some("secret".into());
+
+    let (subscriber, mut rx) = MqttClient::connect(&options).await.unwrap();
+    let granted = subscriber
+        .subscribe(&[("home/#".into(), QoS::Two)])
+        .await
+        .unwrap();
+    assert_eq!(granted, [QoS::Two]);
+
+    let mut config = config(&server);
+    config.username = Some("zc".into());
+    config.password = Some("secret".into());
+    let result = MqttPublishTool::new(security(), config)
+        .execute(json!({"topic": "home/door/set", "payload": "close", "qos": 2}))
+        .await
+        .unwrap();
+    assert!(result.success, "{:?}", result.error);
+
+    let message = recv(&mut rx).await;
+    assert_eq!(message.qos, QoS::Two);
+    assert_eq!(message.payload_str(), "close");
+    assert!(tokio::time::timeout(Duration::from_millis(200), rx.recv())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn routes_publishes_at_every_qos_and_keeps_retained() {
+    let broker = Arc::new(LocalBroker::new());
+    let server = broker.bind("127.0.0.1:0").await.unwrap();
+    let port = server.addr().port();
+
+    let (sub, mut rx) = MqttClient::connect(&MqttOptions::new("127.0.0.1", port, "sub"))
+        .await
+        .unwrap();
+    let granted = sub
+        .subscribe(&[("home/+/temp".into(), QoS::Two)])
+        .await
+        .unwrap();
+    assert_eq!(granted, [QoS::Two]);
+
+    let (publisher, _) = MqttClient::connect(&MqttOptions::new("127.0.0.1", port, "pub"))
+        .await
+        .unwrap();
+    for qos in [QoS::Zero, QoS::One, QoS::Two] {
+        publisher
+            .publish("home/kitchen/temp", format!("{}", qos as u8), qos, false)
+            .await
+            .unwrap();
+        let message = recv(&mut rx).await;
+        assert_eq!(message.qos, qos);
+        assert_eq!(message.payload_str(), format!("{}", qos as u8));
+    }
+
+    publisher
+        .publish("home/hall/temp", "19.5", QoS::One, true)
+        .await
+        .unwrap();
+    assert_eq!(recv(&mut rx).await.payload_str(), "19.5");
+    assert_eq!(broker.retained("home/hall/temp").unwrap(), b"19.5");
+
+    // A late subscriber gets the retained value flagged as such.
+    let (late, mut late_rx) = MqttClient::connect(&MqttOptions::new("127.0.0.1", port, "late"))
+        .await
+        .unwrap();
+    late.subscribe(&[("home/#".into(), QoS::One)])
+        .await
+        .unwrap();
+    let retained = recv(&mut late_rx).await;
+    assert!(retained.retain);
+    assert_eq!(retained.topic, "home/hall/temp");
+}
+
+#[tokio::test]
+async fn refuses_bad_credentials() {
+    let broker = Arc::new(LocalBroker::new().with_credentials("dev", "secret"));
+    let server = broker.bind("127.0.0.1:0").await.unwrap();
+    let mut options = MqttOptions::new("127.0.0.1", server.addr().port(), "c");
+
+    let err = MqttClient::connect(&options).await.err().unwrap();
+    assert!(
+        err.to_string().contains("bad user name or password"),
+        "{err}"
+    );
+
+    options.username = Some("dev".into());
+    options.password = Some("secret".into());
+    let (client, _) = MqttClient::connect(&options).await.unwrap();
+    client.disconnect().await;
+}
@@ -0,0 +1,216 @@
+//! Modbus TCP peripheral against the in-process simulator.
+//!
+//! Exercises the register map tools end to end: scaled reads, writes,
+//! read-only registers, approval gating and device exceptions.
+
+use serde_json::json;
+use std::sync::Arc;
+use zeroclaw::config::{
+    ModbusConfig, ModbusDataType, ModbusRegisterConfig, ModbusRegisterKind, PeripheralBoardConfig,
+    PeripheralsConfig,
+};
+use zeroclaw::peripherals::create_peripheral_tools;
+use zeroclaw::peripherals::modbus_sim::{ModbusSimulator, ModbusTcpServer};
+use zeroclaw::security::{AutonomyLevel, SecurityPolicy};
+use zeroclaw::tools::Tool;
+
+fn register(name: &str, kind: ModbusRegisterKind, address: u16) -> ModbusRegisterConfig {
+    ModbusRegisterConfig {
+        name: name.into(),
+        kind,
+        address,
+        data_type: ModbusDataType::U16,
+        scale: 1.0,
+        offset: 0.0,
+        unit: None,
+        description: None,
+        writable: false,
+        requires_approval: false,
+        min: None,
+        max: None,
+    }
+}
+
+/// A boiler controller: temperature input (0.1 °C), setpoint holding
+/// register with limits, pump coil behind approval.
+async fn boiler() -> (Arc<ModbusSimulator>, ModbusTcpServer, PeripheralsConfig) {
+    let sim = Arc::new(ModbusSimulator::new(1));
+    sim.set_input(0, 634);
+    sim.set_holding(100, 55);
+    sim.set_coil(0, false);
+    let server = sim.bind_tcp("127.0.0.1:0").await.unwrap();
+
+    let mut temperature = register("temperature", ModbusRegisterKind::Input, 0);
+    temperature.data_type = ModbusDataType::I16;
+    temperature.scale = 0.1;
+    temperature.unit = Some("°C".into());
+    let mut setpoint = register("setpoint", ModbusRegisterKind::Holding, 100);
+    setpoint.writable = true;
+    setpoint.min = Some(30.0);
+    setpoint.max = Some(80.0);
+    let mut pump = register("pump", ModbusRegisterKind::Coil, 0);
+    pump.data_type = ModbusDataType::Bool;
+    pump.writable = true;
+    pump.requires_approval = true;
+    let missing = register("missing", ModbusRegisterKind::Holding, 900);
+
+    let config = PeripheralsConfig {
+        enabled: true,
+        boards: vec![PeripheralBoardConfig {
+            board: "boiler".into(),
+            transport: "modbus-tcp".into(),
+            path: Some(server.addr().to_string()),
+            baud: 115_200,
+            allowed_pins: Vec::new(),
+            allowed_buses: Vec::new(),
+            allowed_i2c_addresses: Vec::new(),
+            watch_pins: Vec::new(),
+            sim: None,
+            modbus: Some(ModbusConfig {
+                registers: vec![temperature, setpoint, pump, missing],
+                ..ModbusConfig::default()
+            }),
+        }],
+        datasheet_dir: None,
+        event_rules: Vec::new(),
+    };
+    (sim, server, config)
+}
+
+async fn tools(config: &PeripheralsConfig, autonomy: AutonomyLevel) -> Vec<Box<dyn Tool>> {
+    let security = Arc::new(SecurityPolicy {
+        autonomy,
+        ..SecurityPolicy::default()
+    });
+    create_peripheral_tools(config, &security).await.unwrap()
+}
+
+fn find<'a>(tools: &'a [Box<dyn Tool>], name: &str) -> &'a dyn Tool {
+    tools
+        .iter()
+        .find(|t| t.name() == name)
+        .unwrap_or_else(|| panic!("missing tool {name}"))
+        .as_ref()
+}
+
+#[tokio::test]
+async fn read_returns_scaled_engineering_values() {
+    let (_sim, _server, config) = boiler().await;
+    let tools = tools(&config, AutonomyLevel::Supervised).await;
+    let read = find(&tools, "modbus_read");
+    assert!(read.description().contains("boiler/temperature [°C]"));
+    // Modbus boards do not get the MCU memory map tools.
+    assert!(tools.iter().all(|t| t.name() != "hardware_memory_map"));
+
+    let result = read
+        .execute(json!({"register": "temperature"}))
+        .await
+        .unwrap();
+    assert!(result.success, "{:?}", result.error);
+    let value: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+    assert_eq!(value["value"], json!(63.4));
+    assert_eq!(value["unit"], json!("°C"));
+
+    let result = read
+        .execute(json!({"register": "setpoint", "device": "boiler"}))
+        .await
+        .unwrap();
+    assert!(result.output.contains("55"));
+}
+
+#[tokio::test]
+async fn write_updates_holding_register_within_limits() {
+    let (sim, _server, config) = boiler().await;
+    let tools = tools(&config, AutonomyLevel::Supervised).await;
+    let write = find(&tools, "modbus_write");
+
+    let result = write
+        .execute(json!({"register": "setpoint", "value": 62}))
+        .await
+        .unwrap();
+    assert!(result.success, "{:?}", result.error);
+    assert_eq!(sim.holding(100), Some(62));
+
+    let result = write
+        .execute(json!({"register": "setpoint", "value": 95}))
+        .await
+        .unwrap();
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("at most 80"));
+    assert_eq!(sim.holding(100), Some(62));
+}
+
+#[tokio::test]
+async fn read_only_and_unknown_registers_are_rejected() {
+    let (_sim, _server, config) = boiler().await;
+    let tools = tools(&config, AutonomyLevel::Full).await;
+    let write = find(&tools, "modbus_write");
+
+    let result = write
+        .execute(json!({"register": "temperature", "value": 20}))
+        .await
+        .unwrap();
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("read-only"));
+
+    let result = write
+        .execute(json!({"register": "valve", "value": 1}))
+        .await
+        .unwrap();
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("Unknown Modbus register"));
+}
+
+#[tokio::test]
+async fn approval_gated_coil_needs_approved_flag() {
+    let (sim, _server, config) = boiler().await;
+    let tools = tools(&config, AutonomyLevel::Supervised).await;
+    let write = find(&tools, "modbus_write");
+
+    let result = write
+        .execute(json!({"register": "pump", "value": true}))
+        .await
+        .unwrap();
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("requires explicit approval"));
+    assert_eq!(sim.coil(0), Some(false));
+
+    let result = write
+        .execute(json!({"register": "pump", "value": true, "approved": true}))
+        .await
+        .unwrap();
+    assert!(result.success, "{:?}", result.error);
+    assert_eq!(sim.coil(0), Some(true));
+}
+
+#[tokio::test]
+async fn read_only_autonomy_blocks_writes() {
+    let (sim, _server, config) = boiler().await;
+    let tools = tools(&config, AutonomyLevel::ReadOnly).await;
+
+    let result = find(&tools, "modbus_write")
+        .execute(json!({"register": "setpoint", "value": 40}))
+        .await
+        .unwrap();
+    assert!(!result.success);
+    assert_eq!(sim.holding(100), Some(55));
+
+    // Reads stay available.
+    let result = find(&tools, "modbus_read")
+        .execute(json!({"register": "temperature"}))
+        .await
+        .unwrap();
+    assert!(result.success);
+}
+
+#[tokio::test]
+async fn device_exceptions_surface_as_errors() {
+    let (_sim, _server, config) = boiler().await;
+    let tools = tools(&config, AutonomyLevel::Supervised).await;
+
+    let err = find(&tools, "modbus_read")
+        .execute(json!({"register": "missing"}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("illegal data address"), "{err}");
+}
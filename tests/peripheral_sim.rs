@@ -0,0 +1,376 @@
+//! Integration tests for the simulated peripheral board.
+//!
+//! Drive the serial protocol tools, `hardware_*` tools, RAG pin aliases and a
+//! full agent turn against `board = "sim"` — no hardware required. Also
+//! covers unsolicited board events and the rules that route them.
+#![cfg(feature = "hardware")]
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use zeroclaw::agent::agent::Agent;
+use zeroclaw::agent::dispatcher::NativeToolDispatcher;
+use zeroclaw::config::{
+    MemoryConfig, PeripheralBoardConfig, PeripheralEventAction, PeripheralEventRule,
+    PeripheralsConfig, SensorGenerator, SimBoardConfig, SimI2cDeviceConfig, SimPinConfig,
+    SimSensorConfig,
+};
+use zeroclaw::memory;
+use zeroclaw::observability::NoopObserver;
+use zeroclaw::peripherals::events::EventRouter;
+use zeroclaw::peripherals::sim::{Fault, SimBoard, SimPeripheral};
+use zeroclaw::peripherals::{
+    create_peripheral_tools, subscribe_events, Peripheral, PeripheralEvent,
+};
+use zeroclaw::providers::{ChatRequest, ChatResponse, Provider, ToolCall};
+use zeroclaw::rag::HardwareRag;
+use zeroclaw::security::SecurityPolicy;
+use zeroclaw::tools::Tool;
+
+fn sim_config(sim: SimBoardConfig) -> PeripheralBoardConfig {
+    PeripheralBoardConfig {
+        board: "sim".into(),
+        transport: "sim".into(),
+        sim: Some(sim),
+        ..PeripheralBoardConfig::default()
+    }
+}
+
+fn security() -> Arc<SecurityPolicy> {
+    Arc::new(SecurityPolicy::default())
+}
+
+fn find<'a>(tools: &'a [Box<dyn Tool>], name: &str) -> &'a dyn Tool {
+    tools
+        .iter()
+        .find(|t| t.name() == name)
+        .unwrap_or_else(|| panic!("missing tool {name}"))
+        .as_ref()
+}
+
+#[tokio::test]
+async fn configured_sim_board_exposes_protocol_and_hardware_tools() {
+    let config = PeripheralsConfig {
+        enabled: true,
+        boards: vec![sim_config(SimBoardConfig {
+            pins: vec![SimPinConfig { pin: 2, value: 1 }],
+            sensors: vec![SimSensorConfig {
+                pin: 14,
+                generator: SensorGenerator::Constant { value: 512.0 },
+            }],
+            i2c_devices: vec![SimI2cDeviceConfig {
+                address: 0x3c,
+                registers: vec![0x42],
+            }],
+            ..SimBoardConfig::default()
+        })],
+        datasheet_dir: None,
+        event_rules: Vec::new(),
+    };
+    let tools = create_peripheral_tools(&config, &security()).await.unwrap();
+
+    for name in [
+        "gpio_read",
+        "gpio_write",
+        "adc_read",
+        "pwm_set",
+        "i2c_scan",
+        "i2c_read",
+        "i2c_write",
+        "spi_transfer",
+        "uart_transfer",
+        "hardware_capabilities",
+        "hardware_board_info",
+        "hardware_memory_map",
+    ] {
+        find(&tools, name);
+    }
+
+    let read = find(&tools, "gpio_read");
+    assert_eq!(read.execute(json!({"pin": 2})).await.unwrap().output, "1");
+    let adc = find(&tools, "adc_read");
+    assert_eq!(adc.execute(json!({"pin": 14})).await.unwrap().output, "512");
+    let scan = find(&tools, "i2c_scan");
+    assert_eq!(scan.execute(json!({})).await.unwrap().output, "[60]");
+    let spi = find(&tools, "spi_transfer");
+    assert_eq!(
+        spi.execute(json!({"data": [1, 2, 3]}))
+            .await
+            .unwrap()
+            .output,
+        "[1,2,3]"
+    );
+
+    let caps = find(&tools, "hardware_capabilities")
+        .execute(json!({}))
+        .await
+        .unwrap();
+    assert!(caps.success, "{caps:?}");
+    assert!(caps.output.contains("protocol 2"), "{}", caps.output);
+
+    // Host-side validation still applies: pin 40 is never sent to the board.
+    let rejected = read.execute(json!({"pin": 40})).await.unwrap();
+    assert!(!rejected.success);
+}
+
+#[tokio::test]
+async fn injected_faults_surface_as_tool_errors() {
+    let board = Arc::new(SimBoard::new());
+    let config = sim_config(SimBoardConfig {
+        timeout_ms: 100,
+        ..SimBoardConfig::default()
+    });
+    let peripheral = SimPeripheral::with_board(&config, board.clone()).await;
+    let tools = peripheral.tools();
+    let read = find(&tools, "gpio_read");
+
+    board.inject_fault_for("gpio_read", Fault::Timeout);
+    let err = read.execute(json!({"pin": 3})).await.unwrap_err();
+    assert!(err.to_string().contains("timed out"), "{err}");
+
+    board.inject_fault(Fault::IdMismatch);
+    let err = read.execute(json!({"pin": 3})).await.unwrap_err();
+    assert!(err.to_string().contains("id mismatch"), "{err}");
+
+    board.inject_fault(Fault::Error("brownout".into()));
+    let result = read.execute(json!({"pin": 3})).await.unwrap();
+    assert!(!result.success);
+    assert_eq!(result.error.as_deref(), Some("brownout"));
+
+    assert!(read.execute(json!({"pin": 3})).await.unwrap().success);
+    assert!(peripheral.health_check().await);
+
+    let faults: Vec<Option<Fault>> = board
+        .commands()
+        .into_iter()
+        .filter(|c| c.cmd == "gpio_read")
+        .map(|c| c.fault)
+        .collect();
+    assert_eq!(
+        faults,
+        vec![
+            Some(Fault::Timeout),
+            Some(Fault::IdMismatch),
+            Some(Fault::Error("brownout".into())),
+            None,
+        ]
+    );
+}
+
+/// Provider that replays scripted responses in order.
+struct ScriptedProvider {
+    responses: Mutex<Vec<ChatResponse>>,
+}
+
+#[async_trait]
+impl Provider for ScriptedProvider {
+    async fn chat_with_system(
+        &self,
+        _system_prompt: Option<&str>,
+        _message: &str,
+        _model: &str,
+        _temperature: f64,
+    ) -> Result<String> {
+        Ok("fallback".into())
+    }
+
+    async fn chat(
+        &self,
+        _request: ChatRequest<'_>,
+        _model: &str,
+        _temperature: f64,
+    ) -> Result<ChatResponse> {
+        let mut guard = self.responses.lock().unwrap();
+        if guard.is_empty() {
+            return Ok(ChatResponse {
+                text: Some("done".into()),
+                tool_calls: vec![],
+            });
+        }
+        Ok(guard.remove(0))
+    }
+}
+
+#[tokio::test]
+async fn agent_uses_rag_pin_alias_to_drive_sim_board() {
+    let workspace = tempfile::tempdir().unwrap();
+    let datasheets = workspace.path().join("datasheets");
+    std::fs::create_dir_all(&datasheets).unwrap();
+    std::fs::write(
+        datasheets.join("sim.md"),
+        "# Sim board\n\n## Pin Aliases\n\n| alias | pin |\n|---|---|\n| buzzer | 5 |\n",
+    )
+    .unwrap();
+
+    let rag = HardwareRag::load(workspace.path(), "datasheets").unwrap();
+    let context = rag.pin_alias_context("turn on the buzzer", &["sim".to_string()]);
+    assert!(context.contains("sim: buzzer = pin 5"), "{context}");
+
+    let board = Arc::new(SimBoard::new());
+    let peripheral =
+        SimPeripheral::with_board(&sim_config(SimBoardConfig::default()), board.clone()).await;
+
+    let provider = ScriptedProvider {
+        responses: Mutex::new(vec![
+            ChatResponse {
+                text: Some(String::new()),
+                tool_calls: vec![ToolCall {
+                    id: "tc1".into(),
+                    name: "gpio_write".into(),
+                    arguments: r#"{"pin": 5, "value": 1}"#.into(),
+                }],
+            },
+            ChatResponse {
+                text: Some("Buzzer is on".into()),
+                tool_calls: vec![],
+            },
+        ]),
+    };
+    let memory_config = MemoryConfig {
+        backend: "none".into(),
+        ..MemoryConfig::default()
+    };
+    let mut agent = Agent::builder()
+        .provider(Box::new(provider))
+        .tools(peripheral.tools())
+        .memory(Arc::from(
+            memory::create_memory(&memory_config, workspace.path(), None).unwrap(),
+        ))
+        .observer(Arc::new(NoopObserver {}))
+        .tool_dispatcher(Box::new(NativeToolDispatcher))
+        .workspace_dir(workspace.path().to_path_buf())
+        .build()
+        .unwrap();
+
+    let response = agent.turn("turn on the buzzer").await.unwrap();
+    assert_eq!(response, "Buzzer is on");
+    assert_eq!(board.pin(5), 1);
+
+    let last = board.commands().pop().unwrap();
+    assert_eq!(last.cmd, "gpio_write");
+    assert_eq!(last.args, json!({"pin": 5, "value": 1}));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn serial_peripheral_connects_to_sim_pty() {
+    use zeroclaw::peripherals::serial::SerialPeripheral;
+
+    let board = Arc::new(SimBoard::new());
+    let pty = board.open_pty().unwrap();
+    let config = PeripheralBoardConfig {
+        board: "sim".into(),
+        transport: "serial".into(),
+        path: Some(pty.path().to_string()),
+        ..PeripheralBoardConfig::default()
+    };
+
+    let peripheral = SerialPeripheral::connect(&config).await.unwrap();
+    let tools = peripheral.tools();
+    let write = find(&tools, "pwm_set");
+    let result = write
+        .execute(json!({"pin": 9, "duty": 25.0}))
+        .await
+        .unwrap();
+    assert!(result.success, "{result:?}");
+    assert_eq!(board.pwm_duty(9), Some(25.0));
+    assert_eq!(board.commands()[0].cmd, "capabilities");
+
+    // Other boards may not use a PTY path.
+    let other = PeripheralBoardConfig {
+        board: "nucleo-f401re".into(),
+        ..config
+    };
+    assert!(SerialPeripheral::connect(&other).await.is_err());
+}
+
+async fn next_event(
+    events: &mut tokio::sync::broadcast::Receiver<PeripheralEvent>,
+) -> PeripheralEvent {
+    tokio::time::timeout(std::time::Duration::from_secs(2), events.recv())
+        .await
+        .expect("event within 2s")
+        .unwrap()
+}
+
+#[tokio::test]
+async fn sim_board_events_reach_subscribers_and_rules() {
+    let board = Arc::new(SimBoard::new());
+    let peripheral =
+        SimPeripheral::with_board(&sim_config(SimBoardConfig::default()), board.clone()).await;
+    let mut events = peripheral.events().expect("sim board raises events");
+
+    board.set_pin(2, 1);
+    // Same level again: no edge.
+    board.set_pin(2, 1);
+    board.emit_event("threshold", Some(14), json!(900));
+
+    // Requests still get their replies with events on the same link.
+    let tools = peripheral.tools();
+    let read = find(&tools, "gpio_read");
+    assert_eq!(read.execute(json!({"pin": 2})).await.unwrap().output, "1");
+
+    let edge = next_event(&mut events).await;
+    assert_eq!(
+        edge,
+        PeripheralEvent {
+            board: "sim".into(),
+            event: "edge".into(),
+            pin: Some(2),
+            value: Some(json!(1)),
+        }
+    );
+    let threshold = next_event(&mut events).await;
+    assert_eq!(threshold.event, "threshold");
+    assert_eq!(threshold.value, Some(json!(900)));
+
+    let rule = PeripheralEventRule {
+        name: "button".into(),
+        board: Some("sim".into()),
+        event: "edge".into(),
+        pin: Some(2),
+        action: PeripheralEventAction::Agent,
+        prompt: "Pin {pin} went {value}".into(),
+        delay: None,
+        channel: None,
+        to: None,
+        debounce_ms: 1000,
+        max_per_hour: 30,
+    };
+    let mut router = EventRouter::new(&[rule]);
+    let now = std::time::Instant::now();
+    assert_eq!(router.route(&edge, now).len(), 1);
+    assert!(router.route(&threshold, now).is_empty());
+    // Bounce within the debounce window.
+    assert!(router.route(&edge, now).is_empty());
+}
+
+#[tokio::test]
+async fn configured_sim_board_is_shared_with_event_watchers() {
+    let config = PeripheralsConfig {
+        enabled: true,
+        boards: vec![PeripheralBoardConfig {
+            path: Some("shared-events".into()),
+            ..sim_config(SimBoardConfig::default())
+        }],
+        datasheet_dir: None,
+        event_rules: Vec::new(),
+    };
+    let receivers = subscribe_events(&config).await;
+    assert_eq!(receivers.len(), 1);
+
+    let tools = create_peripheral_tools(&config, &security()).await.unwrap();
+    let write = find(&tools, "gpio_write");
+    assert!(
+        write
+            .execute(json!({"pin": 4, "value": 1}))
+            .await
+            .unwrap()
+            .success
+    );
+    // A second agent run reuses the connected board and sees its state.
+    let tools = create_peripheral_tools(&config, &security()).await.unwrap();
+    let read = find(&tools, "gpio_read");
+    assert_eq!(read.execute(json!({"pin": 4})).await.unwrap().output, "1");
+}
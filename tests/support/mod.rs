@@ -0,0 +1,3 @@
+//! Helpers shared by integration tests.
+
+pub mod mqtt_broker;
@@ -0,0 +1,292 @@
+//! Minimal in-process MQTT 3.1.1 broker for the integration tests.
+//!
+//! [`LocalBroker`] routes publishes to matching subscriptions, keeps retained
+//! messages, answers QoS 1/2 handshakes and pings, and optionally requires a
+//! username and password. It keeps no persistent sessions: every connection
+//! is treated as clean, and a second connection with the same client id
+//! replaces the first.
+
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use zeroclaw::mqtt::packet::{Packet, Publish, QoS};
+use zeroclaw::mqtt::topic_matches;
+
+struct Session {
+    connection: u64,
+    outgoing: mpsc::UnboundedSender<Packet>,
+    subscriptions: Vec<(String, QoS)>,
+    next_pkid: u16,
+}
+
+impl Session {
+    fn deliver(&mut self, publish: &Publish, qos: QoS, retain: bool) {
+        let mut copy = publish.clone();
+        copy.qos = qos;
+        copy.retain = retain;
+        copy.dup = false;
+        copy.pkid = if qos == QoS::Zero {
+            0
+        } else {
+            self.next_pkid = self.next_pkid.checked_add(1).unwrap_or(1);
+            self.next_pkid
+        };
+        let _ = self.outgoing.send(Packet::Publish(copy));
+    }
+}
+
+#[derive(Default)]
+struct State {
+    sessions: HashMap<String, Session>,
+    retained: BTreeMap<String, Publish>,
+    credentials: Option<(String, String)>,
+}
+
+/// In-memory broker; serve it with [`bind`](Self::bind).
+#[derive(Default)]
+pub struct LocalBroker {
+    state: Mutex<State>,
+    connections: AtomicU64,
+}
+
+/// A broker served on a local TCP port until dropped.
+pub struct BrokerServer {
+    addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl BrokerServer {
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for BrokerServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl LocalBroker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Refuse connections without this username and password.
+    pub fn with_credentials(self, username: &str, password: &str) -> Self {
+        self.state.lock().credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Payload of the retained message on `topic`, if any.
+    pub fn retained(&self, topic: &str) -> Option<Vec<u8>> {
+        self.state
+            .lock()
+            .retained
+            .get(topic)
+            .map(|p| p.payload.clone())
+    }
+
+    /// Listen on `addr` (e.g. "127.0.0.1:0").
+    pub async fn bind(self: &Arc<Self>, addr: &str) -> std::io::Result<BrokerServer> {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let broker = self.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let broker = broker.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = broker.serve(stream).await {
+                        tracing::debug!("MQTT broker connection ended: {e}");
+                    }
+                });
+            }
+        });
+        Ok(BrokerServer { addr, task })
+    }
+
+    /// Serve one client connection until it disconnects.
+    async fn serve(
+        &self,
+        stream: impl AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    ) -> anyhow::Result<()> {
+        let (mut reader, mut writer) = tokio::io::split(stream);
+        let Some(Packet::Connect(connect)) = Packet::read(&mut reader).await? else {
+            anyhow::bail!("expected CONNECT");
+        };
+
+        let authorized = match &self.state.lock().credentials {
+            Some((user, pass)) => {
+                connect.username.as_ref() == Some(user) && connect.password.as_ref() == Some(pass)
+            }
+            None => true,
+        };
+        if !authorized {
+            let refusal = Packet::ConnAck {
+                session_present: false,
+                code: 4,
+            };
+            writer.write_all(&refusal.encode()?).await?;
+            return Ok(());
+        }
+
+        let (outgoing, mut outbox) = mpsc::unbounded_channel::<Packet>();
+        let writer_task = tokio::spawn(async move {
+            while let Some(packet) = outbox.recv().await {
+                let Ok(bytes) = packet.encode() else { continue };
+                if writer.write_all(&bytes).await.is_err() || writer.flush().await.is_err() {
+                    return;
+                }
+            }
+            let _ = writer.shutdown().await;
+        });
+
+        let connection = self.connections.fetch_add(1, Ordering::Relaxed);
+        let client_id = connect.client_id;
+        let _ = outgoing.send(Packet::ConnAck {
+            session_present: false,
+            code: 0,
+        });
+        // An older connection with this id stops at its next packet.
+        self.state.lock().sessions.insert(
+            client_id.clone(),
+            Session {
+                connection,
+                outgoing: outgoing.clone(),
+                subscriptions: Vec::new(),
+                next_pkid: 0,
+            },
+        );
+
+        let result = self
+            .handle_packets(&mut reader, &client_id, connection, &outgoing)
+            .await;
+
+        {
+            let mut state = self.state.lock();
+            if state
+                .sessions
+                .get(&client_id)
+                .is_some_and(|s| s.connection == connection)
+            {
+                state.sessions.remove(&client_id);
+            }
+        }
+        drop(outgoing);
+        let _ = writer_task.await;
+        result
+    }
+
+    async fn handle_packets(
+        &self,
+        reader: &mut (impl AsyncRead + Unpin),
+        client_id: &str,
+        connection: u64,
+        outgoing: &mpsc::UnboundedSender<Packet>,
+    ) -> anyhow::Result<()> {
+        let mut received_qos2: HashSet<u16> = HashSet::new();
+        loop {
+            let Some(packet) = Packet::read(reader).await? else {
+                return Ok(());
+            };
+            // A newer connection with the same client id took over.
+            if outgoing.is_closed() || !self.is_current(client_id, connection) {
+                return Ok(());
+            }
+            match packet {
+                Packet::Publish(publish) => {
+                    let first_delivery = match publish.qos {
+                        QoS::Zero => true,
+                        QoS::One => {
+                            let _ = outgoing.send(Packet::PubAck(publish.pkid));
+                            true
+                        }
+                        QoS::Two => {
+                            let _ = outgoing.send(Packet::PubRec(publish.pkid));
+                            received_qos2.insert(publish.pkid)
+                        }
+                    };
+                    if first_delivery {
+                        self.route(&publish);
+                    }
+                }
+                Packet::PubRel(pkid) => {
+                    received_qos2.remove(&pkid);
+                    let _ = outgoing.send(Packet::PubComp(pkid));
+                }
+                Packet::PubRec(pkid) => {
+                    let _ = outgoing.send(Packet::PubRel(pkid));
+                }
+                Packet::Subscribe { pkid, filters } => {
+                    let codes = filters.iter().map(|(_, qos)| *qos as u8).collect();
+                    let _ = outgoing.send(Packet::SubAck { pkid, codes });
+                    let mut state = self.state.lock();
+                    let State {
+                        sessions, retained, ..
+                    } = &mut *state;
+                    let Some(session) = sessions.get_mut(client_id) else {
+                        continue;
+                    };
+                    for (filter, qos) in filters {
+                        session.subscriptions.retain(|(f, _)| *f != filter);
+                        for publish in retained.values() {
+                            if topic_matches(&filter, &publish.topic) {
+                                session.deliver(publish, publish.qos.min(qos), true);
+                            }
+                        }
+                        session.subscriptions.push((filter, qos));
+                    }
+                }
+                Packet::Unsubscribe { pkid, filters } => {
+                    if let Some(session) = self.state.lock().sessions.get_mut(client_id) {
+                        session.subscriptions.retain(|(f, _)| !filters.contains(f));
+                    }
+                    let _ = outgoing.send(Packet::UnsubAck(pkid));
+                }
+                Packet::PingReq => {
+                    let _ = outgoing.send(Packet::PingResp);
+                }
+                Packet::Disconnect => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+
+    fn is_current(&self, client_id: &str, connection: u64) -> bool {
+        self.state
+            .lock()
+            .sessions
+            .get(client_id)
+            .is_some_and(|s| s.connection == connection)
+    }
+
+    fn route(&self, publish: &Publish) {
+        let mut state = self.state.lock();
+        if publish.retain {
+            if publish.payload.is_empty() {
+                state.retained.remove(&publish.topic);
+            } else {
+                state
+                    .retained
+                    .insert(publish.topic.clone(), publish.clone());
+            }
+        }
+        for session in state.sessions.values_mut() {
+            let granted = session
+                .subscriptions
+                .iter()
+                .filter(|(filter, _)| topic_matches(filter, &publish.topic))
+                .map(|(_, qos)| *qos)
+                .max();
+            if let Some(granted) = granted {
+                session.deliver(publish, publish.qos.min(granted), false);
+            }
+        }
+    }
+}